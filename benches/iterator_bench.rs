@@ -93,6 +93,25 @@ fn large_file_bench(c: &mut Criterion) {
         })
     });
 
+    group.bench_function("100_frames_streaming_next", |b| {
+        b.iter(|| {
+            for frame in ConFrameIterator::new(&large) {
+                let _ = black_box(frame);
+            }
+        })
+    });
+
+    group.bench_function("100_frames_next_into", |b| {
+        b.iter(|| {
+            let mut iter = ConFrameIterator::new(&large);
+            let mut frame = iter.next().unwrap().unwrap();
+            while let Some(result) = iter.next_into(&mut frame) {
+                let _ = black_box(result);
+                black_box(&frame);
+            }
+        })
+    });
+
     group.bench_function("100_frames_forward_skip", |b| {
         b.iter(|| {
             let mut iter = ConFrameIterator::new(&large);
@@ -179,6 +198,22 @@ fn fast_float_microbench(c: &mut Criterion) {
         })
     });
 
+    // Header box/angle lines: fixed-arity array vs. allocating Vec.
+    let cell_line = "15.345600 21.702000 100.000000";
+    group.bench_function("parse_fixed_f64_3", |b| {
+        b.iter(|| {
+            let vals = readcon_core::parser::parse_fixed_f64::<3>(black_box(cell_line)).unwrap();
+            let _ = black_box(vals);
+        })
+    });
+
+    group.bench_function("parse_line_of_n_f64_3_vec", |b| {
+        b.iter(|| {
+            let vals = readcon_core::parser::parse_line_of_n_f64(black_box(cell_line), 3).unwrap();
+            let _ = black_box(vals);
+        })
+    });
+
     group.finish();
 }
 
@@ -0,0 +1,181 @@
+//! Criterion benches over generated trajectories of varying size.
+//!
+//! Inputs are built in memory with [`ConFrameBuilder`] and serialized with
+//! [`ConFrameWriter`], so no fixture files are needed and every size point
+//! is reproducible (positions come from a fixed-seed xorshift).
+//!
+//! ```text
+//! cargo bench --bench synthetic_bench
+//! cargo bench --bench synthetic_bench -- 'SyntheticParse/10000'
+//! ```
+//!
+//! Set `READCON_CPP_BENCH` to an executable that reads the `.con` path
+//! given as its only argument (e.g. a small driver over the readCon C++
+//! library) to add a `SyntheticCompare` group. That group times whole
+//! processes, startup included, against the same work done in-process.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::types::{ConFrame, ConFrameBuilder};
+use readcon_core::writer::ConFrameWriter;
+use std::hint::black_box;
+
+/// `(atoms per frame, species)` points; frames are scaled so each input is
+/// roughly the same number of atom lines.
+const SIZES: [(usize, usize); 4] = [(10, 1), (218, 2), (2_000, 3), (10_000, 5)];
+const ATOM_LINES: usize = 200_000;
+
+const SPECIES: [(&str, f64); 5] = [
+    ("Cu", 63.546),
+    ("H", 1.008),
+    ("O", 15.999),
+    ("C", 12.011),
+    ("Pt", 195.084),
+];
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn unit(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn synthetic_frames(natoms: usize, nspecies: usize, nframes: usize) -> Vec<ConFrame> {
+    let cell = [30.0, 30.0, 30.0];
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    (0..nframes)
+        .map(|index| {
+            let mut builder = ConFrameBuilder::new(cell, [90.0, 90.0, 90.0]);
+            builder
+                .prebox_header("synthetic")
+                .set_frame_index(index as u64);
+            for (s, &(symbol, mass)) in SPECIES[..nspecies].iter().enumerate() {
+                let count = natoms / nspecies + usize::from(s < natoms % nspecies);
+                for _ in 0..count {
+                    let id = builder.atom_count() as u64;
+                    builder.add_atom(
+                        symbol,
+                        rng.unit() * cell[0],
+                        rng.unit() * cell[1],
+                        rng.unit() * cell[2],
+                        [id.is_multiple_of(7); 3],
+                        id,
+                        mass,
+                    );
+                }
+            }
+            builder.build()
+        })
+        .collect()
+}
+
+fn serialize(frames: &[ConFrame]) -> String {
+    let mut buffer = Vec::new();
+    ConFrameWriter::new(&mut buffer)
+        .extend(frames.iter())
+        .unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+/// One generated input per size point, labelled `<atoms>x<frames>_<species>sp`.
+fn inputs() -> Vec<(String, Vec<ConFrame>, String)> {
+    SIZES
+        .iter()
+        .map(|&(natoms, nspecies)| {
+            let nframes = (ATOM_LINES / natoms).max(1);
+            let frames = synthetic_frames(natoms, nspecies, nframes);
+            let text = serialize(&frames);
+            (format!("{natoms}x{nframes}_{nspecies}sp"), frames, text)
+        })
+        .collect()
+}
+
+fn synthetic_bench(c: &mut Criterion) {
+    let inputs = inputs();
+
+    let mut group = c.benchmark_group("SyntheticParse");
+    group.sample_size(20);
+    for (label, _, text) in &inputs {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(label), text, |b, text| {
+            b.iter(|| {
+                for frame in ConFrameIterator::new(black_box(text)) {
+                    let _ = black_box(frame.unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("SyntheticSkip");
+    for (label, _, text) in &inputs {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(label), text, |b, text| {
+            b.iter(|| {
+                let mut iter = ConFrameIterator::new(black_box(text));
+                while let Some(result) = iter.forward_fast() {
+                    result.unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("SyntheticWrite");
+    group.sample_size(20);
+    for (label, frames, text) in &inputs {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(label), frames, |b, frames| {
+            b.iter(|| {
+                let mut buffer = Vec::with_capacity(text.len());
+                ConFrameWriter::new(&mut buffer)
+                    .extend(frames.iter())
+                    .unwrap();
+                black_box(buffer)
+            })
+        });
+    }
+    group.finish();
+
+    if let Some(cpp) = std::env::var_os("READCON_CPP_BENCH") {
+        compare_bench(c, &cpp, &inputs);
+    }
+}
+
+/// readcon-core (in process, reading from disk) next to an external reader
+/// process on the same file.
+fn compare_bench(
+    c: &mut Criterion,
+    cpp: &std::ffi::OsStr,
+    inputs: &[(String, Vec<ConFrame>, String)],
+) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut group = c.benchmark_group("SyntheticCompare");
+    group.sample_size(10);
+    for (label, _, text) in inputs {
+        let path = dir.path().join(format!("{label}.con"));
+        std::fs::write(&path, text).unwrap();
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("readcon_core", label), &path, |b, path| {
+            b.iter(|| black_box(readcon_core::iterators::read_all_frames(path).unwrap()))
+        });
+        group.bench_with_input(BenchmarkId::new("external", label), &path, |b, path| {
+            b.iter(|| {
+                let status = std::process::Command::new(cpp).arg(path).status().unwrap();
+                assert!(
+                    status.success(),
+                    "READCON_CPP_BENCH failed on {}",
+                    path.display()
+                );
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, synthetic_bench);
+criterion_main!(benches);
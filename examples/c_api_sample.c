@@ -69,8 +69,8 @@ int main(int argc, char *argv[]) {
 
     CConFrameIterator *iterator = read_con_file_iterator(input_filename);
     if (!iterator) {
-        fprintf(stderr, "Failed to open file or create iterator for '%s'.\n",
-                input_filename);
+        fprintf(stderr, "Failed to open '%s': %s\n", input_filename,
+                rkr_last_error_message());
         return 1;
     }
     printf("Successfully created iterator. Reading all frames from '%s'...\n",
@@ -120,6 +120,10 @@ int main(int argc, char *argv[]) {
             last_handle = current_handle;
         }
     }
+    if (rkr_last_error_status() != RKR_STATUS_SUCCESS) {
+        // NULL from con_frame_iterator_next was a malformed frame, not EOF.
+        fprintf(stderr, "Stopped early: %s\n", rkr_last_error_message());
+    }
     printf("Finished reading. Total frames found: %zu\n", frame_count);
     free_con_frame_iterator(iterator);
 
@@ -139,15 +143,16 @@ int main(int argc, char *argv[]) {
         // Use the new object-oriented writer API
         RKRConFrameWriter *writer = create_writer_from_path_c(output_filename);
         if (!writer) {
-            fprintf(stderr, "Failed to create writer for file '%s'.\n",
-                    output_filename);
+            fprintf(stderr, "Failed to create writer: %s\n",
+                    rkr_last_error_message());
         } else {
             RKRStatus result = rkr_writer_extend(
                 writer, (const RKRConFrame **)handles_array, frame_count);
             if (result == RKR_STATUS_SUCCESS) {
                 printf("Successfully wrote all frames.\n");
             } else {
-                fprintf(stderr, "An error occurred while writing the file.\n");
+                fprintf(stderr, "An error occurred while writing the file: %s\n",
+                        rkr_last_error_message());
             }
             // Free the writer to close the file and release resources.
             free_rkr_writer(writer);
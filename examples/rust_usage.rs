@@ -49,7 +49,7 @@ fn main() {
             print!(
                 "  Atom {}: {} ({:.4}, {:.4}, {:.4}) fixed={} id={}",
                 j,
-                atom.symbol,
+                frame.symbol_of(atom),
                 atom.x,
                 atom.y,
                 atom.z,
@@ -0,0 +1,104 @@
+//! Shared driver for the text-parser targets: every read path over the
+//! same input, with checks that hold for any text.
+
+use readcon_core::error::ParseError;
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::parser::{ParseMode, ParserOptions};
+use readcon_core::types::ConFrame;
+use readcon_core::writer::ConFrameWriter;
+
+/// Runs the full parser, the skip paths and the outline reader over `text`
+/// in strict, tolerant-with-recovery and permissive modes.
+///
+/// Beyond "no panic" this checks that every path makes progress (no more
+/// items than lines, plus one), that parsed frames are self-consistent, and
+/// that a fully parsed input survives write → parse → write unchanged.
+pub fn exercise(text: &str) {
+    let limit = text.lines().count() + 1;
+
+    let strict = drain(ConFrameIterator::new(text), limit);
+    let tolerant = ParserOptions::tolerant();
+    drain(
+        ConFrameIterator::new(text)
+            .options(tolerant.clone())
+            .recover(true),
+        limit,
+    );
+    drain(
+        ConFrameIterator::new(text).with_mode(ParseMode::Permissive),
+        limit,
+    );
+
+    let mut skip = ConFrameIterator::new(text);
+    let mut skipped = 0;
+    while skip.forward_fast().is_some() {
+        skipped += 1;
+        assert!(skipped <= limit, "forward_fast made no progress");
+    }
+
+    let mut outline = ConFrameIterator::new(text).options(tolerant);
+    let mut outlines = 0;
+    while outline.next_outline().is_some() {
+        outlines += 1;
+        assert!(outlines <= limit, "next_outline made no progress");
+    }
+
+    // Lenient parsing admits layouts the writer refuses (a component with
+    // no atoms); those must be refused cleanly, and the rest round-trip.
+    // Rounding to the writer's fixed precision may push a strictly
+    // validated value across its bound (a 1e-9 degree angle becomes 0), so
+    // a validation error on the way back is allowed; nothing else is.
+    if let Some(frames) = strict
+        && let Some(written) = write(&frames)
+    {
+        let reparsed: Vec<ConFrame> = match ConFrameIterator::new(&written).collect() {
+            Ok(frames) => frames,
+            Err(ParseError::ValidationError(_)) => return,
+            Err(e) => panic!("writer output did not parse: {e}\n{written}"),
+        };
+        assert_eq!(reparsed.len(), frames.len());
+        assert_eq!(
+            write(&reparsed).as_deref(),
+            Some(written.as_str()),
+            "write ∘ parse is not stable"
+        );
+    }
+}
+
+/// Drains `iter`, returning the frames when every item parsed.
+fn drain(iter: ConFrameIterator<'_>, limit: usize) -> Option<Vec<ConFrame>> {
+    let mut frames = Vec::new();
+    let mut all_ok = true;
+    for (n, item) in iter.enumerate() {
+        assert!(n < limit, "iterator made no progress");
+        match item {
+            Ok(frame) => {
+                check_frame(&frame);
+                frames.push(frame);
+            }
+            Err(_) => all_ok = false,
+        }
+    }
+    all_ok.then_some(frames)
+}
+
+fn check_frame(frame: &ConFrame) {
+    let header = &frame.header;
+    let natoms: usize = header.natms_per_type.iter().sum();
+    assert_eq!(header.natms_per_type.len(), header.natm_types);
+    assert_eq!(header.masses_per_type.len(), header.natm_types);
+    assert_eq!(frame.atom_data.len(), natoms);
+    assert_eq!(frame.positions.nrows(), natoms);
+    assert_eq!(frame.atom_ids.len(), natoms);
+}
+
+/// The writer's output, or `None` when it rejected a frame as invalid input.
+fn write(frames: &[ConFrame]) -> Option<String> {
+    let mut buffer = Vec::new();
+    let result = ConFrameWriter::new(&mut buffer).extend(frames.iter());
+    match result {
+        Ok(()) => Some(String::from_utf8(buffer).expect("writer emits UTF-8")),
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidInput => None,
+        Err(e) => panic!("writing to a Vec failed: {e}"),
+    }
+}
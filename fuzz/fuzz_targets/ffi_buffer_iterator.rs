@@ -0,0 +1,41 @@
+//! Arbitrary bytes through `read_con_buffer_iterator`, then every
+//! allocating accessor on each frame that parses. Run under ASan (the
+//! cargo-fuzz default) to catch out-of-bounds access and double frees:
+//!
+//! ```text
+//! cargo +nightly fuzz run ffi_buffer_iterator
+//! ```
+#![no_main]
+
+use std::ptr;
+
+use libfuzzer_sys::fuzz_target;
+use readcon_core::ffi::*;
+
+fuzz_target!(|data: &[u8]| unsafe {
+    let it = read_con_buffer_iterator(data.as_ptr(), data.len());
+    if it.is_null() {
+        return;
+    }
+    let _ = con_frame_iterator_count_remaining(it);
+    loop {
+        let mut frame = ptr::null_mut();
+        if con_frame_iterator_try_next(it, &mut frame) != RKRStatus::RKR_STATUS_SUCCESS
+            || frame.is_null()
+        {
+            break;
+        }
+        let n = rkr_frame_atom_count(frame);
+        let mut positions = vec![0.0_f64; 3 * n];
+        let _ = rkr_frame_copy_positions(frame, positions.as_mut_ptr(), positions.len());
+        free_c_frame(rkr_frame_to_c_frame(frame));
+        rkr_free_string(rkr_frame_metadata_json(frame));
+        for i in [0, n.saturating_sub(1), n] {
+            rkr_free_string(rkr_frame_symbol(frame, i));
+        }
+        free_rkr_frame(frame);
+    }
+    let _ = con_frame_iterator_reset(it);
+    let _ = con_frame_iterator_skip(it, usize::MAX);
+    free_con_frame_iterator(it);
+});
@@ -0,0 +1,46 @@
+//! `rkr_frame_get_header_line` with fuzzer-chosen header text, line index
+//! and buffer length, including 0 and lengths past the end of the buffer
+//! the caller really owns (the copy must stop at the line's own length).
+//!
+//! ```text
+//! cargo +nightly fuzz run ffi_header_line
+//! ```
+#![no_main]
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use libfuzzer_sys::fuzz_target;
+use readcon_core::ffi::*;
+
+const BODY: &str = "10.0 10.0 10.0\n90.0 90.0 90.0\n\n\n1\n1\n63.546\nCu\nCoordinates of Component 1\n0.0 0.0 0.0 0 0\n";
+
+fuzz_target!(|input: (u8, bool, u8, &str)| unsafe {
+    let (index, is_prebox, len, header) = input;
+    if header.contains('\n') {
+        return;
+    }
+    let text = format!("{header}\n\n{BODY}");
+    let it = read_con_buffer_iterator(text.as_ptr(), text.len());
+    let frame = con_frame_iterator_next(it);
+    free_con_frame_iterator(it);
+    if frame.is_null() {
+        return;
+    }
+    let line = usize::from(index % 4);
+    let owned = rkr_frame_get_header_line_cpp(frame, is_prebox, line);
+    let fits = !owned.is_null() && CStr::from_ptr(owned).to_bytes().len() < usize::from(len);
+    rkr_free_string(owned);
+    let mut buf = vec![0 as c_char; usize::from(len)];
+    // Overstate the length only when the line fits in what was allocated.
+    let buffer_len = if fits && len == u8::MAX {
+        usize::MAX
+    } else {
+        buf.len()
+    };
+    let status = rkr_frame_get_header_line(frame, is_prebox, line, buf.as_mut_ptr(), buffer_len);
+    if status == RKRStatus::RKR_STATUS_SUCCESS {
+        assert!(buf.contains(&0));
+    }
+    free_rkr_frame(frame);
+});
@@ -0,0 +1,17 @@
+//! Arbitrary bytes through every `ConFrameIterator` read path (see
+//! `common.rs` for the checks beyond "no panic"):
+//!
+//! ```text
+//! cargo +nightly fuzz run parse_bytes
+//! ```
+#![no_main]
+
+mod common;
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        common::exercise(text);
+    }
+});
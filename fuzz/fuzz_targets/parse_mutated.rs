@@ -0,0 +1,129 @@
+//! Structured mutations of the `.con` fixtures: lines deleted, duplicated,
+//! swapped or replaced, single columns swapped for hostile tokens (NaN,
+//! infinities, negatives, values past `u64::MAX` in the atom-id column),
+//! and truncation. Random bytes rarely get past the nine-line header;
+//! these inputs reach the atom and section parsers.
+//!
+//! ```text
+//! cargo +nightly fuzz run parse_mutated
+//! ```
+#![no_main]
+
+mod common;
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+
+const SEEDS: [&str; 6] = [
+    include_str!("../../resources/test/tiny_cuh2.con"),
+    include_str!("../../resources/test/tiny_multi_cuh2.con"),
+    include_str!("../../resources/test/tiny_cuh2.convel"),
+    include_str!("../../resources/test/tiny_multi_cuh2.convel"),
+    include_str!("../../resources/test/tiny_cuh2_vel_forces.con"),
+    include_str!("../../resources/test/tiny_cuh2_charges_spins_magmoms.con"),
+];
+
+#[derive(Arbitrary, Debug)]
+enum Token {
+    Nan,
+    Inf,
+    NegInf,
+    Negative,
+    Huge,
+    PastU64,
+    Empty,
+    Number(f64),
+    Integer(i64),
+    Text(String),
+}
+
+impl Token {
+    fn render(&self) -> String {
+        match self {
+            Token::Nan => "nan".into(),
+            Token::Inf => "inf".into(),
+            Token::NegInf => "-inf".into(),
+            Token::Negative => "-1".into(),
+            Token::Huge => "1e309".into(),
+            Token::PastU64 => "18446744073709551616".into(),
+            Token::Empty => String::new(),
+            Token::Number(v) => v.to_string(),
+            Token::Integer(v) => v.to_string(),
+            Token::Text(s) => s.replace(['\n', '\r'], " "),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+enum Mutation {
+    Delete(u16),
+    Duplicate(u16),
+    Swap(u16, u16),
+    Replace(u16, String),
+    Column { line: u16, column: u8, token: Token },
+    Truncate(u32),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    seed: u8,
+    mutations: Vec<Mutation>,
+}
+
+fn apply(text: &str, mutations: &[Mutation]) -> String {
+    let mut lines: Vec<String> = text.lines().map(str::to_owned).collect();
+    let mut truncate = None;
+    for mutation in mutations.iter().take(16) {
+        if lines.is_empty() {
+            break;
+        }
+        let at = |i: u16| usize::from(i) % lines.len();
+        match mutation {
+            Mutation::Delete(i) => {
+                lines.remove(at(*i));
+            }
+            Mutation::Duplicate(i) => {
+                let i = at(*i);
+                lines.insert(i, lines[i].clone());
+            }
+            Mutation::Swap(a, b) => {
+                let (a, b) = (at(*a), at(*b));
+                lines.swap(a, b);
+            }
+            Mutation::Replace(i, line) => {
+                let i = at(*i);
+                lines[i] = line.replace(['\n', '\r'], " ");
+            }
+            Mutation::Column {
+                line,
+                column,
+                token,
+            } => {
+                let i = at(*line);
+                let mut columns: Vec<String> =
+                    lines[i].split_whitespace().map(str::to_owned).collect();
+                let token = token.render();
+                match columns.get_mut(usize::from(*column)) {
+                    Some(slot) => *slot = token,
+                    None => columns.push(token),
+                }
+                lines[i] = columns.join(" ");
+            }
+            Mutation::Truncate(at) => truncate = Some(*at as usize),
+        }
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    if let Some(mut at) = truncate.map(|at| at % (out.len() + 1)) {
+        while !out.is_char_boundary(at) {
+            at -= 1;
+        }
+        out.truncate(at);
+    }
+    out
+}
+
+fuzz_target!(|input: Input| {
+    let seed = SEEDS[usize::from(input.seed) % SEEDS.len()];
+    common::exercise(&apply(seed, &input.mutations));
+});
@@ -12,13 +12,17 @@
  *     free_rkr_frame_array(frames, *num_frames). Each individual frame is
  *     released by free_rkr_frame_array; do NOT also call free_rkr_frame.
  *   - char* values returned by rkr_frame_metadata_json,
- *     rkr_frame_potential_type, and rkr_frame_get_header_line_cpp are heap
+ *     rkr_frame_potential_type, rkr_frame_symbol and
+ *     rkr_frame_get_header_line_cpp are heap
  *     allocated; free them with rkr_free_string. rkr_free_string is safe
  *     to call with NULL (no-op).
  *   - const char* returned by rkr_library_version is process-static; do
- *     NOT free it.
+ *     NOT free it. rkr_last_error_message is owned by the library and
+ *     valid until the next readcon call on the same thread.
  *   - rkr_frame_to_c_frame returns a CFrame whose `atoms` array is owned
  *     by the caller; release with free_c_frame.
+ *   - rkr_frame_positions_ptr borrows from the frame; the pointer is
+ *     valid until free_rkr_frame and must not be freed.
  *
  * Sentinel values for absent metadata
  *   - Floating-point getters (rkr_frame_energy, rkr_frame_time,
@@ -28,13 +32,30 @@
  *     rkr_frame_neb_band) return UINT64_MAX when absent.
  *   - String getters return NULL when absent.
  *
+ * Errors
+ *   - Functions that fail with NULL (readers, iterators, writer
+ *     constructors) record why in a thread-local slot: read it with
+ *     rkr_last_error_status and rkr_last_error_message.
+ *
  * Thread safety
- *   - Opaque handles are NOT Sync. Do not share a single
- *     RKRConFrame/RKRConFrameWriter/RKRConFrameBuilder across threads
- *     without external synchronization. Distinct handles are independent.
- *   - rkr_read_all_frames internally uses sequential parsing; the
- *     `parallel` Cargo feature is exposed only through the Rust API and
- *     is not surfaced via this C header.
+ *   - Every opaque handle may be created on one thread and used or freed
+ *     on another (e.g. frames handed between OpenMP workers).
+ *   - An RKRConFrame may be read from several threads at once through
+ *     the functions taking `const RKRConFrame*`. Functions taking a
+ *     non-const frame, and free_rkr_frame, need exclusive access.
+ *   - RKRConFrameWriter, RKRConFrameBuilder and CConFrameIterator handles
+ *     must not be used from two threads at the same time without external
+ *     synchronization. Distinct handles are independent.
+ *   - The last-error record (rkr_last_error_status / _message) is
+ *     thread-local; read it on the thread that made the failing call.
+ *   - rkr_set_default_options / rkr_get_default_options are safe to call
+ *     from any thread; the stored RKROptions apply process-wide.
+ *   - rkr_register_element / rkr_clear_elements are likewise thread-safe
+ *     and process-wide; registered labels affect rkr_symbol_to_z and
+ *     rkr_frame_to_c_frame on every thread.
+ *   - rkr_read_all_frames parses sequentially unless the library was
+ *     built with the `parallel` Cargo feature; RKROptions.num_threads
+ *     pins the worker count in that case.
  */
 
 /* Forward-declare the DLPack-managed tensor type for the tier-3
@@ -192,6 +213,11 @@ typedef enum RKRStatus {
      * Build cannot allocate on the requested non-CPU device (use caller-supplied buffers).
      */
     RKR_STATUS_DEVICE_ALLOC_UNSUPPORTED = -13,
+    /**
+     * Input text is not a valid CON/convel frame (see
+     * [`rkr_last_error_message`] for the line and reason).
+     */
+    RKR_STATUS_PARSE_ERROR = -14,
 } RKRStatus;
 
 /**
@@ -220,6 +246,31 @@ typedef struct RKRSelectionResult RKRSelectionResult;
 
 typedef struct String String;
 
+/**
+ * Process-wide defaults consulted by FFI calls that take no explicit
+ * option. Zero in every field means "built-in behavior", so a
+ * zero-initialized struct restores the library defaults.
+ */
+typedef struct RKROptions {
+    /**
+     * Decimal places for writers created without an explicit precision
+     * (`create_writer_from_path_c`, `create_writer_gzip_c`, ...).
+     * 0 keeps the writer's built-in default.
+     */
+    uint8_t precision;
+    /**
+     * Nonzero: iterators and whole-file readers skip blank lines and `#`
+     * comments between frames and accept Fortran `D` exponents (see
+     * [`crate::parser::ParserOptions::tolerant`]).
+     */
+    uint8_t lenient;
+    /**
+     * Rayon workers for `rkr_read_all_frames` in `parallel` builds.
+     * 0 uses the global pool; ignored when `lenient` is set (sequential).
+     */
+    uint32_t num_threads;
+} RKROptions;
+
 /**
  * An opaque handle to a full, lossless Rust `ConFrame` object.
  * The C/C++ side needs to treat this as a void pointer
@@ -231,6 +282,10 @@ typedef struct RKRConFrame {
 typedef struct CConFrameIterator {
     struct ConFrameIterator *iterator;
     struct String *file_contents;
+    /**
+     * Frames (or errors) consumed since creation or the last reset.
+     */
+    uintptr_t position;
 } CConFrameIterator;
 
 /**
@@ -414,8 +469,9 @@ uint64_t rkr_frame_atom_index_by_id(const struct RKRConFrame *frame_handle,
 
 /**
  * Returns the atomic number for a chemical symbol, or 0 if the symbol
- * is unknown or `symbol` is NULL. Lookup covers H..U (Z = 1..=92) and
- * is case-sensitive: "Fe" works, "fe" does not.
+ * is unknown or `symbol` is NULL. Lookup covers H..U (Z = 1..=92) plus
+ * labels added with [`rkr_register_element`], and is case-sensitive:
+ * "Fe" works, "fe" does not.
  *
  * # Safety
  *
@@ -432,6 +488,33 @@ uint64_t rkr_symbol_to_z(const char *symbol);
  */
 const char *rkr_z_to_symbol(uint64_t z);
 
+/**
+ * Register (or replace) a custom species label such as "Cu1" or "OW"
+ * with its atomic number and mass in amu. `mass <= 0` uses the standard
+ * weight of `z`; `z == 0` marks a non-element site and needs a mass.
+ * Returns `RKR_STATUS_VALIDATION_ERROR` for a label with whitespace,
+ * `z > 92`, or a non-finite mass. Applies process-wide.
+ *
+ * # Safety
+ * `label` must be a valid NUL-terminated C string.
+ */
+enum RKRStatus rkr_register_element(const char *label, uint64_t z, double mass);
+
+/**
+ * Drop every label added with [`rkr_register_element`].
+ */
+enum RKRStatus rkr_clear_elements(void);
+
+/**
+ * Returns `RKR_STATUS_VALIDATION_ERROR` when any atom's symbol is
+ * neither a known element nor a registered label, so callers can
+ * refuse frames that [`rkr_frame_to_c_frame`] would map to Z = 0.
+ *
+ * # Safety
+ * `frame_handle` must be a valid frame handle or NULL.
+ */
+enum RKRStatus rkr_frame_check_species(const struct RKRConFrame *frame_handle);
+
 /**
  * Returns the spec version stored in a parsed frame's header.
  * Returns 0 on error (null handle).
@@ -561,6 +644,54 @@ enum RKRStatus rkr_frame_bond_at(const struct RKRConFrame *frame_handle,
  */
 const char *rkr_status_message(enum RKRStatus status);
 
+/**
+ * Status of the last failure recorded on the calling thread, or
+ * `RKR_STATUS_SUCCESS` if there is none.
+ *
+ * Readers, iterators and writer constructors that signal failure with a
+ * NULL return (`read_con_*_iterator`, [`con_frame_iterator_next`],
+ * `rkr_read_*`, `create_writer_*`) clear the record on entry and set it on
+ * failure, as do [`con_frame_iterator_try_next`], [`rkr_writer_extend`],
+ * [`rkr_writer_write_cframe`] and [`rkr_frame_select`]. After one of those
+ * returns NULL, this tells "file not found" (`RKR_STATUS_IO_ERROR`) from
+ * "malformed frame" (`RKR_STATUS_PARSE_ERROR`) and from the end of an
+ * iterator (`RKR_STATUS_SUCCESS`).
+ */
+enum RKRStatus rkr_last_error_status(void);
+
+/**
+ * Human-readable description of the last failure recorded on the calling
+ * thread (e.g. `"frames.con: frame starting at line 1234: invalid number
+ * format: ..."`), or NULL if there is none. See [`rkr_last_error_status`].
+ *
+ * The string is owned by the library: do NOT free it. It stays valid until
+ * the next readcon call on the same thread; copy it to keep it longer.
+ */
+const char *rkr_last_error_message(void);
+
+/**
+ * Forgets the calling thread's last error.
+ */
+void rkr_clear_last_error(void);
+
+/**
+ * Store process-wide defaults honored by subsequent FFI calls on any
+ * thread. NULL resets to the built-in defaults. Calls already in flight
+ * keep the values they started with.
+ *
+ * # Safety
+ * `opts` must be NULL or point to a valid `RKROptions`.
+ */
+enum RKRStatus rkr_set_default_options(const struct RKROptions *opts);
+
+/**
+ * Copy the current process-wide defaults into `out`.
+ *
+ * # Safety
+ * `out` must be a valid, writable `RKROptions` pointer.
+ */
+enum RKRStatus rkr_get_default_options(struct RKROptions *out);
+
 /**
  * Creates a new iterator for a .con / .convel path, including transparent
  * gzip (`.con.gz`) and zstd (`.con.zst`, requires `zstd` feature) inputs via
@@ -592,6 +723,7 @@ struct CConFrameIterator *read_con_string_iterator(const char *contents_c);
  * Iterate frames from a byte buffer (not necessarily null-terminated).
  *
  * `len` is the number of bytes at `data`. Bytes must be valid UTF-8 CON text.
+ * The bytes are copied, so the caller may release `data` once this returns.
  *
  * # Safety
  * `data` must be valid for `len` bytes if non-null and `len > 0`.
@@ -603,11 +735,68 @@ struct CConFrameIterator *read_con_buffer_iterator(const uint8_t *data,
  * Reads the next frame from the iterator, returning an opaque handle.
  * The caller OWNS the returned handle and must free it with `free_rkr_frame`.
  *
+ * NULL means either the end of the input or a malformed frame;
+ * [`rkr_last_error_status`] is `RKR_STATUS_SUCCESS` only in the first case.
+ * [`con_frame_iterator_try_next`] returns the status directly.
+ *
  * # Safety
  * iterator must be valid. The caller takes ownership of the returned frame.
  */
 struct RKRConFrame *con_frame_iterator_next(struct CConFrameIterator *iterator);
 
+/**
+ * Reads the next frame from the iterator into `*out_frame`.
+ *
+ * Returns `RKR_STATUS_SUCCESS` with a frame the caller OWNS (free with
+ * `free_rkr_frame`), or with `*out_frame = NULL` at the end of the input.
+ * A malformed frame returns `RKR_STATUS_PARSE_ERROR` (or the matching
+ * code) and [`rkr_last_error_message`] names the line the frame starts on.
+ *
+ * # Safety
+ * iterator must be valid; out_frame must point to writable memory.
+ */
+enum RKRStatus con_frame_iterator_try_next(struct CConFrameIterator *iterator,
+                                           struct RKRConFrame **out_frame);
+
+/**
+ * Skips up to `n` frames without parsing their atom data and returns how
+ * many were skipped. Fewer than `n` means the input ended or a frame
+ * header was malformed; [`rkr_last_error_status`] tells which.
+ *
+ * # Safety
+ * iterator must be valid or null (returns 0).
+ */
+uintptr_t con_frame_iterator_skip(struct CConFrameIterator *iterator, uintptr_t n);
+
+/**
+ * Rewinds the iterator to the first frame of its input.
+ *
+ * # Safety
+ * iterator must be valid or null.
+ */
+enum RKRStatus con_frame_iterator_reset(struct CConFrameIterator *iterator);
+
+/**
+ * Number of frames left, found by skipping through them on a copy of the
+ * iterator (headers only; the iterator itself does not move). Counting
+ * stops at the first malformed header, which [`rkr_last_error_message`]
+ * then describes.
+ *
+ * # Safety
+ * iterator must be valid or null (returns 0).
+ */
+uintptr_t con_frame_iterator_count_remaining(const struct CConFrameIterator *iterator);
+
+/**
+ * Zero-based index of the frame the next read returns: the number of
+ * frames read, skipped or rejected as malformed since the iterator was
+ * created or reset. 0 for a NULL iterator.
+ *
+ * # Safety
+ * iterator must be valid or null.
+ */
+uintptr_t con_frame_iterator_position(const struct CConFrameIterator *iterator);
+
 /**
  * Frees the memory for an opaque `RKRConFrame` handle.
  *
@@ -627,6 +816,8 @@ void free_con_frame_iterator(struct CConFrameIterator *iterator);
 /**
  * Extracts the core atomic data into a transparent `CFrame` struct.
  * The caller OWNS the returned pointer and MUST call `free_c_frame` on it.
+ * Atomic numbers follow [`rkr_symbol_to_z`], so unknown symbols get 0;
+ * check with [`rkr_frame_check_species`] first to reject them.
  *
  * # Safety
  * frame_handle must be valid. The caller takes ownership of the returned CFrame.
@@ -724,6 +915,31 @@ enum RKRStatus rkr_writer_extend(struct RKRConFrameWriter *writer_handle,
                                  const struct RKRConFrame *const *frame_handles,
                                  uintptr_t num_frames);
 
+/**
+ * Writes one frame given as a transparent `CFrame` (e.g. from
+ * [`rkr_frame_to_c_frame`], or filled in by the caller) without building an
+ * `RKRConFrame` first.
+ *
+ * `symbols` is NULL or an array of `num_atoms` C strings; a NULL array or
+ * entry falls back to the element symbol of `atomic_number`, so labels
+ * without one (Z = 0) must be passed. Velocity, force and per-atom energy
+ * sections are written when the frame's `has_velocities`, `has_forces` and
+ * `has_energies` are set. An atom with `is_fixed` but no per-axis flag is
+ * fixed on all axes. The header lines are the builder defaults.
+ *
+ * Returns `RKR_STATUS_SUCCESS`, `RKR_STATUS_NULL_POINTER`,
+ * `RKR_STATUS_INVALID_UTF8` / `RKR_STATUS_VALIDATION_ERROR` for a bad
+ * symbol, or `RKR_STATUS_IO_ERROR`; see [`rkr_last_error_message`].
+ *
+ * # Safety
+ * writer_handle and frame must be valid; `frame->atoms` must hold
+ * `frame->num_atoms` records; symbols must be NULL or hold `num_atoms`
+ * pointers, each NULL or a NUL-terminated string.
+ */
+enum RKRStatus rkr_writer_write_cframe(struct RKRConFrameWriter *writer_handle,
+                                       const struct CFrame *frame,
+                                       const char *const *symbols);
+
 /**
  * Enable (`canonical != 0`) or disable campaign-stable CON serialization on an open writer.
  * Matches Rust `ConFrameWriter::canonical(true)` (deterministic metadata key order).
@@ -1644,6 +1860,55 @@ enum RKRStatus rkr_frame_metatensor_atom_energies_block(const struct RKRConFrame
  */
 uintptr_t rkr_frame_atom_count(const struct RKRConFrame *frame_handle);
 
+/**
+ * Same as [`rkr_frame_atom_count`]; the name pairs with
+ * [`rkr_frame_positions_ptr`], [`rkr_frame_symbol`] and [`rkr_frame_atom`].
+ *
+ * # Safety
+ * frame_handle must be valid or null.
+ */
+uintptr_t rkr_frame_num_atoms(const struct RKRConFrame *frame_handle);
+
+/**
+ * Returns a pointer to the frame's positions as `3 * rkr_frame_num_atoms`
+ * row-major doubles (`x0, y0, z0, x1, ...`), in file (type-grouped) order.
+ *
+ * The pointer borrows from the frame: it stays valid until the handle is
+ * freed, and must not be written through or freed. Returns NULL if
+ * `frame_handle` is NULL or the frame stores its positions in another
+ * precision (e.g. float32 storage); use [`rkr_frame_atom`] then.
+ *
+ * # Safety
+ * frame_handle must be valid or null.
+ */
+const double *rkr_frame_positions_ptr(const struct RKRConFrame *frame_handle);
+
+/**
+ * Returns the chemical symbol of atom `index` as a heap-allocated
+ * null-terminated C string. The caller MUST free with `rkr_free_string`.
+ * Unlike [`rkr_frame_to_c_frame`] this keeps labels that have no atomic
+ * number. Returns NULL if `frame_handle` is NULL or `index` is out of range.
+ *
+ * # Safety
+ * frame_handle must be valid. The caller takes ownership of the returned string.
+ */
+char *rkr_frame_symbol(const struct RKRConFrame *frame_handle, uintptr_t index);
+
+/**
+ * Fills `*out` with atom `index`, exactly as that atom would appear in
+ * the array returned by [`rkr_frame_to_c_frame`], without copying the
+ * rest of the frame.
+ *
+ * Returns `RKR_STATUS_NULL_POINTER` if `frame_handle` or `out` is NULL and
+ * `RKR_STATUS_INDEX_OUT_OF_BOUNDS` if `index >= rkr_frame_num_atoms`.
+ *
+ * # Safety
+ * frame_handle must be valid; out must point to writable memory for one `CAtom`.
+ */
+enum RKRStatus rkr_frame_atom(const struct RKRConFrame *frame_handle,
+                              uintptr_t index,
+                              struct CAtom *out);
+
 /**
  * Copy positions as row-major `[x0,y0,z0,...]` into `out` (length >= 3*N).
  */
@@ -134,6 +134,45 @@ struct Bond {
     std::optional<int32_t> order;
 };
 
+/**
+ * @brief Exception thrown for a non-success `RKRStatus`.
+ *
+ * Derives from std::runtime_error, so existing
+ * `catch (const std::runtime_error &)` handlers keep working; catch
+ * `readcon::Error` to inspect the status code.
+ */
+class Error : public std::runtime_error {
+  public:
+    Error(RKRStatus status, const std::string &operation)
+        : std::runtime_error(operation + ": " + describe(status)),
+          status_(status) {}
+
+    /// Like the status constructor, but the message is `detail` (e.g. the
+    /// text of rkr_last_error_message()) rather than the generic one.
+    Error(RKRStatus status, const std::string &operation,
+          const std::string &detail)
+        : std::runtime_error(operation + ": " + detail), status_(status) {}
+
+    /// The calling thread's last recorded C API failure
+    /// (rkr_last_error_status / rkr_last_error_message).
+    static Error last(const std::string &operation) {
+        RKRStatus status = rkr_last_error_status();
+        const char *message = rkr_last_error_message();
+        return message ? Error(status, operation, message)
+                       : Error(status, operation);
+    }
+
+    RKRStatus status() const noexcept { return status_; }
+
+  private:
+    static std::string describe(RKRStatus status) {
+        const char *message = rkr_status_message(status);
+        return message ? std::string(message) : std::string("unknown status");
+    }
+
+    RKRStatus status_;
+};
+
 /**
  * @brief An iterator for lazily reading frames from a .con file.
  *
@@ -187,6 +226,13 @@ class ConFrameIterator {
      * @throws std::runtime_error if the file cannot be opened.
      */
     explicit ConFrameIterator(const std::filesystem::path &path);
+    /**
+     * @brief Constructs a frame iterator over CON text already in memory.
+     * @param contents The file contents; copied, so it need not outlive the
+     *        iterator.
+     * @throws readcon::Error if the contents are not valid UTF-8.
+     */
+    static ConFrameIterator from_buffer(std::string_view contents);
     /**
      * @brief Returns an iterator to the beginning of the sequence of frames.
      */
@@ -196,6 +242,19 @@ class ConFrameIterator {
      */
     Iterator end();
 
+    /**
+     * @brief Skips up to `n` frames (headers only); returns how many were
+     *        skipped. Use before begin(), which reads the next frame.
+     * @throws readcon::Error if a skipped frame header is malformed.
+     */
+    std::size_t skip(std::size_t n);
+    /** @brief Rewinds to the first frame of the file. */
+    void reset();
+    /** @brief Frames left from the current position (headers only). */
+    std::size_t count_remaining() const;
+    /** @brief Zero-based index of the next frame to be read. */
+    std::size_t position() const;
+
   private:
     // Custom deleter for the CConFrameIterator to call the C free function.
     struct IteratorDeleter {
@@ -206,6 +265,8 @@ class ConFrameIterator {
         }
     };
 
+    explicit ConFrameIterator(CConFrameIterator *iter_ptr) : iterator_ptr_(iter_ptr) {}
+
     std::unique_ptr<CConFrameIterator, IteratorDeleter> iterator_ptr_;
 };
 
@@ -329,6 +390,27 @@ class ConFrame {
                              : 0;
     }
 
+    /**
+     * Row-major `(atom_count(), 3)` positions owned by the frame, valid
+     * while it lives; nullptr when they are not stored as double.
+     */
+    const double *positions_data() const {
+        return rkr_frame_positions_ptr(frame_handle_.get());
+    }
+
+    /**
+     * Symbol of atom `i`, including labels with no atomic number.
+     * Throws std::out_of_range if `i >= atom_count()`.
+     */
+    std::string symbol(std::size_t i) const {
+        char *p = rkr_frame_symbol(frame_handle_.get(), i);
+        if (!p)
+            throw std::out_of_range("ConFrame::symbol: atom index out of range");
+        std::string s(p);
+        rkr_free_string(p);
+        return s;
+    }
+
     /** Row-major xyz length >= 3*N. Status from C ABI. */
     RKRStatus copy_positions(double *out, std::size_t out_len) const {
         if (!frame_handle_)
@@ -838,8 +920,8 @@ inline std::string status_message(RKRStatus status) {
 
 /**
  * @brief Returns the atomic number for a chemical symbol, or 0 if the
- *        symbol is unknown. Coverage is H..U (Z = 1..=92);
- *        case-sensitive.
+ *        symbol is unknown. Coverage is H..U (Z = 1..=92) plus labels
+ *        added with register_element(); case-sensitive.
  */
 inline uint64_t symbol_to_z(const std::string &symbol) {
     return rkr_symbol_to_z(symbol.c_str());
@@ -855,35 +937,71 @@ inline std::string z_to_symbol(uint64_t z) {
     return symbol ? std::string(symbol) : std::string("X");
 }
 
+/**
+ * @throws readcon::Error when `status` is not RKR_STATUS_SUCCESS.
+ */
 inline void throw_on_error(RKRStatus status, const std::string &operation) {
     if (status != RKRStatus::RKR_STATUS_SUCCESS) {
-        throw std::runtime_error(operation + ": " + status_message(status));
+        throw Error(status, operation);
     }
 }
 
+/**
+ * @brief Sets process-wide defaults (writer precision, lenient parsing,
+ *        reader thread count) for subsequent calls. Pass a
+ *        value-initialized RKROptions{} to restore the built-in defaults.
+ */
+inline void set_default_options(const RKROptions &options) {
+    throw_on_error(rkr_set_default_options(&options), "rkr_set_default_options");
+}
+
+/**
+ * @brief Returns the current process-wide defaults.
+ */
+inline RKROptions default_options() {
+    RKROptions options{};
+    throw_on_error(rkr_get_default_options(&options), "rkr_get_default_options");
+    return options;
+}
+
+/**
+ * @brief Registers a custom species label (e.g. "OW", "Cu1") process-wide.
+ *        `mass <= 0` uses the standard weight of `z`; `z == 0` marks a
+ *        non-element site and needs a mass.
+ * @throws readcon::Error on an invalid label, Z or mass.
+ */
+inline void register_element(const std::string &label, uint64_t z, double mass = 0.0) {
+    throw_on_error(rkr_register_element(label.c_str(), z, mass), "rkr_register_element");
+}
+
+/**
+ * @brief Drops every label added with register_element().
+ */
+inline void clear_elements() {
+    throw_on_error(rkr_clear_elements(), "rkr_clear_elements");
+}
+
 /**
  * @brief Reads the first frame from a .con file using mmap.
- * @throws std::runtime_error on failure.
+ * @throws readcon::Error on failure, carrying rkr_last_error_message().
  */
 inline ConFrame read_first_frame(const std::filesystem::path &path) {
     RKRConFrame *handle = rkr_read_first_frame(path.string().c_str());
     if (!handle) {
-        throw std::runtime_error("Failed to read first frame from: " +
-                                 path.string());
+        throw Error::last("Failed to read first frame");
     }
     return ConFrame(handle);
 }
 
 /**
  * @brief Reads all frames from a .con file using mmap.
- * @throws std::runtime_error on failure.
+ * @throws readcon::Error on failure, carrying rkr_last_error_message().
  */
 inline std::vector<ConFrame> read_all_frames(const std::filesystem::path &path) {
     size_t num_frames = 0;
     RKRConFrame **handles = rkr_read_all_frames(path.string().c_str(), &num_frames);
     if (!handles) {
-        throw std::runtime_error("Failed to read frames from: " +
-                                 path.string());
+        throw Error::last("Failed to read frames");
     }
     std::vector<ConFrame> frames;
     frames.reserve(num_frames);
@@ -904,12 +1022,20 @@ inline std::vector<ConFrame> read_all_frames(const std::filesystem::path &path)
 inline ConFrameIterator::ConFrameIterator(const std::filesystem::path &path) {
     CConFrameIterator *iter_ptr = read_con_file_iterator(path.string().c_str());
     if (!iter_ptr) {
-        throw std::runtime_error("Failed to open .con file for iteration: " +
-                                 path.string());
+        throw Error::last("Failed to open .con file for iteration");
     }
     iterator_ptr_.reset(iter_ptr);
 }
 
+inline ConFrameIterator ConFrameIterator::from_buffer(std::string_view contents) {
+    CConFrameIterator *iter_ptr = read_con_buffer_iterator(
+        reinterpret_cast<const uint8_t *>(contents.data()), contents.size());
+    if (!iter_ptr) {
+        throw Error::last("Failed to read .con buffer for iteration");
+    }
+    return ConFrameIterator(iter_ptr);
+}
+
 inline ConFrameIterator::Iterator ConFrameIterator::begin() {
     return Iterator(iterator_ptr_.get());
 }
@@ -1229,6 +1355,26 @@ inline bool ConFrame::has_bonds() const {
     return rkr_frame_bond_count(frame_handle_.get()) > 0;
 }
 
+inline std::size_t ConFrameIterator::skip(std::size_t n) {
+    std::size_t skipped = con_frame_iterator_skip(iterator_ptr_.get(), n);
+    if (skipped < n && rkr_last_error_status() != RKR_STATUS_SUCCESS) {
+        throw Error::last("ConFrameIterator::skip");
+    }
+    return skipped;
+}
+
+inline void ConFrameIterator::reset() {
+    con_frame_iterator_reset(iterator_ptr_.get());
+}
+
+inline std::size_t ConFrameIterator::count_remaining() const {
+    return con_frame_iterator_count_remaining(iterator_ptr_.get());
+}
+
+inline std::size_t ConFrameIterator::position() const {
+    return con_frame_iterator_position(iterator_ptr_.get());
+}
+
 // --- Implementation of ConFrameWriter methods ---
 
 inline ConFrameWriter::ConFrameWriter(const std::filesystem::path &path,
@@ -1240,8 +1386,7 @@ inline ConFrameWriter::ConFrameWriter(const std::filesystem::path &path,
             create_writer_from_path_with_precision_c(path.string().c_str(), precision));
     }
     if (!writer_handle_) {
-        throw std::runtime_error("Failed to create writer for file: " +
-                                 path.string());
+        throw Error::last("Failed to create writer");
     }
 }
 
@@ -1277,7 +1422,7 @@ inline ConFrameWriter::ConFrameWriter(const std::filesystem::path &path,
     }
     writer_handle_.reset(raw);
     if (!writer_handle_) {
-        throw std::runtime_error("Failed to create writer for file: " + p);
+        throw Error::last("Failed to create writer");
     }
 }
 
@@ -1664,4 +1809,18 @@ inline ConFrame ConFrameBuilder::build() {
 
 } // namespace readcon
 
+/**
+ * @brief Short aliases used by the eOn C++ code base
+ *        (`rkr::Frame`, `rkr::FrameIterator`, `rkr::Writer`).
+ */
+namespace rkr {
+using Atom = readcon::Atom;
+using Error = readcon::Error;
+using Frame = readcon::ConFrame;
+using FrameBuilder = readcon::ConFrameBuilder;
+using FrameIterator = readcon::ConFrameIterator;
+using Reader = readcon::ConFrameIterator;
+using Writer = readcon::ConFrameWriter;
+} // namespace rkr
+
 #endif // READCON_PLUS_PLUS_H
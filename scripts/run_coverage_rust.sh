@@ -5,15 +5,15 @@
 #   - lcov.info (line coverage; Codecov's primary metric from DA records)
 #   - rust_codecov.json (optional second artifact)
 #
-# Features: full non-CUDA set. Ignores CLI/CUDA/PyO3/RPC glue and the thin
-# chemfiles_selection facade (real code is *_imp.rs).
+# Features: full non-CUDA set, CLI included. Ignores CUDA/PyO3/RPC glue and
+# the thin chemfiles_selection facade (real code is *_imp.rs).
 set -euo pipefail
 ROOT="$(cd "$(dirname "$0")/.." && pwd)"
 cd "$ROOT"
 OUT_JSON="${1:-rust_codecov.json}"
 OUT_LCOV="${2:-lcov.info}"
-FEATURES="${READCON_COV_FEATURES:-parallel,chemfiles,zstd,grammar,metatensor}"
-IGNORE='(/src/main\.rs|/src/cuda_array\.rs|/src/python\.rs|/src/rpc/|/src/chemfiles_selection\.rs$)'
+FEATURES="${READCON_COV_FEATURES:-cli,parallel,chemfiles,zstd,grammar,metatensor,nalgebra}"
+IGNORE='(/src/cuda_array\.rs|/src/python\.rs|/src/rpc/|/src/chemfiles_selection\.rs$)'
 
 unset RUSTC_WRAPPER SCCACHE_GHA_ENABLED || true
 export RUSTC_WRAPPER=""
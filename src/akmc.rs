@@ -0,0 +1,392 @@
+//! eOn adaptive kinetic Monte Carlo runs: states, rate tables and the
+//! state-to-state trajectory.
+//!
+//! An AKMC run directory holds `dynamics.txt` (one row per accepted
+//! transition) and `states/<n>/` per visited state, each with the state's
+//! `reactant.con`, its rate table `processtable` and the saddle searches
+//! under `procdata/` (see [`crate::process`]). [`AkmcTrajectory`] replays
+//! `dynamics.txt` against the rate tables into the sequence of visited
+//! states with their residence times, and writes it as one multi-frame CON
+//! file for visualization.
+
+use crate::error::ParseError;
+use crate::iterators;
+use crate::process::ProcessTriple;
+use crate::types::ConFrame;
+use crate::writer::ConFrameWriter;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Metadata key for the AKMC state id of a trajectory frame.
+pub const META_STATE: &str = "akmc_state";
+/// Metadata key for the residence time of a trajectory frame.
+pub const META_RESIDENCE_TIME: &str = "residence_time";
+
+/// One row of a state's `processtable`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessTableEntry {
+    pub id: u64,
+    pub saddle_energy: f64,
+    pub prefactor: f64,
+    /// Product state id; `None` while the product is unassigned (`-1`).
+    pub product: Option<u64>,
+    pub product_energy: f64,
+    pub product_prefactor: f64,
+    pub barrier: f64,
+    pub rate: f64,
+    pub repeats: u64,
+}
+
+/// One row of `dynamics.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicsStep {
+    pub step: u64,
+    pub reactant: u64,
+    pub process: u64,
+    pub product: u64,
+    /// Time spent in `reactant` before this transition.
+    pub step_time: f64,
+    /// Simulation time after this transition.
+    pub total_time: f64,
+    pub barrier: f64,
+    pub rate: f64,
+    pub energy: f64,
+}
+
+/// Splits a whitespace table row into `n` columns, or `None` for header,
+/// separator and blank lines (first column not an integer).
+fn table_row(line: &str, n: usize, what: &str) -> Result<Option<Vec<String>>, ParseError> {
+    let cols: Vec<String> = line.split_whitespace().map(str::to_owned).collect();
+    match cols.first() {
+        Some(c) if c.parse::<i64>().is_ok() => {}
+        _ => return Ok(None),
+    }
+    if cols.len() < n {
+        return Err(ParseError::ValidationError(format!(
+            "{what} row has {} columns, expected {n}: {line:?}",
+            cols.len()
+        )));
+    }
+    Ok(Some(cols))
+}
+
+fn num<T: std::str::FromStr>(s: &str, what: &str) -> Result<T, ParseError> {
+    s.parse()
+        .map_err(|_| ParseError::InvalidNumberFormat(format!("{what}: {s:?}")))
+}
+
+/// Parses a `processtable` (`proc #  saddle energy  prefactor  product
+/// product energy  product prefactor  barrier  rate  repeats`).
+pub fn parse_process_table(text: &str) -> Result<Vec<ProcessTableEntry>, ParseError> {
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let Some(c) = table_row(line, 9, "processtable")? else {
+            continue;
+        };
+        let product: i64 = num(&c[3], "processtable product")?;
+        out.push(ProcessTableEntry {
+            id: num(&c[0], "processtable id")?,
+            saddle_energy: num(&c[1], "processtable saddle energy")?,
+            prefactor: num(&c[2], "processtable prefactor")?,
+            product: u64::try_from(product).ok(),
+            product_energy: num(&c[4], "processtable product energy")?,
+            product_prefactor: num(&c[5], "processtable product prefactor")?,
+            barrier: num(&c[6], "processtable barrier")?,
+            rate: num(&c[7], "processtable rate")?,
+            repeats: num(&c[8], "processtable repeats")?,
+        });
+    }
+    Ok(out)
+}
+
+/// Parses `dynamics.txt` (`step-number  reactant-id  process-id
+/// product-id  step-time  total-time  barrier  rate  energy`).
+pub fn parse_dynamics(text: &str) -> Result<Vec<DynamicsStep>, ParseError> {
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let Some(c) = table_row(line, 9, "dynamics")? else {
+            continue;
+        };
+        out.push(DynamicsStep {
+            step: num(&c[0], "dynamics step")?,
+            reactant: num(&c[1], "dynamics reactant")?,
+            process: num(&c[2], "dynamics process")?,
+            product: num(&c[3], "dynamics product")?,
+            step_time: num(&c[4], "dynamics step time")?,
+            total_time: num(&c[5], "dynamics total time")?,
+            barrier: num(&c[6], "dynamics barrier")?,
+            rate: num(&c[7], "dynamics rate")?,
+            energy: num(&c[8], "dynamics energy")?,
+        });
+    }
+    Ok(out)
+}
+
+/// One AKMC state: its minimum and rate table.
+#[derive(Debug, Clone)]
+pub struct State {
+    pub id: u64,
+    /// `states/<id>` directory.
+    pub dir: PathBuf,
+    pub reactant: ConFrame,
+    pub processes: Vec<ProcessTableEntry>,
+}
+
+impl State {
+    /// Reads `states_dir/<id>/reactant.con` and its `processtable` (empty
+    /// when the state has no table yet).
+    pub fn read(states_dir: &Path, id: u64) -> Result<Self, Box<dyn std::error::Error>> {
+        let dir = states_dir.join(id.to_string());
+        let reactant = iterators::read_first_frame(&dir.join("reactant.con"))?;
+        let table = dir.join("processtable");
+        let processes = if table.exists() {
+            parse_process_table(&std::fs::read_to_string(&table)?)?
+        } else {
+            Vec::new()
+        };
+        Ok(State {
+            id,
+            dir,
+            reactant,
+            processes,
+        })
+    }
+
+    /// Rate-table row for process `id`.
+    pub fn process(&self, id: u64) -> Option<&ProcessTableEntry> {
+        self.processes.iter().find(|p| p.id == id)
+    }
+
+    /// Sum of all process rates: the escape rate out of this state.
+    pub fn total_rate(&self) -> f64 {
+        self.processes.iter().map(|p| p.rate).sum()
+    }
+
+    /// Reads process `id`'s frames from `procdata/`.
+    pub fn read_process(&self, id: u64) -> Result<ProcessTriple, Box<dyn std::error::Error>> {
+        ProcessTriple::read_dir(&self.dir.join("procdata"), Some(id))
+    }
+}
+
+/// One stay in a state along the AKMC trajectory.
+#[derive(Debug, Clone)]
+pub struct StateVisit {
+    pub state: u64,
+    /// The state's `reactant.con`.
+    pub frame: ConFrame,
+    /// Simulation time on entering the state.
+    pub entry_time: f64,
+    /// Time spent before leaving; 0 for the final state.
+    pub residence_time: f64,
+    /// Process taken out of the state; `None` for the final state.
+    pub process: Option<u64>,
+}
+
+/// State-to-state AKMC trajectory; see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct AkmcTrajectory {
+    pub visits: Vec<StateVisit>,
+}
+
+impl AkmcTrajectory {
+    /// Reconstructs the trajectory of the run in `run_dir` from
+    /// `dynamics.txt` and `states/`.
+    pub fn read(run_dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let steps = parse_dynamics(&std::fs::read_to_string(run_dir.join("dynamics.txt"))?)?;
+        let states_dir = run_dir.join("states");
+        let mut cache: HashMap<u64, State> = HashMap::new();
+        Self::from_steps(&steps, |id| {
+            if let Some(s) = cache.get(&id) {
+                return Ok(s.clone());
+            }
+            let s = State::read(&states_dir, id)?;
+            cache.insert(id, s.clone());
+            Ok(s)
+        })
+    }
+
+    /// Replays `steps`, loading states through `state`. Every step must be
+    /// a process listed in its reactant's rate table, with a matching
+    /// product when the table has assigned one, and must start from the
+    /// previous step's product.
+    pub fn from_steps<F>(
+        steps: &[DynamicsStep],
+        mut state: F,
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        F: FnMut(u64) -> Result<State, Box<dyn std::error::Error>>,
+    {
+        let mut visits = Vec::with_capacity(steps.len() + 1);
+        for (k, step) in steps.iter().enumerate() {
+            if k > 0 && steps[k - 1].product != step.reactant {
+                return Err(ParseError::ValidationError(format!(
+                    "step {} starts in state {}, previous step ended in {}",
+                    step.step,
+                    step.reactant,
+                    steps[k - 1].product
+                ))
+                .into());
+            }
+            let s = state(step.reactant)?;
+            let entry = s.process(step.process).ok_or_else(|| {
+                ParseError::ValidationError(format!(
+                    "step {}: process {} not in state {} processtable",
+                    step.step, step.process, step.reactant
+                ))
+            })?;
+            if entry.product.is_some_and(|p| p != step.product) {
+                return Err(ParseError::ValidationError(format!(
+                    "step {}: processtable sends process {} to state {}, dynamics to {}",
+                    step.step,
+                    step.process,
+                    entry.product.unwrap_or_default(),
+                    step.product
+                ))
+                .into());
+            }
+            visits.push(StateVisit {
+                state: step.reactant,
+                frame: s.reactant,
+                entry_time: step.total_time - step.step_time,
+                residence_time: step.step_time,
+                process: Some(step.process),
+            });
+        }
+        if let Some(last) = steps.last() {
+            visits.push(StateVisit {
+                state: last.product,
+                frame: state(last.product)?.reactant,
+                entry_time: last.total_time,
+                residence_time: 0.0,
+                process: None,
+            });
+        }
+        Ok(AkmcTrajectory { visits })
+    }
+
+    /// Number of visits (transitions + 1 for a non-empty run).
+    pub fn len(&self) -> usize {
+        self.visits.len()
+    }
+
+    /// True when no transitions were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.visits.is_empty()
+    }
+
+    /// Simulation time at the end of the trajectory.
+    pub fn total_time(&self) -> f64 {
+        self.visits.last().map_or(0.0, |v| v.entry_time)
+    }
+
+    /// One frame per visit with `time` (entry time), `frame_index`,
+    /// [`META_STATE`] and [`META_RESIDENCE_TIME`] set. A blank comment line
+    /// becomes `AKMC state <id>`, keeping multi-frame output readable.
+    pub fn frames(&self) -> Vec<ConFrame> {
+        self.visits
+            .iter()
+            .enumerate()
+            .map(|(k, v)| {
+                let mut f = v.frame.clone();
+                f.header.set_frame_index(k as u64);
+                f.header.set_time(v.entry_time);
+                f.header
+                    .metadata
+                    .insert(META_STATE.into(), serde_json::Value::from(v.state));
+                f.header.metadata.insert(
+                    META_RESIDENCE_TIME.into(),
+                    serde_json::Value::from(v.residence_time),
+                );
+                if f.header.prebox_header.user.trim().is_empty() {
+                    f.header.prebox_header.user = format!("AKMC state {}", v.state);
+                }
+                f
+            })
+            .collect()
+    }
+
+    /// Writes [`Self::frames`] to one multi-frame file.
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let mut writer = ConFrameWriter::from_path(path)?;
+        writer.extend(self.frames().iter())?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    const TABLE_0: &str = "\
+proc #      saddle energy    prefactor       product      product energy   product prefactor       barrier         rate  repeats
+      0       -99.50000 1.00000e+12         1        -99.90000       1.00000e+12  0.50000  1.00000e+03       2
+      1       -99.20000 1.00000e+12        -1        -99.80000       1.00000e+12  0.80000  1.00000e+00       1
+";
+    const TABLE_1: &str = "\
+proc #      saddle energy    prefactor       product      product energy   product prefactor       barrier         rate  repeats
+      0       -99.50000 1.00000e+12         0       -100.00000       1.00000e+12  0.40000  2.00000e+04       3
+";
+    const DYNAMICS: &str = "\
+ step-number   reactant-id    process-id    product-id     step-time    total-time       barrier          rate        energy
+---------------------------------------------------------------------------------------------------------------------------
+           0             0             0             1  1.000000e-03  1.000000e-03  5.000000e-01  1.000000e+03 -1.000000e+02
+           1             1             0             0  5.000000e-05  1.050000e-03  4.000000e-01  2.000000e+04 -9.990000e+01
+";
+
+    fn write_state(states: &Path, id: u64, x: f64, table: &str) {
+        let dir = states.join(id.to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+        b.add_atom("H", x, 0.0, 0.0, [false; 3], 1, 1.008);
+        let mut w = ConFrameWriter::from_path(dir.join("reactant.con")).unwrap();
+        w.write_frame(&b.build()).unwrap();
+        w.flush().unwrap();
+        std::fs::write(dir.join("processtable"), table).unwrap();
+    }
+
+    #[test]
+    fn parses_tables() {
+        let t = parse_process_table(TABLE_0).unwrap();
+        assert_eq!(t.len(), 2);
+        assert_eq!(t[0].product, Some(1));
+        assert_eq!(t[1].product, None);
+        assert_eq!(t[0].repeats, 2);
+        let d = parse_dynamics(DYNAMICS).unwrap();
+        assert_eq!(d[1].reactant, 1);
+        assert!((d[1].total_time - 1.05e-3).abs() < 1e-15);
+        assert!(parse_dynamics("0 1 2").is_err());
+    }
+
+    #[test]
+    fn reconstructs_and_exports() {
+        let run = tempfile::tempdir().unwrap();
+        let states = run.path().join("states");
+        write_state(&states, 0, 1.0, TABLE_0);
+        write_state(&states, 1, 2.0, TABLE_1);
+        std::fs::write(run.path().join("dynamics.txt"), DYNAMICS).unwrap();
+
+        let traj = AkmcTrajectory::read(run.path()).unwrap();
+        assert_eq!(traj.len(), 3);
+        assert_eq!(
+            traj.visits.iter().map(|v| v.state).collect::<Vec<_>>(),
+            vec![0, 1, 0]
+        );
+        assert!((traj.visits[1].entry_time - 1.0e-3).abs() < 1e-15);
+        assert_eq!(traj.visits[2].process, None);
+        assert!((traj.total_time() - 1.05e-3).abs() < 1e-15);
+
+        let out = run.path().join("akmc.con");
+        traj.write(&out).unwrap();
+        let frames = iterators::read_all_frames(&out).unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].header.metadata[META_STATE], 1);
+        assert_eq!(frames[1].atom_data[1].x, 2.0);
+        assert_eq!(frames[2].header.frame_index(), Some(2));
+
+        // A transition the rate table does not know about is rejected.
+        std::fs::write(states.join("1/processtable"), "").unwrap();
+        assert!(AkmcTrajectory::read(run.path()).is_err());
+    }
+}
@@ -0,0 +1,1439 @@
+//! Trajectory analysis on parsed frames.
+//!
+//! [`rdf`] accumulates radial distribution functions g(r) for chosen
+//! species pairs while frames stream in, so a whole trajectory never has to
+//! be held in memory. Pair distances come from a [`NeighborList`] with the
+//! frame's cell and `pbc` metadata; frames are normalized by their own cell
+//! volume, so variable-cell runs average correctly.
+//!
+//! [`coordination_numbers`] counts each atom's neighbors within cutoffs set
+//! per species pair, which singles out adatoms and the atoms around a
+//! vacancy in a surface or bulk state.
+//!
+//! [`rmsd`] and [`max_displacement`] answer "did anything actually move?"
+//! between two frames of the same system, optionally pairing atoms by
+//! `atom_id` and measuring along the minimum image
+//! ([`DisplacementOptions`]).
+//!
+//! [`align`] superimposes one frame on another with the rigid rotation and
+//! translation that minimize the RMSD over a chosen set of atoms (Kabsch),
+//! so a substrate that drifted between states does not pass for motion.
+//!
+//! [`detect_events`] walks a trajectory and reports the steps in which some
+//! atom moved further than a threshold, with the atoms that did, which is
+//! how an AKMC or MD run is boiled down to its transitions.
+//!
+//! [`frames_equivalent`] decides whether two frames hold the same structure
+//! whatever the order of their atoms, the comparison AKMC needs to
+//! recognize a state it has already visited; [`dedup`] keeps the distinct
+//! structures of a trajectory.
+//!
+//! [`trajectory_stats`] streams a trajectory into per-frame time series:
+//! each atom's cumulative displacement from the first frame (unwrapped
+//! across periodic boundaries step by step), the mean-squared displacement,
+//! the center-of-mass drift and, for frames with velocities, the
+//! instantaneous temperature.
+
+use crate::error::ParseError;
+use crate::frame::AtomicFrame;
+use crate::neighbor::NeighborList;
+use crate::tolerance::Tolerances;
+use crate::types::{AtomDatum, ConFrame};
+use crate::units::unit_conversion_factor;
+use std::collections::HashMap;
+
+/// g(r) of one species pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rdf {
+    /// Central and neighbor species.
+    pub species: (String, String),
+    /// Bin centers.
+    pub r: Vec<f64>,
+    /// g(r) averaged over the frames that contain both species.
+    pub g: Vec<f64>,
+    /// Frames that contributed.
+    pub nframes: usize,
+}
+
+impl Rdf {
+    /// Width of one bin.
+    pub fn bin_width(&self) -> f64 {
+        match self.r.as_slice() {
+            [first, ..] => 2.0 * first,
+            [] => 0.0,
+        }
+    }
+}
+
+/// Streaming g(r) accumulator; [`rdf`] wraps it for iterators.
+#[derive(Debug, Clone)]
+pub struct RdfAccumulator {
+    pairs: Vec<(String, String)>,
+    r_max: f64,
+    nbins: usize,
+    /// Per pair: sum over frames of the per-frame g(r).
+    sums: Vec<Vec<f64>>,
+    nframes: Vec<usize>,
+    neighbors: NeighborList,
+    counts: Vec<f64>,
+}
+
+impl RdfAccumulator {
+    /// Accumulator for `pairs` of species (`("O", "H")` is O-centered),
+    /// with `nbins` bins over `[0, r_max)`.
+    ///
+    /// Errors are [`ParseError::ValidationError`] for no pairs, zero bins
+    /// or a non-positive `r_max`.
+    pub fn new(pairs: &[(&str, &str)], r_max: f64, nbins: usize) -> Result<Self, ParseError> {
+        if pairs.is_empty() || nbins == 0 || !(r_max.is_finite() && r_max > 0.0) {
+            return Err(ParseError::ValidationError(format!(
+                "rdf needs at least one pair, bins and a positive r_max \
+                 (got {} pair(s), {nbins} bin(s), r_max {r_max})",
+                pairs.len()
+            )));
+        }
+        Ok(RdfAccumulator {
+            pairs: pairs
+                .iter()
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .collect(),
+            r_max,
+            nbins,
+            sums: vec![vec![0.0; nbins]; pairs.len()],
+            nframes: vec![0; pairs.len()],
+            neighbors: NeighborList::default(),
+            counts: vec![0.0; nbins],
+        })
+    }
+
+    /// Adds one frame. A pair skips frames lacking either species (or,
+    /// for a like pair, holding a single atom of it).
+    pub fn add_frame<F: AtomicFrame + ?Sized>(&mut self, frame: &F) -> Result<(), ParseError> {
+        let volume = frame
+            .cell()
+            .ok_or_else(|| ParseError::ValidationError("rdf needs a non-degenerate cell".into()))?
+            .volume();
+        self.neighbors.rebuild(frame, self.r_max)?;
+        let width = self.r_max / self.nbins as f64;
+        let count_of = |s: &str| {
+            (0..frame.natoms())
+                .filter(|&i| frame.species(i) == s)
+                .count()
+        };
+        for (k, (a, b)) in self.pairs.iter().enumerate() {
+            let n_a = count_of(a);
+            let n_b = count_of(b);
+            let partners = if a == b { n_b.saturating_sub(1) } else { n_b };
+            if n_a == 0 || partners == 0 {
+                continue;
+            }
+            self.counts.iter_mut().for_each(|c| *c = 0.0);
+            for p in self.neighbors.pairs() {
+                let bin = (p.distance / width) as usize;
+                if bin >= self.nbins {
+                    continue;
+                }
+                let (si, sj) = (frame.species(p.i), frame.species(p.j));
+                // Ordered pairs: a like pair counts from both ends.
+                let hits = usize::from(si == a && sj == b) + usize::from(si == b && sj == a);
+                self.counts[bin] += hits as f64;
+            }
+            let density = partners as f64 / volume;
+            for (bin, (sum, count)) in self.sums[k].iter_mut().zip(&self.counts).enumerate() {
+                let (lo, hi) = (bin as f64 * width, (bin + 1) as f64 * width);
+                let shell = 4.0 / 3.0 * std::f64::consts::PI * (hi.powi(3) - lo.powi(3));
+                *sum += count / (n_a as f64 * density * shell);
+            }
+            self.nframes[k] += 1;
+        }
+        Ok(())
+    }
+
+    /// The averaged g(r) of every pair, in the order given to [`Self::new`].
+    pub fn finish(&self) -> Vec<Rdf> {
+        let width = self.r_max / self.nbins as f64;
+        let r: Vec<f64> = (0..self.nbins).map(|b| (b as f64 + 0.5) * width).collect();
+        self.pairs
+            .iter()
+            .zip(&self.sums)
+            .zip(&self.nframes)
+            .map(|((species, sum), &nframes)| Rdf {
+                species: species.clone(),
+                r: r.clone(),
+                g: sum
+                    .iter()
+                    .map(|s| if nframes > 0 { s / nframes as f64 } else { 0.0 })
+                    .collect(),
+                nframes,
+            })
+            .collect()
+    }
+}
+
+/// g(r) for each of `pairs` over all `frames` (any [`AtomicFrame`]), e.g. a
+/// [`ConFrameIterator`](crate::iterators::ConFrameIterator); stops at the
+/// first frame error. See [`RdfAccumulator`].
+///
+/// # Example
+/// ```
+/// use readcon_core::analysis::rdf;
+/// use readcon_core::iterators::ConFrameIterator;
+/// let text = std::fs::read_to_string(concat!(
+///     env!("CARGO_MANIFEST_DIR"),
+///     "/resources/test/tiny_multi_cuh2.con"
+/// ))
+/// .unwrap();
+/// let g = rdf(ConFrameIterator::new(&text), &[("Cu", "H"), ("H", "H")], 6.0, 60).unwrap();
+/// assert_eq!(g[0].species, ("Cu".to_string(), "H".to_string()));
+/// assert_eq!(g[1].r.len(), 60);
+/// ```
+pub fn rdf<I, F>(
+    frames: I,
+    pairs: &[(&str, &str)],
+    r_max: f64,
+    nbins: usize,
+) -> Result<Vec<Rdf>, ParseError>
+where
+    I: IntoIterator<Item = Result<F, ParseError>>,
+    F: AtomicFrame,
+{
+    let mut acc = RdfAccumulator::new(pairs, r_max, nbins)?;
+    for frame in frames {
+        acc.add_frame(&frame?)?;
+    }
+    Ok(acc.finish())
+}
+
+/// Per-atom coordination numbers: how many neighbors each atom has within
+/// the cutoff `cutoffs` gives for the two species, as `(a, b, r)` entries
+/// that apply both ways round (`("Cu", "H", 2.0)` also covers H-Cu). Pairs
+/// of species without an entry never count; a later entry for the same
+/// pair replaces an earlier one. Periodic images count as in
+/// [`NeighborList`], an atom's own images included.
+///
+/// Errors are [`ParseError::ValidationError`] for no cutoffs, a
+/// non-positive or non-finite cutoff and frames without a usable cell.
+///
+/// # Example
+/// ```
+/// use readcon_core::analysis::coordination_numbers;
+/// use readcon_core::types::ConFrameBuilder;
+/// let mut b = ConFrameBuilder::new([2.5, 2.5, 20.0], [90.0; 3]);
+/// b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+/// b.add_atom("H", 0.0, 0.0, 1.5, [false; 3], 1, 1.008);
+/// let cn = coordination_numbers(&b.build(), &[("Cu", "Cu", 3.0), ("Cu", "H", 2.0)]).unwrap();
+/// // Four in-plane Cu images plus the H on top; the H sees one Cu.
+/// assert_eq!(cn, vec![5, 1]);
+/// ```
+pub fn coordination_numbers<F: AtomicFrame + ?Sized>(
+    frame: &F,
+    cutoffs: &[(&str, &str, f64)],
+) -> Result<Vec<usize>, ParseError> {
+    if cutoffs.is_empty() {
+        return Err(ParseError::ValidationError(
+            "coordination needs at least one species-pair cutoff".into(),
+        ));
+    }
+    let mut table: HashMap<(&str, &str), f64> = HashMap::with_capacity(cutoffs.len());
+    for &(a, b, r) in cutoffs {
+        if !(r.is_finite() && r > 0.0) {
+            return Err(ParseError::ValidationError(format!(
+                "cutoff for {a}-{b} must be positive and finite, got {r}"
+            )));
+        }
+        table.insert(if a <= b { (a, b) } else { (b, a) }, r);
+    }
+    let r_max = table.values().fold(0.0, |m: f64, r| m.max(*r));
+    let neighbors = NeighborList::build(frame, r_max)?;
+    let mut counts = vec![0; frame.natoms()];
+    for p in neighbors.pairs() {
+        let (a, b) = (frame.species(p.i), frame.species(p.j));
+        let key = if a <= b { (a, b) } else { (b, a) };
+        if table.get(&key).is_some_and(|r| p.distance <= *r) {
+            counts[p.i] += 1;
+            counts[p.j] += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// How [`displacements`] pairs atoms and measures distances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplacementOptions {
+    /// Pair atoms by `atom_id` instead of by index, so frames whose atoms
+    /// were reordered (e.g. re-grouped by type) still compare. Ids must be
+    /// unique within each frame.
+    pub match_atom_ids: bool,
+    /// Apply the minimum-image convention with the second frame's cell
+    /// along the axes its `pbc` metadata marks periodic.
+    pub minimum_image: bool,
+}
+
+impl Default for DisplacementOptions {
+    /// Pair by index, with minimum-image correction.
+    fn default() -> Self {
+        DisplacementOptions {
+            match_atom_ids: false,
+            minimum_image: true,
+        }
+    }
+}
+
+/// `b - a` displacement of every atom of `a`, in `a`'s atom order.
+///
+/// Errors are [`ParseError::ValidationError`] for differing atom counts,
+/// duplicate or unmatched `atom_id`s (with
+/// [`match_atom_ids`](DisplacementOptions::match_atom_ids)) and, with
+/// [`minimum_image`](DisplacementOptions::minimum_image), a degenerate cell.
+pub fn displacements<F: AtomicFrame + ?Sized>(
+    a: &F,
+    b: &F,
+    options: &DisplacementOptions,
+) -> Result<Vec<[f64; 3]>, ParseError> {
+    let n = a.natoms();
+    if b.natoms() != n {
+        return Err(ParseError::ValidationError(format!(
+            "frames have {n} and {} atoms",
+            b.natoms()
+        )));
+    }
+    let mic = if options.minimum_image {
+        let cell = b.cell().ok_or_else(|| {
+            ParseError::ValidationError("minimum image needs a non-degenerate cell".into())
+        })?;
+        Some((cell, b.pbc()))
+    } else {
+        None
+    };
+    let partner: Vec<usize> = if options.match_atom_ids {
+        let mut by_id = HashMap::with_capacity(n);
+        for j in 0..n {
+            if by_id.insert(b.atom_id(j), j).is_some() {
+                return Err(ParseError::ValidationError(format!(
+                    "duplicate atom_id {} in second frame",
+                    b.atom_id(j)
+                )));
+            }
+        }
+        let mut seen = vec![false; n];
+        let mut partner = Vec::with_capacity(n);
+        for i in 0..n {
+            let id = a.atom_id(i);
+            let j = *by_id.get(&id).ok_or_else(|| {
+                ParseError::ValidationError(format!("atom_id {id} missing from second frame"))
+            })?;
+            if std::mem::replace(&mut seen[j], true) {
+                return Err(ParseError::ValidationError(format!(
+                    "duplicate atom_id {id} in first frame"
+                )));
+            }
+            partner.push(j);
+        }
+        partner
+    } else {
+        (0..n).collect()
+    };
+    Ok(partner
+        .into_iter()
+        .enumerate()
+        .map(|(i, j)| {
+            let (pa, pb) = (a.position(i), b.position(j));
+            let d = [pb[0] - pa[0], pb[1] - pa[1], pb[2] - pa[2]];
+            match &mic {
+                Some((cell, pbc)) => cell.minimum_image(d, *pbc),
+                None => d,
+            }
+        })
+        .collect())
+}
+
+/// Root-mean-square displacement between `a` and `b` with the default
+/// [`DisplacementOptions`]; `0.0` for empty frames. No rigid-body fit is
+/// applied.
+///
+/// # Example
+/// ```
+/// use readcon_core::analysis::rmsd;
+/// use readcon_core::types::ConFrameBuilder;
+/// let frame = |x: f64| {
+///     let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+///     b.add_atom("H", x, 5.0, 5.0, [false; 3], 0, 1.008);
+///     b.add_atom("H", 5.0, 5.0, 5.0, [false; 3], 1, 1.008);
+///     b.build()
+/// };
+/// // 9.8 -> 0.2 crosses the face: a 0.4 step, not 9.6.
+/// assert!((rmsd(&frame(9.8), &frame(0.2)).unwrap() - 0.4 / 2f64.sqrt()).abs() < 1e-9);
+/// ```
+pub fn rmsd<F: AtomicFrame + ?Sized>(a: &F, b: &F) -> Result<f64, ParseError> {
+    rmsd_with(a, b, &DisplacementOptions::default())
+}
+
+/// [`rmsd`] with explicit `options`.
+pub fn rmsd_with<F: AtomicFrame + ?Sized>(
+    a: &F,
+    b: &F,
+    options: &DisplacementOptions,
+) -> Result<f64, ParseError> {
+    let d = displacements(a, b, options)?;
+    if d.is_empty() {
+        return Ok(0.0);
+    }
+    let sum: f64 = d.iter().flatten().map(|x| x * x).sum();
+    Ok((sum / d.len() as f64).sqrt())
+}
+
+/// Largest single-atom displacement between `a` and `b` with the default
+/// [`DisplacementOptions`]; `0.0` for empty frames.
+pub fn max_displacement<F: AtomicFrame + ?Sized>(a: &F, b: &F) -> Result<f64, ParseError> {
+    max_displacement_with(a, b, &DisplacementOptions::default())
+}
+
+/// [`max_displacement`] with explicit `options`.
+pub fn max_displacement_with<F: AtomicFrame + ?Sized>(
+    a: &F,
+    b: &F,
+    options: &DisplacementOptions,
+) -> Result<f64, ParseError> {
+    Ok(displacements(a, b, options)?
+        .iter()
+        .map(|d| d.iter().map(|x| x * x).sum::<f64>().sqrt())
+        .fold(0.0, f64::max))
+}
+
+/// A step of the trajectory in which at least one atom moved further than
+/// the threshold given to [`detect_events`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// Index of the frame reached by the step (0 is the first frame, which
+    /// is never an event).
+    pub frame_index: usize,
+    /// `atom_id` of every atom that moved further than the threshold, in
+    /// the earlier frame's atom order.
+    pub atom_ids: Vec<u64>,
+    /// Displacement of each of those atoms over the step.
+    pub displacements: Vec<[f64; 3]>,
+}
+
+/// Iterator adapter returned by [`detect_events`].
+pub struct DetectEvents<I> {
+    frames: I,
+    threshold: f64,
+    options: DisplacementOptions,
+    previous: Option<ConFrame>,
+    index: usize,
+    failed: bool,
+}
+
+/// Yields an [`Event`] for every pair of consecutive frames of `frames` in
+/// which some atom moved more than `threshold`, measured as in
+/// [`displacements`] with the default [`DisplacementOptions`]. Quiet steps
+/// yield nothing; only the previous frame is held in memory. The iterator
+/// ends after the first error, whether reading a frame or comparing it with
+/// the previous one.
+///
+/// # Example
+/// ```
+/// use readcon_core::analysis::detect_events;
+/// use readcon_core::types::ConFrameBuilder;
+/// let frame = |x: f64| {
+///     let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+///     b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+///     b.add_atom("H", x, 1.0, 1.0, [false; 3], 7, 1.008);
+///     b.build()
+/// };
+/// let frames = [1.0, 1.01, 2.5, 2.49].map(|x| Ok(frame(x)));
+/// let events: Vec<_> = detect_events(frames, 0.1).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(events.len(), 1);
+/// assert_eq!((events[0].frame_index, events[0].atom_ids.as_slice()), (2, &[7][..]));
+/// ```
+pub fn detect_events<I>(frames: I, threshold: f64) -> DetectEvents<I::IntoIter>
+where
+    I: IntoIterator<Item = Result<ConFrame, ParseError>>,
+{
+    detect_events_with(frames, threshold, DisplacementOptions::default())
+}
+
+/// [`detect_events`] with explicit `options`.
+pub fn detect_events_with<I>(
+    frames: I,
+    threshold: f64,
+    options: DisplacementOptions,
+) -> DetectEvents<I::IntoIter>
+where
+    I: IntoIterator<Item = Result<ConFrame, ParseError>>,
+{
+    DetectEvents {
+        frames: frames.into_iter(),
+        threshold,
+        options,
+        previous: None,
+        index: 0,
+        failed: false,
+    }
+}
+
+impl<I> Iterator for DetectEvents<I>
+where
+    I: Iterator<Item = Result<ConFrame, ParseError>>,
+{
+    type Item = Result<Event, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            let frame = match self.frames.next()? {
+                Ok(frame) => frame,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            };
+            let frame_index = self.index;
+            self.index += 1;
+            let Some(previous) = self.previous.take() else {
+                self.previous = Some(frame);
+                continue;
+            };
+            let d = match displacements(&previous, &frame, &self.options) {
+                Ok(d) => d,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            };
+            let threshold_sq = self.threshold * self.threshold;
+            let (atom_ids, displacements): (Vec<u64>, Vec<[f64; 3]>) = d
+                .into_iter()
+                .enumerate()
+                .filter(|(_, d)| d.iter().map(|x| x * x).sum::<f64>() > threshold_sq)
+                .map(|(i, d)| (previous.atom_data[i].atom_id, d))
+                .unzip();
+            self.previous = Some(frame);
+            if !atom_ids.is_empty() {
+                return Some(Ok(Event {
+                    frame_index,
+                    atom_ids,
+                    displacements,
+                }));
+            }
+        }
+    }
+}
+
+/// Rigid-body fit found by [`align`]: every atom moved as `R x + t`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alignment {
+    /// Proper rotation matrix `R` (determinant +1).
+    pub rotation: [[f64; 3]; 3],
+    /// Translation `t`, applied after the rotation.
+    pub translation: [f64; 3],
+    /// RMSD over the fitted atoms after the fit.
+    pub rmsd: f64,
+}
+
+/// Superimposes `frame` on `reference`, fitting on the atoms that are not
+/// fixed in `frame` with the default [`DisplacementOptions`]; see
+/// [`align_with`].
+///
+/// # Example
+/// ```
+/// use readcon_core::analysis::{align, rmsd_with, DisplacementOptions};
+/// use readcon_core::types::ConFrameBuilder;
+/// let frame = |shift: f64| {
+///     let mut b = ConFrameBuilder::new([20.0; 3], [90.0; 3]);
+///     b.add_atom("C", 5.0 + shift, 5.0, 5.0, [false; 3], 0, 12.011);
+///     b.add_atom("O", 6.2 + shift, 5.0, 5.0, [false; 3], 1, 15.999);
+///     b.build()
+/// };
+/// let (reference, mut moved) = (frame(0.0), frame(0.7));
+/// let fit = align(&mut moved, &reference).unwrap();
+/// assert!((fit.translation[0] + 0.7).abs() < 1e-9 && fit.rmsd < 1e-9);
+/// let options = DisplacementOptions::default();
+/// assert!(rmsd_with(&moved, &reference, &options).unwrap() < 1e-9);
+/// ```
+pub fn align(frame: &mut ConFrame, reference: &ConFrame) -> Result<Alignment, ParseError> {
+    align_with(
+        frame,
+        reference,
+        |a| !a.is_fixed(),
+        &DisplacementOptions::default(),
+    )
+}
+
+/// Finds the rotation and translation that best superimpose the atoms of
+/// `frame` selected by `fit` on their partners in `reference` (least
+/// squares, unweighted) and applies it to every atom of `frame`.
+///
+/// Atoms are paired as in [`displacements`]; with
+/// [`minimum_image`](DisplacementOptions::minimum_image) each fitted atom
+/// targets the periodic image of its partner nearest to it, so the fit
+/// survives wrapping. Velocities and forces are rotated along with the
+/// positions; the cell is left alone, so rotated atoms may end up outside
+/// it. With fewer than three non-collinear fitted atoms the rotation is
+/// not unique and one of the optimal ones is used.
+///
+/// Errors are those of [`displacements`] and
+/// [`ParseError::ValidationError`] when `fit` selects no atom.
+pub fn align_with<P: FnMut(&AtomDatum) -> bool>(
+    frame: &mut ConFrame,
+    reference: &ConFrame,
+    mut fit: P,
+    options: &DisplacementOptions,
+) -> Result<Alignment, ParseError> {
+    let d = displacements(&*frame, reference, options)?;
+    let (mut p, mut q) = (Vec::new(), Vec::new());
+    for (a, d) in frame.atom_data.iter().zip(&d) {
+        if fit(a) {
+            p.push([a.x, a.y, a.z]);
+            q.push([a.x + d[0], a.y + d[1], a.z + d[2]]);
+        }
+    }
+    if p.is_empty() {
+        return Err(ParseError::ValidationError(
+            "no atoms selected for the fit".into(),
+        ));
+    }
+    let (pc, qc) = (centroid(&p), centroid(&q));
+    let mut s = [[0.0; 3]; 3];
+    for (p, q) in p.iter().zip(&q) {
+        for (j, row) in s.iter_mut().enumerate() {
+            for (k, s) in row.iter_mut().enumerate() {
+                *s += (p[j] - pc[j]) * (q[k] - qc[k]);
+            }
+        }
+    }
+    let rotation = kabsch_rotation(&s);
+    let rc = rotate(&rotation, pc);
+    let translation = [qc[0] - rc[0], qc[1] - rc[1], qc[2] - rc[2]];
+    let sum: f64 = p
+        .iter()
+        .zip(&q)
+        .map(|(p, q)| {
+            let r = rotate(&rotation, *p);
+            (0..3)
+                .map(|k| (r[k] + translation[k] - q[k]).powi(2))
+                .sum::<f64>()
+        })
+        .sum();
+
+    for i in 0..frame.atom_data.len() {
+        let a = &mut frame.atom_data[i];
+        let r = rotate(&rotation, [a.x, a.y, a.z]);
+        a.velocity = a.velocity.map(|v| rotate(&rotation, v));
+        a.force = a.force.map(|f| rotate(&rotation, f));
+        frame.set_position(i, [0, 1, 2].map(|k| r[k] + translation[k]));
+    }
+    let n = frame.atom_data.len();
+    for soa in [&mut frame.velocities, &mut frame.forces] {
+        if soa.nrows() == n {
+            for i in 0..n {
+                soa.set_f64_row(i, rotate(&rotation, soa.as_f64_row(i)));
+            }
+        }
+    }
+    Ok(Alignment {
+        rotation,
+        translation,
+        rmsd: (sum / p.len() as f64).sqrt(),
+    })
+}
+
+fn centroid(points: &[[f64; 3]]) -> [f64; 3] {
+    let n = points.len() as f64;
+    [0, 1, 2].map(|k| points.iter().map(|p| p[k]).sum::<f64>() / n)
+}
+
+fn rotate(r: &[[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    r.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// Rotation `R` maximizing `Σ qᵀ R p` for the covariance `s[j][k] = Σ p_j q_k`
+/// of centered point sets, from the top eigenvector of Horn's 4×4 quaternion
+/// matrix. Unlike an SVD of `s` this always yields a proper rotation.
+fn kabsch_rotation(s: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let [[xx, xy, xz], [yx, yy, yz], [zx, zy, zz]] = *s;
+    let mut n = [
+        [xx + yy + zz, yz - zy, zx - xz, xy - yx],
+        [yz - zy, xx - yy - zz, xy + yx, zx + xz],
+        [zx - xz, xy + yx, -xx + yy - zz, yz + zy],
+        [xy - yx, zx + xz, yz + zy, -xx - yy + zz],
+    ];
+    // Cyclic Jacobi sweeps; the columns of `v` converge to eigenvectors.
+    let mut v = [[0.0; 4]; 4];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    for _ in 0..50 {
+        let off: f64 = (0..4)
+            .flat_map(|i| (i + 1..4).map(move |j| (i, j)))
+            .map(|(i, j)| n[i][j] * n[i][j])
+            .sum();
+        if off < 1e-30 {
+            break;
+        }
+        for i in 0..4 {
+            for j in i + 1..4 {
+                if n[i][j] == 0.0 {
+                    continue;
+                }
+                let theta = (n[j][j] - n[i][i]) / (2.0 * n[i][j]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in n.iter_mut() {
+                    let (a, b) = (row[i], row[j]);
+                    row[i] = c * a - s * b;
+                    row[j] = s * a + c * b;
+                }
+                let (a, b) = (n[i], n[j]);
+                n[i] = std::array::from_fn(|k| c * a[k] - s * b[k]);
+                n[j] = std::array::from_fn(|k| s * a[k] + c * b[k]);
+                for row in v.iter_mut() {
+                    let (a, b) = (row[i], row[j]);
+                    row[i] = c * a - s * b;
+                    row[j] = s * a + c * b;
+                }
+            }
+        }
+    }
+    let top = (0..4)
+        .max_by(|&a, &b| n[a][a].total_cmp(&n[b][b]))
+        .unwrap_or(0);
+    let [w, x, y, z] = [0, 1, 2, 3].map(|k| v[k][top]);
+    [
+        [
+            w * w + x * x - y * y - z * z,
+            2.0 * (x * y - w * z),
+            2.0 * (x * z + w * y),
+        ],
+        [
+            2.0 * (x * y + w * z),
+            w * w - x * x + y * y - z * z,
+            2.0 * (y * z - w * x),
+        ],
+        [
+            2.0 * (x * z - w * y),
+            2.0 * (y * z + w * x),
+            w * w - x * x - y * y + z * z,
+        ],
+    ]
+}
+
+/// Whether `a` and `b` are the same structure within `tolerances`, with
+/// atoms of the same species free to appear in any order.
+///
+/// The frames match when they have the same atom count, agree on the cell
+/// (both absent, or lengths and angles within `tolerances.cell`) and every
+/// atom of `a` has its own partner of the same species in `b` within
+/// `tolerances.coordinate`, measured along the minimum image in `a`'s cell
+/// for the axes its `pbc` metadata marks periodic. Partners are taken
+/// first come, first served, which is exact while the tolerance is below
+/// half the shortest interatomic distance. Atom ids, fixed flags and
+/// metadata are ignored, and so is a rigid shift of the whole structure
+/// (see [`align`] to remove one first). Quadratic in the atom count per
+/// species.
+///
+/// # Example
+/// ```
+/// use readcon_core::analysis::frames_equivalent;
+/// use readcon_core::tolerance::Tolerances;
+/// use readcon_core::types::ConFrameBuilder;
+/// let frame = |h: [f64; 2]| {
+///     let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+///     b.add_atom("H", h[0], 0.0, 0.0, [false; 3], 0, 1.008);
+///     b.add_atom("H", h[1], 0.0, 0.0, [false; 3], 1, 1.008);
+///     b.build()
+/// };
+/// let tol = Tolerances::default().with_coordinate(1e-3);
+/// // Same two sites, listed the other way round and one wrapped.
+/// assert!(frames_equivalent(&frame([1.0, 9.5]), &frame([-0.5, 1.0]), &tol));
+/// assert!(!frames_equivalent(&frame([1.0, 9.5]), &frame([1.0, 9.0]), &tol));
+/// ```
+pub fn frames_equivalent<F: AtomicFrame + ?Sized>(a: &F, b: &F, tolerances: &Tolerances) -> bool {
+    let n = a.natoms();
+    if b.natoms() != n {
+        return false;
+    }
+    let cell = match (a.cell(), b.cell()) {
+        (None, None) => None,
+        (Some(ca), Some(cb)) => {
+            let ((la, aa), (lb, ab)) = (ca.to_lengths_angles(), cb.to_lengths_angles());
+            let mismatch = la
+                .iter()
+                .chain(&aa)
+                .zip(lb.iter().chain(&ab))
+                .any(|(x, y)| (x - y).abs() > tolerances.cell);
+            if mismatch {
+                return false;
+            }
+            Some(ca)
+        }
+        _ => return false,
+    };
+    let pbc = a.pbc();
+    let mut unmatched: HashMap<&str, Vec<usize>> = HashMap::new();
+    for j in 0..n {
+        unmatched.entry(b.species(j)).or_default().push(j);
+    }
+    for i in 0..n {
+        let Some(candidates) = unmatched.get_mut(a.species(i)) else {
+            return false;
+        };
+        let pa = a.position(i);
+        let partner = candidates.iter().position(|&j| {
+            let pb = b.position(j);
+            let d = [pb[0] - pa[0], pb[1] - pa[1], pb[2] - pa[2]];
+            let d = match &cell {
+                Some(cell) => cell.minimum_image(d, pbc),
+                None => d,
+            };
+            d.iter().map(|x| x * x).sum::<f64>().sqrt() <= tolerances.coordinate
+        });
+        match partner {
+            Some(k) => {
+                candidates.swap_remove(k);
+            }
+            None => return false,
+        }
+    }
+    true
+}
+
+/// The distinct structures of `frames`, in order of first appearance: a
+/// frame is dropped when it is [`frames_equivalent`] to one already kept.
+/// Stops at the first frame error. Quadratic in the number of kept frames;
+/// [`dedup_indices`](crate::diff::dedup_indices) is the order-sensitive
+/// counterpart.
+pub fn dedup<I>(frames: I, tolerances: &Tolerances) -> Result<Vec<ConFrame>, ParseError>
+where
+    I: IntoIterator<Item = Result<ConFrame, ParseError>>,
+{
+    let mut kept: Vec<ConFrame> = Vec::new();
+    for frame in frames {
+        let frame = frame?;
+        if !kept
+            .iter()
+            .any(|k| frames_equivalent(k, &frame, tolerances))
+        {
+            kept.push(frame);
+        }
+    }
+    Ok(kept)
+}
+
+/// Boltzmann constant in eV/K.
+const BOLTZMANN_EV: f64 = 8.617_333_262e-5;
+
+/// Per-frame time series from [`trajectory_stats`], one entry per frame.
+///
+/// Lengths are in the first frame's length unit, the temperature in K.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrajectoryStats {
+    /// `time` metadata of each frame, or its position in the trajectory
+    /// when the frame has none.
+    pub time: Vec<f64>,
+    /// Distance of every atom from its first-frame position, in the first
+    /// frame's atom order.
+    pub displacement: Vec<Vec<f64>>,
+    /// Mean-squared displacement over all atoms.
+    pub msd: Vec<f64>,
+    /// Center-of-mass displacement from the first frame.
+    pub com_drift: Vec<[f64; 3]>,
+    /// Instantaneous temperature from the kinetic energy of the free
+    /// (non-fixed) Cartesian components; `None` for frames where some atom
+    /// has no velocity.
+    pub temperature: Vec<Option<f64>>,
+}
+
+impl TrajectoryStats {
+    /// Number of frames.
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    /// Whether no frame was added.
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+}
+
+/// Streaming accumulator behind [`trajectory_stats`].
+#[derive(Debug, Clone)]
+pub struct TrajectoryStatsAccumulator {
+    options: DisplacementOptions,
+    /// First-frame slot of each atom id, with `match_atom_ids`.
+    slot_by_id: HashMap<u64, usize>,
+    masses: Vec<f64>,
+    /// Last wrapped and accumulated unwrapped displacement, per slot.
+    last: Vec<[f64; 3]>,
+    unwrapped: Vec<[f64; 3]>,
+    /// eV per amu·Å²/fs².
+    kinetic_to_ev: f64,
+    stats: TrajectoryStats,
+}
+
+impl TrajectoryStatsAccumulator {
+    /// Accumulator pairing atoms between consecutive frames as `options`
+    /// says (see [`displacements`]).
+    pub fn new(options: DisplacementOptions) -> Self {
+        TrajectoryStatsAccumulator {
+            options,
+            slot_by_id: HashMap::new(),
+            masses: Vec::new(),
+            last: Vec::new(),
+            unwrapped: Vec::new(),
+            kinetic_to_ev: unit_conversion_factor("amu*angstrom^2/fs^2", "eV")
+                .expect("built-in units"),
+            stats: TrajectoryStats::default(),
+        }
+    }
+
+    /// First-frame slot of every atom of `frame`.
+    fn slots(&self, frame: &ConFrame) -> Result<Vec<usize>, ParseError> {
+        let n = frame.natoms();
+        if !self.options.match_atom_ids {
+            return Ok((0..n).collect());
+        }
+        let mut seen = vec![false; n];
+        (0..n)
+            .map(|i| {
+                let id = frame.atom_id(i);
+                let slot = *self.slot_by_id.get(&id).ok_or_else(|| {
+                    ParseError::ValidationError(format!("atom_id {id} missing from first frame"))
+                })?;
+                if std::mem::replace(&mut seen[slot], true) {
+                    return Err(ParseError::ValidationError(format!(
+                        "duplicate atom_id {id} in frame {}",
+                        self.stats.len()
+                    )));
+                }
+                Ok(slot)
+            })
+            .collect()
+    }
+
+    /// Kinetic temperature of `frame`, when every atom has a velocity.
+    fn temperature(&self, frame: &ConFrame) -> Result<Option<f64>, ParseError> {
+        let atoms = &frame.atom_data;
+        if atoms.is_empty() || atoms.iter().any(|a| a.velocity.is_none()) {
+            return Ok(None);
+        }
+        let to_ang_fs = match frame.header.unit_for("velocity") {
+            Some(unit) => unit_conversion_factor(unit, "angstrom/fs")?,
+            None => {
+                frame.conversion_factor_or_assumed("length", "angstrom")?
+                    / frame.conversion_factor_or_assumed("time", "fs")?
+            }
+        };
+        let (mut twice_kinetic, mut dof) = (0.0, 0usize);
+        for (i, atom) in atoms.iter().enumerate() {
+            let (Some(v), Some(m)) = (atom.velocity, frame.mass(i)) else {
+                return Ok(None);
+            };
+            for (c, fixed) in v.iter().zip(atom.fixed) {
+                if !fixed {
+                    twice_kinetic += m * (c * to_ang_fs).powi(2);
+                    dof += 1;
+                }
+            }
+        }
+        Ok((dof > 0).then(|| twice_kinetic * self.kinetic_to_ev / (dof as f64 * BOLTZMANN_EV)))
+    }
+
+    /// Adds the next frame.
+    ///
+    /// Errors are [`ParseError::ValidationError`] when the atom count
+    /// changes, for duplicate or unmatched `atom_id`s (with
+    /// [`match_atom_ids`](DisplacementOptions::match_atom_ids)), and, with
+    /// [`minimum_image`](DisplacementOptions::minimum_image), for a
+    /// degenerate cell.
+    pub fn add_frame(&mut self, frame: &ConFrame) -> Result<(), ParseError> {
+        let n = frame.natoms();
+        if self.stats.is_empty() {
+            if self.options.match_atom_ids {
+                for i in 0..n {
+                    if self.slot_by_id.insert(frame.atom_id(i), i).is_some() {
+                        return Err(ParseError::ValidationError(format!(
+                            "duplicate atom_id {} in first frame",
+                            frame.atom_id(i)
+                        )));
+                    }
+                }
+            }
+            self.masses = (0..n).map(|i| frame.mass(i).unwrap_or(1.0)).collect();
+            self.last = (0..n).map(|i| frame.position(i)).collect();
+            self.unwrapped = vec![[0.0; 3]; n];
+        } else if n != self.last.len() {
+            return Err(ParseError::ValidationError(format!(
+                "frame {} has {n} atoms, the first frame {}",
+                self.stats.len(),
+                self.last.len()
+            )));
+        }
+        let mic = if self.options.minimum_image {
+            let cell = frame.cell().ok_or_else(|| {
+                ParseError::ValidationError("minimum image needs a non-degenerate cell".into())
+            })?;
+            Some((cell, frame.pbc()))
+        } else {
+            None
+        };
+        let temperature = self.temperature(frame)?;
+        for (i, slot) in self.slots(frame)?.into_iter().enumerate() {
+            let p = frame.position(i);
+            let prev = self.last[slot];
+            let step = [p[0] - prev[0], p[1] - prev[1], p[2] - prev[2]];
+            let step = match &mic {
+                Some((cell, pbc)) => cell.minimum_image(step, *pbc),
+                None => step,
+            };
+            for (u, s) in self.unwrapped[slot].iter_mut().zip(step) {
+                *u += s;
+            }
+            self.last[slot] = p;
+        }
+
+        let norms: Vec<f64> = self
+            .unwrapped
+            .iter()
+            .map(|u| u.iter().map(|x| x * x).sum::<f64>().sqrt())
+            .collect();
+        let msd = if n == 0 {
+            0.0
+        } else {
+            norms.iter().map(|d| d * d).sum::<f64>() / n as f64
+        };
+        let total_mass: f64 = self.masses.iter().sum();
+        let mut com = [0.0; 3];
+        if total_mass > 0.0 {
+            for (u, m) in self.unwrapped.iter().zip(&self.masses) {
+                for k in 0..3 {
+                    com[k] += m * u[k] / total_mass;
+                }
+            }
+        }
+        let index = self.stats.len();
+        self.stats
+            .time
+            .push(frame.header.time().unwrap_or(index as f64));
+        self.stats.displacement.push(norms);
+        self.stats.msd.push(msd);
+        self.stats.com_drift.push(com);
+        self.stats.temperature.push(temperature);
+        Ok(())
+    }
+
+    /// The series so far.
+    pub fn stats(&self) -> &TrajectoryStats {
+        &self.stats
+    }
+
+    /// The finished series.
+    pub fn finish(self) -> TrajectoryStats {
+        self.stats
+    }
+}
+
+/// Per-frame displacement, drift and temperature series over all `frames`,
+/// e.g. a [`ConFrameIterator`](crate::iterators::ConFrameIterator); stops
+/// at the first frame error. See [`TrajectoryStatsAccumulator`].
+///
+/// # Example
+/// ```
+/// use readcon_core::analysis::{DisplacementOptions, trajectory_stats};
+/// use readcon_core::iterators::ConFrameIterator;
+/// let text = std::fs::read_to_string(concat!(
+///     env!("CARGO_MANIFEST_DIR"),
+///     "/resources/test/tiny_multi_cuh2.con"
+/// ))
+/// .unwrap();
+/// let stats = trajectory_stats(ConFrameIterator::new(&text), DisplacementOptions::default())
+///     .unwrap();
+/// assert_eq!(stats.msd[0], 0.0);
+/// assert_eq!(stats.displacement.len(), stats.len());
+/// ```
+pub fn trajectory_stats<I>(
+    frames: I,
+    options: DisplacementOptions,
+) -> Result<TrajectoryStats, ParseError>
+where
+    I: IntoIterator<Item = Result<ConFrame, ParseError>>,
+{
+    let mut acc = TrajectoryStatsAccumulator::new(options);
+    for frame in frames {
+        acc.add_frame(&frame?)?;
+    }
+    Ok(acc.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    /// Simple cubic lattice, spacing 2, rock-salt species ordering.
+    fn rock_salt(n: usize) -> ConFrame {
+        let a = 2.0;
+        let mut b = ConFrameBuilder::new([a * n as f64; 3], [90.0; 3]);
+        let mut id = 0;
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    let sym = if (i + j + k) % 2 == 0 { "Na" } else { "Cl" };
+                    let mass = if sym == "Na" { 22.99 } else { 35.45 };
+                    b.add_atom(
+                        sym,
+                        i as f64 * a,
+                        j as f64 * a,
+                        k as f64 * a,
+                        [false; 3],
+                        id,
+                        mass,
+                    );
+                    id += 1;
+                }
+            }
+        }
+        b.build()
+    }
+
+    /// Neighbors per central atom within `r_max`, from integrating g(r).
+    fn coordination(g: &Rdf, partners_per_volume: f64) -> f64 {
+        let w = g.bin_width();
+        g.r.iter()
+            .zip(&g.g)
+            .map(|(r, g)| {
+                let (lo, hi) = (r - w / 2.0, r + w / 2.0);
+                g * partners_per_volume * 4.0 / 3.0
+                    * std::f64::consts::PI
+                    * (hi.powi(3) - lo.powi(3))
+            })
+            .sum()
+    }
+
+    #[test]
+    fn rock_salt_shells() {
+        let frame = rock_salt(6);
+        let volume = 12.0f64.powi(3);
+        let frames = vec![Ok(frame.clone()), Ok(frame)];
+        let g = rdf(frames, &[("Na", "Cl"), ("Na", "Na")], 3.0, 30).unwrap();
+        assert_eq!(g[0].nframes, 2);
+
+        // Six unlike neighbors at 2.0, twelve like ones at 2.83.
+        assert!((coordination(&g[0], 108.0 / volume) - 6.0).abs() < 1e-9);
+        assert!((coordination(&g[1], 107.0 / volume) - 12.0).abs() < 1e-9);
+        let peak = g[0]
+            .g
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert!((g[0].r[peak] - 2.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn absent_species_and_bad_options() {
+        let g = rdf(vec![Ok(rock_salt(4))], &[("Na", "K")], 3.0, 10).unwrap();
+        assert_eq!(g[0].nframes, 0);
+        assert!(g[0].g.iter().all(|x| *x == 0.0));
+        assert!(RdfAccumulator::new(&[], 3.0, 10).is_err());
+        assert!(RdfAccumulator::new(&[("Na", "Cl")], -1.0, 10).is_err());
+        assert!(
+            rdf(
+                vec![Err::<ConFrame, _>(ParseError::IncompleteFrame)],
+                &[("Na", "Cl")],
+                3.0,
+                10
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn displacement_matches_ids_and_minimum_image() {
+        let frame = |atoms: &[(u64, f64)]| {
+            let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+            for &(id, x) in atoms {
+                b.add_atom("Ar", x, 5.0, 5.0, [false; 3], id, 39.95);
+            }
+            b.build()
+        };
+        let a = frame(&[(0, 1.0), (1, 9.9)]);
+        let b = frame(&[(1, 0.1), (0, 1.0)]);
+        let by_id = DisplacementOptions {
+            match_atom_ids: true,
+            ..DisplacementOptions::default()
+        };
+        assert!((max_displacement_with(&a, &b, &by_id).unwrap() - 0.2).abs() < 1e-9);
+        assert!((rmsd_with(&a, &b, &by_id).unwrap() - 0.2 / 2f64.sqrt()).abs() < 1e-9);
+
+        // By index, without MIC: 1.0 -> 0.1 and 9.9 -> 1.0.
+        let plain = DisplacementOptions {
+            minimum_image: false,
+            ..DisplacementOptions::default()
+        };
+        assert!((max_displacement_with(&a, &b, &plain).unwrap() - 8.9).abs() < 1e-9);
+        assert!((max_displacement(&a, &b).unwrap() - 1.1).abs() < 1e-9);
+        assert_eq!(rmsd(&a, &a).unwrap(), 0.0);
+
+        assert!(rmsd_with(&a, &frame(&[(0, 1.0), (2, 1.0)]), &by_id).is_err());
+        assert!(rmsd_with(&frame(&[(0, 1.0), (0, 2.0)]), &b, &by_id).is_err());
+        assert!(rmsd(&a, &frame(&[(0, 1.0)])).is_err());
+    }
+
+    #[test]
+    fn trajectory_stats_unwraps_and_measures_temperature() {
+        let frame = |t: f64, atoms: &[(u64, f64)], vel: Option<f64>| {
+            let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+            for &(id, x) in atoms {
+                b.add_atom("Ar", x, 5.0, 5.0, [false, false, id == 1], id, 39.95);
+                if let Some(v) = vel {
+                    b.with_velocity([v, 0.0, 0.0]);
+                }
+            }
+            b.set_time(t);
+            b.build()
+        };
+        // Atom 1 walks +0.4 per frame across the x face; atom 0 stays put.
+        let frames = vec![
+            Ok(frame(0.0, &[(0, 1.0), (1, 9.6)], None)),
+            Ok(frame(2.0, &[(1, 0.0), (0, 1.0)], Some(0.01))),
+            Ok(frame(4.0, &[(0, 1.0), (1, 0.4)], Some(0.01))),
+        ];
+        let by_id = DisplacementOptions {
+            match_atom_ids: true,
+            ..DisplacementOptions::default()
+        };
+        let stats = trajectory_stats(frames, by_id).unwrap();
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats.time, vec![0.0, 2.0, 4.0]);
+        assert!((stats.displacement[2][1] - 0.8).abs() < 1e-9);
+        assert_eq!(stats.displacement[2][0], 0.0);
+        assert!((stats.msd[2] - 0.32).abs() < 1e-9);
+        assert!((stats.com_drift[2][0] - 0.4).abs() < 1e-9);
+        assert_eq!(stats.temperature[0], None);
+        // 2 atoms at 0.01 Å/fs along x, 5 free components.
+        let ke = 39.95 * 1e-4 * unit_conversion_factor("amu*angstrom^2/fs^2", "eV").unwrap();
+        let t = stats.temperature[1].unwrap();
+        assert!(
+            (t - 2.0 * ke / (5.0 * BOLTZMANN_EV)).abs() < 1e-9 * t,
+            "{t}"
+        );
+
+        let mut acc = TrajectoryStatsAccumulator::new(DisplacementOptions::default());
+        acc.add_frame(&frame(0.0, &[(0, 1.0)], None)).unwrap();
+        assert!(
+            acc.add_frame(&frame(1.0, &[(0, 1.0), (1, 2.0)], None))
+                .is_err()
+        );
+        let mut acc = TrajectoryStatsAccumulator::new(by_id);
+        acc.add_frame(&frame(0.0, &[(0, 1.0)], None)).unwrap();
+        assert!(acc.add_frame(&frame(1.0, &[(3, 1.0)], None)).is_err());
+        assert!(
+            trajectory_stats(
+                vec![Err::<ConFrame, _>(ParseError::IncompleteFrame)],
+                DisplacementOptions::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn align_recovers_rigid_motion_on_free_atoms() {
+        let points = [
+            [1.0, 0.2, -0.3],
+            [-0.4, 1.1, 0.5],
+            [0.3, -0.9, 1.2],
+            [-1.2, -0.1, -0.8],
+        ];
+        let build = |r: &[[f64; 3]; 3], t: [f64; 3]| {
+            let mut b = ConFrameBuilder::new([30.0; 3], [90.0; 3]);
+            // The fixed atom stays put, so it must not enter the fit.
+            b.add_atom("Cu", 1.0, 1.0, 1.0, [true; 3], 0, 63.546);
+            for (i, p) in points.iter().enumerate() {
+                let x = rotate(r, *p);
+                b.add_atom(
+                    "H",
+                    x[0] + t[0],
+                    x[1] + t[1],
+                    x[2] + t[2],
+                    [false; 3],
+                    i as u64 + 1,
+                    1.008,
+                )
+                .with_velocity(rotate(r, [0.1, 0.0, 0.0]));
+            }
+            b.build()
+        };
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let (c, s) = (0.6f64, 0.8f64);
+        let turn = [[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]];
+        let reference = build(&identity, [15.0, 15.0, 15.0]);
+        // Shifted across the periodic face in x.
+        let mut moved = build(&turn, [28.5, 14.0, 16.0]);
+
+        let fit = align(&mut moved, &reference).unwrap();
+        assert!(fit.rmsd < 1e-9, "{fit:?}");
+        for (row, expected) in fit.rotation.iter().zip([[c, s, 0.0], [-s, c, 0.0]]) {
+            assert!(row.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-9));
+        }
+        let options = DisplacementOptions::default();
+        let after = displacements(&moved, &reference, &options).unwrap();
+        assert!(after[1..].iter().flatten().all(|d| d.abs() < 1e-9));
+        let v = moved.atom_data[2].velocity.unwrap();
+        assert!((v[0] - 0.1).abs() < 1e-12 && v[1].abs() < 1e-12);
+        assert_eq!(moved.positions.as_f64_row(3)[2], moved.atom_data[3].z);
+
+        // A mirror image cannot be reached by a proper rotation.
+        let mirror = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]];
+        let mut mirrored = build(&mirror, [15.0, 15.0, 15.0]);
+        let fit = align(&mut mirrored, &reference).unwrap();
+        let r = fit.rotation;
+        let det = r[0][0] * (r[1][1] * r[2][2] - r[1][2] * r[2][1])
+            - r[0][1] * (r[1][0] * r[2][2] - r[1][2] * r[2][0])
+            + r[0][2] * (r[1][0] * r[2][1] - r[1][1] * r[2][0]);
+        assert!((det - 1.0).abs() < 1e-9 && fit.rmsd > 0.1);
+
+        assert!(align_with(&mut mirrored, &reference, |_| false, &options).is_err());
+    }
+
+    #[test]
+    fn equivalence_ignores_order_and_wrapping() {
+        let build = |atoms: &[(&str, [f64; 3])]| {
+            let mut b = ConFrameBuilder::new([8.0; 3], [90.0; 3]);
+            for (id, (sym, p)) in atoms.iter().enumerate() {
+                let mass = if *sym == "Cu" { 63.546 } else { 1.008 };
+                b.add_atom(sym, p[0], p[1], p[2], [false; 3], id as u64, mass);
+            }
+            b.build()
+        };
+        let a = build(&[
+            ("Cu", [0.0, 0.0, 0.0]),
+            ("Cu", [2.0, 2.0, 0.0]),
+            ("H", [1.0, 1.0, 1.0]),
+            ("H", [7.5, 1.0, 1.0]),
+        ]);
+        let shuffled = build(&[
+            ("Cu", [2.0, 2.0, 0.0]),
+            ("Cu", [8.0, 0.0, 8.0]),
+            ("H", [-0.5, 1.0, 1.0]),
+            ("H", [1.0, 1.0, 1.0]),
+        ]);
+        let moved = build(&[
+            ("Cu", [0.0, 0.0, 0.0]),
+            ("Cu", [2.0, 2.0, 0.0]),
+            ("H", [1.0, 1.0, 1.2]),
+            ("H", [7.5, 1.0, 1.0]),
+        ]);
+        let swapped = build(&[
+            ("Cu", [0.0, 0.0, 0.0]),
+            ("Cu", [1.0, 1.0, 1.0]),
+            ("H", [2.0, 2.0, 0.0]),
+            ("H", [7.5, 1.0, 1.0]),
+        ]);
+        let tol = Tolerances::default().with_coordinate(1e-3);
+        assert!(frames_equivalent(&a, &shuffled, &tol));
+        assert!(!frames_equivalent(&a, &moved, &tol));
+        assert!(frames_equivalent(&a, &moved, &tol.with_coordinate(0.3)));
+        assert!(!frames_equivalent(&a, &swapped, &tol));
+
+        let mut open = shuffled.clone();
+        open.header.set_pbc([false; 3]);
+        assert!(!frames_equivalent(&open, &a, &tol));
+        let mut bigger = shuffled.clone();
+        bigger.header.boxl[0] = 8.5;
+        assert!(!frames_equivalent(&a, &bigger, &tol));
+
+        let trajectory = [a.clone(), shuffled, moved.clone(), a].map(Ok);
+        let kept = dedup(trajectory, &tol).unwrap();
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[1].atom_data[2].z, moved.atom_data[2].z);
+    }
+
+    #[test]
+    fn coordination_finds_adatom_and_vacancy_neighbors() {
+        let mut slab = rock_salt(4);
+        let cutoffs = [("Na", "Cl", 2.2), ("Na", "Na", 1.0), ("Cl", "Cl", 1.0)];
+        assert!(
+            coordination_numbers(&slab, &cutoffs)
+                .unwrap()
+                .iter()
+                .all(|&c| c == 6)
+        );
+
+        // Remove atom 0 (Na at the origin): its six Cl neighbors drop to 5.
+        slab = slab
+            .select_indices(&(1..slab.atom_data.len()).collect::<Vec<_>>())
+            .unwrap();
+        let cn = coordination_numbers(&slab, &cutoffs).unwrap();
+        let near_vacancy = slab
+            .atom_data
+            .iter()
+            .zip(&cn)
+            .filter(|(a, _)| {
+                let d = [a.x, a.y, a.z].map(|x: f64| x.min(8.0 - x));
+                (d.iter().map(|x| x * x).sum::<f64>() - 4.0).abs() < 1e-9
+            })
+            .map(|(_, &c)| c)
+            .collect::<Vec<_>>();
+        assert_eq!(near_vacancy, vec![5; 6]);
+        assert_eq!(
+            cn.iter().filter(|&&c| c == 6).count(),
+            slab.atom_data.len() - 6
+        );
+
+        // Species order in an entry does not matter; unknown pairs never count.
+        let flipped = coordination_numbers(&slab, &[("Cl", "Na", 2.2)]).unwrap();
+        assert_eq!(flipped, cn);
+        assert!(
+            coordination_numbers(&slab, &[("K", "Cl", 3.0)])
+                .unwrap()
+                .iter()
+                .all(|&c| c == 0)
+        );
+        assert!(coordination_numbers(&slab, &[]).is_err());
+        assert!(coordination_numbers(&slab, &[("Na", "Cl", -1.0)]).is_err());
+    }
+
+    #[test]
+    fn events_report_moved_atoms_between_consecutive_frames() {
+        let frame = |h: [f64; 2]| {
+            let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+            b.add_atom("H", h[0], 5.0, 5.0, [false; 3], 3, 1.008);
+            b.add_atom("H", h[1], 5.0, 5.0, [false; 3], 9, 1.008);
+            b.build()
+        };
+        let frames = vec![
+            Ok(frame([1.0, 5.0])),
+            Ok(frame([1.05, 5.0])),
+            Ok(frame([2.0, 9.9])),
+            // 9.9 -> 0.2 is a 0.3 step through the face: no event.
+            Ok(frame([2.0, 0.2])),
+            Ok(frame([2.0, 0.2])),
+            Err(ParseError::IncompleteFrame),
+            Ok(frame([5.0, 5.0])),
+        ];
+        let mut events = detect_events(frames, 0.5);
+        let first = events.next().unwrap().unwrap();
+        assert_eq!(first.frame_index, 2);
+        assert_eq!(first.atom_ids, vec![3, 9]);
+        assert!((first.displacements[0][0] - 0.95).abs() < 1e-12);
+        assert!((first.displacements[1][0] - 4.9).abs() < 1e-12);
+        assert!(events.next().unwrap().is_err());
+        assert!(events.next().is_none());
+
+        let plain = DisplacementOptions {
+            minimum_image: false,
+            ..DisplacementOptions::default()
+        };
+        let frames = [frame([2.0, 9.9]), frame([2.0, 0.2])].map(Ok);
+        let events: Vec<_> = detect_events_with(frames, 0.5, plain)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(events[0].atom_ids, vec![9]);
+        assert!((events[0].displacements[0][0] + 9.7).abs() < 1e-12);
+    }
+}
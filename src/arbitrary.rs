@@ -0,0 +1,135 @@
+//! [`proptest`] strategies for [`ConFrame`] (feature `proptest`).
+//!
+//! `any::<ConFrame>()` yields frames that the writer can serialize and the
+//! parser can read back: one to four species, finite coordinates, optional
+//! velocity and force sections, and free-form header lines. Downstream crates
+//! can use it to property-test their own `.con` handling; the round-trip
+//! suite in `tests/roundtrip_props.rs` runs on it.
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use readcon_core::types::ConFrame;
+//!
+//! proptest!(|(frame in any::<ConFrame>())| {
+//!     prop_assert!(!frame.atom_data.is_empty());
+//! });
+//! ```
+
+use crate::types::{ConFrame, ConFrameBuilder};
+use proptest::prelude::*;
+
+/// Size limits for `any_with::<ConFrame>(..)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameParams {
+    /// Upper bound on the number of atoms (at least one is always generated).
+    pub max_atoms: usize,
+    /// Upper bound on the number of distinct species, at most 8.
+    pub max_species: usize,
+}
+
+impl Default for FrameParams {
+    fn default() -> Self {
+        FrameParams {
+            max_atoms: 16,
+            max_species: 4,
+        }
+    }
+}
+
+const SPECIES: [(&str, f64); 8] = [
+    ("H", 1.008),
+    ("C", 12.011),
+    ("N", 14.007),
+    ("O", 15.999),
+    ("Si", 28.085),
+    ("Fe", 55.845),
+    ("Cu", 63.546),
+    ("Pt", 195.084),
+];
+
+/// Free-form header text: printable ASCII without leading or trailing
+/// whitespace, since the parser does not promise to keep either.
+fn header_line() -> impl Strategy<Value = String> {
+    "([!-~]([ -~]{0,30}[!-~])?)?"
+}
+
+/// Per-direction fixed flags the format can express: bitmask 1 is read as
+/// the legacy "all fixed", so x-only is written as all three.
+fn fixed_flags() -> impl Strategy<Value = [bool; 3]> {
+    any::<[bool; 3]>().prop_map(|f| {
+        if f == [true, false, false] {
+            [true; 3]
+        } else {
+            f
+        }
+    })
+}
+
+fn vector(bound: f64) -> impl Strategy<Value = [f64; 3]> {
+    [-bound..bound, -bound..bound, -bound..bound]
+}
+
+/// One generated atom: species index, position, fixed flags, id, velocity,
+/// force.
+type AtomParts = (usize, [f64; 3], [bool; 3], u64, [f64; 3], [f64; 3]);
+
+impl Arbitrary for ConFrame {
+    type Parameters = FrameParams;
+    type Strategy = BoxedStrategy<ConFrame>;
+
+    fn arbitrary_with(params: FrameParams) -> Self::Strategy {
+        let max_species = params.max_species.clamp(1, SPECIES.len());
+        let max_atoms = params.max_atoms.max(1);
+        let cell = [1.0..200.0, 1.0..200.0, 1.0..200.0];
+        let angles = [60.0..120.0, 60.0..120.0, 60.0..120.0];
+        let species = proptest::sample::subsequence(SPECIES.to_vec(), 1..=max_species);
+        (
+            cell,
+            angles,
+            (header_line(), header_line(), header_line()),
+            species,
+            any::<(bool, bool)>(),
+        )
+            .prop_flat_map(move |(cell, angles, headers, species, sections)| {
+                let atom = (
+                    0..species.len(),
+                    vector(1.0e3),
+                    fixed_flags(),
+                    any::<u64>(),
+                    vector(1.0e2),
+                    vector(1.0e2),
+                );
+                let atoms = proptest::collection::vec(atom, 1..=max_atoms);
+                (Just((cell, angles, headers, species, sections)), atoms)
+            })
+            .prop_map(|((cell, angles, headers, species, sections), atoms)| {
+                build_frame(cell, angles, headers, &species, sections, &atoms)
+            })
+            .boxed()
+    }
+}
+
+fn build_frame(
+    cell: [f64; 3],
+    angles: [f64; 3],
+    (prebox, postbox0, postbox1): (String, String, String),
+    species: &[(&str, f64)],
+    (velocities, forces): (bool, bool),
+    atoms: &[AtomParts],
+) -> ConFrame {
+    let mut builder = ConFrameBuilder::new(cell, angles);
+    builder
+        .prebox_header(prebox)
+        .postbox_header([postbox0, postbox1]);
+    for &(s, [x, y, z], fixed, id, velocity, force) in atoms {
+        let (symbol, mass) = species[s];
+        builder.add_atom(symbol, x, y, z, fixed, id, mass);
+        if velocities {
+            builder.with_velocity(velocity);
+        }
+        if forces {
+            builder.with_force(force);
+        }
+    }
+    builder.build()
+}
@@ -0,0 +1,504 @@
+//! eOn NEB bands as a single value.
+//!
+//! eOn stores a nudged-elastic-band path either as one multi-frame file
+//! (`neb.con`, or one `neb_path_NNN.con` snapshot per optimizer iteration)
+//! or as one image per file in a directory. [`Band`] loads both layouts into
+//! an ordered list of images with per-image energies taken from the
+//! `energy` metadata key, and writes them back with `neb_bead` stamped on
+//! each image.
+//!
+//! [`interpolate`] and [`interpolate_with`] seed a new band from its two
+//! endpoints, linearly or with the image-dependent pair potential (IDPP).
+
+use crate::analysis::{DisplacementOptions, displacements};
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::frame::AtomicFrame;
+use crate::iterators;
+use crate::types::{ConFrame, con_frame_from_atom_data, meta};
+use crate::writer::ConFrameWriter;
+use std::path::{Path, PathBuf};
+
+/// Ordered NEB images, reactant first.
+#[derive(Debug, Clone, Default)]
+pub struct Band {
+    /// One frame per image.
+    pub images: Vec<ConFrame>,
+    /// Per-image total energy (`metadata["energy"]`), `None` when absent.
+    pub energies: Vec<Option<f64>>,
+}
+
+impl Band {
+    /// Wraps `images`, reading each image's energy from its metadata.
+    pub fn from_frames(images: Vec<ConFrame>) -> Self {
+        let energies = images.iter().map(|f| f.header.energy()).collect();
+        Band { images, energies }
+    }
+
+    /// Reads a multi-frame band file (`neb.con`, `neb_path_003.con`, ...).
+    pub fn read(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::from_frames(iterators::read_all_frames(path)?))
+    }
+
+    /// Reads an image-per-file band: every `<prefix>*.con` (optionally
+    /// `.gz` / `.zst`) in `dir`, ordered by the integer after `prefix`
+    /// (`image_2.con` before `image_10.con`). Only the first frame of each
+    /// file is used. Files whose suffix is not an integer are ignored.
+    pub fn read_dir(dir: &Path, prefix: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut found: Vec<(u64, PathBuf)> = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(rest) = name.strip_prefix(prefix) else {
+                continue;
+            };
+            let stem = [".con.gz", ".con.zst", ".con"]
+                .iter()
+                .find_map(|ext| rest.strip_suffix(ext));
+            if let Some(index) = stem.and_then(|s| s.parse::<u64>().ok()) {
+                found.push((index, path));
+            }
+        }
+        if found.is_empty() {
+            return Err(format!("no {prefix}*.con images in {}", dir.display()).into());
+        }
+        found.sort();
+        let images = found
+            .iter()
+            .map(|(_, p)| iterators::read_first_frame(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_frames(images))
+    }
+
+    /// Number of images.
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// True when the band has no images.
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// Energies relative to the first image; `None` where either is missing.
+    pub fn relative_energies(&self) -> Vec<Option<f64>> {
+        let e0 = self.energies.first().copied().flatten();
+        self.energies.iter().map(|e| Some((*e)? - e0?)).collect()
+    }
+
+    /// Index and relative energy of the highest image (the climbing-image
+    /// saddle estimate). `None` unless every image has an energy.
+    pub fn highest_image(&self) -> Option<(usize, f64)> {
+        let rel: Option<Vec<f64>> = self.relative_energies().into_iter().collect();
+        rel?.into_iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Cumulative Cartesian path length at each image (0 at the reactant),
+    /// the usual reaction coordinate for plotting and re-interpolation.
+    /// Atoms are paired by `atom_data` index; no minimum image is applied.
+    pub fn path_coordinate(&self) -> Vec<f64> {
+        let mut out = Vec::with_capacity(self.images.len());
+        let mut s = 0.0;
+        for (k, image) in self.images.iter().enumerate() {
+            if k > 0 {
+                s += self.images[k - 1]
+                    .atom_data
+                    .iter()
+                    .zip(&image.atom_data)
+                    .map(|(a, b)| {
+                        let d = [b.x - a.x, b.y - a.y, b.z - a.z];
+                        d[0] * d[0] + d[1] * d[1] + d[2] * d[2]
+                    })
+                    .sum::<f64>()
+                    .sqrt();
+            }
+            out.push(s);
+        }
+        out
+    }
+
+    /// Images with `neb_bead` set to their index and `energy` synced from
+    /// [`Self::energies`]. A blank comment line is replaced by
+    /// `NEB image <k>`: without a `sections` declaration, a blank line after
+    /// the coordinates reads back as a velocity separator.
+    fn stamped_images(&self) -> Vec<ConFrame> {
+        self.images
+            .iter()
+            .enumerate()
+            .map(|(k, image)| {
+                let mut image = image.clone();
+                image.header.set_neb_bead(k as u64);
+                if image.header.prebox_header.user.trim().is_empty() {
+                    image.header.prebox_header.user = format!("NEB image {k}");
+                }
+                if let Some(Some(e)) = self.energies.get(k) {
+                    image.header.set_energy(*e);
+                }
+                image
+            })
+            .collect()
+    }
+
+    /// Writes every image to one multi-frame file.
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let mut writer = ConFrameWriter::from_path(path)?;
+        writer.extend(self.stamped_images().iter())?;
+        writer.flush()
+    }
+
+    /// Writes one `<prefix><NNN>.con` per image into `dir` (created if
+    /// missing) and returns the paths in image order. The index is
+    /// zero-padded to three digits, eOn's convention.
+    pub fn write_dir(&self, dir: &Path, prefix: &str) -> std::io::Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(dir)?;
+        let mut paths = Vec::with_capacity(self.images.len());
+        for (k, image) in self.stamped_images().iter().enumerate() {
+            let path = dir.join(format!("{prefix}{k:03}.con"));
+            let mut writer = ConFrameWriter::from_path(&path)?;
+            writer.write_frame(image)?;
+            writer.flush()?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// How [`interpolate_with`] places the intermediate images.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Evenly spaced on the straight line between the endpoints.
+    #[default]
+    Linear,
+    /// The linear path relaxed, image by image, on the image-dependent pair
+    /// potential of Smidstrup et al., J. Chem. Phys. 140, 214106 (2014):
+    /// every interatomic distance is pulled towards the interpolation of
+    /// its endpoint values, so atoms go around each other instead of
+    /// through. Costs O(N²) per step in the number of atoms.
+    Idpp,
+}
+
+/// `n_images` frames evenly spaced on the straight line from `a` to `b`,
+/// endpoints excluded; see [`interpolate_with`].
+///
+/// # Example
+/// ```
+/// use readcon_core::band::interpolate;
+/// use readcon_core::types::ConFrameBuilder;
+/// let frame = |x: f64| {
+///     let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+///     b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+///     b.add_atom("H", x, 1.0, 1.0, [false; 3], 1, 1.008);
+///     b.build()
+/// };
+/// let images = interpolate(&frame(1.0), &frame(3.0), 3).unwrap();
+/// let x: Vec<f64> = images.iter().map(|f| f.atom_data[1].x).collect();
+/// assert_eq!(x, vec![1.5, 2.0, 2.5]);
+/// ```
+pub fn interpolate(
+    a: &ConFrame,
+    b: &ConFrame,
+    n_images: usize,
+) -> Result<Vec<ConFrame>, ParseError> {
+    interpolate_with(a, b, n_images, Interpolation::Linear)
+}
+
+/// `n_images` intermediate frames from `a` to `b` (endpoints excluded)
+/// placed by `method`, ready to sit between the endpoints of a [`Band`].
+///
+/// Atoms are paired by index and each one travels along the minimum-image
+/// displacement when `b` has a cell, so a path crossing a periodic face
+/// stays short (images are not wrapped back into the cell). Components
+/// fixed in `a` keep `a`'s value in every image. Images are copies of `a`
+/// without velocities, forces, per-atom energies or the `energy` metadata
+/// key, none of which describe the new geometry.
+///
+/// Errors are those of [`displacements`] and
+/// [`ParseError::ValidationError`] when the endpoints disagree on an atom's
+/// species.
+pub fn interpolate_with(
+    a: &ConFrame,
+    b: &ConFrame,
+    n_images: usize,
+    method: Interpolation,
+) -> Result<Vec<ConFrame>, ParseError> {
+    let options = DisplacementOptions {
+        match_atom_ids: false,
+        minimum_image: b.header.cell().is_some(),
+    };
+    let mut d = displacements(a, b, &options)?;
+    for (i, (x, y)) in a.atom_data.iter().zip(&b.atom_data).enumerate() {
+        if a.symbol_of(x) != b.symbol_of(y) {
+            return Err(ParseError::ValidationError(format!(
+                "atom {i} is {} in the first frame and {} in the second",
+                a.symbol_of(x),
+                b.symbol_of(y)
+            )));
+        }
+        for (dk, fixed) in d[i].iter_mut().zip(x.fixed) {
+            if fixed {
+                *dk = 0.0;
+            }
+        }
+    }
+    let start: Vec<[f64; 3]> = (0..a.natoms()).map(|i| a.position(i)).collect();
+    let end: Vec<[f64; 3]> = start
+        .iter()
+        .zip(&d)
+        .map(|(p, d)| [p[0] + d[0], p[1] + d[1], p[2] + d[2]])
+        .collect();
+    let fixed: Vec<[bool; 3]> = a.atom_data.iter().map(|x| x.fixed).collect();
+    let mic = a.header.cell().map(|c| (c, AtomicFrame::pbc(a)));
+
+    let mut header = a.header.clone();
+    header.metadata.remove(meta::ENERGY);
+    let mut atoms = a.atom_data.clone();
+    for atom in &mut atoms {
+        atom.velocity = None;
+        atom.force = None;
+        atom.energy = None;
+    }
+    let mut images = Vec::with_capacity(n_images);
+    for k in 1..=n_images {
+        let t = k as f64 / (n_images + 1) as f64;
+        let mut pos: Vec<[f64; 3]> = start
+            .iter()
+            .zip(&end)
+            .map(|(p, q)| [0, 1, 2].map(|c| p[c] + t * (q[c] - p[c])))
+            .collect();
+        if method == Interpolation::Idpp {
+            idpp_relax(&mut pos, &start, &end, t, &fixed, mic.as_ref());
+        }
+        for (atom, p) in atoms.iter_mut().zip(&pos) {
+            [atom.x, atom.y, atom.z] = *p;
+        }
+        let mut image =
+            con_frame_from_atom_data(header.clone(), a.symbol_table.clone(), atoms.clone());
+        image.properties = a.properties.clone();
+        images.push(image);
+    }
+    Ok(images)
+}
+
+/// Steepest-descent steps at most this long (Å) per coordinate.
+const IDPP_MAX_MOVE: f64 = 0.1;
+/// Relaxation stops once no coordinate moves more than this (Å).
+const IDPP_TOLERANCE: f64 = 1e-7;
+const IDPP_MAX_STEPS: usize = 5000;
+
+/// `p - q`, along the minimum image when a cell is given.
+fn separation(p: [f64; 3], q: [f64; 3], mic: Option<&(Cell, [bool; 3])>) -> [f64; 3] {
+    let v = [p[0] - q[0], p[1] - q[1], p[2] - q[2]];
+    match mic {
+        Some((cell, pbc)) => cell.minimum_image(v, *pbc),
+        None => v,
+    }
+}
+
+fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+/// IDPP objective `Σ (dᵢⱼ* − dᵢⱼ)² / dᵢⱼ⁴` over atom pairs and its gradient
+/// (zero along fixed components).
+fn idpp_objective(
+    pos: &[[f64; 3]],
+    targets: &[f64],
+    fixed: &[[bool; 3]],
+    mic: Option<&(Cell, [bool; 3])>,
+    grad: &mut [[f64; 3]],
+) -> f64 {
+    grad.fill([0.0; 3]);
+    let mut total = 0.0;
+    let mut target = targets.iter();
+    for i in 0..pos.len() {
+        for j in i + 1..pos.len() {
+            let goal = *target.next().unwrap_or(&0.0);
+            let v = separation(pos[i], pos[j], mic);
+            let dist = norm(v);
+            if dist == 0.0 {
+                continue;
+            }
+            let diff = goal - dist;
+            total += diff * diff / dist.powi(4);
+            // d/d(dist) of the pair term, then along the unit separation.
+            let g = -2.0 * diff / dist.powi(4) - 4.0 * diff * diff / dist.powi(5);
+            for c in 0..3 {
+                let f = g * v[c] / dist;
+                grad[i][c] += f;
+                grad[j][c] -= f;
+            }
+        }
+    }
+    for (g, fixed) in grad.iter_mut().zip(fixed) {
+        for (g, fixed) in g.iter_mut().zip(fixed) {
+            if *fixed {
+                *g = 0.0;
+            }
+        }
+    }
+    total
+}
+
+/// Relaxes one image on the IDPP surface for path fraction `t`, with a
+/// backtracking steepest descent whose longest move is capped.
+fn idpp_relax(
+    pos: &mut [[f64; 3]],
+    start: &[[f64; 3]],
+    end: &[[f64; 3]],
+    t: f64,
+    fixed: &[[bool; 3]],
+    mic: Option<&(Cell, [bool; 3])>,
+) {
+    let n = pos.len();
+    let mut targets = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for i in 0..n {
+        for j in i + 1..n {
+            let da = norm(separation(start[i], start[j], mic));
+            let db = norm(separation(end[i], end[j], mic));
+            targets.push(da + t * (db - da));
+        }
+    }
+    let mut grad = vec![[0.0; 3]; n];
+    let mut trial_grad = vec![[0.0; 3]; n];
+    let mut value = idpp_objective(pos, &targets, fixed, mic, &mut grad);
+    let mut alpha = 1.0;
+    for _ in 0..IDPP_MAX_STEPS {
+        let longest = grad.iter().flatten().fold(0.0, |m: f64, g| m.max(g.abs()));
+        if longest == 0.0 {
+            break;
+        }
+        let step = (alpha * longest).min(IDPP_MAX_MOVE) / longest;
+        if step * longest < IDPP_TOLERANCE {
+            break;
+        }
+        let trial: Vec<[f64; 3]> = pos
+            .iter()
+            .zip(&grad)
+            .map(|(p, g)| [0, 1, 2].map(|c| p[c] - step * g[c]))
+            .collect();
+        let trial_value = idpp_objective(&trial, &targets, fixed, mic, &mut trial_grad);
+        if trial_value < value {
+            pos.copy_from_slice(&trial);
+            std::mem::swap(&mut grad, &mut trial_grad);
+            value = trial_value;
+            alpha = step * 1.2;
+        } else {
+            alpha = step * 0.5;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    fn image(x: f64, energy: f64) -> ConFrame {
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+        b.add_atom("H", x, 0.0, 0.0, [false; 3], 1, 1.008);
+        let mut f = b.build();
+        f.header.set_energy(energy);
+        f
+    }
+
+    fn band() -> Band {
+        Band::from_frames(vec![image(1.0, -1.0), image(2.0, -0.25), image(4.0, -1.5)])
+    }
+
+    #[test]
+    fn analysis_helpers() {
+        let b = band();
+        assert_eq!(b.len(), 3);
+        assert_eq!(
+            b.relative_energies(),
+            vec![Some(0.0), Some(0.75), Some(-0.5)]
+        );
+        assert_eq!(b.highest_image(), Some((1, 0.75)));
+        assert_eq!(b.path_coordinate(), vec![0.0, 1.0, 3.0]);
+
+        let mut partial = b.clone();
+        partial.energies[2] = None;
+        assert_eq!(partial.highest_image(), None);
+    }
+
+    #[test]
+    fn single_file_and_directory_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let b = band();
+
+        let path = dir.path().join("neb.con");
+        b.write(&path).unwrap();
+        let back = Band::read(&path).unwrap();
+        assert_eq!(back.energies, b.energies);
+        assert_eq!(back.images[2].header.neb_bead(), Some(2));
+        assert_eq!(back.images[2].header.prebox_header.user, "NEB image 2");
+
+        let images = dir.path().join("images");
+        let paths = b.write_dir(&images, "image_").unwrap();
+        assert!(paths[1].ends_with("image_001.con"));
+        // A stray file with a non-numeric suffix is skipped.
+        std::fs::write(images.join("image_notes.con"), "").unwrap();
+        let back = Band::read_dir(&images, "image_").unwrap();
+        assert_eq!(back.len(), 3);
+        assert_eq!(back.path_coordinate(), b.path_coordinate());
+        assert!(Band::read_dir(&images, "missing_").is_err());
+    }
+
+    #[test]
+    fn interpolation_crosses_faces_and_keeps_fixed_atoms() {
+        let frame = |x: f64, cu: f64| {
+            let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+            b.add_atom("Cu", cu, 0.0, 0.0, [true, false, false], 0, 63.546);
+            b.add_atom("H", x, 5.0, 5.0, [false; 3], 1, 1.008);
+            let mut f = b.build();
+            f.header.set_energy(-1.0);
+            f
+        };
+        let images = interpolate(&frame(9.8, 0.0), &frame(0.2, 1.0), 1).unwrap();
+        assert_eq!(images.len(), 1);
+        assert!((images[0].atom_data[1].x - 10.0).abs() < 1e-12);
+        assert_eq!(images[0].atom_data[0].x, 0.0);
+        assert_eq!(
+            images[0].positions.as_f64_row(1)[0],
+            images[0].atom_data[1].x
+        );
+        assert_eq!(images[0].header.energy(), None);
+        assert!(
+            interpolate(&frame(1.0, 0.0), &frame(2.0, 0.0), 0)
+                .unwrap()
+                .is_empty()
+        );
+
+        let mut other = frame(1.0, 0.0);
+        other.set_symbol(1, "He").unwrap();
+        assert!(interpolate(&frame(1.0, 0.0), &other, 2).is_err());
+    }
+
+    #[test]
+    fn idpp_keeps_bond_lengths_through_a_rotation() {
+        // A 2 Å dimer turning by 90°: the straight line shortens the bond
+        // to √2 Å halfway, IDPP keeps it near 2 Å.
+        let dimer = |(x, y): (f64, f64)| {
+            let mut b = ConFrameBuilder::new([20.0; 3], [90.0; 3]);
+            b.add_atom("H", 10.0 + x, 10.0 + y, 10.0, [false; 3], 0, 1.008);
+            b.add_atom("H", 10.0 - x, 10.0 - y, 10.0, [false; 3], 1, 1.008);
+            b.build()
+        };
+        let (a, b) = (dimer((1.0, 0.0)), dimer((0.0, 1.0)));
+        let bond = |f: &ConFrame| {
+            let (p, q) = (f.position(0), f.position(1));
+            norm([p[0] - q[0], p[1] - q[1], p[2] - q[2]])
+        };
+        let linear = interpolate(&a, &b, 3).unwrap();
+        assert!((bond(&linear[1]) - 2f64.sqrt()).abs() < 1e-12);
+        let idpp = interpolate_with(&a, &b, 3, Interpolation::Idpp).unwrap();
+        for image in &idpp {
+            assert!((bond(image) - 2.0).abs() < 1e-3, "{}", bond(image));
+        }
+    }
+}
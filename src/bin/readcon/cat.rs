@@ -0,0 +1,158 @@
+//! `readcon cat`: concatenate the frames of several files into one CON
+//! stream.
+
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+use readcon_core::compression::FileContents;
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::writer::ConFrameWriter;
+
+use crate::{CmdResult, con_writer, read_input};
+
+pub fn command() -> Command {
+    Command::new("cat")
+        .about("Concatenate frames of CON/convel files and write them as CON")
+        .arg(
+            Arg::new("inputs")
+                .value_name("INPUT")
+                .required(true)
+                .num_args(1..)
+                .help("Input files; `-` reads stdin")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("OUTPUT")
+                .help("Write to this file instead of stdout")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("first")
+                .long("first")
+                .value_name("N")
+                .conflicts_with("last")
+                .help("Only write the first N frames")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("last")
+                .long("last")
+                .value_name("N")
+                .help("Only write the last N frames")
+                .value_parser(value_parser!(usize)),
+        )
+}
+
+/// Which frames of the concatenated inputs to write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Keep {
+    All,
+    First(usize),
+    Last(usize),
+}
+
+/// Writes the `keep` frames of `inputs`, in order, as CON to `output`
+/// (stdout when `None` or `-`). Shared by `cat`, `head` and `tail`.
+pub(crate) fn write_frames(inputs: &[&Path], keep: Keep, output: Option<&Path>) -> CmdResult {
+    let mut writer = con_writer(output)?;
+    match keep {
+        Keep::All => copy_first(&mut writer, inputs, usize::MAX)?,
+        Keep::First(n) => copy_first(&mut writer, inputs, n)?,
+        Keep::Last(n) => {
+            // Every input is needed twice (count, then copy) and stdin can
+            // only be read once, so hold all of them.
+            let contents = inputs
+                .iter()
+                .map(|input| read_input(input))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut total: usize = 0;
+            for (input, contents) in inputs.iter().zip(&contents) {
+                let mut it = ConFrameIterator::new(contents.as_str()?);
+                while let Some(skipped) = it.forward() {
+                    skipped.map_err(|e| format!("{}: frame {total}: {e}", input.display()))?;
+                    total += 1;
+                }
+            }
+            let skip = total.saturating_sub(n);
+            let mut offset = 0;
+            for (input, contents) in inputs.iter().zip(&contents) {
+                offset += copy_text(&mut writer, input, contents, skip - skip.min(offset), n)?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Copies the first `take` frames of the concatenated inputs, reading each
+/// input only once the previous ones are exhausted.
+fn copy_first<W: Write>(
+    writer: &mut ConFrameWriter<W>,
+    inputs: &[&Path],
+    mut take: usize,
+) -> Result<(), Box<dyn Error>> {
+    for input in inputs {
+        if take == 0 {
+            break;
+        }
+        let contents = read_input(input)?;
+        take -= copy_text(writer, input, &contents, 0, take)?;
+    }
+    Ok(())
+}
+
+/// Skips `skip` frames of one input, then writes up to `take`; returns the
+/// number of frames consumed (skipped plus written).
+fn copy_text<W: Write>(
+    writer: &mut ConFrameWriter<W>,
+    input: &Path,
+    contents: &FileContents,
+    skip: usize,
+    take: usize,
+) -> Result<usize, Box<dyn Error>> {
+    let mut frames = ConFrameIterator::new(contents.as_str()?);
+    let mut seen = 0;
+    while seen < skip {
+        match frames.forward() {
+            Some(skipped) => {
+                skipped.map_err(|e| format!("{}: frame {seen}: {e}", input.display()))?
+            }
+            None => return Ok(seen),
+        }
+        seen += 1;
+    }
+    for frame in frames.take(take) {
+        let frame = frame.map_err(|e| format!("{}: frame {seen}: {e}", input.display()))?;
+        writer.write_frame(&frame)?;
+        seen += 1;
+    }
+    Ok(seen)
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let inputs: Vec<&Path> = matches
+        .get_many::<PathBuf>("inputs")
+        .into_iter()
+        .flatten()
+        .map(PathBuf::as_path)
+        .collect();
+    let keep = match (
+        matches.get_one::<usize>("first"),
+        matches.get_one::<usize>("last"),
+    ) {
+        (Some(&n), _) => Keep::First(n),
+        (_, Some(&n)) => Keep::Last(n),
+        _ => Keep::All,
+    };
+    write_frames(
+        &inputs,
+        keep,
+        matches.get_one::<PathBuf>("output").map(PathBuf::as_path),
+    )
+}
@@ -0,0 +1,348 @@
+//! `readcon convert`: any supported format → any supported format.
+//!
+//! Formats come from the file extensions (`--from` / `--to` override them).
+//! Frames stream through [`readcon_core::trajectory::copy_frames`], so only
+//! one frame is held at a time except for chemfiles imports, which load the
+//! whole trajectory first. `--skip-bad-frames` reports unreadable frames on
+//! stderr and carries on instead of stopping. `--provenance` replaces each
+//! frame's comment with a [`Provenance`] record of this conversion.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Arg, ArgAction, ArgMatches, Command, builder::PossibleValue, value_parser};
+use readcon_core::compression::{FileContents, read_file_contents};
+use readcon_core::convert::{ConvertError, read_frames_for_convert};
+use readcon_core::formats::bcon::{BconReader, BconWriter};
+use readcon_core::formats::dcd::DcdWriter;
+use readcon_core::formats::gro::GroFrameIterator;
+use readcon_core::formats::jsonl::JsonlFrameIterator;
+use readcon_core::formats::xyz::XyzFrameIterator;
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::provenance::Provenance;
+use readcon_core::trajectory::{
+    FormatWriter, FrameRange, FrameSink, OnReadError, copy_frames, select,
+};
+use readcon_core::types::ConFrame;
+use readcon_core::writer::ConFrameWriter;
+
+use crate::{CmdResult, progress_printer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Con,
+    Bcon,
+    Jsonl,
+    Gro,
+    Xyz,
+    Dcd,
+    Mol2,
+    Sdf,
+}
+
+impl Format {
+    const ALL: [Format; 8] = [
+        Format::Con,
+        Format::Bcon,
+        Format::Jsonl,
+        Format::Gro,
+        Format::Xyz,
+        Format::Dcd,
+        Format::Mol2,
+        Format::Sdf,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Format::Con => "con",
+            Format::Bcon => "bcon",
+            Format::Jsonl => "jsonl",
+            Format::Gro => "gro",
+            Format::Xyz => "xyz",
+            Format::Dcd => "dcd",
+            Format::Mol2 => "mol2",
+            Format::Sdf => "sdf",
+        }
+    }
+
+    /// Format implied by the extension, ignoring a `.gz` / `.zst` suffix.
+    fn from_path(path: &Path) -> Option<Format> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        let base = name
+            .strip_suffix(".gz")
+            .or_else(|| name.strip_suffix(".zst"))
+            .unwrap_or(&name);
+        Some(match base.rsplit_once('.')?.1 {
+            "con" | "convel" => Format::Con,
+            "bcon" => Format::Bcon,
+            "jsonl" | "ndjson" => Format::Jsonl,
+            "gro" => Format::Gro,
+            "xyz" | "extxyz" => Format::Xyz,
+            "dcd" => Format::Dcd,
+            "mol2" => Format::Mol2,
+            "sdf" | "mol" => Format::Sdf,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl clap::ValueEnum for Format {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+pub fn command() -> Command {
+    Command::new("convert")
+        .about("Convert a structure or trajectory between formats")
+        .long_about(
+            "Convert a structure or trajectory between formats, chosen from the\n\
+             file extensions unless --from / --to are given.\n\
+             - read and write: con/convel (and .gz/.zst input), bcon, jsonl, gro, xyz\n\
+             - write only: dcd, mol2, sdf\n\
+             - other inputs (PDB, LAMMPS, …): requires --features chemfiles",
+        )
+        .arg(
+            Arg::new("input")
+                .value_name("INPUT")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("output")
+                .value_name("OUTPUT")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("from")
+                .long("from")
+                .value_name("FORMAT")
+                .help("Input format (default: from the extension)")
+                .value_parser(value_parser!(Format)),
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("FORMAT")
+                .help("Output format (default: from the extension)")
+                .value_parser(value_parser!(Format)),
+        )
+        .arg(
+            Arg::new("start")
+                .long("start")
+                .value_name("N")
+                .help("First frame to write (0-based)")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("stop")
+                .long("stop")
+                .value_name("N")
+                .help("Stop before this frame")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .action(ArgAction::SetTrue)
+                .help("Show parse progress on stderr (CON input)"),
+        )
+        .arg(
+            Arg::new("stride")
+                .long("stride")
+                .value_name("N")
+                .help("Write every N-th frame from --start")
+                .value_parser(value_parser!(u64).range(1..)),
+        )
+        .arg(
+            Arg::new("skip-bad-frames")
+                .long("skip-bad-frames")
+                .action(ArgAction::SetTrue)
+                .help("Report unreadable frames and continue instead of stopping"),
+        )
+        .arg(
+            Arg::new("provenance")
+                .long("provenance")
+                .action(ArgAction::SetTrue)
+                .help("Record tool, time, input file and frame index in each frame's comment"),
+        )
+}
+
+/// Frames from any reader, with errors unified as [`ConvertError`].
+type Frames<'a> = Box<dyn Iterator<Item = Result<ConFrame, ConvertError>> + 'a>;
+
+fn parse_err(e: readcon_core::error::ParseError) -> ConvertError {
+    ConvertError::Parse(e.to_string())
+}
+
+/// Output writer; the binary formats need [`Output::finish`] to write their
+/// trailer.
+enum Output {
+    Con(ConFrameWriter<File>),
+    Text(FormatWriter<BufWriter<File>>),
+    Bcon(BconWriter<BufWriter<File>>),
+    Dcd(DcdWriter<BufWriter<File>>),
+}
+
+impl Output {
+    fn create(format: Format, path: &Path) -> io::Result<Self> {
+        let text = || io::Result::Ok(BufWriter::new(File::create(path)?));
+        Ok(match format {
+            Format::Con => Output::Con(ConFrameWriter::from_path(path)?),
+            Format::Bcon => Output::Bcon(BconWriter::create(path)?),
+            Format::Dcd => Output::Dcd(DcdWriter::create(path)?),
+            Format::Jsonl => Output::Text(FormatWriter::jsonl(text()?)),
+            Format::Gro => Output::Text(FormatWriter::gro(text()?)),
+            Format::Xyz => Output::Text(FormatWriter::xyz(text()?)),
+            Format::Mol2 => Output::Text(FormatWriter::mol2(text()?)),
+            Format::Sdf => Output::Text(FormatWriter::sdf(text()?)),
+        })
+    }
+
+    fn sink(&mut self) -> &mut dyn FrameSink {
+        match self {
+            Output::Con(w) => w,
+            Output::Text(w) => w,
+            Output::Bcon(w) => w,
+            Output::Dcd(w) => w,
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Output::Con(mut w) => w.flush(),
+            Output::Text(w) => w.into_inner()?.flush(),
+            Output::Bcon(w) => w.finish()?.flush(),
+            Output::Dcd(w) => w.finish()?.flush(),
+        }
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let input = matches.get_one::<PathBuf>("input").expect("required");
+    let output = matches.get_one::<PathBuf>("output").expect("required");
+    let from = matches
+        .get_one::<Format>("from")
+        .copied()
+        .or_else(|| Format::from_path(input));
+    let to = matches
+        .get_one::<Format>("to")
+        .copied()
+        .or_else(|| Format::from_path(output))
+        .ok_or_else(|| {
+            format!(
+                "cannot tell the output format of {}; pass --to",
+                output.display()
+            )
+        })?;
+    let range = FrameRange {
+        start: matches.get_one::<u64>("start").map_or(0, |&n| n as usize),
+        stop: matches.get_one::<u64>("stop").map(|&n| n as usize),
+        step: matches.get_one::<u64>("stride").map_or(1, |&n| n as usize),
+    };
+    let on_error = if matches.get_flag("skip-bad-frames") {
+        OnReadError::Skip
+    } else {
+        OnReadError::Abort
+    };
+    if !input.is_file() {
+        return Err(ConvertError::InputMissing(input.display().to_string()).into());
+    }
+
+    // Readers borrow from these, so they live for the whole function.
+    let contents: FileContents;
+    let mut bcon: BconReader<_>;
+    let frames: Frames = match from {
+        Some(Format::Bcon) => {
+            bcon = BconReader::open(input)?;
+            Box::new(bcon.frames().map(|r| r.map_err(ConvertError::Io)))
+        }
+        Some(f @ (Format::Con | Format::Jsonl | Format::Gro | Format::Xyz)) => {
+            contents =
+                read_file_contents(input).map_err(|e| format!("{}: {e}", input.display()))?;
+            let text = contents.as_str()?;
+            // Resynchronize on the next header so one bad frame is one error.
+            let con = ConFrameIterator::new(text).recover(on_error == OnReadError::Skip);
+            match f {
+                Format::Con if matches.get_flag("progress") => Box::new(
+                    con.with_progress(progress_printer())
+                        .map(|r| r.map_err(parse_err)),
+                ),
+                Format::Con => Box::new(con.map(|r| r.map_err(parse_err))),
+                Format::Jsonl => {
+                    Box::new(JsonlFrameIterator::new(text).map(|r| r.map_err(parse_err)))
+                }
+                Format::Gro => Box::new(GroFrameIterator::new(text).map(|r| r.map_err(parse_err))),
+                _ => Box::new(XyzFrameIterator::new(text).map(|r| r.map_err(parse_err))),
+            }
+        }
+        Some(f) => return Err(format!("{f} is a write-only format").into()),
+        None => Box::new(read_frames_for_convert(input)?.0.into_iter().map(Ok)),
+    };
+
+    let frames: Frames = if matches.get_flag("provenance") {
+        let stamp = Provenance::new(concat!("readcon ", env!("CARGO_PKG_VERSION"), " convert"))
+            .stamped_now()
+            .with_source(input.display().to_string());
+        // Indexed before selection so they count source frames.
+        Box::new(frames.enumerate().map(move |(i, r)| {
+            r.map(|mut frame| {
+                frame.set_comment(&stamp.clone().with_frame_index(i as u64));
+                frame
+            })
+        }))
+    } else {
+        frames
+    };
+
+    let mut out = Output::create(to, output)?;
+    let report = copy_frames(&mut select(frames, range), out.sink(), on_error)?;
+    out.finish()?;
+    // Indices count the selected frames only, so they are left out.
+    for (_, e) in &report.skipped {
+        eprintln!("Error: {}: skipped unreadable frame: {e}", input.display());
+    }
+    let n = report.written;
+    let from = from.map_or("chemfiles import", Format::name);
+    println!(
+        "-> convert ({from} → {to}): {n} frame(s) → {}",
+        output.display()
+    );
+    if n == 0 {
+        eprintln!("Error: no frames selected from {}", input.display());
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_from_extensions() {
+        assert_eq!(Format::from_path(Path::new("a.con.gz")), Some(Format::Con));
+        assert_eq!(Format::from_path(Path::new("a.CONVEL")), Some(Format::Con));
+        assert_eq!(Format::from_path(Path::new("dir/a.xyz")), Some(Format::Xyz));
+        assert_eq!(
+            Format::from_path(Path::new("a.ndjson")),
+            Some(Format::Jsonl)
+        );
+        assert_eq!(Format::from_path(Path::new("a.pdb")), None);
+        assert_eq!(Format::from_path(Path::new("noext")), None);
+    }
+}
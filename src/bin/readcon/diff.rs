@@ -0,0 +1,197 @@
+//! `readcon diff`: frame-by-frame structural comparison of two files.
+//!
+//! Frames are paired by index and compared with
+//! [`FrameDiff::compute_minimum_image`], so coordinates are compared within
+//! a tolerance instead of as text and an atom that crossed a periodic
+//! boundary is not reported as moving a box length. Exit status follows
+//! `diff(1)`: 0 same, 1 different, 2 trouble.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use readcon_core::diff::FrameDiff;
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::tolerance::Tolerances;
+use readcon_core::types::ConFrame;
+
+use crate::{CmdResult, read_input};
+
+pub fn command() -> Command {
+    Command::new("diff")
+        .about("Compare two CON files frame by frame within a coordinate tolerance")
+        .long_about(
+            "Compare two CON files frame by frame: header lines, cell, masses,\n\
+             metadata, per-atom symbols and fixed flags, and positions within a\n\
+             tolerance (minimum-image displacements unless --no-mic). Exit status:\n\
+             0 same, 1 different, 2 unreadable input.",
+        )
+        .arg(
+            Arg::new("a")
+                .value_name("A")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("b")
+                .value_name("B")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("tol")
+                .long("tol")
+                .value_name("TOL")
+                .default_value("1e-6")
+                .value_parser(value_parser!(f64))
+                .help("Displacement (Å) below which an atom counts as unmoved"),
+        )
+        .arg(
+            Arg::new("cell-tol")
+                .long("cell-tol")
+                .value_name("TOL")
+                .value_parser(value_parser!(f64))
+                .help("Tolerance for cell lengths and angles [default: 1e-6]"),
+        )
+        .arg(
+            Arg::new("no-mic")
+                .long("no-mic")
+                .action(ArgAction::SetTrue)
+                .help("Report plain Cartesian displacements, ignoring periodicity"),
+        )
+        .arg(
+            Arg::new("max-atoms")
+                .long("max-atoms")
+                .value_name("N")
+                .default_value("10")
+                .value_parser(value_parser!(usize))
+                .help("List at most N moved atoms per frame"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .action(ArgAction::SetTrue)
+                .help("Print nothing; only set the exit status"),
+        )
+}
+
+/// Header-line and section differences [`FrameDiff`] does not cover.
+fn header_lines(a: &ConFrame, b: &ConFrame) -> Vec<String> {
+    let (ha, hb) = (&a.header, &b.header);
+    let mut out = Vec::new();
+    if ha.prebox_header.user != hb.prebox_header.user {
+        out.push(format!(
+            "comment: {:?} -> {:?}",
+            ha.prebox_header.user, hb.prebox_header.user
+        ));
+    }
+    for (k, (la, lb)) in ha.postbox_header.iter().zip(&hb.postbox_header).enumerate() {
+        if la != lb {
+            out.push(format!("postbox line {}: {la:?} -> {lb:?}", k + 1));
+        }
+    }
+    if ha.sections != hb.sections {
+        out.push(format!("sections: {:?} -> {:?}", ha.sections, hb.sections));
+    }
+    out
+}
+
+fn report(index: usize, diff: &FrameDiff, header: &[String], max_atoms: usize) {
+    println!("frame {index}:");
+    for line in header {
+        println!("  {line}");
+    }
+    for line in diff.to_string().lines() {
+        println!("  {line}");
+    }
+    for atom in diff.moved.iter().take(max_atoms) {
+        let [dx, dy, dz] = atom.displacement;
+        println!(
+            "    atom {} ({} id {}): {:.6} [{dx:.6} {dy:.6} {dz:.6}]",
+            atom.index, atom.symbol, atom.atom_id, atom.distance
+        );
+    }
+    if diff.moved.len() > max_atoms {
+        println!("    ... {} more", diff.moved.len() - max_atoms);
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let a = matches.get_one::<PathBuf>("a").expect("required");
+    let b = matches.get_one::<PathBuf>("b").expect("required");
+    let coordinate = *matches.get_one::<f64>("tol").expect("defaulted");
+    let mut tolerances = Tolerances::default().with_coordinate(coordinate);
+    if let Some(&cell) = matches.get_one::<f64>("cell-tol") {
+        tolerances = tolerances.with_cell(cell);
+    }
+    let mic = !matches.get_flag("no-mic");
+    let max_atoms = *matches.get_one::<usize>("max-atoms").expect("defaulted");
+    let quiet = matches.get_flag("quiet");
+
+    let trouble = |e: String| {
+        eprintln!("Error: {e}");
+        Ok(ExitCode::from(2))
+    };
+    let (ca, cb) = match (read_input(a), read_input(b)) {
+        (Ok(ca), Ok(cb)) => (ca, cb),
+        (Err(e), _) | (_, Err(e)) => return trouble(e.to_string()),
+    };
+    let (ta, tb) = match (ca.as_str(), cb.as_str()) {
+        (Ok(ta), Ok(tb)) => (ta, tb),
+        (Err(e), _) => return trouble(format!("{}: {e}", a.display())),
+        (_, Err(e)) => return trouble(format!("{}: {e}", b.display())),
+    };
+
+    let (mut fa, mut fb) = (ConFrameIterator::new(ta), ConFrameIterator::new(tb));
+    let (mut compared, mut differing) = (0, 0);
+    let mut extra = None;
+    loop {
+        let (frame_a, frame_b) = match (fa.next(), fb.next()) {
+            (Some(Ok(x)), Some(Ok(y))) => (x, y),
+            (Some(Err(e)), _) => {
+                return trouble(format!("{}: frame {compared}: {e}", a.display()));
+            }
+            (_, Some(Err(e))) => {
+                return trouble(format!("{}: frame {compared}: {e}", b.display()));
+            }
+            (None, None) => break,
+            (extra_a, extra_b) => {
+                // One side ran out: count the rest of the longer file.
+                extra = Some(match (extra_a, extra_b) {
+                    (Some(_), _) => (a, 1 + fa.by_ref().count()),
+                    _ => (b, 1 + fb.by_ref().count()),
+                });
+                break;
+            }
+        };
+        let diff = if mic {
+            FrameDiff::compute_minimum_image(&frame_a, &frame_b, &tolerances)
+        } else {
+            FrameDiff::compute(&frame_a, &frame_b, &tolerances)
+        };
+        let header = header_lines(&frame_a, &frame_b);
+        if !diff.is_identical() || !header.is_empty() {
+            differing += 1;
+            if !quiet {
+                report(compared, &diff, &header, max_atoms);
+            }
+        }
+        compared += 1;
+    }
+
+    if let (Some((longer, rest)), false) = (extra, quiet) {
+        println!(
+            "frame count: {} has {rest} extra frame(s) after the first {compared}",
+            longer.display()
+        );
+    }
+    if !quiet {
+        println!(
+            "-> diff: {compared} frame(s) compared, {} differ (tol {coordinate:e}{})",
+            differing,
+            if mic { ", minimum image" } else { "" }
+        );
+    }
+    Ok(ExitCode::from(u8::from(differing > 0 || extra.is_some())))
+}
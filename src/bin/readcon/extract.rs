@@ -0,0 +1,222 @@
+//! `readcon extract`: pull selected frames out of a trajectory.
+//!
+//! Unselected frames are skipped with [`ConFrameIterator::forward`], which
+//! only reads their headers, so extracting a few frames from a long
+//! trajectory costs little more than scanning its line breaks.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+use readcon_core::iterators::ConFrameIterator;
+
+use crate::{CmdResult, con_writer, read_input};
+
+pub fn command() -> Command {
+    Command::new("extract")
+        .about("Write selected frames of a trajectory as CON")
+        .long_about(
+            "Write selected frames of a trajectory as CON, in file order.\n\
+             FRAMES is a comma-separated list of 0-based indices (`5`), indices\n\
+             from the end (`-1`, or `last`) and half-open ranges (`10:20`, `:3`,\n\
+             `-5:`), e.g. `--frames 0,100,last`.",
+        )
+        .arg(
+            Arg::new("input")
+                .value_name("INPUT")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("frames")
+                .long("frames")
+                .value_name("FRAMES")
+                .required(true)
+                .allow_hyphen_values(true),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("OUTPUT")
+                .help("Write to this file instead of stdout")
+                .value_parser(value_parser!(PathBuf)),
+        )
+}
+
+/// One frame index of a selection; negative values count from the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Index {
+    Start(usize),
+    End(usize),
+}
+
+impl Index {
+    fn parse(token: &str) -> Result<Self, String> {
+        let bad = || format!("invalid frame index {token:?}");
+        match token {
+            "last" => Ok(Index::End(1)),
+            _ => match token.strip_prefix('-') {
+                Some(n) => match n.parse() {
+                    Ok(0) | Err(_) => Err(bad()),
+                    Ok(n) => Ok(Index::End(n)),
+                },
+                None => token.parse().map(Index::Start).map_err(|_| bad()),
+            },
+        }
+    }
+
+    /// Absolute index in a trajectory of `n` frames (may be `>= n`).
+    fn resolve(self, n: usize) -> Option<usize> {
+        match self {
+            Index::Start(i) => Some(i),
+            Index::End(k) => n.checked_sub(k),
+        }
+    }
+}
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Index::Start(i) => write!(f, "{i}"),
+            Index::End(k) => write!(f, "-{k}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selector {
+    One(Index),
+    Range(Option<Index>, Option<Index>),
+}
+
+fn parse_frames(spec: &str) -> Result<Vec<Selector>, String> {
+    let bound = |s: &str| match s.trim() {
+        "" => Ok(None),
+        s => Index::parse(s).map(Some),
+    };
+    spec.split(',')
+        .map(|item| match item.split_once(':') {
+            Some((a, b)) => Ok(Selector::Range(bound(a)?, bound(b)?)),
+            None => Index::parse(item.trim()).map(Selector::One),
+        })
+        .collect()
+}
+
+fn needs_count(selectors: &[Selector]) -> bool {
+    selectors.iter().any(|s| match s {
+        Selector::One(i) => matches!(i, Index::End(_)),
+        Selector::Range(a, b) => {
+            b.is_none() || [a, b].iter().any(|i| matches!(i, Some(Index::End(_))))
+        }
+    })
+}
+
+/// Sorted frame indices picked by `selectors` from `n` frames (`None`
+/// when the count is unknown, i.e. no selector refers to the end).
+fn resolve(selectors: &[Selector], n: Option<usize>) -> Result<BTreeSet<usize>, String> {
+    let total = n.unwrap_or(usize::MAX);
+    let mut out = BTreeSet::new();
+    for s in selectors {
+        match *s {
+            Selector::One(i) => {
+                let index = i
+                    .resolve(total)
+                    .filter(|&i| i < total)
+                    .ok_or_else(|| format!("frame {i} is out of range ({total} frames)"))?;
+                out.insert(index);
+            }
+            Selector::Range(a, b) => {
+                let lo = a.map_or(Some(0), |i| i.resolve(total)).unwrap_or(0);
+                let hi = b.map_or(Some(total), |i| i.resolve(total)).unwrap_or(0);
+                out.extend(lo..hi.min(total));
+            }
+        }
+    }
+    Ok(out)
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let input = matches.get_one::<PathBuf>("input").expect("required");
+    let selectors = parse_frames(matches.get_one::<String>("frames").expect("required"))?;
+    let contents = read_input(input)?;
+    let text = contents.as_str()?;
+
+    let count = if needs_count(&selectors) {
+        let mut it = ConFrameIterator::new(text);
+        let mut n = 0;
+        while let Some(skipped) = it.forward() {
+            skipped.map_err(|e| format!("{}: frame {n}: {e}", input.display()))?;
+            n += 1;
+        }
+        Some(n)
+    } else {
+        None
+    };
+    let wanted = resolve(&selectors, count)?;
+
+    let mut frames = ConFrameIterator::new(text);
+    let mut selected = Vec::with_capacity(wanted.len());
+    let mut next = 0;
+    for &index in &wanted {
+        while next < index {
+            frames
+                .forward()
+                .ok_or_else(|| format!("frame {index} is out of range ({next} frames)"))?
+                .map_err(|e| format!("{}: frame {next}: {e}", input.display()))?;
+            next += 1;
+        }
+        let frame = frames
+            .next()
+            .ok_or_else(|| format!("frame {index} is out of range ({next} frames)"))?
+            .map_err(|e| format!("{}: frame {index}: {e}", input.display()))?;
+        selected.push(frame);
+        next += 1;
+    }
+
+    let mut writer = con_writer(matches.get_one::<PathBuf>("output").map(PathBuf::as_path))?;
+    writer.extend(selected.iter())?;
+    writer.flush()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_resolve_selections() {
+        let s = parse_frames("0,100,last").unwrap();
+        assert!(needs_count(&s));
+        assert_eq!(
+            resolve(&s, Some(200))
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![0, 100, 199]
+        );
+        assert!(resolve(&s, Some(50)).is_err());
+
+        let s = parse_frames("2:5,-2:,:1").unwrap();
+        assert_eq!(
+            resolve(&s, Some(10))
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![0, 2, 3, 4, 8, 9]
+        );
+
+        let s = parse_frames("3,7:9").unwrap();
+        assert!(!needs_count(&s));
+        assert_eq!(
+            resolve(&s, None).unwrap().into_iter().collect::<Vec<_>>(),
+            vec![3, 7, 8]
+        );
+
+        assert!(parse_frames("x").is_err());
+        assert!(parse_frames("-0").is_err());
+        assert!(parse_frames("1,,2").is_err());
+    }
+}
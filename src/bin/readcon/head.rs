@@ -0,0 +1,52 @@
+//! `readcon head`: the first frames of a trajectory.
+
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+
+use crate::CmdResult;
+use crate::cat::{Keep, write_frames};
+
+pub fn command() -> Command {
+    Command::new("head")
+        .about("Write the first N frames as CON (stdin to stdout by default)")
+        .arg(
+            Arg::new("inputs")
+                .value_name("INPUT")
+                .num_args(0..)
+                .default_value("-")
+                .help("Input files; `-` reads stdin")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("count")
+                .short('n')
+                .long("frames")
+                .value_name("N")
+                .default_value("1")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("OUTPUT")
+                .help("Write to this file instead of stdout")
+                .value_parser(value_parser!(PathBuf)),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let inputs: Vec<&Path> = matches
+        .get_many::<PathBuf>("inputs")
+        .into_iter()
+        .flatten()
+        .map(PathBuf::as_path)
+        .collect();
+    let n = *matches.get_one::<usize>("count").expect("defaulted");
+    write_frames(
+        &inputs,
+        Keep::First(n),
+        matches.get_one::<PathBuf>("output").map(PathBuf::as_path),
+    )
+}
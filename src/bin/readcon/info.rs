@@ -0,0 +1,143 @@
+//! `readcon info`: per-file and per-frame statistics.
+//!
+//! Uses [`ConFrameIterator::next_outline`], which parses headers and skips
+//! the coordinate columns, so even long trajectories summarize quickly.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use readcon_core::cell::Cell;
+use readcon_core::iterators::{ConFrameIterator, FrameOutline};
+
+use crate::CmdResult;
+
+pub fn command() -> Command {
+    Command::new("info")
+        .about("Print frame, species, box and constraint statistics of CON/convel files")
+        .arg(
+            Arg::new("inputs")
+                .value_name("INPUT")
+                .required(true)
+                .num_args(1..)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("frames")
+                .long("frames")
+                .action(ArgAction::SetTrue)
+                .help("Also print one line per frame (atoms, fixed, box, volume)"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let per_frame = matches.get_flag("frames");
+    for input in matches.get_many::<PathBuf>("inputs").into_iter().flatten() {
+        let outlines = read_outlines(input)?;
+        print_summary(input, &outlines);
+        if per_frame {
+            print_frames(&outlines);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn read_outlines(path: &Path) -> Result<Vec<FrameOutline>, Box<dyn std::error::Error>> {
+    let contents = crate::read_input(path)?;
+    let mut it = ConFrameIterator::new(contents.as_str()?);
+    let mut outlines = Vec::new();
+    while let Some(outline) = it.next_outline() {
+        outlines.push(
+            outline.map_err(|e| format!("{}: frame {}: {e}", path.display(), outlines.len()))?,
+        );
+    }
+    Ok(outlines)
+}
+
+fn volume(outline: &FrameOutline) -> Option<f64> {
+    Cell::from_header(&outline.header).map(|c| c.volume())
+}
+
+/// `min..max` of `values`, or the single value when they all agree.
+fn range(values: impl Iterator<Item = f64>) -> String {
+    let (lo, hi) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if lo == hi {
+        format!("{lo}")
+    } else {
+        format!("{lo}..{hi}")
+    }
+}
+
+/// Atom count per symbol, merging types that share a symbol.
+fn species(outline: &FrameOutline) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+    for (sym, n) in outline.symbols.iter().zip(&outline.header.natms_per_type) {
+        *counts.entry(sym.as_str()).or_default() += n;
+    }
+    counts
+}
+
+fn print_summary(path: &Path, outlines: &[FrameOutline]) {
+    println!("{}: {} frame(s)", path.display(), outlines.len());
+    let (Some(first), Some(last)) = (outlines.first(), outlines.last()) else {
+        return;
+    };
+    let natoms = outlines.iter().map(|o| o.natoms() as f64);
+    println!("  atoms per frame: {}", range(natoms));
+
+    // The first frame is representative unless the composition changes
+    // along the trajectory.
+    let first_species = species(first);
+    let listing: Vec<String> = first_species
+        .iter()
+        .map(|(sym, n)| format!("{sym} {n}"))
+        .collect();
+    println!("  species: {}", listing.join(", "));
+    if outlines.iter().any(|o| species(o) != first_species) {
+        println!("    (composition varies between frames)");
+    }
+
+    let nfixed = outlines.iter().map(|o| o.nfixed() as f64);
+    let nfree = outlines.iter().map(|o| (o.natoms() - o.nfixed()) as f64);
+    println!("  fixed atoms: {}", range(nfixed));
+    println!("  free atoms: {}", range(nfree));
+
+    println!(
+        "  box lengths: first {:?}, last {:?}",
+        first.header.boxl, last.header.boxl
+    );
+    for (k, label) in ["a", "b", "c"].iter().enumerate() {
+        println!(
+            "    {label}: {}",
+            range(outlines.iter().map(|o| o.header.boxl[k]))
+        );
+    }
+    println!(
+        "  angles: first {:?}, last {:?}",
+        first.header.angles, last.header.angles
+    );
+    let volumes: Vec<f64> = outlines.iter().filter_map(volume).collect();
+    if !volumes.is_empty() {
+        println!("  volume: {}", range(volumes.into_iter()));
+    }
+    if !last.header.sections.is_empty() {
+        println!("  sections: {}", last.header.sections.join(", "));
+    }
+}
+
+fn print_frames(outlines: &[FrameOutline]) {
+    println!("  frame  atoms  fixed  a b c  alpha beta gamma  volume");
+    for (i, o) in outlines.iter().enumerate() {
+        let [a, b, c] = o.header.boxl;
+        let [alpha, beta, gamma] = o.header.angles;
+        let vol = volume(o).map_or_else(|| "-".to_string(), |v| format!("{v:.6}"));
+        println!(
+            "  {i}  {}  {}  {a} {b} {c}  {alpha} {beta} {gamma}  {vol}",
+            o.natoms(),
+            o.nfixed()
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! `readcon merge`: many CON files → one trajectory with a consistent
+//! species layout.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+use readcon_core::types::ConFrame;
+use readcon_core::writer::ConFrameWriter;
+
+use crate::{CmdResult, read_con_frames};
+
+pub fn command() -> Command {
+    Command::new("merge")
+        .about("Concatenate CON files into one trajectory, checking the species layout")
+        .long_about(
+            "Concatenate CON files into one trajectory. Every frame must have the\n\
+             same atom types in the same order with the same counts as the first\n\
+             frame; use `readcon cat` to concatenate without this check.",
+        )
+        .arg(
+            Arg::new("inputs")
+                .value_name("INPUT")
+                .required(true)
+                .num_args(1..)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("OUTPUT")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+}
+
+/// `(symbol, count)` per atom type, in file order.
+fn layout(frame: &ConFrame) -> Vec<(&str, usize)> {
+    let mut start = 0;
+    frame
+        .header
+        .natms_per_type
+        .iter()
+        .map(|&n| {
+            let symbol = frame
+                .atom_data
+                .get(start)
+                .map_or("", |a| frame.symbol_of(a));
+            start += n;
+            (symbol, n)
+        })
+        .collect()
+}
+
+fn describe(layout: &[(&str, usize)]) -> String {
+    let parts: Vec<String> = layout.iter().map(|(s, n)| format!("{s} {n}")).collect();
+    parts.join(", ")
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let output = matches.get_one::<PathBuf>("output").expect("required");
+    // Read and check everything first so a layout mismatch leaves no
+    // partial output behind.
+    let mut frames = Vec::new();
+    for input in matches.get_many::<PathBuf>("inputs").into_iter().flatten() {
+        for (i, frame) in read_con_frames(input)?.into_iter().enumerate() {
+            if let Some(first) = frames.first() {
+                let (want, got) = (layout(first), layout(&frame));
+                if want != got {
+                    return Err(format!(
+                        "{} frame {i}: species layout [{}] differs from [{}]",
+                        input.display(),
+                        describe(&got),
+                        describe(&want)
+                    )
+                    .into());
+                }
+            }
+            frames.push(frame);
+        }
+    }
+    let mut writer = ConFrameWriter::from_path(output)?;
+    writer.extend(frames.iter())?;
+    writer.flush()?;
+    println!("-> merge: {} frame(s) → {}", frames.len(), output.display());
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,132 @@
+//! `readcon split`: one CON file per frame.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::writer::ConFrameWriter;
+
+use crate::{CmdResult, is_stdio, read_input};
+
+pub fn command() -> Command {
+    Command::new("split")
+        .about("Write each frame of a trajectory to its own CON file")
+        .arg(
+            Arg::new("input")
+                .value_name("INPUT")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("template")
+                .short('o')
+                .long("template")
+                .value_name("TEMPLATE")
+                .help(
+                    "Output path with one `{}` (frame index) or `{:0N}` (zero-padded \
+                     to N digits) placeholder [default: <input stem>_{:04}.con]",
+                ),
+        )
+}
+
+/// Fills the single `{}` / `{:0N}` placeholder of `template` with `index`.
+fn render(template: &str, index: usize) -> Result<String, String> {
+    let start = template
+        .find('{')
+        .ok_or_else(|| format!("template {template:?} has no {{}} placeholder"))?;
+    let len = template[start..]
+        .find('}')
+        .ok_or_else(|| format!("template {template:?} has an unclosed {{"))?;
+    let spec = &template[start + 1..start + len];
+    let number = match spec {
+        "" => index.to_string(),
+        _ => {
+            let width = spec
+                .strip_prefix(":0")
+                .and_then(|w| w.parse::<usize>().ok())
+                .ok_or_else(|| format!("unsupported placeholder {{{spec}}} in {template:?}"))?;
+            format!("{index:0width$}")
+        }
+    };
+    let rest = &template[start + len + 1..];
+    if rest.contains('{') {
+        return Err(format!(
+            "template {template:?} has more than one placeholder"
+        ));
+    }
+    Ok(format!("{}{number}{rest}", &template[..start]))
+}
+
+fn default_template(input: &Path) -> String {
+    if is_stdio(input) {
+        return "frame_{:04}.con".into();
+    }
+    let name = input
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("frame");
+    let stem = name
+        .strip_suffix(".gz")
+        .or_else(|| name.strip_suffix(".zst"))
+        .unwrap_or(name);
+    let stem = Path::new(stem)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("frame");
+    format!("{stem}_{{:04}}.con")
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let input = matches.get_one::<PathBuf>("input").expect("required");
+    let template = matches
+        .get_one::<String>("template")
+        .cloned()
+        .unwrap_or_else(|| default_template(input));
+    // Validate before touching the filesystem.
+    render(&template, 0)?;
+
+    let contents = read_input(input)?;
+    let mut n = 0;
+    for frame in ConFrameIterator::new(contents.as_str()?) {
+        let frame = frame.map_err(|e| format!("{}: frame {n}: {e}", input.display()))?;
+        let path = render(&template, n)?;
+        if let Some(dir) = Path::new(&path)
+            .parent()
+            .filter(|d| !d.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut writer = ConFrameWriter::from_path(&path).map_err(|e| format!("{path}: {e}"))?;
+        writer.write_frame(&frame)?;
+        writer.flush()?;
+        n += 1;
+    }
+    println!("-> split: {n} frame(s) → {template}");
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_placeholders() {
+        assert_eq!(render("f{}.con", 7).unwrap(), "f7.con");
+        assert_eq!(render("out/f_{:04}.con", 7).unwrap(), "out/f_0007.con");
+        assert!(render("f.con", 0).is_err());
+        assert!(render("f{x}.con", 0).is_err());
+        assert!(render("f{}_{}.con", 0).is_err());
+        assert!(render("f{.con", 0).is_err());
+    }
+
+    #[test]
+    fn default_template_strips_extensions() {
+        assert_eq!(
+            default_template(Path::new("a/traj.con.gz")),
+            "traj_{:04}.con"
+        );
+        assert_eq!(default_template(Path::new("run.convel")), "run_{:04}.con");
+        assert_eq!(default_template(Path::new("-")), "frame_{:04}.con");
+    }
+}
@@ -0,0 +1,89 @@
+//! `readcon validate`: strict checker for CI pipelines.
+//!
+//! Exit status: 0 when every input is clean, 1 when the worst finding is a
+//! warning, 2 when any input has an error or cannot be read.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use readcon_core::validate::{Severity, ValidationReport, validate_path, validate_str};
+use serde_json::json;
+
+use crate::{CmdResult, is_stdio, read_input};
+
+pub fn command() -> Command {
+    Command::new("validate")
+        .about("Run strict validation on every frame and report errors and warnings")
+        .long_about(
+            "Run strict validation on every frame and report errors and warnings\n\
+             with frame and line numbers. Exit status: 0 clean, 1 warnings only,\n\
+             2 errors (including unreadable inputs).",
+        )
+        .arg(
+            Arg::new("inputs")
+                .value_name("INPUT")
+                .required(true)
+                .num_args(1..)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("Print a JSON array with one report object per input"),
+        )
+}
+
+fn validate_input(path: &Path) -> Result<ValidationReport, Box<dyn std::error::Error>> {
+    if is_stdio(path) {
+        let contents = read_input(path)?;
+        return Ok(validate_str(contents.as_str()?));
+    }
+    validate_path(path)
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let as_json = matches.get_flag("json");
+    let mut worst = None;
+    let mut reports = Vec::new();
+    for input in matches.get_many::<PathBuf>("inputs").into_iter().flatten() {
+        let name = input.display();
+        match validate_input(input) {
+            Ok(report) => {
+                worst = worst.max(report.max_severity());
+                if as_json {
+                    let mut value = report.to_json();
+                    value["file"] = json!(name.to_string());
+                    reports.push(value);
+                    continue;
+                }
+                for issue in &report.issues {
+                    println!("{name}:{issue}");
+                }
+                println!(
+                    "-> {name}: {} frame(s), {} error(s), {} warning(s)",
+                    report.nframes,
+                    report.count(Severity::Error),
+                    report.count(Severity::Warning)
+                );
+            }
+            Err(e) => {
+                worst = Some(Severity::Error);
+                if as_json {
+                    reports.push(json!({ "file": name.to_string(), "error": e.to_string() }));
+                } else {
+                    eprintln!("Error: {name}: {e}");
+                }
+            }
+        }
+    }
+    if as_json {
+        println!("{}", serde_json::Value::Array(reports));
+    }
+    Ok(ExitCode::from(match worst {
+        None => 0,
+        Some(Severity::Warning) => 1,
+        Some(Severity::Error) => 2,
+    }))
+}
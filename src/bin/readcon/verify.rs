@@ -0,0 +1,50 @@
+//! `readcon verify`: parse∘write round-trip check.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+use readcon_core::verify::{VerifyOptions, verify_path};
+
+use crate::CmdResult;
+
+pub fn command() -> Command {
+    Command::new("verify")
+        .about("Check that every frame survives write + re-parse unchanged")
+        .long_about(
+            "Check that every frame survives write + re-parse unchanged; reports\n\
+             the first differing field. Exit status 1 on any divergence.",
+        )
+        .arg(
+            Arg::new("inputs")
+                .value_name("INPUT")
+                .required(true)
+                .num_args(1..)
+                .value_parser(value_parser!(PathBuf)),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let mut failed = false;
+    for input in matches.get_many::<PathBuf>("inputs").into_iter().flatten() {
+        let name = input.display();
+        match verify_path(input, &VerifyOptions::default()) {
+            Ok(report) => match report.divergence {
+                None => println!("-> {name}: {} frame(s) round-trip", report.nframes),
+                Some(d) => {
+                    println!("-> {name}: {d}");
+                    failed = true;
+                }
+            },
+            Err(e) => {
+                eprintln!("Error: {name}: {e}");
+                failed = true;
+            }
+        }
+    }
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
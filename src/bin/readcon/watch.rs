@@ -0,0 +1,156 @@
+//! `readcon watch`: follow a trajectory that is still being written, like
+//! `tail -f`, emitting each frame once it is complete.
+//!
+//! The file is polled; appended bytes go to a [`FrameFollower`], so a
+//! partly written trailing frame is simply waited for. If the file shrinks
+//! (a job restarted and truncated it), reading starts over from the top.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+use readcon_core::error::ParseError;
+use readcon_core::follow::FrameFollower;
+use readcon_core::trajectory::{FormatWriter, FrameSink};
+use readcon_core::types::ConFrame;
+use readcon_core::writer::ConFrameWriter;
+
+use crate::CmdResult;
+
+pub fn command() -> Command {
+    Command::new("watch")
+        .about("Follow a growing trajectory and emit frames as they are completed")
+        .long_about(
+            "Follow a growing trajectory (e.g. a running eOn job's output) and\n\
+             write each frame to stdout once it is completely written: as CON,\n\
+             XYZ or JSONL, or as a one-line summary. Existing frames are emitted\n\
+             first. Runs until interrupted, or until --timeout seconds pass\n\
+             without the file growing.",
+        )
+        .arg(
+            Arg::new("input")
+                .value_name("INPUT")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("FORMAT")
+                .default_value("summary")
+                .value_parser(["summary", "con", "xyz", "jsonl"])
+                .help("What to print per frame"),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .value_name("MS")
+                .default_value("500")
+                .value_parser(value_parser!(u64).range(1..))
+                .help("Polling interval in milliseconds"),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .value_name("SECS")
+                .value_parser(value_parser!(f64))
+                .help("Stop after this many seconds without new data"),
+        )
+}
+
+/// One line per frame: index, atoms, energy and largest force if present.
+fn summary(index: usize, frame: &ConFrame) -> String {
+    let mut line = format!("frame {index}: {} atoms", frame.atom_data.len());
+    if let Some(e) = frame.header.energy() {
+        line += &format!(", energy {e:.6}");
+    }
+    let max_force = frame
+        .atom_data
+        .iter()
+        .filter_map(|a| a.force)
+        .map(|f| f.iter().map(|x| x * x).sum::<f64>().sqrt())
+        .reduce(f64::max);
+    if let Some(f) = max_force {
+        line += &format!(", max |F| {f:.6}");
+    }
+    line
+}
+
+pub fn run(matches: &ArgMatches) -> CmdResult {
+    let input = matches.get_one::<PathBuf>("input").expect("required");
+    let interval = Duration::from_millis(*matches.get_one::<u64>("interval").expect("defaulted"));
+    let timeout = matches
+        .get_one::<f64>("timeout")
+        .map(|&s| Duration::from_secs_f64(s.max(0.0)));
+    // `None` prints summaries.
+    let mut sink: Option<Box<dyn FrameSink>> =
+        match matches.get_one::<String>("to").map(String::as_str) {
+            Some("con") => Some(Box::new(ConFrameWriter::new(io::stdout()))),
+            Some("xyz") => Some(Box::new(FormatWriter::xyz(io::stdout()))),
+            Some("jsonl") => Some(Box::new(FormatWriter::jsonl(io::stdout()))),
+            _ => None,
+        };
+
+    let mut file = File::open(input).map_err(|e| format!("{}: {e}", input.display()))?;
+    let mut follower = FrameFollower::new();
+    let mut errors = 0;
+    let mut emit = |index: usize, result: Result<ConFrame, ParseError>| -> io::Result<()> {
+        match result {
+            Ok(frame) => match &mut sink {
+                Some(sink) => {
+                    sink.write_frame(&frame)?;
+                    sink.flush()
+                }
+                None => {
+                    let mut out = io::stdout().lock();
+                    writeln!(out, "{}", summary(index, &frame))?;
+                    out.flush()
+                }
+            },
+            Err(e) => {
+                errors += 1;
+                eprintln!("Error: {}: frame {index}: {e}", input.display());
+                Ok(())
+            }
+        }
+    };
+
+    let mut read = 0;
+    let mut chunk = vec![0u8; 64 * 1024];
+    let mut last_growth = Instant::now();
+    loop {
+        if file.metadata()?.len() < read {
+            eprintln!("{}: file was truncated; starting over", input.display());
+            file.seek(SeekFrom::Start(0))?;
+            read = 0;
+            follower.reset();
+        }
+        let n = file.read(&mut chunk)?;
+        if n > 0 {
+            read += n as u64;
+            last_growth = Instant::now();
+            follower.push(&chunk[..n]);
+            while let Some(result) = follower.next_frame() {
+                emit(follower.yielded() - 1, result)?;
+            }
+            continue;
+        }
+        if timeout.is_some_and(|t| last_growth.elapsed() >= t) {
+            let first = follower.yielded();
+            for (i, result) in follower.finish().into_iter().enumerate() {
+                emit(first + i, result)?;
+            }
+            break;
+        }
+        thread::sleep(interval);
+    }
+    Ok(if errors == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,125 @@
+//! Connectivity guessing from interatomic distances.
+//!
+//! CON files carry no bonds unless a writer stored them in the `bonds`
+//! metadata, but molecule detection and connectivity-aware export formats
+//! (PDB `CONECT` records, mol2 `@<TRIPOS>BOND`) need them.
+//! [`guess_bonds`] fills the gap with the usual distance criterion: two
+//! atoms are bonded when they are closer than the sum of their covalent
+//! radii ([`crate::helpers::covalent_radius`]) plus a tolerance. Pairs are
+//! found with a [`NeighborList`], so periodic images count and the search
+//! stays linear in the number of atoms.
+
+use crate::error::ParseError;
+use crate::frame::AtomicFrame;
+use crate::helpers::covalent_radius;
+use crate::neighbor::NeighborList;
+use crate::types::{Bond, ConFrame};
+
+/// Default slack (Å) added to the sum of covalent radii, as in Open Babel
+/// and VMD-style connectivity perception.
+pub const DEFAULT_BOND_TOLERANCE: f64 = 0.45;
+
+/// [`guess_bonds_with`] using [`DEFAULT_BOND_TOLERANCE`].
+///
+/// # Example
+/// ```
+/// use readcon_core::bonds::guess_bonds;
+/// use readcon_core::types::{Bond, ConFrameBuilder};
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("O", 5.0, 5.0, 5.0, [false; 3], 0, 15.999);
+/// b.add_atom("H", 5.76, 5.59, 5.0, [false; 3], 1, 1.008);
+/// b.add_atom("H", 4.24, 5.59, 5.0, [false; 3], 2, 1.008);
+/// let mut frame = b.build();
+/// let bonds = guess_bonds(&frame).unwrap();
+/// assert_eq!(bonds, vec![Bond::new(0, 1), Bond::new(0, 2)]);
+/// frame.header.set_bonds(&bonds);
+/// ```
+pub fn guess_bonds<F: AtomicFrame + ?Sized>(frame: &F) -> Result<Vec<Bond>, ParseError> {
+    guess_bonds_with(frame, DEFAULT_BOND_TOLERANCE)
+}
+
+/// Bonds between atoms `i` and `j` (indices into the frame, `i < j`, sorted)
+/// whose distance, over any periodic image, is at most
+/// `r_i + r_j + tolerance` with `r` the covalent radius. A pair bonded
+/// through several images is listed once; an atom is never bonded to its
+/// own image. Atoms whose species has no covalent radius (ghost atoms,
+/// custom labels) get no bonds. Bond orders are left unset.
+///
+/// Errors are [`ParseError::ValidationError`] for a negative or non-finite
+/// tolerance and for frames without a usable cell.
+pub fn guess_bonds_with<F: AtomicFrame + ?Sized>(
+    frame: &F,
+    tolerance: f64,
+) -> Result<Vec<Bond>, ParseError> {
+    if !(tolerance.is_finite() && tolerance >= 0.0) {
+        return Err(ParseError::ValidationError(format!(
+            "bond tolerance must be non-negative and finite, got {tolerance}"
+        )));
+    }
+    let radii: Vec<Option<f64>> = (0..frame.natoms())
+        .map(|i| covalent_radius(frame.species(i)))
+        .collect();
+    let Some(max_radius) = radii.iter().flatten().copied().reduce(f64::max) else {
+        return Ok(Vec::new());
+    };
+    let nl = NeighborList::build(frame, 2.0 * max_radius + tolerance)?;
+    let mut bonds: Vec<Bond> = nl
+        .pairs()
+        .iter()
+        .filter(|p| p.i != p.j)
+        .filter_map(|p| {
+            let limit = radii[p.i]? + radii[p.j]? + tolerance;
+            (p.distance <= limit).then(|| Bond::new(p.i as u32, p.j as u32))
+        })
+        .collect();
+    bonds.sort_unstable_by_key(|b| (b.i, b.j));
+    bonds.dedup();
+    Ok(bonds)
+}
+
+/// The bonds stored in the frame's `bonds` metadata, or [`guess_bonds`]
+/// when it has none. Used by the connectivity-aware exporters.
+pub fn bonds_or_guess(frame: &ConFrame) -> Result<Vec<Bond>, ParseError> {
+    let stored = frame.bonds();
+    if stored.is_empty() {
+        guess_bonds(frame)
+    } else {
+        Ok(stored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    #[test]
+    fn bonds_across_the_boundary_once() {
+        // The two carbons are 8.5 Å apart in the box but 1.5 Å apart
+        // through the x boundary.
+        let mut b = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0; 3]);
+        b.add_atom("C", 0.5, 5.0, 5.0, [false; 3], 0, 12.011);
+        b.add_atom("C", 9.0, 5.0, 5.0, [false; 3], 1, 12.011);
+        b.add_atom("H", 2.0, 5.0, 5.0, [false; 3], 2, 1.008);
+        b.add_atom("Gh", 0.5, 5.5, 5.0, [false; 3], 3, 1.0);
+        let frame = b.build();
+        assert_eq!(
+            guess_bonds(&frame).unwrap(),
+            vec![Bond::new(0, 1), Bond::new(0, 2)]
+        );
+        // C-H at 1.5 Å exceeds 0.76 + 0.31 without slack; C-C does not.
+        assert_eq!(
+            guess_bonds_with(&frame, 0.0).unwrap(),
+            vec![Bond::new(0, 1)]
+        );
+        assert!(guess_bonds_with(&frame, -1.0).is_err());
+    }
+
+    #[test]
+    fn small_cell_does_not_bond_atom_to_itself() {
+        let mut b = ConFrameBuilder::new([1.5; 3], [90.0; 3]);
+        b.add_atom("C", 0.0, 0.0, 0.0, [false; 3], 0, 12.011);
+        let frame = b.build();
+        assert!(guess_bonds(&frame).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+//! Triclinic cell geometry.
+//!
+//! A CON header stores the cell as three lengths and three angles
+//! (`boxl`, `angles`, degrees), optionally overridden by an exact
+//! `metadata["lattice_vectors"]` matrix. [`Cell`] is the single place that
+//! turns either form into lattice vectors, so every consumer uses the same
+//! orientation: `a` along +x, `b` in the xy plane with positive y, `c` with
+//! positive z. Lattice vectors are stored as **rows**, so Cartesian
+//! `r = f · M` for fractional `f`.
+//!
+//! Angles follow the crystallographic convention: `alpha` is between `b`
+//! and `c`, `beta` between `a` and `c`, `gamma` between `a` and `b`.
+
+use crate::types::FrameHeader;
+
+/// Lattice vectors and their inverse; see the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    matrix: [[f64; 3]; 3],
+    inverse: [[f64; 3]; 3],
+}
+
+impl Cell {
+    /// Cell from row-major lattice vectors `[a, b, c]`. `None` when any
+    /// entry is non-finite or the vectors are (numerically) coplanar.
+    pub fn from_matrix(matrix: [[f64; 3]; 3]) -> Option<Self> {
+        if !matrix.iter().flatten().all(|v| v.is_finite()) {
+            return None;
+        }
+        let det = determinant(&matrix);
+        let scale = matrix
+            .iter()
+            .map(|r| (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt())
+            .product::<f64>();
+        if det.abs() <= 1e-12 * scale {
+            return None;
+        }
+        let mut inverse = [[0.0; 3]; 3];
+        for (i, row) in inverse.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                // Adjugate: cofactor of matrix[j][i].
+                let (r0, r1) = ((j + 1) % 3, (j + 2) % 3);
+                let (c0, c1) = ((i + 1) % 3, (i + 2) % 3);
+                *v = (matrix[r0][c0] * matrix[r1][c1] - matrix[r0][c1] * matrix[r1][c0]) / det;
+            }
+        }
+        Some(Cell { matrix, inverse })
+    }
+
+    /// Cell from lengths and angles (degrees) in the standard orientation.
+    /// `None` unless all six values are finite and positive and the angles
+    /// describe a real parallelepiped.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::cell::Cell;
+    /// let cell = Cell::from_lengths_angles([3.0, 4.0, 5.0], [90.0; 3]).unwrap();
+    /// assert!((cell.volume() - 60.0).abs() < 1e-9);
+    /// let f = cell.cartesian_to_fractional([1.5, 2.0, 2.5]);
+    /// assert!(f.iter().all(|x| (x - 0.5).abs() < 1e-12));
+    /// ```
+    pub fn from_lengths_angles(lengths: [f64; 3], angles: [f64; 3]) -> Option<Self> {
+        if !lengths
+            .iter()
+            .chain(&angles)
+            .all(|x| x.is_finite() && *x > 0.0)
+        {
+            return None;
+        }
+        let [a, b, c] = lengths;
+        let [alpha, beta, gamma] = crate::units::angles_to_radians(angles);
+        let (ca, cb, cg, sg) = (alpha.cos(), beta.cos(), gamma.cos(), gamma.sin());
+        if sg.abs() < 1e-15 {
+            return None;
+        }
+        let cy = (ca - cb * cg) / sg;
+        let cz2 = 1.0 - cb * cb - cy * cy;
+        if cz2 <= 1e-12 {
+            return None;
+        }
+        Self::from_matrix([
+            [a, 0.0, 0.0],
+            [b * cg, b * sg, 0.0],
+            [c * cb, c * cy, c * cz2.sqrt()],
+        ])
+    }
+
+    /// Cell of a frame header: `metadata["lattice_vectors"]` when present,
+    /// else `boxl` + `angles`.
+    pub fn from_header(header: &FrameHeader) -> Option<Self> {
+        match header.lattice_vectors() {
+            Some(lv) => Self::from_matrix(lv),
+            None => Self::from_lengths_angles(header.boxl, header.angles),
+        }
+    }
+
+    /// Row-major lattice vectors `[a, b, c]`.
+    pub fn matrix(&self) -> [[f64; 3]; 3] {
+        self.matrix
+    }
+
+    /// Inverse of [`Self::matrix`]; its columns are the reciprocal vectors
+    /// (without the 2π factor).
+    pub fn inverse(&self) -> [[f64; 3]; 3] {
+        self.inverse
+    }
+
+    /// Lengths and angles (degrees) of the lattice vectors, the inverse of
+    /// [`Self::from_lengths_angles`] up to orientation.
+    pub fn to_lengths_angles(&self) -> ([f64; 3], [f64; 3]) {
+        let [a, b, c] = self.matrix;
+        let lengths = [norm(a), norm(b), norm(c)];
+        let angle = |u: [f64; 3], v: [f64; 3]| {
+            (dot(u, v) / (norm(u) * norm(v)))
+                .clamp(-1.0, 1.0)
+                .acos()
+                .to_degrees()
+        };
+        (lengths, [angle(b, c), angle(a, c), angle(a, b)])
+    }
+
+    /// Cell volume (always non-negative).
+    pub fn volume(&self) -> f64 {
+        determinant(&self.matrix).abs()
+    }
+
+    /// Fractional coordinates `f = r · M⁻¹`. Not wrapped into `[0, 1)`.
+    pub fn cartesian_to_fractional(&self, r: [f64; 3]) -> [f64; 3] {
+        row_times(&self.inverse, r)
+    }
+
+    /// Cartesian coordinates `r = f · M`.
+    pub fn fractional_to_cartesian(&self, f: [f64; 3]) -> [f64; 3] {
+        row_times(&self.matrix, f)
+    }
+
+    /// Minimum-image form of displacement `d` along the periodic axes
+    /// (`pbc[k]` for lattice vector `k`): each fractional component is
+    /// shifted into `[-0.5, 0.5]`. Exact for orthogonal cells; for strongly
+    /// skewed cells a shorter image may still exist.
+    pub fn minimum_image(&self, d: [f64; 3], pbc: [bool; 3]) -> [f64; 3] {
+        let mut f = self.cartesian_to_fractional(d);
+        for (x, periodic) in f.iter_mut().zip(pbc) {
+            if periodic {
+                *x -= x.round();
+            }
+        }
+        self.fractional_to_cartesian(f)
+    }
+}
+
+impl FrameHeader {
+    /// The frame's [`Cell`]; `None` for degenerate or non-finite cells.
+    pub fn cell(&self) -> Option<Cell> {
+        Cell::from_header(self)
+    }
+}
+
+fn dot(u: [f64; 3], v: [f64; 3]) -> f64 {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+fn norm(u: [f64; 3]) -> f64 {
+    dot(u, u).sqrt()
+}
+
+fn determinant(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Row vector times matrix: `v · m`.
+fn row_times(m: &[[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    let mut r = [0.0; 3];
+    for (k, row) in m.iter().enumerate() {
+        for axis in 0..3 {
+            r[axis] += v[k] * row[axis];
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: [f64; 3], b: [f64; 3]) -> bool {
+        a.iter().zip(&b).all(|(x, y)| (x - y).abs() < 1e-9)
+    }
+
+    #[test]
+    fn triclinic_roundtrips() {
+        let lengths = [10.0, 11.0, 12.0];
+        let angles = [80.0, 95.0, 110.0];
+        let cell = Cell::from_lengths_angles(lengths, angles).unwrap();
+        let m = cell.matrix();
+        assert_eq!(m[0][1], 0.0);
+        assert_eq!(m[1][2], 0.0);
+        assert!(m[2][2] > 0.0);
+
+        let (l, a) = cell.to_lengths_angles();
+        assert!(close(l, lengths) && close(a, angles));
+
+        let r = [1.0, -2.0, 3.5];
+        let f = cell.cartesian_to_fractional(r);
+        assert!(close(cell.fractional_to_cartesian(f), r));
+        assert!(close(cell.cartesian_to_fractional(m[1]), [0.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn minimum_image_respects_pbc() {
+        let cell = Cell::from_lengths_angles([10.0; 3], [90.0; 3]).unwrap();
+        assert!(close(
+            cell.minimum_image([9.0, -6.0, 4.0], [true; 3]),
+            [-1.0, 4.0, 4.0]
+        ));
+        assert!(close(
+            cell.minimum_image([9.0, -6.0, 4.0], [false, true, true]),
+            [9.0, 4.0, 4.0]
+        ));
+    }
+
+    #[test]
+    fn volume_matches_closed_form() {
+        let cell = Cell::from_lengths_angles([10.0, 11.0, 12.0], [80.0, 95.0, 110.0]).unwrap();
+        let [ca, cb, cg] = [80.0f64, 95.0, 110.0].map(|d| d.to_radians().cos());
+        let t = 1.0 - ca * ca - cb * cb - cg * cg + 2.0 * ca * cb * cg;
+        assert!((cell.volume() - 1320.0 * t.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn header_prefers_lattice_vectors_and_rejects_degenerate() {
+        let mut header = crate::types::ConFrameBuilder::new([5.0; 3], [90.0; 3])
+            .build()
+            .header;
+        assert!((header.cell().unwrap().volume() - 125.0).abs() < 1e-9);
+        header.set_lattice_vectors([[0.0, 2.0, 0.0], [-2.0, 0.0, 0.0], [0.0, 0.0, 2.0]]);
+        let cell = header.cell().unwrap();
+        assert!((cell.volume() - 8.0).abs() < 1e-12);
+        assert!(close(
+            cell.cartesian_to_fractional([0.0, 1.0, 0.0]),
+            [0.5, 0.0, 0.0]
+        ));
+
+        assert!(Cell::from_lengths_angles([0.0, 1.0, 1.0], [90.0; 3]).is_none());
+        assert!(Cell::from_lengths_angles([1.0; 3], [120.0, 120.0, 120.0]).is_none());
+        assert!(Cell::from_matrix([[1.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 0.0, 1.0]]).is_none());
+    }
+}
@@ -1,4 +1,4 @@
-//! Chemfiles → CON conversion.
+//! Chemfiles ↔ CON conversion.
 //!
 //! Real implementation requires the `chemfiles` Cargo feature (links libchemfiles).
 //! Without it, path/memory helpers are still present and return
@@ -103,6 +103,21 @@ mod stubs {
         disabled()
     }
 
+    /// Write `frames` with chemfiles to any format it can write.
+    ///
+    /// Stub without the `chemfiles` feature — always returns [`ChemfilesImportError::FeatureDisabled`].
+    pub fn con_frames_to_trajectory_path<'a, P, I>(
+        _path: P,
+        _format: Option<&str>,
+        _frames: I,
+    ) -> Result<(), ChemfilesImportError>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = &'a ConFrame>,
+    {
+        disabled()
+    }
+
     /// Whether this build linked libchemfiles and implements import/selection.
     pub const fn chemfiles_enabled() -> bool {
         false
@@ -132,6 +147,8 @@ mod stub_tests {
     fn trajectory_path_stub_is_feature_disabled() {
         let err = con_frame_from_trajectory_path("nope.xyz").unwrap_err();
         assert!(matches!(err, ChemfilesImportError::FeatureDisabled));
+        let out = con_frames_to_trajectory_path("nope.pdb", None, &[]);
+        assert!(matches!(out, Err(ChemfilesImportError::FeatureDisabled)));
         let msg = err.to_string();
         assert!(msg.contains("chemfiles"), "{msg}");
     }
@@ -1,9 +1,12 @@
-//! Optional chemfiles -> readcon conversion (feature = "chemfiles").
+//! Optional chemfiles <-> readcon conversion (feature = "chemfiles").
 //!
 //! Maps a chemfiles [`Frame`](chemfiles::Frame) (and trajectory readers) into
 //! a spec-v2 [`ConFrame`](crate::types::ConFrame) with geometry, optional
 //! velocities, and frame/atom properties preserved in
-//! [`FrameHeader::metadata`](crate::types::FrameHeader).
+//! [`FrameHeader::metadata`](crate::types::FrameHeader). The reverse
+//! direction is `Frame::from(&con_frame)`, and
+//! [`con_frames_to_trajectory_path`] writes CON frames to any format
+//! chemfiles supports; `ConFrame::try_from(&frame)` is the import.
 //!
 //! Build with `cargo build --features chemfiles`. Default builds do not
 //! require libchemfiles.
@@ -406,6 +409,61 @@ pub fn con_frame_from_chemfiles(frame: &Frame) -> Result<ConFrame, ChemfilesImpo
     Ok(con)
 }
 
+/// CON → chemfiles projection (the one [`crate::chemfiles_selection`] uses):
+/// cell, positions, velocities, masses, charges, bonds, energy and time, with
+/// names and types restored from the import sidecars. Fixed flags and forces
+/// have no chemfiles counterpart and are dropped.
+impl From<&ConFrame> for Frame {
+    fn from(frame: &ConFrame) -> Self {
+        crate::chemfiles_selection::project_con_frame(frame)
+    }
+}
+
+impl From<ConFrame> for Frame {
+    fn from(frame: ConFrame) -> Self {
+        Frame::from(&frame)
+    }
+}
+
+/// Same as [`con_frame_from_chemfiles`].
+impl TryFrom<&Frame> for ConFrame {
+    type Error = ChemfilesImportError;
+
+    fn try_from(frame: &Frame) -> Result<Self, Self::Error> {
+        con_frame_from_chemfiles(frame)
+    }
+}
+
+impl TryFrom<Frame> for ConFrame {
+    type Error = ChemfilesImportError;
+
+    fn try_from(frame: Frame) -> Result<Self, Self::Error> {
+        con_frame_from_chemfiles(&frame)
+    }
+}
+
+/// Write `frames` with chemfiles to any format it can write (PDB, LAMMPS
+/// data, mmCIF, …), chosen from the extension of `path` unless `format`
+/// (a chemfiles format name such as `"PDB"`) is given.
+pub fn con_frames_to_trajectory_path<'a, P, I>(
+    path: P,
+    format: Option<&str>,
+    frames: I,
+) -> Result<(), ChemfilesImportError>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    let mut traj = match format {
+        Some(format) => Trajectory::open_with_format(path.as_ref(), 'w', format)?,
+        None => Trajectory::open(path.as_ref(), 'w')?,
+    };
+    for frame in frames {
+        traj.write(&Frame::from(frame))?;
+    }
+    Ok(())
+}
+
 /// Open a trajectory with chemfiles and convert every step to [`ConFrame`].
 pub fn con_frames_from_trajectory_path<P: AsRef<Path>>(
     path: P,
@@ -480,11 +538,7 @@ mod tests {
         assert_eq!(con.header.angles, [90.0, 90.0, 90.0]);
         assert_eq!(con.header.natm_types, 2); // O and H grouped
 
-        let symbols: Vec<&str> = con
-            .atom_data
-            .iter()
-            .map(|a| a.symbol.as_ref())
-            .collect();
+        let symbols = con.symbols();
         assert!(symbols.contains(&"O"));
         assert!(symbols.contains(&"H"));
 
@@ -571,11 +625,7 @@ H  1.0 0.0 0.0
         assert_eq!(frames.len(), 1);
         let con = &frames[0];
         assert_eq!(con.atom_data.len(), 2);
-        let symbols: Vec<&str> = con
-            .atom_data
-            .iter()
-            .map(|a| a.symbol.as_ref())
-            .collect();
+        let symbols = con.symbols();
         assert!(symbols.contains(&"Cu"));
         assert!(symbols.contains(&"H"));
         // XYZ via chemfiles typically has infinite/default cell
@@ -605,7 +655,7 @@ H -0.240  0.927  0.000
         assert_eq!(con.atom_data.len(), 3);
         assert_eq!(con.header.natm_types, 2);
         let positions_ok = con.atom_data.iter().any(|a| {
-            a.symbol.as_ref() == "O" && a.x.abs() < 1e-6 && a.y.abs() < 1e-6 && a.z.abs() < 1e-6
+            con.symbol_of(a) == "O" && a.x.abs() < 1e-6 && a.y.abs() < 1e-6 && a.z.abs() < 1e-6
         });
         assert!(positions_ok, "expected O at origin in converted frame");
     }
@@ -632,4 +682,27 @@ H -0.240  0.927  0.000
             .expect("partial_charge");
         assert!((q - (-0.5)).abs() < 1e-12);
     }
+
+    #[test]
+    fn from_impls_round_trip_and_write_any_format() {
+        let con = ConFrame::try_from(make_water_frame()).expect("import");
+        let chfl = Frame::from(&con);
+        assert_eq!(chfl.size(), con.atom_data.len());
+        assert_eq!(chfl.atom(0).name(), "O");
+        assert!((chfl.atom(0).mass() - con.header.masses_per_type[0]).abs() < 1e-12);
+        let back = ConFrame::try_from(&chfl).expect("reimport");
+        assert_eq!(back.atom_data.len(), con.atom_data.len());
+        for (a, b) in con.atom_data.iter().zip(&back.atom_data) {
+            assert_eq!(con.symbol_of(a), back.symbol_of(b));
+            assert_eq!([a.x, a.y, a.z], [b.x, b.y, b.z]);
+        }
+        assert_eq!(back.header.energy(), Some(-12.5));
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("water.pdb");
+        con_frames_to_trajectory_path(&path, None, [&con, &con]).expect("write pdb");
+        let frames = con_frames_from_trajectory_path(&path).expect("read pdb");
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].atom_data.len(), 3);
+    }
 }
@@ -88,6 +88,8 @@ pub use imp::{
     evaluate_selection_on_frames, parse_selection_string, select_atom_indices,
     select_atom_positions_on_frames,
 };
+#[cfg(feature = "chemfiles")]
+pub(crate) use imp::project_con_frame;
 
 #[cfg(not(feature = "chemfiles"))]
 /// Evaluate a chemfiles selection-language string on a [`ConFrame`].
@@ -11,6 +11,7 @@ use serde_json::Value;
 use crate::chemfiles_import::{
     ChemfilesImportError, CHEMFILES_ATOM_NAMES_KEY, CHEMFILES_ATOM_TYPES_KEY,
 };
+use crate::frame::AtomicFrame;
 use crate::types::ConFrame;
 
 /// Chemfiles display name / atomic type for one CON atom, from optional import sidecars.
@@ -19,7 +20,7 @@ use crate::types::ConFrame;
 /// on import). Falls back to CON `symbol` for both when sidecars are absent.
 fn chemfiles_name_and_type_for_atom(frame: &ConFrame, atom_data_idx: usize) -> (String, String) {
     let atom = &frame.atom_data[atom_data_idx];
-    let symbol = atom.symbol.as_ref().to_string();
+    let symbol = frame.symbol_of(atom).to_string();
     let chfl_idx = atom.atom_id as usize;
     let name = frame
         .header
@@ -84,6 +85,13 @@ pub fn apply_con_bonds_to_chemfiles_frame(frame: &ConFrame, chfl: &mut Frame) {
 use super::{SelectionMatch, SelectionResult};
 
 pub fn chemfiles_frame_from_con_frame(frame: &ConFrame) -> Result<Frame, ChemfilesImportError> {
+    Ok(project_con_frame(frame))
+}
+
+/// Infallible body of [`chemfiles_frame_from_con_frame`], shared with
+/// `impl From<&ConFrame> for Frame`: one chemfiles atom per CON atom, in
+/// `atom_data` order, carrying mass and (when set) charge.
+pub(crate) fn project_con_frame(frame: &ConFrame) -> Frame {
     let mut chfl = Frame::new();
     let cell = frame.header.boxl;
     let angles = frame.header.angles;
@@ -99,7 +107,6 @@ pub fn chemfiles_frame_from_con_frame(frame: &ConFrame) -> Result<Frame, Chemfil
     };
     chfl.set_cell(&unit_cell);
 
-    let n = frame.atom_data.len();
     let mut need_vel = false;
     for atom in &frame.atom_data {
         if atom.velocity.is_some() {
@@ -115,6 +122,12 @@ pub fn chemfiles_frame_from_con_frame(frame: &ConFrame) -> Result<Frame, Chemfil
         let (display_name, atomic_type) = chemfiles_name_and_type_for_atom(frame, data_idx);
         let mut ch_atom = Atom::new(display_name.as_str());
         ch_atom.set_atomic_type(atomic_type.as_str());
+        if let Some(mass) = frame.mass(data_idx) {
+            ch_atom.set_mass(mass);
+        }
+        if let Some(charge) = atom.charge {
+            ch_atom.set_charge(charge);
+        }
         let vel = atom.velocity;
         chfl.add_atom(&ch_atom, [atom.x, atom.y, atom.z], vel);
     }
@@ -132,14 +145,6 @@ pub fn chemfiles_frame_from_con_frame(frame: &ConFrame) -> Result<Frame, Chemfil
         }
     }
 
-    if chfl.size() != n {
-        return Err(ChemfilesImportError::InvalidFrame(format!(
-            "chemfiles frame size {} != con frame {}",
-            chfl.size(),
-            n
-        )));
-    }
-
     // Preserve step from metadata when present.
     if let Some(idx) = frame.header.frame_index() {
         chfl.set_step(idx as usize);
@@ -156,7 +161,7 @@ pub fn chemfiles_frame_from_con_frame(frame: &ConFrame) -> Result<Frame, Chemfil
     // Optional topology: enables bonds:/angles:/is_bonded selections.
     apply_con_bonds_to_chemfiles_frame(frame, &mut chfl);
 
-    Ok(chfl)
+    chfl
 }
 
 /// Evaluate a chemfiles selection-language string on a chemfiles [`Frame`].
@@ -381,7 +386,7 @@ mod tests {
         let con = con_frame_from_chemfiles(&chfl).expect("import");
         let idxs = select_atom_indices("name Cu", &con).expect("select Cu");
         assert_eq!(idxs.len(), 1);
-        assert_eq!(con.atom_data[idxs[0]].symbol.as_ref(), "Cu");
+        assert_eq!(con.symbol(idxs[0]), "Cu");
     }
 
     #[test]
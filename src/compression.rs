@@ -80,9 +80,10 @@ pub fn read_file_contents(path: &Path) -> Result<FileContents, Box<dyn std::erro
 
     match compression {
         Compression::Gzip => {
-            // Re-open and decompress the entire file
+            // Re-open and decompress the entire file. Multi-member so files
+            // grown by appending gzip streams read back in full.
             let file = std::fs::File::open(path)?;
-            let mut decoder = flate2::read::GzDecoder::new(file);
+            let mut decoder = flate2::read::MultiGzDecoder::new(file);
             let mut contents = String::new();
             decoder.read_to_string(&mut contents)?;
             Ok(FileContents::Owned(contents))
@@ -118,6 +119,41 @@ pub fn read_file_contents(path: &Path) -> Result<FileContents, Box<dyn std::erro
     }
 }
 
+/// Reads a whole stream such as stdin, decompressing gzip or zstd input
+/// detected from its magic bytes like [`read_file_contents`] does.
+pub fn read_reader_contents<R: Read>(
+    mut reader: R,
+) -> Result<FileContents, Box<dyn std::error::Error>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let contents = match detect_compression(&bytes) {
+        Compression::Gzip => {
+            let mut contents = String::new();
+            flate2::read::MultiGzDecoder::new(bytes.as_slice()).read_to_string(&mut contents)?;
+            contents
+        }
+        Compression::Zstd => {
+            #[cfg(feature = "zstd")]
+            {
+                let mut contents = String::new();
+                zstd::stream::read::Decoder::new(bytes.as_slice())?
+                    .read_to_string(&mut contents)?;
+                contents
+            }
+            #[cfg(not(feature = "zstd"))]
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "zstd-compressed input detected; rebuild readcon-core with --features zstd",
+                )
+                .into());
+            }
+        }
+        Compression::None => String::from_utf8(bytes)?,
+    };
+    Ok(FileContents::Owned(contents))
+}
+
 /// Holds file contents either as an owned String or a memory-mapped region.
 pub enum FileContents {
     Owned(String),
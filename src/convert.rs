@@ -1,6 +1,6 @@
 //! Foreign path or CON → multi-frame CON write (migration surface).
 //!
-//! Used by the CLI (`readcon-core convert …`) and callable from Rust without
+//! Used by the CLI (`readcon convert …`) and callable from Rust without
 //! inventing a second conversion stack. Non-CON inputs require the `chemfiles`
 //! feature at runtime ([`chemfiles_import::chemfiles_enabled`]).
 
@@ -152,14 +152,9 @@ pub fn convert_path_to_con(input: &Path, output: &Path) -> Result<ConvertReport,
 mod tests {
     use super::*;
     use std::fs;
+    use crate::test_util::fixture_path;
     use std::path::PathBuf;
 
-    fn fixture(name: &str) -> PathBuf {
-        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("resources/test")
-            .join(name)
-    }
-
     #[test]
     fn path_looks_like_con_suffixes() {
         assert!(path_looks_like_con(Path::new("a.con")));
@@ -173,7 +168,7 @@ mod tests {
     fn convert_native_con_roundtrip() {
         let dir = tempfile_dir();
         let out = dir.join("out.con");
-        let report = convert_path_to_con(&fixture("tiny_multi_cuh2.con"), &out).unwrap();
+        let report = convert_path_to_con(&fixture_path("tiny_multi_cuh2.con"), &out).unwrap();
         assert!(report.native_con);
         assert_eq!(report.n_frames, 2);
         assert_eq!(report.n_atoms_last, 4);
@@ -204,7 +199,7 @@ mod tests {
         let symbols: Vec<_> = back[0]
             .atom_data
             .iter()
-            .map(|a| a.symbol.as_ref())
+            .map(|a| back[0].symbol_of(a))
             .collect();
         assert!(symbols.contains(&"O"));
         assert_eq!(symbols.iter().filter(|s| **s == "H").count(), 2);
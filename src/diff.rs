@@ -0,0 +1,336 @@
+//! Structural comparison of two frames.
+//!
+//! [`FrameDiff`] pairs atoms by position in the (type-grouped) atom list, so it
+//! is meant for frames of the same system: reactant vs. saddle vs. product,
+//! or consecutive trajectory snapshots. Displacements are plain Cartesian
+//! differences, or minimum-image ones in the first frame's cell with
+//! [`FrameDiff::compute_minimum_image`] (so an atom that crossed a periodic
+//! boundary is not reported as moving a box length). How close counts as
+//! equal is set by a [`Tolerances`] value.
+
+use crate::cell::Cell;
+use crate::tolerance::Tolerances;
+use crate::types::ConFrame;
+use std::fmt;
+
+/// Per-atom displacement reported by [`FrameDiff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomDelta {
+    /// Index into `atom_data` of both frames.
+    pub index: usize,
+    /// `atom_id` of the atom in the first frame.
+    pub atom_id: u64,
+    /// Symbol of the atom in the first frame.
+    pub symbol: String,
+    /// `b - a` position difference (minimum image when requested).
+    pub displacement: [f64; 3],
+    /// Euclidean norm of `displacement`.
+    pub distance: f64,
+}
+
+/// Differences between two frames; see [`ConFrame::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameDiff {
+    /// Tolerances the comparison used.
+    pub tolerances: Tolerances,
+    /// Atom counts of the two frames.
+    pub natoms: (usize, usize),
+    /// `b - a` cell lengths.
+    pub cell_delta: [f64; 3],
+    /// `b - a` cell angles.
+    pub angles_delta: [f64; 3],
+    /// Indices (over the shared prefix) whose symbols differ.
+    pub symbol_mismatches: Vec<usize>,
+    /// Indices (over the shared prefix) whose fixed flags differ.
+    pub fixed_mismatches: Vec<usize>,
+    /// Atom type indices (over the shared prefix of `masses_per_type`)
+    /// whose masses differ by more than the mass tolerance.
+    pub mass_mismatches: Vec<usize>,
+    /// Atoms that moved by more than the coordinate tolerance, in index
+    /// order.
+    pub moved: Vec<AtomDelta>,
+    /// Largest per-atom displacement over the shared prefix.
+    pub max_displacement: f64,
+    /// Root-mean-square displacement over the shared prefix.
+    pub rms_displacement: f64,
+    /// Metadata keys added, removed or changed, sorted. `energy` is only
+    /// listed when it changed by more than the energy tolerance.
+    pub metadata_changed: Vec<String>,
+    /// `b - a` total energy when both frames carry one.
+    pub energy_delta: Option<f64>,
+}
+
+impl FrameDiff {
+    /// Compares `a` against `b` within `tolerances`.
+    pub fn compute(a: &ConFrame, b: &ConFrame, tolerances: &Tolerances) -> Self {
+        Self::compute_impl(a, b, tolerances, None)
+    }
+
+    /// Like [`compute`](Self::compute), but displacements are wrapped to
+    /// the minimum image in `a`'s cell, periodic along all three axes.
+    /// Falls back to plain differences when that cell is degenerate.
+    pub fn compute_minimum_image(a: &ConFrame, b: &ConFrame, tolerances: &Tolerances) -> Self {
+        Self::compute_impl(a, b, tolerances, a.header.cell())
+    }
+
+    fn compute_impl(
+        a: &ConFrame,
+        b: &ConFrame,
+        tolerances: &Tolerances,
+        cell: Option<Cell>,
+    ) -> Self {
+        let mut cell_delta = [0.0; 3];
+        let mut angles_delta = [0.0; 3];
+        for k in 0..3 {
+            cell_delta[k] = b.header.boxl[k] - a.header.boxl[k];
+            angles_delta[k] = b.header.angles[k] - a.header.angles[k];
+        }
+
+        let mut symbol_mismatches = Vec::new();
+        let mut fixed_mismatches = Vec::new();
+        let mut moved = Vec::new();
+        let mut max_displacement: f64 = 0.0;
+        let mut sum_sq = 0.0;
+        let shared = a.atom_data.len().min(b.atom_data.len());
+        for (index, (pa, pb)) in a.atom_data.iter().zip(&b.atom_data).enumerate() {
+            if a.symbol_of(pa) != b.symbol_of(pb) {
+                symbol_mismatches.push(index);
+            }
+            if pa.fixed != pb.fixed {
+                fixed_mismatches.push(index);
+            }
+            let mut displacement = [pb.x - pa.x, pb.y - pa.y, pb.z - pa.z];
+            if let Some(cell) = &cell {
+                displacement = cell.minimum_image(displacement, [true; 3]);
+            }
+            let d2 = displacement.iter().map(|d| d * d).sum::<f64>();
+            let distance = d2.sqrt();
+            sum_sq += d2;
+            max_displacement = max_displacement.max(distance);
+            if distance > tolerances.coordinate {
+                moved.push(AtomDelta {
+                    index,
+                    atom_id: pa.atom_id,
+                    symbol: a.symbol_of(pa).to_string(),
+                    displacement,
+                    distance,
+                });
+            }
+        }
+        let rms_displacement = if shared > 0 {
+            (sum_sq / shared as f64).sqrt()
+        } else {
+            0.0
+        };
+
+        let mass_mismatches = a
+            .header
+            .masses_per_type
+            .iter()
+            .zip(&b.header.masses_per_type)
+            .enumerate()
+            .filter(|(_, (ma, mb))| (*ma - *mb).abs() > tolerances.mass)
+            .map(|(k, _)| k)
+            .collect();
+
+        let energy_delta = match (a.header.energy(), b.header.energy()) {
+            (Some(ea), Some(eb)) => Some(eb - ea),
+            _ => None,
+        };
+        let energy_within = energy_delta.is_some_and(|de| de.abs() <= tolerances.energy);
+
+        let (ma, mb) = (&a.header.metadata, &b.header.metadata);
+        let mut metadata_changed: Vec<String> = ma
+            .iter()
+            .filter(|(k, v)| mb.get(*k) != Some(*v))
+            .map(|(k, _)| k.clone())
+            .chain(mb.keys().filter(|k| !ma.contains_key(*k)).cloned())
+            .filter(|k| !(energy_within && k == crate::types::meta::ENERGY))
+            .collect();
+        metadata_changed.sort();
+
+        FrameDiff {
+            tolerances: *tolerances,
+            natoms: (a.atom_data.len(), b.atom_data.len()),
+            cell_delta,
+            angles_delta,
+            symbol_mismatches,
+            fixed_mismatches,
+            mass_mismatches,
+            moved,
+            max_displacement,
+            rms_displacement,
+            metadata_changed,
+            energy_delta,
+        }
+    }
+
+    /// Whether the cell (lengths and angles) agrees within the cell
+    /// tolerance.
+    pub fn cell_matches(&self) -> bool {
+        self.cell_delta
+            .iter()
+            .chain(&self.angles_delta)
+            .all(|d| d.abs() <= self.tolerances.cell)
+    }
+
+    /// `true` when the frames agree on atom count, symbols, fixed flags,
+    /// masses, cell, positions and metadata (each within its tolerance).
+    pub fn is_identical(&self) -> bool {
+        self.natoms.0 == self.natoms.1
+            && self.cell_matches()
+            && self.symbol_mismatches.is_empty()
+            && self.fixed_mismatches.is_empty()
+            && self.mass_mismatches.is_empty()
+            && self.moved.is_empty()
+            && self.metadata_changed.is_empty()
+    }
+}
+
+impl fmt::Display for FrameDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_identical() {
+            return write!(
+                f,
+                "frames identical (tol {:e})",
+                self.tolerances.coordinate
+            );
+        }
+        if self.natoms.0 != self.natoms.1 {
+            writeln!(f, "atom count: {} -> {}", self.natoms.0, self.natoms.1)?;
+        }
+        if !self.cell_matches() {
+            writeln!(
+                f,
+                "cell delta: {:?}, angles delta: {:?}",
+                self.cell_delta, self.angles_delta
+            )?;
+        }
+        if !self.symbol_mismatches.is_empty() {
+            writeln!(f, "symbol mismatches at {:?}", self.symbol_mismatches)?;
+        }
+        if !self.fixed_mismatches.is_empty() {
+            writeln!(f, "fixed-flag mismatches at {:?}", self.fixed_mismatches)?;
+        }
+        if !self.mass_mismatches.is_empty() {
+            writeln!(f, "mass mismatches at types {:?}", self.mass_mismatches)?;
+        }
+        if !self.metadata_changed.is_empty() {
+            writeln!(f, "metadata changed: {}", self.metadata_changed.join(", "))?;
+        }
+        if let Some(de) = self.energy_delta {
+            writeln!(f, "energy delta: {de:.6}")?;
+        }
+        write!(
+            f,
+            "{} atom(s) moved; max {:.6}, rms {:.6}",
+            self.moved.len(),
+            self.max_displacement,
+            self.rms_displacement
+        )
+    }
+}
+
+impl ConFrame {
+    /// Structural diff against `other` with one `tolerance` for every
+    /// quantity; see [`FrameDiff`].
+    pub fn diff(&self, other: &ConFrame, tolerance: f64) -> FrameDiff {
+        FrameDiff::compute(self, other, &Tolerances::uniform(tolerance))
+    }
+
+    /// Structural diff against `other` within `tolerances`.
+    pub fn diff_with(&self, other: &ConFrame, tolerances: &Tolerances) -> FrameDiff {
+        FrameDiff::compute(self, other, tolerances)
+    }
+
+    /// Whether `other` describes the same frame within `tolerances`
+    /// ([`FrameDiff::is_identical`]).
+    pub fn approx_eq(&self, other: &ConFrame, tolerances: &Tolerances) -> bool {
+        self.diff_with(other, tolerances).is_identical()
+    }
+}
+
+/// Indices of the frames to keep when dropping near-duplicates: each frame
+/// is kept unless it is [`ConFrame::approx_eq`] to an earlier kept frame.
+/// Quadratic in the number of kept frames.
+pub fn dedup_indices(frames: &[ConFrame], tolerances: &Tolerances) -> Vec<usize> {
+    let mut kept: Vec<usize> = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        if !kept.iter().any(|&k| frames[k].approx_eq(frame, tolerances)) {
+            kept.push(i);
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::fixture_frame;
+
+    #[test]
+    fn self_diff_is_identical() {
+        let f = fixture_frame("tiny_cuh2.con");
+        let d = f.diff(&f, 1e-9);
+        assert!(d.is_identical());
+        assert_eq!(d.max_displacement, 0.0);
+        assert!(d.to_string().starts_with("frames identical"));
+    }
+
+    #[test]
+    fn reports_moved_atom_and_metadata() {
+        let a = fixture_frame("tiny_cuh2.con");
+        let mut b = a.clone();
+        b.atom_data[1].x += 0.5;
+        b.header.set_energy(-1.0);
+        let d = a.diff(&b, 1e-6);
+        assert!(!d.is_identical());
+        assert_eq!(d.moved.len(), 1);
+        assert_eq!(d.moved[0].index, 1);
+        assert!((d.moved[0].distance - 0.5).abs() < 1e-12);
+        assert!((d.max_displacement - 0.5).abs() < 1e-12);
+        assert!(d.metadata_changed.contains(&"energy".to_string()));
+    }
+
+    #[test]
+    fn minimum_image_ignores_boundary_crossing() {
+        use super::FrameDiff;
+        use crate::tolerance::Tolerances;
+        let a = fixture_frame("tiny_cuh2.con");
+        let mut b = a.clone();
+        b.atom_data[0].x += a.header.boxl[0] + 0.25;
+        let tol = Tolerances::uniform(1e-6);
+        let plain = FrameDiff::compute(&a, &b, &tol);
+        assert!((plain.moved[0].distance - (a.header.boxl[0] + 0.25)).abs() < 1e-9);
+        let mic = FrameDiff::compute_minimum_image(&a, &b, &tol);
+        assert_eq!(mic.moved.len(), 1);
+        assert!((mic.moved[0].distance - 0.25).abs() < 1e-9);
+        assert!((mic.moved[0].displacement[0] - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tolerances_apply_per_quantity() {
+        use super::dedup_indices;
+        use crate::tolerance::Tolerances;
+        let mut a = fixture_frame("tiny_cuh2.con");
+        a.header.set_energy(-10.0);
+        let mut b = a.clone();
+        b.atom_data[0].x += 1e-4;
+        b.header.set_energy(-10.0 + 1e-4);
+        b.header.masses_per_type[0] += 1e-5;
+
+        let tol = Tolerances::default();
+        let d = a.diff_with(&b, &tol);
+        assert_eq!(d.moved.len(), 1);
+        assert!(d.metadata_changed.contains(&"energy".to_string()));
+        assert!(d.mass_mismatches.is_empty());
+
+        let loose = tol.with_coordinate(1e-3).with_energy(1e-3);
+        assert!(a.approx_eq(&b, &loose));
+        assert!(!a.approx_eq(&b, &Tolerances::exact()));
+
+        let mut c = a.clone();
+        c.header.masses_per_type[0] += 1.0;
+        assert_eq!(a.diff_with(&c, &loose).mass_mismatches, vec![0]);
+        assert_eq!(dedup_indices(&[a.clone(), b, c, a], &loose), vec![0, 2]);
+    }
+}
@@ -0,0 +1,214 @@
+//! Ensemble averaging over frames of one system.
+//!
+//! [`average_frames`] pairs atoms by position in `atom_data` (as
+//! [`crate::diff::FrameDiff`] does) and averages them in fractional
+//! coordinates. Along periodic axes the mean is circular: each fractional
+//! coordinate is mapped to an angle on the unit circle, the unit vectors are
+//! summed, and the mean angle is mapped back. An atom hopping across a cell
+//! face therefore averages to the face rather than to the cell centre.
+//! Non-periodic axes use the arithmetic mean.
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::types::ConFrame;
+use std::f64::consts::TAU;
+
+/// Result of [`average_frames`].
+#[derive(Debug, Clone)]
+pub struct EnsembleAverage {
+    /// Copy of the first frame with positions replaced by the mean
+    /// structure. Cell, metadata and the other per-atom sections are those
+    /// of the first frame.
+    pub mean: ConFrame,
+    /// Per-atom mean squared (minimum-image) deviation from the mean
+    /// position, in squared length units, `atom_data` order.
+    pub variance: Vec<f64>,
+    /// Number of frames averaged.
+    pub nframes: usize,
+}
+
+/// Mean structure and per-atom positional variance of `frames`.
+///
+/// All frames must have the same atom count and symbols in the same order.
+/// Each frame is converted to fractional coordinates with its own [`Cell`];
+/// the mean is mapped back to Cartesian with the first frame's cell.
+/// Periodicity comes from the first frame's `pbc` metadata, defaulting to
+/// fully periodic.
+///
+/// Errors are [`ParseError::ValidationError`].
+///
+/// # Example
+/// ```
+/// use readcon_core::ensemble::average_frames;
+/// use readcon_core::types::ConFrameBuilder;
+/// let frame = |x: f64| {
+///     let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+///     b.add_atom("H", x, 5.0, 5.0, [false; 3], 0, 1.008);
+///     b.build()
+/// };
+/// // Either side of the x = 0 face; the periodic mean sits on the face.
+/// let avg = average_frames(&[frame(9.9), frame(0.1)]).unwrap();
+/// let x = avg.mean.atom_data[0].x;
+/// assert!(x.abs() < 1e-9 || (x - 10.0).abs() < 1e-9);
+/// assert!((avg.variance[0] - 0.01).abs() < 1e-9);
+/// ```
+pub fn average_frames(frames: &[ConFrame]) -> Result<EnsembleAverage, ParseError> {
+    let Some(first) = frames.first() else {
+        return Err(ParseError::ValidationError(
+            "average_frames needs at least one frame".into(),
+        ));
+    };
+    let natoms = first.atom_data.len();
+    for (k, frame) in frames.iter().enumerate().skip(1) {
+        if frame.atom_data.len() != natoms {
+            return Err(ParseError::ValidationError(format!(
+                "frame {k} has {} atoms, frame 0 has {natoms}",
+                frame.atom_data.len()
+            )));
+        }
+        if let Some(i) = (0..natoms).find(|&i| frame.symbol(i) != first.symbol(i)) {
+            return Err(ParseError::ValidationError(format!(
+                "frame {k} atom {i}: symbol {} differs from {} in frame 0",
+                frame.symbol(i),
+                first.symbol(i)
+            )));
+        }
+    }
+    let pbc = first.header.pbc().unwrap_or([true; 3]);
+    let cells = frames
+        .iter()
+        .enumerate()
+        .map(|(k, f)| {
+            f.header.cell().ok_or_else(|| {
+                ParseError::ValidationError(format!("frame {k}: cell is degenerate or not finite"))
+            })
+        })
+        .collect::<Result<Vec<Cell>, ParseError>>()?;
+
+    let nf = frames.len() as f64;
+    let mut mean = first.clone();
+    let mut variance = Vec::with_capacity(natoms);
+    let mut fracs = vec![[0.0; 3]; frames.len()];
+    for i in 0..natoms {
+        for (k, frame) in frames.iter().enumerate() {
+            let a = &frame.atom_data[i];
+            fracs[k] = cells[k].cartesian_to_fractional([a.x, a.y, a.z]);
+        }
+        let mut mean_frac = [0.0; 3];
+        for axis in 0..3 {
+            mean_frac[axis] = if pbc[axis] {
+                let (s, c) = fracs.iter().fold((0.0, 0.0), |(s, c), f| {
+                    let t = TAU * f[axis];
+                    (s + t.sin(), c + t.cos())
+                });
+                // Uniformly spread samples have no preferred angle; fall back
+                // to the first frame rather than returning atan2(0, 0).
+                if s.hypot(c) < 1e-12 * nf {
+                    fracs[0][axis]
+                } else {
+                    s.atan2(c).rem_euclid(TAU) / TAU
+                }
+            } else {
+                fracs.iter().map(|f| f[axis]).sum::<f64>() / nf
+            };
+        }
+        let mut sum_sq = 0.0;
+        for (k, f) in fracs.iter().enumerate() {
+            let mut d = [0.0; 3];
+            for axis in 0..3 {
+                d[axis] = f[axis] - mean_frac[axis];
+                if pbc[axis] {
+                    d[axis] -= d[axis].round();
+                }
+            }
+            let dc = cells[k].fractional_to_cartesian(d);
+            sum_sq += dc.iter().map(|v| v * v).sum::<f64>();
+        }
+        variance.push(sum_sq / nf);
+
+        let p = cells[0].fractional_to_cartesian(mean_frac);
+        let atom = &mut mean.atom_data[i];
+        atom.x = p[0];
+        atom.y = p[1];
+        atom.z = p[2];
+        if mean.positions.nrows() == natoms {
+            mean.positions.set_f64_row(i, p);
+        }
+    }
+    Ok(EnsembleAverage {
+        mean,
+        variance,
+        nframes: frames.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    fn two_atoms(h: [f64; 3], cu: [f64; 3], angles: [f64; 3]) -> ConFrame {
+        let mut b = ConFrameBuilder::new([10.0, 12.0, 14.0], angles);
+        b.add_atom("H", h[0], h[1], h[2], [false; 3], 0, 1.008);
+        b.add_atom("Cu", cu[0], cu[1], cu[2], [false; 3], 1, 63.546);
+        b.build()
+    }
+
+    #[test]
+    fn arithmetic_mean_inside_cell() {
+        let frames = [
+            two_atoms([1.0, 2.0, 3.0], [5.0, 5.0, 5.0], [90.0; 3]),
+            two_atoms([3.0, 2.0, 3.0], [5.0, 5.0, 5.0], [90.0; 3]),
+        ];
+        let avg = average_frames(&frames).unwrap();
+        assert_eq!(avg.nframes, 2);
+        let h = &avg.mean.atom_data[0];
+        assert!((h.x - 2.0).abs() < 1e-9);
+        assert!((avg.variance[0] - 1.0).abs() < 1e-9);
+        assert!(avg.variance[1].abs() < 1e-12);
+        assert!((avg.mean.positions.as_f64_row(0)[0] - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn periodic_mean_across_triclinic_face() {
+        let angles = [80.0, 95.0, 110.0];
+        let probe = two_atoms([0.0; 3], [4.0; 3], angles);
+        let cell = probe.header.cell().unwrap();
+
+        // H at fractional a = +0.02 and a = 0.98: either side of the a = 0 face.
+        let lo = cell.fractional_to_cartesian([0.02, 0.3, 0.3]);
+        let hi = cell.fractional_to_cartesian([0.98, 0.3, 0.3]);
+        let avg = average_frames(&[
+            two_atoms(lo, [4.0; 3], angles),
+            two_atoms(hi, [4.0; 3], angles),
+        ])
+        .unwrap();
+        let h = &avg.mean.atom_data[0];
+        let mean_f = cell.cartesian_to_fractional([h.x, h.y, h.z]);
+        let wrapped = mean_f[0].rem_euclid(1.0);
+        assert!(wrapped < 1e-9 || (1.0 - wrapped) < 1e-9, "{mean_f:?}");
+        assert!((mean_f[1] - 0.3).abs() < 1e-9);
+        // 0.02 of |a| = 10 is 0.2 from the mean in both frames.
+        assert!((avg.variance[0] - 0.04).abs() < 1e-9);
+    }
+
+    #[test]
+    fn non_periodic_axis_uses_arithmetic_mean() {
+        let mut a = two_atoms([0.5, 1.0, 1.0], [4.0; 3], [90.0; 3]);
+        a.header.set_pbc([false, true, true]);
+        let mut b = a.clone();
+        b.atom_data[0].x = 9.5;
+        let avg = average_frames(&[a, b]).unwrap();
+        assert!((avg.mean.atom_data[0].x - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_mismatched_frames() {
+        assert!(average_frames(&[]).is_err());
+        let a = two_atoms([0.0; 3], [1.0; 3], [90.0; 3]);
+        let mut b = a.clone();
+        b.set_symbol(0, "He").unwrap();
+        let err = average_frames(&[a, b]).unwrap_err().to_string();
+        assert!(err.contains("symbol"), "{err}");
+    }
+}
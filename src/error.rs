@@ -17,6 +17,12 @@ pub enum ParseError {
     IncompleteSection(String),
     UnknownSection(String),
     ValidationError(String),
+    /// An `atom_id` column that is not a non-negative integer, or too large
+    /// for `u64`. Holds the offending token.
+    InvalidAtomId(String),
+    /// A `fixed_flag` column that is not an integer from 0 to 255. Holds the
+    /// offending token.
+    InvalidFixedFlag(String),
     /// An in-place builder mutation
     /// (`ConFrameBuilder::set_atom_position` / `set_atom_velocity` /
     /// `set_atom_force` / `set_atom_energy` / `set_atom_fixed` /
@@ -71,6 +77,12 @@ impl fmt::Display for ParseError {
             ParseError::ValidationError(msg) => {
                 write!(f, "CON validation failed: {msg}")
             }
+            ParseError::InvalidAtomId(token) => {
+                write!(f, "invalid atom_id {token:?}: expected a non-negative integer")
+            }
+            ParseError::InvalidFixedFlag(token) => {
+                write!(f, "invalid fixed_flag {token:?}: expected an integer bitmask")
+            }
             ParseError::IndexOutOfBounds { index, len } => {
                 write!(
                     f,
@@ -83,6 +95,85 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// Input that [`crate::parser::ParseMode::Permissive`] accepted but
+/// [`crate::parser::ParseMode::Strict`] rejects. Collected by
+/// [`crate::iterators::ConFrameIterator::warnings`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseWarning {
+    /// `lines` coordinate lines of the frame carried columns past the fifth,
+    /// kept in [`crate::types::AtomDatum::extra`].
+    ExtraColumns { lines: usize },
+    /// Component `component` (1-based) went straight from its symbol line
+    /// to its atoms, without a `Coordinates of Component` line.
+    MissingComponentLabel { component: usize },
+    /// Atom `atom` (0-based within its frame) had a `fixed_flag` that is not
+    /// an integer; it was truncated toward zero.
+    FractionalFixedFlag { atom: usize, value: f64 },
+    /// Text from byte `offset` to the end of the input neither starts a
+    /// frame nor precedes one, and was ignored.
+    TrailingGarbage { offset: usize },
+    /// Component `component` (1-based) has a symbol that is neither an
+    /// element nor the `X` placeholder.
+    UnknownSymbol { component: usize, symbol: String },
+    /// More than one atom of the frame carries `atom_id`.
+    DuplicateAtomId { atom_id: u64 },
+    /// `lines` whitespace-only lines between frames were skipped, or kept
+    /// in [`crate::types::ConFrame::trailing_lines`] when they follow one.
+    BlankLines { lines: usize },
+    /// `lines` unrecognized lines after the frame were kept in
+    /// [`crate::types::ConFrame::trailing_lines`].
+    TrailingLines { lines: usize },
+    /// Component `component` (1-based) has a mass more than
+    /// [`crate::masses::MASS_WARNING_THRESHOLD`] amu from the standard
+    /// weight of its symbol.
+    MassMismatch {
+        component: usize,
+        symbol: String,
+        mass: f64,
+        standard: f64,
+    },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::ExtraColumns { lines } => {
+                write!(f, "{lines} coordinate lines have columns past the fifth")
+            }
+            ParseWarning::MissingComponentLabel { component } => {
+                write!(f, "component {component} has no \"Coordinates of Component\" line")
+            }
+            ParseWarning::FractionalFixedFlag { atom, value } => {
+                write!(f, "atom {atom} has non-integer fixed_flag {value}")
+            }
+            ParseWarning::TrailingGarbage { offset } => {
+                write!(f, "ignored trailing text from byte {offset}")
+            }
+            ParseWarning::UnknownSymbol { component, symbol } => {
+                write!(f, "component {component} has unknown symbol {symbol:?}")
+            }
+            ParseWarning::DuplicateAtomId { atom_id } => {
+                write!(f, "atom_id {atom_id} is used by more than one atom")
+            }
+            ParseWarning::BlankLines { lines } => {
+                write!(f, "{lines} blank lines between frames")
+            }
+            ParseWarning::TrailingLines { lines } => {
+                write!(f, "kept {lines} unrecognized lines after the frame")
+            }
+            ParseWarning::MassMismatch {
+                component,
+                symbol,
+                mass,
+                standard,
+            } => write!(
+                f,
+                "component {component} has mass {mass} for {symbol}, standard {standard}"
+            ),
+        }
+    }
+}
+
 impl From<ParseFloatError> for ParseError {
     fn from(e: ParseFloatError) -> Self {
         ParseError::InvalidNumberFormat(e.to_string())
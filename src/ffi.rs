@@ -1,7 +1,10 @@
-use crate::helpers::symbol_to_atomic_number;
+use crate::error::ParseError;
+use crate::helpers::ElementRegistry;
 use crate::iterators::{self, ConFrameIterator};
-use crate::types::{ConFrame, ConFrameBuilder, meta};
+use crate::storage_dtype::Array2Storage;
+use crate::types::{AtomDatum, ConFrame, ConFrameBuilder, meta};
 use crate::writer::ConFrameWriter;
+use std::cell::RefCell;
 use std::ffi::{CStr, CString, c_char};
 use std::fs::File;
 use std::path::Path;
@@ -56,8 +59,9 @@ pub unsafe extern "C" fn rkr_frame_atom_index_by_id(
     }
 }
 /// Returns the atomic number for a chemical symbol, or 0 if the symbol
-/// is unknown or `symbol` is NULL. Lookup covers H..U (Z = 1..=92) and
-/// is case-sensitive: "Fe" works, "fe" does not.
+/// is unknown or `symbol` is NULL. Lookup covers H..U (Z = 1..=92) plus
+/// labels added with [`rkr_register_element`], and is case-sensitive:
+/// "Fe" works, "fe" does not.
 ///
 /// # Safety
 ///
@@ -69,7 +73,7 @@ pub unsafe extern "C" fn rkr_symbol_to_z(symbol: *const c_char) -> u64 {
         return 0;
     }
     match unsafe { CStr::from_ptr(symbol) }.to_str() {
-        Ok(s) => symbol_to_atomic_number(s),
+        Ok(s) => elements().atomic_number(s).unwrap_or(0),
         Err(_) => 0,
     }
 }
@@ -100,6 +104,70 @@ pub extern "C" fn rkr_z_to_symbol(z: u64) -> *const c_char {
     let idx = if (1..=92).contains(&z) { z as usize } else { 0 };
     TABLE[idx].as_ptr() as *const c_char
 }
+//=============================================================================
+// Element Registry
+//=============================================================================
+/// Process-wide custom element labels, consulted by [`rkr_symbol_to_z`],
+/// [`rkr_frame_to_c_frame`] and [`rkr_frame_check_species`].
+static ELEMENTS: std::sync::LazyLock<std::sync::RwLock<ElementRegistry>> =
+    std::sync::LazyLock::new(Default::default);
+
+/// Read access to the process-wide registry.
+fn elements() -> std::sync::RwLockReadGuard<'static, ElementRegistry> {
+    ELEMENTS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Register (or replace) a custom species label such as "Cu1" or "OW"
+/// with its atomic number and mass in amu. `mass <= 0` uses the standard
+/// weight of `z`; `z == 0` marks a non-element site and needs a mass.
+/// Returns `RKR_STATUS_VALIDATION_ERROR` for a label with whitespace,
+/// `z > 92`, or a non-finite mass. Applies process-wide.
+///
+/// # Safety
+/// `label` must be a valid NUL-terminated C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_register_element(
+    label: *const c_char,
+    z: u64,
+    mass: f64,
+) -> RKRStatus {
+    if label.is_null() {
+        return RKRStatus::RKR_STATUS_NULL_POINTER;
+    }
+    let Ok(label) = (unsafe { CStr::from_ptr(label) }).to_str() else {
+        return RKRStatus::RKR_STATUS_INVALID_UTF8;
+    };
+    let mass = (mass.is_nan() || mass > 0.0).then_some(mass);
+    let mut registry = ELEMENTS.write().unwrap_or_else(|e| e.into_inner());
+    match registry.register(label, z, mass) {
+        Ok(_) => RKRStatus::RKR_STATUS_SUCCESS,
+        Err(_) => RKRStatus::RKR_STATUS_VALIDATION_ERROR,
+    }
+}
+
+/// Drop every label added with [`rkr_register_element`].
+#[unsafe(no_mangle)]
+pub extern "C" fn rkr_clear_elements() -> RKRStatus {
+    *ELEMENTS.write().unwrap_or_else(|e| e.into_inner()) = ElementRegistry::new();
+    RKRStatus::RKR_STATUS_SUCCESS
+}
+
+/// Returns `RKR_STATUS_VALIDATION_ERROR` when any atom's symbol is
+/// neither a known element nor a registered label, so callers can
+/// refuse frames that [`rkr_frame_to_c_frame`] would map to Z = 0.
+///
+/// # Safety
+/// `frame_handle` must be a valid frame handle or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_frame_check_species(frame_handle: *const RKRConFrame) -> RKRStatus {
+    let Some(frame) = (unsafe { (frame_handle as *const ConFrame).as_ref() }) else {
+        return RKRStatus::RKR_STATUS_NULL_POINTER;
+    };
+    match elements().check_symbols(frame.atom_data.iter().map(|a| frame.symbol_of(a))) {
+        Ok(()) => RKRStatus::RKR_STATUS_SUCCESS,
+        Err(_) => RKRStatus::RKR_STATUS_VALIDATION_ERROR,
+    }
+}
 /// Returns the spec version stored in a parsed frame's header.
 /// Returns 0 on error (null handle).
 #[unsafe(no_mangle)]
@@ -318,7 +386,7 @@ pub extern "C" fn rkr_frame_neb_band(frame_handle: *const RKRConFrame) -> u64 {
 /// Error codes for RKR functions.
 #[repr(C)]
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RKRStatus {
     /// Function completed successfully.
     RKR_STATUS_SUCCESS = 0,
@@ -350,6 +418,9 @@ pub enum RKRStatus {
     RKR_STATUS_DEVICE_MISMATCH = -12,
     /// Build cannot allocate on the requested non-CPU device (use caller-supplied buffers).
     RKR_STATUS_DEVICE_ALLOC_UNSUPPORTED = -13,
+    /// Input text is not a valid CON/convel frame (see
+    /// [`rkr_last_error_message`] for the line and reason).
+    RKR_STATUS_PARSE_ERROR = -14,
 }
 /// Number of optional frame topology bonds (`metadata["bonds"]`), or 0 if absent.
 ///
@@ -428,8 +499,162 @@ pub extern "C" fn rkr_status_message(status: RKRStatus) -> *const c_char {
         RKRStatus::RKR_STATUS_DEVICE_ALLOC_UNSUPPORTED => {
             c"device allocation unsupported in this build".as_ptr()
         }
+        RKRStatus::RKR_STATUS_PARSE_ERROR => c"malformed frame".as_ptr(),
+    }
+}
+//=============================================================================
+// Last Error (thread-local)
+//=============================================================================
+thread_local! {
+    /// Status and message of the last failure recorded on this thread.
+    static LAST_ERROR: RefCell<Option<(RKRStatus, CString)>> = const { RefCell::new(None) };
+}
+/// Records `message` as this thread's last error and returns `status`.
+fn set_last_error(status: RKRStatus, message: impl std::fmt::Display) -> RKRStatus {
+    let message = CString::new(message.to_string().replace('\0', "\\0")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some((status, message)));
+    status
+}
+/// [`set_last_error`] for functions that report failure as NULL.
+fn null_with_error<T>(status: RKRStatus, message: impl std::fmt::Display) -> *mut T {
+    set_last_error(status, message);
+    ptr::null_mut()
+}
+fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+/// The status code that best describes a Rust-side error.
+fn error_status(e: &(dyn std::error::Error + 'static)) -> RKRStatus {
+    if let Some(e) = e.downcast_ref::<ParseError>() {
+        match e {
+            ParseError::InvalidMetadataJson(_) => RKRStatus::RKR_STATUS_INVALID_JSON,
+            ParseError::IndexOutOfBounds { .. } => RKRStatus::RKR_STATUS_INDEX_OUT_OF_BOUNDS,
+            _ => RKRStatus::RKR_STATUS_PARSE_ERROR,
+        }
+    } else if e.is::<std::io::Error>() {
+        RKRStatus::RKR_STATUS_IO_ERROR
+    } else if e.is::<std::str::Utf8Error>() {
+        RKRStatus::RKR_STATUS_INVALID_UTF8
+    } else {
+        RKRStatus::RKR_STATUS_PARSE_ERROR
+    }
+}
+/// Records a malformed frame that starts on (1-based) line `line` of the
+/// input; see [`ConFrameIterator::frame_line`].
+fn set_frame_error(e: &ParseError, line: usize) -> RKRStatus {
+    set_last_error(
+        error_status(e),
+        format!("frame starting at line {line}: {e}"),
+    )
+}
+/// Status of the last failure recorded on the calling thread, or
+/// `RKR_STATUS_SUCCESS` if there is none.
+///
+/// Readers, iterators and writer constructors that signal failure with a
+/// NULL return (`read_con_*_iterator`, [`con_frame_iterator_next`],
+/// `rkr_read_*`, `create_writer_*`) clear the record on entry and set it on
+/// failure, as do [`con_frame_iterator_try_next`], [`rkr_writer_extend`],
+/// [`rkr_writer_write_cframe`] and [`rkr_frame_select`]. After one of those
+/// returns NULL, this tells "file not found" (`RKR_STATUS_IO_ERROR`) from
+/// "malformed frame" (`RKR_STATUS_PARSE_ERROR`) and from the end of an
+/// iterator (`RKR_STATUS_SUCCESS`).
+#[unsafe(no_mangle)]
+pub extern "C" fn rkr_last_error_status() -> RKRStatus {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(RKRStatus::RKR_STATUS_SUCCESS, |(status, _)| *status)
+    })
+}
+/// Human-readable description of the last failure recorded on the calling
+/// thread (e.g. `"frames.con: frame starting at line 1234: invalid number
+/// format: ..."`), or NULL if there is none. See [`rkr_last_error_status`].
+///
+/// The string is owned by the library: do NOT free it. It stays valid until
+/// the next readcon call on the same thread; copy it to keep it longer.
+#[unsafe(no_mangle)]
+pub extern "C" fn rkr_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |(_, message)| message.as_ptr())
+    })
+}
+/// Forgets the calling thread's last error.
+#[unsafe(no_mangle)]
+pub extern "C" fn rkr_clear_last_error() {
+    clear_last_error();
+}
+//=============================================================================
+// Process-wide Defaults
+//=============================================================================
+/// Process-wide defaults consulted by FFI calls that take no explicit
+/// option. Zero in every field means "built-in behavior", so a
+/// zero-initialized struct restores the library defaults.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RKROptions {
+    /// Decimal places for writers created without an explicit precision
+    /// (`create_writer_from_path_c`, `create_writer_gzip_c`, ...).
+    /// 0 keeps the writer's built-in default.
+    pub precision: u8,
+    /// Nonzero: iterators and whole-file readers skip blank lines and `#`
+    /// comments between frames and accept Fortran `D` exponents (see
+    /// [`crate::parser::ParserOptions::tolerant`]).
+    pub lenient: u8,
+    /// Rayon workers for `rkr_read_all_frames` in `parallel` builds.
+    /// 0 uses the global pool; ignored when `lenient` is set (sequential).
+    pub num_threads: u32,
+}
+
+static DEFAULT_OPTIONS: std::sync::RwLock<RKROptions> = std::sync::RwLock::new(RKROptions {
+    precision: 0,
+    lenient: 0,
+    num_threads: 0,
+});
+
+/// Snapshot of the current process-wide defaults.
+fn default_options() -> RKROptions {
+    *DEFAULT_OPTIONS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Parser options implied by [`RKROptions::lenient`].
+fn default_parser_options() -> crate::parser::ParserOptions {
+    if default_options().lenient != 0 {
+        crate::parser::ParserOptions::tolerant()
+    } else {
+        crate::parser::ParserOptions::default()
     }
 }
+
+/// Store process-wide defaults honored by subsequent FFI calls on any
+/// thread. NULL resets to the built-in defaults. Calls already in flight
+/// keep the values they started with.
+///
+/// # Safety
+/// `opts` must be NULL or point to a valid `RKROptions`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_set_default_options(opts: *const RKROptions) -> RKRStatus {
+    let next = match unsafe { opts.as_ref() } {
+        Some(o) => *o,
+        None => RKROptions::default(),
+    };
+    *DEFAULT_OPTIONS.write().unwrap_or_else(|e| e.into_inner()) = next;
+    RKRStatus::RKR_STATUS_SUCCESS
+}
+
+/// Copy the current process-wide defaults into `out`.
+///
+/// # Safety
+/// `out` must be a valid, writable `RKROptions` pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_get_default_options(out: *mut RKROptions) -> RKRStatus {
+    if out.is_null() {
+        return RKRStatus::RKR_STATUS_NULL_POINTER;
+    }
+    unsafe { *out = default_options() };
+    RKRStatus::RKR_STATUS_SUCCESS
+}
 /// An opaque handle to a full, lossless Rust `ConFrame` object.
 /// The C/C++ side needs to treat this as a void pointer
 #[repr(C)]
@@ -495,6 +720,8 @@ pub struct CAtom {
 pub struct CConFrameIterator {
     iterator: *mut ConFrameIterator<'static>,
     file_contents: *mut String,
+    /// Frames (or errors) consumed since creation or the last reset.
+    position: usize,
 }
 
 /// Build a path/buffer-backed C iterator from an owned CON text buffer.
@@ -502,10 +729,13 @@ fn c_iterator_from_owned_string(contents: String) -> *mut CConFrameIterator {
     let file_contents_box = Box::new(contents);
     let file_contents_ptr = Box::into_raw(file_contents_box);
     let static_file_contents: &'static str = unsafe { &*file_contents_ptr };
-    let iterator = Box::new(ConFrameIterator::new(static_file_contents));
+    let iterator = Box::new(
+        ConFrameIterator::new(static_file_contents).options(default_parser_options()),
+    );
     let c_iterator = Box::new(CConFrameIterator {
         iterator: Box::into_raw(iterator),
         file_contents: file_contents_ptr,
+        position: 0,
     });
     Box::into_raw(c_iterator)
 }
@@ -529,19 +759,24 @@ fn c_iterator_from_owned_string(contents: String) -> *mut CConFrameIterator {
 pub unsafe extern "C" fn read_con_file_iterator(
     filename_c: *const c_char,
 ) -> *mut CConFrameIterator {
-    if filename_c.is_null() {
-        return ptr::null_mut();
-    }
-    let filename = match unsafe { CStr::from_ptr(filename_c).to_str() } {
-        Ok(s) => s,
-        Err(_) => return ptr::null_mut(),
+    clear_last_error();
+    let filename = match unsafe { cstr_path(filename_c) } {
+        Some(s) => s,
+        None => return ptr::null_mut(),
     };
     let owned = match crate::compression::read_file_contents(Path::new(filename)) {
         Ok(fc) => match fc.as_str() {
             Ok(s) => s.to_owned(),
-            Err(_) => return ptr::null_mut(),
+            Err(e) => {
+                return null_with_error(
+                    RKRStatus::RKR_STATUS_INVALID_UTF8,
+                    format!("{filename}: {e}"),
+                );
+            }
         },
-        Err(_) => return ptr::null_mut(),
+        Err(e) => {
+            return null_with_error(RKRStatus::RKR_STATUS_IO_ERROR, format!("{filename}: {e}"));
+        }
     };
     c_iterator_from_owned_string(owned)
 }
@@ -557,12 +792,13 @@ pub unsafe extern "C" fn read_con_file_iterator(
 pub unsafe extern "C" fn read_con_string_iterator(
     contents_c: *const c_char,
 ) -> *mut CConFrameIterator {
+    clear_last_error();
     if contents_c.is_null() {
-        return ptr::null_mut();
+        return null_with_error(RKRStatus::RKR_STATUS_NULL_POINTER, "contents is NULL");
     }
     let contents = match unsafe { CStr::from_ptr(contents_c).to_str() } {
         Ok(s) => s.to_owned(),
-        Err(_) => return ptr::null_mut(),
+        Err(e) => return null_with_error(RKRStatus::RKR_STATUS_INVALID_UTF8, e),
     };
     c_iterator_from_owned_string(contents)
 }
@@ -570,6 +806,7 @@ pub unsafe extern "C" fn read_con_string_iterator(
 /// Iterate frames from a byte buffer (not necessarily null-terminated).
 ///
 /// `len` is the number of bytes at `data`. Bytes must be valid UTF-8 CON text.
+/// The bytes are copied, so the caller may release `data` once this returns.
 ///
 /// # Safety
 /// `data` must be valid for `len` bytes if non-null and `len > 0`.
@@ -578,8 +815,9 @@ pub unsafe extern "C" fn read_con_buffer_iterator(
     data: *const u8,
     len: usize,
 ) -> *mut CConFrameIterator {
+    clear_last_error();
     if data.is_null() && len > 0 {
-        return ptr::null_mut();
+        return null_with_error(RKRStatus::RKR_STATUS_NULL_POINTER, "data is NULL");
     }
     if len == 0 {
         return c_iterator_from_owned_string(String::new());
@@ -587,28 +825,148 @@ pub unsafe extern "C" fn read_con_buffer_iterator(
     let slice = unsafe { std::slice::from_raw_parts(data, len) };
     let contents = match std::str::from_utf8(slice) {
         Ok(s) => s.to_owned(),
-        Err(_) => return ptr::null_mut(),
+        Err(e) => return null_with_error(RKRStatus::RKR_STATUS_INVALID_UTF8, e),
     };
     c_iterator_from_owned_string(contents)
 }
 /// Reads the next frame from the iterator, returning an opaque handle.
 /// The caller OWNS the returned handle and must free it with `free_rkr_frame`.
 ///
+/// NULL means either the end of the input or a malformed frame;
+/// [`rkr_last_error_status`] is `RKR_STATUS_SUCCESS` only in the first case.
+/// [`con_frame_iterator_try_next`] returns the status directly.
+///
 /// # Safety
 /// iterator must be valid. The caller takes ownership of the returned frame.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn con_frame_iterator_next(
     iterator: *mut CConFrameIterator,
 ) -> *mut RKRConFrame {
-    if iterator.is_null() {
-        return ptr::null_mut();
+    let mut frame = ptr::null_mut();
+    let _ = unsafe { con_frame_iterator_try_next(iterator, &mut frame) };
+    frame
+}
+/// Reads the next frame from the iterator into `*out_frame`.
+///
+/// Returns `RKR_STATUS_SUCCESS` with a frame the caller OWNS (free with
+/// `free_rkr_frame`), or with `*out_frame = NULL` at the end of the input.
+/// A malformed frame returns `RKR_STATUS_PARSE_ERROR` (or the matching
+/// code) and [`rkr_last_error_message`] names the line the frame starts on.
+///
+/// # Safety
+/// iterator must be valid; out_frame must point to writable memory.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn con_frame_iterator_try_next(
+    iterator: *mut CConFrameIterator,
+    out_frame: *mut *mut RKRConFrame,
+) -> RKRStatus {
+    clear_last_error();
+    if iterator.is_null() || out_frame.is_null() {
+        return set_last_error(
+            RKRStatus::RKR_STATUS_NULL_POINTER,
+            "iterator or out_frame is NULL",
+        );
     }
+    unsafe { *out_frame = ptr::null_mut() };
     let iter = unsafe { &mut *(*iterator).iterator };
-    match iter.next() {
-        Some(Ok(frame)) => Box::into_raw(Box::new(frame)) as *mut RKRConFrame,
-        _ => ptr::null_mut(),
+    let next = iter.next();
+    if next.is_some() {
+        unsafe { (*iterator).position += 1 };
+    }
+    match next {
+        Some(Ok(frame)) => {
+            unsafe { *out_frame = Box::into_raw(Box::new(frame)) as *mut RKRConFrame };
+            RKRStatus::RKR_STATUS_SUCCESS
+        }
+        Some(Err(e)) => set_frame_error(&e, iter.frame_line()),
+        None => RKRStatus::RKR_STATUS_SUCCESS,
     }
 }
+/// Skips up to `n` frames without parsing their atom data and returns how
+/// many were skipped. Fewer than `n` means the input ended or a frame
+/// header was malformed; [`rkr_last_error_status`] tells which.
+///
+/// # Safety
+/// iterator must be valid or null (returns 0).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn con_frame_iterator_skip(
+    iterator: *mut CConFrameIterator,
+    n: usize,
+) -> usize {
+    clear_last_error();
+    let Some(c_iter) = (unsafe { iterator.as_mut() }) else {
+        set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "iterator is NULL");
+        return 0;
+    };
+    let iter = unsafe { &mut *c_iter.iterator };
+    let mut skipped = 0;
+    while skipped < n {
+        match iter.forward() {
+            Some(Ok(())) => skipped += 1,
+            Some(Err(e)) => {
+                c_iter.position += 1;
+                set_frame_error(&e, iter.frame_line());
+                break;
+            }
+            None => break,
+        }
+    }
+    c_iter.position += skipped;
+    skipped
+}
+/// Rewinds the iterator to the first frame of its input.
+///
+/// # Safety
+/// iterator must be valid or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn con_frame_iterator_reset(iterator: *mut CConFrameIterator) -> RKRStatus {
+    let Some(c_iter) = (unsafe { iterator.as_mut() }) else {
+        return RKRStatus::RKR_STATUS_NULL_POINTER;
+    };
+    unsafe { (*c_iter.iterator).reset() };
+    c_iter.position = 0;
+    RKRStatus::RKR_STATUS_SUCCESS
+}
+/// Number of frames left, found by skipping through them on a copy of the
+/// iterator (headers only; the iterator itself does not move). Counting
+/// stops at the first malformed header, which [`rkr_last_error_message`]
+/// then describes.
+///
+/// # Safety
+/// iterator must be valid or null (returns 0).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn con_frame_iterator_count_remaining(
+    iterator: *const CConFrameIterator,
+) -> usize {
+    clear_last_error();
+    let Some(c_iter) = (unsafe { iterator.as_ref() }) else {
+        set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "iterator is NULL");
+        return 0;
+    };
+    let mut probe = unsafe { (*c_iter.iterator).clone() };
+    let mut remaining = 0;
+    loop {
+        match probe.forward() {
+            Some(Ok(())) => remaining += 1,
+            Some(Err(e)) => {
+                set_frame_error(&e, probe.frame_line());
+                break;
+            }
+            None => break,
+        }
+    }
+    remaining
+}
+/// Zero-based index of the frame the next read returns: the number of
+/// frames read, skipped or rejected as malformed since the iterator was
+/// created or reset. 0 for a NULL iterator.
+///
+/// # Safety
+/// iterator must be valid or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn con_frame_iterator_position(iterator: *const CConFrameIterator) -> usize {
+    unsafe { iterator.as_ref() }.map_or(0, |c_iter| c_iter.position)
+}
 /// Frees the memory for an opaque `RKRConFrame` handle.
 ///
 /// # Safety
@@ -637,8 +995,45 @@ pub unsafe extern "C" fn free_con_frame_iterator(iterator: *mut CConFrameIterato
 //=============================================================================
 // Data Accessors (The "Getter" API)
 //=============================================================================
+/// Builds the transparent record for one atom of `frame`; `mass` is its
+/// type's mass.
+fn c_atom(
+    frame: &ConFrame,
+    atom_datum: &AtomDatum,
+    mass: f64,
+    registry: &ElementRegistry,
+) -> CAtom {
+    let [vx, vy, vz] = atom_datum.velocity.unwrap_or([0.0; 3]);
+    let [fx, fy, fz] = atom_datum.force.unwrap_or([0.0; 3]);
+    CAtom {
+        atomic_number: registry
+            .atomic_number(frame.symbol_of(atom_datum))
+            .unwrap_or(0),
+        x: atom_datum.x,
+        y: atom_datum.y,
+        z: atom_datum.z,
+        is_fixed: atom_datum.is_fixed(),
+        fixed_x: atom_datum.fixed[0],
+        fixed_y: atom_datum.fixed[1],
+        fixed_z: atom_datum.fixed[2],
+        atom_id: atom_datum.atom_id,
+        mass,
+        vx,
+        vy,
+        vz,
+        has_velocity: atom_datum.has_velocity(),
+        fx,
+        fy,
+        fz,
+        has_forces: atom_datum.has_forces(),
+        energy: atom_datum.energy.unwrap_or(0.0),
+        has_energy: atom_datum.has_energy(),
+    }
+}
 /// Extracts the core atomic data into a transparent `CFrame` struct.
 /// The caller OWNS the returned pointer and MUST call `free_c_frame` on it.
+/// Atomic numbers follow [`rkr_symbol_to_z`], so unknown symbols get 0;
+/// check with [`rkr_frame_check_species`] first to reject them.
 ///
 /// # Safety
 /// frame_handle must be valid. The caller takes ownership of the returned CFrame.
@@ -649,42 +1044,15 @@ pub unsafe extern "C" fn rkr_frame_to_c_frame(frame_handle: *const RKRConFrame)
         None => return ptr::null_mut(),
     };
     let masses_iter = frame
-        .header
-        .natms_per_type
-        .iter()
-        .zip(frame.header.masses_per_type.iter())
-        .flat_map(|(num_atoms, mass)| std::iter::repeat_n(*mass, *num_atoms));
+        .by_type()
+        .flat_map(|(_, mass, atoms)| std::iter::repeat_n(mass, atoms.len()));
     let has_velocities = frame.has_velocities();
+    let registry = elements();
     let mut c_atoms: Vec<CAtom> = frame
         .atom_data
         .iter()
         .zip(masses_iter)
-        .map(|(atom_datum, mass)| {
-            let [vx, vy, vz] = atom_datum.velocity.unwrap_or([0.0; 3]);
-            let [fx, fy, fz] = atom_datum.force.unwrap_or([0.0; 3]);
-            CAtom {
-                atomic_number: symbol_to_atomic_number(&atom_datum.symbol),
-                x: atom_datum.x,
-                y: atom_datum.y,
-                z: atom_datum.z,
-                is_fixed: atom_datum.is_fixed(),
-                fixed_x: atom_datum.fixed[0],
-                fixed_y: atom_datum.fixed[1],
-                fixed_z: atom_datum.fixed[2],
-                atom_id: atom_datum.atom_id,
-                mass,
-                vx,
-                vy,
-                vz,
-                has_velocity: atom_datum.has_velocity(),
-                fx,
-                fy,
-                fz,
-                has_forces: atom_datum.has_forces(),
-                energy: atom_datum.energy.unwrap_or(0.0),
-                has_energy: atom_datum.has_energy(),
-            }
-        })
+        .map(|(atom_datum, mass)| c_atom(frame, atom_datum, mass, &registry))
         .collect();
     let atoms_ptr = c_atoms.as_mut_ptr();
     let num_atoms = c_atoms.len();
@@ -836,32 +1204,61 @@ pub unsafe extern "C" fn rkr_free_string(s: *mut c_char) {
 /// `ConFrameWriter<W>` is generic over its sink, so a plain `File`, a
 /// gzip `GzEncoder<File>`, and a zstd encoder all monomorphise to
 /// distinct, layout-incompatible types. Boxing the sink as
-/// `Box<dyn Write>` collapses them to a single concrete handle type, so
-/// `free_rkr_writer` and `rkr_writer_extend` can cast the opaque pointer
-/// to exactly one type regardless of the compression chosen at
-/// construction. Dropping the box flushes the `BufWriter` and then runs
-/// the sink's own `Drop` (gzip/zstd finalize their streams there).
-type RkrWriter = ConFrameWriter<Box<dyn std::io::Write>>;
+/// `Box<dyn Write + Send>` collapses them to a single concrete handle
+/// type, so `free_rkr_writer` and `rkr_writer_extend` can cast the opaque
+/// pointer to exactly one type regardless of the compression chosen at
+/// construction.
+///
+/// The `Send` bound lets a writer handle move to another thread. Dropping
+/// the box flushes the `BufWriter` and then runs the sink's own `Drop`
+/// (gzip/zstd finalize their streams there).
+type RkrWriter = ConFrameWriter<Box<dyn std::io::Write + Send>>;
+
+// Threading contract promised in the C header: every handle may move to
+// another thread, and frames may also be read from several threads at once.
+// A non-`Send` field (e.g. an `Rc`) anywhere behind a handle fails here.
+const _: () = {
+    const fn send<T: Send>() {}
+    const fn send_sync<T: Send + Sync>() {}
+    send_sync::<ConFrame>();
+    send::<RkrWriter>();
+    send::<ConFrameBuilder>();
+    send::<ConFrameIterator<'static>>();
+    send::<String>();
+};
 /// Boxes a sink into an `RKRConFrameWriter` handle at the requested
-/// precision. `precision == None` selects the writer's built-in default.
+/// precision. `precision == None` selects [`RKROptions::precision`], or the
+/// writer's built-in default when that is 0.
 #[inline]
 fn into_rkr_writer(
-    sink: Box<dyn std::io::Write>,
+    sink: Box<dyn std::io::Write + Send>,
     precision: Option<u8>,
 ) -> *mut RKRConFrameWriter {
+    let precision = precision.or(match default_options().precision {
+        0 => None,
+        p => Some(p),
+    });
     let writer: RkrWriter = match precision {
         Some(p) => ConFrameWriter::with_precision(sink, p as usize),
         None => ConFrameWriter::new(sink),
     };
     Box::into_raw(Box::new(writer)) as *mut RKRConFrameWriter
 }
-/// Parses a borrowed C string, returning `None` for null or non-UTF-8.
+/// Parses a borrowed C string, returning `None` (and recording the last
+/// error) for null or non-UTF-8.
 #[inline]
 unsafe fn cstr_path<'a>(filename_c: *const c_char) -> Option<&'a str> {
     if filename_c.is_null() {
+        set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "path is NULL");
         return None;
     }
-    unsafe { CStr::from_ptr(filename_c).to_str().ok() }
+    match unsafe { CStr::from_ptr(filename_c).to_str() } {
+        Ok(s) => Some(s),
+        Err(e) => {
+            set_last_error(RKRStatus::RKR_STATUS_INVALID_UTF8, format!("path: {e}"));
+            None
+        }
+    }
 }
 /// Creates a new frame writer for the specified file.
 /// The caller OWNS the returned pointer and MUST call `free_rkr_writer`.
@@ -872,13 +1269,14 @@ unsafe fn cstr_path<'a>(filename_c: *const c_char) -> Option<&'a str> {
 pub unsafe extern "C" fn create_writer_from_path_c(
     filename_c: *const c_char,
 ) -> *mut RKRConFrameWriter {
+    clear_last_error();
     let filename = match unsafe { cstr_path(filename_c) } {
         Some(s) => s,
         None => return ptr::null_mut(),
     };
     match File::create(filename) {
         Ok(file) => into_rkr_writer(Box::new(file), None),
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(RKRStatus::RKR_STATUS_IO_ERROR, format!("{filename}: {e}")),
     }
 }
 /// Frees the memory for an `RKRConFrameWriter`, closing the associated file.
@@ -902,19 +1300,20 @@ pub unsafe extern "C" fn rkr_writer_extend(
     frame_handles: *const *const RKRConFrame,
     num_frames: usize,
 ) -> RKRStatus {
+    clear_last_error();
     let writer = match unsafe { (writer_handle as *mut RkrWriter).as_mut() } {
         Some(w) => w,
-        None => return RKRStatus::RKR_STATUS_NULL_POINTER,
+        None => return set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "writer is NULL"),
     };
     if frame_handles.is_null() {
-        return RKRStatus::RKR_STATUS_NULL_POINTER;
+        return set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "frame_handles is NULL");
     }
     let handles_slice = unsafe { std::slice::from_raw_parts(frame_handles, num_frames) };
     let mut rust_frames: Vec<&ConFrame> = Vec::with_capacity(num_frames);
     if handles_slice.iter().any(|&handle| handle.is_null()) {
         // Fail fast if any handle is null, as this indicates a bug on the
         // caller's side.
-        return RKRStatus::RKR_STATUS_NULL_POINTER;
+        return set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "a frame handle is NULL");
     }
     for &handle in handles_slice.iter() {
         // Assume the handle is valid.
@@ -926,7 +1325,110 @@ pub unsafe extern "C" fn rkr_writer_extend(
     }
     match writer.extend(rust_frames.into_iter()) {
         Ok(_) => RKRStatus::RKR_STATUS_SUCCESS,
-        Err(_) => RKRStatus::RKR_STATUS_IO_ERROR,
+        Err(e) => set_last_error(RKRStatus::RKR_STATUS_IO_ERROR, e),
+    }
+}
+/// Writes one frame given as a transparent `CFrame` (e.g. from
+/// [`rkr_frame_to_c_frame`], or filled in by the caller) without building an
+/// `RKRConFrame` first.
+///
+/// `symbols` is NULL or an array of `num_atoms` C strings; a NULL array or
+/// entry falls back to the element symbol of `atomic_number`, so labels
+/// without one (Z = 0) must be passed. Velocity, force and per-atom energy
+/// sections are written when the frame's `has_velocities`, `has_forces` and
+/// `has_energies` are set. An atom with `is_fixed` but no per-axis flag is
+/// fixed on all axes. The header lines are the builder defaults.
+///
+/// Returns `RKR_STATUS_SUCCESS`, `RKR_STATUS_NULL_POINTER`,
+/// `RKR_STATUS_INVALID_UTF8` / `RKR_STATUS_VALIDATION_ERROR` for a bad
+/// symbol, or `RKR_STATUS_IO_ERROR`; see [`rkr_last_error_message`].
+///
+/// # Safety
+/// writer_handle and frame must be valid; `frame->atoms` must hold
+/// `frame->num_atoms` records; symbols must be NULL or hold `num_atoms`
+/// pointers, each NULL or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_writer_write_cframe(
+    writer_handle: *mut RKRConFrameWriter,
+    frame: *const CFrame,
+    symbols: *const *const c_char,
+) -> RKRStatus {
+    clear_last_error();
+    let writer = match unsafe { (writer_handle as *mut RkrWriter).as_mut() } {
+        Some(w) => w,
+        None => return set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "writer is NULL"),
+    };
+    let Some(c_frame) = (unsafe { frame.as_ref() }) else {
+        return set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "frame is NULL");
+    };
+    let atoms = match (c_frame.atoms.is_null(), c_frame.num_atoms) {
+        (_, 0) => &[][..],
+        (true, _) => {
+            return set_last_error(RKRStatus::RKR_STATUS_NULL_POINTER, "frame->atoms is NULL");
+        }
+        (false, n) => unsafe { std::slice::from_raw_parts(c_frame.atoms, n) },
+    };
+    let mut builder = ConFrameBuilder::new(c_frame.cell, c_frame.angles);
+    for (i, atom) in atoms.iter().enumerate() {
+        let given = if symbols.is_null() {
+            ptr::null()
+        } else {
+            unsafe { *symbols.add(i) }
+        };
+        let symbol = if given.is_null() {
+            match crate::helpers::atomic_number_to_symbol(atom.atomic_number) {
+                "X" => {
+                    return set_last_error(
+                        RKRStatus::RKR_STATUS_VALIDATION_ERROR,
+                        format!(
+                            "atom {i}: atomic number {} has no symbol; pass it in symbols",
+                            atom.atomic_number
+                        ),
+                    );
+                }
+                symbol => symbol,
+            }
+        } else {
+            match unsafe { CStr::from_ptr(given) }.to_str() {
+                Ok(symbol) => symbol,
+                Err(e) => {
+                    return set_last_error(
+                        RKRStatus::RKR_STATUS_INVALID_UTF8,
+                        format!("symbol of atom {i}: {e}"),
+                    );
+                }
+            }
+        };
+        let mut fixed = [atom.fixed_x, atom.fixed_y, atom.fixed_z];
+        if atom.is_fixed && fixed == [false; 3] {
+            fixed = [true; 3];
+        }
+        builder.add_atom(
+            symbol,
+            atom.x,
+            atom.y,
+            atom.z,
+            fixed,
+            atom.atom_id,
+            atom.mass,
+        );
+        if c_frame.has_velocities {
+            builder.with_velocity([atom.vx, atom.vy, atom.vz]);
+        }
+        if c_frame.has_forces {
+            builder.with_force([atom.fx, atom.fy, atom.fz]);
+        }
+        if c_frame.has_energies {
+            builder.with_energy(atom.energy);
+        }
+    }
+    let frame = match builder.assemble() {
+        Ok(frame) => frame,
+        Err(e) => return set_last_error(RKRStatus::RKR_STATUS_VALIDATION_ERROR, e),
+    };
+    match writer.write_frame(&frame) {
+        Ok(()) => RKRStatus::RKR_STATUS_SUCCESS,
+        Err(e) => set_last_error(RKRStatus::RKR_STATUS_IO_ERROR, e),
     }
 }
 
@@ -960,17 +1462,13 @@ pub unsafe extern "C" fn rkr_writer_is_canonical(writer_handle: *const RKRConFra
 #[cfg(test)]
 mod index_proj_ffi_tests {
     use super::*;
+    use crate::test_util::fixture_path;
     use std::ffi::CStr;
     use std::fs;
-    use std::path::PathBuf;
-
-    fn fixture_path() -> PathBuf {
-        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/test/tiny_cuh2.con")
-    }
 
     #[test]
     fn ffi_projection_matches_index_proj() {
-        let frames = crate::iterators::read_all_frames(&fixture_path()).unwrap();
+        let frames = crate::iterators::read_all_frames(&fixture_path("tiny_cuh2.con")).unwrap();
         let fr = &frames[0];
         let handle = fr as *const ConFrame as *const RKRConFrame;
         let proj = crate::index_proj::FrameIndexProjection::from_frame(fr);
@@ -1014,7 +1512,7 @@ mod index_proj_ffi_tests {
 
     #[test]
     fn ffi_canonical_writer_byte_identical() {
-        let frames = crate::iterators::read_all_frames(&fixture_path()).unwrap();
+        let frames = crate::iterators::read_all_frames(&fixture_path("tiny_cuh2.con")).unwrap();
         let fr = &frames[0];
         let dir = tempfile::tempdir().unwrap();
         let p1 = dir.path().join("a.con");
@@ -1054,13 +1552,14 @@ pub unsafe extern "C" fn create_writer_from_path_with_precision_c(
     filename_c: *const c_char,
     precision: u8,
 ) -> *mut RKRConFrameWriter {
+    clear_last_error();
     let filename = match unsafe { cstr_path(filename_c) } {
         Some(s) => s,
         None => return ptr::null_mut(),
     };
     match File::create(filename) {
         Ok(file) => into_rkr_writer(Box::new(file), Some(precision)),
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(RKRStatus::RKR_STATUS_IO_ERROR, format!("{filename}: {e}")),
     }
 }
 //=============================================================================
@@ -2811,8 +3310,10 @@ pub unsafe extern "C" fn rkr_frame_builder_build(
         return ptr::null_mut();
     }
     let builder = unsafe { *Box::from_raw(builder_handle as *mut ConFrameBuilder) };
-    let frame = builder.build();
-    Box::into_raw(Box::new(frame)) as *mut RKRConFrame
+    match builder.assemble() {
+        Ok(frame) => Box::into_raw(Box::new(frame)) as *mut RKRConFrame,
+        Err(e) => null_with_error(RKRStatus::RKR_STATUS_VALIDATION_ERROR, e),
+    }
 }
 /// Frees a frame builder without building.
 ///
@@ -2858,13 +3359,14 @@ pub unsafe extern "C" fn rkr_frame_builder_clone(
 /// filename_c must be valid. The caller takes ownership of the returned writer.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn create_writer_gzip_c(filename_c: *const c_char) -> *mut RKRConFrameWriter {
+    clear_last_error();
     let filename = match unsafe { cstr_path(filename_c) } {
         Some(s) => s,
         None => return ptr::null_mut(),
     };
     match crate::compression::gzip_writer(Path::new(filename)) {
         Ok(encoder) => into_rkr_writer(Box::new(encoder), None),
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(RKRStatus::RKR_STATUS_IO_ERROR, format!("{filename}: {e}")),
     }
 }
 /// Creates a gzip-compressed frame writer with a custom floating-point
@@ -2878,13 +3380,14 @@ pub unsafe extern "C" fn create_writer_gzip_with_precision_c(
     filename_c: *const c_char,
     precision: u8,
 ) -> *mut RKRConFrameWriter {
+    clear_last_error();
     let filename = match unsafe { cstr_path(filename_c) } {
         Some(s) => s,
         None => return ptr::null_mut(),
     };
     match crate::compression::gzip_writer(Path::new(filename)) {
         Ok(encoder) => into_rkr_writer(Box::new(encoder), Some(precision)),
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(RKRStatus::RKR_STATUS_IO_ERROR, format!("{filename}: {e}")),
     }
 }
 /// Creates a new zstd-compressed frame writer for the specified file.
@@ -2899,13 +3402,14 @@ pub unsafe extern "C" fn create_writer_gzip_with_precision_c(
 #[cfg(feature = "zstd")]
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn create_writer_zstd_c(filename_c: *const c_char) -> *mut RKRConFrameWriter {
+    clear_last_error();
     let filename = match unsafe { cstr_path(filename_c) } {
         Some(s) => s,
         None => return ptr::null_mut(),
     };
     match crate::compression::zstd_writer(Path::new(filename)) {
         Ok(encoder) => into_rkr_writer(Box::new(encoder), None),
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(RKRStatus::RKR_STATUS_IO_ERROR, format!("{filename}: {e}")),
     }
 }
 /// Creates a zstd-compressed frame writer with a custom floating-point
@@ -2924,13 +3428,14 @@ pub unsafe extern "C" fn create_writer_zstd_with_precision_c(
     filename_c: *const c_char,
     precision: u8,
 ) -> *mut RKRConFrameWriter {
+    clear_last_error();
     let filename = match unsafe { cstr_path(filename_c) } {
         Some(s) => s,
         None => return ptr::null_mut(),
     };
     match crate::compression::zstd_writer(Path::new(filename)) {
         Ok(encoder) => into_rkr_writer(Box::new(encoder), Some(precision)),
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(RKRStatus::RKR_STATUS_IO_ERROR, format!("{filename}: {e}")),
     }
 }
 //=============================================================================
@@ -2946,17 +3451,52 @@ pub unsafe extern "C" fn create_writer_zstd_with_precision_c(
 /// filename_c must be valid. The caller takes ownership of the returned frame.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn rkr_read_first_frame(filename_c: *const c_char) -> *mut RKRConFrame {
-    if filename_c.is_null() {
-        return ptr::null_mut();
-    }
-    let filename = match unsafe { CStr::from_ptr(filename_c).to_str() } {
-        Ok(s) => s,
-        Err(_) => return ptr::null_mut(),
+    clear_last_error();
+    let filename = match unsafe { cstr_path(filename_c) } {
+        Some(s) => s,
+        None => return ptr::null_mut(),
     };
-    match iterators::read_first_frame(Path::new(filename)) {
+    match read_first_frame_with_defaults(Path::new(filename)) {
         Ok(frame) => Box::into_raw(Box::new(frame)) as *mut RKRConFrame,
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(error_status(&*e), format!("{filename}: {e}")),
+    }
+}
+/// [`iterators::read_first_frame`] honoring [`RKROptions::lenient`].
+fn read_first_frame_with_defaults(path: &Path) -> Result<ConFrame, Box<dyn std::error::Error>> {
+    if default_options().lenient == 0 {
+        return iterators::read_first_frame(path);
+    }
+    let contents = crate::compression::read_file_contents(path)?;
+    let text = contents.as_str()?;
+    match ConFrameIterator::new(text).options(default_parser_options()).next() {
+        Some(Ok(frame)) => Ok(frame),
+        Some(Err(e)) => Err(Box::new(e)),
+        None => Err("No frames found in file".into()),
+    }
+}
+/// [`iterators::read_all_frames`] honoring [`RKROptions::lenient`] and
+/// [`RKROptions::num_threads`].
+fn read_all_frames_with_defaults(path: &Path) -> Result<Vec<ConFrame>, Box<dyn std::error::Error>> {
+    let defaults = default_options();
+    if defaults.lenient == 0 && defaults.num_threads == 0 {
+        return iterators::read_all_frames(path);
+    }
+    let contents = crate::compression::read_file_contents(path)?;
+    let text = contents.as_str()?;
+    #[cfg(feature = "parallel")]
+    if defaults.lenient == 0 {
+        let parts =
+            iterators::parse_frames_parallel_with_threads(text, Some(defaults.num_threads as usize));
+        let mut frames = Vec::with_capacity(parts.len());
+        for r in parts {
+            frames.push(r?);
+        }
+        return Ok(frames);
     }
+    let frames: Result<Vec<_>, _> = ConFrameIterator::new(text)
+        .options(default_parser_options())
+        .collect();
+    Ok(frames?)
 }
 /// Reads all frames from a .con file using mmap.
 /// Returns an array of frame handles and sets `num_frames` to the count.
@@ -2971,14 +3511,15 @@ pub unsafe extern "C" fn rkr_read_all_frames(
     filename_c: *const c_char,
     num_frames: *mut usize,
 ) -> *mut *mut RKRConFrame {
-    if filename_c.is_null() || num_frames.is_null() {
-        return ptr::null_mut();
+    clear_last_error();
+    if num_frames.is_null() {
+        return null_with_error(RKRStatus::RKR_STATUS_NULL_POINTER, "num_frames is NULL");
     }
-    let filename = match unsafe { CStr::from_ptr(filename_c).to_str() } {
-        Ok(s) => s,
-        Err(_) => return ptr::null_mut(),
+    let filename = match unsafe { cstr_path(filename_c) } {
+        Some(s) => s,
+        None => return ptr::null_mut(),
     };
-    match iterators::read_all_frames(Path::new(filename)) {
+    match read_all_frames_with_defaults(Path::new(filename)) {
         Ok(frames) => {
             let count = frames.len();
             // shrink_to_fit ensures len == capacity so the matching
@@ -2995,7 +3536,7 @@ pub unsafe extern "C" fn rkr_read_all_frames(
             unsafe { *num_frames = count };
             ptr
         }
-        Err(_) => ptr::null_mut(),
+        Err(e) => null_with_error(error_status(&*e), format!("{filename}: {e}")),
     }
 }
 /// Frees an array of frame handles returned by `rkr_read_all_frames`.
@@ -3150,7 +3691,10 @@ pub unsafe extern "C" fn rkr_frame_metatensor_atom_energies_block(
 #[cfg(not(feature = "zstd"))]
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn create_writer_zstd_c(_filename_c: *const c_char) -> *mut RKRConFrameWriter {
-    ptr::null_mut()
+    null_with_error(
+        RKRStatus::RKR_STATUS_FEATURE_DISABLED,
+        "zstd output requires the `zstd` feature",
+    )
 }
 #[cfg(not(feature = "zstd"))]
 #[unsafe(no_mangle)]
@@ -3158,7 +3702,10 @@ pub unsafe extern "C" fn create_writer_zstd_with_precision_c(
     _filename_c: *const c_char,
     _precision: u8,
 ) -> *mut RKRConFrameWriter {
-    ptr::null_mut()
+    null_with_error(
+        RKRStatus::RKR_STATUS_FEATURE_DISABLED,
+        "zstd output requires the `zstd` feature",
+    )
 }
 //=============================================================================
 /// Lean-build stubs: always export metatensor C symbols so Fortran/C can link without `#ifdef`.
@@ -3224,6 +3771,98 @@ pub unsafe extern "C" fn rkr_frame_atom_count(frame_handle: *const RKRConFrame)
     };
     frame.atom_data.len()
 }
+/// Same as [`rkr_frame_atom_count`]; the name pairs with
+/// [`rkr_frame_positions_ptr`], [`rkr_frame_symbol`] and [`rkr_frame_atom`].
+///
+/// # Safety
+/// frame_handle must be valid or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_frame_num_atoms(frame_handle: *const RKRConFrame) -> usize {
+    unsafe { rkr_frame_atom_count(frame_handle) }
+}
+/// Returns a pointer to the frame's positions as `3 * rkr_frame_num_atoms`
+/// row-major doubles (`x0, y0, z0, x1, ...`), in file (type-grouped) order.
+///
+/// The pointer borrows from the frame: it stays valid until the handle is
+/// freed, and must not be written through or freed. Returns NULL if
+/// `frame_handle` is NULL or the frame stores its positions in another
+/// precision (e.g. float32 storage); use [`rkr_frame_atom`] then.
+///
+/// # Safety
+/// frame_handle must be valid or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_frame_positions_ptr(frame_handle: *const RKRConFrame) -> *const f64 {
+    let frame = match unsafe { (frame_handle as *const ConFrame).as_ref() } {
+        Some(f) => f,
+        None => return ptr::null(),
+    };
+    match &frame.positions {
+        Array2Storage::F64(a) if a.nrows() == frame.atom_data.len() && a.ncols() == 3 => {
+            a.as_slice().map_or(ptr::null(), <[f64]>::as_ptr)
+        }
+        _ => ptr::null(),
+    }
+}
+/// Returns the chemical symbol of atom `index` as a heap-allocated
+/// null-terminated C string. The caller MUST free with `rkr_free_string`.
+/// Unlike [`rkr_frame_to_c_frame`] this keeps labels that have no atomic
+/// number. Returns NULL if `frame_handle` is NULL or `index` is out of range.
+///
+/// # Safety
+/// frame_handle must be valid. The caller takes ownership of the returned string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_frame_symbol(
+    frame_handle: *const RKRConFrame,
+    index: usize,
+) -> *mut c_char {
+    let frame = match unsafe { (frame_handle as *const ConFrame).as_ref() } {
+        Some(f) => f,
+        None => return ptr::null_mut(),
+    };
+    match frame.atom_data.get(index) {
+        Some(atom) => match CString::new(frame.symbol_of(atom)) {
+            Ok(cs) => cs.into_raw(),
+            Err(_) => ptr::null_mut(),
+        },
+        None => ptr::null_mut(),
+    }
+}
+/// Fills `*out` with atom `index`, exactly as that atom would appear in
+/// the array returned by [`rkr_frame_to_c_frame`], without copying the
+/// rest of the frame.
+///
+/// Returns `RKR_STATUS_NULL_POINTER` if `frame_handle` or `out` is NULL and
+/// `RKR_STATUS_INDEX_OUT_OF_BOUNDS` if `index >= rkr_frame_num_atoms`.
+///
+/// # Safety
+/// frame_handle must be valid; out must point to writable memory for one `CAtom`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rkr_frame_atom(
+    frame_handle: *const RKRConFrame,
+    index: usize,
+    out: *mut CAtom,
+) -> RKRStatus {
+    let frame = match unsafe { (frame_handle as *const ConFrame).as_ref() } {
+        Some(f) => f,
+        None => return RKRStatus::RKR_STATUS_NULL_POINTER,
+    };
+    if out.is_null() {
+        return RKRStatus::RKR_STATUS_NULL_POINTER;
+    }
+    let Some(atom_datum) = frame.atom_data.get(index) else {
+        return RKRStatus::RKR_STATUS_INDEX_OUT_OF_BOUNDS;
+    };
+    let mut start = 0;
+    let mass = frame
+        .by_type()
+        .find_map(|(_, mass, atoms)| {
+            start += atoms.len();
+            (index < start).then_some(mass)
+        })
+        .unwrap_or(0.0);
+    unsafe { out.write(c_atom(frame, atom_datum, mass, &elements())) };
+    RKRStatus::RKR_STATUS_SUCCESS
+}
 /// Copy positions as row-major `[x0,y0,z0,...]` into `out` (length >= 3*N).
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn rkr_frame_copy_positions(
@@ -3350,15 +3989,12 @@ pub unsafe extern "C" fn rkr_frame_copy_masses(
         return RKRStatus::RKR_STATUS_BUFFER_TOO_SMALL;
     }
     let slice = unsafe { std::slice::from_raw_parts_mut(out, n) };
-    let mut expanded: Vec<f64> = Vec::with_capacity(n);
-    for (ti, &count) in frame.header.natms_per_type.iter().enumerate() {
-        let m = frame.header.masses_per_type.get(ti).copied().unwrap_or(0.0);
-        expanded.extend(std::iter::repeat_n(m, count));
-    }
-    if expanded.len() != n {
-        expanded.resize(n, 0.0);
+    slice.fill(0.0);
+    let mut k = 0;
+    for (_, mass, atoms) in frame.by_type() {
+        slice[k..k + atoms.len()].fill(mass);
+        k += atoms.len();
     }
-    slice.copy_from_slice(&expanded[..n]);
     RKRStatus::RKR_STATUS_SUCCESS
 }
 #[unsafe(no_mangle)]
@@ -3682,6 +4318,7 @@ pub unsafe extern "C" fn rkr_frame_select(
     selection: *const c_char,
     out_result: *mut *mut RKRSelectionResult,
 ) -> RKRStatus {
+    clear_last_error();
     if frame_handle.is_null() || selection.is_null() || out_result.is_null() {
         return RKRStatus::RKR_STATUS_NULL_POINTER;
     }
@@ -3698,7 +4335,7 @@ pub unsafe extern "C" fn rkr_frame_select(
             }
             RKRStatus::RKR_STATUS_SUCCESS
         }
-        Err(_) => RKRStatus::RKR_STATUS_SELECTION_ERROR,
+        Err(e) => set_last_error(RKRStatus::RKR_STATUS_SELECTION_ERROR, e),
     }
 }
 /// Number of matches in a selection result.
@@ -3964,6 +4601,164 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn frame_atom_accessors_match_c_frame() {
+        let path = CString::new("resources/test/tiny_cuh2.con").unwrap();
+        let frame = unsafe { rkr_read_first_frame(path.as_ptr()) };
+        assert!(!frame.is_null());
+        let n = unsafe { rkr_frame_num_atoms(frame) };
+        assert_eq!(n, unsafe { rkr_frame_atom_count(frame) });
+        let positions =
+            unsafe { std::slice::from_raw_parts(rkr_frame_positions_ptr(frame), n * 3) };
+        let c_frame_ptr = unsafe { rkr_frame_to_c_frame(frame) };
+        let c_frame = unsafe { &*c_frame_ptr };
+        let c_atoms = unsafe { std::slice::from_raw_parts(c_frame.atoms, c_frame.num_atoms) };
+        let mut atom = std::mem::MaybeUninit::<CAtom>::uninit();
+        for (i, expected) in c_atoms.iter().enumerate() {
+            assert_eq!(
+                unsafe { rkr_frame_atom(frame, i, atom.as_mut_ptr()) },
+                RKRStatus::RKR_STATUS_SUCCESS
+            );
+            let atom = unsafe { atom.assume_init_ref() };
+            assert_eq!(atom.atomic_number, expected.atomic_number);
+            assert_eq!(atom.atom_id, expected.atom_id);
+            assert_eq!(atom.mass, expected.mass);
+            assert_eq!(
+                &positions[i * 3..i * 3 + 3],
+                [expected.x, expected.y, expected.z]
+            );
+            let symbol = unsafe { rkr_frame_symbol(frame, i) };
+            let z = unsafe { rkr_symbol_to_z(symbol) };
+            assert_eq!(z, expected.atomic_number);
+            unsafe { rkr_free_string(symbol) };
+        }
+        assert!(unsafe { rkr_frame_symbol(frame, n) }.is_null());
+        assert_eq!(
+            unsafe { rkr_frame_atom(frame, n, atom.as_mut_ptr()) },
+            RKRStatus::RKR_STATUS_INDEX_OUT_OF_BOUNDS
+        );
+        assert!(unsafe { rkr_frame_positions_ptr(ptr::null()) }.is_null());
+        unsafe {
+            free_c_frame(c_frame_ptr);
+            free_rkr_frame(frame);
+        }
+    }
+    fn last_error_message() -> String {
+        let message = rkr_last_error_message();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+    #[test]
+    fn last_error_tells_missing_file_from_malformed_frame() {
+        let missing = CString::new("resources/test/does_not_exist.con").unwrap();
+        assert!(unsafe { rkr_read_first_frame(missing.as_ptr()) }.is_null());
+        assert_eq!(rkr_last_error_status(), RKRStatus::RKR_STATUS_IO_ERROR);
+        assert!(last_error_message().starts_with("resources/test/does_not_exist.con: "));
+
+        let good = std::fs::read_to_string("resources/test/tiny_cuh2.con").unwrap();
+        let bad = good.replace("0.63940000000000108", "0.6394x");
+        let text = CString::new(format!("{good}{bad}")).unwrap();
+        let it = unsafe { read_con_string_iterator(text.as_ptr()) };
+        assert!(!it.is_null());
+        assert!(rkr_last_error_message().is_null());
+        let mut frame = ptr::null_mut();
+        assert_eq!(
+            unsafe { con_frame_iterator_try_next(it, &mut frame) },
+            RKRStatus::RKR_STATUS_SUCCESS
+        );
+        assert!(!frame.is_null());
+        unsafe { free_rkr_frame(frame) };
+        assert!(unsafe { con_frame_iterator_next(it) }.is_null());
+        assert_eq!(rkr_last_error_status(), RKRStatus::RKR_STATUS_PARSE_ERROR);
+        let message = last_error_message();
+        assert!(
+            message.starts_with("frame starting at line 18: "),
+            "{message}"
+        );
+        rkr_clear_last_error();
+        assert_eq!(rkr_last_error_status(), RKRStatus::RKR_STATUS_SUCCESS);
+        assert!(rkr_last_error_message().is_null());
+        unsafe { free_con_frame_iterator(it) };
+    }
+    #[test]
+    fn write_cframe_round_trips_through_the_writer() {
+        let input = CString::new("resources/test/tiny_cuh2_vel_forces.con").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("from_cframe.con");
+        let out_c = CString::new(out.to_str().unwrap()).unwrap();
+        unsafe {
+            let frame = rkr_read_first_frame(input.as_ptr());
+            let c_frame = rkr_frame_to_c_frame(frame);
+            let writer = create_writer_from_path_c(out_c.as_ptr());
+            assert_eq!(
+                rkr_writer_write_cframe(writer, c_frame, ptr::null()),
+                RKRStatus::RKR_STATUS_SUCCESS
+            );
+            free_rkr_writer(writer);
+
+            let original = &*(frame as *const ConFrame);
+            let written = crate::iterators::read_first_frame(&out).unwrap();
+            assert_eq!(written.atom_data, original.atom_data);
+
+            // Z = 0 needs an explicit symbol.
+            (*(*c_frame).atoms).atomic_number = 0;
+            let writer = create_writer_from_path_c(out_c.as_ptr());
+            assert_eq!(
+                rkr_writer_write_cframe(writer, c_frame, ptr::null()),
+                RKRStatus::RKR_STATUS_VALIDATION_ERROR
+            );
+            let label = CString::new("Cu1").unwrap();
+            let mut symbols = vec![ptr::null(); (*c_frame).num_atoms];
+            symbols[0] = label.as_ptr();
+            assert_eq!(
+                rkr_writer_write_cframe(writer, c_frame, symbols.as_ptr()),
+                RKRStatus::RKR_STATUS_SUCCESS
+            );
+            free_rkr_writer(writer);
+            let written = crate::iterators::read_first_frame(&out).unwrap();
+            assert_eq!(written.symbol(0), "Cu1");
+
+            free_c_frame(c_frame);
+            free_rkr_frame(frame);
+        }
+    }
+    #[test]
+    fn iterator_skip_reset_count_and_position() {
+        let path = CString::new("resources/test/tiny_multi_cuh2.con").unwrap();
+        unsafe {
+            let it = read_con_file_iterator(path.as_ptr());
+            let total = con_frame_iterator_count_remaining(it);
+            assert_eq!(total, 2);
+            assert_eq!(con_frame_iterator_position(it), 0);
+
+            assert_eq!(con_frame_iterator_skip(it, 1), 1);
+            assert_eq!(con_frame_iterator_position(it), 1);
+            assert_eq!(con_frame_iterator_count_remaining(it), total - 1);
+            let second = con_frame_iterator_next(it);
+            assert!(!second.is_null());
+            assert_eq!(con_frame_iterator_position(it), 2);
+
+            assert_eq!(con_frame_iterator_skip(it, usize::MAX), total - 2);
+            assert_eq!(rkr_last_error_status(), RKRStatus::RKR_STATUS_SUCCESS);
+            assert!(con_frame_iterator_next(it).is_null());
+            assert_eq!(con_frame_iterator_position(it), total);
+
+            assert_eq!(con_frame_iterator_reset(it), RKRStatus::RKR_STATUS_SUCCESS);
+            assert_eq!(con_frame_iterator_position(it), 0);
+            assert_eq!(con_frame_iterator_skip(it, 1), 1);
+            let again = con_frame_iterator_next(it);
+            assert_eq!(
+                (*(again as *const ConFrame)).atom_data,
+                (*(second as *const ConFrame)).atom_data
+            );
+            free_rkr_frame(again);
+            free_rkr_frame(second);
+            free_con_frame_iterator(it);
+        }
+    }
     fn test_frame_handle() -> *mut RKRConFrame {
         let mut builder = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
         builder
@@ -4212,6 +5007,7 @@ mod tests {
                 RKRStatus::RKR_STATUS_FEATURE_DISABLED,
                 "feature disabled in this build",
             ),
+            (RKRStatus::RKR_STATUS_PARSE_ERROR, "malformed frame"),
         ];
         for (status, expected) in cases {
             let message = unsafe { CStr::from_ptr(rkr_status_message(status)) };
@@ -4803,4 +5599,34 @@ mod tests {
             free_con_frame_iterator(it);
         }
     }
+    #[test]
+    fn registered_labels_resolve_over_ffi() {
+        let mut builder = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        builder.add_atom("Cu7", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+        let frame = Box::into_raw(Box::new(builder.build())) as *mut RKRConFrame;
+        let label = CString::new("Cu7").unwrap();
+        unsafe {
+            assert_eq!(
+                rkr_frame_check_species(frame),
+                RKRStatus::RKR_STATUS_VALIDATION_ERROR
+            );
+            assert_eq!(rkr_symbol_to_z(label.as_ptr()), 0);
+            assert_eq!(
+                rkr_register_element(label.as_ptr(), 93, 0.0),
+                RKRStatus::RKR_STATUS_VALIDATION_ERROR
+            );
+            assert_eq!(
+                rkr_register_element(label.as_ptr(), 29, 0.0),
+                RKRStatus::RKR_STATUS_SUCCESS
+            );
+            assert_eq!(rkr_frame_check_species(frame), RKRStatus::RKR_STATUS_SUCCESS);
+            assert_eq!(rkr_symbol_to_z(label.as_ptr()), 29);
+            let c_frame = rkr_frame_to_c_frame(frame);
+            assert_eq!((*(*c_frame).atoms).atomic_number, 29);
+            free_c_frame(c_frame);
+            assert_eq!(rkr_clear_elements(), RKRStatus::RKR_STATUS_SUCCESS);
+            assert_eq!(rkr_symbol_to_z(label.as_ptr()), 0);
+            free_rkr_frame(frame);
+        }
+    }
 }
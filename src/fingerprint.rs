@@ -0,0 +1,136 @@
+//! Content fingerprints of frames.
+//!
+//! [`ConFrame::fingerprint`] hashes what makes two frames the same
+//! structure: cell lengths and angles, per-type masses, and the symbol and
+//! position of each atom in `atom_data` order. Comment lines, metadata,
+//! atom ids, fixed flags and per-atom sections are left out. Each value is
+//! rounded to a multiple of its [`Tolerances`] entry first, so frames that
+//! differ only in how their floats were printed hash alike.
+//!
+//! The hash is FNV-1a over little-endian integers, fixed across platforms
+//! and releases, so fingerprints can be stored as keys in state databases
+//! and caches. Equal fingerprints mean equal structures up to the
+//! tolerances (barring collisions); the converse does not hold near a
+//! rounding boundary, where values closer than a tolerance can land in
+//! different steps. Confirm near-misses with
+//! [`ConFrame::approx_eq`](crate::types::ConFrame::approx_eq).
+
+use crate::tolerance::Tolerances;
+use crate::types::ConFrame;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a.
+struct Fnv(u64);
+
+impl Fnv {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    /// `v` as a number of `step`s; the bit pattern when `step` is zero.
+    fn quantized(&mut self, v: f64, step: f64) {
+        if step > 0.0 {
+            self.u64((v / step).round() as i64 as u64);
+        } else {
+            // -0.0 and 0.0 are the same number.
+            self.u64((v + 0.0).to_bits());
+        }
+    }
+}
+
+impl ConFrame {
+    /// Fingerprint at the [`Tolerances::default`] resolution; see the
+    /// [module docs](crate::fingerprint).
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("Cu", 1.0, 2.0, 3.0, [false; 3], 0, 63.546);
+    /// let a = b.build();
+    /// let mut moved = a.clone();
+    /// moved.atom_data[0].x += 1e-9;
+    /// assert_eq!(a.fingerprint(), moved.fingerprint());
+    /// moved.atom_data[0].x += 0.1;
+    /// assert_ne!(a.fingerprint(), moved.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint_with(&Tolerances::default())
+    }
+
+    /// Fingerprint rounding coordinates, cell and masses to the
+    /// `coordinate`, `cell` and `mass` steps of `tolerances`. Zero steps
+    /// hash the exact values.
+    pub fn fingerprint_with(&self, tolerances: &Tolerances) -> u64 {
+        let mut h = Fnv(FNV_OFFSET);
+        for &v in &self.header.boxl {
+            h.quantized(v, tolerances.cell);
+        }
+        for &v in &self.header.angles {
+            h.quantized(v, tolerances.cell);
+        }
+        h.u64(self.header.natms_per_type.len() as u64);
+        for (&n, &mass) in self
+            .header
+            .natms_per_type
+            .iter()
+            .zip(&self.header.masses_per_type)
+        {
+            h.u64(n as u64);
+            h.quantized(mass, tolerances.mass);
+        }
+        for atom in &self.atom_data {
+            // The length keeps `Cu` + `O` apart from `C` + `uO`.
+            let symbol = self.symbol_of(atom);
+            h.u64(symbol.len() as u64);
+            h.bytes(symbol.as_bytes());
+            for v in [atom.x, atom.y, atom.z] {
+                h.quantized(v, tolerances.coordinate);
+            }
+        }
+        h.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+    use crate::writer::ConFrameWriter;
+
+    #[test]
+    fn fingerprint_survives_rewrite_and_tracks_structure() {
+        let text = fixture("tiny_cuh2.con");
+        let frame = ConFrameIterator::new(&text).next().unwrap().unwrap();
+
+        // Fewer printed digits and a new comment keep the fingerprint.
+        let mut w = ConFrameWriter::with_precision(Vec::new(), 9);
+        let mut relabelled = frame.clone();
+        relabelled.header.prebox_header.user = "rewritten".into();
+        relabelled.header.set_energy(-1.0);
+        w.write_frame(&relabelled).unwrap();
+        let out = String::from_utf8(w.into_inner().unwrap()).unwrap();
+        let back = ConFrameIterator::new(&out).next().unwrap().unwrap();
+        assert_eq!(back.fingerprint(), frame.fingerprint());
+        assert_ne!(
+            back.fingerprint_with(&Tolerances::exact()),
+            frame.fingerprint_with(&Tolerances::exact())
+        );
+
+        let mut other = frame.clone();
+        other.header.boxl[0] += 0.01;
+        assert_ne!(other.fingerprint(), frame.fingerprint());
+        let mut other = frame.clone();
+        other.atom_data.swap(0, 1);
+        assert_ne!(other.fingerprint(), frame.fingerprint());
+    }
+}
@@ -0,0 +1,223 @@
+//! Incremental parsing of a trajectory that is still being written.
+//!
+//! A running eOn job appends frames to its `.con` output a few lines at a
+//! time, so a reader polling the file usually sees a partly written last
+//! frame. [`FrameFollower`] buffers the appended bytes and yields only the
+//! frames that are complete: text after the last newline is never parsed,
+//! and a frame that fails with one of the `Incomplete*` errors is kept
+//! until more data arrives. `readcon watch` drives it from a polling loop.
+//!
+//! A frame that ends exactly at the end of the data so far is held back
+//! unless its metadata declares its `sections`: in a legacy convel file a
+//! velocity block may still follow it. [`FrameFollower::finish`] flushes
+//! such a frame once the writer is done.
+
+use crate::error::ParseError;
+use crate::iterators::ConFrameIterator;
+use crate::parser::ParserOptions;
+use crate::types::ConFrame;
+
+/// Buffer of appended text that yields complete frames; see the
+/// [module docs](self).
+///
+/// # Example
+/// ```
+/// use readcon_core::follow::FrameFollower;
+/// let text = std::fs::read_to_string(concat!(
+///     env!("CARGO_MANIFEST_DIR"),
+///     "/resources/test/tiny_cuh2_vel_forces.con"
+/// ))
+/// .unwrap();
+/// let (head, tail) = text.split_at(text.len() / 2);
+/// let mut follower = FrameFollower::new();
+/// follower.push(head.as_bytes());
+/// assert!(follower.next_frame().is_none());
+/// follower.push(tail.as_bytes());
+/// assert!(follower.next_frame().unwrap().is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct FrameFollower {
+    buffer: Vec<u8>,
+    options: ParserOptions,
+    frames: usize,
+}
+
+/// Whether `e` means "the text ran out" rather than "the text is wrong".
+fn is_truncation(e: &ParseError) -> bool {
+    matches!(
+        e,
+        ParseError::IncompleteHeader
+            | ParseError::IncompleteFrame
+            | ParseError::IncompleteVelocitySection
+            | ParseError::IncompleteForceSection
+            | ParseError::IncompleteEnergySection
+            | ParseError::IncompleteSection(_)
+    )
+}
+
+impl FrameFollower {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse with the given [`ParserOptions`].
+    pub fn options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Appends newly read bytes. They may end mid-line or mid-character.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Bytes received but not yet consumed by a yielded frame or error.
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Frames and errors yielded so far.
+    pub fn yielded(&self) -> usize {
+        self.frames
+    }
+
+    /// Discards all buffered data, e.g. after the file was truncated and
+    /// rewritten from the start.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.frames = 0;
+    }
+
+    /// The next complete frame, or `None` until more data is pushed. A
+    /// malformed frame is yielded as an error and skipped up to the next
+    /// plausible frame header.
+    pub fn next_frame(&mut self) -> Option<Result<ConFrame, ParseError>> {
+        let complete = memchr::memrchr(b'\n', &self.buffer)? + 1;
+        let text = match std::str::from_utf8(&self.buffer[..complete]) {
+            Ok(text) => text,
+            Err(e) => {
+                // Drop through the end of the offending line.
+                let bad = e.valid_up_to();
+                let resume =
+                    memchr::memchr(b'\n', &self.buffer[bad..]).map_or(complete, |i| bad + i + 1);
+                return Some(self.fail(
+                    resume,
+                    ParseError::ValidationError(format!("invalid UTF-8: {e}")),
+                ));
+            }
+        };
+        let mut it = ConFrameIterator::new(text).options(self.options.clone());
+        let result = it.next()?;
+        let end = it.offset();
+        match result {
+            Err(e) if is_truncation(&e) => None,
+            Err(e) => {
+                let resume = if it.resync(0) {
+                    it.offset()
+                } else {
+                    // No header yet, but one may have started in the last
+                    // 8 lines: keep those, drop the rest (at least the
+                    // failing frame's first line).
+                    let first_line =
+                        memchr::memchr(b'\n', text.as_bytes()).map_or(complete, |i| i + 1);
+                    let mut keep = complete;
+                    for _ in 0..8 {
+                        keep = memchr::memrchr(b'\n', &text.as_bytes()[..keep.saturating_sub(1)])
+                            .map_or(0, |i| i + 1);
+                    }
+                    keep.max(first_line)
+                };
+                Some(self.fail(resume, e))
+            }
+            Ok(frame) if end == complete && !frame.header.sections_declared => None,
+            Ok(frame) => {
+                self.consume(end);
+                Some(Ok(frame))
+            }
+        }
+    }
+
+    /// Parses whatever is left, including a trailing frame held back by
+    /// [`next_frame`](Self::next_frame), and empties the buffer. A frame
+    /// cut short yields its `Incomplete*` error.
+    pub fn finish(&mut self) -> Vec<Result<ConFrame, ParseError>> {
+        let mut text = String::from_utf8_lossy(&self.buffer).into_owned();
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        let out: Vec<_> = ConFrameIterator::new(&text)
+            .options(self.options.clone())
+            .recover(true)
+            .collect();
+        self.buffer.clear();
+        self.frames += out.len();
+        out
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.buffer.drain(..n);
+        self.frames += 1;
+    }
+
+    fn fail(&mut self, resume: usize, e: ParseError) -> Result<ConFrame, ParseError> {
+        self.consume(resume);
+        Err(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::fixture;
+
+    /// Feeds `text` in `chunk`-byte pieces, collecting frames as they
+    /// become available.
+    fn follow(text: &str, chunk: usize) -> Vec<ConFrame> {
+        let mut follower = FrameFollower::new();
+        let mut frames = Vec::new();
+        for piece in text.as_bytes().chunks(chunk) {
+            follower.push(piece);
+            while let Some(frame) = follower.next_frame() {
+                frames.push(frame.unwrap());
+            }
+        }
+        frames.extend(follower.finish().into_iter().map(Result::unwrap));
+        frames
+    }
+
+    #[test]
+    fn any_chunking_yields_the_same_frames() {
+        for name in [
+            "tiny_multi_cuh2.con",
+            "tiny_multi_cuh2.convel",
+            "tiny_cuh2_vel_forces.con",
+        ] {
+            let text = fixture(name);
+            let expected: Vec<ConFrame> =
+                ConFrameIterator::new(&text).map(Result::unwrap).collect();
+            for chunk in [1, 7, 64, text.len()] {
+                assert_eq!(follow(&text, chunk), expected, "{name}, chunk {chunk}");
+            }
+        }
+    }
+
+    #[test]
+    fn declared_sections_are_yielded_without_waiting() {
+        let text = fixture("tiny_cuh2_vel_forces.con");
+        let mut follower = FrameFollower::new();
+        follower.push(text.as_bytes());
+        assert!(follower.next_frame().unwrap().is_ok());
+        assert_eq!(follower.pending(), 0);
+        assert_eq!(follower.yielded(), 1);
+    }
+
+    #[test]
+    fn garbage_is_reported_then_skipped() {
+        let good = fixture("tiny_cuh2_vel_forces.con");
+        let mut follower = FrameFollower::new();
+        follower.push(format!("not a frame\n{good}").as_bytes());
+        assert!(follower.next_frame().unwrap().is_err());
+        assert!(follower.next_frame().unwrap().is_ok());
+        assert!(follower.next_frame().is_none());
+    }
+}
@@ -0,0 +1,267 @@
+//! ASE `Atoms` as JSON, in the row layout of `ase.db`.
+//!
+//! [`frame_to_ase_dict`] builds the dictionary `ase.db` stores per row:
+//!
+//! ```json
+//! {"numbers":[29,1],"positions":[[..],..],"cell":[[..],[..],[..]],
+//!  "pbc":[true,true,true],"masses":[63.546,1.008],
+//!  "constraints":[{"name":"FixAtoms","kwargs":{"indices":[0]}}],
+//!  "momenta":[[..],..],"energy":-3.1,"forces":[[..],..]}
+//! ```
+//!
+//! so Python picks a frame up with
+//!
+//! ```text
+//! from ase.db.row import AtomsRow
+//! atoms = AtomsRow(json.loads(line)).toatoms()
+//! ```
+//!
+//! Atoms fixed on every axis go into one `FixAtoms`. Partly fixed atoms
+//! (see [`Constraint`]) go into one `FixedPlane` per fixed axis, normal to
+//! it, or one `FixedLine` per free axis, along it; unlike `FixCartesian`
+//! these read the same in every ASE version. The Python
+//! `ConFrame.to_ase()` builds the same constraints. Quantities are
+//! converted from the frame's units (see [`crate::units`]) to ASE's Å, eV and
+//! amu; `momenta` are mass × velocity in amu·Å per ASE time unit
+//! (Å·√(amu/eV), about 10.18 fs). Optional keys
+//! (`momenta`, `initial_charges`, `initial_magmoms`, `energy`, `forces`) are
+//! written only when every atom carries the section. Atoms stay in
+//! `atom_data` order.
+
+use crate::error::ParseError;
+use crate::frame::AtomicFrame;
+use crate::helpers::ElementRegistry;
+use crate::types::{ConFrame, Constraint};
+use crate::units::unit_conversion_factor;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// ASE time unit, Å·√(amu/eV), as a unit expression.
+const ASE_TIME: &str = "angstrom*(amu/eV)^0.5";
+
+fn fix_constraints(frame: &ConFrame) -> Vec<Value> {
+    let mut all = Vec::new();
+    let mut partial: BTreeMap<[bool; 3], Vec<usize>> = BTreeMap::new();
+    for (i, atom) in frame.atom_data.iter().enumerate() {
+        match atom.constraint() {
+            Constraint::Fixed => all.push(i),
+            Constraint::Free => {}
+            Constraint::FixedAxes(mask) => partial.entry(mask).or_default().push(i),
+        }
+    }
+    let mut constraints = Vec::new();
+    if !all.is_empty() {
+        constraints.push(json!({"name": "FixAtoms", "kwargs": {"indices": all}}));
+    }
+    for (mask, indices) in partial {
+        let Some((name, direction)) = Constraint::FixedAxes(mask).ase_constraint() else {
+            continue;
+        };
+        constraints.push(json!({
+            "name": name,
+            "kwargs": {"indices": indices, "direction": direction},
+        }));
+    }
+    constraints
+}
+
+/// The `ase.db` row dictionary for `frame` (see the [module docs](self)).
+///
+/// Errors are [`ParseError::ValidationError`] when the frame's units cannot
+/// be converted to ASE's.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::ase::frame_to_ase_dict;
+/// use readcon_core::types::ConFrameBuilder;
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+/// b.add_atom("H", 1.0, 0.0, 0.0, [false; 3], 1, 1.008);
+/// let d = frame_to_ase_dict(&b.build()).unwrap();
+/// assert_eq!(d["numbers"], serde_json::json!([29, 1]));
+/// assert_eq!(d["cell"][0], serde_json::json!([10.0, 0.0, 0.0]));
+/// assert_eq!(d["constraints"][0]["kwargs"]["indices"], serde_json::json!([0]));
+/// ```
+pub fn frame_to_ase_dict(frame: &ConFrame) -> Result<Value, ParseError> {
+    let atoms = &frame.atom_data;
+    let to_ang = frame.conversion_factor_or_assumed("length", "angstrom")?;
+    let to_ev = frame.conversion_factor_or_assumed("energy", "eV")?;
+    let mut obj = Map::new();
+    obj.insert(
+        "numbers".into(),
+        json!(
+            atoms
+                .iter()
+                .map(|a| {
+                    ElementRegistry::new()
+                        .atomic_number(frame.symbol_of(a))
+                        .unwrap_or(0)
+                })
+                .collect::<Vec<_>>()
+        ),
+    );
+    obj.insert(
+        "positions".into(),
+        json!(
+            atoms
+                .iter()
+                .map(|a| [a.x, a.y, a.z].map(|v| v * to_ang))
+                .collect::<Vec<_>>()
+        ),
+    );
+    let (cell, pbc) = match AtomicFrame::cell(frame) {
+        // cos(90°) round-off would otherwise show up as 6e-16.
+        Some(c) => (
+            c.matrix()
+                .map(|row| row.map(|v| if v.abs() < 1e-10 { 0.0 } else { v * to_ang })),
+            AtomicFrame::pbc(frame),
+        ),
+        None => ([[0.0; 3]; 3], [false; 3]),
+    };
+    obj.insert("cell".into(), json!(cell));
+    obj.insert("pbc".into(), json!(pbc));
+    let masses: Option<Vec<f64>> = (0..atoms.len()).map(|i| frame.mass(i)).collect();
+    if let Some(masses) = &masses {
+        obj.insert("masses".into(), json!(masses));
+    }
+    let constraints = fix_constraints(frame);
+    if !constraints.is_empty() {
+        obj.insert("constraints".into(), Value::Array(constraints));
+    }
+
+    let velocities: Option<Vec<_>> = atoms.iter().map(|a| a.velocity).collect();
+    if let (Some(v), Some(m)) = (velocities.filter(|v| !v.is_empty()), &masses) {
+        let to_ase = to_ang / frame.conversion_factor_or_assumed("time", "fs")?
+            * unit_conversion_factor(ASE_TIME, "fs")?;
+        let momenta: Vec<[f64; 3]> = v
+            .iter()
+            .zip(m)
+            .map(|(v, m)| v.map(|c| c * to_ase * m))
+            .collect();
+        obj.insert("momenta".into(), json!(momenta));
+    }
+    let charges: Option<Vec<_>> = atoms.iter().map(|a| a.charge).collect();
+    if let Some(q) = charges.filter(|q| !q.is_empty()) {
+        obj.insert("initial_charges".into(), json!(q));
+    }
+    let magmoms: Option<Vec<_>> = atoms.iter().map(|a| a.magmom).collect();
+    if let Some(m) = magmoms.filter(|m| !m.is_empty()) {
+        obj.insert("initial_magmoms".into(), json!(m));
+    }
+    if let Some(e) = frame.header.energy() {
+        obj.insert("energy".into(), json!(e * to_ev));
+    }
+    let forces: Option<Vec<_>> = atoms.iter().map(|a| a.force).collect();
+    if let Some(f) = forces.filter(|f| !f.is_empty()) {
+        let to_ev_ang = match frame.header.unit_for("force") {
+            Some(unit) => unit_conversion_factor(unit, "eV/angstrom")?,
+            None => to_ev / to_ang,
+        };
+        let forces: Vec<[f64; 3]> = f.iter().map(|f| f.map(|c| c * to_ev_ang)).collect();
+        obj.insert("forces".into(), json!(forces));
+    }
+    Ok(Value::Object(obj))
+}
+
+/// Writes `frame` as one `ase.db` row dictionary on its own line.
+///
+/// Errors are [`io::ErrorKind::InvalidInput`] when the frame's units cannot
+/// be converted to ASE's.
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    let dict = frame_to_ase_dict(frame)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    serde_json::to_writer(&mut *writer, &dict)?;
+    writer.write_all(b"\n")
+}
+
+/// Writes every frame of `frames` as its own line.
+pub fn write_ase_jsonl<'a, W, I>(writer: &mut W, frames: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    for frame in frames {
+        write_frame(writer, frame)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::symbol_to_atomic_number;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+
+    #[test]
+    fn fixtures_map_to_ase_rows() {
+        let text = fixture("tiny_cuh2_vel_forces.con");
+        let frame = ConFrameIterator::new(&text).next().unwrap().unwrap();
+        let d = frame_to_ase_dict(&frame).unwrap();
+        let n = frame.atom_data.len();
+        assert_eq!(d["numbers"].as_array().unwrap().len(), n);
+        assert_eq!(d["numbers"][0], symbol_to_atomic_number(frame.symbol(0)));
+        assert_eq!(d["forces"].as_array().unwrap().len(), n);
+        assert_eq!(d["pbc"], json!([true, true, true]));
+
+        // Velocity in Å/fs → momentum in amu·Å per ASE time unit.
+        let ase_fs = unit_conversion_factor(ASE_TIME, "fs").unwrap();
+        assert!((ase_fs - 10.1805).abs() < 1e-3, "{ase_fs}");
+        let (i, v) = frame
+            .atom_data
+            .iter()
+            .enumerate()
+            .find_map(|(i, a)| a.velocity.filter(|v| v[0] != 0.0).map(|v| (i, v)))
+            .unwrap();
+        let p = d["momenta"][i][0].as_f64().unwrap();
+        let expected = v[0] * frame.mass(i).unwrap() * ase_fs;
+        assert!(
+            (p - expected).abs() < 1e-9 * expected.abs(),
+            "{p} {expected}"
+        );
+    }
+
+    #[test]
+    fn fixed_flags_become_constraints_and_units_convert() {
+        let mut b = crate::types::ConFrameBuilder::new([1.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+        b.add_atom("Cu", 0.5, 0.0, 0.0, [false, false, true], 1, 63.546);
+        b.add_atom("Cu", 0.7, 0.0, 0.0, [true, false, false], 2, 63.546);
+        b.add_atom("Cu", 0.9, 0.0, 0.0, [false, false, true], 3, 63.546);
+        b.add_atom("Cu", 0.3, 0.0, 0.0, [true, true, false], 4, 63.546);
+        b.add_atom("H", 0.1, 0.0, 0.0, [false; 3], 5, 1.008);
+        b.set_energy(-0.5);
+        let mut frame = b.build();
+        frame
+            .header
+            .set_units(json!({"length": "nm", "energy": "hartree"}));
+        frame.header.set_pbc([true, true, false]);
+        let d = frame_to_ase_dict(&frame).unwrap();
+        assert_eq!(
+            d["constraints"],
+            json!([
+                {"name": "FixAtoms", "kwargs": {"indices": [0]}},
+                {"name": "FixedPlane", "kwargs": {"indices": [1, 3], "direction": [0.0, 0.0, 1.0]}},
+                {"name": "FixedPlane", "kwargs": {"indices": [2], "direction": [1.0, 0.0, 0.0]}},
+                {"name": "FixedLine", "kwargs": {"indices": [4], "direction": [0.0, 0.0, 1.0]}}
+            ])
+        );
+        assert_eq!(d["positions"][1], json!([5.0, 0.0, 0.0]));
+        assert_eq!(d["cell"][2], json!([0.0, 0.0, 10.0]));
+        assert_eq!(d["pbc"], json!([true, true, false]));
+        assert!((d["energy"].as_f64().unwrap() + 13.6057).abs() < 1e-3);
+        assert!(d.get("momenta").is_none() && d.get("forces").is_none());
+
+        let mut out = Vec::new();
+        write_ase_jsonl(&mut out, [&frame, &frame]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+
+        frame
+            .header
+            .set_units(json!({"length": "eV", "energy": "eV"}));
+        assert!(frame_to_ase_dict(&frame).is_err());
+        let err = write_frame(&mut Vec::new(), &frame).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
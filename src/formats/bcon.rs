@@ -0,0 +1,537 @@
+//! `.bcon`: a compact, randomly accessible binary trajectory format.
+//!
+//! Text `.con` trajectories must be scanned from the start to reach frame
+//! `k` and spend ~20 bytes per coordinate. A `.bcon` file stores the same
+//! frames as fixed-width little-endian records behind a frame offset index,
+//! so [`BconReader::read_frame`] seeks straight to any frame.
+//!
+//! Layout (all integers and floats little-endian):
+//!
+//! | Part | Contents |
+//! |------|----------|
+//! | file header (16 B) | magic `b"BCON"`, `u16` format version ([`BCON_VERSION`]), `u16` reserved, `u64` offset of the index |
+//! | frame record × n | see below |
+//! | index | `u64` frame count, then one `u64` file offset per frame |
+//!
+//! A frame record is `u64` atom count `N`, `u8` section mask (bit 0
+//! velocities, 1 forces, 2 energies, 3 charges, 4 spins, 5 magmoms),
+//! `f64 × 6` box lengths and angles, a `u32`-prefixed UTF-8 JSON object
+//! with the text header (comment, post-box lines, spec version, metadata,
+//! sections, per-type symbols / counts / masses, non-standard component
+//! labels), then the per-atom columns: positions `f64 × 3N`, fixed-flag
+//! bitmasks `u8 × N`, atom ids `u64 × N` and one `f64` column block per set
+//! mask bit in bit order.
+//!
+//! Named [`properties`](crate::properties) and extra coordinate columns are
+//! not stored, matching what [`ConFrameWriter`](crate::writer::ConFrameWriter)
+//! keeps. The index is written by [`BconWriter::finish`]; a file whose writer
+//! was dropped unfinished has a zero index offset and is rejected on open.
+
+use crate::error::ParseError;
+use crate::types::{
+    AtomDatum, ConFrame, FrameHeader, PreboxHeader, con_frame_from_atom_data, decode_fixed_bitmask,
+    encode_fixed_bitmask, intern_symbol, meta,
+};
+use serde_json::{Value, json};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// File signature at offset 0.
+pub const BCON_MAGIC: [u8; 4] = *b"BCON";
+/// Format version written by this build; readers reject newer files.
+pub const BCON_VERSION: u16 = 1;
+
+const FILE_HEADER_LEN: u64 = 16;
+const VELOCITIES: u8 = 1 << 0;
+const FORCES: u8 = 1 << 1;
+const ENERGIES: u8 = 1 << 2;
+const CHARGES: u8 = 1 << 3;
+const SPINS: u8 = 1 << 4;
+const MAGMOMS: u8 = 1 << 5;
+
+fn invalid_data(msg: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn write_f64s<W: Write>(w: &mut W, values: impl IntoIterator<Item = f64>) -> io::Result<()> {
+    for v in values {
+        w.write_all(&v.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Streams frames into a `.bcon` file; call [`Self::finish`] to write the
+/// index.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::bcon::{BconReader, BconWriter};
+/// use readcon_core::types::ConFrameBuilder;
+/// use std::io::Cursor;
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+/// let frame = b.build();
+///
+/// let mut w = BconWriter::new(Cursor::new(Vec::new())).unwrap();
+/// w.extend([&frame, &frame]).unwrap();
+/// let bytes = w.finish().unwrap().into_inner();
+///
+/// let mut r = BconReader::new(Cursor::new(bytes)).unwrap();
+/// assert_eq!(r.len(), 2);
+/// assert_eq!(r.read_frame(1).unwrap(), frame);
+/// ```
+pub struct BconWriter<W: Write + Seek> {
+    writer: W,
+    offsets: Vec<u64>,
+    pos: u64,
+}
+
+impl BconWriter<BufWriter<File>> {
+    /// Creates (or truncates) `path`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write + Seek> BconWriter<W> {
+    /// Writes the file header at the current position of `writer`, which
+    /// must be the start of the output.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(&BCON_MAGIC)?;
+        writer.write_all(&BCON_VERSION.to_le_bytes())?;
+        writer.write_all(&[0; 2])?;
+        writer.write_all(&0u64.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            offsets: Vec::new(),
+            pos: FILE_HEADER_LEN,
+        })
+    }
+
+    /// Number of frames written so far.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// True before the first frame.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Appends one frame record.
+    pub fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        let record = encode_frame(frame)?;
+        self.writer.write_all(&record)?;
+        self.offsets.push(self.pos);
+        self.pos += record.len() as u64;
+        Ok(())
+    }
+
+    /// Appends every frame of `frames`.
+    pub fn extend<'a, I>(&mut self, frames: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'a ConFrame>,
+    {
+        for frame in frames {
+            self.write_frame(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the offset index, points the file header at it and returns
+    /// the flushed writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let index_offset = self.pos;
+        self.writer
+            .write_all(&(self.offsets.len() as u64).to_le_bytes())?;
+        for off in &self.offsets {
+            self.writer.write_all(&off.to_le_bytes())?;
+        }
+        let end = self.writer.stream_position()?;
+        self.writer.seek(SeekFrom::Start(8))?;
+        self.writer.write_all(&index_offset.to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn section_mask(atoms: &[AtomDatum]) -> u8 {
+    if atoms.is_empty() {
+        return 0;
+    }
+    let all = |has: fn(&AtomDatum) -> bool| atoms.iter().all(has);
+    let mut mask = 0;
+    for (bit, has) in [
+        (
+            VELOCITIES,
+            AtomDatum::has_velocity as fn(&AtomDatum) -> bool,
+        ),
+        (FORCES, AtomDatum::has_forces),
+        (ENERGIES, AtomDatum::has_energy),
+        (CHARGES, AtomDatum::has_charge),
+        (SPINS, AtomDatum::has_spin),
+        (MAGMOMS, AtomDatum::has_magmom),
+    ] {
+        if all(has) {
+            mask |= bit;
+        }
+    }
+    mask
+}
+
+fn encode_frame(frame: &ConFrame) -> io::Result<Vec<u8>> {
+    let h = &frame.header;
+    let atoms = &frame.atom_data;
+    let n = atoms.len();
+    if h.natms_per_type.iter().sum::<usize>() != n {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "natms_per_type {:?} does not cover {n} atoms",
+                h.natms_per_type
+            ),
+        ));
+    }
+    let mut offset = 0;
+    let symbols: Vec<&str> = h
+        .natms_per_type
+        .iter()
+        .map(|&count| {
+            let s = atoms.get(offset).map_or("", |a| frame.symbol_of(a));
+            offset += count;
+            s
+        })
+        .collect();
+    let mut text = json!({
+        "comment": h.prebox_header.user,
+        "postbox": h.postbox_header,
+        "spec_version": h.spec_version,
+        "metadata": h.metadata,
+        "sections": h.sections,
+        "symbols": symbols,
+        "natms_per_type": h.natms_per_type,
+        "masses_per_type": h.masses_per_type,
+    });
+    if !h.component_labels.is_empty() {
+        text["component_labels"] = json!(h.component_labels);
+    }
+    let text = serde_json::to_vec(&text)?;
+    let mask = section_mask(atoms);
+
+    let mut out = Vec::with_capacity(64 + text.len() + n * 33);
+    out.extend_from_slice(&(n as u64).to_le_bytes());
+    out.push(mask);
+    write_f64s(&mut out, h.boxl.into_iter().chain(h.angles))?;
+    out.extend_from_slice(&(text.len() as u32).to_le_bytes());
+    out.extend_from_slice(&text);
+    write_f64s(&mut out, atoms.iter().flat_map(|a| [a.x, a.y, a.z]))?;
+    out.extend(atoms.iter().map(|a| encode_fixed_bitmask(a.fixed)));
+    for a in atoms {
+        out.extend_from_slice(&a.atom_id.to_le_bytes());
+    }
+    let vec3 = |v: Option<[f64; 3]>| v.unwrap_or_default();
+    let scalar = |v: Option<f64>| v.unwrap_or_default();
+    if mask & VELOCITIES != 0 {
+        write_f64s(&mut out, atoms.iter().flat_map(|a| vec3(a.velocity)))?;
+    }
+    if mask & FORCES != 0 {
+        write_f64s(&mut out, atoms.iter().flat_map(|a| vec3(a.force)))?;
+    }
+    if mask & ENERGIES != 0 {
+        write_f64s(&mut out, atoms.iter().map(|a| scalar(a.energy)))?;
+    }
+    if mask & CHARGES != 0 {
+        write_f64s(&mut out, atoms.iter().map(|a| scalar(a.charge)))?;
+    }
+    if mask & SPINS != 0 {
+        write_f64s(&mut out, atoms.iter().map(|a| scalar(a.spin)))?;
+    }
+    if mask & MAGMOMS != 0 {
+        write_f64s(&mut out, atoms.iter().flat_map(|a| vec3(a.magmom)))?;
+    }
+    Ok(out)
+}
+
+/// Random-access reader over a finished `.bcon` file.
+pub struct BconReader<R: Read + Seek> {
+    reader: R,
+    offsets: Vec<u64>,
+}
+
+impl BconReader<BufReader<File>> {
+    /// Opens `path` and loads its frame index.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+fn read_array<R: Read, const N: usize>(r: &mut R) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    read_array(r).map(u64::from_le_bytes)
+}
+
+fn read_f64<R: Read>(r: &mut R) -> io::Result<f64> {
+    read_array(r).map(f64::from_le_bytes)
+}
+
+fn read_vec3<R: Read>(r: &mut R) -> io::Result<[f64; 3]> {
+    Ok([read_f64(r)?, read_f64(r)?, read_f64(r)?])
+}
+
+impl<R: Read + Seek> BconReader<R> {
+    /// Checks the file header and loads the frame index.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        reader.seek(SeekFrom::Start(0))?;
+        let magic: [u8; 4] = read_array(&mut reader)?;
+        if magic != BCON_MAGIC {
+            return Err(invalid_data("not a .bcon file (bad magic)"));
+        }
+        let version = u16::from_le_bytes(read_array(&mut reader)?);
+        if version > BCON_VERSION {
+            return Err(invalid_data(format!(
+                ".bcon format version {version} is newer than supported {BCON_VERSION}"
+            )));
+        }
+        let _reserved: [u8; 2] = read_array(&mut reader)?;
+        let index_offset = read_u64(&mut reader)?;
+        if index_offset < FILE_HEADER_LEN {
+            return Err(invalid_data(
+                ".bcon file has no index (writer not finished)",
+            ));
+        }
+        reader.seek(SeekFrom::Start(index_offset))?;
+        let count = read_u64(&mut reader)?;
+        let offsets = (0..count)
+            .map(|_| read_u64(&mut reader))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self { reader, offsets })
+    }
+
+    /// Number of frames in the file.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// True for a file without frames.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Reads frame `index` (0-based).
+    ///
+    /// Errors are [`io::ErrorKind::InvalidInput`] for an index past the end
+    /// and [`io::ErrorKind::InvalidData`] for a corrupt record.
+    pub fn read_frame(&mut self, index: usize) -> io::Result<ConFrame> {
+        let Some(&offset) = self.offsets.get(index) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("frame {index} out of range for {} frames", self.len()),
+            ));
+        };
+        self.reader.seek(SeekFrom::Start(offset))?;
+        decode_frame(&mut self.reader)
+    }
+
+    /// Iterates over all frames in file order.
+    pub fn frames(&mut self) -> impl Iterator<Item = io::Result<ConFrame>> + '_ {
+        (0..self.len()).map(move |i| self.read_frame(i))
+    }
+}
+
+fn json_err(e: impl std::fmt::Display) -> io::Error {
+    invalid_data(ParseError::InvalidMetadataJson(e.to_string()))
+}
+
+fn decode_frame<R: Read>(r: &mut R) -> io::Result<ConFrame> {
+    let n = usize::try_from(read_u64(r)?).map_err(invalid_data)?;
+    let [mask] = read_array(r)?;
+    let boxl = read_vec3(r)?;
+    let angles = read_vec3(r)?;
+    let text_len = u32::from_le_bytes(read_array(r)?) as usize;
+    let mut text = vec![0u8; text_len];
+    r.read_exact(&mut text)?;
+    let text: Value = serde_json::from_slice(&text).map_err(json_err)?;
+
+    let strings = |key: &str| -> io::Result<Vec<String>> {
+        text.get(key)
+            .and_then(Value::as_array)
+            .map(|a| a.iter().map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or(Some(Vec::new()))
+            .ok_or_else(|| json_err(format!("{key}: expected strings")))
+    };
+    let symbols = strings("symbols")?;
+    let sections = strings("sections")?;
+    let postbox = strings("postbox")?;
+    let component_labels = strings("component_labels")?;
+    let natms_per_type: Vec<usize> = text
+        .get("natms_per_type")
+        .and_then(Value::as_array)
+        .and_then(|a| a.iter().map(|v| v.as_u64().map(|c| c as usize)).collect())
+        .ok_or_else(|| json_err("natms_per_type: expected unsigned integers"))?;
+    let masses_per_type: Vec<f64> = text
+        .get("masses_per_type")
+        .and_then(Value::as_array)
+        .and_then(|a| a.iter().map(Value::as_f64).collect())
+        .ok_or_else(|| json_err("masses_per_type: expected numbers"))?;
+    if symbols.len() != natms_per_type.len()
+        || masses_per_type.len() != natms_per_type.len()
+        || natms_per_type.iter().sum::<usize>() != n
+        || !(component_labels.is_empty() || component_labels.len() == natms_per_type.len())
+    {
+        return Err(invalid_data("per-type tables do not match the atom count"));
+    }
+    let metadata = match text.get("metadata") {
+        Some(Value::Object(m)) => m.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        _ => Default::default(),
+    };
+
+    let positions = (0..n)
+        .map(|_| read_vec3(r))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut fixed = vec![0u8; n];
+    r.read_exact(&mut fixed)?;
+    let ids = (0..n)
+        .map(|_| read_u64(r))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut vec3_block = |bit: u8| -> io::Result<Option<Vec<[f64; 3]>>> {
+        (mask & bit != 0)
+            .then(|| (0..n).map(|_| read_vec3(r)).collect())
+            .transpose()
+    };
+    let velocities = vec3_block(VELOCITIES)?;
+    let forces = vec3_block(FORCES)?;
+    let mut scalar_block = |bit: u8| -> io::Result<Option<Vec<f64>>> {
+        (mask & bit != 0)
+            .then(|| (0..n).map(|_| read_f64(r)).collect())
+            .transpose()
+    };
+    let energies = scalar_block(ENERGIES)?;
+    let charges = scalar_block(CHARGES)?;
+    let spins = scalar_block(SPINS)?;
+    let magmoms = (mask & MAGMOMS != 0)
+        .then(|| (0..n).map(|_| read_vec3(r)).collect::<io::Result<Vec<_>>>())
+        .transpose()?;
+
+    let mut symbol_table = Vec::new();
+    let type_symbols = symbols
+        .iter()
+        .map(|s| intern_symbol(&mut symbol_table, s))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| invalid_data("more than 65536 distinct symbols"))?;
+    let atom_data = natms_per_type
+        .iter()
+        .zip(&type_symbols)
+        .flat_map(|(&count, sym)| std::iter::repeat_n(sym, count))
+        .enumerate()
+        .map(|(i, sym)| {
+            let [x, y, z] = positions[i];
+            AtomDatum {
+                symbol: *sym,
+                x,
+                y,
+                z,
+                fixed: decode_fixed_bitmask(fixed[i]),
+                atom_id: ids[i],
+                velocity: velocities.as_ref().map(|v| v[i]),
+                force: forces.as_ref().map(|v| v[i]),
+                energy: energies.as_ref().map(|v| v[i]),
+                charge: charges.as_ref().map(|v| v[i]),
+                spin: spins.as_ref().map(|v| v[i]),
+                magmom: magmoms.as_ref().map(|v| v[i]),
+                extra: Vec::new(),
+            }
+        })
+        .collect();
+
+    let strict_validation = matches!(
+        text.get("metadata").and_then(|m| m.get(meta::VALIDATE)),
+        Some(Value::Bool(true))
+    );
+    let line = |i: usize| postbox.get(i).cloned().unwrap_or_default();
+    let header = FrameHeader {
+        prebox_header: PreboxHeader::new(text.get("comment").and_then(Value::as_str).unwrap_or("")),
+        boxl,
+        angles,
+        postbox_header: [line(0), line(1)],
+        natm_types: natms_per_type.len(),
+        natms_per_type,
+        masses_per_type,
+        component_labels,
+        spec_version: text
+            .get("spec_version")
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(crate::CON_SPEC_VERSION),
+        metadata,
+        sections_declared: !sections.is_empty(),
+        sections,
+        strict_validation,
+    };
+    Ok(con_frame_from_atom_data(header, symbol_table, atom_data))
+}
+
+/// Writes `frames` to a new `.bcon` file at `path`.
+pub fn write_bcon<'a, I>(path: impl AsRef<Path>, frames: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    let mut w = BconWriter::create(path)?;
+    w.extend(frames)?;
+    w.finish()?;
+    Ok(())
+}
+
+/// Reads every frame of the `.bcon` file at `path`.
+pub fn read_bcon(path: impl AsRef<Path>) -> io::Result<Vec<ConFrame>> {
+    BconReader::open(path)?.frames().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::fixture_frames;
+    use std::io::Cursor;
+
+    #[test]
+    fn fixtures_round_trip_with_random_access() {
+        for name in [
+            "tiny_multi_cuh2.con",
+            "tiny_cuh2_vel_forces.con",
+            "tiny_cuh2_charges_spins_magmoms.con",
+            "tiny_multi_cuh2.convel",
+        ] {
+            let frames = fixture_frames(name);
+            let mut w = BconWriter::new(Cursor::new(Vec::new())).unwrap();
+            w.extend(&frames).unwrap();
+            let bytes = w.finish().unwrap().into_inner();
+            let mut r = BconReader::new(Cursor::new(bytes)).unwrap();
+            assert_eq!(r.len(), frames.len(), "{name}");
+            let last = frames.len() - 1;
+            assert_eq!(r.read_frame(last).unwrap(), frames[last], "{name}");
+            let all: Vec<ConFrame> = r.frames().collect::<io::Result<_>>().unwrap();
+            assert_eq!(all, frames, "{name}");
+            assert!(r.read_frame(frames.len()).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_unfinished_and_foreign_files() {
+        let frames = fixture_frames("tiny_cuh2.con");
+        let mut w = BconWriter::new(Cursor::new(Vec::new())).unwrap();
+        w.write_frame(&frames[0]).unwrap();
+        let unfinished = w.writer.into_inner();
+        let err = BconReader::new(Cursor::new(unfinished)).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = BconReader::new(Cursor::new(b"CON text, not binary".to_vec()))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
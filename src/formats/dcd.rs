@@ -0,0 +1,315 @@
+//! CHARMM/NAMD binary DCD trajectories, as read by VMD.
+//!
+//! DCD carries coordinates only (single precision, one Fortran record per
+//! axis) plus an optional unit cell per frame; atom names and elements come
+//! from a separate topology file. [`write_pdb_topology`] produces that
+//! topology from the first frame, so a `.con` trajectory opens in VMD with
+//!
+//! ```text
+//! vmd topology.pdb traj.dcd
+//! ```
+//!
+//! Atoms are written in `atom_data` order, so every frame must hold the same
+//! number of atoms in the same species order as the topology frame.
+//! Coordinates and cell lengths are converted from the frame's `units.length`
+//! to the Å that DCD and PDB store.
+
+use crate::types::ConFrame;
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// CHARMM version stamped into the header; VMD checks it to enable the
+/// unit cell record.
+const CHARMM_VERSION: i32 = 24;
+/// File offset of NSET (the frame count) inside the first record.
+const NSET_OFFSET: u64 = 8;
+
+/// Writes one Fortran unformatted record: length marker, payload, marker.
+fn record<W: Write>(w: &mut W, payload: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(payload.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "DCD record too large"))?;
+    w.write_all(&len.to_le_bytes())?;
+    w.write_all(payload)?;
+    w.write_all(&len.to_le_bytes())
+}
+
+/// Streams frames into a DCD file; call [`Self::finish`] to fix the frame
+/// count in the header.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::dcd::DcdWriter;
+/// use readcon_core::types::ConFrameBuilder;
+/// use std::io::Cursor;
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("Ar", 1.0, 2.0, 3.0, [false; 3], 0, 39.948);
+/// let frame = b.build();
+/// let mut w = DcdWriter::new(Cursor::new(Vec::new()));
+/// w.write_frame(&frame).unwrap();
+/// w.write_frame(&frame).unwrap();
+/// let bytes = w.finish().unwrap().into_inner();
+/// assert_eq!(&bytes[4..8], b"CORD");
+/// ```
+pub struct DcdWriter<W: Write + Seek> {
+    writer: W,
+    natoms: Option<usize>,
+    nframes: usize,
+    timestep: f32,
+    title: String,
+}
+
+impl DcdWriter<BufWriter<File>> {
+    /// Creates (or truncates) `path`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Seek> DcdWriter<W> {
+    /// A writer positioned at the start of `writer`. The header is written
+    /// with the first frame, once the atom count is known.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            natoms: None,
+            nframes: 0,
+            timestep: 1.0,
+            title: format!("Created by readcon-core {}", crate::VERSION),
+        }
+    }
+
+    /// Timestep between frames stored in the header (AKMA units in CHARMM;
+    /// VMD ignores it). Defaults to 1.
+    pub fn timestep(mut self, dt: f32) -> Self {
+        self.timestep = dt;
+        self
+    }
+
+    /// Title line stored in the header (truncated to 80 bytes).
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Number of frames written so far.
+    pub fn len(&self) -> usize {
+        self.nframes
+    }
+
+    /// True before the first frame.
+    pub fn is_empty(&self) -> bool {
+        self.nframes == 0
+    }
+
+    fn write_header(&mut self, natoms: usize) -> io::Result<()> {
+        let natoms = i32::try_from(natoms)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many atoms for DCD"))?;
+        let mut icntrl = [0i32; 20];
+        icntrl[1] = 0; // ISTART
+        icntrl[2] = 1; // NSAVC
+        icntrl[10] = 1; // unit cell present
+        icntrl[19] = CHARMM_VERSION;
+        let mut first = Vec::with_capacity(84);
+        first.extend_from_slice(b"CORD");
+        for (i, v) in icntrl.iter().enumerate() {
+            if i == 9 {
+                first.extend_from_slice(&self.timestep.to_le_bytes());
+            } else {
+                first.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        record(&mut self.writer, &first)?;
+
+        let mut title = [b' '; 80];
+        let bytes = self.title.as_bytes();
+        let n = bytes.len().min(80);
+        title[..n].copy_from_slice(&bytes[..n]);
+        let mut second = Vec::with_capacity(84);
+        second.extend_from_slice(&1i32.to_le_bytes());
+        second.extend_from_slice(&title);
+        record(&mut self.writer, &second)?;
+
+        record(&mut self.writer, &natoms.to_le_bytes())
+    }
+
+    /// Appends one frame.
+    ///
+    /// Errors are [`io::ErrorKind::InvalidInput`] when the frame's atom count
+    /// differs from the first frame's or its length unit cannot be converted
+    /// to Å.
+    pub fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        let n = frame.atom_data.len();
+        match self.natoms {
+            None => {
+                self.write_header(n)?;
+                self.natoms = Some(n);
+            }
+            Some(expected) if expected != n => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("DCD frame has {n} atoms, trajectory has {expected}"),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        // CHARMM order: a, gamma, b, beta, alpha, c (lengths in Å, angles
+        // in degrees).
+        let to_ang = super::length_factor_to_angstrom(frame)?;
+        let [a, b, c] = frame.header.boxl.map(|l| l * to_ang);
+        let [alpha, beta, gamma] = frame.header.angles;
+        let cell: Vec<u8> = [a, gamma, b, beta, alpha, c]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        record(&mut self.writer, &cell)?;
+
+        let mut axis = Vec::with_capacity(4 * n);
+        for k in 0..3 {
+            axis.clear();
+            for atom in &frame.atom_data {
+                let x = ([atom.x, atom.y, atom.z][k] * to_ang) as f32;
+                axis.extend_from_slice(&x.to_le_bytes());
+            }
+            record(&mut self.writer, &axis)?;
+        }
+        self.nframes += 1;
+        Ok(())
+    }
+
+    /// Appends every frame of `frames`.
+    pub fn extend<'a, I>(&mut self, frames: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'a ConFrame>,
+    {
+        for frame in frames {
+            self.write_frame(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Stores the frame count in the header and returns the flushed writer.
+    ///
+    /// Errors are [`io::ErrorKind::InvalidInput`] when no frame was written
+    /// (a DCD file needs the atom count from a frame).
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.natoms.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot write a DCD file without frames",
+            ));
+        }
+        let nset = i32::try_from(self.nframes)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many DCD frames"))?;
+        let end = self.writer.stream_position()?;
+        self.writer.seek(SeekFrom::Start(NSET_OFFSET))?;
+        self.writer.write_all(&nset.to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Writes `frames` to a new DCD file at `path`.
+pub fn write_dcd<'a, I>(path: impl AsRef<Path>, frames: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    let mut w = DcdWriter::create(path)?;
+    w.extend(frames)?;
+    w.finish()?;
+    Ok(())
+}
+
+/// Writes `frame` as a minimal PDB (`CRYST1` plus one `HETATM` per atom in
+/// `atom_data` order) for use as the DCD topology.
+///
+/// Atom names are the element symbols; residues are numbered by species.
+/// Serial numbers wrap at 99999 as PDB requires, which VMD accepts.
+pub fn write_pdb_topology<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    let to_ang = super::length_factor_to_angstrom(frame)?;
+    let [a, b, c] = frame.header.boxl.map(|l| l * to_ang);
+    let [alpha, beta, gamma] = frame.header.angles;
+    writeln!(
+        writer,
+        "CRYST1{a:9.3}{b:9.3}{c:9.3}{alpha:7.2}{beta:7.2}{gamma:7.2} P 1           1"
+    )?;
+    let mut residue = 0;
+    let mut prev: Option<&str> = None;
+    for (i, atom) in frame.atom_data.iter().enumerate() {
+        let sym = frame.symbol_of(atom);
+        if prev != Some(sym) {
+            residue += 1;
+            prev = Some(sym);
+        }
+        let resname: String = sym.chars().take(3).collect();
+        let element: String = sym.chars().take(2).collect();
+        writeln!(
+            writer,
+            "HETATM{:5} {:<4} {:>3} X{:4}    {:8.3}{:8.3}{:8.3}{:6.2}{:6.2}          {:>2}",
+            (i + 1) % 100_000,
+            sym,
+            resname,
+            residue % 10_000,
+            atom.x * to_ang,
+            atom.y * to_ang,
+            atom.z * to_ang,
+            1.0,
+            0.0,
+            element
+        )?;
+    }
+    writeln!(writer, "END")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+    use std::io::Cursor;
+
+    fn i32_at(b: &[u8], off: usize) -> i32 {
+        i32::from_le_bytes(b[off..off + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn layout_matches_charmm_records() {
+        let text = fixture("tiny_multi_cuh2.con");
+        let frames: Vec<ConFrame> = ConFrameIterator::new(&text)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let n = frames[0].atom_data.len();
+        let mut w = DcdWriter::new(Cursor::new(Vec::new()));
+        w.extend(&frames).unwrap();
+        let bytes = w.finish().unwrap().into_inner();
+
+        assert_eq!(i32_at(&bytes, 0), 84);
+        assert_eq!(i32_at(&bytes, 8), frames.len() as i32);
+        assert_eq!(i32_at(&bytes, 4 + 4 + 4 * 10), 1);
+        assert_eq!(i32_at(&bytes, 4 + 4 + 4 * 19), CHARMM_VERSION);
+        let header = (4 + 84 + 4) + (4 + 84 + 4) + (4 + 4 + 4);
+        assert_eq!(i32_at(&bytes, header - 8), n as i32);
+        let frame_len = (4 + 48 + 4) + 3 * (4 + 4 * n + 4);
+        assert_eq!(bytes.len(), header + frames.len() * frame_len);
+
+        // First x coordinate of the second frame.
+        let x0 = header + frame_len + (4 + 48 + 4) + 4;
+        let x = f32::from_le_bytes(bytes[x0..x0 + 4].try_into().unwrap());
+        assert_eq!(x, frames[1].atom_data[0].x as f32);
+
+        let mut bad = frames[0].clone();
+        bad.atom_data.pop();
+        let mut w = DcdWriter::new(Cursor::new(Vec::new()));
+        w.write_frame(&frames[0]).unwrap();
+        assert!(w.write_frame(&bad).is_err());
+        assert!(DcdWriter::new(Cursor::new(Vec::new())).finish().is_err());
+
+        let mut pdb = Vec::new();
+        write_pdb_topology(&mut pdb, &frames[0]).unwrap();
+        let pdb = String::from_utf8(pdb).unwrap();
+        assert_eq!(pdb.lines().count(), n + 2);
+        assert!(pdb.lines().nth(1).unwrap().starts_with("HETATM    1 Cu"));
+    }
+}
@@ -0,0 +1,389 @@
+//! GROMACS `.gro` coordinate files.
+//!
+//! A `.gro` frame is a title line, the atom count, one fixed-column line per
+//! atom (residue number and name, atom name and number, position in nm and
+//! optionally velocity in nm/ps) and a box line holding the lattice vectors
+//! in nm. Several frames may follow each other in one file.
+//!
+//! Coordinates are converted between nm and the frame's `units.length`
+//! (Å when unset), velocities between nm/ps and `units.length`/`units.time`
+//! (Å/fs when unset). GROMACS names atoms and residues rather than elements,
+//! so the reader guesses the element from the atom name (`OW` → O, `HW1`
+//! → H, and two-letter ions such as `NA` in residue `NA` → Na) and keeps the
+//! original names in the `atom_name`, `resname` and `resid`
+//! [properties](crate::properties). The writer uses those properties when
+//! present and otherwise names each atom after its element, one residue per
+//! atom.
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::helpers::{atomic_mass, symbol_to_atomic_number};
+use crate::properties::PropertyArray;
+use crate::types::{ConFrame, ConFrameBuilder};
+use crate::units::{nm_to_angstrom, unit_conversion_factor};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Property holding the GROMACS atom name of each atom.
+pub const PROP_ATOM_NAME: &str = "atom_name";
+/// Property holding the residue name of each atom.
+pub const PROP_RESNAME: &str = "resname";
+/// Property holding the residue number of each atom.
+pub const PROP_RESID: &str = "resid";
+
+fn invalid(msg: String) -> ParseError {
+    ParseError::ValidationError(format!("gro: {msg}"))
+}
+
+/// `(length, velocity)` factors from frame units to nm and nm/ps.
+fn factors_to_gro(frame: &ConFrame) -> Result<(f64, f64), ParseError> {
+    let length = frame.conversion_factor_or_assumed("length", "nm")?;
+    Ok((
+        length,
+        length / frame.conversion_factor_or_assumed("time", "ps")?,
+    ))
+}
+
+/// Element symbol guessed from a GROMACS atom name and its residue name.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::gro::element_from_names;
+/// assert_eq!(element_from_names("OW", "SOL"), "O");
+/// assert_eq!(element_from_names("HW2", "SOL"), "H");
+/// assert_eq!(element_from_names("CA", "ALA"), "C");
+/// assert_eq!(element_from_names("CA", "CA"), "Ca");
+/// assert_eq!(element_from_names("Cu", "CU"), "Cu");
+/// ```
+pub fn element_from_names(atom_name: &str, resname: &str) -> String {
+    let letters: String = atom_name
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect();
+    let mut chars = letters.chars();
+    let Some(first) = chars.next() else {
+        return atom_name.to_string();
+    };
+    let one = first.to_ascii_uppercase().to_string();
+    if let Some(second) = chars.next() {
+        let two = format!("{one}{}", second.to_ascii_lowercase());
+        // A lowercase second letter is an explicit element ("Cu"); a name
+        // equal to its residue is a monatomic ion ("NA" in "NA").
+        let explicit = second.is_ascii_lowercase() || letters.eq_ignore_ascii_case(resname.trim());
+        if explicit && symbol_to_atomic_number(&two) != 0 {
+            return two;
+        }
+    }
+    if symbol_to_atomic_number(&one) != 0 {
+        one
+    } else {
+        letters
+    }
+}
+
+fn string_property<'a>(frame: &'a ConFrame, name: &str) -> Option<&'a [String]> {
+    match frame.property(name) {
+        Some(PropertyArray::Str(v)) => Some(v),
+        _ => None,
+    }
+}
+
+/// Writes `frame` as one `.gro` frame.
+///
+/// Errors are [`io::ErrorKind::InvalidInput`] when the frame's units cannot
+/// be converted to nm.
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    let (to_nm, to_nm_ps) = factors_to_gro(frame)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    let title = frame.header.prebox_header.user.trim();
+    if title.is_empty() {
+        writeln!(writer, "Generated by readcon-core")?;
+    } else {
+        writeln!(writer, "{title}")?;
+    }
+    writeln!(writer, "{:5}", frame.atom_data.len())?;
+
+    let atom_names = string_property(frame, PROP_ATOM_NAME);
+    let resnames = string_property(frame, PROP_RESNAME);
+    let resids = match frame.property(PROP_RESID) {
+        Some(PropertyArray::Int(v)) => Some(v.as_slice()),
+        _ => None,
+    };
+    let has_vel = !frame.atom_data.is_empty() && frame.atom_data.iter().all(|a| a.has_velocity());
+    for (i, atom) in frame.atom_data.iter().enumerate() {
+        let resid = resids.map_or(i as i64 + 1, |r| r[i]);
+        let resname = match resnames {
+            Some(r) => r[i].clone(),
+            None => frame.symbol_of(atom).to_ascii_uppercase(),
+        };
+        let name = atom_names.map_or(frame.symbol_of(atom), |n| n[i].as_str());
+        write!(
+            writer,
+            "{:5}{:<5.5}{:>5.5}{:5}{:8.3}{:8.3}{:8.3}",
+            resid.rem_euclid(100_000),
+            resname,
+            name,
+            (i + 1) % 100_000,
+            atom.x * to_nm,
+            atom.y * to_nm,
+            atom.z * to_nm
+        )?;
+        if let (true, Some(v)) = (has_vel, atom.velocity) {
+            write!(
+                writer,
+                "{:8.4}{:8.4}{:8.4}",
+                v[0] * to_nm_ps,
+                v[1] * to_nm_ps,
+                v[2] * to_nm_ps
+            )?;
+        }
+        writeln!(writer)?;
+    }
+
+    let m = Cell::from_header(&frame.header).map_or([[0.0; 3]; 3], |c| c.matrix());
+    let m = m.map(|row| row.map(|v| v * to_nm));
+    let diagonal = [m[0][0], m[1][1], m[2][2]];
+    let off = [m[0][1], m[0][2], m[1][0], m[1][2], m[2][0], m[2][1]];
+    for v in diagonal {
+        write!(writer, "{v:10.5}")?;
+    }
+    if off.iter().any(|v| v.abs() > 5e-6) {
+        for v in off {
+            write!(writer, "{v:10.5}")?;
+        }
+    }
+    writeln!(writer)
+}
+
+/// Writes every frame of `frames`, one after the other.
+pub fn write_gro<'a, W, I>(writer: &mut W, frames: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    for frame in frames {
+        write_frame(writer, frame)?;
+    }
+    Ok(())
+}
+
+/// Writes `frames` to a new `.gro` file at `path`.
+pub fn write_gro_to_path<'a, I>(path: &Path, frames: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    let mut w = io::BufWriter::new(std::fs::File::create(path)?);
+    write_gro(&mut w, frames)?;
+    w.flush()
+}
+
+/// One atom line of a `.gro` frame, converted to Å and Å/fs.
+struct GroAtom {
+    resid: i64,
+    resname: String,
+    name: String,
+    position: [f64; 3],
+    velocity: Option<[f64; 3]>,
+}
+
+/// Parses three fixed-width numbers starting at byte `start` of `line`, with
+/// the width taken from the distance between decimal points as GROMACS does.
+/// Returns the values and the field width.
+fn fixed_vec3(line: &str, start: usize) -> Result<([f64; 3], usize), ParseError> {
+    let rest = line.get(start..).unwrap_or("");
+    let dots: Vec<usize> = rest.match_indices('.').map(|(i, _)| i).take(2).collect();
+    let width = match dots.as_slice() {
+        [a, b] => b - a,
+        _ => 8,
+    };
+    let mut v = [0.0; 3];
+    for (k, out) in v.iter_mut().enumerate() {
+        let field = rest
+            .get(k * width..(k + 1) * width)
+            .or_else(|| rest.get(k * width..))
+            .ok_or(ParseError::InvalidVectorLength {
+                expected: 3,
+                found: k,
+            })?;
+        *out = field
+            .trim()
+            .parse::<f64>()
+            .map_err(|e| ParseError::InvalidNumberFormat(format!("{field:?}: {e}")))?;
+    }
+    Ok((v, width))
+}
+
+/// Lazily parses `.gro` text, one [`ConFrame`] per frame.
+pub struct GroFrameIterator<'a> {
+    lines: std::str::Lines<'a>,
+}
+
+impl<'a> GroFrameIterator<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            lines: text.lines(),
+        }
+    }
+
+    fn parse_frame(&mut self, title: &str) -> Result<ConFrame, ParseError> {
+        let n: usize = self
+            .lines
+            .next()
+            .ok_or(ParseError::IncompleteHeader)?
+            .trim()
+            .parse()?;
+        let to_ang_fs = unit_conversion_factor("nm/ps", "angstrom/fs")?;
+        let mut atoms = Vec::with_capacity(n);
+        for _ in 0..n {
+            let line = self.lines.next().ok_or(ParseError::IncompleteFrame)?;
+            let col = |a: usize, b: usize| line.get(a..b).map(str::trim).unwrap_or("");
+            if col(20, line.len()).is_empty() {
+                return Err(invalid(format!("atom line without coordinates: {line:?}")));
+            }
+            let (position, width) = fixed_vec3(line, 20)?;
+            let vel_start = 20 + 3 * width;
+            let velocity = match line.get(vel_start..) {
+                Some(rest) if !rest.trim().is_empty() => {
+                    Some(fixed_vec3(line, vel_start)?.0.map(|v| v * to_ang_fs))
+                }
+                _ => None,
+            };
+            atoms.push(GroAtom {
+                resid: col(0, 5).parse()?,
+                resname: col(5, 10).to_string(),
+                name: col(10, 15).to_string(),
+                position: position.map(nm_to_angstrom),
+                velocity,
+            });
+        }
+        let box_line = self.lines.next().ok_or(ParseError::IncompleteFrame)?;
+        let b: Vec<f64> = box_line
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        let m = match b.as_slice() {
+            [x, y, z] => [[*x, 0.0, 0.0], [0.0, *y, 0.0], [0.0, 0.0, *z]],
+            [v1x, v2y, v3z, v1y, v1z, v2x, v2z, v3x, v3y] => {
+                [[*v1x, *v1y, *v1z], [*v2x, *v2y, *v2z], [*v3x, *v3y, *v3z]]
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "box line needs 3 or 9 numbers: {box_line:?}"
+                )));
+            }
+        };
+        let (lengths, angles) = Cell::from_matrix(m.map(|row| row.map(nm_to_angstrom)))
+            .map_or(([0.0; 3], [90.0; 3]), |c| c.to_lengths_angles());
+
+        let mut builder = ConFrameBuilder::new(lengths, angles);
+        builder.prebox_header(title.trim());
+        for (i, atom) in atoms.iter().enumerate() {
+            let symbol = element_from_names(&atom.name, &atom.resname);
+            let mass = atomic_mass(&symbol).unwrap_or(0.0);
+            let [x, y, z] = atom.position;
+            builder.add_atom(&symbol, x, y, z, [false; 3], i as u64, mass);
+            if let Some(v) = atom.velocity {
+                builder.with_velocity(v);
+            }
+        }
+        let mut frame = builder.build();
+        // The builder groups atoms by species; atom ids hold file order.
+        let order: Vec<&GroAtom> = frame
+            .atom_ids()
+            .iter()
+            .map(|&i| &atoms[i as usize])
+            .collect();
+        frame.set_property(
+            PROP_ATOM_NAME,
+            PropertyArray::Str(order.iter().map(|a| a.name.clone()).collect()),
+        )?;
+        frame.set_property(
+            PROP_RESNAME,
+            PropertyArray::Str(order.iter().map(|a| a.resname.clone()).collect()),
+        )?;
+        frame.set_property(
+            PROP_RESID,
+            PropertyArray::Int(order.iter().map(|a| a.resid).collect()),
+        )?;
+        Ok(frame)
+    }
+}
+
+impl Iterator for GroFrameIterator<'_> {
+    type Item = Result<ConFrame, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let title = self.lines.find(|l| !l.trim().is_empty())?;
+        Some(self.parse_frame(title))
+    }
+}
+
+/// Reads every frame of a `.gro` file (optionally compressed, as for
+/// [`crate::iterators::read_all_frames`]).
+pub fn read_gro(path: &Path) -> Result<Vec<ConFrame>, Box<dyn std::error::Error>> {
+    let contents = crate::compression::read_file_contents(path)?;
+    let text = contents.as_str()?;
+    let frames: Result<Vec<_>, _> = GroFrameIterator::new(text).collect();
+    Ok(frames?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WATER: &str = "\
+Water, t= 0.0
+    3
+    1SOL     OW    1   0.126   1.624   1.679  0.1227 -0.0580  0.0434
+    1SOL    HW1    2   0.190   1.661   1.747  0.8085  0.3191 -0.7791
+    1SOL    HW2    3   0.177   1.568   1.613 -0.9045 -2.6469  1.3180
+   1.86206   1.86206   1.86206
+";
+
+    #[test]
+    fn reads_names_units_and_round_trips() {
+        let frames: Vec<ConFrame> = GroFrameIterator::new(&format!("{WATER}{WATER}"))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(frames.len(), 2);
+        let f = &frames[0];
+        assert_eq!(f.header.prebox_header.user, "Water, t= 0.0");
+        assert_eq!(f.symbols(), vec!["O", "H", "H"]);
+        assert!((f.atom_data[0].x - 1.26).abs() < 1e-12);
+        assert!((f.atom_data[0].velocity.unwrap()[0] - 0.001227).abs() < 1e-12);
+        assert!((f.header.boxl[0] - 18.6206).abs() < 1e-9);
+        assert_eq!(
+            f.property(PROP_ATOM_NAME),
+            Some(&PropertyArray::Str(vec![
+                "OW".into(),
+                "HW1".into(),
+                "HW2".into()
+            ]))
+        );
+
+        let mut out = Vec::new();
+        write_frame(&mut out, f).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), WATER);
+    }
+
+    #[test]
+    fn triclinic_box_and_default_names() {
+        let mut b = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 60.0]);
+        b.add_atom("Cu", 1.0, 2.0, 3.0, [false; 3], 0, 63.546);
+        let frame = b.build();
+        let mut out = Vec::new();
+        write_frame(&mut out, &frame).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[2], "    1CU      Cu    1   0.100   0.200   0.300");
+        assert_eq!(lines[3].split_whitespace().count(), 9);
+
+        let back = GroFrameIterator::new(&text).next().unwrap().unwrap();
+        assert_eq!(back.symbols(), vec!["Cu"]);
+        for (a, b) in back.header.angles.iter().zip(frame.header.angles) {
+            assert!((a - b).abs() < 1e-3);
+        }
+        assert!(GroFrameIterator::new("t\n2\n").next().unwrap().is_err());
+    }
+}
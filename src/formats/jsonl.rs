@@ -0,0 +1,547 @@
+//! JSON lines: one [`ConFrame`] per line as a self-contained JSON object.
+//!
+//! The layout is columnar so each line loads straight into a dataframe row
+//! or a `jq` filter:
+//!
+//! ```json
+//! {"comment":"...","cell":[..],"angles":[..],"postbox":["",""],
+//!  "spec_version":2,"metadata":{..},"sections":["forces"],
+//!  "natms_per_type":[2,1],"masses_per_type":[63.5,1.0],
+//!  "symbols":["Cu","Cu","H"],"positions":[[..],..],"fixed":[[..],..],
+//!  "atom_ids":[0,1,2],"forces":[[..],..],
+//!  "properties":{"tag":{"type":"int","values":[..]}}}
+//! ```
+//!
+//! Optional columns (`component_labels`, `velocities`, `forces`, `energies`,
+//! `charges`, `spins`, `magmoms`, `extra`, `properties`) are written only
+//! when the frame has them. Atoms stay in `atom_data` order, so [`frame_from_json`] rebuilds an
+//! identical frame.
+
+use crate::error::ParseError;
+use crate::properties::PropertyArray;
+use crate::types::{
+    AtomDatum, ConFrame, FrameHeader, PreboxHeader, SymbolId, con_frame_from_atom_data,
+    intern_symbol, meta,
+};
+use serde_json::{Map, Value, json};
+use std::io::{self, Write};
+use std::path::Path;
+
+fn vec3_column(values: impl Iterator<Item = [f64; 3]>) -> Value {
+    Value::Array(values.map(|v| json!(v)).collect())
+}
+
+fn property_to_json(values: &PropertyArray) -> Value {
+    let (kind, values) = match values {
+        PropertyArray::Float(v) => ("float", json!(v)),
+        PropertyArray::Vector(v) => ("vector", json!(v)),
+        PropertyArray::Int(v) => ("int", json!(v)),
+        PropertyArray::Bool(v) => ("bool", json!(v)),
+        PropertyArray::Str(v) => ("str", json!(v)),
+    };
+    json!({"type": kind, "values": values})
+}
+
+/// The JSON object for `frame` (see the [module docs](self) for the keys).
+pub fn frame_to_json(frame: &ConFrame) -> Value {
+    let h = &frame.header;
+    let atoms = &frame.atom_data;
+    let mut obj = Map::new();
+    obj.insert("comment".into(), json!(h.prebox_header.user));
+    obj.insert("cell".into(), json!(h.boxl));
+    obj.insert("angles".into(), json!(h.angles));
+    obj.insert("postbox".into(), json!(h.postbox_header));
+    obj.insert("spec_version".into(), json!(h.spec_version));
+    obj.insert("metadata".into(), json!(h.metadata));
+    obj.insert("sections".into(), json!(h.sections));
+    obj.insert("natms_per_type".into(), json!(h.natms_per_type));
+    obj.insert("masses_per_type".into(), json!(h.masses_per_type));
+    if !h.component_labels.is_empty() {
+        obj.insert("component_labels".into(), json!(h.component_labels));
+    }
+    obj.insert(
+        "symbols".into(),
+        Value::Array(atoms.iter().map(|a| json!(frame.symbol_of(a))).collect()),
+    );
+    obj.insert(
+        "positions".into(),
+        vec3_column(atoms.iter().map(|a| [a.x, a.y, a.z])),
+    );
+    obj.insert(
+        "fixed".into(),
+        Value::Array(atoms.iter().map(|a| json!(a.fixed)).collect()),
+    );
+    obj.insert(
+        "atom_ids".into(),
+        Value::Array(atoms.iter().map(|a| json!(a.atom_id)).collect()),
+    );
+
+    // A section is written only when every atom carries it.
+    let vector = |get: fn(&AtomDatum) -> Option<[f64; 3]>| {
+        let column: Option<Vec<_>> = atoms.iter().map(get).collect();
+        column
+            .filter(|c| !c.is_empty())
+            .map(|c| vec3_column(c.into_iter()))
+    };
+    let scalar = |get: fn(&AtomDatum) -> Option<f64>| {
+        let column: Option<Vec<_>> = atoms.iter().map(get).collect();
+        column.filter(|c| !c.is_empty()).map(|c| json!(c))
+    };
+    let sections = [
+        ("velocities", vector(|a| a.velocity)),
+        ("forces", vector(|a| a.force)),
+        ("energies", scalar(|a| a.energy)),
+        ("charges", scalar(|a| a.charge)),
+        ("spins", scalar(|a| a.spin)),
+        ("magmoms", vector(|a| a.magmom)),
+    ];
+    for (key, column) in sections {
+        if let Some(column) = column {
+            obj.insert(key.into(), column);
+        }
+    }
+    if atoms.iter().any(|a| !a.extra.is_empty()) {
+        obj.insert(
+            "extra".into(),
+            Value::Array(atoms.iter().map(|a| json!(a.extra)).collect()),
+        );
+    }
+    if !frame.properties.is_empty() {
+        obj.insert(
+            "properties".into(),
+            Value::Object(
+                frame
+                    .properties
+                    .iter()
+                    .map(|(k, v)| (k.clone(), property_to_json(v)))
+                    .collect(),
+            ),
+        );
+    }
+    Value::Object(obj)
+}
+
+fn invalid(msg: String) -> ParseError {
+    ParseError::ValidationError(format!("jsonl: {msg}"))
+}
+
+fn field<'a>(obj: &'a Map<String, Value>, key: &str) -> Result<&'a Value, ParseError> {
+    obj.get(key)
+        .ok_or_else(|| invalid(format!("missing key {key:?}")))
+}
+
+fn as_f64(v: &Value, key: &str) -> Result<f64, ParseError> {
+    v.as_f64()
+        .ok_or_else(|| invalid(format!("{key}: expected a number, got {v}")))
+}
+
+fn as_array<'a>(v: &'a Value, key: &str) -> Result<&'a Vec<Value>, ParseError> {
+    v.as_array()
+        .ok_or_else(|| invalid(format!("{key}: expected an array")))
+}
+
+fn vec3(v: &Value, key: &str) -> Result<[f64; 3], ParseError> {
+    let a = as_array(v, key)?;
+    if a.len() != 3 {
+        return Err(ParseError::InvalidVectorLength {
+            expected: 3,
+            found: a.len(),
+        });
+    }
+    Ok([
+        as_f64(&a[0], key)?,
+        as_f64(&a[1], key)?,
+        as_f64(&a[2], key)?,
+    ])
+}
+
+/// Per-atom column `key`, checked to hold `n` entries; `None` when absent.
+fn column<'a>(
+    obj: &'a Map<String, Value>,
+    key: &str,
+    n: usize,
+) -> Result<Option<&'a Vec<Value>>, ParseError> {
+    let Some(v) = obj.get(key) else {
+        return Ok(None);
+    };
+    let a = as_array(v, key)?;
+    if a.len() != n {
+        return Err(invalid(format!(
+            "{key} has {} entries for {n} atoms",
+            a.len()
+        )));
+    }
+    Ok(Some(a))
+}
+
+fn property_from_json(name: &str, v: &Value) -> Result<PropertyArray, ParseError> {
+    let kind = v.get("type").and_then(Value::as_str);
+    let values = v
+        .get("values")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid(format!("property {name:?} needs a values array")))?;
+    let bad = |what: &str| invalid(format!("property {name:?}: expected {what} values"));
+    Ok(match kind {
+        Some("float") => PropertyArray::Float(
+            values
+                .iter()
+                .map(|x| x.as_f64().ok_or_else(|| bad("float")))
+                .collect::<Result<_, _>>()?,
+        ),
+        Some("vector") => PropertyArray::Vector(
+            values
+                .iter()
+                .map(|x| vec3(x, name))
+                .collect::<Result<_, _>>()?,
+        ),
+        Some("int") => PropertyArray::Int(
+            values
+                .iter()
+                .map(|x| x.as_i64().ok_or_else(|| bad("int")))
+                .collect::<Result<_, _>>()?,
+        ),
+        Some("bool") => PropertyArray::Bool(
+            values
+                .iter()
+                .map(|x| x.as_bool().ok_or_else(|| bad("bool")))
+                .collect::<Result<_, _>>()?,
+        ),
+        Some("str") => PropertyArray::Str(
+            values
+                .iter()
+                .map(|x| x.as_str().map(str::to_string).ok_or_else(|| bad("str")))
+                .collect::<Result<_, _>>()?,
+        ),
+        other => {
+            return Err(invalid(format!(
+                "property {name:?} has unknown type {other:?}"
+            )));
+        }
+    })
+}
+
+/// Rebuilds a frame from the object written by [`frame_to_json`].
+///
+/// Errors are [`ParseError::ValidationError`] for missing keys, wrong JSON
+/// types and per-atom columns whose length differs from `symbols`.
+pub fn frame_from_json(value: &Value) -> Result<ConFrame, ParseError> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| invalid("a frame must be a JSON object".into()))?;
+    let str_of = |key: &str| -> Result<String, ParseError> {
+        match obj.get(key) {
+            None => Ok(String::new()),
+            Some(v) => v
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| invalid(format!("{key}: expected a string"))),
+        }
+    };
+
+    let symbols = as_array(field(obj, "symbols")?, "symbols")?;
+    let n = symbols.len();
+    let positions =
+        column(obj, "positions", n)?.ok_or_else(|| invalid("missing key \"positions\"".into()))?;
+    let fixed = column(obj, "fixed", n)?;
+    let atom_ids = column(obj, "atom_ids", n)?;
+    let velocities = column(obj, "velocities", n)?;
+    let forces = column(obj, "forces", n)?;
+    let magmoms = column(obj, "magmoms", n)?;
+    let energies = column(obj, "energies", n)?;
+    let charges = column(obj, "charges", n)?;
+    let spins = column(obj, "spins", n)?;
+    let extra = column(obj, "extra", n)?;
+
+    let mut atom_data = Vec::with_capacity(n);
+    let mut symbol_table = Vec::new();
+    let mut symbol: Option<SymbolId> = None;
+    for i in 0..n {
+        let s = symbols[i]
+            .as_str()
+            .ok_or_else(|| invalid("symbols: expected strings".into()))?;
+        // Consecutive atoms of one species skip the table lookup.
+        let sym = match symbol {
+            Some(prev) if symbol_table[prev.index()] == s => prev,
+            _ => intern_symbol(&mut symbol_table, s)
+                .ok_or_else(|| invalid("symbols: more than 65536 distinct symbols".into()))?,
+        };
+        symbol = Some(sym);
+        let [x, y, z] = vec3(&positions[i], "positions")?;
+        let fixed = match fixed {
+            None => [false; 3],
+            Some(f) => {
+                let a = as_array(&f[i], "fixed")?;
+                let flags: Vec<bool> = a.iter().filter_map(Value::as_bool).collect();
+                <[bool; 3]>::try_from(flags)
+                    .map_err(|_| invalid("fixed: expected three booleans per atom".into()))?
+            }
+        };
+        let atom_id = match atom_ids {
+            None => i as u64,
+            Some(ids) => ids[i]
+                .as_u64()
+                .ok_or_else(|| invalid("atom_ids: expected unsigned integers".into()))?,
+        };
+        let vector = |col: Option<&Vec<Value>>, key| col.map(|c| vec3(&c[i], key)).transpose();
+        let scalar = |col: Option<&Vec<Value>>, key| col.map(|c| as_f64(&c[i], key)).transpose();
+        atom_data.push(AtomDatum {
+            symbol: sym,
+            x,
+            y,
+            z,
+            fixed,
+            atom_id,
+            velocity: vector(velocities, "velocities")?,
+            force: vector(forces, "forces")?,
+            energy: scalar(energies, "energies")?,
+            charge: scalar(charges, "charges")?,
+            spin: scalar(spins, "spins")?,
+            magmom: vector(magmoms, "magmoms")?,
+            extra: match extra {
+                None => Vec::new(),
+                Some(e) => as_array(&e[i], "extra")?
+                    .iter()
+                    .map(|x| as_f64(x, "extra"))
+                    .collect::<Result<_, _>>()?,
+            },
+        });
+    }
+
+    let metadata = match obj.get("metadata") {
+        None | Some(Value::Null) => Default::default(),
+        Some(Value::Object(m)) => m.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        Some(_) => return Err(invalid("metadata: expected an object".into())),
+    };
+    let sections = match obj.get("sections") {
+        None => Vec::new(),
+        Some(v) => as_array(v, "sections")?
+            .iter()
+            .map(|s| {
+                s.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| invalid("sections: expected strings".into()))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+    let natms_per_type: Vec<usize> = as_array(field(obj, "natms_per_type")?, "natms_per_type")?
+        .iter()
+        .map(|v| {
+            v.as_u64()
+                .map(|c| c as usize)
+                .ok_or_else(|| invalid("natms_per_type: expected unsigned integers".into()))
+        })
+        .collect::<Result<_, _>>()?;
+    let masses_per_type: Vec<f64> = as_array(field(obj, "masses_per_type")?, "masses_per_type")?
+        .iter()
+        .map(|v| as_f64(v, "masses_per_type"))
+        .collect::<Result<_, _>>()?;
+    if natms_per_type.len() != masses_per_type.len() || natms_per_type.iter().sum::<usize>() != n {
+        return Err(invalid(format!(
+            "natms_per_type {natms_per_type:?} and masses_per_type do not describe {n} atoms"
+        )));
+    }
+    let component_labels: Vec<String> = match obj.get("component_labels") {
+        None => Vec::new(),
+        Some(v) => as_array(v, "component_labels")?
+            .iter()
+            .map(|s| {
+                s.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| invalid("component_labels: expected strings".into()))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+    if !component_labels.is_empty() && component_labels.len() != natms_per_type.len() {
+        return Err(invalid(format!(
+            "component_labels has {} entries for {} components",
+            component_labels.len(),
+            natms_per_type.len()
+        )));
+    }
+    let postbox = match obj.get("postbox") {
+        None => [String::new(), String::new()],
+        Some(v) => {
+            let a = as_array(v, "postbox")?;
+            let line = |i: usize| a.get(i).and_then(Value::as_str).unwrap_or("").to_string();
+            [line(0), line(1)]
+        }
+    };
+    let spec_version = match obj.get("spec_version") {
+        None => crate::CON_SPEC_VERSION,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| invalid("spec_version: expected an unsigned integer".into()))?,
+    };
+
+    let strict_validation = matches!(
+        obj.get("metadata").and_then(|m| m.get(meta::VALIDATE)),
+        Some(Value::Bool(true))
+    );
+    let header = FrameHeader {
+        prebox_header: PreboxHeader::new(str_of("comment")?),
+        boxl: vec3(field(obj, "cell")?, "cell")?,
+        angles: vec3(field(obj, "angles")?, "angles")?,
+        postbox_header: postbox,
+        natm_types: natms_per_type.len(),
+        natms_per_type,
+        masses_per_type,
+        component_labels,
+        spec_version,
+        metadata,
+        sections_declared: !sections.is_empty(),
+        sections,
+        strict_validation,
+    };
+    let mut frame = con_frame_from_atom_data(header, symbol_table, atom_data);
+
+    if let Some(props) = obj.get("properties") {
+        let props = props
+            .as_object()
+            .ok_or_else(|| invalid("properties: expected an object".into()))?;
+        for (name, v) in props {
+            frame.set_property(name, property_from_json(name, v)?)?;
+        }
+    }
+    Ok(frame)
+}
+
+/// Writes `frame` as one JSON line (terminated by `\n`).
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, &frame_to_json(frame))?;
+    writer.write_all(b"\n")
+}
+
+/// Writes every frame of `frames` as its own JSON line.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::jsonl;
+/// use readcon_core::types::ConFrameBuilder;
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("O", 0.0, 0.0, 0.0, [false; 3], 0, 15.999);
+/// let frame = b.build();
+/// let mut out = Vec::new();
+/// jsonl::write_jsonl(&mut out, [&frame, &frame]).unwrap();
+/// let text = String::from_utf8(out).unwrap();
+/// assert_eq!(text.lines().count(), 2);
+/// let back: Vec<_> = jsonl::JsonlFrameIterator::new(&text)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(back, vec![frame.clone(), frame]);
+/// ```
+pub fn write_jsonl<'a, W, I>(writer: &mut W, frames: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    for frame in frames {
+        write_frame(writer, frame)?;
+    }
+    Ok(())
+}
+
+/// Writes `frames` to a new `.jsonl` file at `path`.
+pub fn write_jsonl_to_path<'a, I>(path: &Path, frames: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    let mut w = io::BufWriter::new(std::fs::File::create(path)?);
+    write_jsonl(&mut w, frames)?;
+    w.flush()
+}
+
+/// Lazily parses JSON-lines text, one frame per non-blank line.
+pub struct JsonlFrameIterator<'a> {
+    lines: std::str::Lines<'a>,
+}
+
+impl<'a> JsonlFrameIterator<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            lines: text.lines(),
+        }
+    }
+}
+
+impl Iterator for JsonlFrameIterator<'_> {
+    type Item = Result<ConFrame, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.find(|l| !l.trim().is_empty())?;
+        Some(
+            serde_json::from_str::<Value>(line)
+                .map_err(ParseError::from)
+                .and_then(|v| frame_from_json(&v)),
+        )
+    }
+}
+
+/// Reads every frame of a `.jsonl` file (optionally compressed, as for
+/// [`crate::iterators::read_all_frames`]).
+pub fn read_jsonl(path: &Path) -> Result<Vec<ConFrame>, Box<dyn std::error::Error>> {
+    let contents = crate::compression::read_file_contents(path)?;
+    let text = contents.as_str()?;
+    let frames: Result<Vec<_>, _> = JsonlFrameIterator::new(text).collect();
+    Ok(frames?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+
+    #[test]
+    fn fixtures_round_trip() {
+        for name in [
+            "tiny_multi_cuh2.con",
+            "tiny_cuh2_vel_forces.con",
+            "tiny_cuh2_charges_spins_magmoms.con",
+            "sulfolene.con",
+        ] {
+            let text = fixture(name);
+            let frames: Vec<ConFrame> = ConFrameIterator::new(&text)
+                .collect::<Result<_, _>>()
+                .unwrap();
+            let mut out = Vec::new();
+            write_jsonl(&mut out, &frames).unwrap();
+            let out = String::from_utf8(out).unwrap();
+            assert_eq!(out.lines().count(), frames.len(), "{name}");
+            let back: Vec<ConFrame> = JsonlFrameIterator::new(&out)
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(back, frames, "{name}");
+        }
+    }
+
+    #[test]
+    fn properties_round_trip_and_bad_columns_error() {
+        let mut b = crate::types::ConFrameBuilder::new([5.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [true, false, true], 4, 63.546);
+        b.add_atom("H", 1.0, 1.0, 1.0, [false; 3], 2, 1.008);
+        let mut frame = b.build();
+        frame
+            .set_property("mol", PropertyArray::Int(vec![1, 1]))
+            .unwrap();
+        frame
+            .set_property("dip", PropertyArray::Vector(vec![[0.1, 0.0, 0.0]; 2]))
+            .unwrap();
+        let value = frame_to_json(&frame);
+        assert_eq!(value["properties"]["mol"]["type"], "int");
+        assert_eq!(frame_from_json(&value).unwrap(), frame);
+
+        let mut short = value.clone();
+        short["positions"] = json!([[0.0, 0.0, 0.0]]);
+        assert!(frame_from_json(&short).is_err());
+        let mut untyped = value;
+        untyped["properties"]["mol"]["type"] = json!("complex");
+        assert!(frame_from_json(&untyped).is_err());
+        assert!(
+            JsonlFrameIterator::new("{not json}\n")
+                .next()
+                .unwrap()
+                .is_err()
+        );
+    }
+}
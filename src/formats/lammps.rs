@@ -0,0 +1,100 @@
+//! LAMMPS data file export.
+//!
+//! One frame becomes one data file for `read_data`: a type per component
+//! with its mass, the cell as LAMMPS' restricted triclinic box (`a` along
+//! +x, `b` in the xy plane, as [`crate::cell`] orients it) and an `Atoms`
+//! section in `atom_style charge` when every atom carries a charge,
+//! `atomic` otherwise. Atom ids are 1-based positions in `atom_data`.
+//! Lengths are written in Å, masses in amu and charges in e, which suit the
+//! `metal` and `real` unit styles.
+
+use crate::cell::Cell;
+use crate::types::ConFrame;
+use std::io::{self, Write};
+
+/// Writes `frame` as a LAMMPS data file.
+///
+/// Errors are [`io::ErrorKind::InvalidData`] when the cell is degenerate or
+/// its `lattice_vectors` are not in the restricted triclinic orientation,
+/// and [`io::ErrorKind::InvalidInput`] when the length unit cannot be
+/// converted to Å.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::lammps;
+/// use readcon_core::types::ConFrameBuilder;
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("Na", 0.0, 0.0, 0.0, [false; 3], 0, 22.990);
+/// b.add_atom("Cl", 2.8, 0.0, 0.0, [false; 3], 1, 35.45);
+/// let mut frame = b.build();
+/// frame.atom_data[0].charge = Some(1.0);
+/// frame.atom_data[1].charge = Some(-1.0);
+/// let mut out = Vec::new();
+/// lammps::write_frame(&mut out, &frame).unwrap();
+/// let text = String::from_utf8(out).unwrap();
+/// assert!(text.contains("Atoms # charge\n\n1 1 1.000000 0.000000 0.000000 0.000000\n"));
+/// ```
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    let to_ang = super::length_factor_to_angstrom(frame)?;
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    let [a, b, c] = Cell::from_header(&frame.header)
+        .ok_or_else(|| invalid("degenerate cell"))?
+        .matrix()
+        .map(|row| row.map(|v| v * to_ang));
+    if [a[1], a[2], b[2]].iter().any(|v| v.abs() > 1e-8 * a[0]) {
+        return Err(invalid(
+            "lattice_vectors are not in LAMMPS orientation (a along x, b in the xy plane)",
+        ));
+    }
+    let charges = !frame.atom_data.is_empty() && frame.atom_data.iter().all(|a| a.has_charge());
+    let name = frame.header.prebox_header.user.trim();
+
+    writeln!(
+        writer,
+        "{}",
+        if name.is_empty() { "LAMMPS data" } else { name }
+    )?;
+    writeln!(writer)?;
+    writeln!(writer, "{} atoms", frame.atom_data.len())?;
+    writeln!(writer, "{} atom types", frame.header.natms_per_type.len())?;
+    writeln!(writer)?;
+    writeln!(writer, "0.0 {:.6} xlo xhi", a[0])?;
+    writeln!(writer, "0.0 {:.6} ylo yhi", b[1])?;
+    writeln!(writer, "0.0 {:.6} zlo zhi", c[2])?;
+    if [b[0], c[0], c[1]].iter().any(|v| v.abs() > 1e-8 * a[0]) {
+        writeln!(writer, "{:.6} {:.6} {:.6} xy xz yz", b[0], c[0], c[1])?;
+    }
+    writeln!(writer)?;
+    writeln!(writer, "Masses")?;
+    writeln!(writer)?;
+    let mut offset = 0;
+    for (t, &n) in frame.header.natms_per_type.iter().enumerate() {
+        let mass = frame.header.masses_per_type.get(t).copied().unwrap_or(0.0);
+        match frame.atom_data.get(offset).filter(|_| n > 0) {
+            Some(atom) => writeln!(writer, "{} {mass} # {}", t + 1, frame.symbol_of(atom))?,
+            None => writeln!(writer, "{} {mass}", t + 1)?,
+        }
+        offset += n;
+    }
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Atoms # {}",
+        if charges { "charge" } else { "atomic" }
+    )?;
+    writeln!(writer)?;
+    let types = frame
+        .header
+        .natms_per_type
+        .iter()
+        .enumerate()
+        .flat_map(|(t, &n)| std::iter::repeat_n(t + 1, n));
+    for (i, (atom, t)) in frame.atom_data.iter().zip(types).enumerate() {
+        let [x, y, z] = [atom.x, atom.y, atom.z].map(|v| v * to_ang);
+        match atom.charge.filter(|_| charges) {
+            Some(q) => writeln!(writer, "{} {t} {q:.6} {x:.6} {y:.6} {z:.6}", i + 1)?,
+            None => writeln!(writer, "{} {t} {x:.6} {y:.6} {z:.6}", i + 1)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! Interchange formats other than `.con`.
+//!
+//! Each submodule converts [`ConFrame`](crate::types::ConFrame)s to and from
+//! one external representation without going through chemfiles.
+
+pub mod ase;
+pub mod bcon;
+pub mod dcd;
+pub mod gro;
+pub mod jsonl;
+pub mod lammps;
+pub mod mol2;
+pub mod sdf;
+pub mod xyz;
+
+use crate::types::ConFrame;
+use std::io;
+
+/// Factor taking `frame`'s lengths (Å when `units.length` is unset) to Å,
+/// for the formats that store Å.
+///
+/// Errors are [`io::ErrorKind::InvalidInput`] when the unit cannot be
+/// converted.
+pub(crate) fn length_factor_to_angstrom(frame: &ConFrame) -> io::Result<f64> {
+    frame
+        .conversion_factor_or_assumed("length", "angstrom")
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))
+}
@@ -0,0 +1,125 @@
+//! Tripos mol2 export with connectivity.
+//!
+//! Cheminformatics tools (RDKit, Open Babel, Avogadro) need bonds, which a
+//! CON file does not carry. The writer uses the frame's `bonds` metadata and
+//! falls back to [`crate::bonds::guess_bonds`]; guessed bonds have unknown
+//! order and are written as single bonds. Bonds that only exist through a
+//! periodic image are kept, as they are in the guess.
+//!
+//! Atoms are typed by element (`Cu`, `O`, ...) rather than by Sybyl
+//! hybridisation, and the cell goes into a `@<TRIPOS>CRYSIN` record.
+//! Coordinates and cell lengths are written in Å whatever the frame's
+//! `units.length`.
+
+use crate::bonds::bonds_or_guess;
+use crate::types::{Bond, ConFrame};
+use std::io::{self, Write};
+
+/// mol2 bond type for a stored bond order (`4` is aromatic).
+fn bond_type(bond: &Bond) -> &'static str {
+    match bond.order {
+        Some(2) => "2",
+        Some(3) => "3",
+        Some(4) => "ar",
+        _ => "1",
+    }
+}
+
+/// Writes `frame` as one mol2 molecule named after its comment line.
+///
+/// Errors are [`io::ErrorKind::InvalidData`] when stored bonds reference
+/// atoms past the end or when guessing bonds fails, and
+/// [`io::ErrorKind::InvalidInput`] when the length unit cannot be converted
+/// to Å.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::mol2;
+/// use readcon_core::types::ConFrameBuilder;
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("O", 5.0, 5.0, 5.0, [false; 3], 0, 15.999);
+/// b.add_atom("H", 5.76, 5.59, 5.0, [false; 3], 1, 1.008);
+/// b.add_atom("H", 4.24, 5.59, 5.0, [false; 3], 2, 1.008);
+/// let mut out = Vec::new();
+/// mol2::write_frame(&mut out, &b.build()).unwrap();
+/// let text = String::from_utf8(out).unwrap();
+/// assert!(text.contains("@<TRIPOS>BOND\n     1     1     2 1\n"));
+/// ```
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    let bonds = bonds_or_guess(frame).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let n = frame.atom_data.len();
+    if let Some(b) = bonds
+        .iter()
+        .find(|b| b.i as usize >= n || b.j as usize >= n)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("bond ({}, {}) out of range for {n} atoms", b.i, b.j),
+        ));
+    }
+    let to_ang = super::length_factor_to_angstrom(frame)?;
+    let charges = !frame.atom_data.is_empty() && frame.atom_data.iter().all(|a| a.has_charge());
+    let name = frame.header.prebox_header.user.trim();
+
+    writeln!(writer, "@<TRIPOS>MOLECULE")?;
+    writeln!(writer, "{}", if name.is_empty() { "frame" } else { name })?;
+    writeln!(writer, "{n} {} 0 0 0", bonds.len())?;
+    writeln!(writer, "SMALL")?;
+    writeln!(
+        writer,
+        "{}",
+        if charges {
+            "USER_CHARGES"
+        } else {
+            "NO_CHARGES"
+        }
+    )?;
+    writeln!(writer)?;
+    writeln!(writer, "@<TRIPOS>ATOM")?;
+    for (i, atom) in frame.atom_data.iter().enumerate() {
+        let symbol = frame.symbol_of(atom);
+        writeln!(
+            writer,
+            "{:7} {:<8} {:10.4} {:10.4} {:10.4} {:<5} {:5} {:<8} {:8.4}",
+            i + 1,
+            format!("{symbol}{}", i + 1),
+            atom.x * to_ang,
+            atom.y * to_ang,
+            atom.z * to_ang,
+            symbol,
+            1,
+            "UNL1",
+            atom.charge.unwrap_or(0.0)
+        )?;
+    }
+    writeln!(writer, "@<TRIPOS>BOND")?;
+    for (k, b) in bonds.iter().enumerate() {
+        writeln!(
+            writer,
+            "{:6}{:6}{:6} {}",
+            k + 1,
+            b.i + 1,
+            b.j + 1,
+            bond_type(b)
+        )?;
+    }
+    let [a, b, c] = frame.header.boxl.map(|l| l * to_ang);
+    let [alpha, beta, gamma] = frame.header.angles;
+    writeln!(writer, "@<TRIPOS>CRYSIN")?;
+    writeln!(
+        writer,
+        "{a:10.4} {b:10.4} {c:10.4} {alpha:8.3} {beta:8.3} {gamma:8.3} 1 1"
+    )
+}
+
+/// Writes every frame of `frames` as consecutive mol2 molecules.
+pub fn write_mol2<'a, W, I>(writer: &mut W, frames: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    for frame in frames {
+        write_frame(writer, frame)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! MDL SDF (V2000 molfile) export with connectivity.
+//!
+//! Bonds come from the frame's `bonds` metadata or, when it has none, from
+//! [`crate::bonds::guess_bonds`] (written as single bonds, see
+//! [`crate::formats::mol2`]). Numeric and string metadata entries are
+//! appended as `> <key>` data items, so the frame energy travels with the
+//! structure. V2000 counts are three digits wide: frames with more than 999
+//! atoms or bonds are rejected.
+
+use crate::bonds::bonds_or_guess;
+use crate::types::ConFrame;
+use serde_json::Value;
+use std::io::{self, Write};
+
+const V2000_MAX: usize = 999;
+
+/// Writes `frame` as one SDF record terminated by `$$$$`.
+///
+/// Coordinates are written in Å whatever the frame's `units.length`.
+///
+/// Errors are [`io::ErrorKind::InvalidInput`] past the V2000 size limit or
+/// for a length unit that cannot be converted, and
+/// [`io::ErrorKind::InvalidData`] when bonds cannot be determined or
+/// reference atoms past the end.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::sdf;
+/// use readcon_core::types::ConFrameBuilder;
+/// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+/// b.add_atom("O", 5.0, 5.0, 5.0, [false; 3], 0, 15.999);
+/// b.add_atom("H", 5.76, 5.59, 5.0, [false; 3], 1, 1.008);
+/// b.set_energy(-14.2);
+/// let mut out = Vec::new();
+/// sdf::write_frame(&mut out, &b.build()).unwrap();
+/// let text = String::from_utf8(out).unwrap();
+/// assert!(text.contains("  2  1  0  0  0  0  0  0  0  0999 V2000"));
+/// assert!(text.contains("> <energy>\n-14.2\n"));
+/// ```
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    let bonds = bonds_or_guess(frame).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let n = frame.atom_data.len();
+    if n > V2000_MAX || bonds.len() > V2000_MAX {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "SDF V2000 holds at most {V2000_MAX} atoms and bonds, frame has {n} atoms and {} bonds",
+                bonds.len()
+            ),
+        ));
+    }
+    if let Some(b) = bonds
+        .iter()
+        .find(|b| b.i as usize >= n || b.j as usize >= n)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("bond ({}, {}) out of range for {n} atoms", b.i, b.j),
+        ));
+    }
+    let to_ang = super::length_factor_to_angstrom(frame)?;
+    let name = frame.header.prebox_header.user.trim();
+    writeln!(writer, "{}", if name.is_empty() { "frame" } else { name })?;
+    writeln!(writer, "  readcon           3D")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{n:3}{:3}  0  0  0  0  0  0  0  0999 V2000",
+        bonds.len()
+    )?;
+    for atom in &frame.atom_data {
+        writeln!(
+            writer,
+            "{:10.4}{:10.4}{:10.4} {:<3} 0  0  0  0  0  0  0  0  0  0  0  0",
+            atom.x * to_ang,
+            atom.y * to_ang,
+            atom.z * to_ang,
+            frame.symbol_of(atom)
+        )?;
+    }
+    for b in &bonds {
+        let order = b.order.filter(|o| (1..=4).contains(o)).unwrap_or(1);
+        writeln!(writer, "{:3}{:3}{:3}  0  0  0  0", b.i + 1, b.j + 1, order)?;
+    }
+    writeln!(writer, "M  END")?;
+    for (key, value) in &frame.header.metadata {
+        let text = match value {
+            Value::Number(x) => x.to_string(),
+            Value::String(s) => s.clone(),
+            Value::Bool(b) => b.to_string(),
+            _ => continue,
+        };
+        writeln!(writer, "> <{key}>\n{text}\n")?;
+    }
+    writeln!(writer, "$$$$")
+}
+
+/// Writes every frame of `frames` as consecutive SDF records.
+pub fn write_sdf<'a, W, I>(writer: &mut W, frames: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    for frame in frames {
+        write_frame(writer, frame)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Bond, ConFrameBuilder};
+
+    #[test]
+    fn stored_bonds_win_over_guessing() {
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        b.add_atom("C", 1.0, 1.0, 1.0, [false; 3], 0, 12.011);
+        b.add_atom("C", 2.3, 1.0, 1.0, [false; 3], 1, 12.011);
+        b.add_atom("C", 6.0, 6.0, 6.0, [false; 3], 2, 12.011);
+        let mut frame = b.build();
+
+        let mut out = Vec::new();
+        write_frame(&mut out, &frame).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("\n  3  1  0"));
+        assert!(text.contains("\n  1  2  1  0  0  0  0\n"));
+        assert!(text.ends_with("$$$$\n"));
+
+        frame
+            .header
+            .set_bonds(&[Bond::new(0, 1).with_order(2), Bond::new(1, 2)]);
+        let mut out = Vec::new();
+        write_frame(&mut out, &frame).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("\n  1  2  2  0  0  0  0\n  2  3  1  0  0  0  0\n"));
+
+        let mut mol2 = Vec::new();
+        crate::formats::mol2::write_frame(&mut mol2, &frame).unwrap();
+        let mol2 = String::from_utf8(mol2).unwrap();
+        assert!(mol2.contains("\n3 2 0 0 0\n"));
+        assert!(mol2.contains("     1     1     2 2\n     2     2     3 1\n"));
+
+        frame.header.set_bonds(&[Bond::new(0, 7)]);
+        assert!(write_frame(&mut Vec::new(), &frame).is_err());
+        assert!(crate::formats::mol2::write_frame(&mut Vec::new(), &frame).is_err());
+    }
+
+    #[test]
+    fn coordinates_are_converted_to_angstrom() {
+        let mut b = ConFrameBuilder::new([2.0; 3], [90.0; 3]);
+        b.add_atom("O", 0.5, 0.5, 0.5, [false; 3], 0, 15.999);
+        b.add_atom("H", 0.576, 0.559, 0.5, [false; 3], 1, 1.008);
+        let mut frame = b.build();
+        frame
+            .header
+            .set_units(serde_json::json!({"length": "nm", "energy": "eV"}));
+
+        let mut out = Vec::new();
+        write_frame(&mut out, &frame).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("\n    5.0000    5.0000    5.0000 O "), "{text}");
+
+        let mut mol2 = Vec::new();
+        crate::formats::mol2::write_frame(&mut mol2, &frame).unwrap();
+        let mol2 = String::from_utf8(mol2).unwrap();
+        assert!(mol2.contains("    5.7600     5.5900     5.0000 H"), "{mol2}");
+        assert!(mol2.contains("   20.0000    20.0000    20.0000"), "{mol2}");
+
+        let mut pdb = Vec::new();
+        crate::formats::dcd::write_pdb_topology(&mut pdb, &frame).unwrap();
+        assert!(String::from_utf8(pdb).unwrap().starts_with("CRYST1   20.000"));
+
+        frame
+            .header
+            .set_units(serde_json::json!({"length": "eV", "energy": "eV"}));
+        let err = write_frame(&mut Vec::new(), &frame).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,224 @@
+//! XYZ and extended XYZ coordinate files.
+//!
+//! An XYZ frame is the atom count, a comment line and one `symbol x y z`
+//! line per atom, in Å. The writer puts the cell into the comment the way
+//! extended XYZ does (`Lattice="ax ay az bx by bz cx cy cz"`,
+//! `Properties=species:S:1:pos:R:3`, `pbc="T T T"`), so ASE, OVITO and
+//! chemfiles recover the box while plain XYZ readers still work. The reader
+//! accepts both: a `Lattice` key sets the box, any other comment becomes the
+//! frame's first header line, and columns past the position are ignored.
+//!
+//! Positions are converted between Å and the frame's `units.length`. Fixed
+//! flags, sections and properties have no XYZ column and are not written.
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::helpers::ElementRegistry;
+use crate::types::{ConFrame, ConFrameBuilder};
+use std::io::{self, Write};
+use std::path::Path;
+
+fn invalid(msg: String) -> ParseError {
+    ParseError::ValidationError(format!("xyz: {msg}"))
+}
+
+/// Writes `frame` as one extended XYZ frame.
+///
+/// Errors are [`io::ErrorKind::InvalidInput`] when the frame's length unit
+/// cannot be converted to Å.
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ConFrame) -> io::Result<()> {
+    let to_ang = super::length_factor_to_angstrom(frame)?;
+    writeln!(writer, "{}", frame.atom_data.len())?;
+    if let Some(cell) = Cell::from_header(&frame.header) {
+        let lattice: Vec<String> = cell
+            .matrix()
+            .iter()
+            .flatten()
+            // cos(90°) round-off would otherwise print as 6e-15.
+            .map(|v| if v.abs() < 1e-10 { 0.0 } else { v * to_ang }.to_string())
+            .collect();
+        write!(writer, "Lattice=\"{}\" ", lattice.join(" "))?;
+    }
+    writeln!(writer, "Properties=species:S:1:pos:R:3 pbc=\"T T T\"")?;
+    for atom in &frame.atom_data {
+        writeln!(
+            writer,
+            "{} {} {} {}",
+            frame.symbol_of(atom),
+            atom.x * to_ang,
+            atom.y * to_ang,
+            atom.z * to_ang
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes every frame of `frames`, one after the other.
+pub fn write_xyz<'a, W, I>(writer: &mut W, frames: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    for frame in frames {
+        write_frame(writer, frame)?;
+    }
+    Ok(())
+}
+
+/// Writes `frames` to a new `.xyz` file at `path`.
+pub fn write_xyz_to_path<'a, I>(path: &Path, frames: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a ConFrame>,
+{
+    let mut w = io::BufWriter::new(std::fs::File::create(path)?);
+    write_xyz(&mut w, frames)?;
+    w.flush()
+}
+
+/// The nine numbers of a `Lattice="..."` key in an extended XYZ comment.
+fn lattice_from_comment(comment: &str) -> Result<Option<[[f64; 3]; 3]>, ParseError> {
+    let Some(start) = comment.find("Lattice=\"") else {
+        return Ok(None);
+    };
+    let rest = &comment[start + "Lattice=\"".len()..];
+    let end = rest
+        .find('"')
+        .ok_or_else(|| invalid(format!("unterminated Lattice in {comment:?}")))?;
+    let v: Vec<f64> = rest[..end]
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()?;
+    match v.as_slice() {
+        [ax, ay, az, bx, by, bz, cx, cy, cz] => {
+            Ok(Some([[*ax, *ay, *az], [*bx, *by, *bz], [*cx, *cy, *cz]]))
+        }
+        _ => Err(ParseError::InvalidVectorLength {
+            expected: 9,
+            found: v.len(),
+        }),
+    }
+}
+
+/// Lazily parses XYZ or extended XYZ text, one [`ConFrame`] per frame.
+pub struct XyzFrameIterator<'a> {
+    lines: std::str::Lines<'a>,
+}
+
+impl<'a> XyzFrameIterator<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            lines: text.lines(),
+        }
+    }
+
+    fn parse_frame(&mut self, count_line: &str) -> Result<ConFrame, ParseError> {
+        let n: usize = count_line.trim().parse()?;
+        let comment = self.lines.next().ok_or(ParseError::IncompleteHeader)?;
+        let (lengths, angles) = match lattice_from_comment(comment)? {
+            Some(m) => {
+                Cell::from_matrix(m).map_or(([0.0; 3], [90.0; 3]), |c| c.to_lengths_angles())
+            }
+            None => ([0.0; 3], [90.0; 3]),
+        };
+        let mut builder = ConFrameBuilder::new(lengths, angles);
+        if !comment.contains("Lattice=") {
+            builder.prebox_header(comment.trim());
+        }
+        for i in 0..n {
+            let line = self.lines.next().ok_or(ParseError::IncompleteFrame)?;
+            let mut cols = line.split_whitespace();
+            let symbol = cols
+                .next()
+                .ok_or_else(|| invalid(format!("empty atom line {}", i + 1)))?;
+            let mut pos = [0.0; 3];
+            for (k, out) in pos.iter_mut().enumerate() {
+                *out = cols
+                    .next()
+                    .ok_or(ParseError::InvalidVectorLength {
+                        expected: 4,
+                        found: k + 1,
+                    })?
+                    .parse()?;
+            }
+            let mass = ElementRegistry::new().mass(symbol).unwrap_or(0.0);
+            builder.add_atom(symbol, pos[0], pos[1], pos[2], [false; 3], i as u64, mass);
+        }
+        Ok(builder.build())
+    }
+}
+
+impl Iterator for XyzFrameIterator<'_> {
+    type Item = Result<ConFrame, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let count_line = self.lines.find(|l| !l.trim().is_empty())?;
+        Some(self.parse_frame(count_line))
+    }
+}
+
+/// Reads every frame of an `.xyz` file (optionally compressed, as for
+/// [`crate::iterators::read_all_frames`]).
+pub fn read_xyz(path: &Path) -> Result<Vec<ConFrame>, Box<dyn std::error::Error>> {
+    let contents = crate::compression::read_file_contents(path)?;
+    let text = contents.as_str()?;
+    let frames: Result<Vec<_>, _> = XyzFrameIterator::new(text).collect();
+    Ok(frames?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+
+    #[test]
+    fn round_trips_positions_and_cell() {
+        let con = fixture("tiny_multi_cuh2.con");
+        let frames: Vec<ConFrame> = ConFrameIterator::new(&con).map(|r| r.unwrap()).collect();
+        let mut out = Vec::new();
+        write_xyz(&mut out, &frames).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(
+            text.lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("Lattice=\"15.3456 0 0 ")
+        );
+
+        let back: Vec<ConFrame> = XyzFrameIterator::new(&text)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(back.len(), frames.len());
+        for (b, f) in back.iter().zip(&frames) {
+            assert_eq!(b.symbols(), f.symbols());
+            for (x, y) in b.atom_data.iter().zip(&f.atom_data) {
+                assert_eq!([x.x, x.y, x.z], [y.x, y.y, y.z]);
+            }
+            for k in 0..3 {
+                assert!((b.header.boxl[k] - f.header.boxl[k]).abs() < 1e-9);
+                assert!((b.header.angles[k] - f.header.angles[k]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_plain_xyz() {
+        let text = "3\nwater\nO 0 0 0\nH 0.96 0 0 extra\nH -0.24 0.93 0\n";
+        let frame = XyzFrameIterator::new(text).next().unwrap().unwrap();
+        assert_eq!(frame.header.prebox_header.user, "water");
+        assert_eq!(frame.atom_data.len(), 3);
+        assert_eq!(frame.header.boxl, [0.0; 3]);
+        assert!(
+            XyzFrameIterator::new("2\nx\nO 0 0 0\n")
+                .next()
+                .unwrap()
+                .is_err()
+        );
+        assert!(
+            XyzFrameIterator::new("1\nx\nO 0 0\n")
+                .next()
+                .unwrap()
+                .is_err()
+        );
+    }
+}
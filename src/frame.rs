@@ -0,0 +1,272 @@
+//! A minimal structure trait for generic algorithms.
+//!
+//! [`AtomicFrame`] is the view the geometry code in this crate needs:
+//! positions, species, cell and fixed flags, plus optional periodicity, ids
+//! and masses. [`ConFrame`] implements it, and so can any third-party
+//! structure type (the trait is local to this crate, so the orphan rule
+//! allows `impl AtomicFrame for TheirType`). Anything implementing it works
+//! with [`NeighborList`](crate::neighbor::NeighborList), the
+//! [`analysis`](crate::analysis) functions and
+//! [`ConFrameWriter::write_atomic`](crate::writer::ConFrameWriter::write_atomic).
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::helpers::ElementRegistry;
+use crate::types::{ConFrame, ConFrameBuilder};
+
+/// Read-only per-atom view of a periodic (or partly periodic) structure.
+///
+/// Atom indices run over `0..natoms()`; every per-atom method may assume
+/// `i < natoms()`.
+pub trait AtomicFrame {
+    /// Number of atoms.
+    fn natoms(&self) -> usize;
+
+    /// Cartesian position of atom `i`.
+    fn position(&self, i: usize) -> [f64; 3];
+
+    /// Chemical symbol (species label) of atom `i`.
+    fn species(&self, i: usize) -> &str;
+
+    /// Simulation cell; `None` when the structure has no usable cell.
+    fn cell(&self) -> Option<Cell>;
+
+    /// Per-axis fixed flags of atom `i`; free by default.
+    fn fixed(&self, i: usize) -> [bool; 3] {
+        let _ = i;
+        [false; 3]
+    }
+
+    /// Periodic axes; fully periodic by default.
+    fn pbc(&self) -> [bool; 3] {
+        [true; 3]
+    }
+
+    /// Stable id of atom `i`; its index by default.
+    fn atom_id(&self, i: usize) -> u64 {
+        i as u64
+    }
+
+    /// Mass of atom `i`, when known.
+    fn mass(&self, i: usize) -> Option<f64> {
+        let _ = i;
+        None
+    }
+}
+
+impl<T: AtomicFrame + ?Sized> AtomicFrame for &T {
+    fn natoms(&self) -> usize {
+        (**self).natoms()
+    }
+    fn position(&self, i: usize) -> [f64; 3] {
+        (**self).position(i)
+    }
+    fn species(&self, i: usize) -> &str {
+        (**self).species(i)
+    }
+    fn cell(&self) -> Option<Cell> {
+        (**self).cell()
+    }
+    fn fixed(&self, i: usize) -> [bool; 3] {
+        (**self).fixed(i)
+    }
+    fn pbc(&self) -> [bool; 3] {
+        (**self).pbc()
+    }
+    fn atom_id(&self, i: usize) -> u64 {
+        (**self).atom_id(i)
+    }
+    fn mass(&self, i: usize) -> Option<f64> {
+        (**self).mass(i)
+    }
+}
+
+impl AtomicFrame for ConFrame {
+    fn natoms(&self) -> usize {
+        self.atom_data.len()
+    }
+    fn position(&self, i: usize) -> [f64; 3] {
+        let a = &self.atom_data[i];
+        [a.x, a.y, a.z]
+    }
+    fn species(&self, i: usize) -> &str {
+        self.symbol(i)
+    }
+    fn cell(&self) -> Option<Cell> {
+        self.header.cell()
+    }
+    fn fixed(&self, i: usize) -> [bool; 3] {
+        self.atom_data[i].fixed
+    }
+    fn pbc(&self) -> [bool; 3] {
+        self.header.pbc().unwrap_or([true; 3])
+    }
+    fn atom_id(&self, i: usize) -> u64 {
+        self.atom_data[i].atom_id
+    }
+    fn mass(&self, i: usize) -> Option<f64> {
+        if self.masses.len() == self.atom_data.len() {
+            return Some(self.masses.get_f64(i));
+        }
+        let mut end = 0;
+        for (t, &count) in self.header.natms_per_type.iter().enumerate() {
+            end += count;
+            if i < end {
+                return self.header.masses_per_type.get(t).copied();
+            }
+        }
+        None
+    }
+}
+
+impl ConFrame {
+    /// Builds a CON frame from any [`AtomicFrame`], grouping atoms by
+    /// species as [`ConFrameBuilder`] does. The cell's lattice vectors
+    /// are kept in `lattice_vectors` metadata when they are not in the
+    /// standard orientation of its lengths and angles, and `pbc` is
+    /// recorded unless fully periodic. Atoms without a mass get the
+    /// standard weight of their symbol ([`crate::helpers::atomic_mass`]).
+    ///
+    /// Errors are [`ParseError::ValidationError`] for a missing cell or
+    /// an atom with neither a mass nor a known symbol.
+    pub fn from_atomic<F: AtomicFrame + ?Sized>(frame: &F) -> Result<ConFrame, ParseError> {
+        Self::from_atomic_with(frame, &ElementRegistry::new())
+    }
+
+    /// [`Self::from_atomic`] resolving missing masses through `registry`,
+    /// so custom labels convert instead of failing.
+    pub fn from_atomic_with<F: AtomicFrame + ?Sized>(
+        frame: &F,
+        registry: &ElementRegistry,
+    ) -> Result<ConFrame, ParseError> {
+        let cell = frame
+            .cell()
+            .ok_or_else(|| ParseError::ValidationError("frame has no usable cell".into()))?;
+        let (lengths, angles) = cell.to_lengths_angles();
+        let mut b = ConFrameBuilder::new(lengths, angles);
+        for i in 0..frame.natoms() {
+            let mass = match frame.mass(i) {
+                Some(m) => m,
+                None => registry.mass(frame.species(i))?,
+            };
+            let [x, y, z] = frame.position(i);
+            b.add_atom(
+                frame.species(i),
+                x,
+                y,
+                z,
+                frame.fixed(i),
+                frame.atom_id(i),
+                mass,
+            );
+        }
+        let mut out = b.build();
+        let standard = Cell::from_lengths_angles(lengths, angles).map(|c| c.matrix());
+        let same = standard.is_some_and(|s| {
+            s.iter()
+                .flatten()
+                .zip(cell.matrix().iter().flatten())
+                .all(|(u, v)| (u - v).abs() <= 1e-9 * (1.0 + v.abs()))
+        });
+        if !same {
+            out.header.set_lattice_vectors(cell.matrix());
+        }
+        if frame.pbc() != [true; 3] {
+            out.header.set_pbc(frame.pbc());
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+
+    /// A foreign structure type: flat arrays, a rotated cell, no masses.
+    struct Foreign {
+        species: Vec<&'static str>,
+        xyz: Vec<[f64; 3]>,
+        lattice: [[f64; 3]; 3],
+    }
+
+    impl AtomicFrame for Foreign {
+        fn natoms(&self) -> usize {
+            self.xyz.len()
+        }
+        fn position(&self, i: usize) -> [f64; 3] {
+            self.xyz[i]
+        }
+        fn species(&self, i: usize) -> &str {
+            self.species[i]
+        }
+        fn cell(&self) -> Option<Cell> {
+            Cell::from_matrix(self.lattice)
+        }
+        fn pbc(&self) -> [bool; 3] {
+            [true, true, false]
+        }
+    }
+
+    fn water() -> Foreign {
+        Foreign {
+            species: vec!["H", "O", "H"],
+            xyz: vec![[0.76, 0.59, 5.0], [0.0, 0.0, 5.0], [-0.76, 0.59, 5.0]],
+            lattice: [[0.0, 8.0, 0.0], [-8.0, 0.0, 0.0], [0.0, 0.0, 9.0]],
+        }
+    }
+
+    #[test]
+    fn con_frame_view_matches_atom_data() {
+        let text = fixture("tiny_cuh2.con");
+        let frame = ConFrameIterator::new(&text).next().unwrap().unwrap();
+        assert_eq!(frame.natoms(), 4);
+        assert_eq!(AtomicFrame::species(&frame, 2), "H");
+        assert_eq!(AtomicFrame::mass(&frame, 0), Some(63.546));
+        assert_eq!(AtomicFrame::fixed(&frame, 0), [true; 3]);
+        let copy = ConFrame::from_atomic(&frame).unwrap();
+        assert_eq!(copy.atom_data, frame.atom_data);
+        assert!(!copy.header.metadata.contains_key("lattice_vectors"));
+    }
+
+    #[test]
+    fn foreign_frame_converts_with_cell_and_pbc() {
+        let w = water();
+        let frame = ConFrame::from_atomic(&w).unwrap();
+        assert_eq!(frame.header.natms_per_type, vec![2, 1]);
+        assert_eq!(frame.header.masses_per_type, vec![1.008, 15.999]);
+        assert_eq!(frame.header.lattice_vectors(), Some(w.lattice));
+        assert_eq!(frame.header.pbc(), Some([true, true, false]));
+        assert_eq!(*frame.atom_ids(), [0, 2, 1]);
+        assert!((frame.header.boxl[0] - 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn custom_labels_convert_through_a_registry() {
+        let mut w = water();
+        w.species[1] = "OW";
+        assert!(ConFrame::from_atomic(&w).is_err());
+        let mut reg = ElementRegistry::new();
+        reg.register("OW", 8, None).unwrap();
+        let frame = ConFrame::from_atomic_with(&w, &reg).unwrap();
+        assert_eq!(frame.header.masses_per_type, vec![1.008, 15.999]);
+    }
+
+    #[test]
+    fn generic_pipelines_accept_foreign_frames() {
+        let w = water();
+        let nl = crate::neighbor::NeighborList::build(&w, 1.2).unwrap();
+        assert_eq!(nl.coordination_numbers(), vec![1, 2, 1]);
+        let mut moved = water();
+        moved.xyz[1][2] += 0.3;
+        assert!((crate::analysis::max_displacement(&w, &moved).unwrap() - 0.3).abs() < 1e-12);
+
+        let mut writer = crate::writer::ConFrameWriter::new(Vec::new());
+        writer.write_atomic(&w).unwrap();
+        let text = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        let back = ConFrameIterator::new(&text).next().unwrap().unwrap();
+        assert_eq!(back.symbols(), vec!["H", "H", "O"]);
+        assert_eq!(back.header.pbc(), Some([true, true, false]));
+    }
+}
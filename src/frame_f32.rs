@@ -0,0 +1,282 @@
+//! Single-precision frames for memory-bound trajectory analysis.
+//!
+//! A [`ConFrame`] keeps every atom twice, as an [`AtomDatum`] and in the
+//! f64 SoA arrays, which is the right trade for editing and writing but
+//! not for holding tens of millions of atom-frames in memory. A
+//! [`ConFrameF32`] keeps the header, one symbol per component and flat
+//! per-atom arrays: `[f32; 3]` positions (and velocities and forces when
+//! present), fixed-flag bitmasks and atom ids, 21 bytes per atom for a
+//! coordinates-only frame.
+//!
+//! [`ConFrameIterator::next_f32`](crate::iterators::ConFrameIterator::next_f32)
+//! parses coordinates-only frames straight into these arrays, one line at
+//! a time with no f64 buffers; frames with velocity or force blocks, or
+//! read under [`ParseMode::Permissive`](crate::parser::ParseMode) or
+//! validation, go through the full parser and are converted. Per-atom
+//! energies, charges, spins, magnetic moments, extra columns and
+//! properties are not kept.
+
+use crate::error::ParseError;
+use crate::parser::{
+    LineStream, atom_id_from, fixed_flag_from, is_standard_component_label, is_velocity_label,
+    parse_frame_header, parse_line_of_range_f64_stack, total_atom_count,
+};
+use crate::types::{
+    AtomDatum, ConFrame, FrameHeader, decode_fixed_bitmask, encode_fixed_bitmask, intern_symbol,
+    symbol_table_full,
+};
+
+/// Atoms reserved up front at most, as for [`ConFrame`] parsing: the
+/// header's counts are untrusted.
+const PREALLOC_ATOMS: usize = 1 << 16;
+
+/// A frame with single-precision per-atom arrays; see the
+/// [module docs](crate::frame_f32).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConFrameF32 {
+    /// The frame header, as parsed.
+    pub header: FrameHeader,
+    /// Symbol of each component, parallel to `header.natms_per_type`.
+    pub symbols: Vec<String>,
+    /// Position of each atom, in file order.
+    pub positions: Vec<[f32; 3]>,
+    /// Fixed-flag bitmask of each atom (bit 0 x, bit 1 y, bit 2 z), as
+    /// [`encode_fixed_bitmask`] writes it: legacy flags read as 7.
+    pub fixed: Vec<u8>,
+    /// Atom id of each atom.
+    pub atom_ids: Vec<u64>,
+    /// Velocity of each atom; empty when the frame has none.
+    pub velocities: Vec<[f32; 3]>,
+    /// Force on each atom; empty when the frame has none.
+    pub forces: Vec<[f32; 3]>,
+}
+
+impl ConFrameF32 {
+    /// Number of atoms.
+    pub fn natoms(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Symbol of atom `i`, or `None` past the last atom.
+    pub fn symbol(&self, i: usize) -> Option<&str> {
+        let mut end = 0;
+        for (symbol, &n) in self.symbols.iter().zip(&self.header.natms_per_type) {
+            end += n;
+            if i < end {
+                return Some(symbol);
+            }
+        }
+        None
+    }
+
+    /// Heap bytes held by the per-atom arrays (capacity, not length).
+    pub fn atom_bytes(&self) -> usize {
+        use std::mem::size_of;
+        (self.positions.capacity() + self.velocities.capacity() + self.forces.capacity())
+            * size_of::<[f32; 3]>()
+            + self.fixed.capacity()
+            + self.atom_ids.capacity() * size_of::<u64>()
+    }
+
+    /// Widens back to a [`ConFrame`], velocities and forces included.
+    ///
+    /// Errors with [`ParseError::ValidationError`] when the components
+    /// carry more distinct symbols than a [`ConFrame::symbol_table`] holds.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::frame_f32::ConFrameF32;
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("Cu", 0.5, 1.25, 2.0, [false, false, true], 7, 63.546);
+    /// let frame = b.build();
+    /// let narrow = ConFrameF32::from(&frame);
+    /// assert_eq!(narrow.positions, [[0.5, 1.25, 2.0]]);
+    /// assert_eq!(narrow.to_con_frame().unwrap().atom_data, frame.atom_data);
+    /// ```
+    pub fn to_con_frame(&self) -> Result<ConFrame, ParseError> {
+        let mut symbol_table = Vec::new();
+        let mut atom_data = Vec::with_capacity(self.natoms());
+        let mut i = 0;
+        for (symbol, &n) in self.symbols.iter().zip(&self.header.natms_per_type) {
+            let symbol = intern_symbol(&mut symbol_table, symbol)
+                .ok_or_else(|| symbol_table_full(symbol))?;
+            for _ in 0..n {
+                let [x, y, z] = self.positions[i].map(f64::from);
+                atom_data.push(AtomDatum {
+                    symbol,
+                    x,
+                    y,
+                    z,
+                    fixed: decode_fixed_bitmask(self.fixed[i]),
+                    atom_id: self.atom_ids[i],
+                    velocity: self.velocities.get(i).map(|v| v.map(f64::from)),
+                    force: self.forces.get(i).map(|f| f.map(f64::from)),
+                    energy: None,
+                    charge: None,
+                    spin: None,
+                    magmom: None,
+                    extra: Vec::new(),
+                });
+                i += 1;
+            }
+        }
+        Ok(crate::types::con_frame_from_atom_data(
+            self.header.clone(),
+            symbol_table,
+            atom_data,
+        ))
+    }
+}
+
+impl From<&ConFrame> for ConFrameF32 {
+    /// Narrows `frame`, rounding each value to the nearest f32. The symbol
+    /// of a component is that of its first atom.
+    fn from(frame: &ConFrame) -> Self {
+        let atoms = &frame.atom_data;
+        let narrow = |v: [f64; 3]| v.map(|c| c as f32);
+        let mut symbols = Vec::with_capacity(frame.header.natms_per_type.len());
+        let mut offset = 0;
+        for &n in &frame.header.natms_per_type {
+            let symbol = atoms.get(offset).filter(|_| n > 0);
+            symbols.push(symbol.map_or_else(String::new, |a| frame.symbol_of(a).to_string()));
+            offset += n;
+        }
+        let vectors = |get: fn(&AtomDatum) -> Option<[f64; 3]>| -> Vec<[f32; 3]> {
+            if atoms.first().and_then(get).is_none() {
+                return Vec::new();
+            }
+            atoms
+                .iter()
+                .map(|a| get(a).map_or([0.0; 3], narrow))
+                .collect()
+        };
+        ConFrameF32 {
+            header: frame.header.clone(),
+            symbols,
+            positions: atoms.iter().map(|a| narrow([a.x, a.y, a.z])).collect(),
+            fixed: atoms
+                .iter()
+                .map(|a| encode_fixed_bitmask(a.fixed))
+                .collect(),
+            atom_ids: atoms.iter().map(|a| a.atom_id).collect(),
+            velocities: vectors(|a| a.velocity),
+            forces: vectors(|a| a.force),
+        }
+    }
+}
+
+/// Parses a coordinates-only frame from `lines` straight into f32 arrays.
+///
+/// Returns `Ok(None)`, with `lines` partly consumed, when the frame needs
+/// the full parser: validation is on, sections are declared, a charge
+/// column is named, or a velocity or force block follows. Errors are those
+/// of the first line that does not parse; callers re-read the frame with
+/// the full parser for its complete diagnosis.
+pub(crate) fn parse_plain_frame<'a>(
+    lines: &mut impl LineStream<'a>,
+) -> Result<Option<ConFrameF32>, ParseError> {
+    let mut header = parse_frame_header(&mut std::iter::from_fn(|| lines.next_line()))?;
+    if header.strict_validation
+        || (header.sections_declared && !header.sections.is_empty())
+        || header.charge_column().is_some()
+    {
+        return Ok(None);
+    }
+    let reserve = total_atom_count(&header.natms_per_type)?.min(PREALLOC_ATOMS);
+    let mut positions = Vec::with_capacity(reserve);
+    let mut fixed = Vec::with_capacity(reserve);
+    let mut atom_ids = Vec::with_capacity(reserve);
+    let mut symbols = Vec::with_capacity(header.natm_types);
+    let mut component_labels: Vec<String> = Vec::new();
+    for (type_idx, &natoms) in header.natms_per_type.iter().enumerate() {
+        let symbol = lines.next_line().ok_or(ParseError::IncompleteFrame)?;
+        let label = lines.next_line().ok_or(ParseError::IncompleteFrame)?.trim();
+        symbols.push(symbol.trim().to_string());
+        if !is_standard_component_label(label, type_idx) {
+            if component_labels.is_empty() {
+                component_labels.extend((0..type_idx).map(FrameHeader::standard_component_label));
+            }
+            component_labels.push(label.to_string());
+        } else if !component_labels.is_empty() {
+            component_labels.push(FrameHeader::standard_component_label(type_idx));
+        }
+        for _ in 0..natoms {
+            let line = lines.next_line().ok_or(ParseError::IncompleteFrame)?;
+            let defaults = [0.0, 0.0, 0.0, 0.0, positions.len() as f64];
+            let mut vals = [0.0f64; 5];
+            parse_line_of_range_f64_stack(line, 4, 5, &defaults, &mut vals)?;
+            positions.push([vals[0] as f32, vals[1] as f32, vals[2] as f32]);
+            let flag = fixed_flag_from(vals[3], line, 3)?;
+            fixed.push(encode_fixed_bitmask(decode_fixed_bitmask(flag)));
+            atom_ids.push(atom_id_from(vals[4], line, 4)?);
+        }
+        // eOn dynamics frames interleave each component's velocities.
+        if lines
+            .peek_nth(1)
+            .is_some_and(|l| is_velocity_label(l, type_idx))
+        {
+            return Ok(None);
+        }
+    }
+    // A blank line after the coordinates may open a velocity or force
+    // block, which the full parser tells from the next frame.
+    if lines.peek_line().is_some_and(|l| l.trim().is_empty()) {
+        return Ok(None);
+    }
+    header.component_labels = component_labels;
+    Ok(Some(ConFrameF32 {
+        header,
+        symbols,
+        positions,
+        fixed,
+        atom_ids,
+        velocities: Vec::new(),
+        forces: Vec::new(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+
+    #[test]
+    fn next_f32_matches_narrowed_full_parse() {
+        for name in [
+            "tiny_cuh2.con",
+            "tiny_multi_cuh2.con",
+            "tiny_cuh2.convel",
+            "tiny_multi_cuh2.convel",
+            "tiny_cuh2_dynamics.con",
+            "tiny_cuh2_forces.con",
+            "tiny_cuh2_vel_forces.con",
+        ] {
+            let text = fixture(name);
+            let wide: Vec<_> = ConFrameIterator::new(&text)
+                .map(|r| ConFrameF32::from(&r.unwrap()))
+                .collect();
+            let mut iter = ConFrameIterator::new(&text);
+            let mut narrow = Vec::new();
+            while let Some(frame) = iter.next_f32() {
+                narrow.push(frame.unwrap());
+            }
+            assert_eq!(narrow, wide, "{name}");
+        }
+        let text = fixture("tiny_cuh2_vel_forces.con");
+        let frame = ConFrameIterator::new(&text).next_f32().unwrap().unwrap();
+        assert_eq!(frame.velocities.len(), frame.natoms());
+        assert_eq!(frame.forces.len(), frame.natoms());
+        assert_eq!(frame.symbol(0), Some("Cu"));
+        assert_eq!(frame.symbol(frame.natoms()), None);
+    }
+
+    #[test]
+    fn next_f32_reports_errors_like_next() {
+        let bad = fixture("tiny_cuh2.con").replace("0.63940000000000108", "0.6394x");
+        let expected = ConFrameIterator::new(&bad).next().unwrap().unwrap_err();
+        let got = ConFrameIterator::new(&bad).next_f32().unwrap().unwrap_err();
+        assert_eq!(got.to_string(), expected.to_string());
+    }
+}
@@ -0,0 +1,155 @@
+//! Centers, inertia tensor and rigid translations of a frame.
+//!
+//! Masses are the per-atom masses when the frame carries them and the
+//! per-type `masses_per_type` otherwise (see
+//! [`AtomicFrame::mass`](crate::frame::AtomicFrame::mass)). Coordinates are
+//! used as stored: a molecule split across a periodic face is not made whole
+//! first, so call [`unwrap_trajectory`](crate::pbc::unwrap_trajectory) or
+//! keep molecules whole when that matters.
+
+use crate::frame::AtomicFrame;
+use crate::types::ConFrame;
+
+impl ConFrame {
+    /// Weighted mean position, or `None` for an empty frame, unknown masses
+    /// or a zero total weight.
+    fn weighted_center(&self, weight: impl Fn(usize) -> Option<f64>) -> Option<[f64; 3]> {
+        let mut total = 0.0;
+        let mut sum = [0.0; 3];
+        for (i, a) in self.atom_data.iter().enumerate() {
+            let w = weight(i)?;
+            total += w;
+            for (s, x) in sum.iter_mut().zip([a.x, a.y, a.z]) {
+                *s += w * x;
+            }
+        }
+        (total != 0.0).then(|| sum.map(|s| s / total))
+    }
+
+    /// Mass-weighted center of the atoms; `None` for an empty frame or
+    /// when masses are unknown or sum to zero.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("O", 0.0, 0.0, 0.0, [false; 3], 0, 16.0);
+    /// b.add_atom("H", 1.7, 0.0, 0.0, [false; 3], 1, 1.0);
+    /// let com = b.build().center_of_mass().unwrap();
+    /// assert!((com[0] - 0.1).abs() < 1e-12);
+    /// ```
+    pub fn center_of_mass(&self) -> Option<[f64; 3]> {
+        self.weighted_center(|i| self.mass(i))
+    }
+
+    /// Unweighted mean position of the atoms; `None` for an empty frame.
+    pub fn center_of_geometry(&self) -> Option<[f64; 3]> {
+        self.weighted_center(|_| Some(1.0))
+    }
+
+    /// Inertia tensor about the center of mass,
+    /// `I = Σ mᵢ (|rᵢ|² 𝟙 − rᵢ ⊗ rᵢ)` with `rᵢ` relative to the center, in
+    /// mass × length² of the frame's units. `None` when
+    /// [`center_of_mass`](Self::center_of_mass) is.
+    pub fn inertia_tensor(&self) -> Option<[[f64; 3]; 3]> {
+        let com = self.center_of_mass()?;
+        let mut tensor = [[0.0; 3]; 3];
+        for (i, a) in self.atom_data.iter().enumerate() {
+            let m = self.mass(i)?;
+            let r = [a.x - com[0], a.y - com[1], a.z - com[2]];
+            let r2 = r.iter().map(|x| x * x).sum::<f64>();
+            for (j, row) in tensor.iter_mut().enumerate() {
+                for (k, t) in row.iter_mut().enumerate() {
+                    let diagonal = if j == k { r2 } else { 0.0 };
+                    *t += m * (diagonal - r[j] * r[k]);
+                }
+            }
+        }
+        Some(tensor)
+    }
+
+    /// Moves every atom, fixed ones included, by `shift`.
+    pub fn translate(&mut self, shift: [f64; 3]) {
+        for i in 0..self.atom_data.len() {
+            let a = &self.atom_data[i];
+            let p = [a.x + shift[0], a.y + shift[1], a.z + shift[2]];
+            self.set_position(i, p);
+        }
+    }
+
+    /// Translates the frame so its center of mass sits at the middle of the
+    /// cell (the origin when the frame has no usable cell). Returns `false`,
+    /// changing nothing, when there is no center of mass.
+    pub fn recenter(&mut self) -> bool {
+        let Some(com) = self.center_of_mass() else {
+            return false;
+        };
+        let target = self
+            .header
+            .cell()
+            .map_or([0.0; 3], |c| c.fractional_to_cartesian([0.5; 3]));
+        self.translate([target[0] - com[0], target[1] - com[1], target[2] - com[2]]);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{ConFrame, ConFrameBuilder};
+
+    fn water() -> ConFrame {
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        b.add_atom("O", 1.0, 1.0, 1.0, [true; 3], 0, 15.999);
+        b.add_atom("H", 1.757, 1.586, 1.0, [false; 3], 1, 1.008);
+        b.add_atom("H", 0.243, 1.586, 1.0, [false; 3], 2, 1.008);
+        b.build()
+    }
+
+    #[test]
+    fn centers_and_inertia_of_water() {
+        let f = water();
+        let com = f.center_of_mass().unwrap();
+        let y = 1.0 + 2.0 * 1.008 * 0.586 / (15.999 + 2.0 * 1.008);
+        assert!((com[0] - 1.0).abs() < 1e-12 && (com[1] - y).abs() < 1e-12);
+        let cog = f.center_of_geometry().unwrap();
+        assert!((cog[1] - (1.0 + 2.0 * 0.586 / 3.0)).abs() < 1e-12);
+
+        let i = f.inertia_tensor().unwrap();
+        // Planar molecule in z = 1: I_zz = I_xx + I_yy, no z couplings.
+        assert!((i[2][2] - i[0][0] - i[1][1]).abs() < 1e-9);
+        assert_eq!([i[0][2], i[1][2]], [0.0, 0.0]);
+        assert!(i[0][1].abs() < 1e-12, "symmetric about x = 1");
+        let ixx = 2.0 * 1.008 * (0.586 - (y - 1.0)).powi(2) + 15.999 * (y - 1.0).powi(2);
+        assert!((i[0][0] - ixx).abs() < 1e-9);
+
+        assert!(
+            ConFrameBuilder::new([10.0; 3], [90.0; 3])
+                .build()
+                .center_of_mass()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn translate_and_recenter_move_every_atom() {
+        let mut f = water();
+        f.translate([1.0, -1.0, 0.5]);
+        assert_eq!(
+            [f.atom_data[0].x, f.atom_data[0].y, f.atom_data[0].z],
+            [2.0, 0.0, 1.5]
+        );
+        let h = &f.atom_data[1];
+        assert_eq!(f.positions.as_f64_row(1), [h.x, h.y, h.z]);
+        assert!((h.x - 2.757).abs() < 1e-12 && (h.y - 0.586).abs() < 1e-12);
+
+        assert!(f.recenter());
+        let com = f.center_of_mass().unwrap();
+        assert!(com.iter().all(|c| (c - 5.0).abs() < 1e-12), "{com:?}");
+        assert_eq!(f.atom_data[0].fixed, [true; 3]);
+        assert!(
+            !ConFrameBuilder::new([10.0; 3], [90.0; 3])
+                .build()
+                .recenter()
+        );
+    }
+}
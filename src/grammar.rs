@@ -34,16 +34,10 @@ pub fn accepts(input: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::fixture;
     use std::fs;
     use std::path::PathBuf;
 
-    fn fixture(name: &str) -> String {
-        let p = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("resources/test")
-            .join(name);
-        fs::read_to_string(&p).unwrap_or_else(|e| panic!("read {}: {e}", p.display()))
-    }
-
     #[test]
     fn grammar_accepts_core_fixtures() {
         for name in [
@@ -20,6 +20,36 @@
 //! The same lookup is exposed to C/C++ via [`crate::ffi::rkr_symbol_to_z`]
 //! and [`crate::ffi::rkr_z_to_symbol`] so downstream tools can drop
 //! their own copies of the periodic table.
+//!
+//! [`atomic_mass`] adds standard atomic weights (IUPAC abridged values,
+//! amu) for the same range; elements without a stable isotope use the
+//! mass number of their longest-lived one. Deuterium and tritium get
+//! their isotopic masses.
+//!
+//! Isotope labels, an element symbol followed by its mass number (`H2`,
+//! `C13`, `Cu65`), are read by [`parse_isotope`] and weighed by
+//! [`isotope_mass`]. They are not elements for [`symbol_to_atomic_number`];
+//! [`ElementRegistry`] resolves them, so conversions keep the label and get
+//! the isotopic mass.
+//!
+//! [`covalent_radius`] (Cordero et al., 2008) and [`vdw_radius`] (Bondi,
+//! 1964, extended by Mantina et al., 2009) give the radii in Å used for
+//! connectivity guessing ([`crate::bonds::guess_bonds`]). Elements without
+//! a tabulated van der Waals radius return `None`.
+//!
+//! Where a silent 0 or "X" is not acceptable, [`ElementRegistry`] gives
+//! the same lookups as `Result`s with an explicit error for unknown
+//! species, and lets callers register their own labels (`Cu1`, `OW`,
+//! ghost sites) with an atomic number and mass. The conversions that
+//! need element data take one ([`ConFrame::from_atomic_with`],
+//! [`ConFrameBuilder::infer_masses_with`]); over the C ABI a process-wide
+//! registry is filled with [`crate::ffi::rkr_register_element`].
+//!
+//! [`ConFrame::from_atomic_with`]: crate::types::ConFrame::from_atomic_with
+//! [`ConFrameBuilder::infer_masses_with`]: crate::types::ConFrameBuilder::infer_masses_with
+
+use crate::error::ParseError;
+use std::collections::HashMap;
 
 /// Returns the atomic number for a chemical symbol, or 0 if unknown.
 pub fn symbol_to_atomic_number(symbol: &str) -> u64 {
@@ -120,6 +150,343 @@ pub fn symbol_to_atomic_number(symbol: &str) -> u64 {
     }
 }
 
+/// Standard atomic weights in amu, indexed by `Z - 1`.
+const ATOMIC_MASSES: [f64; 92] = [
+    1.008, 4.0026, 6.94, 9.0122, 10.81, 12.011, 14.007, 15.999, 18.998, 20.180,
+    22.990, 24.305, 26.982, 28.085, 30.974, 32.06, 35.45, 39.95, 39.098, 40.078,
+    44.956, 47.867, 50.942, 51.996, 54.938, 55.845, 58.933, 58.693, 63.546, 65.38,
+    69.723, 72.630, 74.922, 78.971, 79.904, 83.798, 85.468, 87.62, 88.906, 91.224,
+    92.906, 95.95, 98.0, 101.07, 102.91, 106.42, 107.87, 112.41, 114.82, 118.71,
+    121.76, 127.60, 126.90, 131.29, 132.91, 137.33, 138.91, 140.12, 140.91, 144.24,
+    145.0, 150.36, 151.96, 157.25, 158.93, 162.50, 164.93, 167.26, 168.93, 173.05,
+    174.97, 178.49, 180.95, 183.84, 186.21, 190.23, 192.22, 195.08, 196.97, 200.59,
+    204.38, 207.2, 208.98, 209.0, 210.0, 222.0, 223.0, 226.0, 227.0, 232.04,
+    231.04, 238.03,
+];
+
+/// Standard atomic weight (amu) for a chemical symbol, or `None` if
+/// unknown. Used by
+/// [`ConFrameBuilder::infer_masses`](crate::types::ConFrameBuilder::infer_masses).
+///
+/// # Example
+/// ```
+/// use readcon_core::helpers::atomic_mass;
+/// assert_eq!(atomic_mass("Cu"), Some(63.546));
+/// assert_eq!(atomic_mass("D"), Some(2.0141));
+/// assert_eq!(atomic_mass("Gh"), None);
+/// ```
+pub fn atomic_mass(symbol: &str) -> Option<f64> {
+    match symbol {
+        "D" => Some(2.0141),
+        "T" => Some(3.0160),
+        _ => match symbol_to_atomic_number(symbol) {
+            0 => None,
+            z => ATOMIC_MASSES.get(z as usize - 1).copied(),
+        },
+    }
+}
+
+/// An element with a given mass number, as named by an isotope label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Isotope {
+    pub atomic_number: u64,
+    /// Protons plus neutrons.
+    pub mass_number: u32,
+}
+
+/// Isotopic masses (AME2020, amu) of commonly substituted isotopes as
+/// `(Z, A, mass)`; other isotopes are weighed by their mass number.
+const ISOTOPE_MASSES: [(u64, u32, f64); 36] = [
+    (1, 1, 1.007_825_03),
+    (1, 2, 2.014_101_78),
+    (1, 3, 3.016_049_28),
+    (2, 3, 3.016_029_32),
+    (2, 4, 4.002_603_25),
+    (3, 6, 6.015_122_9),
+    (3, 7, 7.016_003_4),
+    (5, 10, 10.012_937_0),
+    (5, 11, 11.009_305_4),
+    (6, 12, 12.0),
+    (6, 13, 13.003_354_84),
+    (6, 14, 14.003_241_99),
+    (7, 14, 14.003_074_01),
+    (7, 15, 15.000_108_90),
+    (8, 16, 15.994_914_62),
+    (8, 17, 16.999_131_76),
+    (8, 18, 17.999_159_61),
+    (9, 19, 18.998_403_16),
+    (14, 28, 27.976_926_53),
+    (14, 29, 28.976_494_66),
+    (14, 30, 29.973_770_14),
+    (16, 32, 31.972_071_17),
+    (16, 34, 33.967_867_01),
+    (17, 35, 34.968_852_68),
+    (17, 37, 36.965_902_60),
+    (26, 54, 53.939_608_99),
+    (26, 56, 55.934_936_33),
+    (26, 57, 56.935_392_84),
+    (29, 63, 62.929_597_72),
+    (29, 65, 64.927_789_70),
+    (30, 64, 63.929_142_01),
+    (47, 107, 106.905_091_6),
+    (47, 109, 108.904_755_3),
+    (79, 197, 196.966_570_1),
+    (92, 235, 235.043_930_1),
+    (92, 238, 238.050_788_4),
+];
+
+/// Reads an isotope label: `D` or `T`, or an element symbol followed by a
+/// mass number between `Z` and `3Z + 10`. The range keeps site labels such
+/// as `C1` or `O2` from reading as isotopes; `H2`, `H3` and `He3` do.
+///
+/// # Example
+/// ```
+/// use readcon_core::helpers::parse_isotope;
+/// let cu = parse_isotope("Cu65").unwrap();
+/// assert_eq!((cu.atomic_number, cu.mass_number), (29, 65));
+/// assert_eq!(parse_isotope("D").unwrap().mass_number, 2);
+/// assert_eq!(parse_isotope("O2"), None);
+/// assert_eq!(parse_isotope("Cu"), None);
+/// ```
+pub fn parse_isotope(label: &str) -> Option<Isotope> {
+    let (z, a) = match label {
+        "D" => (1, 2),
+        "T" => (1, 3),
+        _ => {
+            let split = label.find(|c: char| c.is_ascii_digit())?;
+            let (symbol, digits) = label.split_at(split);
+            if symbol == "D" || symbol == "T" || digits.starts_with('0') {
+                return None;
+            }
+            let z = symbol_to_atomic_number(symbol);
+            let a: u32 = digits.parse().ok()?;
+            (z, a)
+        }
+    };
+    (z != 0 && (z..=3 * z + 10).contains(&u64::from(a))).then_some(Isotope {
+        atomic_number: z,
+        mass_number: a,
+    })
+}
+
+/// Mass of `isotope` in amu: the tabulated isotopic mass, else the mass
+/// number (within 0.1 amu for every nuclide).
+///
+/// # Example
+/// ```
+/// use readcon_core::helpers::{isotope_mass, parse_isotope};
+/// assert_eq!(isotope_mass(parse_isotope("C13").unwrap()), 13.00335484);
+/// assert_eq!(isotope_mass(parse_isotope("Pt195").unwrap()), 195.0);
+/// ```
+pub fn isotope_mass(isotope: Isotope) -> f64 {
+    ISOTOPE_MASSES
+        .iter()
+        .find(|&&(z, a, _)| z == isotope.atomic_number && a == isotope.mass_number)
+        .map_or(f64::from(isotope.mass_number), |&(_, _, m)| m)
+}
+
+/// Covalent radii in Å (Cordero et al., 2008; low-spin values for Mn,
+/// Fe, Co and sp3 carbon), indexed by `Z - 1`.
+const COVALENT_RADII: [f64; 92] = [
+    0.31, 0.28, 1.28, 0.96, 0.84, 0.76, 0.71, 0.66, 0.57, 0.58, 1.66, 1.41, 1.21, 1.11, 1.07, 1.05,
+    1.02, 1.06, 2.03, 1.76, 1.70, 1.60, 1.53, 1.39, 1.39, 1.32, 1.26, 1.24, 1.32, 1.22, 1.22, 1.20,
+    1.19, 1.20, 1.20, 1.16, 2.20, 1.95, 1.90, 1.75, 1.64, 1.54, 1.47, 1.46, 1.42, 1.39, 1.45, 1.44,
+    1.42, 1.39, 1.39, 1.38, 1.39, 1.40, 2.44, 2.15, 2.07, 2.04, 2.03, 2.01, 1.99, 1.98, 1.98, 1.96,
+    1.94, 1.92, 1.92, 1.89, 1.90, 1.87, 1.87, 1.75, 1.70, 1.62, 1.51, 1.44, 1.41, 1.36, 1.36, 1.32,
+    1.45, 1.46, 1.48, 1.40, 1.50, 1.50, 2.60, 2.21, 2.15, 2.06, 2.00, 1.96,
+];
+
+/// Van der Waals radii in Å (Bondi, 1964; Mantina et al., 2009), indexed
+/// by `Z - 1`; `0.0` marks elements without a tabulated value.
+const VDW_RADII: [f64; 92] = [
+    1.20, 1.40, 1.82, 1.53, 1.92, 1.70, 1.55, 1.52, 1.47, 1.54, 2.27, 1.73, 1.84, 2.10, 1.80, 1.80,
+    1.75, 1.88, 2.75, 2.31, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.63, 1.40, 1.39, 1.87, 2.11, 1.85,
+    1.90, 1.85, 2.02, 3.03, 2.49, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.63, 1.72, 1.58, 1.93, 2.17,
+    2.06, 2.06, 1.98, 2.16, 3.43, 2.68, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.75, 1.66, 1.55, 1.96, 2.02, 2.07, 1.97, 2.02,
+    2.20, 3.48, 2.83, 0.0, 0.0, 0.0, 1.86,
+];
+
+/// `table[Z - 1]` for the element `symbol`; zero entries read as missing.
+fn radius_lookup(table: &[f64; 92], symbol: &str) -> Option<f64> {
+    match symbol_to_atomic_number(symbol) {
+        0 => None,
+        z => table.get(z as usize - 1).copied().filter(|r| *r > 0.0),
+    }
+}
+
+/// Covalent radius (Å) for a chemical symbol, or `None` if unknown.
+/// Deuterium and tritium share hydrogen's radius.
+///
+/// # Example
+/// ```
+/// use readcon_core::helpers::covalent_radius;
+/// assert_eq!(covalent_radius("C"), Some(0.76));
+/// assert_eq!(covalent_radius("D"), Some(0.31));
+/// assert_eq!(covalent_radius("Gh"), None);
+/// ```
+pub fn covalent_radius(symbol: &str) -> Option<f64> {
+    radius_lookup(&COVALENT_RADII, symbol)
+}
+
+/// Van der Waals radius (Å) for a chemical symbol, or `None` when the
+/// element is unknown or has no tabulated value (most transition metals
+/// and lanthanides).
+pub fn vdw_radius(symbol: &str) -> Option<f64> {
+    radius_lookup(&VDW_RADII, symbol)
+}
+
+/// Element data for a custom label.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementInfo {
+    /// Atomic number; 0 for a site that is not an element (ghost atom,
+    /// virtual site).
+    pub atomic_number: u64,
+    /// Mass in amu; `None` falls back to the standard weight of
+    /// `atomic_number`.
+    pub mass: Option<f64>,
+}
+
+/// Symbol lookups that fail loudly, extendable with custom labels.
+///
+/// A new registry knows the built-in table (H..U plus D and T) and isotope
+/// labels ([`parse_isotope`]), which get the element's atomic number and
+/// the isotopic mass. Labels added with [`Self::register`] take precedence
+/// over both, so a registry can also override the mass of a real element
+/// (e.g. an isotope-labelled `"H"`). Lookups are case-sensitive like the
+/// rest of this module.
+///
+/// # Example
+/// ```
+/// use readcon_core::helpers::ElementRegistry;
+/// let mut reg = ElementRegistry::new();
+/// assert!(reg.atomic_number("Cu1").is_err());
+/// reg.register("Cu1", 29, None).unwrap();
+/// assert_eq!(reg.atomic_number("Cu1").unwrap(), 29);
+/// assert_eq!(reg.mass("Cu1").unwrap(), 63.546);
+/// reg.register("Gh", 0, Some(1.0)).unwrap();
+/// assert_eq!(reg.mass("Gh").unwrap(), 1.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementRegistry {
+    custom: HashMap<String, ElementInfo>,
+}
+
+impl ElementRegistry {
+    /// Registry holding only the built-in table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces a custom label.
+    ///
+    /// Errors are [`ParseError::ValidationError`] for an empty label or a
+    /// label with whitespace (it could not be written to a CON file), an
+    /// atomic number past 92, a mass that is not positive and finite, and
+    /// a non-element site (`atomic_number` 0) without a mass.
+    pub fn register(
+        &mut self,
+        label: &str,
+        atomic_number: u64,
+        mass: Option<f64>,
+    ) -> Result<&mut Self, ParseError> {
+        if label.is_empty() || label.chars().any(char::is_whitespace) {
+            return Err(ParseError::ValidationError(format!(
+                "element label {label:?} must be non-empty without whitespace"
+            )));
+        }
+        if atomic_number > 92 {
+            return Err(ParseError::ValidationError(format!(
+                "atomic number {atomic_number} for {label:?} is outside 0..=92"
+            )));
+        }
+        if let Some(m) = mass.filter(|m| !(m.is_finite() && *m > 0.0)) {
+            return Err(ParseError::ValidationError(format!(
+                "mass {m} for {label:?} must be positive and finite"
+            )));
+        }
+        if atomic_number == 0 && mass.is_none() {
+            return Err(ParseError::ValidationError(format!(
+                "non-element label {label:?} needs an explicit mass"
+            )));
+        }
+        self.custom.insert(
+            label.to_string(),
+            ElementInfo {
+                atomic_number,
+                mass,
+            },
+        );
+        Ok(self)
+    }
+
+    /// Removes a custom label; built-in symbols cannot be removed.
+    pub fn unregister(&mut self, label: &str) -> Option<ElementInfo> {
+        self.custom.remove(label)
+    }
+
+    /// Custom label data, if `label` was registered.
+    pub fn custom(&self, label: &str) -> Option<&ElementInfo> {
+        self.custom.get(label)
+    }
+
+    /// True when `symbol` is a registered label, a built-in symbol or an
+    /// isotope label.
+    pub fn contains(&self, symbol: &str) -> bool {
+        self.custom.contains_key(symbol)
+            || symbol_to_atomic_number(symbol) != 0
+            || parse_isotope(symbol).is_some()
+    }
+
+    /// Atomic number of `symbol` (0 for registered non-element sites).
+    ///
+    /// Errors are [`ParseError::ValidationError`] for an unknown symbol.
+    pub fn atomic_number(&self, symbol: &str) -> Result<u64, ParseError> {
+        if let Some(info) = self.custom.get(symbol) {
+            return Ok(info.atomic_number);
+        }
+        match symbol_to_atomic_number(symbol) {
+            0 => parse_isotope(symbol)
+                .map(|i| i.atomic_number)
+                .ok_or_else(|| unknown_species(symbol)),
+            z => Ok(z),
+        }
+    }
+
+    /// Mass of `symbol` in amu: the registered mass, else the standard
+    /// weight ([`atomic_mass`]) of the symbol or its atomic number, else
+    /// the [`isotope_mass`] of an isotope label.
+    ///
+    /// Errors are [`ParseError::ValidationError`] for an unknown symbol.
+    pub fn mass(&self, symbol: &str) -> Result<f64, ParseError> {
+        match self.custom.get(symbol) {
+            Some(ElementInfo { mass: Some(m), .. }) => Ok(*m),
+            Some(info) => atomic_mass(atomic_number_to_symbol(info.atomic_number))
+                .ok_or_else(|| unknown_species(symbol)),
+            None => atomic_mass(symbol)
+                .or_else(|| parse_isotope(symbol).map(isotope_mass))
+                .ok_or_else(|| unknown_species(symbol)),
+        }
+    }
+
+    /// Checks every symbol, naming the first unknown one in the error.
+    pub fn check_symbols<'a, I: IntoIterator<Item = &'a str>>(
+        &self,
+        symbols: I,
+    ) -> Result<(), ParseError> {
+        match symbols.into_iter().find(|s| !self.contains(s)) {
+            Some(s) => Err(unknown_species(s)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn unknown_species(symbol: &str) -> ParseError {
+    ParseError::ValidationError(format!(
+        "unknown species {symbol:?}; register it in an ElementRegistry"
+    ))
+}
+
 /// Returns the chemical symbol for an atomic number, or "X" if unknown.
 pub fn atomic_number_to_symbol(atomic_number: u64) -> &'static str {
     match atomic_number {
@@ -257,6 +624,69 @@ mod tests {
         assert_eq!(symbol_to_atomic_number("M"), 0);
     }
 
+    #[test]
+    fn masses_increase_roughly_with_z() {
+        assert_eq!(atomic_mass("H"), Some(1.008));
+        assert_eq!(atomic_mass("U"), Some(238.03));
+        assert_eq!(atomic_mass("Xx"), None);
+        for z in 2u64..=92 {
+            let (prev, cur) = (
+                atomic_mass(atomic_number_to_symbol(z - 1)).unwrap(),
+                atomic_mass(atomic_number_to_symbol(z)).unwrap(),
+            );
+            // Only a few well-known inversions (Ar/K, Co/Ni, Te/I, Th/Pa).
+            assert!(cur > prev - 1.1, "Z={z}: {prev} -> {cur}");
+        }
+    }
+
+    #[test]
+    fn radii_cover_known_elements() {
+        for z in 1u64..=92 {
+            let r = covalent_radius(atomic_number_to_symbol(z)).unwrap();
+            assert!((0.2..3.0).contains(&r), "Z={z}: {r}");
+        }
+        assert_eq!(vdw_radius("O"), Some(1.52));
+        assert_eq!(vdw_radius("U"), Some(1.86));
+        assert_eq!(vdw_radius("Fe"), None);
+        assert_eq!(vdw_radius("Xx"), None);
+        assert_eq!(covalent_radius("T"), covalent_radius("H"));
+    }
+
+    #[test]
+    fn registry_overrides_and_rejects() {
+        let mut reg = ElementRegistry::new();
+        assert_eq!(reg.atomic_number("Fe").unwrap(), 26);
+        assert!(reg.mass("X").is_err());
+        assert!(reg.check_symbols(["O", "H", "OW"]).is_err());
+        reg.register("OW", 8, Some(15.9994)).unwrap();
+        reg.register("H", 1, Some(2.0141)).unwrap();
+        assert!(reg.check_symbols(["O", "H", "OW"]).is_ok());
+        assert_eq!(reg.mass("OW").unwrap(), 15.9994);
+        assert_eq!(reg.mass("H").unwrap(), 2.0141);
+        assert!(reg.register("Gh", 0, None).is_err());
+        assert!(reg.register("a b", 1, None).is_err());
+        assert!(reg.register("Q", 93, None).is_err());
+        assert!(reg.register("Q", 1, Some(-1.0)).is_err());
+        assert!(reg.unregister("OW").is_some());
+        assert!(!reg.contains("OW"));
+    }
+
+    #[test]
+    fn registry_resolves_isotope_labels() {
+        let mut reg = ElementRegistry::new();
+        assert_eq!(reg.atomic_number("Cu65").unwrap(), 29);
+        assert_eq!(reg.mass("Cu65").unwrap(), 64.9277897);
+        assert_eq!(reg.mass("H2").unwrap(), 2.01410178);
+        assert_eq!(reg.mass("D").unwrap(), 2.0141);
+        assert!(reg.check_symbols(["H3", "U238", "Pt195"]).is_ok());
+        for label in ["C1", "O2", "Cu05", "D2", "Cu999", "X12"] {
+            assert!(!reg.contains(label), "{label}");
+        }
+        // Registered labels win over the isotope reading.
+        reg.register("O17", 8, None).unwrap();
+        assert_eq!(reg.mass("O17").unwrap(), 15.999);
+    }
+
     #[test]
     fn unknown_z_returns_x() {
         assert_eq!(atomic_number_to_symbol(0), "X");
@@ -57,7 +57,7 @@ pub fn species_counts_from_symbols(symbols: impl Iterator<Item = impl AsRef<str>
 
 /// Species multiset from a frame's `atom_data` symbols.
 pub fn frame_species_counts(frame: &ConFrame) -> Vec<(String, u32)> {
-    species_counts_from_symbols(frame.atom_data.iter().map(|a| a.symbol.as_ref()))
+    species_counts_from_symbols(frame.atom_data.iter().map(|a| frame.symbol_of(a)))
 }
 
 /// Canonical formula string for `frame` (empty string if no non-empty symbols).
@@ -114,41 +114,9 @@ pub fn frame_total_mass(frame: &ConFrame) -> Option<f64> {
     m.is_finite().then_some(m)
 }
 
-fn scalar_triple(a: [f64; 3], b: [f64; 3], c: [f64; 3]) -> f64 {
-    a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
-        + a[2] * (b[0] * c[1] - b[1] * c[0])
-}
-
 /// Cell volume: prefer lattice determinant; else triclinic from `boxl` + `angles` (degrees).
 pub fn frame_cell_volume(frame: &ConFrame) -> Option<f64> {
-    if let Some(lv) = frame.header.lattice_vectors() {
-        let det = scalar_triple(lv[0], lv[1], lv[2]).abs();
-        return det.is_finite().then_some(det);
-    }
-    let [a, b, c] = frame.header.boxl;
-    let [alpha, beta, gamma] = frame.header.angles;
-    if ![a, b, c, alpha, beta, gamma]
-        .iter()
-        .all(|x| x.is_finite() && *x > 0.0)
-    {
-        return None;
-    }
-    let ar = alpha.to_radians();
-    let br = beta.to_radians();
-    let gr = gamma.to_radians();
-    let ca = ar.cos();
-    let cb = br.cos();
-    let cg = gr.cos();
-    let sg = gr.sin();
-    if sg.abs() < 1e-15 {
-        return None;
-    }
-    let t = 1.0 - ca * ca - cb * cb - cg * cg + 2.0 * ca * cb * cg;
-    if t <= 0.0 {
-        return None;
-    }
-    let v = a * b * c * t.sqrt();
-    v.is_finite().then_some(v)
+    frame.header.cell().map(|c| c.volume())
 }
 
 /// True if forces are declared or any atom carries force data.
@@ -368,10 +336,11 @@ pub fn symbol_histogram(file_contents: &str) -> Result<BTreeMap<String, u32>, cr
     for item in crate::iterators::ConFrameIterator::new(file_contents) {
         let frame = item?;
         for a in &frame.atom_data {
-            if a.symbol.is_empty() {
+            let symbol = frame.symbol_of(a);
+            if symbol.is_empty() {
                 continue;
             }
-            *hist.entry(a.symbol.to_string()).or_insert(0u32) += 1;
+            *hist.entry(symbol.to_string()).or_insert(0u32) += 1;
         }
     }
     Ok(hist)
@@ -400,15 +369,10 @@ pub fn spans_cover_buffer(file_contents: &str) -> Result<bool, crate::error::Par
 mod tests {
     use super::*;
     use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
     use crate::writer::ConFrameWriter;
     use std::io::Cursor;
 
-    fn fixture_text() -> String {
-        let p = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("resources/test/tiny_cuh2.con");
-        std::fs::read_to_string(p).unwrap()
-    }
-
     #[test]
     fn formula_canonical_order() {
         let f1 = composition_formula(&[("H".into(), 2), ("Cu".into(), 2)]);
@@ -419,7 +383,7 @@ mod tests {
 
     #[test]
     fn projection_from_fixture() {
-        let text = fixture_text();
+        let text = fixture("tiny_cuh2.con");
         let fr = ConFrameIterator::new(&text).next().unwrap().unwrap();
         let p = FrameIndexProjection::from_frame(&fr);
         assert!(p.n_atoms >= 1);
@@ -434,7 +398,7 @@ mod tests {
 
     #[test]
     fn finite_energy_rejects_nan() {
-        let text = fixture_text();
+        let text = fixture("tiny_cuh2.con");
         let mut fr = ConFrameIterator::new(&text).next().unwrap().unwrap();
         fr.header
             .metadata
@@ -463,7 +427,7 @@ mod tests {
 
     #[test]
     fn span_preserving_concat() {
-        let text = fixture_text();
+        let text = fixture("tiny_cuh2.con");
         let spans = frame_byte_spans(&text).unwrap();
         assert!(!spans.is_empty());
         let mut acc = String::new();
@@ -479,14 +443,14 @@ mod tests {
 
     #[test]
     fn symbol_histogram_nonempty() {
-        let h = symbol_histogram(&fixture_text()).unwrap();
+        let h = symbol_histogram(&fixture("tiny_cuh2.con")).unwrap();
         assert!(!h.is_empty());
     }
 
     /// Projection contract vs shipped frame parse (regression if index_proj drifts from CON).
     #[test]
     fn projection_matches_iterator_frame_fields() {
-        let text = fixture_text();
+        let text = fixture("tiny_cuh2.con");
         let fr = ConFrameIterator::new(&text).next().unwrap().unwrap();
         let p = FrameIndexProjection::from_frame(&fr);
         assert_eq!(p.n_atoms as usize, fr.atom_data.len());
@@ -517,7 +481,7 @@ mod tests {
 
     #[test]
     fn canonical_writer_deterministic() {
-        let text = fixture_text();
+        let text = fixture("tiny_cuh2.con");
         let fr = ConFrameIterator::new(&text).next().unwrap().unwrap();
         let mut a = Cursor::new(Vec::new());
         let mut b = Cursor::new(Vec::new());
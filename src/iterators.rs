@@ -2,7 +2,11 @@
 // The Public API - A clean iterator for users of our library
 //=============================================================================
 
-use crate::parser::{parse_declared_sections, parse_single_frame, LineStream};
+use crate::parser::{
+    parse_declared_sections, parse_single_frame_into, skip_ignorable_lines, warn, FrameBuffers,
+    LineStream, ParseMode, ParserOptions,
+};
+use crate::logging::{log_debug, log_span, log_warn};
 use crate::{error, types};
 use std::path::Path;
 
@@ -12,6 +16,7 @@ use std::path::Path;
 /// `ConFrameIterator::next` on multi-atom multi-frame workloads. One cursor
 /// serves `next` / `peek` / `forward_fast` so skip and full parse share the
 /// same O(1) newline scan rather than two desynchronized views of the buffer.
+#[derive(Clone)]
 pub struct MemchrLines<'a> {
     bytes: &'a [u8],
     pos: usize,
@@ -89,6 +94,40 @@ impl<'a> LineStream<'a> for MemchrLines<'a> {
     fn peek_line(&mut self) -> Option<&'a str> {
         MemchrLines::peek_line(self)
     }
+    fn peek_nth(&mut self, n: usize) -> Option<&'a str> {
+        let mut probe = MemchrLines {
+            bytes: self.bytes,
+            pos: self.pos,
+            peeked: self.peeked,
+        };
+        for _ in 0..n {
+            probe.next_line()?;
+        }
+        probe.next_line()
+    }
+}
+
+/// Header-level view of one frame from [`ConFrameIterator::next_outline`].
+#[derive(Debug, Clone)]
+pub struct FrameOutline {
+    /// The fully parsed frame header.
+    pub header: types::FrameHeader,
+    /// Symbol of each atom type, in file order.
+    pub symbols: Vec<String>,
+    /// Number of atoms per type with at least one fixed direction.
+    pub nfixed_per_type: Vec<usize>,
+}
+
+impl FrameOutline {
+    /// Total number of atoms in the frame.
+    pub fn natoms(&self) -> usize {
+        self.header.natms_per_type.iter().sum()
+    }
+
+    /// Total number of atoms with at least one fixed direction.
+    pub fn nfixed(&self) -> usize {
+        self.nfixed_per_type.iter().sum()
+    }
 }
 
 /// An iterator that lazily parses simulation frames from a `.con` or `.convel`
@@ -101,8 +140,29 @@ impl<'a> LineStream<'a> for MemchrLines<'a> {
 ///
 /// The iterator yields items of type `Result<ConFrame, ParseError>`, allowing for
 /// robust error handling for each frame.
+///
+/// By default a malformed frame leaves the cursor wherever the parser gave
+/// up, so later items are usually spurious errors. Enable
+/// [`Self::recover`] to resynchronize on the next plausible frame header
+/// after each error instead.
+///
+/// Files with stray blank lines or `#` comments between frames can be read
+/// with [`Self::options`] and [`ParserOptions::tolerant`].
+///
+/// Cloning is cheap (the text is borrowed) and gives an independent cursor,
+/// e.g. to count the frames left without moving this one.
+#[derive(Clone)]
 pub struct ConFrameIterator<'a> {
     pub(crate) lines: MemchrLines<'a>,
+    /// Scan forward to the next plausible header after a parse error.
+    recover: bool,
+    /// Between-frame leniency; strict by default.
+    options: ParserOptions,
+    /// What [`ParseMode::Permissive`] let through, by frame start offset.
+    warnings: Vec<(usize, error::ParseWarning)>,
+    /// Byte offset and 1-based line number of the last frame started, so
+    /// [`Self::frame_line`] counts newlines only since the previous frame.
+    frame_mark: (usize, usize),
 }
 
 impl<'a> ConFrameIterator<'a> {
@@ -114,9 +174,239 @@ impl<'a> ConFrameIterator<'a> {
     pub fn new(file_contents: &'a str) -> Self {
         ConFrameIterator {
             lines: MemchrLines::new(file_contents),
+            recover: false,
+            options: ParserOptions::default(),
+            warnings: Vec::new(),
+            frame_mark: (0, 1),
         }
     }
 
+    /// Parse with the given [`ParserOptions`] (e.g. skip blank lines and
+    /// comments between frames).
+    pub fn options(mut self, options: ParserOptions) -> Self {
+        self.set_options(options);
+        self
+    }
+
+    /// Replace the parser options on an existing iterator.
+    pub fn set_options(&mut self, options: ParserOptions) {
+        self.options = options;
+    }
+
+    /// The parser options in effect.
+    pub fn parser_options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// Parse in `mode`, keeping the other options. With
+    /// [`ParseMode::Permissive`], input the strict grammar rejects is read
+    /// where it can be and reported through [`Self::warnings`]:
+    ///
+    /// ```
+    /// use readcon_core::iterators::ConFrameIterator;
+    /// use readcon_core::parser::ParseMode;
+    ///
+    /// let text = "Generated\n{\"con_spec_version\":2}\n10 10 10\n90 90 90\n0 0\n0 0\n\
+    ///             1\n1\n1.008\nH\n0.0 0.0 0.0 0 0\n";
+    /// assert!(ConFrameIterator::new(text).next().unwrap().is_err());
+    ///
+    /// let mut iter = ConFrameIterator::new(text).with_mode(ParseMode::Permissive);
+    /// assert_eq!(iter.next().unwrap().unwrap().atom_data.len(), 1);
+    /// assert_eq!(iter.warnings().len(), 1);
+    /// ```
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+
+    /// Warnings recorded so far under [`ParseMode::Permissive`], each with
+    /// the byte offset of the frame (or trailing text) it concerns.
+    pub fn warnings(&self) -> &[(usize, error::ParseWarning)] {
+        &self.warnings
+    }
+
+    /// Returns the recorded warnings and clears them.
+    pub fn take_warnings(&mut self) -> Vec<(usize, error::ParseWarning)> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Whether a blank separator at the cursor opens a section block
+    /// (`symbol`, then an `"... of Component N"` label) rather than being a
+    /// stray blank line between frames, or the cursor is at the velocity
+    /// block of a single-component eOn dynamics frame. Only consulted in
+    /// tolerant and permissive modes.
+    fn section_block_follows(&mut self) -> bool {
+        let blank_then_label = self
+            .lines
+            .peek_nth(0)
+            .is_some_and(|line| line.trim().is_empty())
+            && self
+                .lines
+                .peek_nth(2)
+                .is_some_and(|line| line.contains("of Component"));
+        blank_then_label
+            || self
+                .lines
+                .peek_nth(1)
+                .is_some_and(|line| crate::parser::is_velocity_label(line, 0))
+    }
+
+    /// Skips the velocity block that follows component `type_idx` in the
+    /// eOn dynamics layout, if there is one.
+    fn skip_interleaved_velocities(
+        &mut self,
+        type_idx: usize,
+        natoms: usize,
+    ) -> Result<(), error::ParseError> {
+        if self
+            .lines
+            .peek_nth(1)
+            .is_some_and(|line| crate::parser::is_velocity_label(line, type_idx))
+        {
+            self.advance_lines(natoms.saturating_add(2))?;
+        }
+        Ok(())
+    }
+
+    /// Opt-in **error recovery**: after a frame fails to parse, the error is
+    /// still yielded, but the cursor is moved to the next line that starts a
+    /// plausible 9-line header (see [`Self::resync`]) so one corrupt frame
+    /// does not poison the rest of a long trajectory.
+    pub fn recover(mut self, on: bool) -> Self {
+        self.set_recover(on);
+        self
+    }
+
+    /// Set or clear recovery mode on an existing iterator (C ABI / FFI).
+    pub fn set_recover(&mut self, on: bool) {
+        self.recover = on;
+    }
+
+    /// Whether recovery mode is enabled.
+    pub fn is_recovering(&self) -> bool {
+        self.recover
+    }
+
+    /// Moves the cursor back to the start of the buffer and clears the
+    /// warnings; options and recovery mode are kept.
+    pub fn reset(&mut self) {
+        self.lines.clear_peek();
+        self.lines.pos = 0;
+        self.warnings.clear();
+        self.frame_mark = (0, 1);
+    }
+
+    /// 1-based line of the buffer on which the last frame read or skipped
+    /// began, after any blank lines or comments skipped before it; 1 before
+    /// the first frame. Use it to place an error from [`Iterator::next`] or
+    /// [`Self::forward`] in a multi-frame file.
+    pub fn frame_line(&self) -> usize {
+        self.frame_mark.1
+    }
+
+    /// Moves the [`Self::frame_line`] mark to byte offset `start`.
+    fn mark_frame_start(&mut self, start: usize) {
+        let (from, line) = if start >= self.frame_mark.0 {
+            self.frame_mark
+        } else {
+            (0, 1)
+        };
+        let newlines = memchr::memchr_iter(b'\n', &self.lines.bytes[from..start]).count();
+        self.frame_mark = (start, line + newlines);
+    }
+
+    /// Byte offset into the buffer passed to [`Self::new`] where the next
+    /// item starts reading (after a peeked line is returned to the stream).
+    pub fn offset(&self) -> usize {
+        match self.lines.peeked {
+            Some(line) => line.as_ptr() as usize - self.lines.bytes.as_ptr() as usize,
+            None => self.lines.pos,
+        }
+    }
+
+    /// Moves the cursor to the next line, strictly after byte offset `from`,
+    /// that begins a plausible frame header: lines 3-4 hold three floats
+    /// each, line 7 a positive type count `n`, line 8 `n` atom counts and
+    /// line 9 `n` masses. Returns `false` (cursor at EOF) if none is found.
+    ///
+    /// Used by recovery mode; callable directly to skip damaged regions.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn resync(&mut self, from: usize) -> bool {
+        self.lines.clear_peek();
+        let bytes = self.lines.bytes;
+        match next_plausible_header(bytes, from) {
+            Some(start) => {
+                log_debug!(byte = start, "recovery: resynchronized");
+                self.lines.pos = start;
+                true
+            }
+            None => {
+                log_debug!("recovery: no frame header left");
+                self.lines.pos = bytes.len();
+                false
+            }
+        }
+    }
+
+    /// [`ParseMode::Permissive`]: skips whitespace-only lines at the cursor
+    /// with a warning, stopping at one that is the (empty) comment line of
+    /// a plausible header.
+    fn skip_blank_lines(&mut self) {
+        if self.options.mode != ParseMode::Permissive {
+            return;
+        }
+        let start = self.offset();
+        let mut lines = 0;
+        while self
+            .lines
+            .peek_line()
+            .is_some_and(|line| line.trim().is_empty())
+            && !plausible_header_at(self.lines.bytes, self.offset())
+        {
+            self.lines.next_line();
+            lines += 1;
+        }
+        if lines > 0 {
+            let warning = error::ParseWarning::BlankLines { lines };
+            log_warn!(byte = start, "{warning}");
+            self.warnings.push((start, warning));
+        }
+    }
+
+    /// Lines from the cursor up to the next plausible header or the end of
+    /// the input, for [`types::ConFrame::trailing_lines`].
+    fn take_trailing_lines(&mut self) -> Vec<String> {
+        let mut lines = Vec::new();
+        while let Some(line) = self.lines.peek_line() {
+            if plausible_header_at(self.lines.bytes, self.offset()) {
+                break;
+            }
+            lines.push(line.to_string());
+            self.lines.next_line();
+        }
+        lines
+    }
+
+    /// [`ParseMode::Permissive`] handling of a failed item at byte `start`:
+    /// when no plausible header starts there or later, the rest of the
+    /// input is trailing garbage, skipped with a warning. Returns whether
+    /// it was.
+    fn skip_trailing_garbage(&mut self, start: usize) -> bool {
+        let bytes = self.lines.bytes;
+        if self.options.mode != ParseMode::Permissive
+            || plausible_header_at(bytes, start)
+            || next_plausible_header(bytes, start).is_some()
+        {
+            return false;
+        }
+        let warning = error::ParseWarning::TrailingGarbage { offset: start };
+        log_warn!(byte = start, "{warning}");
+        self.warnings.push((start, warning));
+        self.lines.clear_peek();
+        self.lines.pos = bytes.len();
+        true
+    }
+
     /// Bulk-skips `n` lines from the shared memchr cursor.
     fn advance_lines(&mut self, n: usize) -> Result<(), error::ParseError> {
         self.lines.clear_peek();
@@ -145,8 +435,27 @@ impl<'a> ConFrameIterator<'a> {
     /// memchr-backed equivalent of [`Self::forward`]. Skips the next
     /// frame without fully parsing its atom data. Shares the same line
     /// cursor as [`Iterator::next`], so skip and full parse interleave safely.
+    ///
+    /// Of the [`ParseMode::Permissive`] allowances only trailing text applies
+    /// here: lines are counted, not read, so a missing component label
+    /// shifts every later line.
     pub fn forward_fast(&mut self) -> Option<Result<(), error::ParseError>> {
+        skip_ignorable_lines(&mut self.lines, &self.options);
+        self.skip_blank_lines();
         self.lines.clear_peek();
+        let start = self.lines.pos;
+        self.mark_frame_start(start);
+        log_span!(TRACE, "frame", byte = start, line = self.frame_line());
+        let result = self.skip_frame()?;
+        if result.is_err() && self.skip_trailing_garbage(start) {
+            return None;
+        }
+        Some(result)
+    }
+
+    /// [`Self::forward_fast`] from the cursor, without the trailing-garbage
+    /// check.
+    fn skip_frame(&mut self) -> Option<Result<(), error::ParseError>> {
         if self.lines.pos >= self.lines.bytes.len() {
             return None;
         }
@@ -156,8 +465,8 @@ impl<'a> ConFrameIterator<'a> {
         }
         // Line 7: natm_types.
         let natm_types: usize = match self.read_line_str() {
-            Some(line) => match crate::parser::parse_line_of_n::<usize>(line, 1) {
-                Ok(v) => v[0],
+            Some(line) => match crate::parser::parse_fixed::<usize, 1>(line) {
+                Ok([n]) => n,
                 Err(e) => return Some(Err(e)),
             },
             None => return Some(Err(error::ParseError::IncompleteHeader)),
@@ -174,12 +483,29 @@ impl<'a> ConFrameIterator<'a> {
         if let Err(e) = self.advance_lines(1) {
             return Some(Err(e));
         }
-        let total_atoms: usize = natms_per_type.iter().sum();
-        let coord_block_lines = total_atoms + natm_types * 2;
-        if let Err(e) = self.advance_lines(coord_block_lines) {
+        let coord_block_lines = match crate::parser::total_atom_count(&natms_per_type)
+            .map(|total| total.saturating_add(natm_types.saturating_mul(2)))
+        {
+            Ok(n) => n,
+            Err(e) => return Some(Err(e)),
+        };
+        for (type_idx, &natoms) in natms_per_type.iter().enumerate() {
+            if let Err(e) = self
+                .advance_lines(natoms.saturating_add(2))
+                .and_then(|()| self.skip_interleaved_velocities(type_idx, natoms))
+            {
+                return Some(Err(e));
+            }
+        }
+        if let Err(e) = self.skip_sections(coord_block_lines) {
             return Some(Err(e));
         }
-        // Optional sections: blank line + same-shape block, repeated.
+        Some(Ok(()))
+    }
+
+    /// Skips the optional section blocks (blank line + same-shape block,
+    /// repeated) that follow a coordinate block of `coord_block_lines` lines.
+    fn skip_sections(&mut self, coord_block_lines: usize) -> Result<(), error::ParseError> {
         self.lines.clear_peek();
         loop {
             let rest = &self.lines.bytes[self.lines.pos..];
@@ -192,16 +518,79 @@ impl<'a> ConFrameIterator<'a> {
                 None => rest,
             };
             let is_blank = line.iter().all(|b| matches!(b, b' ' | b'\t' | b'\r'));
-            if !is_blank {
+            // Same rule as `parse_declared_sections`: a visible line without
+            // a component label means the blank line opens the next frame.
+            let labelled = match self.lines.peek_nth(2) {
+                Some(label) => label.contains("of Component"),
+                None => self.options.is_strict(),
+            };
+            if !is_blank || !labelled {
                 break;
             }
             // Consume the blank separator and the section block.
             self.lines.pos += next_eol.map(|p| p + 1).unwrap_or(rest.len());
-            if let Err(e) = self.advance_lines(coord_block_lines) {
-                return Some(Err(e));
+            self.advance_lines(coord_block_lines)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the next frame's header and coordinate-block layout without
+    /// parsing any atom columns other than the fixed flag.
+    ///
+    /// Per type this keeps the symbol line and counts atoms whose column-4
+    /// bitmask fixes any direction; coordinates and section blocks are
+    /// skipped with the same memchr cursor as [`Self::forward_fast`]. Much
+    /// cheaper than [`Iterator::next`] for summaries of large trajectories.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Ok(outline))` on success.
+    /// * `Some(Err(ParseError::...))` if the header or layout is malformed.
+    /// * `None` if the iterator is already at the end.
+    pub fn next_outline(&mut self) -> Option<Result<FrameOutline, error::ParseError>> {
+        skip_ignorable_lines(&mut self.lines, &self.options);
+        self.lines.clear_peek();
+        if self.lines.pos >= self.lines.bytes.len() {
+            return None;
+        }
+        self.mark_frame_start(self.lines.pos);
+        Some(self.read_outline())
+    }
+
+    fn read_outline(&mut self) -> Result<FrameOutline, error::ParseError> {
+        let header = crate::parser::parse_frame_header(&mut self.lines)?;
+        let mut symbols = Vec::with_capacity(header.natm_types);
+        let mut nfixed_per_type = Vec::with_capacity(header.natm_types);
+        for (type_idx, &natoms) in header.natms_per_type.iter().enumerate() {
+            let symbol = self.lines.next_line().ok_or(error::ParseError::IncompleteFrame)?;
+            symbols.push(symbol.trim().to_string());
+            self.lines.next_line().ok_or(error::ParseError::IncompleteFrame)?;
+            let mut nfixed = 0;
+            for _ in 0..natoms {
+                let line = self.lines.next_line().ok_or(error::ParseError::IncompleteFrame)?;
+                let flag = line
+                    .split_whitespace()
+                    .nth(3)
+                    .and_then(|tok| tok.parse::<f64>().ok())
+                    .ok_or(error::ParseError::InvalidVectorLength {
+                        expected: 4,
+                        found: line.split_whitespace().count(),
+                    })?;
+                let flag = crate::parser::fixed_flag_from(flag, line, 3)?;
+                if types::decode_fixed_bitmask(flag).iter().any(|&f| f) {
+                    nfixed += 1;
+                }
             }
+            nfixed_per_type.push(nfixed);
+            self.skip_interleaved_velocities(type_idx, natoms)?;
         }
-        Some(Ok(()))
+        let total_atoms: usize = header.natms_per_type.iter().sum();
+        self.skip_sections(total_atoms + header.natm_types * 2)?;
+        Ok(FrameOutline {
+            header,
+            symbols,
+            nfixed_per_type,
+        })
     }
 
     /// Skips the next frame without fully parsing its atomic data.
@@ -220,6 +609,79 @@ impl<'a> ConFrameIterator<'a> {
         self.forward_fast()
     }
 
+    /// Parses the next frame into `frame`, reusing its atom and position
+    /// allocations instead of allocating fresh ones.
+    ///
+    /// For bulk passes that look at one frame at a time, calling this in a
+    /// loop with the same `ConFrame` skips reallocating the per-atom records
+    /// and the position array once they have grown to the largest frame:
+    ///
+    /// ```
+    /// use readcon_core::iterators::ConFrameIterator;
+    ///
+    /// let text = std::fs::read_to_string("resources/test/tiny_multi_cuh2.con").unwrap();
+    /// let mut iter = ConFrameIterator::new(&text);
+    /// let mut frame = iter.next().unwrap().unwrap();
+    /// let mut atoms = frame.atom_data.len();
+    /// while let Some(result) = iter.next_into(&mut frame) {
+    ///     result.unwrap();
+    ///     atoms += frame.atom_data.len();
+    /// }
+    /// assert!(atoms > 0);
+    /// ```
+    ///
+    /// Returns the same `Some`/`None` sequence as [`Iterator::next`]. The
+    /// contents of `frame` are unspecified unless this returns `Some(Ok(()))`.
+    /// Position storage is only recycled when `frame` holds the last
+    /// reference to it, so a clone kept elsewhere is never overwritten.
+    pub fn next_into(
+        &mut self,
+        frame: &mut types::ConFrame,
+    ) -> Option<Result<(), error::ParseError>> {
+        let buffers = FrameBuffers::reclaim(frame);
+        Some(self.next_with(buffers)?.map(|parsed| *frame = parsed))
+    }
+
+    /// Next frame in single precision; `None` at the end of the input, like
+    /// [`Iterator::next`].
+    ///
+    /// Coordinates-only frames read under [`ParseMode::Strict`] are parsed
+    /// straight into the f32 arrays of [`ConFrameF32`], without building
+    /// the f64 [`types::ConFrame`]; others are parsed in full and narrowed
+    /// (see [`crate::frame_f32`]). Errors, recovery and warnings are those
+    /// of [`Iterator::next`].
+    ///
+    /// ```
+    /// use readcon_core::iterators::ConFrameIterator;
+    ///
+    /// let text = std::fs::read_to_string("resources/test/tiny_multi_cuh2.con").unwrap();
+    /// let mut iter = ConFrameIterator::new(&text);
+    /// let mut frames = Vec::new();
+    /// while let Some(frame) = iter.next_f32() {
+    ///     frames.push(frame.unwrap());
+    /// }
+    /// assert!(frames.iter().all(|f| f.positions.len() == f.atom_ids.len()));
+    /// ```
+    ///
+    /// [`ConFrameF32`]: crate::frame_f32::ConFrameF32
+    pub fn next_f32(&mut self) -> Option<Result<crate::frame_f32::ConFrameF32, error::ParseError>> {
+        skip_ignorable_lines(&mut self.lines, &self.options);
+        self.skip_blank_lines();
+        let first = self.lines.peek_line()?;
+        let start = first.as_ptr() as usize - self.lines.bytes.as_ptr() as usize;
+        if self.options.mode == ParseMode::Strict && !self.options.validate {
+            self.mark_frame_start(start);
+            log_span!(TRACE, "frame", byte = start, line = self.frame_line());
+            if let Ok(Some(frame)) = crate::frame_f32::parse_plain_frame(&mut self.lines) {
+                return Some(Ok(frame));
+            }
+            self.lines.clear_peek();
+            self.lines.pos = start;
+        }
+        self.next_with(FrameBuffers::default())
+            .map(|result| result.map(|frame| (&frame).into()))
+    }
+
     /// Next frame plus the exact substring of the buffer passed to [`Self::new`].
     ///
     /// **Corpus ingest contract:** successive successful spans from the same
@@ -267,19 +729,219 @@ impl<'a> Iterator for ConFrameIterator<'a> {
     /// If there are lines but they do not form a complete frame, it will return
     /// `Some(Err(ParseError::...))`.
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_with(FrameBuffers::default())
+    }
+}
+
+impl<'a> ConFrameIterator<'a> {
+    fn next_with(
+        &mut self,
+        buffers: FrameBuffers,
+    ) -> Option<Result<types::ConFrame, error::ParseError>> {
+        skip_ignorable_lines(&mut self.lines, &self.options);
+        self.skip_blank_lines();
         // If there are no more lines at all, the iterator is exhausted.
-        self.lines.peek_line()?;
+        let first = self.lines.peek_line()?;
+        let start = first.as_ptr() as usize - self.lines.bytes.as_ptr() as usize;
+        self.mark_frame_start(start);
+        log_span!(TRACE, "frame", byte = start, line = self.frame_line());
+        let result = self.parse_next(buffers)?;
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(frame) => tracing::trace!(
+                atoms = frame.atom_data.len(),
+                next = self.offset(),
+                "parsed"
+            ),
+            Err(e) => tracing::debug!(error = %e, "frame failed to parse"),
+        }
+        if result.is_err() && self.skip_trailing_garbage(start) {
+            return None;
+        }
+        if self.recover && result.is_err() {
+            self.resync(start);
+        }
+        Some(result)
+    }
+}
+
+/// How far a [`WithProgress`] iterator has read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Bytes of the input consumed so far.
+    pub bytes: usize,
+    /// Length of the whole input in bytes.
+    pub total_bytes: usize,
+    /// Items (frames and errors) yielded so far.
+    pub frames: usize,
+}
+
+impl Progress {
+    /// `bytes / total_bytes` in `[0, 1]`; 1 for an empty input.
+    pub fn fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.bytes as f64 / self.total_bytes as f64
+        }
+    }
+}
+
+/// [`ConFrameIterator`] that reports [`Progress`] to a callback after every
+/// item and once more, with `bytes == total_bytes`, when it is exhausted.
+/// Built by [`ConFrameIterator::with_progress`].
+pub struct WithProgress<'a, F: FnMut(Progress)> {
+    inner: ConFrameIterator<'a>,
+    callback: F,
+    frames: usize,
+    done: bool,
+}
+
+impl<'a> ConFrameIterator<'a> {
+    /// Reports [`Progress`] to `callback` as frames are parsed, for progress
+    /// bars on large files. The byte counts map directly onto an
+    /// `indicatif::ProgressBar` created with the input length:
+    ///
+    /// ```ignore
+    /// let bar = indicatif::ProgressBar::new(text.len() as u64);
+    /// for frame in ConFrameIterator::new(&text).with_progress(|p| bar.set_position(p.bytes as u64)) {
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::iterators::ConFrameIterator;
+    /// let text = std::fs::read_to_string(concat!(
+    ///     env!("CARGO_MANIFEST_DIR"),
+    ///     "/resources/test/tiny_multi_cuh2.con"
+    /// ))
+    /// .unwrap();
+    /// let mut reports = Vec::new();
+    /// let n = ConFrameIterator::new(&text)
+    ///     .with_progress(|p| reports.push(p))
+    ///     .count();
+    /// assert_eq!(n, 2);
+    /// let last = reports.last().unwrap();
+    /// assert_eq!((last.frames, last.fraction()), (2, 1.0));
+    /// ```
+    pub fn with_progress<F: FnMut(Progress)>(self, callback: F) -> WithProgress<'a, F> {
+        WithProgress {
+            inner: self,
+            callback,
+            frames: 0,
+            done: false,
+        }
+    }
+}
+
+impl<F: FnMut(Progress)> Iterator for WithProgress<'_, F> {
+    type Item = Result<types::ConFrame, error::ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let total_bytes = self.inner.lines.bytes.len();
+        let item = self.inner.next();
+        let bytes = match item {
+            Some(_) => {
+                self.frames += 1;
+                self.inner.offset()
+            }
+            None if self.done => return None,
+            None => {
+                self.done = true;
+                total_bytes
+            }
+        };
+        (self.callback)(Progress {
+            bytes,
+            total_bytes,
+            frames: self.frames,
+        });
+        item
+    }
+}
+
+impl<'a> ConFrameIterator<'a> {
+    fn parse_next(
+        &mut self,
+        buffers: FrameBuffers,
+    ) -> Option<Result<types::ConFrame, error::ParseError>> {
+        let start = self.lines.peek_line()?.as_ptr() as usize - self.lines.bytes.as_ptr() as usize;
+        match self.parse_frame(buffers)? {
+            Err(e @ error::ParseError::InvalidNumberFormat(_))
+                if !self.options.numbers.is_identity() =>
+            {
+                Some(self.reparse_normalized(start, e))
+            }
+            other => Some(other),
+        }
+    }
+
+    /// Fallback for [`ParserOptions::numbers`]: re-reads the frame starting
+    /// at byte `start` from a normalized copy of its text. Strict files never
+    /// reach this path, so the hot parse stays allocation-free.
+    fn reparse_normalized(
+        &mut self,
+        start: usize,
+        original: error::ParseError,
+    ) -> Result<types::ConFrame, error::ParseError> {
+        log_debug!(error = %original, "reparsing with numeric normalization");
+        self.lines.clear_peek();
+        self.lines.pos = start;
+        if !matches!(self.skip_frame(), Some(Ok(()))) {
+            return Err(original);
+        }
+        self.lines.clear_peek();
+        let end = self.lines.pos;
+        let Ok(chunk) = std::str::from_utf8(&self.lines.bytes[start..end]) else {
+            return Err(original);
+        };
+        let text = normalize_frame_text(chunk, &self.options.numbers);
+        let options = ParserOptions {
+            numbers: Default::default(),
+            ..self.options.clone()
+        };
+        let mut inner = ConFrameIterator::new(&text).options(options);
+        let result = inner.next().unwrap_or(Err(original));
+        self.warnings
+            .extend(inner.warnings.into_iter().map(|(_, w)| (start, w)));
+        result
+    }
+
+    fn parse_frame(
+        &mut self,
+        buffers: FrameBuffers,
+    ) -> Option<Result<types::ConFrame, error::ParseError>> {
         // Otherwise, attempt to parse the next frame from the available lines.
-        let mut frame = match parse_single_frame(&mut self.lines) {
+        let start = self.offset();
+        let mut warnings = Vec::new();
+        let parsed =
+            parse_single_frame_into(&mut self.lines, &self.options, buffers, &mut warnings);
+        let mut frame = match parsed {
             Ok(f) => f,
             Err(e) => return Some(Err(e)),
         };
+        // Tolerant mode: a stray blank line after a legacy frame must not be
+        // taken for the convel velocity separator. Permissive mode keeps
+        // such lines (and any others before the next header) instead.
+        let permissive = self.options.mode == ParseMode::Permissive;
+        let mut trailing_lines = Vec::new();
+        if (permissive || !self.options.is_strict())
+            && !frame.header.sections_declared
+            && !self.section_block_follows()
+        {
+            skip_ignorable_lines(&mut self.lines, &self.options);
+            if permissive {
+                trailing_lines = self.take_trailing_lines();
+            }
+        }
         // Optional sections mutate AoS; only re-sync section SoA when needed.
         // Plain .con assembly already filled positions/ids/masses (no O(N)
         // post-scan when no velocity/force sections were applied).
         let sections = match parse_declared_sections(
             &mut self.lines,
             &mut frame.header,
+            &frame.symbol_table,
             &mut frame.atom_data,
         ) {
             Ok(n) => n,
@@ -287,21 +949,557 @@ impl<'a> Iterator for ConFrameIterator<'a> {
         };
         if sections > 0 {
             frame.sync_arrays_from_atom_data();
+            frame.record_legacy_forces();
+        }
+        if permissive {
+            trailing_lines.extend(self.take_trailing_lines());
+            frame.trailing_lines = trailing_lines;
+            match frame.trailing_lines.len() {
+                0 => {}
+                lines if frame.trailing_lines.iter().all(|l| l.trim().is_empty()) => {
+                    warn(&mut warnings, error::ParseWarning::BlankLines { lines });
+                }
+                lines => warn(&mut warnings, error::ParseWarning::TrailingLines { lines }),
+            }
         }
+        // Only frames that parse report warnings, so a failed frame retried
+        // by `reparse_normalized` is not counted twice.
+        self.warnings
+            .extend(warnings.into_iter().map(|w| (start, w)));
         Some(Ok(frame))
     }
 }
 
+/// Applies `numbers` to every line of one frame's text except the free-form
+/// prebox lines (comment and JSON metadata).
+fn normalize_frame_text(chunk: &str, numbers: &crate::parser::NumericNormalizer) -> String {
+    let mut out = String::with_capacity(chunk.len());
+    for (i, line) in chunk.split_inclusive('\n').enumerate() {
+        let body = line.trim_end_matches(['\n', '\r']);
+        if i < 2 {
+            out.push_str(body);
+        } else {
+            out.push_str(&numbers.normalize_line(body));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Start of the first line strictly after byte `from` that passes
+/// [`plausible_header_at`].
+fn next_plausible_header(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut start = match memchr::memchr(b'\n', &bytes[from.min(bytes.len())..]) {
+        Some(i) => from + i + 1,
+        None => bytes.len(),
+    };
+    while start < bytes.len() {
+        if plausible_header_at(bytes, start) {
+            return Some(start);
+        }
+        start = match memchr::memchr(b'\n', &bytes[start..]) {
+            Some(i) => start + i + 1,
+            None => bytes.len(),
+        };
+    }
+    None
+}
+
+/// Header-shape probe for [`ConFrameIterator::resync`]. Cheap: stops at the
+/// first line that does not fit and never allocates for atom data.
+fn plausible_header_at(bytes: &[u8], start: usize) -> bool {
+    let mut cursor = MemchrLines {
+        bytes,
+        pos: start,
+        peeked: None,
+    };
+    let mut header = [""; 9];
+    for slot in header.iter_mut() {
+        match cursor.next_line() {
+            Some(line) => *slot = line,
+            None => return false,
+        }
+    }
+    let floats = |line: &str, n: usize| crate::parser::parse_line_of_n_f64(line, n).is_ok();
+    if crate::parser::parse_fixed_f64::<3>(header[2]).is_err()
+        || crate::parser::parse_fixed_f64::<3>(header[3]).is_err()
+    {
+        return false;
+    }
+    let natm_types = match crate::parser::parse_fixed::<usize, 1>(header[6]) {
+        Ok([n]) if n > 0 => n,
+        _ => return false,
+    };
+    crate::parser::parse_line_of_n::<usize>(header[7], natm_types).is_ok()
+        && floats(header[8], natm_types)
+}
+
+#[cfg(test)]
+mod recovery_tests {
+    use super::*;
+    use crate::test_util::fixture;
+
+    /// Three copies of a frame with a corrupt coordinate row in the middle one.
+    fn corrupt_middle() -> String {
+        let one = fixture("tiny_cuh2.con");
+        let bad = one.replacen(
+            "Coordinates of Component 1\n",
+            "Coordinates of Component 1\nnot numbers\n",
+            1,
+        );
+        format!("{one}{bad}{one}")
+    }
+
+    #[test]
+    fn default_mode_does_not_resync() {
+        let text = corrupt_middle();
+        let results: Vec<_> = ConFrameIterator::new(&text).collect();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(!ConFrameIterator::new(&text).is_recovering());
+    }
+
+    #[test]
+    fn progress_reports_monotonic_offsets() {
+        let text = corrupt_middle();
+        let mut reports = Vec::new();
+        let results: Vec<_> = ConFrameIterator::new(&text)
+            .recover(true)
+            .with_progress(|p| reports.push(p))
+            .collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(reports.len(), 4);
+        assert!(reports.windows(2).all(|w| w[0].bytes <= w[1].bytes));
+        assert_eq!(reports[0].frames, 1);
+        assert_eq!(reports[0].bytes, fixture("tiny_cuh2.con").len());
+        assert_eq!(reports[3].bytes, text.len());
+        assert_eq!(reports[3].frames, 3);
+    }
+
+    #[test]
+    fn recover_mode_yields_error_then_next_frame() {
+        let text = corrupt_middle();
+        let results: Vec<_> = ConFrameIterator::new(&text).recover(true).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        let last = results[2].as_ref().expect("resynced frame");
+        let first = results[0].as_ref().unwrap();
+        assert_eq!(last.atom_data.len(), first.atom_data.len());
+        assert_eq!(last.atom_data[0].x, first.atom_data[0].x);
+    }
+
+    #[test]
+    fn outline_matches_full_parse() {
+        for name in ["tiny_multi_cuh2.con", "tiny_cuh2_vel_forces.con", "cuh2.con"] {
+            let text = fixture(name);
+            let frames: Vec<_> = ConFrameIterator::new(&text).map(|r| r.unwrap()).collect();
+            let mut it = ConFrameIterator::new(&text);
+            let mut outlines = Vec::new();
+            while let Some(outline) = it.next_outline() {
+                outlines.push(outline.unwrap());
+            }
+            assert_eq!(outlines.len(), frames.len(), "{name}");
+            for (outline, frame) in outlines.iter().zip(&frames) {
+                assert_eq!(outline.header, frame.header);
+                assert_eq!(outline.natoms(), frame.atom_data.len());
+                let nfixed = frame.atom_data.iter().filter(|a| a.is_fixed()).count();
+                assert_eq!(outline.nfixed(), nfixed);
+                let first = frame.symbol(0);
+                assert_eq!(outline.symbols[0], first);
+            }
+        }
+    }
+
+    #[test]
+    fn resync_without_header_goes_to_eof() {
+        let text = "garbage\nmore garbage\n1 2 3\n";
+        let mut it = ConFrameIterator::new(text);
+        assert!(!it.resync(0));
+        assert!(it.next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod options_tests {
+    use super::*;
+    use crate::test_util::fixture;
+
+    fn noisy(frame: &str) -> String {
+        format!("# written by another code\n\n{frame}\n   \n# next\n{frame}\n\n")
+    }
+
+    #[test]
+    fn strict_default_rejects_comments() {
+        let text = noisy(&fixture("tiny_cuh2.con"));
+        let results: Vec<_> = ConFrameIterator::new(&text).collect();
+        assert!(results.iter().any(|r| r.is_err()));
+    }
+
+    #[test]
+    fn tolerant_skips_blank_and_comment_lines() {
+        let one = fixture("tiny_cuh2.con");
+        let text = noisy(&one);
+        let expected = ConFrameIterator::new(&one).next().unwrap().unwrap();
+        let frames: Vec<_> = ConFrameIterator::new(&text)
+            .options(ParserOptions::tolerant())
+            .map(|r| r.expect("tolerant parse"))
+            .collect();
+        assert_eq!(frames.len(), 2);
+        assert!(frames.iter().all(|f| *f == expected));
+
+        let mut it = ConFrameIterator::new(&text).options(ParserOptions::tolerant());
+        assert!(matches!(it.forward(), Some(Ok(()))));
+        assert!(matches!(it.forward(), Some(Ok(()))));
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn frame_line_counts_skipped_lines() {
+        let one = fixture("tiny_cuh2.con");
+        let bad = one.replace("0.63940000000000108", "0.6394x");
+        let text = noisy(&bad);
+        let mut it = ConFrameIterator::new(&text).options(ParserOptions::tolerant());
+        assert_eq!(it.frame_line(), 1);
+        assert!(it.next().unwrap().is_err());
+        assert_eq!(it.frame_line(), 3);
+        assert!(it.next().unwrap().is_err());
+        // 17 frame lines and the blank, whitespace and comment lines of
+        // `noisy` come before the second frame.
+        assert_eq!(it.frame_line(), 23);
+
+        it.reset();
+        assert!(matches!(it.forward(), Some(Ok(()))));
+        assert_eq!(it.frame_line(), 3);
+        assert!(matches!(it.forward(), Some(Ok(()))));
+        assert_eq!(it.frame_line(), 23);
+    }
+
+    #[test]
+    fn extra_columns_parse_and_round_trip() {
+        let text = "\
+comment
+{\"con_spec_version\":2}
+10 10 10
+90 90 90
+0 0
+0 0 0
+1
+2
+63.546
+Cu
+Coordinates of Component 1
+0.0 0.0 0.0 0 0 -3.25 0.1
+1.0 0.0 0.0 1 1 -3.5 -0.1
+";
+        assert!(matches!(
+            ConFrameIterator::new(text).next(),
+            Some(Err(error::ParseError::InvalidVectorLength { .. }))
+        ));
+        let options = ParserOptions {
+            extra_columns: true,
+            ..Default::default()
+        };
+        let frame = ConFrameIterator::new(text)
+            .options(options.clone())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(frame.atom_data[0].extra, vec![-3.25, 0.1]);
+        assert_eq!(frame.atom_data[1].atom_id, 1);
+        assert!(frame.atom_data[1].is_fixed());
+
+        let mut w = crate::writer::ConFrameWriter::new(Vec::new());
+        w.write_frame(&frame).unwrap();
+        let out = String::from_utf8(w.into_inner().unwrap()).unwrap();
+        assert!(out.contains(" 7 1 -3.500000 -0.100000\n"));
+        let back = ConFrameIterator::new(&out)
+            .options(options)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(back.atom_data, frame.atom_data);
+
+        // Standard files parse the same with the option on.
+        let one = fixture("tiny_cuh2.con");
+        let plain = ConFrameIterator::new(&one).next().unwrap().unwrap();
+        let tolerant = ConFrameIterator::new(&one)
+            .options(ParserOptions::tolerant())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(plain, tolerant);
+    }
+
+    #[test]
+    fn declared_charge_column_reads_into_charge_and_round_trips() {
+        let text = "\
+comment
+{\"con_spec_version\":2,\"extra_columns\":[\"energy\",\"charge\"]}
+10 10 10
+90 90 90
+0 0
+0 0 0
+2
+1 1
+22.990 35.45
+Na
+Coordinates of Component 1
+0.0 0.0 0.0 0 0 -1.5 0.8
+Cl
+Coordinates of Component 2
+2.8 0.0 0.0 0 1 -2.5 -0.8
+";
+        let mut it = ConFrameIterator::new(text).with_mode(ParseMode::Permissive);
+        let frame = it.next().unwrap().unwrap();
+        assert_eq!(frame.header.charge_column(), Some(1));
+        assert_eq!(frame.atom_data[0].charge, Some(0.8));
+        assert_eq!(frame.atom_data[1].extra, vec![-2.5]);
+        assert!(frame.has_charges());
+
+        let mut w = crate::writer::ConFrameWriter::new(Vec::new());
+        w.write_frame(&frame).unwrap();
+        let out = String::from_utf8(w.into_inner().unwrap()).unwrap();
+        assert!(out.contains(" 0 1 -2.500000 -0.800000\n"));
+        assert!(!out.contains("Charges of Component"));
+        let back = ConFrameIterator::new(&out)
+            .with_mode(ParseMode::Permissive)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(back, frame);
+
+        let mut lmp = Vec::new();
+        crate::formats::lammps::write_frame(&mut lmp, &frame).unwrap();
+        let lmp = String::from_utf8(lmp).unwrap();
+        assert!(lmp.contains("2 2 -0.800000 2.800000 0.000000 0.000000\n"));
+    }
+
+    #[test]
+    fn permissive_mode_reads_strict_errors_with_warnings() {
+        use error::ParseWarning;
+        let one = fixture("tiny_cuh2.con");
+        let text = one
+            .replacen("6.97529999999999539 1    0", "6.97529999999999539 1.5  0", 1)
+            .replacen("Coordinates of Component 2\n", "", 1)
+            .replacen(
+                "11.73299999999999343 0  3",
+                "11.73299999999999343 0  3  -3.25",
+                1,
+            )
+            + "end of run\n";
+        let results: Vec<_> = ConFrameIterator::new(&text).collect();
+        assert!(results[0].is_err());
+
+        let expected = ConFrameIterator::new(&one).next().unwrap().unwrap();
+        let mut it = ConFrameIterator::new(&text).with_mode(ParseMode::Permissive);
+        let frame = it.next().unwrap().unwrap();
+        assert!(it.next().is_none());
+        assert_eq!(frame.positions, expected.positions);
+        assert_eq!(frame.atom_ids, expected.atom_ids);
+        assert!(frame.atom_data[0].is_fixed());
+        assert_eq!(frame.atom_data[3].extra, vec![-3.25]);
+        let warnings: Vec<_> = it.warnings().iter().map(|(_, w)| w.clone()).collect();
+        assert_eq!(
+            warnings,
+            [
+                ParseWarning::FractionalFixedFlag {
+                    atom: 0,
+                    value: 1.5
+                },
+                ParseWarning::MissingComponentLabel { component: 2 },
+                ParseWarning::ExtraColumns { lines: 1 },
+                ParseWarning::TrailingLines { lines: 1 },
+            ]
+        );
+        assert_eq!(frame.trailing_lines, ["end of run"]);
+
+        // The skip path counts lines, so only trailing text is tolerated there.
+        let text = one + "end of run\n";
+        let mut it = ConFrameIterator::new(&text).with_mode(ParseMode::Permissive);
+        assert!(matches!(it.forward_fast(), Some(Ok(()))));
+        assert!(it.forward_fast().is_none());
+        assert_eq!(it.take_warnings().len(), 1);
+        assert!(it.warnings().is_empty());
+    }
+
+    #[test]
+    fn permissive_mode_audits_symbols_ids_and_blank_lines() {
+        use error::ParseWarning;
+        let one = fixture("tiny_cuh2.con");
+        let odd = one
+            .replacen("\nH\n", "\nHx\n", 1)
+            .replacen("343 0  3", "343 0  2", 1);
+        let text = format!("{one}\n  \n{odd}");
+        let strict: Vec<_> = ConFrameIterator::new(&text).collect();
+        assert!(strict[1].is_err());
+
+        let mut it = ConFrameIterator::new(&text).with_mode(ParseMode::Permissive);
+        let frames: Vec<_> = it.by_ref().map(|r| r.unwrap()).collect();
+        assert_eq!(frames.len(), 2);
+        let warnings: Vec<_> = it.take_warnings().into_iter().map(|(_, w)| w).collect();
+        assert_eq!(
+            warnings,
+            [
+                ParseWarning::BlankLines { lines: 2 },
+                ParseWarning::UnknownSymbol {
+                    component: 2,
+                    symbol: "Hx".into()
+                },
+                ParseWarning::DuplicateAtomId { atom_id: 2 },
+            ]
+        );
+
+        // A blank comment line opening the next frame is not skipped.
+        let text = format!("{one}{}", one.replacen("Random Number Seed", "", 1));
+        let mut it = ConFrameIterator::new(&text).with_mode(ParseMode::Permissive);
+        assert_eq!(it.by_ref().filter(|r| r.is_ok()).count(), 2);
+        assert!(it.warnings().is_empty());
+    }
+
+    #[test]
+    fn permissive_mode_keeps_trailing_lines_for_the_writer() {
+        use crate::writer::ConFrameWriter;
+        let one = fixture("tiny_cuh2.con");
+        let text = format!("{one}# appended by a workflow tool\n# step 3\n{one}\n");
+        let frames: Vec<_> = ConFrameIterator::new(&text)
+            .with_mode(ParseMode::Permissive)
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(
+            frames[0].trailing_lines,
+            ["# appended by a workflow tool", "# step 3"]
+        );
+        assert_eq!(frames[1].trailing_lines, [""]);
+
+        let write = |frames: &[types::ConFrame], keep: bool| {
+            let mut out = Vec::new();
+            ConFrameWriter::new(&mut out)
+                .keep_trailing_lines(keep)
+                .extend(frames.iter())
+                .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let kept = write(&frames, true);
+        let reread: Vec<_> = ConFrameIterator::new(&kept)
+            .with_mode(ParseMode::Permissive)
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(reread.len(), 2);
+        for (a, b) in reread.iter().zip(&frames) {
+            assert_eq!(a.trailing_lines, b.trailing_lines);
+        }
+        assert_eq!(write(&reread, true), kept);
+        let dropped = write(&frames, false);
+        assert!(!dropped.contains("workflow"));
+        assert_eq!(ConFrameIterator::new(&dropped).count(), 2);
+    }
+
+    #[test]
+    fn permissive_mode_still_reports_truncated_frames() {
+        let one = fixture("tiny_cuh2.con");
+        let text = format!("{one}{}", &one[..one.len() - 20]);
+        let results: Vec<_> = ConFrameIterator::new(&text)
+            .with_mode(ParseMode::Permissive)
+            .collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn tolerant_keeps_legacy_velocity_blocks() {
+        let one = fixture("tiny_cuh2.convel");
+        let text = noisy(&one);
+        let frames: Vec<_> = ConFrameIterator::new(&text)
+            .options(ParserOptions::tolerant())
+            .map(|r| r.expect("tolerant parse"))
+            .collect();
+        assert_eq!(frames.len(), 2);
+        assert!(frames.iter().all(|f| f.has_velocities()));
+    }
+
+    #[test]
+    fn tolerant_accepts_fortran_exponents_and_decimal_commas() {
+        let one = fixture("tiny_cuh2.con");
+        let expected = ConFrameIterator::new(&one).next().unwrap().unwrap();
+        // Rewrite the cell line and one coordinate in legacy spellings.
+        let mut lines: Vec<String> = one.lines().map(str::to_owned).collect();
+        let cell: Vec<f64> = crate::parser::parse_line_of_n(&lines[2], 3).unwrap();
+        lines[2] = cell
+            .iter()
+            .map(|v| format!("{:.6}D+00", v).replace('.', ","))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut atom: Vec<String> = lines[11].split_whitespace().map(str::to_owned).collect();
+        let x: f64 = atom[0].parse().unwrap();
+        atom[0] = format!("{:.17e}", x).replace('e', "d");
+        lines[11] = atom.join(" ");
+        let text = lines.join("\n") + "\n";
+
+        assert!(ConFrameIterator::new(&text).next().unwrap().is_err());
+        let frame = ConFrameIterator::new(&text)
+            .options(ParserOptions::tolerant())
+            .next()
+            .unwrap()
+            .expect("normalized parse");
+        for k in 0..3 {
+            assert!((frame.header.boxl[k] - expected.header.boxl[k]).abs() < 1e-6);
+        }
+        assert_eq!(frame.atom_data, expected.atom_data);
+    }
+
+    #[test]
+    fn ignorable_lines() {
+        let opts = ParserOptions::tolerant();
+        assert!(opts.is_ignorable("   "));
+        assert!(opts.is_ignorable("  # note"));
+        assert!(!opts.is_ignorable("Generated"));
+        assert!(ParserOptions::default().is_strict());
+        assert!(!ParserOptions::default().is_ignorable(""));
+    }
+
+    #[test]
+    fn next_into_matches_next_and_reuses_buffers() {
+        use crate::storage_dtype::Array2Storage;
+        let positions_ptr = |frame: &types::ConFrame| match &frame.positions {
+            Array2Storage::F64(a) => a.as_ptr(),
+            other => panic!("expected f64 positions, got {:?}", other.kind()),
+        };
+        let text = fixture("tiny_multi_cuh2.con");
+        let expected: Vec<_> = ConFrameIterator::new(&text)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let mut iter = ConFrameIterator::new(&text);
+        let mut frame = iter.next().unwrap().unwrap();
+        assert_eq!(frame, expected[0]);
+        for want in &expected[1..] {
+            let atoms = frame.atom_data.as_ptr();
+            let positions = positions_ptr(&frame);
+            iter.next_into(&mut frame).unwrap().unwrap();
+            assert_eq!(&frame, want);
+            assert_eq!(frame.atom_data.as_ptr(), atoms);
+            assert_eq!(positions_ptr(&frame), positions);
+        }
+        assert!(iter.next_into(&mut frame).is_none());
+
+        // A clone still holding the positions must not be overwritten.
+        let mut iter = ConFrameIterator::new(&text);
+        let mut frame = iter.next().unwrap().unwrap();
+        let kept = frame.clone();
+        iter.next_into(&mut frame).unwrap().unwrap();
+        assert_eq!(kept, expected[0]);
+        assert_eq!(frame, expected[1]);
+    }
+}
+
 #[cfg(test)]
 mod aos_soa_agreement_tests {
     use super::*;
-    use std::path::PathBuf;
+    use crate::test_util::fixture;
 
     #[test]
     fn iterator_vel_forces_soa_matches_aos() {
-        let p = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("resources/test/tiny_cuh2_vel_forces.con");
-        let text = std::fs::read_to_string(&p).expect("fixture");
+        let text = fixture("tiny_cuh2_vel_forces.con");
         let fr = ConFrameIterator::new(&text)
             .next()
             .expect("frame")
@@ -337,9 +1535,7 @@ mod aos_soa_agreement_tests {
     /// (nrows already equals N); forces SoA still filled from AoS.
     #[test]
     fn sync_skips_pos_when_nrows_matches_keeps_force_soa() {
-        let p = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("resources/test/tiny_cuh2_forces.con");
-        let text = std::fs::read_to_string(&p).expect("fixture");
+        let text = fixture("tiny_cuh2_forces.con");
         let fr = ConFrameIterator::new(&text)
             .next()
             .expect("frame")
@@ -374,6 +1570,10 @@ mod aos_soa_agreement_tests {
 #[cfg(feature = "parallel")]
 pub const PARALLEL_BYTES_THRESHOLD: usize = 48 * 1024;
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(path = %path.display()), err)
+)]
 pub fn read_all_frames(path: &Path) -> Result<Vec<types::ConFrame>, Box<dyn std::error::Error>> {
     let contents = crate::compression::read_file_contents(path)?;
     let text = contents.as_str()?;
@@ -397,6 +1597,10 @@ pub fn read_all_frames(path: &Path) -> Result<Vec<types::ConFrame>, Box<dyn std:
 /// when possible, else [`ConFrameIterator::forward`]).
 ///
 /// Prefer this over `read_all_frames(...).len()` when only the frame count is needed.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(path = %path.display()), err)
+)]
 pub fn count_frames(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
     let contents = crate::compression::read_file_contents(path)?;
     let text = contents.as_str()?;
@@ -416,6 +1620,10 @@ pub fn count_frames(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
 ///
 /// More efficient than `read_all_frames` for single-frame access because it
 /// stops parsing after the first frame rather than collecting all of them.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(path = %path.display()), err)
+)]
 pub fn read_first_frame(path: &Path) -> Result<types::ConFrame, Box<dyn std::error::Error>> {
     let contents = crate::compression::read_file_contents(path)?;
     let text = contents.as_str()?;
@@ -517,12 +1725,10 @@ pub fn parse_frames_parallel_with_threads(
 #[cfg(all(test, feature = "parallel"))]
 mod parallel_strong_scale_tests {
     use super::*;
-    use std::path::PathBuf;
+    use crate::test_util::fixture;
 
     fn multi_frame_fixture() -> String {
-        let p = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("resources/test/tiny_cuh2.con");
-        let one = std::fs::read_to_string(p).expect("fixture");
+        let one = fixture("tiny_cuh2.con");
         // Enough frames for >1 worker to exercise the pool.
         one.repeat(8)
     }
@@ -537,7 +1743,7 @@ mod parallel_strong_scale_tests {
         let atoms: Vec<_> = f
             .atom_data
             .iter()
-            .map(|a| (a.symbol.to_string(), a.x, a.y, a.z))
+            .map(|a| (f.symbol_of(a).to_string(), a.x, a.y, a.z))
             .collect();
         (f.atom_data.len(), atoms)
     }
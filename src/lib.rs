@@ -1,19 +1,53 @@
+pub mod akmc;
+pub mod analysis;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
 pub mod array;
+pub mod band;
+pub mod bonds;
+pub mod cell;
 #[cfg(feature = "cuda")]
 pub mod cuda_array;
 pub mod compression;
+pub mod diff;
+pub mod ensemble;
 pub mod error;
 pub mod ffi;
+pub mod fingerprint;
+mod float;
+pub mod follow;
+pub mod formats;
+pub mod frame;
+pub mod frame_f32;
+pub mod geometry;
 pub mod helpers;
 /// Campaign screening scalars / CON ingest contracts for corpus stores (`readcon-db`).
 pub mod index_proj;
 pub mod iterators;
+pub mod masses;
+mod logging;
+pub mod neighbor;
+pub mod normalize;
 pub mod parser;
+pub mod pbc;
+pub mod process;
+pub mod provenance;
+pub mod properties;
+pub mod range_read;
+pub mod select;
 #[cfg(feature = "grammar")]
 pub mod grammar;
 pub mod types;
 pub mod storage_dtype;
+pub mod supercell;
+#[cfg(test)]
+mod test_util;
+pub mod tokenizer;
+pub mod tolerance;
+pub mod trajectory;
 pub mod units;
+pub mod validate;
+pub mod verify;
 pub mod writer;
 
 /// Foreign path / CON → CON write for stack migration (CLI + library).
@@ -22,6 +56,9 @@ pub mod convert;
 #[cfg(feature = "metatensor")]
 pub mod metatensor_export;
 
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra_interop;
+
 /// Chemfiles multi-format import (real impl behind `chemfiles` feature; stubs otherwise).
 pub mod chemfiles_import;
 
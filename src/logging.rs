@@ -0,0 +1,59 @@
+//! Optional diagnostics from the file readers, parser, iterators and writer.
+//!
+//! With the `tracing` feature the macros below forward to `tracing` events
+//! and the entry points open spans, all with the calling module path
+//! (`readcon_core::iterators`, …) as target, so a subscriber can show what
+//! the parser did with a malformed file:
+//!
+//! - `read_all_frames` / `read_first_frame` / `count_frames` (`path`)
+//! - `frame`, one per frame read or skipped by a `ConFrameIterator`
+//!   (`byte`, `line` where it starts)
+//! - `resync`, a recovery scan (`from`)
+//! - `write_frame` (`atoms`)
+//!
+//! Events carry their values as fields (`byte`, `line`, `atoms`, `error`,
+//! …) under these levels:
+//!
+//! - `warn`: input accepted only because of `ParseMode::Permissive`
+//! - `debug`: frames that fail to parse, recovery resynchronizations,
+//!   numeric-normalization reparses, legacy section blocks detected
+//! - `trace`: every frame parsed or written, every ignorable line skipped
+//!
+//! The `log` feature adds `tracing/log`, which also emits everything as
+//! `log` records. Without either feature the macros expand to nothing and
+//! the parse loop pays nothing.
+
+/// `tracing::warn!` under the `tracing` feature, else nothing.
+macro_rules! log_warn {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!($($arg)+);
+    }};
+}
+
+/// `tracing::debug!` under the `tracing` feature, else nothing.
+macro_rules! log_debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)+);
+    }};
+}
+
+/// `tracing::trace!` under the `tracing` feature, else nothing.
+macro_rules! log_trace {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)+);
+    }};
+}
+
+/// Enters a `tracing` span at `$level` until the end of the enclosing
+/// block under the `tracing` feature, else nothing.
+macro_rules! log_span {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::span!(::tracing::Level::$level, $($arg)+).entered();
+    };
+}
+
+pub(crate) use {log_debug, log_span, log_trace, log_warn};
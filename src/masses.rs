@@ -0,0 +1,205 @@
+//! Per-type masses checked against the standard atomic weights.
+//!
+//! Some tools write isotopic, rounded or plainly wrong masses, or a mass
+//! that belongs to another element than the block's symbol.
+//! [`ConFrame::mass_mismatches`] lists the components whose mass is off,
+//! [`ConFrame::fix_masses`] trusts the symbols and
+//! [`ConFrame::fix_symbols`] trusts the masses. All three compare in amu
+//! and do nothing for frames whose `units` declare another mass unit;
+//! symbols that are not elements are skipped.
+//!
+//! [`crate::parser::ParseMode::Permissive`] reports components off by more
+//! than [`MASS_WARNING_THRESHOLD`] as
+//! [`ParseWarning::MassMismatch`](crate::error::ParseWarning::MassMismatch),
+//! and `readcon validate` as warnings.
+
+use crate::helpers::{atomic_mass, atomic_number_to_symbol, isotope_mass, parse_isotope};
+use crate::types::{ConFrame, FrameHeader};
+
+/// Difference from the standard weight, in amu, above which parsing and
+/// validation warn; wide enough for rounded masses such as `12` for C.
+pub const MASS_WARNING_THRESHOLD: f64 = 0.5;
+
+/// A component whose mass is not the standard weight of its symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MassMismatch {
+    /// Zero-based component (atom type) index.
+    pub component: usize,
+    pub symbol: String,
+    /// `masses_per_type[component]`.
+    pub mass: f64,
+    /// Standard atomic weight of `symbol`.
+    pub standard: f64,
+}
+
+/// Mass `symbol` should carry: its standard weight, or the isotopic mass
+/// of an isotope label such as `C13`.
+pub(crate) fn reference_mass(symbol: &str) -> Option<f64> {
+    atomic_mass(symbol).or_else(|| parse_isotope(symbol).map(isotope_mass))
+}
+
+/// Whether masses in `header` are in amu (the default when undeclared).
+pub(crate) fn masses_in_amu(header: &FrameHeader) -> bool {
+    header.unit_for("mass").is_none_or(|u| u == "amu")
+}
+
+/// The element whose standard weight is nearest `mass`, if within
+/// `tolerance`.
+fn element_for_mass(mass: f64, tolerance: f64) -> Option<&'static str> {
+    (1..=92)
+        .map(atomic_number_to_symbol)
+        .filter_map(|s| Some((s, (atomic_mass(s)? - mass).abs())))
+        .filter(|&(_, d)| d <= tolerance)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(s, _)| s)
+}
+
+impl ConFrame {
+    /// Components whose mass differs from the standard weight of their
+    /// symbol by more than `tolerance` amu. Isotope labels (`D`, `C13`) are
+    /// compared with their isotopic mass.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("H", 0.0, 0.0, 0.0, [false; 3], 0, 2.014);
+    /// b.add_atom("O", 1.0, 0.0, 0.0, [false; 3], 1, 15.999);
+    /// let off = b.build().mass_mismatches(0.01);
+    /// assert_eq!(off.len(), 1);
+    /// assert_eq!((off[0].symbol.as_str(), off[0].standard), ("H", 1.008));
+    /// ```
+    pub fn mass_mismatches(&self, tolerance: f64) -> Vec<MassMismatch> {
+        if !masses_in_amu(&self.header) {
+            return Vec::new();
+        }
+        let mut out = Vec::new();
+        let mut offset = 0;
+        for (t, &n) in self.header.natms_per_type.iter().enumerate() {
+            let atom = self.atom_data.get(offset).filter(|_| n > 0);
+            offset += n;
+            let (Some(atom), Some(&mass)) = (atom, self.header.masses_per_type.get(t)) else {
+                continue;
+            };
+            let symbol = self.symbol_of(atom);
+            if let Some(standard) = reference_mass(symbol)
+                && (mass - standard).abs() > tolerance
+            {
+                out.push(MassMismatch {
+                    component: t,
+                    symbol: symbol.to_string(),
+                    mass,
+                    standard,
+                });
+            }
+        }
+        out
+    }
+
+    /// Sets each mismatched component (see [`Self::mass_mismatches`]) to
+    /// the standard weight of its symbol, per-atom masses included.
+    /// Returns the number of components changed.
+    pub fn fix_masses(&mut self, tolerance: f64) -> usize {
+        let mismatches = self.mass_mismatches(tolerance);
+        for m in &mismatches {
+            self.set_component_mass(m.component, m.standard);
+        }
+        mismatches.len()
+    }
+
+    /// Renames each mismatched component to the element whose standard
+    /// weight is within `tolerance` of its mass, for files that paired the
+    /// right masses with the wrong symbols. Components whose mass matches no
+    /// element, or whose element no longer fits the frame's symbol table,
+    /// are left alone. Returns the number of components renamed.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 195.08);
+    /// let mut frame = b.build();
+    /// assert_eq!(frame.fix_symbols(0.01), 1);
+    /// assert_eq!(frame.symbol(0), "Pt");
+    /// ```
+    pub fn fix_symbols(&mut self, tolerance: f64) -> usize {
+        let mut renamed = 0;
+        for m in self.mass_mismatches(tolerance) {
+            let Some(Ok(symbol)) =
+                element_for_mass(m.mass, tolerance).map(|s| self.intern_symbol(s))
+            else {
+                continue;
+            };
+            let start: usize = self.header.natms_per_type[..m.component].iter().sum();
+            let end = start + self.header.natms_per_type[m.component];
+            for atom in &mut self.atom_data[start..end] {
+                atom.symbol = symbol;
+            }
+            renamed += 1;
+        }
+        renamed
+    }
+
+    fn set_component_mass(&mut self, component: usize, mass: f64) {
+        self.header.masses_per_type[component] = mass;
+        if self.masses.len() == self.atom_data.len() {
+            let start: usize = self.header.natms_per_type[..component].iter().sum();
+            for i in start..start + self.header.natms_per_type[component] {
+                self.masses.set_f64(i, mass);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frame::AtomicFrame;
+    use crate::types::ConFrameBuilder;
+
+    #[test]
+    fn fix_masses_and_symbols_repair_each_side() {
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+        b.add_atom("H", 1.0, 0.0, 0.0, [false; 3], 1, 2.0141);
+        b.add_atom("H", 2.0, 0.0, 0.0, [false; 3], 2, 2.0141);
+        b.add_atom("C", 3.0, 0.0, 0.0, [false; 3], 3, 15.999);
+        let frame = b.build();
+        let off = frame.mass_mismatches(0.01);
+        assert_eq!(off.iter().map(|m| m.component).collect::<Vec<_>>(), [1, 2]);
+
+        let mut masses = frame.clone();
+        assert_eq!(masses.fix_masses(0.01), 2);
+        assert_eq!(masses.header.masses_per_type, [63.546, 1.008, 12.011]);
+        assert_eq!(masses.mass(2), Some(1.008));
+        assert!(masses.mass_mismatches(0.01).is_empty());
+
+        // 2.0141 is no element's standard weight; 15.999 is oxygen's.
+        let mut symbols = frame;
+        assert_eq!(symbols.fix_symbols(0.01), 1);
+        assert_eq!(symbols.symbol(1), "H");
+        assert_eq!(symbols.symbol(3), "O");
+        assert_eq!(symbols.header.masses_per_type[2], 15.999);
+    }
+
+    #[test]
+    fn permissive_parse_warns_on_isotopic_mass() {
+        use crate::error::ParseWarning;
+        use crate::iterators::ConFrameIterator;
+        use crate::parser::ParseMode;
+        let text = "d2\n\n10 10 10\n90 90 90\n\n\n2\n1 1\n63.546 2.0141\n\
+                    Cu\nCoordinates of Component 1\n0 0 0 0 0\n\
+                    H\nCoordinates of Component 2\n1 1 1 0 1\n";
+        let mut it = ConFrameIterator::new(text).with_mode(ParseMode::Permissive);
+        assert!(it.next().unwrap().is_ok());
+        let warnings: Vec<_> = it.take_warnings().into_iter().map(|(_, w)| w).collect();
+        assert_eq!(
+            warnings,
+            [ParseWarning::MassMismatch {
+                component: 2,
+                symbol: "H".into(),
+                mass: 2.0141,
+                standard: 1.008,
+            }]
+        );
+    }
+}
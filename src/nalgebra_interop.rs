@@ -0,0 +1,95 @@
+//! Conversions to `nalgebra` geometry types.
+//!
+//! Available behind the `nalgebra` Cargo feature. nalgebra's convention is
+//! column vectors, so the cell is returned with the lattice vectors `a`,
+//! `b`, `c` as **columns**: `r = H * s` maps fractional `s` to Cartesian
+//! `r`. This is the transpose of the row-major
+//! [`crate::types::FrameHeader::lattice_matrix`].
+
+use crate::types::{ConFrame, FrameHeader};
+use nalgebra::{Matrix3, Point3, Vector3};
+
+impl FrameHeader {
+    /// Cell matrix with lattice vectors as columns; `None` for a degenerate
+    /// cell. Honors `metadata["lattice_vectors"]` like
+    /// [`Self::lattice_matrix`].
+    pub fn cell_matrix3(&self) -> Option<Matrix3<f64>> {
+        let [a, b, c] = self.lattice_matrix()?;
+        Some(Matrix3::from_columns(&[
+            Vector3::from(a),
+            Vector3::from(b),
+            Vector3::from(c),
+        ]))
+    }
+}
+
+impl ConFrame {
+    /// See [`FrameHeader::cell_matrix3`].
+    pub fn cell_matrix3(&self) -> Option<Matrix3<f64>> {
+        self.header.cell_matrix3()
+    }
+
+    /// Binary64 positions as points, `atom_data` order.
+    pub fn positions_point3(&self) -> Vec<Point3<f64>> {
+        self.atom_data
+            .iter()
+            .map(|a| Point3::new(a.x, a.y, a.z))
+            .collect()
+    }
+
+    /// Binary64 positions as displacement vectors from the origin,
+    /// `atom_data` order.
+    pub fn positions_vector3(&self) -> Vec<Vector3<f64>> {
+        self.atom_data
+            .iter()
+            .map(|a| Vector3::new(a.x, a.y, a.z))
+            .collect()
+    }
+
+    /// Overwrites positions (both `atom_data` and the SoA array) from
+    /// `points`. Returns `false`, leaving the frame untouched, when the
+    /// length differs from the atom count.
+    pub fn set_positions_point3(&mut self, points: &[Point3<f64>]) -> bool {
+        if points.len() != self.atom_data.len() {
+            return false;
+        }
+        let sync_soa = self.positions.nrows() == points.len();
+        for (i, (atom, p)) in self.atom_data.iter_mut().zip(points).enumerate() {
+            atom.x = p.x;
+            atom.y = p.y;
+            atom.z = p.z;
+            if sync_soa {
+                self.positions.set_f64_row(i, [p.x, p.y, p.z]);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::ConFrameBuilder;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn cell_columns_and_fractional_roundtrip() {
+        let mut b = ConFrameBuilder::new([10.0, 11.0, 12.0], [80.0, 95.0, 110.0]);
+        b.add_atom("H", 1.0, 2.0, 3.0, [false; 3], 0, 1.008);
+        let mut frame = b.build();
+
+        let h = frame.cell_matrix3().unwrap();
+        assert!((h.column(0).norm() - 10.0).abs() < 1e-12);
+        assert!((h.column(2).norm() - 12.0).abs() < 1e-12);
+        let gamma = h.column(0).angle(&h.column(1)).to_degrees();
+        assert!((gamma - 110.0).abs() < 1e-9);
+
+        let p = frame.positions_point3()[0];
+        let s = h.try_inverse().unwrap() * p.coords;
+        assert!(((h * s) - Vector3::new(1.0, 2.0, 3.0)).norm() < 1e-12);
+
+        assert!(frame.set_positions_point3(&[Point3::new(4.0, 5.0, 6.0)]));
+        assert_eq!(frame.positions_vector3()[0], Vector3::new(4.0, 5.0, 6.0));
+        assert_eq!(frame.positions.as_f64_row(0), [4.0, 5.0, 6.0]);
+        assert!(!frame.set_positions_point3(&[]));
+    }
+}
@@ -0,0 +1,352 @@
+//! Cutoff neighbor lists via linked cell lists.
+//!
+//! [`NeighborList::build`] bins atoms on a grid over the frame's [`Cell`]
+//! (in fractional coordinates, so triclinic cells work unchanged) and only
+//! compares atoms in nearby bins. Periodic axes (`pbc` metadata, default
+//! all) wrap and report the lattice image of each neighbor; cutoffs larger
+//! than the cell are handled by searching further images, including an
+//! atom's own images. Each unordered pair is listed once.
+//!
+//! [`NeighborList::rebuild`] reuses the bin and pair buffers, so updating
+//! the list along a trajectory does not reallocate once it has reached
+//! its working size.
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::frame::AtomicFrame;
+
+/// Sentinel terminating a bin's linked list.
+const EMPTY: usize = usize::MAX;
+
+/// One neighbor pair within the cutoff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NeighborPair {
+    /// `atom_data` index of the first atom (`i <= j`).
+    pub i: usize,
+    /// `atom_data` index of the second atom.
+    pub j: usize,
+    /// Lattice image of `j`: the neighbor sits at
+    /// `r_j + shift[0]·a + shift[1]·b + shift[2]·c`.
+    pub shift: [i32; 3],
+    /// Vector from `i` to that image of `j`.
+    pub vector: [f64; 3],
+    /// Length of `vector`.
+    pub distance: f64,
+}
+
+/// Pairs of atoms closer than a cutoff; see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct NeighborList {
+    cutoff: f64,
+    natoms: usize,
+    pairs: Vec<NeighborPair>,
+    // Linked cell list: `head[bin]` is the first atom in the bin, `next[atom]`
+    // the following one. Kept between rebuilds to reuse the allocations.
+    head: Vec<usize>,
+    next: Vec<usize>,
+    wrapped: Vec<[f64; 3]>,
+    image: Vec<[i32; 3]>,
+    bin_of: Vec<[usize; 3]>,
+}
+
+impl NeighborList {
+    /// All pairs of `frame` at most `cutoff` apart.
+    ///
+    /// Errors are [`ParseError::ValidationError`] for a non-positive or
+    /// non-finite cutoff and for frames without a usable cell.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::neighbor::NeighborList;
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("H", 0.5, 5.0, 5.0, [false; 3], 0, 1.008);
+    /// b.add_atom("H", 9.5, 5.0, 5.0, [false; 3], 1, 1.008);
+    /// let nl = NeighborList::build(&b.build(), 1.5).unwrap();
+    /// assert_eq!(nl.len(), 1);
+    /// assert_eq!(nl.pairs()[0].shift, [-1, 0, 0]);
+    /// assert!((nl.pairs()[0].distance - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn build<F: AtomicFrame + ?Sized>(frame: &F, cutoff: f64) -> Result<Self, ParseError> {
+        let mut list = NeighborList::default();
+        list.rebuild(frame, cutoff)?;
+        Ok(list)
+    }
+
+    /// Recomputes the list for `frame`, reusing this list's buffers.
+    pub fn rebuild<F: AtomicFrame + ?Sized>(
+        &mut self,
+        frame: &F,
+        cutoff: f64,
+    ) -> Result<(), ParseError> {
+        if !(cutoff.is_finite() && cutoff > 0.0) {
+            return Err(ParseError::ValidationError(format!(
+                "neighbor cutoff must be positive and finite, got {cutoff}"
+            )));
+        }
+        let cell = frame.cell().ok_or_else(|| {
+            ParseError::ValidationError("neighbor list needs a non-degenerate cell".into())
+        })?;
+        let pbc = frame.pbc();
+        let natoms = frame.natoms();
+        self.cutoff = cutoff;
+        self.natoms = natoms;
+        self.pairs.clear();
+
+        let nbins = bin_counts(&cell, cutoff, natoms);
+        // Bins to search on each side along each axis.
+        let widths = perpendicular_widths(&cell);
+        let reach: [i64; 3] =
+            std::array::from_fn(|k| (cutoff * nbins[k] as f64 / widths[k]).ceil() as i64);
+
+        self.head.clear();
+        self.head.resize(nbins[0] * nbins[1] * nbins[2], EMPTY);
+        self.next.clear();
+        self.next.resize(natoms, EMPTY);
+        self.wrapped.clear();
+        self.image.clear();
+        self.bin_of.clear();
+        for idx in 0..natoms {
+            let mut f = cell.cartesian_to_fractional(frame.position(idx));
+            let mut n = [0i32; 3];
+            let mut b = [0usize; 3];
+            for k in 0..3 {
+                if pbc[k] {
+                    let shift = f[k].floor();
+                    f[k] -= shift;
+                    n[k] = shift as i32;
+                }
+                let raw = (f[k] * nbins[k] as f64).floor();
+                b[k] = raw.clamp(0.0, (nbins[k] - 1) as f64) as usize;
+            }
+            let bin = (b[0] * nbins[1] + b[1]) * nbins[2] + b[2];
+            self.next[idx] = self.head[bin];
+            self.head[bin] = idx;
+            self.wrapped.push(f);
+            self.image.push(n);
+            self.bin_of.push(b);
+        }
+
+        let cutoff2 = cutoff * cutoff;
+        for i in 0..natoms {
+            let bi = self.bin_of[i];
+            for o0 in -reach[0]..=reach[0] {
+                let Some((b0, s0)) = neighbor_bin(bi[0], o0, nbins[0], pbc[0]) else {
+                    continue;
+                };
+                for o1 in -reach[1]..=reach[1] {
+                    let Some((b1, s1)) = neighbor_bin(bi[1], o1, nbins[1], pbc[1]) else {
+                        continue;
+                    };
+                    for o2 in -reach[2]..=reach[2] {
+                        let Some((b2, s2)) = neighbor_bin(bi[2], o2, nbins[2], pbc[2]) else {
+                            continue;
+                        };
+                        let s = [s0, s1, s2];
+                        let mut j = self.head[(b0 * nbins[1] + b1) * nbins[2] + b2];
+                        while j != EMPTY {
+                            // Half list: i < j, or own images with a positive shift.
+                            if j > i || (j == i && s > [0; 3]) {
+                                let (fi, fj) = (self.wrapped[i], self.wrapped[j]);
+                                let df = std::array::from_fn(|k| fj[k] + s[k] as f64 - fi[k]);
+                                let v = cell.fractional_to_cartesian(df);
+                                let d2 = v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+                                if d2 <= cutoff2 {
+                                    let (ni, nj) = (self.image[i], self.image[j]);
+                                    self.pairs.push(NeighborPair {
+                                        i,
+                                        j,
+                                        shift: std::array::from_fn(|k| s[k] + ni[k] - nj[k]),
+                                        vector: v,
+                                        distance: d2.sqrt(),
+                                    });
+                                }
+                            }
+                            j = self.next[j];
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Cutoff the list was built with.
+    pub fn cutoff(&self) -> f64 {
+        self.cutoff
+    }
+
+    /// Pairs in order of the first atom; each unordered pair appears once.
+    pub fn pairs(&self) -> &[NeighborPair] {
+        &self.pairs
+    }
+
+    /// Number of pairs.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// True when no pair is within the cutoff.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Neighbors of atom `i` as `(index, distance)`, including periodic
+    /// images of `i` itself.
+    pub fn neighbors_of(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.pairs.iter().flat_map(move |p| {
+            let a = (p.i == i).then_some((p.j, p.distance));
+            let b = (p.j == i).then_some((p.i, p.distance));
+            a.into_iter().chain(b)
+        })
+    }
+
+    /// Number of neighbors of every atom (a pair counts for both ends).
+    pub fn coordination_numbers(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.natoms];
+        for p in &self.pairs {
+            counts[p.i] += 1;
+            counts[p.j] += 1;
+        }
+        counts
+    }
+}
+
+/// Distance between opposite faces of the cell along each lattice vector:
+/// `1 / |k-th reciprocal vector|`.
+fn perpendicular_widths(cell: &Cell) -> [f64; 3] {
+    let inv = cell.inverse();
+    std::array::from_fn(|k| {
+        let col = [inv[0][k], inv[1][k], inv[2][k]];
+        1.0 / (col[0] * col[0] + col[1] * col[1] + col[2] * col[2]).sqrt()
+    })
+}
+
+/// Bins per axis: as many bins at least `cutoff` wide as fit, capped so the
+/// grid has no more than about two bins per atom.
+fn bin_counts(cell: &Cell, cutoff: f64, natoms: usize) -> [usize; 3] {
+    let widths = perpendicular_widths(cell);
+    let mut n: [usize; 3] =
+        std::array::from_fn(|k| ((widths[k] / cutoff).floor() as usize).clamp(1, 1 << 10));
+    let limit = 2 * natoms.max(1);
+    while n[0] * n[1] * n[2] > limit {
+        let k = (0..3).max_by_key(|&k| n[k]).unwrap_or(0);
+        n[k] = n[k].div_ceil(2);
+    }
+    n
+}
+
+/// Bin `b + offset` along one axis, with the lattice shift it implies on
+/// periodic axes; `None` past the edge of a non-periodic axis.
+fn neighbor_bin(b: usize, offset: i64, n: usize, periodic: bool) -> Option<(usize, i32)> {
+    let t = b as i64 + offset;
+    let n = n as i64;
+    if periodic {
+        Some((t.rem_euclid(n) as usize, t.div_euclid(n) as i32))
+    } else if (0..n).contains(&t) {
+        Some((t as usize, 0))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ConFrame, ConFrameBuilder};
+
+    /// All pairs by trying every image in a generous shift range.
+    fn brute_force(frame: &ConFrame, cutoff: f64, range: i32) -> Vec<(usize, usize, [i32; 3])> {
+        let cell = frame.header.cell().unwrap();
+        let pbc = frame.header.pbc().unwrap_or([true; 3]);
+        let r = |k: usize| if pbc[k] { range } else { 0 };
+        let a = &frame.atom_data;
+        let mut out = Vec::new();
+        for i in 0..a.len() {
+            for j in i..a.len() {
+                for s0 in -r(0)..=r(0) {
+                    for s1 in -r(1)..=r(1) {
+                        for s2 in -r(2)..=r(2) {
+                            let s = [s0, s1, s2];
+                            if j == i && s <= [0; 3] {
+                                continue;
+                            }
+                            let t = cell.fractional_to_cartesian(s.map(f64::from));
+                            let d = [
+                                a[j].x + t[0] - a[i].x,
+                                a[j].y + t[1] - a[i].y,
+                                a[j].z + t[2] - a[i].z,
+                            ];
+                            if d[0] * d[0] + d[1] * d[1] + d[2] * d[2] <= cutoff * cutoff {
+                                out.push((i, j, s));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        out.sort();
+        out
+    }
+
+    fn random_frame(lengths: [f64; 3], angles: [f64; 3], natoms: usize) -> ConFrame {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut rand = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let cell = Cell::from_lengths_angles(lengths, angles).unwrap();
+        let mut b = ConFrameBuilder::new(lengths, angles);
+        for i in 0..natoms {
+            // Some atoms start outside the home cell.
+            let f = [rand() * 1.4 - 0.2, rand() * 1.4 - 0.2, rand() * 1.4 - 0.2];
+            let r = cell.fractional_to_cartesian(f);
+            b.add_atom("Ar", r[0], r[1], r[2], [false; 3], i as u64, 39.948);
+        }
+        b.build()
+    }
+
+    fn listed(nl: &NeighborList) -> Vec<(usize, usize, [i32; 3])> {
+        let mut v: Vec<_> = nl.pairs().iter().map(|p| (p.i, p.j, p.shift)).collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn matches_brute_force_triclinic() {
+        let frame = random_frame([12.0, 13.0, 14.0], [80.0, 95.0, 105.0], 60);
+        let nl = NeighborList::build(&frame, 3.5).unwrap();
+        assert_eq!(listed(&nl), brute_force(&frame, 3.5, 2));
+        for p in nl.pairs() {
+            assert!(p.distance <= 3.5);
+        }
+    }
+
+    #[test]
+    fn cutoff_larger_than_cell_finds_self_images() {
+        let frame = random_frame([3.0, 3.5, 4.0], [90.0, 90.0, 100.0], 3);
+        let nl = NeighborList::build(&frame, 5.0).unwrap();
+        assert_eq!(listed(&nl), brute_force(&frame, 5.0, 4));
+        assert!(nl.pairs().iter().any(|p| p.i == p.j));
+        let cn = nl.coordination_numbers();
+        assert_eq!(cn.iter().sum::<usize>(), 2 * nl.len());
+        assert_eq!(nl.neighbors_of(0).count(), cn[0]);
+    }
+
+    #[test]
+    fn respects_pbc_and_reuses_buffers() {
+        let mut frame = random_frame([10.0; 3], [90.0; 3], 40);
+        frame.header.set_pbc([true, false, true]);
+        let mut nl = NeighborList::build(&frame, 3.0).unwrap();
+        assert_eq!(listed(&nl), brute_force(&frame, 3.0, 1));
+        assert!(nl.pairs().iter().all(|p| p.shift[1] == 0));
+
+        frame.header.set_pbc([true; 3]);
+        nl.rebuild(&frame, 2.0).unwrap();
+        assert_eq!(nl.cutoff(), 2.0);
+        assert_eq!(listed(&nl), brute_force(&frame, 2.0, 1));
+        assert!(NeighborList::build(&frame, 0.0).is_err());
+    }
+}
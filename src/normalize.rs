@@ -0,0 +1,273 @@
+//! Species re-grouping into canonical CON order.
+//!
+//! Other tools may write the same species in several component blocks
+//! (`Cu`, `H`, `Cu`), which eOn refuses to read. [`ConFrame::normalize`]
+//! merges such blocks so each species appears exactly once, in order of
+//! first appearance. [`ConFrame::canonicalize`] goes further for
+//! deterministic output: species in a fixed order, atoms sorted within each
+//! block and `atom_id`s renumbered `0..N`, so two files describing the same
+//! structure come out identical (`readcon sort`).
+
+use crate::frame::AtomicFrame;
+use crate::helpers::symbol_to_atomic_number;
+use crate::properties::gather_properties;
+use crate::select::remap_bonds;
+use crate::types::{ConFrame, con_frame_from_atom_data};
+
+/// Order of the species blocks written by [`ConFrame::canonicalize`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpeciesOrder {
+    /// Order of first appearance, as [`ConFrame::normalize`].
+    #[default]
+    FirstSeen,
+    /// Lexicographic by symbol.
+    Symbol,
+    /// By atomic number, unknown symbols last (then by symbol).
+    AtomicNumber,
+}
+
+/// Order of atoms within a species block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AtomOrder {
+    /// Keep the relative input order.
+    #[default]
+    Keep,
+    /// Ascending `atom_id` (stable for duplicate ids).
+    AtomId,
+    /// Ascending `(x, y, z)`, compared lexicographically.
+    Position,
+}
+
+/// What [`ConFrame::canonicalize`] does; the default only merges blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CanonicalOptions {
+    pub species: SpeciesOrder,
+    pub atoms: AtomOrder,
+    /// Set `atom_id` to each atom's final index.
+    pub renumber: bool,
+}
+
+impl ConFrame {
+    /// Regroups atoms so each species forms one block, in order of first
+    /// appearance; atoms keep their relative order within a species.
+    /// `natms_per_type`, `masses_per_type` (the first mass seen for each
+    /// species), the SoA arrays, `bonds` metadata and
+    /// [`properties`](ConFrame::properties) follow the atoms. Returns
+    /// whether any atom moved or block merged.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::iterators::ConFrameIterator;
+    /// let text = "\
+    /// comment
+    /// {\"con_spec_version\":2}
+    /// 10 10 10
+    /// 90 90 90
+    /// 0 0
+    /// 0 0 0
+    /// 3
+    /// 1 1 1
+    /// 63.546 1.008 63.546
+    /// Cu
+    /// Coordinates of Component 1
+    /// 0 0 0 0 0
+    /// H
+    /// Coordinates of Component 2
+    /// 1 1 1 0 1
+    /// Cu
+    /// Coordinates of Component 3
+    /// 2 2 2 0 2
+    /// ";
+    /// let mut frame = ConFrameIterator::new(text).next().unwrap().unwrap();
+    /// assert!(frame.normalize());
+    /// assert_eq!(frame.header.natms_per_type, vec![2, 1]);
+    /// assert_eq!(*frame.atom_ids(), [0, 2, 1]);
+    /// ```
+    pub fn normalize(&mut self) -> bool {
+        self.canonicalize(&CanonicalOptions::default())
+    }
+
+    /// [`Self::normalize`], additionally sorting each species block by
+    /// `atom_id` (stable for duplicate ids).
+    pub fn normalize_by_atom_id(&mut self) -> bool {
+        self.canonicalize(&CanonicalOptions {
+            atoms: AtomOrder::AtomId,
+            ..Default::default()
+        })
+    }
+
+    /// Sets every `atom_id` to the atom's index. Returns whether any id
+    /// changed.
+    pub fn renumber_atom_ids(&mut self) -> bool {
+        if self.atom_ids().iter().enumerate().all(|(i, &id)| id == i as u64) {
+            return false;
+        }
+        for (i, atom) in self.atom_data.iter_mut().enumerate() {
+            atom.atom_id = i as u64;
+        }
+        self.sync_arrays_from_atom_data();
+        true
+    }
+
+    /// Regroups species like [`Self::normalize`], then orders blocks and
+    /// atoms and renumbers ids as `options` asks. Sorts are stable: ties
+    /// keep their input order. Returns whether anything changed.
+    pub fn canonicalize(&mut self, options: &CanonicalOptions) -> bool {
+        let regrouped = self.regroup(options);
+        let renumbered = options.renumber && self.renumber_atom_ids();
+        regrouped || renumbered
+    }
+
+    fn regroup(&mut self, options: &CanonicalOptions) -> bool {
+        let n = self.atom_data.len();
+        let mut species: Vec<&str> = Vec::new();
+        let mut masses: Vec<f64> = Vec::new();
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        for i in 0..n {
+            let symbol = self.symbol(i);
+            let t = match species.iter().position(|s| *s == symbol) {
+                Some(t) => t,
+                None => {
+                    species.push(symbol);
+                    masses.push(self.mass(i).unwrap_or(0.0));
+                    members.push(Vec::new());
+                    species.len() - 1
+                }
+            };
+            members[t].push(i);
+        }
+        let mut blocks: Vec<usize> = (0..species.len()).collect();
+        match options.species {
+            SpeciesOrder::FirstSeen => {}
+            SpeciesOrder::Symbol => blocks.sort_by_key(|&t| species[t]),
+            SpeciesOrder::AtomicNumber => blocks.sort_by_key(|&t| {
+                let z = symbol_to_atomic_number(species[t]);
+                (z == 0, z, species[t])
+            }),
+        }
+        let masses: Vec<f64> = blocks.iter().map(|&t| masses[t]).collect();
+        let mut members: Vec<Vec<usize>> =
+            blocks.iter().map(|&t| std::mem::take(&mut members[t])).collect();
+        for block in &mut members {
+            match options.atoms {
+                AtomOrder::Keep => {}
+                AtomOrder::AtomId => block.sort_by_key(|&i| self.atom_data[i].atom_id),
+                AtomOrder::Position => block.sort_by(|&i, &j| {
+                    let (a, b) = (&self.atom_data[i], &self.atom_data[j]);
+                    a.x.total_cmp(&b.x)
+                        .then(a.y.total_cmp(&b.y))
+                        .then(a.z.total_cmp(&b.z))
+                }),
+            }
+        }
+        let order: Vec<usize> = members.iter().flatten().copied().collect();
+        let natms_per_type: Vec<usize> = members.iter().map(Vec::len).collect();
+        if order.iter().enumerate().all(|(k, &i)| k == i)
+            && natms_per_type == self.header.natms_per_type
+        {
+            return false;
+        }
+
+        let mut header = self.header.clone();
+        header.natm_types = natms_per_type.len();
+        header.natms_per_type = natms_per_type;
+        header.masses_per_type = masses;
+        // Stored labels described the old components.
+        header.component_labels.clear();
+        let mut new_index = vec![None; n];
+        for (k, &i) in order.iter().enumerate() {
+            new_index[i] = Some(k as u32);
+        }
+        remap_bonds(&mut header, &new_index);
+        let atom_data = order.iter().map(|&i| self.atom_data[i].clone()).collect();
+        let properties = gather_properties(&self.properties, n, &order);
+        let symbol_table = std::mem::take(&mut self.symbol_table);
+        *self = con_frame_from_atom_data(header, symbol_table, atom_data);
+        self.properties = properties;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::types::Bond;
+
+    const INTERLEAVED: &str = "\
+comment
+{\"con_spec_version\":2,\"sections\":[\"forces\"]}
+10 10 10
+90 90 90
+0 0
+0 0 0
+3
+2 1 1
+63.546 1.008 63.546
+Cu
+Coordinates of Component 1
+0 0 0 1 3
+1 0 0 0 1
+H
+Coordinates of Component 2
+5 5 5 0 2
+Cu
+Coordinates of Component 3
+2 0 0 0 0
+
+Cu
+Forces of Component 1
+0.1 0 0 1 3
+0.2 0 0 0 1
+H
+Forces of Component 2
+0.3 0 0 0 2
+Cu
+Forces of Component 3
+0.4 0 0 0 0
+";
+
+    #[test]
+    fn canonicalize_orders_species_atoms_and_ids() {
+        let mut frame = ConFrameIterator::new(INTERLEAVED).next().unwrap().unwrap();
+        let options = CanonicalOptions {
+            species: SpeciesOrder::AtomicNumber,
+            atoms: AtomOrder::Position,
+            renumber: true,
+        };
+        assert!(frame.canonicalize(&options));
+        assert_eq!(frame.symbols(), vec!["H", "Cu", "Cu", "Cu"]);
+        assert_eq!(frame.header.masses_per_type, vec![1.008, 63.546]);
+        let xs: Vec<f64> = frame.atom_data.iter().map(|a| a.x).collect();
+        assert_eq!(xs, vec![5.0, 0.0, 1.0, 2.0]);
+        assert_eq!(*frame.atom_ids(), [0, 1, 2, 3]);
+        assert_eq!(frame.forces.as_f64_row(3)[0], 0.4);
+        assert!(!frame.canonicalize(&options));
+
+        let options = CanonicalOptions {
+            species: SpeciesOrder::Symbol,
+            ..Default::default()
+        };
+        assert!(frame.canonicalize(&options));
+        assert_eq!(frame.symbols(), vec!["Cu", "Cu", "Cu", "H"]);
+    }
+
+    #[test]
+    fn merges_blocks_and_sorts_by_id() {
+        let mut frame = ConFrameIterator::new(INTERLEAVED).next().unwrap().unwrap();
+        frame.header.set_bonds(&[Bond::new(3, 2), Bond::new(0, 1)]);
+        assert!(frame.normalize());
+        assert_eq!(frame.header.natm_types, 2);
+        assert_eq!(frame.header.natms_per_type, vec![3, 1]);
+        assert_eq!(frame.header.masses_per_type, vec![63.546, 1.008]);
+        assert_eq!(*frame.atom_ids(), [3, 1, 0, 2]);
+        assert_eq!(frame.header.bonds(), vec![Bond::new(2, 3), Bond::new(0, 1)]);
+        assert_eq!(frame.forces.as_f64_row(2)[0], 0.4);
+        assert!(!frame.normalize());
+
+        assert!(frame.normalize_by_atom_id());
+        assert_eq!(*frame.atom_ids(), [0, 1, 3, 2]);
+        assert_eq!(frame.atom_data[2].fixed, [true; 3]);
+        assert_eq!(frame.header.bonds(), vec![Bond::new(0, 3), Bond::new(2, 1)]);
+    }
+}
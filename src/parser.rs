@@ -1,14 +1,15 @@
-use crate::error::ParseError;
-use crate::helpers::symbol_to_atomic_number;
+use crate::error::{ParseError, ParseWarning};
+use crate::helpers::{parse_isotope, symbol_to_atomic_number};
+use crate::logging::{log_debug, log_trace, log_warn};
+use crate::masses::{MASS_WARNING_THRESHOLD, reference_mass};
 use crate::types::{
     AtomDatum, ConFrame, FrameHeader, PreboxHeader, SECTION_CHARGES, SECTION_ENERGIES,
-    SECTION_FORCES, SECTION_MAGMOMS, SECTION_SPINS, SECTION_VELOCITIES,
-    decode_fixed_bitmask, meta,
+    SECTION_FORCES, SECTION_MAGMOMS, SECTION_SPINS, SECTION_VELOCITIES, decode_fixed_bitmask,
+    intern_symbol, meta,
 };
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::iter::Peekable;
-use std::sync::Arc;
 
 /// Line source with peek for section detection (blank-line separators).
 ///
@@ -17,6 +18,16 @@ use std::sync::Arc;
 pub trait LineStream<'a> {
     fn next_line(&mut self) -> Option<&'a str>;
     fn peek_line(&mut self) -> Option<&'a str>;
+    /// The line `n` positions ahead (`0` is [`Self::peek_line`]) without
+    /// consuming anything; `None` past the end or when the stream cannot
+    /// look that far ahead.
+    fn peek_nth(&mut self, n: usize) -> Option<&'a str> {
+        if n == 0 {
+            self.peek_line()
+        } else {
+            None
+        }
+    }
 }
 
 impl<'a, I> LineStream<'a> for Peekable<I>
@@ -38,8 +49,9 @@ where
 /// Pads `out[found..max]` from `defaults` when `found < max` and `found >= min`.
 ///
 /// Single-pass over the line bytes: skip ASCII whitespace, then
-/// [`fast_float2::parse_partial`] (Eisel–Lemire / SIMD-class decimal kernel)
-/// with a token-boundary check. No `SplitWhitespace`, no per-token `&str`,
+/// fast-float2's `parse_partial` (Eisel–Lemire / SIMD-class decimal kernel;
+/// `f64::from_str` without the `fast-float` feature) with a token-boundary
+/// check. No `SplitWhitespace`, no per-token `&str`,
 /// no heap `Vec`. Prefer this over allocating [`parse_line_of_n_f64`] on atom lines.
 #[inline]
 pub fn parse_line_of_range_f64_stack(
@@ -61,7 +73,7 @@ pub fn parse_line_of_range_f64_stack(
         if i >= n {
             break;
         }
-        let (val, consumed) = fast_float2::parse_partial::<f64, _>(&bytes[i..]).map_err(|_| {
+        let (val, consumed) = crate::float::parse_partial(&bytes[i..]).ok_or_else(|| {
             // Best-effort token for the error message (up to next whitespace).
             let end = bytes[i..]
                 .iter()
@@ -115,7 +127,8 @@ pub fn parse_line_of_range_f64_stack(
 ///
 /// This is the hot-path parser for coordinate and velocity lines. It uses
 /// `fast_float2::parse` instead of `str::parse::<f64>()` for better throughput
-/// on the numeric-heavy atom data lines. Fixed-width atom lines use
+/// on the numeric-heavy atom data lines (unless the `fast-float` feature is
+/// off). Fixed-width atom lines use
 /// [`parse_line_of_range_f64_stack`] to avoid a heap `Vec` per line.
 ///
 /// # Arguments
@@ -131,8 +144,8 @@ pub fn parse_line_of_n_f64(line: &str, n: usize) -> Result<Vec<f64>, ParseError>
     }
     let mut values = Vec::with_capacity(n);
     for token in line.split_ascii_whitespace() {
-        let val: f64 = fast_float2::parse(token)
-            .map_err(|_| ParseError::InvalidNumberFormat(format!("invalid float: {token}")))?;
+        let val = crate::float::parse(token)
+            .ok_or_else(|| ParseError::InvalidNumberFormat(format!("invalid float: {token}")))?;
         values.push(val);
     }
     if values.len() == n {
@@ -145,6 +158,22 @@ pub fn parse_line_of_n_f64(line: &str, n: usize) -> Result<Vec<f64>, ParseError>
     }
 }
 
+/// Parses exactly `N` (at most 5) f64 values into an array, with no heap
+/// allocation. Same errors as [`parse_line_of_n_f64`].
+///
+/// ```
+/// use readcon_core::parser::parse_fixed_f64;
+/// assert_eq!(parse_fixed_f64::<3>("10 20.5 30").unwrap(), [10.0, 20.5, 30.0]);
+/// assert!(parse_fixed_f64::<3>("10 20.5").is_err());
+/// ```
+#[inline]
+pub fn parse_fixed_f64<const N: usize>(line: &str) -> Result<[f64; N], ParseError> {
+    const { assert!(N <= 5, "parse_fixed_f64 handles at most 5 columns") };
+    let mut buf = [0.0f64; 5];
+    parse_line_of_range_f64_stack(line, N, N, &[0.0; 5], &mut buf)?;
+    Ok(std::array::from_fn(|i| buf[i]))
+}
+
 /// Parses a line of whitespace-separated f64 values, accepting between `min`
 /// and `max` values (inclusive). Returns a vector of exactly `max` elements,
 /// padding with values from `defaults` when fewer than `max` are present.
@@ -164,8 +193,8 @@ pub fn parse_line_of_range_f64(
     }
     let mut values = Vec::with_capacity(max);
     for token in line.split_ascii_whitespace() {
-        let val: f64 = fast_float2::parse(token)
-            .map_err(|_| ParseError::InvalidNumberFormat(format!("invalid float: {token}")))?;
+        let val = crate::float::parse(token)
+            .ok_or_else(|| ParseError::InvalidNumberFormat(format!("invalid float: {token}")))?;
         values.push(val);
     }
     if values.len() < min || values.len() > max {
@@ -272,6 +301,16 @@ pub fn validate_metadata_schema(
                 return Err(metadata_json_error("converged must be a boolean"));
             }
             meta::CONVERGED => {}
+            meta::EXTRA_COLUMNS
+                if !value
+                    .as_array()
+                    .is_some_and(|names| names.iter().all(Value::is_string)) =>
+            {
+                return Err(metadata_json_error(
+                    "extra_columns must be an array of strings",
+                ));
+            }
+            meta::EXTRA_COLUMNS => {}
             _ => {}
         }
     }
@@ -426,6 +465,174 @@ where
     }
 }
 
+/// Fixed-arity [`parse_line_of_n`]: parses exactly `N` values into an array
+/// instead of a `Vec`. For f64 columns prefer [`parse_fixed_f64`].
+///
+/// ```
+/// use readcon_core::parser::parse_fixed;
+/// let [natm_types] = parse_fixed::<usize, 1>("2").unwrap();
+/// assert_eq!(natm_types, 2);
+/// assert!(parse_fixed::<usize, 1>("2 3").is_err());
+/// ```
+pub fn parse_fixed<T, const N: usize>(line: &str) -> Result<[T; N], ParseError>
+where
+    T: std::str::FromStr + Copy + Default,
+    ParseError: From<<T as std::str::FromStr>::Err>,
+{
+    let mut values = [T::default(); N];
+    let mut tokens = line.split_whitespace();
+    for (found, slot) in values.iter_mut().enumerate() {
+        match tokens.next() {
+            Some(token) => *slot = token.parse::<T>()?,
+            None => return Err(ParseError::InvalidVectorLength { expected: N, found }),
+        }
+    }
+    let extra = tokens.count();
+    if extra > 0 {
+        return Err(ParseError::InvalidVectorLength {
+            expected: N,
+            found: N + extra,
+        });
+    }
+    Ok(values)
+}
+
+/// Rewrites non-standard numeric spellings found in legacy outputs.
+///
+/// Only whole tokens that are otherwise well-formed numbers are touched, so
+/// symbol lines and component labels pass through unchanged. The default
+/// (all `false`) is the identity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumericNormalizer {
+    /// Accept Fortran `D`/`d` exponent markers (`1.0D+00`).
+    pub fortran_exponents: bool,
+    /// Accept a comma as the decimal separator (`1,25`).
+    pub decimal_comma: bool,
+}
+
+impl NumericNormalizer {
+    /// Every rewrite enabled.
+    pub fn lenient() -> Self {
+        NumericNormalizer {
+            fortran_exponents: true,
+            decimal_comma: true,
+        }
+    }
+
+    /// `true` when no rewrite is enabled.
+    pub fn is_identity(&self) -> bool {
+        !self.fortran_exponents && !self.decimal_comma
+    }
+
+    /// Normalizes one whitespace-free token; borrowed when nothing changes.
+    ///
+    /// ```
+    /// use readcon_core::parser::NumericNormalizer;
+    /// let n = NumericNormalizer::lenient();
+    /// assert_eq!(n.normalize_token("-1.5D+02"), "-1.5E+02");
+    /// assert_eq!(n.normalize_token("0,25"), "0.25");
+    /// assert_eq!(n.normalize_token("D"), "D");
+    /// ```
+    pub fn normalize_token<'t>(&self, token: &'t str) -> std::borrow::Cow<'t, str> {
+        use std::borrow::Cow;
+        if self.is_identity() {
+            return Cow::Borrowed(token);
+        }
+        let bytes = token.as_bytes();
+        let mut i = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+        let mut digits = 0usize;
+        let mut separator = None;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'0'..=b'9' => digits += 1,
+                b'.' | b',' if separator.is_none() => separator = Some(i),
+                _ => break,
+            }
+            i += 1;
+        }
+        if digits == 0 {
+            return Cow::Borrowed(token);
+        }
+        let comma = separator.filter(|&k| bytes[k] == b',');
+        if comma.is_some() && !self.decimal_comma {
+            return Cow::Borrowed(token);
+        }
+        let mut exponent = None;
+        if i < bytes.len() {
+            if !matches!(bytes[i], b'e' | b'E' | b'd' | b'D') {
+                return Cow::Borrowed(token);
+            }
+            let mut j = i + 1;
+            if matches!(bytes.get(j), Some(b'+' | b'-')) {
+                j += 1;
+            }
+            if j == bytes.len() || !bytes[j..].iter().all(u8::is_ascii_digit) {
+                return Cow::Borrowed(token);
+            }
+            if matches!(bytes[i], b'd' | b'D') {
+                if !self.fortran_exponents {
+                    return Cow::Borrowed(token);
+                }
+                exponent = Some(i);
+            }
+        }
+        if comma.is_none() && exponent.is_none() {
+            return Cow::Borrowed(token);
+        }
+        let mut out = token.as_bytes().to_vec();
+        if let Some(k) = comma {
+            out[k] = b'.';
+        }
+        if let Some(k) = exponent {
+            out[k] = b'E';
+        }
+        // Only ASCII bytes were replaced by ASCII bytes.
+        Cow::Owned(String::from_utf8(out).expect("ascii rewrite keeps utf-8"))
+    }
+
+    /// Normalizes every token of `line`. Rewritten lines are rejoined with
+    /// single spaces; borrowed when nothing changes.
+    pub fn normalize_line<'t>(&self, line: &'t str) -> std::borrow::Cow<'t, str> {
+        use std::borrow::Cow;
+        if self.is_identity() {
+            return Cow::Borrowed(line);
+        }
+        let tokens: Vec<Cow<'_, str>> = line
+            .split_ascii_whitespace()
+            .map(|t| self.normalize_token(t))
+            .collect();
+        if tokens.iter().all(|t| matches!(t, Cow::Borrowed(_))) {
+            return Cow::Borrowed(line);
+        }
+        Cow::Owned(tokens.join(" "))
+    }
+}
+
+/// [`parse_line_of_n`] that retries once through `normalizer` when the
+/// strict parse fails, so well-formed lines pay nothing extra.
+///
+/// ```
+/// use readcon_core::parser::{parse_line_of_n_with, NumericNormalizer};
+/// let v: Vec<f64> = parse_line_of_n_with("1.0D+01 2,5", 2, &NumericNormalizer::lenient()).unwrap();
+/// assert_eq!(v, vec![10.0, 2.5]);
+/// ```
+pub fn parse_line_of_n_with<T: std::str::FromStr>(
+    line: &str,
+    n: usize,
+    normalizer: &NumericNormalizer,
+) -> Result<Vec<T>, ParseError>
+where
+    ParseError: From<<T as std::str::FromStr>::Err>,
+{
+    match parse_line_of_n(line, n) {
+        Err(ParseError::InvalidNumberFormat(msg)) => match normalizer.normalize_line(line) {
+            std::borrow::Cow::Owned(fixed) => parse_line_of_n(&fixed, n),
+            std::borrow::Cow::Borrowed(_) => Err(ParseError::InvalidNumberFormat(msg)),
+        },
+        other => other,
+    }
+}
+
 /// Parses the 9-line header of a `.con` file frame from an iterator.
 ///
 /// This function consumes the next 9 lines from the given line iterator to
@@ -440,12 +647,6 @@ where
 /// * `ParseError::IncompleteHeader` if the iterator has fewer than 9 lines remaining.
 /// * Propagates any errors from `parse_line_of_n` if the numeric data within
 ///   the header is malformed.
-///
-/// # Panics
-///
-/// This function will panic if the intermediate vectors for box dimensions or angles,
-/// after being successfully parsed, cannot be converted into fixed-size arrays.
-/// This should not happen if `parse_line_of_n` is used correctly with `n=3`.
 pub fn parse_frame_header<'a>(
     lines: &mut impl Iterator<Item = &'a str>,
 ) -> Result<FrameHeader, ParseError> {
@@ -467,7 +668,9 @@ pub fn parse_frame_header<'a>(
         let ver = json_obj
             .get(meta::CON_SPEC_VERSION)
             .and_then(|v| v.as_u64())
-            .ok_or(ParseError::MissingSpecVersion)? as u32;
+            .ok_or(ParseError::MissingSpecVersion)?;
+        // Versions past u32 are reported as u32::MAX rather than truncated.
+        let ver = u32::try_from(ver).unwrap_or(u32::MAX);
         if ver > crate::CON_SPEC_VERSION {
             return Err(ParseError::UnsupportedSpecVersion(ver));
         }
@@ -534,8 +737,8 @@ pub fn parse_frame_header<'a>(
     };
     let prebox2 = prebox2_raw.to_string();
 
-    let boxl_vec = parse_line_of_n_f64(lines.next().ok_or(ParseError::IncompleteHeader)?, 3)?;
-    let angles_vec = parse_line_of_n_f64(lines.next().ok_or(ParseError::IncompleteHeader)?, 3)?;
+    let boxl = parse_fixed_f64::<3>(lines.next().ok_or(ParseError::IncompleteHeader)?)?;
+    let angles = parse_fixed_f64::<3>(lines.next().ok_or(ParseError::IncompleteHeader)?)?;
     let postbox1 = lines
         .next()
         .ok_or(ParseError::IncompleteHeader)?
@@ -544,8 +747,7 @@ pub fn parse_frame_header<'a>(
         .next()
         .ok_or(ParseError::IncompleteHeader)?
         .to_string();
-    let natm_types =
-        parse_line_of_n::<usize>(lines.next().ok_or(ParseError::IncompleteHeader)?, 1)?[0];
+    let [natm_types] = parse_fixed::<usize, 1>(lines.next().ok_or(ParseError::IncompleteHeader)?)?;
     let natms_per_type = parse_line_of_n::<usize>(
         lines.next().ok_or(ParseError::IncompleteHeader)?,
         natm_types,
@@ -555,7 +757,7 @@ pub fn parse_frame_header<'a>(
         natm_types,
     )?;
     if validate {
-        validate_header_geometry(&boxl_vec, &angles_vec, natm_types, &natms_per_type)?;
+        validate_header_geometry(&boxl, &angles, natm_types, &natms_per_type)?;
         validate_masses(&masses_per_type)?;
     }
     Ok(FrameHeader {
@@ -563,12 +765,13 @@ pub fn parse_frame_header<'a>(
             user: prebox1,
             metadata_line: prebox2,
         },
-        boxl: boxl_vec.try_into().unwrap(),
-        angles: angles_vec.try_into().unwrap(),
+        boxl,
+        angles,
         postbox_header: [postbox1, postbox2],
         natm_types,
         natms_per_type,
         masses_per_type,
+        component_labels: Vec::new(),
         spec_version,
         metadata,
         sections,
@@ -577,6 +780,147 @@ pub fn parse_frame_header<'a>(
     })
 }
 
+/// Leniency knobs for `.con` files written by other codes.
+///
+/// The default is the strict grammar. With [`Self::tolerant`] (or the fields
+/// set by hand), blank lines and lines starting with one of
+/// `comment_prefixes` are skipped **between frames**: before a frame's first
+/// header line and after its last block. Lines inside a frame are never
+/// skipped, so a frame whose prebox comment itself starts with a comment
+/// prefix cannot be read in this mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// Skip whitespace-only lines between frames.
+    pub skip_blank_lines: bool,
+    /// Skip lines whose first non-blank text starts with any of these.
+    pub comment_prefixes: Vec<String>,
+    /// Numeric spellings to accept in header and atom lines. Applied by
+    /// [`crate::iterators::ConFrameIterator`] as a fallback re-parse of a
+    /// frame that failed with [`ParseError::InvalidNumberFormat`].
+    pub numbers: NumericNormalizer,
+    /// Accept numeric columns after the standard five on coordinate lines
+    /// (per-atom energies or charges appended by some eOn variants) and
+    /// keep them in [`AtomDatum::extra`], or in [`AtomDatum::charge`] for
+    /// the column that [`meta::EXTRA_COLUMNS`] names `"charge"`. Such
+    /// lines must carry the
+    /// atom id column. Off by default: the strict grammar rejects them
+    /// with [`ParseError::InvalidVectorLength`].
+    pub extra_columns: bool,
+    /// Apply the strict checks a file opts into with `"validate": true`
+    /// (metadata schema, component labels, fixed-flag and atom-id columns,
+    /// section identity) to every frame, whatever its metadata says.
+    pub validate: bool,
+    /// Whether readable deviations from the grammar are errors or
+    /// warnings; see [`ParseMode`].
+    pub mode: ParseMode,
+}
+
+/// How the parser treats deviations from the grammar that still leave a
+/// frame readable. Set through [`ParserOptions::mode`] or
+/// [`crate::iterators::ConFrameIterator::with_mode`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    /// Each deviation is an error, except columns past the fifth where
+    /// [`ParserOptions::extra_columns`] opts in.
+    #[default]
+    Strict,
+    /// Each deviation is accepted and recorded as a [`ParseWarning`]:
+    ///
+    /// - columns past the fifth on coordinate lines, kept in
+    ///   [`AtomDatum::extra`];
+    /// - a component whose `Coordinates of Component` line is missing;
+    /// - a `fixed_flag` that is not an integer, truncated toward zero;
+    /// - lines after a frame that do not start the next one, kept in
+    ///   [`ConFrame::trailing_lines`] (iterators only).
+    ///
+    /// It also reports, without changing what is parsed, component symbols
+    /// that are not elements, atom ids used twice within a frame, and
+    /// whitespace-only lines before the first frame (iterators only; a
+    /// blank line that reads as the comment line of a header is kept).
+    ///
+    /// Frames under strict validation (`"validate": true` or
+    /// [`ParserOptions::validate`]) keep their checks.
+    Permissive,
+}
+
+impl ParserOptions {
+    /// Blank lines and `#` comments skipped between frames; Fortran `D`
+    /// exponents, decimal commas and extra coordinate columns accepted.
+    pub fn tolerant() -> Self {
+        ParserOptions {
+            skip_blank_lines: true,
+            comment_prefixes: vec!["#".into()],
+            numbers: NumericNormalizer::lenient(),
+            extra_columns: true,
+            validate: false,
+            mode: ParseMode::Strict,
+        }
+    }
+
+    /// `true` when no line skipping is enabled (the default grammar).
+    /// Numeric normalization is tracked separately by [`Self::numbers`].
+    pub fn is_strict(&self) -> bool {
+        !self.skip_blank_lines && self.comment_prefixes.is_empty()
+    }
+
+    /// Whether `line` may be dropped between frames under these options.
+    pub fn is_ignorable(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return self.skip_blank_lines;
+        }
+        self.comment_prefixes
+            .iter()
+            .any(|p| !p.is_empty() && trimmed.starts_with(p.as_str()))
+    }
+}
+
+/// Consumes ignorable lines (see [`ParserOptions::is_ignorable`]) from the
+/// front of `lines`; returns how many were skipped.
+pub fn skip_ignorable_lines<'a>(lines: &mut impl LineStream<'a>, options: &ParserOptions) -> usize {
+    if options.is_strict() {
+        return 0;
+    }
+    let mut skipped = 0;
+    while let Some(line) = lines.peek_line() {
+        if !options.is_ignorable(line) {
+            break;
+        }
+        log_trace!(line, "skipped ignorable line");
+        lines.next_line();
+        skipped += 1;
+    }
+    skipped
+}
+
+/// [`parse_single_frame`] preceded by [`skip_ignorable_lines`].
+///
+/// `options.numbers` is not applied here (the stream cannot be rewound);
+/// use [`crate::iterators::ConFrameIterator::options`] for that.
+///
+/// # Example
+///
+/// ```
+/// use readcon_core::parser::{parse_single_frame_with_options, ParserOptions};
+///
+/// let text = "# exported by some code\n\nGenerated\n{\"con_spec_version\":2}\n\
+///             10 10 10\n90 90 90\n0 0\n0 0\n1\n1\n1.008\nH\n\
+///             Coordinates of Component 1\n0.0 0.0 0.0 0 0\n";
+/// let mut lines = text.lines().peekable();
+/// let frame = parse_single_frame_with_options(&mut lines, &ParserOptions::tolerant()).unwrap();
+/// assert_eq!(frame.atom_data.len(), 1);
+/// ```
+pub fn parse_single_frame_with_options<'a, L>(
+    lines: &mut L,
+    options: &ParserOptions,
+) -> Result<ConFrame, ParseError>
+where
+    L: LineStream<'a> + Iterator<Item = &'a str>,
+{
+    skip_ignorable_lines(lines, options);
+    parse_single_frame_impl(lines, options)
+}
+
 /// Parses a complete frame from a `.con` file, including its header and atomic data.
 ///
 /// This function first parses the complete frame header and then uses the information within it
@@ -622,46 +966,202 @@ pub fn parse_frame_header<'a>(
 ///
 /// assert_eq!(con_frame.header.natm_types, 2);
 /// assert_eq!(con_frame.atom_data.len(), 2);
-/// assert_eq!(&*con_frame.atom_data[0].symbol, "C");
+/// assert_eq!(con_frame.symbol(0), "C");
 /// assert_eq!(con_frame.atom_data[1].atom_id, 2);
 /// ```
 pub fn parse_single_frame<'a>(
     lines: &mut impl Iterator<Item = &'a str>,
 ) -> Result<ConFrame, ParseError> {
-    let header = parse_frame_header(lines)?;
+    parse_single_frame_impl(lines, &ParserOptions::default())
+}
+
+/// Upper bound on the atoms [`parse_single_frame_into`] reserves room for
+/// before reading any atom lines.
+const PREALLOC_ATOMS: usize = 1 << 16;
+
+/// Allocations recycled from a previous frame by
+/// [`crate::iterators::ConFrameIterator::next_into`]. The vectors are
+/// cleared before use; only their capacity carries over.
+#[derive(Debug, Default)]
+pub(crate) struct FrameBuffers {
+    pub(crate) symbol_table: Vec<String>,
+    pub(crate) atom_data: Vec<AtomDatum>,
+    pub(crate) positions: Vec<f64>,
+}
+
+impl FrameBuffers {
+    /// Takes the per-atom storage out of `frame`, leaving it empty. The
+    /// positions buffer is only reclaimed when `frame` holds the sole
+    /// reference to it; a shared or non-f64 array is left to drop.
+    pub(crate) fn reclaim(frame: &mut ConFrame) -> Self {
+        let symbol_table = std::mem::take(&mut frame.symbol_table);
+        let atom_data = std::mem::take(&mut frame.atom_data);
+        let positions = std::mem::replace(
+            &mut frame.positions,
+            crate::storage_dtype::FloatArray2::zeros_f64(0, 3),
+        )
+        .into_f64_buffer()
+        .unwrap_or_default();
+        FrameBuffers {
+            symbol_table,
+            atom_data,
+            positions,
+        }
+    }
+}
+
+/// [`parse_single_frame`], keeping columns past the fifth in
+/// [`AtomDatum::extra`] when `options.extra_columns` is set and forcing
+/// strict validation when `options.validate` is set.
+pub(crate) fn parse_single_frame_impl<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    options: &ParserOptions,
+) -> Result<ConFrame, ParseError> {
+    parse_single_frame_into(lines, options, FrameBuffers::default(), &mut Vec::new())
+}
+
+/// [`parse_single_frame_impl`] filling `buffers` instead of fresh vectors
+/// and pushing what [`ParseMode::Permissive`] let through onto `warnings`.
+pub(crate) fn parse_single_frame_into<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    options: &ParserOptions,
+    buffers: FrameBuffers,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<ConFrame, ParseError> {
+    let mut header = parse_frame_header(lines)?;
+    if options.validate && !header.strict_validation {
+        let json: serde_json::Map<String, Value> = header
+            .metadata
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        validate_metadata_schema(&json)?;
+        header.strict_validation = true;
+    }
     let validate = header.strict_validation;
-    let total_atoms: usize = header.natms_per_type.iter().sum();
-    let mut atom_data = Vec::with_capacity(total_atoms);
-    // SoA positions: default f64 fills a flat `Vec` then one Arc wrap (profile:
+    let permissive = options.mode == ParseMode::Permissive && !validate;
+    let extra_columns = options.extra_columns || permissive;
+    let mut extra_lines = 0;
+    let total_atoms = total_atom_count(&header.natms_per_type)?;
+    // The header's count is untrusted: reserve at most PREALLOC_ATOMS and
+    // let larger frames grow as their lines arrive.
+    let reserve = total_atoms.min(PREALLOC_ATOMS);
+    let FrameBuffers {
+        mut symbol_table,
+        mut atom_data,
+        positions: mut pos_flat,
+    } = buffers;
+    symbol_table.clear();
+    atom_data.clear();
+    atom_data.reserve(reserve);
+    // SoA positions fill a flat f64 `Vec`, then one Arc wrap (profile:
     // per-row ArcArray mut checks were a real cost on multi-atom parse).
     use crate::storage_dtype::{ElementKind, FloatArray2, StorageDtypes};
     let dt = StorageDtypes::from_metadata(&header.metadata).unwrap_or_default();
-    let f64_positions = dt.positions == ElementKind::Float64;
-    let mut pos_flat = if f64_positions {
-        vec![0.0f64; total_atoms.saturating_mul(3)]
-    } else {
-        Vec::new()
-    };
-    let mut positions_other = if f64_positions {
-        None
-    } else {
-        Some(FloatArray2::zeros(dt.positions, total_atoms, 3))
-    };
+    pos_flat.clear();
+    pos_flat.reserve(reserve * 3);
 
     let mut global_atom_idx: u64 = 0;
-    let mut atom_i = 0usize;
+    // eOn dynamics frames follow each component's coordinates with its
+    // velocities. The layout is recognized after the first component, whose
+    // following symbol/label pair is otherwise the second component's.
+    let natm_types = header.natm_types;
+    let mut interleaved = false;
+    let mut next_component: Option<(&str, &str)> = None;
+    let check_masses = permissive && crate::masses::masses_in_amu(&header);
+    let charge_column = header.charge_column();
+    // Filled only once a non-standard label turns up.
+    let mut component_labels: Vec<String> = Vec::new();
     for (type_idx, num_atoms) in header.natms_per_type.iter().enumerate() {
-        // Allocate the per-component Arc<str> directly from the trimmed
-        // line; going through a String intermediate would add a second
-        // allocation and copy for no semantic gain.
-        let symbol_line = lines.next().ok_or(ParseError::IncompleteFrame)?;
-        let symbol: Arc<str> = Arc::from(symbol_line.trim());
-        let coord_label = lines.next().ok_or(ParseError::IncompleteFrame)?;
+        let (symbol_line, coord_label) = match next_component.take() {
+            Some(pair) => pair,
+            None => (
+                lines.next().ok_or(ParseError::IncompleteFrame)?,
+                lines.next().ok_or(ParseError::IncompleteFrame)?,
+            ),
+        };
+        let symbol = symbol_line.trim();
+        // Components sharing a symbol share its table entry; every atom of
+        // the component copies the id.
+        let symbol_id = intern_symbol(&mut symbol_table, symbol).ok_or_else(|| {
+            ParseError::ValidationError(format!(
+                "component {} adds a symbol past the {}-entry symbol table limit",
+                type_idx + 1,
+                symbol_table.len()
+            ))
+        })?;
+        if permissive
+            && symbol != "X"
+            && symbol_to_atomic_number(symbol) == 0
+            && parse_isotope(symbol).is_none()
+        {
+            warn(
+                warnings,
+                ParseWarning::UnknownSymbol {
+                    component: type_idx + 1,
+                    symbol: symbol.to_string(),
+                },
+            );
+        } else if check_masses
+            && *num_atoms > 0
+            && let (Some(&mass), Some(standard)) =
+                (header.masses_per_type.get(type_idx), reference_mass(symbol))
+            && (mass - standard).abs() > MASS_WARNING_THRESHOLD
+        {
+            warn(
+                warnings,
+                ParseWarning::MassMismatch {
+                    component: type_idx + 1,
+                    symbol: symbol.to_string(),
+                    mass,
+                    standard,
+                },
+            );
+        }
         if validate {
-            validate_coordinate_component(type_idx, symbol.as_ref(), coord_label)?;
+            validate_coordinate_component(type_idx, symbol, coord_label)?;
+        }
+        // Permissive: a line that reads as coordinates is the first atom of
+        // a component whose label was left out.
+        let mut first_atom = None;
+        if permissive && *num_atoms > 0 && is_coordinate_line(coord_label) {
+            warn(
+                warnings,
+                ParseWarning::MissingComponentLabel {
+                    component: type_idx + 1,
+                },
+            );
+            first_atom = Some(coord_label);
+        }
+        let label = coord_label.trim();
+        if first_atom.is_none() && !is_standard_component_label(label, type_idx) {
+            if component_labels.is_empty() {
+                component_labels.extend((0..type_idx).map(FrameHeader::standard_component_label));
+            }
+            component_labels.push(label.to_string());
+        } else if !component_labels.is_empty() {
+            component_labels.push(FrameHeader::standard_component_label(type_idx));
         }
         for _ in 0..*num_atoms {
-            let coord_line = lines.next().ok_or(ParseError::IncompleteFrame)?;
+            let mut coord_line = match first_atom.take() {
+                Some(line) => line,
+                None => lines.next().ok_or(ParseError::IncompleteFrame)?,
+            };
+            let mut charge = None;
+            let extra = if extra_columns {
+                let (head, tail) = split_after_columns(coord_line, 5);
+                coord_line = head;
+                let mut extra = parse_extra_columns(tail)?;
+                if !options.extra_columns && !extra.is_empty() {
+                    extra_lines += 1;
+                }
+                if let Some(k) = charge_column.filter(|&k| k < extra.len()) {
+                    charge = Some(extra.remove(k));
+                }
+                extra
+            } else {
+                Vec::new()
+            };
             // Column 5 (atom_index) is optional; defaults to sequential index.
             let defaults = [0.0, 0.0, 0.0, 0.0, global_atom_idx as f64];
             let mut vals = [0.0f64; 5];
@@ -669,20 +1169,28 @@ pub fn parse_single_frame<'a>(
             let (fixed, atom_id) = if validate {
                 parse_identity_columns(coord_line, "coordinate", 3, 4, 5)?
             } else {
-                (decode_fixed_bitmask(vals[3] as u8), vals[4] as u64)
+                let flag = match fixed_flag_from(vals[3], coord_line, 3) {
+                    Err(_) if permissive && (0.0..256.0).contains(&vals[3]) => {
+                        warn(
+                            warnings,
+                            ParseWarning::FractionalFixedFlag {
+                                atom: atom_data.len(),
+                                value: vals[3],
+                            },
+                        );
+                        vals[3] as u8
+                    }
+                    flag => flag?,
+                };
+                (
+                    decode_fixed_bitmask(flag),
+                    atom_id_from(vals[4], coord_line, 4)?,
+                )
             };
             let xyz = [vals[0], vals[1], vals[2]];
-            if f64_positions {
-                let o = atom_i * 3;
-                pos_flat[o] = xyz[0];
-                pos_flat[o + 1] = xyz[1];
-                pos_flat[o + 2] = xyz[2];
-            } else if let Some(ref mut pos) = positions_other {
-                pos.set_f64_row(atom_i, xyz);
-            }
+            pos_flat.extend_from_slice(&xyz);
             atom_data.push(AtomDatum {
-                // This is a cheap reference-count increment, not a full string clone.
-                symbol: Arc::clone(&symbol),
+                symbol: symbol_id,
                 x: xyz[0],
                 y: xyz[1],
                 z: xyz[2],
@@ -691,25 +1199,152 @@ pub fn parse_single_frame<'a>(
                 velocity: None,
                 force: None,
                 energy: None,
-                charge: None,
+                charge,
                 spin: None,
                 magmom: None,
+                extra,
             });
             global_atom_idx += 1;
-            atom_i += 1;
+        }
+        if !validate && (interleaved || (type_idx == 0 && natm_types > 1)) {
+            let first = atom_data.len() - num_atoms;
+            let symbol_line = lines.next().ok_or(if interleaved {
+                ParseError::IncompleteVelocitySection
+            } else {
+                ParseError::IncompleteFrame
+            })?;
+            let label = lines.next().ok_or(ParseError::IncompleteFrame)?;
+            if is_velocity_label(label, type_idx) {
+                interleaved = true;
+                parse_interleaved_velocities(lines, &mut atom_data[first..])?;
+            } else if interleaved {
+                return Err(ParseError::IncompleteVelocitySection);
+            } else {
+                next_component = Some((symbol_line, label));
+            }
         }
     }
-    let positions = if f64_positions {
-        FloatArray2::from_f64_row_major(total_atoms, 3, pos_flat)
-    } else {
-        positions_other.expect("non-f64 positions allocated")
-    };
+    header.component_labels = component_labels;
+    if interleaved {
+        log_debug!("velocities interleaved with coordinates (eOn dynamics layout)");
+        // Declared sections are parsed from `header.sections`; the
+        // velocities are already in hand.
+        if !header.sections_declared {
+            header.sections.push(SECTION_VELOCITIES.into());
+        }
+    }
+    if extra_lines > 0 {
+        warn(warnings, ParseWarning::ExtraColumns { lines: extra_lines });
+    }
+    if permissive {
+        let mut seen = std::collections::HashSet::with_capacity(atom_data.len());
+        let mut reported = std::collections::HashSet::new();
+        for atom in &atom_data {
+            if !seen.insert(atom.atom_id) && reported.insert(atom.atom_id) {
+                warn(
+                    warnings,
+                    ParseWarning::DuplicateAtomId {
+                        atom_id: atom.atom_id,
+                    },
+                );
+            }
+        }
+    }
+    let mut positions = FloatArray2::from_f64_row_major(total_atoms, 3, pos_flat);
+    if dt.positions != ElementKind::Float64 {
+        positions.project_to(dt.positions);
+    }
     // Sections still attach to AoS; assemble uses prefilled positions (no second pos pass).
     Ok(crate::types::con_frame_from_atom_data_with_positions(
-        header, atom_data, positions,
+        header,
+        symbol_table,
+        atom_data,
+        positions,
     ))
 }
 
+/// Whether the trimmed `label` is `Coordinates of Component N` with `N` the
+/// 1-based `type_idx`.
+pub(crate) fn is_standard_component_label(label: &str, type_idx: usize) -> bool {
+    label
+        .strip_prefix("Coordinates of Component ")
+        .and_then(|n| n.parse::<usize>().ok())
+        == Some(type_idx + 1)
+}
+
+/// Whether `label` opens the velocity block of component `type_idx`
+/// (0-based).
+pub(crate) fn is_velocity_label(label: &str, type_idx: usize) -> bool {
+    label
+        .trim()
+        .strip_prefix("Velocities of Component ")
+        .and_then(|n| n.parse::<usize>().ok())
+        == Some(type_idx + 1)
+}
+
+/// Reads one velocity row per atom of `component` (eOn dynamics layout,
+/// after the block's symbol and label). A row's atom id, when present,
+/// must match the coordinates it follows.
+fn parse_interleaved_velocities<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    component: &mut [AtomDatum],
+) -> Result<(), ParseError> {
+    for atom in component {
+        let line = lines.next().ok_or(ParseError::IncompleteVelocitySection)?;
+        let mut vals = [0.0f64; 5];
+        let columns = parse_line_of_range_f64_stack(line, 3, 5, &[0.0; 5], &mut vals)?;
+        if columns == 5 {
+            let atom_id = atom_id_from(vals[4], line, 4)?;
+            if atom_id != atom.atom_id {
+                return Err(ParseError::ValidationError(format!(
+                    "velocity row for atom_id {atom_id} follows coordinates of atom_id {}",
+                    atom.atom_id
+                )));
+            }
+        }
+        atom.velocity = Some([vals[0], vals[1], vals[2]]);
+    }
+    Ok(())
+}
+
+/// Records a [`ParseMode::Permissive`] warning.
+pub(crate) fn warn(warnings: &mut Vec<ParseWarning>, warning: ParseWarning) {
+    log_warn!("{warning}");
+    warnings.push(warning);
+}
+
+/// Whether `line` starts with three numbers, as an atom line does and a
+/// component label does not.
+fn is_coordinate_line(line: &str) -> bool {
+    let mut columns = line.split_ascii_whitespace();
+    (0..3).all(|_| columns.next().and_then(crate::float::parse).is_some())
+}
+
+/// Splits `line` after its first `n` whitespace-separated columns.
+fn split_after_columns(line: &str, n: usize) -> (&str, &str) {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    for _ in 0..n {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+    }
+    line.split_at(i)
+}
+
+/// Parses the columns past the standard five of a coordinate line.
+fn parse_extra_columns(tail: &str) -> Result<Vec<f64>, ParseError> {
+    tail.split_ascii_whitespace()
+        .map(|token| {
+            crate::float::parse(token)
+                .ok_or_else(|| ParseError::InvalidNumberFormat(format!("invalid float: {token}")))
+        })
+        .collect()
+}
+
 fn validate_header_geometry(
     boxl: &[f64],
     angles: &[f64],
@@ -806,7 +1441,46 @@ fn parse_identity_columns(
     Ok((decode_fixed_bitmask(fixed_flag), atom_id))
 }
 
+/// Sum of the header's per-type counts, which come from untrusted text.
+pub(crate) fn total_atom_count(natms_per_type: &[usize]) -> Result<usize, ParseError> {
+    natms_per_type
+        .iter()
+        .try_fold(0usize, |sum, &n| sum.checked_add(n))
+        .ok_or_else(|| ParseError::ValidationError("total atom count overflows".to_string()))
+}
 
+/// The `fixed_flag` in column `column` of `line`, already parsed as `value`.
+pub(crate) fn fixed_flag_from(value: f64, line: &str, column: usize) -> Result<u8, ParseError> {
+    if value.fract() == 0.0 && (0.0..=255.0).contains(&value) {
+        Ok(value as u8)
+    } else {
+        Err(ParseError::InvalidFixedFlag(column_token(line, column)))
+    }
+}
+
+/// The `atom_id` in column `column` of `line`, already parsed as `value`.
+///
+/// Ids from 2^53 up are not exact in `value`, so they are read again from
+/// the token itself.
+pub(crate) fn atom_id_from(value: f64, line: &str, column: usize) -> Result<u64, ParseError> {
+    const EXACT: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+    if value.fract() == 0.0 && (0.0..EXACT).contains(&value) {
+        return Ok(value as u64);
+    }
+    let token = column_token(line, column);
+    token
+        .parse::<u64>()
+        .map_err(|_| ParseError::InvalidAtomId(token))
+}
+
+fn column_token(line: &str, column: usize) -> String {
+    line.split_ascii_whitespace()
+        .nth(column)
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[allow(clippy::too_many_arguments)]
 fn validate_section_component(
     section: &str,
     type_idx: usize,
@@ -814,6 +1488,7 @@ fn validate_section_component(
     symbol: &str,
     label: &str,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &[AtomDatum],
 ) -> Result<(), ParseError> {
     let expected_label = format!("{section} of Component {}", type_idx + 1);
@@ -829,7 +1504,8 @@ fn validate_section_component(
 
     let expected_symbol = atom_data
         .get(atom_idx)
-        .map(|atom| atom.symbol.as_ref())
+        .and_then(|atom| symbol_table.get(atom.symbol.index()))
+        .map(String::as_str)
         .ok_or_else(|| {
             ParseError::ValidationError(format!(
                 "{section} component {} has no coordinate atom to validate against",
@@ -883,13 +1559,15 @@ fn validate_section_atom_identity(
 /// `vx vy vz fixed atomID`).
 ///
 /// This function peeks at the next line. If it is blank (or contains only whitespace),
-/// it consumes the blank line and parses velocity data into the existing `atom_data`.
+/// it consumes the blank line and parses velocity data into the existing `atom_data`,
+/// whose symbol ids index `symbol_table` (see [`ConFrame::symbol_table`]).
 /// If the next line is not blank (or is absent), no velocities are parsed.
 ///
 /// Returns `Ok(true)` if velocities were found and parsed, `Ok(false)` otherwise.
 pub fn parse_velocity_section<'a>(
     lines: &mut impl LineStream<'a>,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
 ) -> Result<bool, ParseError> {
     let validate = header.strict_validation;
@@ -924,6 +1602,7 @@ pub fn parse_velocity_section<'a>(
                 symbol,
                 comp_line,
                 header,
+                symbol_table,
                 atom_data,
             )?;
         }
@@ -959,6 +1638,7 @@ pub fn parse_velocity_section<'a>(
 pub fn parse_force_section<'a>(
     lines: &mut impl LineStream<'a>,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
 ) -> Result<bool, ParseError> {
     let validate = header.strict_validation;
@@ -983,7 +1663,14 @@ pub fn parse_force_section<'a>(
         }
         if validate {
             validate_section_component(
-                "Forces", type_idx, atom_idx, symbol, comp_line, header, atom_data,
+                "Forces",
+                type_idx,
+                atom_idx,
+                symbol,
+                comp_line,
+                header,
+                symbol_table,
+                atom_data,
             )?;
         }
 
@@ -1022,6 +1709,7 @@ pub fn parse_force_section<'a>(
 pub fn parse_energy_section<'a>(
     lines: &mut impl LineStream<'a>,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
 ) -> Result<bool, ParseError> {
     let validate = header.strict_validation;
@@ -1045,7 +1733,14 @@ pub fn parse_energy_section<'a>(
         }
         if validate {
             validate_section_component(
-                "Energies", type_idx, atom_idx, symbol, comp_line, header, atom_data,
+                "Energies",
+                type_idx,
+                atom_idx,
+                symbol,
+                comp_line,
+                header,
+                symbol_table,
+                atom_data,
             )?;
         }
 
@@ -1054,7 +1749,8 @@ pub fn parse_energy_section<'a>(
             // Single energy column, plus optional fixed flag and atom_id
             // for round-trip identity checks.
             let defaults = [0.0, 0.0, atom_idx as f64];
-            let vals = parse_line_of_range_f64(energy_line, 1, 3, &defaults)?;
+            let mut vals = [0.0f64; 5];
+            parse_line_of_range_f64_stack(energy_line, 1, 3, &defaults, &mut vals)?;
             if validate {
                 let (fixed, atom_id) =
                     parse_identity_columns(energy_line, "energies", 1, 2, 3)?;
@@ -1070,65 +1766,177 @@ pub fn parse_energy_section<'a>(
     Ok(true)
 }
 
+/// One undeclared, blank-separated per-atom vector block of a legacy file:
+/// velocities (`Velocities of Component N`) or forces (`Forces of
+/// Component N`), chosen by the first component label. Returns the
+/// section name, or `None` when no blank separator follows.
+fn parse_legacy_vector_block<'a>(
+    lines: &mut impl LineStream<'a>,
+    header: &FrameHeader,
+    symbol_table: &[String],
+    atom_data: &mut [AtomDatum],
+) -> Result<Option<&'static str>, ParseError> {
+    match lines.peek_line() {
+        Some(line) if line.trim().is_empty() => {
+            lines.next_line();
+        }
+        _ => return Ok(None),
+    }
+    let incomplete = |section: Option<&str>| match section {
+        Some(SECTION_FORCES) => ParseError::IncompleteForceSection,
+        _ => ParseError::IncompleteVelocitySection,
+    };
+    let mut section: Option<&'static str> = None;
+    let mut atom_idx: usize = 0;
+    for (type_idx, &num_atoms) in header.natms_per_type.iter().enumerate() {
+        let symbol = lines.next_line().ok_or(incomplete(section))?.trim();
+        let comp_line = lines.next_line().ok_or(incomplete(section))?;
+        let (kind, label) = if comp_line.contains("Velocities of Component") {
+            (SECTION_VELOCITIES, "Velocities")
+        } else if comp_line.contains("Forces of Component") {
+            (SECTION_FORCES, "Forces")
+        } else {
+            return Err(incomplete(section));
+        };
+        if section.is_some_and(|s| s != kind) {
+            return Err(incomplete(section));
+        }
+        section = Some(kind);
+        if header.strict_validation {
+            validate_section_component(
+                label,
+                type_idx,
+                atom_idx,
+                symbol,
+                comp_line,
+                header,
+                symbol_table,
+                atom_data,
+            )?;
+        }
+        for _ in 0..num_atoms {
+            let line = lines.next_line().ok_or(incomplete(section))?;
+            let defaults = [0.0, 0.0, 0.0, 0.0, atom_idx as f64];
+            let mut vals = [0.0f64; 5];
+            parse_line_of_range_f64_stack(line, 4, 5, &defaults, &mut vals)?;
+            if header.strict_validation {
+                let (fixed, atom_id) = parse_identity_columns(line, kind, 3, 4, 5)?;
+                validate_section_atom_identity(kind, atom_idx, fixed, atom_id, atom_data)?;
+            }
+            if let Some(atom) = atom_data.get_mut(atom_idx) {
+                let v = Some([vals[0], vals[1], vals[2]]);
+                if kind == SECTION_FORCES {
+                    atom.force = v;
+                } else {
+                    atom.velocity = v;
+                }
+            }
+            atom_idx += 1;
+        }
+    }
+    Ok(section)
+}
+
 /// Parses declared sections from a frame's header metadata.
 ///
 /// If `header.sections` is non-empty (v2 file with `"sections"` key in JSON),
 /// parses each declared section in order. Otherwise falls back to legacy
-/// blank-separator velocity detection.
+/// blank-separator detection of velocity and force blocks, in either order.
+/// `symbol_table` resolves the symbol ids of `atom_data`, against which
+/// strict validation checks each section's component symbols.
 pub fn parse_declared_sections<'a>(
     lines: &mut impl LineStream<'a>,
     header: &mut FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
 ) -> Result<usize, ParseError> {
     let mut applied = 0usize;
-    if !header.sections_declared && header.sections.is_empty() {
-        // Legacy: try velocity detection via blank separator
-        let found = parse_velocity_section(lines, header, atom_data)?;
-        if found {
-            header.sections.push(SECTION_VELOCITIES.into());
-            applied = 1;
+    // A single component's interleaved velocities are only told apart from
+    // the next frame by looking ahead, so they are read here.
+    if !header.sections_declared
+        && !header.strict_validation
+        && header.natm_types == 1
+        && header.sections.is_empty()
+        && lines.peek_nth(1).is_some_and(|l| is_velocity_label(l, 0))
+        && lines.peek_line().is_some_and(|l| !l.trim().is_empty())
+    {
+        lines.next_line();
+        lines.next_line();
+        parse_interleaved_velocities(&mut std::iter::from_fn(|| lines.next_line()), atom_data)?;
+        log_debug!("velocities interleaved with coordinates (eOn dynamics layout)");
+        header.sections.push(SECTION_VELOCITIES.into());
+        applied += 1;
+    }
+    if !header.sections_declared {
+        // Legacy: blank-separated velocity and/or force blocks, told apart
+        // by their component labels (eOn results files append forces).
+        // A block after the first is only taken when its label is visible
+        // ahead, so a stray blank line before the next frame is left alone.
+        // The first is also declined when the lookahead shows no label: the
+        // blank line is then the next frame's empty comment line.
+        let labelled = |l: &str| l.contains("of Component");
+        while if applied == 0 {
+            lines.peek_nth(2).is_none_or(labelled)
+        } else {
+            lines.peek_nth(2).is_some_and(labelled)
+        } {
+            let Some(section) = parse_legacy_vector_block(lines, header, symbol_table, atom_data)?
+            else {
+                break;
+            };
+            if header.sections.iter().any(|s| s == section) {
+                return Err(ParseError::ValidationError(format!(
+                    "legacy {section} block appears twice"
+                )));
+            }
+            log_debug!(
+                section,
+                "legacy section block without a `sections` declaration"
+            );
+            header.sections.push(section.into());
+            applied += 1;
         }
     } else {
         let sections = std::mem::take(&mut header.sections);
         for section in &sections {
             match section.as_str() {
                 SECTION_VELOCITIES => {
-                    let found = parse_velocity_section(lines, header, atom_data)?;
+                    let found = parse_velocity_section(lines, header, symbol_table, atom_data)?;
                     if !found {
                         return Err(ParseError::IncompleteVelocitySection);
                     }
                     applied += 1;
                 }
                 SECTION_FORCES => {
-                    let found = parse_force_section(lines, header, atom_data)?;
+                    let found = parse_force_section(lines, header, symbol_table, atom_data)?;
                     if !found {
                         return Err(ParseError::IncompleteForceSection);
                     }
                     applied += 1;
                 }
                 SECTION_ENERGIES => {
-                    let found = parse_energy_section(lines, header, atom_data)?;
+                    let found = parse_energy_section(lines, header, symbol_table, atom_data)?;
                     if !found {
                         return Err(ParseError::IncompleteEnergySection);
                     }
                     applied += 1;
                 }
                 SECTION_CHARGES => {
-                    let found = parse_charge_section(lines, header, atom_data)?;
+                    let found = parse_charge_section(lines, header, symbol_table, atom_data)?;
                     if !found {
                         return Err(ParseError::IncompleteSection(SECTION_CHARGES.into()));
                     }
                     applied += 1;
                 }
                 SECTION_SPINS => {
-                    let found = parse_spin_section(lines, header, atom_data)?;
+                    let found = parse_spin_section(lines, header, symbol_table, atom_data)?;
                     if !found {
                         return Err(ParseError::IncompleteSection(SECTION_SPINS.into()));
                     }
                     applied += 1;
                 }
                 SECTION_MAGMOMS => {
-                    let found = parse_magmom_section(lines, header, atom_data)?;
+                    let found = parse_magmom_section(lines, header, symbol_table, atom_data)?;
                     if !found {
                         return Err(ParseError::IncompleteSection(SECTION_MAGMOMS.into()));
                     }
@@ -1147,6 +1955,7 @@ pub fn parse_declared_sections<'a>(
 fn parse_scalar_atom_section<'a>(
     lines: &mut impl LineStream<'a>,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
     section_name: &str,
     component_label: &str,
@@ -1181,6 +1990,7 @@ fn parse_scalar_atom_section<'a>(
                 symbol,
                 comp_line,
                 header,
+                symbol_table,
                 atom_data,
             )?;
         }
@@ -1190,7 +2000,8 @@ fn parse_scalar_atom_section<'a>(
                 .next_line()
                 .ok_or_else(|| ParseError::IncompleteSection(section_name.into()))?;
             let defaults = [0.0, 0.0, atom_idx as f64];
-            let vals = parse_line_of_range_f64(data_line, 1, 3, &defaults)?;
+            let mut vals = [0.0f64; 5];
+            parse_line_of_range_f64_stack(data_line, 1, 3, &defaults, &mut vals)?;
             if validate {
                 let (fixed, atom_id) =
                     parse_identity_columns(data_line, section_name, 1, 2, 3)?;
@@ -1208,11 +2019,13 @@ fn parse_scalar_atom_section<'a>(
 pub fn parse_charge_section<'a>(
     lines: &mut impl LineStream<'a>,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
 ) -> Result<bool, ParseError> {
     parse_scalar_atom_section(
         lines,
         header,
+        symbol_table,
         atom_data,
         SECTION_CHARGES,
         "Charges of Component",
@@ -1223,11 +2036,13 @@ pub fn parse_charge_section<'a>(
 pub fn parse_spin_section<'a>(
     lines: &mut impl LineStream<'a>,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
 ) -> Result<bool, ParseError> {
     parse_scalar_atom_section(
         lines,
         header,
+        symbol_table,
         atom_data,
         SECTION_SPINS,
         "Spins of Component",
@@ -1239,6 +2054,7 @@ pub fn parse_spin_section<'a>(
 pub fn parse_magmom_section<'a>(
     lines: &mut impl LineStream<'a>,
     header: &FrameHeader,
+    symbol_table: &[String],
     atom_data: &mut [AtomDatum],
 ) -> Result<bool, ParseError> {
     let validate = header.strict_validation;
@@ -1270,6 +2086,7 @@ pub fn parse_magmom_section<'a>(
                 symbol,
                 comp_line,
                 header,
+                symbol_table,
                 atom_data,
             )?;
         }
@@ -1305,6 +2122,7 @@ pub fn parse_magmom_section<'a>(
 mod tests {
     use super::*;
     use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
 
     #[test]
     fn test_parse_line_of_n_success() {
@@ -1352,6 +2170,38 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_fixed_matches_parse_line_of_n() {
+        for line in ["1.0 2.5 -3.0", "  4 5 6  "] {
+            let vec = parse_line_of_n::<f64>(line, 3).unwrap();
+            assert_eq!(parse_fixed::<f64, 3>(line).unwrap().to_vec(), vec);
+            assert_eq!(parse_fixed_f64::<3>(line).unwrap().to_vec(), vec);
+        }
+        assert_eq!(parse_fixed::<usize, 2>("216 2").unwrap(), [216, 2]);
+    }
+
+    #[test]
+    fn test_parse_fixed_length_errors() {
+        for line in ["1.0 2.5", "1.0 2.5 -3.0 4.0"] {
+            let expected = parse_line_of_n::<f64>(line, 3).unwrap_err();
+            let generic = parse_fixed::<f64, 3>(line).unwrap_err();
+            let fast = parse_fixed_f64::<3>(line).unwrap_err();
+            assert_eq!(format!("{generic:?}"), format!("{expected:?}"));
+            assert!(matches!(
+                fast,
+                ParseError::InvalidVectorLength { expected: 3, .. }
+            ));
+        }
+        assert!(matches!(
+            parse_fixed::<usize, 1>("x"),
+            Err(ParseError::InvalidNumberFormat(_))
+        ));
+        assert!(matches!(
+            parse_fixed_f64::<3>("1.0 abc -3.0"),
+            Err(ParseError::InvalidNumberFormat(_))
+        ));
+    }
+
     #[test]
     fn test_parse_frame_header_success() {
         let lines = [
@@ -1490,6 +2340,15 @@ mod tests {
             result.unwrap_err(),
             ParseError::UnsupportedSpecVersion(999)
         ));
+
+        // 2^32 + 2 must not wrap around to a supported version.
+        let mut lines = lines;
+        lines[1] = "{\"con_spec_version\":4294967298}";
+        let result = parse_frame_header(&mut lines.iter().copied());
+        assert!(matches!(
+            result,
+            Err(ParseError::UnsupportedSpecVersion(u32::MAX))
+        ));
     }
 
     #[test]
@@ -1630,12 +2489,85 @@ mod tests {
         assert_eq!(frame.header.natms_per_type, vec![3, 3]);
         assert_eq!(frame.header.masses_per_type, vec![12.011, 1.008]);
         assert_eq!(frame.atom_data.len(), 6);
-        assert_eq!(&*frame.atom_data[0].symbol, "1");
+        assert_eq!(frame.symbol(0), "1");
         assert_eq!(frame.atom_data[0].atom_id, 1);
-        assert_eq!(&*frame.atom_data[5].symbol, "2");
+        assert_eq!(frame.symbol(5), "2");
         assert_eq!(frame.atom_data[5].atom_id, 6);
     }
 
+    #[test]
+    fn test_parse_single_frame_untrusted_atom_counts() {
+        let frame = |counts: &'static str| {
+            vec![
+                "PREBOX1",
+                "{\"con_spec_version\":2}",
+                "10.0 10.0 10.0",
+                "90.0 90.0 90.0",
+                "POSTBOX1",
+                "POSTBOX2",
+                "2",
+                counts,
+                "63.546 1.008",
+                "Cu",
+                "Coordinates of Component 1",
+                "0.0 0.0 0.0 0 0",
+            ]
+        };
+        // A header claiming ~10^15 atoms must fail on the missing lines,
+        // not abort trying to reserve room for them.
+        let lines = frame("1000000000000000 1");
+        let result = parse_single_frame(&mut lines.iter().copied());
+        assert!(matches!(result, Err(ParseError::IncompleteFrame)));
+
+        let lines = frame("18446744073709551615 1");
+        let result = parse_single_frame(&mut lines.iter().copied());
+        assert!(matches!(result, Err(ParseError::ValidationError(_))));
+
+        let text = lines.join("\n");
+        let mut iter = crate::iterators::ConFrameIterator::new(&text);
+        assert!(matches!(
+            iter.forward_fast(),
+            Some(Err(ParseError::ValidationError(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_single_frame_identity_columns_are_checked() {
+        let parse = |atom: &'static str| {
+            let lines = [
+                "PREBOX1",
+                "{\"con_spec_version\":2}",
+                "10.0 10.0 10.0",
+                "90.0 90.0 90.0",
+                "POSTBOX1",
+                "POSTBOX2",
+                "1",
+                "1",
+                "63.546",
+                "Cu",
+                "Coordinates of Component 1",
+                atom,
+            ];
+            parse_single_frame(&mut lines.iter().copied())
+        };
+        for atom in ["0 0 0 0 -1", "0 0 0 0 1.5", "0 0 0 0 nan", "0 0 0 0 1e30"] {
+            assert!(
+                matches!(parse(atom), Err(ParseError::InvalidAtomId(_))),
+                "{atom}"
+            );
+        }
+        for atom in ["0 0 0 -1 0", "0 0 0 0.5 0", "0 0 0 256 0", "0 0 0 inf 0"] {
+            assert!(
+                matches!(parse(atom), Err(ParseError::InvalidFixedFlag(_))),
+                "{atom}"
+            );
+        }
+        let frame = parse("0 0 0 0 1152921504606846977").unwrap();
+        assert_eq!(frame.atom_data[0].atom_id, (1 << 60) + 1);
+        let frame = parse("0 0 0 0 18446744073709551615").unwrap();
+        assert_eq!(frame.atom_data[0].atom_id, u64::MAX);
+    }
+
     #[test]
     fn test_parse_single_frame_missing_line() {
         // With a valid header but truncated atom data, we get IncompleteFrame.
@@ -1761,7 +2693,12 @@ mod tests {
         assert!(!frame.has_velocities());
 
         // Now parse the velocity section
-        let has_vel = parse_velocity_section(&mut line_it, &frame.header, &mut frame.atom_data)
+        let has_vel = parse_velocity_section(
+            &mut line_it,
+            &frame.header,
+            &frame.symbol_table,
+            &mut frame.atom_data,
+        )
             .expect("velocity parsing should succeed");
         assert!(has_vel);
         assert_eq!(frame.atom_data[0].velocity, Some([0.1, 0.2, 0.3]));
@@ -2163,7 +3100,12 @@ Coordinates of Component 1
         ];
         let mut line_it = lines.iter().copied().peekable();
         let mut frame = parse_single_frame(&mut line_it).expect("parse should succeed");
-        let has_vel = parse_velocity_section(&mut line_it, &frame.header, &mut frame.atom_data)
+        let has_vel = parse_velocity_section(
+            &mut line_it,
+            &frame.header,
+            &frame.symbol_table,
+            &mut frame.atom_data,
+        )
             .expect("should succeed with no velocities");
         assert!(!has_vel);
         assert_eq!(frame.atom_data[0].velocity, None);
@@ -2251,4 +3193,52 @@ Coordinates of Component 1
         assert_eq!(frame.atom_data[2].atom_id, 2);
         assert!(frame.atom_data[2].is_fixed());
     }
+
+    #[test]
+    fn test_legacy_force_blocks_after_coordinates_and_velocities() {
+        let convel = fixture("tiny_cuh2.convel");
+        let forces = "\nCu\nForces of Component 1\n0.1 0 0 1 0\n0.2 0 0 1 1\n\
+                      H\nForces of Component 2\n0.3 0 0 0 2\n0.4 0 0 0 3\n";
+        let coords: String = convel.lines().take(17).map(|l| format!("{l}\n")).collect();
+
+        // Forces only, as in an eOn results file.
+        let text = format!("{coords}{forces}");
+        let frames: Vec<_> = ConFrameIterator::new(&text).map(Result::unwrap).collect();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].header.sections, vec![SECTION_FORCES]);
+        assert_eq!(frames[0].atom_data[3].force, Some([0.4, 0.0, 0.0]));
+        assert!(!frames[0].has_velocities());
+        assert_eq!(frames[0].forces.as_f64_row(2)[0], 0.3);
+        assert_eq!(
+            frames[0].property(crate::properties::FORCES),
+            Some(&crate::properties::PropertyArray::Vector(vec![
+                [0.1, 0.0, 0.0],
+                [0.2, 0.0, 0.0],
+                [0.3, 0.0, 0.0],
+                [0.4, 0.0, 0.0],
+            ]))
+        );
+
+        // Velocities then forces, followed by another frame.
+        let text = format!("{convel}{forces}{coords}");
+        let frames: Vec<_> = ConFrameIterator::new(&text).map(Result::unwrap).collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].header.sections, vec![SECTION_VELOCITIES, SECTION_FORCES]);
+        assert_eq!(frames[0].atom_data[0].force, Some([0.1, 0.0, 0.0]));
+        assert!(frames[0].atom_data[0].velocity.is_some());
+        assert!(frames[0].property(crate::properties::FORCES).is_some());
+        assert!(frames[1].header.sections.is_empty());
+        assert!(frames[1].properties.is_empty());
+
+        // A stray blank line after the last block is not a third block.
+        let text = format!("{convel}\n{coords}");
+        let frames: Vec<_> = ConFrameIterator::new(&text)
+            .options(ParserOptions::tolerant())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(frames.len(), 2);
+
+        let twice = format!("{coords}{forces}{forces}");
+        assert!(ConFrameIterator::new(&twice).next().unwrap().is_err());
+    }
 }
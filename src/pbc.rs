@@ -0,0 +1,172 @@
+//! Periodic wrapping and trajectory unwrapping.
+//!
+//! Both operate on the frame's [`Cell`] along the axes its `pbc` metadata
+//! marks periodic (all three when absent). Only coordinates change:
+//! `atom_id`, symbols, fixed flags and the other sections are carried over
+//! untouched, so a wrapped or unwrapped frame writes back to the same CON
+//! layout it was read from.
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::types::ConFrame;
+
+impl ConFrame {
+    /// Moves every atom into the home cell (fractional coordinates in
+    /// `[0, 1)` along periodic axes). Atoms with any fixed axis are left
+    /// where they are, so constrained slab layers keep their exact
+    /// coordinates. Returns `false`, changing nothing, when the frame has
+    /// no usable cell.
+    pub fn wrap_positions(&mut self) -> bool {
+        let Some(cell) = self.header.cell() else {
+            return false;
+        };
+        let pbc = self.header.pbc().unwrap_or([true; 3]);
+        for i in 0..self.atom_data.len() {
+            let a = &self.atom_data[i];
+            if a.is_fixed() {
+                continue;
+            }
+            let mut f = cell.cartesian_to_fractional([a.x, a.y, a.z]);
+            let mut moved = false;
+            for (x, periodic) in f.iter_mut().zip(pbc) {
+                if periodic && !(0.0..1.0).contains(x) {
+                    *x = x.rem_euclid(1.0);
+                    // rem_euclid can round up to exactly 1.0 for tiny negatives.
+                    if *x >= 1.0 {
+                        *x = 0.0;
+                    }
+                    moved = true;
+                }
+            }
+            // Atoms already inside keep their exact coordinates.
+            if moved {
+                self.set_position(i, cell.fractional_to_cartesian(f));
+            }
+        }
+        true
+    }
+
+    /// Sets atom `i`'s position in `atom_data` and, when allocated, the SoA
+    /// `positions` array.
+    pub(crate) fn set_position(&mut self, i: usize, p: [f64; 3]) {
+        let a = &mut self.atom_data[i];
+        a.x = p[0];
+        a.y = p[1];
+        a.z = p[2];
+        if self.positions.nrows() == self.atom_data.len() {
+            self.positions.set_f64_row(i, p);
+        }
+    }
+}
+
+/// Continuous copy of a trajectory whose atoms were wrapped back into the
+/// cell, for diffusion analysis (mean-squared displacement and friends).
+///
+/// The first frame is kept as is. Each later step adds the minimum-image
+/// displacement since the previous frame (using the later frame's cell), so
+/// atoms moving less than half a cell per frame are tracked across faces.
+/// Frames must list the same atoms (count and `atom_id`) in the same order.
+///
+/// Errors are [`ParseError::ValidationError`].
+///
+/// # Example
+/// ```
+/// use readcon_core::pbc::unwrap_trajectory;
+/// use readcon_core::types::ConFrameBuilder;
+/// let frame = |x: f64| {
+///     let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+///     b.add_atom("H", x, 5.0, 5.0, [false; 3], 0, 1.008);
+///     b.build()
+/// };
+/// let frames = unwrap_trajectory(&[frame(9.5), frame(0.5), frame(1.5)]).unwrap();
+/// assert!((frames[2].atom_data[0].x - 11.5).abs() < 1e-9);
+/// ```
+pub fn unwrap_trajectory(frames: &[ConFrame]) -> Result<Vec<ConFrame>, ParseError> {
+    let Some(first) = frames.first() else {
+        return Ok(Vec::new());
+    };
+    let natoms = first.atom_data.len();
+    let mut out = Vec::with_capacity(frames.len());
+    out.push(first.clone());
+    for (k, pair) in frames.windows(2).enumerate() {
+        let (prev, cur) = (&pair[0], &pair[1]);
+        let k = k + 1;
+        if cur.atom_data.len() != natoms {
+            return Err(ParseError::ValidationError(format!(
+                "frame {k} has {} atoms, frame 0 has {natoms}",
+                cur.atom_data.len()
+            )));
+        }
+        if let Some(i) =
+            (0..natoms).find(|&i| cur.atom_data[i].atom_id != prev.atom_data[i].atom_id)
+        {
+            return Err(ParseError::ValidationError(format!(
+                "frame {k} atom {i}: atom_id {} differs from {} in frame {}",
+                cur.atom_data[i].atom_id,
+                prev.atom_data[i].atom_id,
+                k - 1
+            )));
+        }
+        let cell: Cell = cur.header.cell().ok_or_else(|| {
+            ParseError::ValidationError(format!("frame {k}: cell is degenerate or not finite"))
+        })?;
+        let pbc = cur.header.pbc().unwrap_or([true; 3]);
+        let mut next = cur.clone();
+        let last = out.last().expect("first frame pushed");
+        for i in 0..natoms {
+            let (p, c, u) = (&prev.atom_data[i], &cur.atom_data[i], &last.atom_data[i]);
+            let d = cell.minimum_image([c.x - p.x, c.y - p.y, c.z - p.z], pbc);
+            next.set_position(i, [u.x + d[0], u.y + d[1], u.z + d[2]]);
+        }
+        out.push(next);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    fn frame(h: [f64; 3], cu: [f64; 3]) -> ConFrame {
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        b.add_atom("Cu", cu[0], cu[1], cu[2], [true; 3], 5, 63.546);
+        b.add_atom("H", h[0], h[1], h[2], [false; 3], 3, 1.008);
+        b.build()
+    }
+
+    #[test]
+    fn wrap_moves_free_atoms_only() {
+        let mut f = frame([-0.5, 12.0, 25.0], [-1.0, 0.0, 0.0]);
+        assert!(f.wrap_positions());
+        let h = &f.atom_data[1];
+        assert!((h.x - 9.5).abs() < 1e-9 && (h.y - 2.0).abs() < 1e-9 && (h.z - 5.0).abs() < 1e-9);
+        assert_eq!(f.positions.as_f64_row(1), [h.x, h.y, h.z]);
+        assert_eq!(f.atom_data[0].x, -1.0);
+        assert_eq!(*f.atom_ids(), [5, 3]);
+
+        f.header.set_pbc([false, true, true]);
+        f.atom_data[1].x = -3.0;
+        assert!(f.wrap_positions());
+        assert_eq!(f.atom_data[1].x, -3.0);
+    }
+
+    #[test]
+    fn unwrap_follows_face_crossings() {
+        let frames = [
+            frame([9.0, 5.0, 0.5], [0.0; 3]),
+            frame([0.0, 5.0, 9.8], [0.0; 3]),
+            frame([1.0, 5.0, 9.0], [0.0; 3]),
+        ];
+        let out = unwrap_trajectory(&frames).unwrap();
+        let h = &out[2].atom_data[1];
+        assert!((h.x - 11.0).abs() < 1e-9);
+        assert!((h.z + 1.0).abs() < 1e-9);
+        assert_eq!(out[2].atom_data[0].fixed, [true; 3]);
+        assert!(unwrap_trajectory(&[]).unwrap().is_empty());
+
+        let mut bad = frames[1].clone();
+        bad.atom_data[1].atom_id = 9;
+        assert!(unwrap_trajectory(&[frames[0].clone(), bad]).is_err());
+    }
+}
@@ -0,0 +1,337 @@
+//! eOn processes: the reactant / saddle / product frames of one event.
+//!
+//! eOn's saddle searches write `reactant.con`, `saddle.con` and
+//! `product.con` into the client directory; the server then files them as
+//! `procdata/reactant_<id>.con` (and so on) under each state. [`ProcessTriple`]
+//! reads and writes both spellings and checks that the three frames
+//! describe the same atoms. [`ProcessTriple::displacements`] ranks atoms by
+//! how far they move along the process and separates participating atoms
+//! from spectators.
+
+use crate::error::ParseError;
+use crate::iterators;
+use crate::types::ConFrame;
+use crate::writer::ConFrameWriter;
+use std::path::{Path, PathBuf};
+
+/// One of the three frames of a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessRole {
+    Reactant,
+    Saddle,
+    Product,
+}
+
+impl ProcessRole {
+    /// All roles in path order.
+    pub const ALL: [ProcessRole; 3] = [
+        ProcessRole::Reactant,
+        ProcessRole::Saddle,
+        ProcessRole::Product,
+    ];
+
+    /// Lower-case name used in eOn file names.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProcessRole::Reactant => "reactant",
+            ProcessRole::Saddle => "saddle",
+            ProcessRole::Product => "product",
+        }
+    }
+
+    /// eOn file name: `reactant.con` without an id, `reactant_<id>.con`
+    /// (the `procdata/` spelling) with one.
+    pub fn file_name(self, id: Option<u64>) -> String {
+        match id {
+            Some(id) => format!("{}_{id}.con", self.as_str()),
+            None => format!("{}.con", self.as_str()),
+        }
+    }
+}
+
+/// Reactant, saddle and product of one process, validated to share atoms.
+#[derive(Debug, Clone)]
+pub struct ProcessTriple {
+    pub reactant: ConFrame,
+    pub saddle: ConFrame,
+    pub product: ConFrame,
+}
+
+impl ProcessTriple {
+    /// Bundles the three frames after [`Self::validate`].
+    pub fn new(
+        reactant: ConFrame,
+        saddle: ConFrame,
+        product: ConFrame,
+    ) -> Result<Self, ParseError> {
+        let triple = ProcessTriple {
+            reactant,
+            saddle,
+            product,
+        };
+        triple.validate()?;
+        Ok(triple)
+    }
+
+    /// The frame for `role`.
+    pub fn get(&self, role: ProcessRole) -> &ConFrame {
+        match role {
+            ProcessRole::Reactant => &self.reactant,
+            ProcessRole::Saddle => &self.saddle,
+            ProcessRole::Product => &self.product,
+        }
+    }
+
+    /// Checks that saddle and product match the reactant atom for atom:
+    /// same count, and the same symbol and `atom_id` at every `atom_data`
+    /// index. Errors are [`ParseError::ValidationError`].
+    pub fn validate(&self) -> Result<(), ParseError> {
+        let reference = &self.reactant.atom_data;
+        for role in [ProcessRole::Saddle, ProcessRole::Product] {
+            let frame = self.get(role);
+            let atoms = &frame.atom_data;
+            let name = role.as_str();
+            if atoms.len() != reference.len() {
+                return Err(ParseError::ValidationError(format!(
+                    "{name} has {} atoms, reactant has {}",
+                    atoms.len(),
+                    reference.len()
+                )));
+            }
+            for (i, (a, r)) in atoms.iter().zip(reference).enumerate() {
+                let (symbol, reference_symbol) = (frame.symbol_of(a), self.reactant.symbol_of(r));
+                if symbol != reference_symbol {
+                    return Err(ParseError::ValidationError(format!(
+                        "{name} atom {i} is {symbol}, reactant atom {i} is {reference_symbol}"
+                    )));
+                }
+                if a.atom_id != r.atom_id {
+                    return Err(ParseError::ValidationError(format!(
+                        "{name} atom {i} has atom_id {}, reactant has {}",
+                        a.atom_id, r.atom_id
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads `reactant.con` / `saddle.con` / `product.con` from `dir`, or
+    /// the `_<id>` variants when `id` is given (see
+    /// [`ProcessRole::file_name`]). The first frame of each file is used.
+    pub fn read_dir(dir: &Path, id: Option<u64>) -> Result<Self, Box<dyn std::error::Error>> {
+        let [reactant, saddle, product] =
+            ProcessRole::ALL.map(|role| iterators::read_first_frame(&dir.join(role.file_name(id))));
+        Ok(Self::new(reactant?, saddle?, product?)?)
+    }
+
+    /// Writes the three frames into `dir` (created if missing) under eOn's
+    /// names and returns the paths in reactant, saddle, product order.
+    pub fn write_dir(&self, dir: &Path, id: Option<u64>) -> std::io::Result<[PathBuf; 3]> {
+        std::fs::create_dir_all(dir)?;
+        let paths = ProcessRole::ALL.map(|role| dir.join(role.file_name(id)));
+        for (role, path) in ProcessRole::ALL.iter().zip(&paths) {
+            let mut writer = ConFrameWriter::from_path(path)?;
+            writer.write_frame(self.get(*role))?;
+            writer.flush()?;
+        }
+        Ok(paths)
+    }
+
+    /// Per-atom displacement from the reactant to the saddle and to the
+    /// product, ranked; see [`ProcessDisplacements`]. Displacements use the
+    /// minimum image of the reactant cell along periodic axes (`pbc`
+    /// metadata, default fully periodic); without a usable cell they are
+    /// plain Cartesian differences.
+    pub fn displacements(&self, threshold: f64) -> ProcessDisplacements {
+        let cell = self.reactant.header.cell();
+        let pbc = self.reactant.header.pbc().unwrap_or([true; 3]);
+        let distance = |a: &crate::types::AtomDatum, b: &crate::types::AtomDatum| {
+            let mut d = [b.x - a.x, b.y - a.y, b.z - a.z];
+            if let Some(cell) = &cell {
+                d = cell.minimum_image(d, pbc);
+            }
+            (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+        };
+        let mut atoms: Vec<AtomDisplacement> = self
+            .reactant
+            .atom_data
+            .iter()
+            .zip(&self.saddle.atom_data)
+            .zip(&self.product.atom_data)
+            .enumerate()
+            .map(|(index, ((r, s), p))| {
+                let to_saddle = distance(r, s);
+                let to_product = distance(r, p);
+                AtomDisplacement {
+                    index,
+                    atom_id: r.atom_id,
+                    symbol: self.reactant.symbol_of(r).to_string(),
+                    to_saddle,
+                    to_product,
+                    participating: to_saddle.max(to_product) > threshold,
+                }
+            })
+            .collect();
+        atoms.sort_by(|a, b| {
+            b.to_saddle
+                .total_cmp(&a.to_saddle)
+                .then(b.to_product.total_cmp(&a.to_product))
+                .then(a.index.cmp(&b.index))
+        });
+        ProcessDisplacements { threshold, atoms }
+    }
+
+    /// Forward barrier `E(saddle) - E(reactant)` from frame metadata.
+    pub fn barrier(&self) -> Option<f64> {
+        Some(self.saddle.header.energy()? - self.reactant.header.energy()?)
+    }
+
+    /// Reverse barrier `E(saddle) - E(product)` from frame metadata.
+    pub fn reverse_barrier(&self) -> Option<f64> {
+        Some(self.saddle.header.energy()? - self.product.header.energy()?)
+    }
+
+    /// Reaction energy `E(product) - E(reactant)` from frame metadata.
+    pub fn reaction_energy(&self) -> Option<f64> {
+        Some(self.product.header.energy()? - self.reactant.header.energy()?)
+    }
+}
+
+/// One row of [`ProcessDisplacements`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomDisplacement {
+    /// Index into `atom_data` (shared by all three frames).
+    pub index: usize,
+    pub atom_id: u64,
+    pub symbol: String,
+    /// Distance moved from reactant to saddle.
+    pub to_saddle: f64,
+    /// Distance moved from reactant to product.
+    pub to_product: f64,
+    /// `max(to_saddle, to_product) > threshold`; spectators otherwise.
+    pub participating: bool,
+}
+
+/// Per-atom displacement table of a process, from
+/// [`ProcessTriple::displacements`]. Rows are ranked by `to_saddle`
+/// (largest first), then `to_product`. `Display` prints an aligned table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessDisplacements {
+    /// Distance above which an atom counts as participating.
+    pub threshold: f64,
+    pub atoms: Vec<AtomDisplacement>,
+}
+
+impl ProcessDisplacements {
+    /// Participating atoms, in rank order.
+    pub fn participating(&self) -> impl Iterator<Item = &AtomDisplacement> {
+        self.atoms.iter().filter(|a| a.participating)
+    }
+
+    /// Spectator atoms, in rank order.
+    pub fn spectators(&self) -> impl Iterator<Item = &AtomDisplacement> {
+        self.atoms.iter().filter(|a| !a.participating)
+    }
+}
+
+impl std::fmt::Display for ProcessDisplacements {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>4} {:>6} {:>8} {:<4} {:>12} {:>12}  role",
+            "rank", "index", "atom_id", "sym", "to_saddle", "to_product"
+        )?;
+        for (rank, a) in self.atoms.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4} {:>6} {:>8} {:<4} {:>12.6} {:>12.6}  {}",
+                rank + 1,
+                a.index,
+                a.atom_id,
+                a.symbol,
+                a.to_saddle,
+                a.to_product,
+                if a.participating {
+                    "participating"
+                } else {
+                    "spectator"
+                }
+            )?;
+        }
+        write!(
+            f,
+            "{} of {} atom(s) participating (threshold {})",
+            self.participating().count(),
+            self.atoms.len(),
+            self.threshold
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    fn frame(x: f64, energy: f64) -> ConFrame {
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        b.prebox_header("process test");
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+        b.add_atom("H", x, 1.0, 1.0, [false; 3], 1, 1.008);
+        let mut f = b.build();
+        f.header.set_energy(energy);
+        f
+    }
+
+    #[test]
+    fn energies_and_roundtrip() {
+        let triple =
+            ProcessTriple::new(frame(1.0, -2.0), frame(1.5, -1.25), frame(2.0, -2.5)).unwrap();
+        assert_eq!(triple.barrier(), Some(0.75));
+        assert_eq!(triple.reverse_barrier(), Some(1.25));
+        assert_eq!(triple.reaction_energy(), Some(-0.5));
+
+        let dir = tempfile::tempdir().unwrap();
+        let paths = triple.write_dir(dir.path(), Some(4)).unwrap();
+        assert!(paths[1].ends_with("saddle_4.con"));
+        let back = ProcessTriple::read_dir(dir.path(), Some(4)).unwrap();
+        assert_eq!(back.saddle.atom_data[1].x, 1.5);
+        assert!(ProcessTriple::read_dir(dir.path(), None).is_err());
+    }
+
+    #[test]
+    fn displacement_table_ranks_and_classifies() {
+        let mut saddle = frame(1.5, 0.0);
+        // Cu crosses the x = 0 face: 0.2 by minimum image, not 9.8.
+        saddle.atom_data[0].x = 9.8;
+        let triple = ProcessTriple::new(frame(1.0, 0.0), saddle, frame(2.0, 0.0)).unwrap();
+        let table = triple.displacements(0.1);
+        assert_eq!(table.atoms[0].symbol, "H");
+        assert!((table.atoms[0].to_saddle - 0.5).abs() < 1e-12);
+        assert!((table.atoms[0].to_product - 1.0).abs() < 1e-12);
+        assert!((table.atoms[1].to_saddle - 0.2).abs() < 1e-12);
+        assert_eq!(table.participating().count(), 2);
+
+        let strict = triple.displacements(0.6);
+        let spectators: Vec<_> = strict.spectators().map(|a| a.atom_id).collect();
+        assert_eq!(spectators, vec![0]);
+        let text = strict.to_string();
+        assert!(text.lines().nth(1).unwrap().contains("participating"));
+        assert!(text.ends_with("1 of 2 atom(s) participating (threshold 0.6)"));
+    }
+
+    #[test]
+    fn rejects_mismatched_atoms() {
+        let mut product = frame(2.0, 0.0);
+        product.atom_data[1].atom_id = 7;
+        let err = ProcessTriple::new(frame(1.0, 0.0), frame(1.5, 0.0), product)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("product atom 1 has atom_id 7"), "{err}");
+
+        let mut saddle = frame(1.5, 0.0);
+        saddle.set_symbol(0, "Ag").unwrap();
+        assert!(ProcessTriple::new(frame(1.0, 0.0), saddle, frame(2.0, 0.0)).is_err());
+    }
+}
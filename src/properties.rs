@@ -0,0 +1,220 @@
+//! Named per-atom properties.
+//!
+//! CON files encode a fixed set of per-atom data (positions, fixed flags,
+//! ids and the declared sections). Converters to and from extXYZ, LAMMPS
+//! dumps or HDF5 meet other columns (`tags`, `mol_id`, per-atom stress,
+//! ...) that have no CON representation. [`ConFrame::properties`] holds
+//! them by name, one value per atom in `atom_data` order, so they survive
+//! a round trip through a [`ConFrame`] even though
+//! [`ConFrameWriter`](crate::writer::ConFrameWriter) does not write them.
+//!
+//! [`ConFrame::select`], [`ConFrame::normalize`] and
+//! [`ConFrame::make_supercell`] carry properties along with the atoms.
+//!
+//! Forces read from an eOn results file, a legacy `Forces of Component N`
+//! block after the coordinates, are also stored as the [`FORCES`] property
+//! for force post-processing that works on properties.
+
+use crate::error::ParseError;
+use crate::types::ConFrame;
+use std::collections::BTreeMap;
+
+/// Properties by name, in name order.
+pub type PropertyMap = BTreeMap<String, PropertyArray>;
+
+/// Name of the [`PropertyArray::Vector`] that holds the forces of a legacy
+/// trailing force block. It is a copy taken when parsing: the forces stay
+/// in `atom_data` and [`ConFrame::forces`], which the writer uses.
+pub const FORCES: &str = "forces";
+
+/// One value per atom, in `atom_data` order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyArray {
+    /// Scalar floats (charges, per-atom energies, occupancies).
+    Float(Vec<f64>),
+    /// Cartesian 3-vectors (dipoles, displacements).
+    Vector(Vec<[f64; 3]>),
+    /// Integers (tags, molecule ids, LAMMPS types).
+    Int(Vec<i64>),
+    /// Flags (selection masks).
+    Bool(Vec<bool>),
+    /// Labels (residue names, Wyckoff letters).
+    Str(Vec<String>),
+}
+
+impl PropertyArray {
+    /// Number of atoms covered.
+    pub fn len(&self) -> usize {
+        match self {
+            PropertyArray::Float(v) => v.len(),
+            PropertyArray::Vector(v) => v.len(),
+            PropertyArray::Int(v) => v.len(),
+            PropertyArray::Bool(v) => v.len(),
+            PropertyArray::Str(v) => v.len(),
+        }
+    }
+
+    /// True for a property of a frame without atoms.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Values at `order` (indices into this array, repeats allowed), e.g.
+    /// to follow atoms that were reordered, selected or replicated.
+    ///
+    /// # Panics
+    /// If an index is out of range.
+    pub fn gather(&self, order: &[usize]) -> PropertyArray {
+        fn pick<T: Clone>(v: &[T], order: &[usize]) -> Vec<T> {
+            order.iter().map(|&i| v[i].clone()).collect()
+        }
+        match self {
+            PropertyArray::Float(v) => PropertyArray::Float(pick(v, order)),
+            PropertyArray::Vector(v) => PropertyArray::Vector(pick(v, order)),
+            PropertyArray::Int(v) => PropertyArray::Int(pick(v, order)),
+            PropertyArray::Bool(v) => PropertyArray::Bool(pick(v, order)),
+            PropertyArray::Str(v) => PropertyArray::Str(pick(v, order)),
+        }
+    }
+}
+
+/// `properties` with every array gathered at `order`; arrays whose length
+/// no longer matches the `natoms` atoms they index are dropped.
+pub(crate) fn gather_properties(
+    properties: &PropertyMap,
+    natoms: usize,
+    order: &[usize],
+) -> PropertyMap {
+    properties
+        .iter()
+        .filter(|(_, v)| v.len() == natoms)
+        .map(|(k, v)| (k.clone(), v.gather(order)))
+        .collect()
+}
+
+impl ConFrame {
+    /// The property `name`, if set.
+    pub fn property(&self, name: &str) -> Option<&PropertyArray> {
+        self.properties.get(name)
+    }
+
+    /// Sets the property `name`, returning the previous value.
+    ///
+    /// Errors are [`ParseError::ValidationError`] when `values` does not
+    /// hold exactly one entry per atom.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::properties::PropertyArray;
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("O", 0.0, 0.0, 0.0, [false; 3], 0, 15.999);
+    /// b.add_atom("H", 0.9, 0.0, 0.0, [false; 3], 1, 1.008);
+    /// let mut frame = b.build();
+    /// frame
+    ///     .set_property("mol_id", PropertyArray::Int(vec![7, 7]))
+    ///     .unwrap();
+    /// assert_eq!(frame.property("mol_id"), Some(&PropertyArray::Int(vec![7, 7])));
+    /// assert!(frame.set_property("tag", PropertyArray::Int(vec![1])).is_err());
+    /// ```
+    pub fn set_property(
+        &mut self,
+        name: &str,
+        values: PropertyArray,
+    ) -> Result<Option<PropertyArray>, ParseError> {
+        let n = self.atom_data.len();
+        if values.len() != n {
+            return Err(ParseError::ValidationError(format!(
+                "property {name:?} has {} values for {n} atoms",
+                values.len()
+            )));
+        }
+        Ok(self.properties.insert(name.to_string(), values))
+    }
+
+    /// Removes the property `name`, returning it.
+    pub fn remove_property(&mut self, name: &str) -> Option<PropertyArray> {
+        self.properties.remove(name)
+    }
+
+    /// Copies the parsed forces to the [`FORCES`] property when they came
+    /// from an undeclared (eOn-style) force block.
+    pub(crate) fn record_legacy_forces(&mut self) {
+        if self.header.sections_declared
+            || !self
+                .header
+                .sections
+                .iter()
+                .any(|s| s == crate::types::SECTION_FORCES)
+        {
+            return;
+        }
+        let forces = self
+            .atom_data
+            .iter()
+            .map(|a| a.force.unwrap_or_default())
+            .collect();
+        self.properties
+            .insert(FORCES.to_string(), PropertyArray::Vector(forces));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    fn frame() -> ConFrame {
+        let mut b = ConFrameBuilder::new([4.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+        b.add_atom("H", 1.0, 1.0, 1.0, [false; 3], 1, 1.008);
+        b.add_atom("Cu", 2.0, 2.0, 2.0, [false; 3], 2, 63.546);
+        let mut frame = b.build();
+        let ids: Vec<i64> = frame.atom_ids().iter().map(|&i| i as i64).collect();
+        frame.set_property("tag", PropertyArray::Int(ids)).unwrap();
+        frame
+            .set_property(
+                "label",
+                PropertyArray::Str(frame.symbols().iter().map(|s| s.to_string()).collect()),
+            )
+            .unwrap();
+        frame
+    }
+
+    #[test]
+    fn properties_follow_atoms() {
+        let f = frame();
+        let tags = |f: &ConFrame| match f.property("tag") {
+            Some(PropertyArray::Int(v)) => v.clone(),
+            other => panic!("{other:?}"),
+        };
+        let h = f.select(|a| f.symbol_of(a) == "H");
+        assert_eq!(tags(&h), vec![1]);
+        assert_eq!(
+            h.property("label"),
+            Some(&PropertyArray::Str(vec!["H".into()]))
+        );
+
+        let big = f.make_supercell([2, 1, 1]).unwrap();
+        let expected: Vec<i64> = big
+            .atom_data
+            .iter()
+            .map(|a| (a.atom_id % 3) as i64)
+            .collect();
+        assert_eq!(tags(&big), expected);
+
+        // Interleave the species blocks by hand: Cu, H, Cu.
+        let mut mixed = f.clone();
+        mixed.remove_property("label");
+        mixed.atom_data.swap(1, 2);
+        mixed.header.natm_types = 3;
+        mixed.header.natms_per_type = vec![1, 1, 1];
+        mixed.header.masses_per_type = vec![63.546, 1.008, 63.546];
+        mixed
+            .set_property("tag", PropertyArray::Int(vec![0, 1, 2]))
+            .unwrap();
+        assert!(mixed.normalize());
+        assert_eq!(*mixed.atom_ids(), [0, 2, 1]);
+        assert_eq!(tags(&mixed), vec![0, 2, 1]);
+    }
+}
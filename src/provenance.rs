@@ -0,0 +1,170 @@
+//! Provenance in the free-text user line: which tool wrote a frame, when,
+//! and from which file and frame.
+//!
+//! [`ConFrame::set_comment`] writes the line as `key=value` fields joined
+//! by `; `, tool first:
+//!
+//! ```text
+//! readcon convert; created=2026-10-16T09:30:00Z; source=run/neb.con; frame=3
+//! ```
+//!
+//! [`ConFrame::comment`] reads it back. The line survives every format that
+//! keeps the comment (CON, bcon, JSON lines, GRO titles), so the record
+//! follows a frame through a conversion pipeline.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::ConFrame;
+
+/// Fields of a provenance comment; see the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// Name (and version) of the tool that wrote the frame.
+    pub tool: String,
+    /// When the frame was written, as an RFC 3339 UTC timestamp.
+    pub timestamp: Option<String>,
+    /// File the frame was read from.
+    pub source: Option<String>,
+    /// Zero-based index of the frame in `source`.
+    pub frame_index: Option<u64>,
+}
+
+impl Provenance {
+    /// A record for `tool` with no other fields.
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the timestamp to the current time.
+    pub fn stamped_now(mut self) -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.timestamp = Some(rfc3339_utc(secs));
+        self
+    }
+
+    /// Sets the source file.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets the frame index within the source.
+    pub fn with_frame_index(mut self, index: u64) -> Self {
+        self.frame_index = Some(index);
+        self
+    }
+
+    /// The user line for this record.
+    pub fn to_line(&self) -> String {
+        let mut line = self.tool.clone();
+        let fields = [
+            ("created", self.timestamp.clone()),
+            ("source", self.source.clone()),
+            ("frame", self.frame_index.map(|i| i.to_string())),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                line.push_str(&format!("; {key}={value}"));
+            }
+        }
+        line
+    }
+
+    /// Parses a line written by [`Self::to_line`]. `None` unless at least
+    /// one of `created`, `source` or `frame` is present, so ordinary
+    /// comments such as `Generated by eOn` are not mistaken for records.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut parts = line.trim_end().split("; ");
+        let mut record = Self::new(parts.next()?);
+        let mut found = false;
+        for part in parts {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            match key {
+                "created" => record.timestamp = Some(value.to_string()),
+                "source" => record.source = Some(value.to_string()),
+                "frame" => match value.parse() {
+                    Ok(i) => record.frame_index = Some(i),
+                    Err(_) => continue,
+                },
+                _ => continue,
+            }
+            found = true;
+        }
+        found.then_some(record)
+    }
+}
+
+impl ConFrame {
+    /// Replaces the user line with the provenance record `p`.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::provenance::Provenance;
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut frame = ConFrameBuilder::new([10.0; 3], [90.0; 3]).build();
+    /// frame.set_comment(&Provenance::new("mytool 1.2").with_source("in.con").with_frame_index(4));
+    /// assert_eq!(frame.header.prebox_header.user, "mytool 1.2; source=in.con; frame=4");
+    /// assert_eq!(frame.comment().unwrap().frame_index, Some(4));
+    /// ```
+    pub fn set_comment(&mut self, p: &Provenance) {
+        self.header.prebox_header.user = p.to_line();
+    }
+
+    /// The provenance record in the user line, if it holds one.
+    pub fn comment(&self) -> Option<Provenance> {
+        Provenance::from_line(&self.header.prebox_header.user)
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SSZ` for seconds since the Unix epoch.
+fn rfc3339_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil date from days since 1970-01-01 (Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3_600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConFrameBuilder;
+
+    #[test]
+    fn comment_round_trips_and_ignores_plain_lines() {
+        assert_eq!(rfc3339_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339_utc(951_782_400 + 3_661), "2000-02-29T01:01:01Z");
+
+        let mut frame = ConFrameBuilder::new([10.0; 3], [90.0; 3]).build();
+        assert_eq!(frame.comment(), None);
+        let p = Provenance::new("readcon convert")
+            .stamped_now()
+            .with_source("dir with space/a.con")
+            .with_frame_index(12);
+        frame.set_comment(&p);
+        assert_eq!(frame.comment(), Some(p));
+
+        frame.header.prebox_header.user = "Generated by eOn".into();
+        assert_eq!(frame.comment(), None);
+    }
+}
@@ -11,7 +11,7 @@ use std::collections::BTreeMap;
 use std::path::Path;
 
 use crate::iterators::ConFrameIterator;
-use crate::types::{AtomDatum, ConFrame, ConFrameBuilder, meta};
+use crate::types::{AtomDatum, ConFrame, ConFrameBuilder, Constraint, meta};
 use crate::writer::ConFrameWriter;
 
 /// Python-visible atom data.
@@ -140,7 +140,7 @@ impl PyAtomDatum {
 }
 
 impl PyAtomDatum {
-    fn from_atom_with_mass(atom: &AtomDatum, mass: f64) -> Self {
+    fn from_atom_with_mass(atom: &AtomDatum, symbol: &str, mass: f64) -> Self {
         let (vx, vy, vz) = match atom.velocity {
             Some([x, y, z]) => (Some(x), Some(y), Some(z)),
             None => (None, None, None),
@@ -154,7 +154,7 @@ impl PyAtomDatum {
             None => (None, None, None),
         };
         PyAtomDatum {
-            symbol: atom.symbol.to_string(),
+            symbol: symbol.to_string(),
             x: atom.x,
             y: atom.y,
             z: atom.z,
@@ -394,6 +394,104 @@ impl PyConFrame {
         self.atoms.bind(py).len()
     }
 
+    /// Jupyter rich display: composition, cell, energy and the first atoms.
+    fn _repr_html_(&self, py: Python<'_>) -> PyResult<String> {
+        let atoms = self.py_atoms(py)?;
+        let mut composition: Vec<(String, usize)> = Vec::new();
+        for atom in &atoms {
+            match composition.iter_mut().find(|(sym, _)| *sym == atom.symbol) {
+                Some((_, n)) => *n += 1,
+                None => composition.push((atom.symbol.clone(), 1)),
+            }
+        }
+        let formula: String = composition
+            .iter()
+            .map(|(sym, n)| {
+                let sym = html_escape(sym);
+                if *n == 1 {
+                    sym
+                } else {
+                    format!("{sym}<sub>{n}</sub>")
+                }
+            })
+            .collect();
+        let n_fixed = atoms.iter().filter(|a| a.fixed.iter().any(|&f| f)).count();
+
+        let mut html = String::from("<div class=\"readcon-conframe\"><table>");
+        let mut row = |k: &str, v: String| {
+            html.push_str(&format!("<tr><th style=\"text-align:left\">{k}</th><td>{v}</td></tr>"));
+        };
+        row("formula", formula);
+        row("atoms", format!("{} ({n_fixed} fixed)", atoms.len()));
+        row(
+            "cell",
+            format!(
+                "{:.4} {:.4} {:.4} / {:.2} {:.2} {:.2}",
+                self.cell[0],
+                self.cell[1],
+                self.cell[2],
+                self.angles[0],
+                self.angles[1],
+                self.angles[2]
+            ),
+        );
+        if let Some(e) = self.energy(py)? {
+            row("energy", format!("{e:.6}"));
+        }
+        row("spec version", self.spec_version.to_string());
+        html.push_str("</table>");
+        if !atoms.is_empty() {
+            html.push_str(
+                "<details><summary>atoms</summary><table>\
+                 <tr><th>#</th><th>symbol</th><th>x</th><th>y</th><th>z</th><th>fixed</th><th>id</th></tr>",
+            );
+            for (i, a) in atoms.iter().take(CONFRAME_HTML_ROWS).enumerate() {
+                let fixed: String = a
+                    .fixed
+                    .iter()
+                    .zip(['x', 'y', 'z'])
+                    .filter(|(f, _)| **f)
+                    .map(|(_, c)| c)
+                    .collect();
+                html.push_str(&format!(
+                    "<tr><td>{i}</td><td>{}</td><td>{:.6}</td><td>{:.6}</td><td>{:.6}</td><td>{}</td><td>{}</td></tr>",
+                    html_escape(&a.symbol),
+                    a.x,
+                    a.y,
+                    a.z,
+                    fixed,
+                    a.atom_id
+                ));
+            }
+            if atoms.len() > CONFRAME_HTML_ROWS {
+                html.push_str(&format!(
+                    "<tr><td colspan=\"7\">&hellip; {} more</td></tr>",
+                    atoms.len() - CONFRAME_HTML_ROWS
+                ));
+            }
+            html.push_str("</table></details>");
+        }
+        html.push_str("</div>");
+        Ok(html)
+    }
+
+    /// 3D notebook viewer via ``nglview.show_ase`` (requires nglview and ase).
+    ///
+    /// Keyword arguments are forwarded to ``nglview.show_ase``.
+    #[pyo3(signature = (**kwargs))]
+    fn to_nglview(
+        &self,
+        py: Python<'_>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let nglview = py.import("nglview")?;
+        let atoms = ase_from_pyconframe(py, self)?;
+        Ok(nglview
+            .getattr("show_ase")?
+            .call((atoms,), kwargs)?
+            .unbind())
+    }
+
     // --- NumPy array views ---
     //
     // Each method materialises a fresh contiguous f64 ndarray sized
@@ -822,12 +920,113 @@ impl PyConFrame {
         ase_from_pyconframe(py, self)
     }
 
+    /// ``ase.db`` row dict for this frame (no ase import needed);
+    /// ``ase.db.row.AtomsRow(d).toatoms()`` turns it into Atoms.
+    fn to_ase_dict(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let frame = self.to_con_frame(py)?;
+        let dict = crate::formats::ase::frame_to_ase_dict(&frame)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        json_value_to_py(py, &dict)
+    }
+
     /// Create a ConFrame from an ASE Atoms object.
     #[staticmethod]
     fn from_ase(py: Python<'_>, ase_atoms: &Bound<'_, PyAny>) -> PyResult<Self> {
         pyconframe_from_ase(py, ase_atoms)
     }
 
+    /// Strict constructor from NumPy-style arrays.
+    ///
+    /// ``positions`` must have shape ``(N, 3)`` and ``symbols`` length ``N``;
+    /// optional ``masses`` / ``atom_ids`` are length ``N``, ``fixed`` is
+    /// ``(N, 3)`` bool, and ``velocities`` / ``forces`` are ``(N, 3)``.
+    /// Numeric inputs must have an integer or floating dtype. A ``(3, N)``
+    /// positions array is rejected with a hint instead of being silently
+    /// reinterpreted, which would scramble atoms in the written ``.con``.
+    ///
+    /// Raises ``ValueError`` on shape mismatches and ``TypeError`` on
+    /// unsupported dtypes.
+    #[staticmethod]
+    #[pyo3(signature = (symbols, positions, cell, angles=None, *, masses=None, fixed=None, atom_ids=None, velocities=None, forces=None, metadata=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn from_arrays(
+        py: Python<'_>,
+        symbols: &Bound<'_, PyAny>,
+        positions: &Bound<'_, PyAny>,
+        cell: &Bound<'_, PyAny>,
+        angles: Option<&Bound<'_, PyAny>>,
+        masses: Option<&Bound<'_, PyAny>>,
+        fixed: Option<&Bound<'_, PyAny>>,
+        atom_ids: Option<&Bound<'_, PyAny>>,
+        velocities: Option<&Bound<'_, PyAny>>,
+        forces: Option<&Bound<'_, PyAny>>,
+        metadata: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let symbols = py_symbol_array(py, symbols)?;
+        let n = symbols.len();
+        let positions = py_float_rows3(py, "positions", positions, n)?;
+        let cell = py_float_vec3(py, "cell", cell)?;
+        let angles = match angles {
+            Some(obj) => py_float_vec3(py, "angles", obj)?,
+            None => [90.0; 3],
+        };
+        let masses = masses
+            .map(|obj| py_float_column(py, "masses", obj, n))
+            .transpose()?;
+        let fixed = fixed.map(|obj| py_bool_rows3(py, obj, n)).transpose()?;
+        let atom_ids = atom_ids
+            .map(|obj| py_index_column(py, "atom_ids", obj, n))
+            .transpose()?;
+        let velocities = velocities
+            .map(|obj| py_float_rows3(py, "velocities", obj, n))
+            .transpose()?;
+        let forces = forces
+            .map(|obj| py_float_rows3(py, "forces", obj, n))
+            .transpose()?;
+
+        let atoms: Vec<PyAtomDatum> = (0..n)
+            .map(|i| {
+                let [x, y, z] = positions[i];
+                let v = velocities.as_ref().map(|v| v[i]);
+                let f = forces.as_ref().map(|f| f[i]);
+                PyAtomDatum {
+                    symbol: symbols[i].clone(),
+                    x,
+                    y,
+                    z,
+                    fixed: fixed.as_ref().map_or([false; 3], |m| m[i]),
+                    atom_id: atom_ids.as_ref().map_or(i as u64, |ids| ids[i]),
+                    mass: masses.as_ref().map(|m| m[i]),
+                    vx: v.map(|v| v[0]),
+                    vy: v.map(|v| v[1]),
+                    vz: v.map(|v| v[2]),
+                    fx: f.map(|f| f[0]),
+                    fy: f.map(|f| f[1]),
+                    fz: f.map(|f| f[2]),
+                    energy: None,
+                    charge: None,
+                    spin: None,
+                    mx: None,
+                    my: None,
+                    mz: None,
+                }
+            })
+            .collect();
+        let metadata = match metadata {
+            Some(obj) => json_map_to_py_dict(py, &py_metadata_to_json_map(obj)?)?,
+            None => PyDict::new(py).unbind(),
+        };
+        Ok(PyConFrame {
+            cell,
+            angles,
+            prebox_header: Default::default(),
+            postbox_header: Default::default(),
+            atoms: py_atoms_to_list(py, atoms)?,
+            spec_version: crate::CON_SPEC_VERSION,
+            metadata,
+        })
+    }
+
     /// Chemfiles selection on this frame (see module :func:`select_on_frame`).
     ///
     /// Requires a chemfiles-linked build (``readcon-chemfiles`` / ``--features chemfiles``).
@@ -852,6 +1051,213 @@ impl PyConFrame {
             .map_err(|e| PyIOError::new_err(e.to_string()))?;
         Ok(())
     }
+
+    /// Structural diff against another frame (atoms paired by index).
+    ///
+    /// Returns a ``FrameDiff`` with cell/position/metadata differences; it
+    /// renders as a table in Jupyter.
+    #[pyo3(signature = (other, tolerance=1e-6))]
+    fn diff(
+        &self,
+        py: Python<'_>,
+        other: PyRef<'_, PyConFrame>,
+        tolerance: f64,
+    ) -> PyResult<PyFrameDiff> {
+        let a = self.to_con_frame(py)?;
+        let b = other.to_con_frame(py)?;
+        Ok(PyFrameDiff {
+            inner: a.diff(&b, tolerance),
+        })
+    }
+}
+
+/// Result of ``ConFrame.diff``; wraps [`crate::diff::FrameDiff`].
+#[pyclass(name = "FrameDiff", frozen)]
+struct PyFrameDiff {
+    inner: crate::diff::FrameDiff,
+}
+
+/// Atom rows shown by `ConFrame._repr_html_` before truncating.
+const CONFRAME_HTML_ROWS: usize = 20;
+
+/// Moved-atom rows shown by `FrameDiff._repr_html_` before truncating.
+const FRAME_DIFF_HTML_ROWS: usize = 50;
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[pymethods]
+impl PyFrameDiff {
+    #[getter]
+    fn tolerance(&self) -> f64 {
+        self.inner.tolerances.coordinate
+    }
+
+    #[getter]
+    fn natoms(&self) -> (usize, usize) {
+        self.inner.natoms
+    }
+
+    #[getter]
+    fn cell_delta(&self) -> [f64; 3] {
+        self.inner.cell_delta
+    }
+
+    #[getter]
+    fn angles_delta(&self) -> [f64; 3] {
+        self.inner.angles_delta
+    }
+
+    #[getter]
+    fn symbol_mismatches(&self) -> Vec<usize> {
+        self.inner.symbol_mismatches.clone()
+    }
+
+    #[getter]
+    fn fixed_mismatches(&self) -> Vec<usize> {
+        self.inner.fixed_mismatches.clone()
+    }
+
+    #[getter]
+    fn mass_mismatches(&self) -> Vec<usize> {
+        self.inner.mass_mismatches.clone()
+    }
+
+    /// Moved atoms as ``(index, atom_id, symbol, (dx, dy, dz), distance)`` tuples.
+    #[getter]
+    fn moved(&self) -> Vec<(usize, u64, String, [f64; 3], f64)> {
+        self.inner
+            .moved
+            .iter()
+            .map(|m| (m.index, m.atom_id, m.symbol.clone(), m.displacement, m.distance))
+            .collect()
+    }
+
+    #[getter]
+    fn max_displacement(&self) -> f64 {
+        self.inner.max_displacement
+    }
+
+    #[getter]
+    fn rms_displacement(&self) -> f64 {
+        self.inner.rms_displacement
+    }
+
+    #[getter]
+    fn metadata_changed(&self) -> Vec<String> {
+        self.inner.metadata_changed.clone()
+    }
+
+    #[getter]
+    fn energy_delta(&self) -> Option<f64> {
+        self.inner.energy_delta
+    }
+
+    fn is_identical(&self) -> bool {
+        self.inner.is_identical()
+    }
+
+    fn __bool__(&self) -> bool {
+        !self.inner.is_identical()
+    }
+
+    /// Plain ``dict`` view (JSON-friendly).
+    fn to_dict(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let d = PyDict::new(py);
+        d.set_item("tolerance", self.tolerance())?;
+        d.set_item("natoms", self.natoms())?;
+        d.set_item("cell_delta", self.cell_delta())?;
+        d.set_item("angles_delta", self.angles_delta())?;
+        d.set_item("symbol_mismatches", self.symbol_mismatches())?;
+        d.set_item("fixed_mismatches", self.fixed_mismatches())?;
+        d.set_item("mass_mismatches", self.mass_mismatches())?;
+        d.set_item("moved", self.moved())?;
+        d.set_item("max_displacement", self.max_displacement())?;
+        d.set_item("rms_displacement", self.rms_displacement())?;
+        d.set_item("metadata_changed", self.metadata_changed())?;
+        d.set_item("energy_delta", self.energy_delta())?;
+        d.set_item("identical", self.is_identical())?;
+        Ok(d.into())
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FrameDiff(identical={}, natoms={:?}, moved={}, max_displacement={:.6})",
+            if self.inner.is_identical() { "True" } else { "False" },
+            self.inner.natoms,
+            self.inner.moved.len(),
+            self.inner.max_displacement
+        )
+    }
+
+    /// Jupyter rich display: summary table plus the moved-atom list.
+    fn _repr_html_(&self) -> String {
+        let d = &self.inner;
+        let mut html = String::from("<div class=\"readcon-framediff\"><table>");
+        let mut row = |k: &str, v: String| {
+            html.push_str(&format!("<tr><th style=\"text-align:left\">{k}</th><td>{v}</td></tr>"));
+        };
+        row("identical", d.is_identical().to_string());
+        row("atoms", format!("{} &rarr; {}", d.natoms.0, d.natoms.1));
+        row(
+            "cell &Delta;",
+            format!("{:?} / angles {:?}", d.cell_delta, d.angles_delta),
+        );
+        row("max displacement", format!("{:.6}", d.max_displacement));
+        row("rms displacement", format!("{:.6}", d.rms_displacement));
+        if let Some(de) = d.energy_delta {
+            row("energy &Delta;", format!("{de:.6}"));
+        }
+        if !d.symbol_mismatches.is_empty() {
+            row("symbol mismatches", format!("{:?}", d.symbol_mismatches));
+        }
+        if !d.fixed_mismatches.is_empty() {
+            row("fixed mismatches", format!("{:?}", d.fixed_mismatches));
+        }
+        if !d.metadata_changed.is_empty() {
+            row(
+                "metadata changed",
+                html_escape(&d.metadata_changed.join(", ")),
+            );
+        }
+        html.push_str("</table>");
+        if !d.moved.is_empty() {
+            html.push_str(&format!(
+                "<details><summary>{} atom(s) moved &gt; {:e}</summary><table>\
+                 <tr><th>index</th><th>id</th><th>symbol</th><th>dx</th><th>dy</th><th>dz</th><th>|d|</th></tr>",
+                d.moved.len(),
+                d.tolerances.coordinate
+            ));
+            for m in d.moved.iter().take(FRAME_DIFF_HTML_ROWS) {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.6}</td><td>{:.6}</td><td>{:.6}</td><td>{:.6}</td></tr>",
+                    m.index,
+                    m.atom_id,
+                    html_escape(&m.symbol),
+                    m.displacement[0],
+                    m.displacement[1],
+                    m.displacement[2],
+                    m.distance
+                ));
+            }
+            if d.moved.len() > FRAME_DIFF_HTML_ROWS {
+                html.push_str(&format!(
+                    "<tr><td colspan=\"7\">&hellip; {} more</td></tr>",
+                    d.moved.len() - FRAME_DIFF_HTML_ROWS
+                ));
+            }
+            html.push_str("</table></details>");
+        }
+        html.push_str("</div>");
+        html
+    }
 }
 
 impl PyConFrame {
@@ -876,7 +1282,7 @@ impl PyConFrame {
             .enumerate()
             .map(|(i, atom)| {
                 let mass = per_atom_mass.get(i).copied().unwrap_or(0.0);
-                PyAtomDatum::from_atom_with_mass(atom, mass)
+                PyAtomDatum::from_atom_with_mass(atom, frame.symbol_of(atom), mass)
             })
             .collect();
 
@@ -1196,6 +1602,209 @@ fn write_con_string(
     String::from_utf8(buffer).map_err(|e| PyIOError::new_err(format!("utf8 error: {e}")))
 }
 
+/// Concrete output stream behind a [`PyConWriter`].
+enum PyWriterSink {
+    Plain(ConFrameWriter<std::fs::File>),
+    Gzip(ConFrameWriter<flate2::write::GzEncoder<std::fs::File>>),
+    #[cfg(feature = "zstd")]
+    Zstd(ConFrameWriter<zstd::stream::write::Encoder<'static, std::fs::File>>),
+}
+
+impl PyWriterSink {
+    fn write_frame(&mut self, frame: &ConFrame) -> std::io::Result<()> {
+        match self {
+            PyWriterSink::Plain(w) => w.write_frame(frame),
+            PyWriterSink::Gzip(w) => w.write_frame(frame),
+            #[cfg(feature = "zstd")]
+            PyWriterSink::Zstd(w) => w.write_frame(frame),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            PyWriterSink::Plain(w) => w.flush(),
+            PyWriterSink::Gzip(w) => w.flush(),
+            #[cfg(feature = "zstd")]
+            PyWriterSink::Zstd(w) => w.flush(),
+        }
+    }
+
+    /// Flush and terminate the compressed stream (if any).
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            PyWriterSink::Plain(w) => w.into_inner()?.sync_all(),
+            PyWriterSink::Gzip(w) => w.into_inner()?.finish()?.sync_all(),
+            #[cfg(feature = "zstd")]
+            PyWriterSink::Zstd(w) => w.into_inner()?.finish()?.sync_all(),
+        }
+    }
+}
+
+/// Streaming writer usable as a context manager:
+///
+/// ```python
+/// with readcon.ConWriter("out.con.gz", append=True, precision=9) as w:
+///     w.write(frame)
+/// ```
+///
+/// `compression` is `"gzip"`/`"gz"`, `"zstd"`/`"zst"` (zstd builds only),
+/// `"none"`, or `None` to pick from the extension. With `append=True` frames
+/// go after any existing content; compressed files gain a new stream member,
+/// which the readers decode transparently.
+#[pyclass(name = "ConWriter")]
+struct PyConWriter {
+    sink: Option<PyWriterSink>,
+    path: String,
+    frames_written: usize,
+}
+
+impl PyConWriter {
+    fn sink_mut(&mut self) -> PyResult<&mut PyWriterSink> {
+        self.sink
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("I/O operation on closed ConWriter"))
+    }
+}
+
+#[pymethods]
+impl PyConWriter {
+    #[new]
+    #[pyo3(signature = (path, append=false, compression=None, precision=6, *, canonical=false))]
+    fn new(
+        path: &str,
+        append: bool,
+        compression: Option<&str>,
+        precision: usize,
+        canonical: bool,
+    ) -> PyResult<Self> {
+        use crate::compression::{Compression, detect_compression_from_extension};
+        let kind = match compression {
+            Some("gzip") | Some("gz") => Compression::Gzip,
+            Some("zstd") | Some("zst") => Compression::Zstd,
+            Some("none") => Compression::None,
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown compression: {other}. Use \"gzip\", \"zstd\" or \"none\"."
+                )));
+            }
+            None => detect_compression_from_extension(Path::new(path)),
+        };
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)
+            .map_err(|e| PyIOError::new_err(format!("failed to open {path}: {e}")))?;
+        let sink = match kind {
+            Compression::None => PyWriterSink::Plain(
+                ConFrameWriter::with_precision(file, precision).canonical(canonical),
+            ),
+            Compression::Gzip => {
+                let enc = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+                PyWriterSink::Gzip(ConFrameWriter::with_precision(enc, precision).canonical(canonical))
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                // Level 3 matches `compression::zstd_writer`.
+                let enc = zstd::stream::write::Encoder::new(file, 3)
+                    .map_err(|e| PyIOError::new_err(format!("failed to create zstd writer: {e}")))?;
+                PyWriterSink::Zstd(ConFrameWriter::with_precision(enc, precision).canonical(canonical))
+            }
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => {
+                return Err(PyIOError::new_err(
+                    "zstd output requires a readcon build with the zstd feature",
+                ));
+            }
+        };
+        Ok(PyConWriter {
+            sink: Some(sink),
+            path: path.to_owned(),
+            frames_written: 0,
+        })
+    }
+
+    /// Write one `ConFrame`.
+    fn write(&mut self, py: Python<'_>, frame: PyRef<'_, PyConFrame>) -> PyResult<()> {
+        let rust_frame = frame.to_con_frame(py)?;
+        self.sink_mut()?
+            .write_frame(&rust_frame)
+            .map_err(|e| PyIOError::new_err(format!("write error: {e}")))?;
+        self.frames_written += 1;
+        Ok(())
+    }
+
+    /// Write every `ConFrame` from an iterable.
+    fn extend(&mut self, py: Python<'_>, frames: &Bound<'_, PyAny>) -> PyResult<()> {
+        for frame in py_frames_to_rust(py, frames)? {
+            self.sink_mut()?
+                .write_frame(&frame)
+                .map_err(|e| PyIOError::new_err(format!("write error: {e}")))?;
+            self.frames_written += 1;
+        }
+        Ok(())
+    }
+
+    /// Push buffered output to the OS (compressed streams stay open).
+    fn flush(&mut self) -> PyResult<()> {
+        self.sink_mut()?
+            .flush()
+            .map_err(|e| PyIOError::new_err(format!("flush error: {e}")))
+    }
+
+    /// Flush, finish any compressed stream and close the file. Idempotent.
+    fn close(&mut self, py: Python<'_>) -> PyResult<()> {
+        match self.sink.take() {
+            Some(sink) => py
+                .detach(|| sink.finish())
+                .map_err(|e| PyIOError::new_err(format!("close error: {e}"))),
+            None => Ok(()),
+        }
+    }
+
+    #[getter]
+    fn closed(&self) -> bool {
+        self.sink.is_none()
+    }
+
+    #[getter]
+    fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Number of frames written through this writer (not counting appended-to content).
+    #[getter]
+    fn frames_written(&self) -> usize {
+        self.frames_written
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &mut self,
+        py: Python<'_>,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.close(py)?;
+        Ok(false)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ConWriter(path={:?}, frames_written={}, closed={})",
+            self.path,
+            self.frames_written,
+            self.sink.is_none()
+        )
+    }
+}
+
 /// Read a .con file and return a list of ASE Atoms objects.
 /// Requires the ase package.
 #[pyfunction]
@@ -1325,12 +1934,12 @@ fn ase_from_pyconframe(py: Python<'_>, frame: &PyConFrame) -> PyResult<Py<PyAny>
         .map(|(i, _)| i)
         .collect();
 
-    let partial_fixed: Vec<(usize, [bool; 3])> = frame_atoms
-        .iter()
-        .enumerate()
-        .filter(|(_, a)| a.is_fixed() && a.fixed != [true, true, true])
-        .map(|(i, a)| (i, a.fixed))
-        .collect();
+    let mut partial_fixed: BTreeMap<[bool; 3], Vec<usize>> = BTreeMap::new();
+    for (i, atom) in frame_atoms.iter().enumerate() {
+        if let Constraint::FixedAxes(mask) = Constraint::from_mask(atom.fixed) {
+            partial_fixed.entry(mask).or_default().push(i);
+        }
+    }
 
     if !fixed_indices.is_empty() || !partial_fixed.is_empty() {
         let ase_constraints = py.import("ase.constraints")?;
@@ -1342,11 +1951,14 @@ fn ase_from_pyconframe(py: Python<'_>, frame: &PyConFrame) -> PyResult<Py<PyAny>
             )?;
             constraints.push(fix_atoms.unbind());
         }
-        for (index, mask) in partial_fixed {
-            let fix_cartesian = ase_constraints
-                .getattr("FixCartesian")?
-                .call1((index, mask))?;
-            constraints.push(fix_cartesian.unbind());
+        for (mask, indices) in partial_fixed {
+            let Some((name, direction)) = Constraint::FixedAxes(mask).ase_constraint() else {
+                continue;
+            };
+            let constraint = ase_constraints
+                .getattr(name)?
+                .call1((indices, direction.to_vec()))?;
+            constraints.push(constraint.unbind());
         }
         atoms.call_method1("set_constraint", (constraints,))?;
     }
@@ -1374,6 +1986,180 @@ fn py_usize_values(obj: &Bound<'_, PyAny>) -> PyResult<Vec<usize>> {
     ))
 }
 
+// --- Strict array validation for `ConFrame.from_arrays` ---
+
+/// `numpy.asarray(obj)` plus its shape and dtype kind character.
+fn py_asarray<'py>(
+    py: Python<'py>,
+    obj: &Bound<'py, PyAny>,
+) -> PyResult<(Bound<'py, PyAny>, Vec<usize>, String)> {
+    let arr = py.import("numpy")?.call_method1("asarray", (obj,))?;
+    let shape: Vec<usize> = arr.getattr("shape")?.extract()?;
+    let kind: String = arr.getattr("dtype")?.getattr("kind")?.extract()?;
+    Ok((arr, shape, kind))
+}
+
+fn shape_str(shape: &[usize]) -> String {
+    let dims: Vec<String> = shape.iter().map(|d| d.to_string()).collect();
+    if dims.len() == 1 {
+        format!("({},)", dims[0])
+    } else {
+        format!("({})", dims.join(", "))
+    }
+}
+
+fn require_numeric_kind(name: &str, kind: &str) -> PyResult<()> {
+    if matches!(kind, "f" | "i" | "u") {
+        Ok(())
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "{name} must have an integer or floating dtype, got dtype kind '{kind}'"
+        )))
+    }
+}
+
+fn py_float_flat(arr: &Bound<'_, PyAny>) -> PyResult<Vec<f64>> {
+    arr.call_method1("astype", ("float64",))?
+        .call_method0("ravel")?
+        .call_method0("tolist")?
+        .extract()
+}
+
+/// Validated `(n, 3)` float rows. A `(3, n)` input is reported as transposed.
+fn py_float_rows3(
+    py: Python<'_>,
+    name: &str,
+    obj: &Bound<'_, PyAny>,
+    n: usize,
+) -> PyResult<Vec<[f64; 3]>> {
+    let (arr, shape, kind) = py_asarray(py, obj)?;
+    require_numeric_kind(name, &kind)?;
+    if shape != [n, 3] {
+        let hint = if shape == [3, n] {
+            " (array looks transposed; pass arr.T)"
+        } else {
+            ""
+        };
+        return Err(PyValueError::new_err(format!(
+            "{name} must have shape ({n}, 3), got {}{hint}",
+            shape_str(&shape)
+        )));
+    }
+    let flat = py_float_flat(&arr)?;
+    if flat.iter().any(|v| !v.is_finite()) {
+        return Err(PyValueError::new_err(format!(
+            "{name} must contain only finite values"
+        )));
+    }
+    Ok(flat.chunks_exact(3).map(|r| [r[0], r[1], r[2]]).collect())
+}
+
+/// Validated length-`n` float column.
+fn py_float_column(
+    py: Python<'_>,
+    name: &str,
+    obj: &Bound<'_, PyAny>,
+    n: usize,
+) -> PyResult<Vec<f64>> {
+    let (arr, shape, kind) = py_asarray(py, obj)?;
+    require_numeric_kind(name, &kind)?;
+    if shape != [n] {
+        return Err(PyValueError::new_err(format!(
+            "{name} must have shape ({n},), got {}",
+            shape_str(&shape)
+        )));
+    }
+    py_float_flat(&arr)
+}
+
+/// Validated length-3 float vector (cell lengths / angles).
+fn py_float_vec3(py: Python<'_>, name: &str, obj: &Bound<'_, PyAny>) -> PyResult<[f64; 3]> {
+    let v = py_float_column(py, name, obj, 3)?;
+    Ok([v[0], v[1], v[2]])
+}
+
+/// Validated length-`n` non-negative integer column.
+fn py_index_column(
+    py: Python<'_>,
+    name: &str,
+    obj: &Bound<'_, PyAny>,
+    n: usize,
+) -> PyResult<Vec<u64>> {
+    let (arr, shape, kind) = py_asarray(py, obj)?;
+    if !matches!(kind.as_str(), "i" | "u") {
+        return Err(PyTypeError::new_err(format!(
+            "{name} must have an integer dtype, got dtype kind '{kind}'"
+        )));
+    }
+    if shape != [n] {
+        return Err(PyValueError::new_err(format!(
+            "{name} must have shape ({n},), got {}",
+            shape_str(&shape)
+        )));
+    }
+    let values = arr.call_method0("tolist")?;
+    // Unsigned columns may hold ids past i64::MAX; signed ones only need
+    // the sign check.
+    if kind == "u" {
+        return values.extract();
+    }
+    let values: Vec<i64> = values.extract()?;
+    values
+        .into_iter()
+        .map(|v| {
+            u64::try_from(v)
+                .map_err(|_| PyValueError::new_err(format!("{name} must be non-negative")))
+        })
+        .collect()
+}
+
+/// Validated `(n, 3)` bool mask rows.
+fn py_bool_rows3(py: Python<'_>, obj: &Bound<'_, PyAny>, n: usize) -> PyResult<Vec<[bool; 3]>> {
+    let (arr, shape, kind) = py_asarray(py, obj)?;
+    if kind != "b" {
+        return Err(PyTypeError::new_err(format!(
+            "fixed must have a bool dtype, got dtype kind '{kind}'"
+        )));
+    }
+    if shape != [n, 3] {
+        return Err(PyValueError::new_err(format!(
+            "fixed must have shape ({n}, 3), got {}",
+            shape_str(&shape)
+        )));
+    }
+    let rows: Vec<[bool; 3]> = arr.call_method0("tolist")?.extract()?;
+    Ok(rows)
+}
+
+/// Validated 1-D sequence of element symbols.
+fn py_symbol_array(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    if obj.is_instance_of::<pyo3::types::PyString>() {
+        return Err(PyTypeError::new_err(
+            "symbols must be a sequence of strings, not a single string",
+        ));
+    }
+    let (arr, shape, kind) = py_asarray(py, obj)?;
+    if shape.len() != 1 {
+        return Err(PyValueError::new_err(format!(
+            "symbols must be one-dimensional, got shape {}",
+            shape_str(&shape)
+        )));
+    }
+    if !matches!(kind.as_str(), "U" | "O") {
+        return Err(PyTypeError::new_err(format!(
+            "symbols must be strings, got dtype kind '{kind}'"
+        )));
+    }
+    let symbols: Vec<String> = arr
+        .call_method0("tolist")?
+        .extract()
+        .map_err(|_| PyTypeError::new_err("symbols must contain only strings"))?;
+    if let Some(i) = symbols.iter().position(|s| s.trim().is_empty()) {
+        return Err(PyValueError::new_err(format!("symbols[{i}] is empty")));
+    }
+    Ok(symbols)
+}
+
 fn py_bool_mask(obj: &Bound<'_, PyAny>) -> PyResult<[bool; 3]> {
     let values: Vec<bool> = if let Ok(list) = obj.call_method0("tolist") {
         list.extract()?
@@ -1433,6 +2219,22 @@ fn pyconframe_from_ase(py: Python<'_>, ase_atoms: &Bound<'_, PyAny>) -> PyResult
                     *fixed_mask = mask;
                 }
             }
+        } else if type_name == "FixedPlane" || type_name == "FixedLine" {
+            let direction: [f64; 3] = constraint
+                .getattr("dir")?
+                .call_method0("tolist")?
+                .extract()?;
+            // Only axis-aligned directions map onto a per-axis mask.
+            let Some(c) = Constraint::from_ase_constraint(&type_name, direction) else {
+                continue;
+            };
+            let mask = c.mask();
+            let index_obj = constraint.getattr("index")?;
+            for index in py_usize_values(&index_obj)? {
+                if let Some(fixed_mask) = fixed_masks.get_mut(index) {
+                    *fixed_mask = mask;
+                }
+            }
         }
     }
 
@@ -1557,6 +2359,8 @@ fn readcon(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAtomDatum>()?;
     m.add_class::<PyConFrame>()?;
     m.add_class::<PyConFrameIterator>()?;
+    m.add_class::<PyConWriter>()?;
+    m.add_class::<PyFrameDiff>()?;
     m.add_function(wrap_pyfunction!(read_con, m)?)?;
     // Ergonomic alias for multi-language matrix (batch all frames).
     m.add_function(wrap_pyfunction!(read_all_frames, m)?)?;
@@ -0,0 +1,296 @@
+//! Single-frame reads by byte range.
+//!
+//! With the frame offsets of a trajectory at hand (from
+//! [`frame_byte_spans`](crate::index_proj::frame_byte_spans), computed once
+//! and stored next to the file), [`read_frame_at`] fetches and parses just
+//! one frame's bytes instead of the whole file. The bytes come from a
+//! [`RangeSource`]: a local [`File`] (seek + read), an in-memory buffer, or,
+//! with the `http` feature, a URL fetched with `Range: bytes=start-(end-1)`
+//! requests (`HttpRange`; presigned S3 URLs work the same way). Other
+//! stores plug in by implementing the trait.
+//!
+//! Ranges index the uncompressed text, so this only applies to plain CON
+//! files; gzip and zstd streams cannot be entered mid-way.
+
+use crate::index_proj::FrameByteSpan;
+use crate::iterators::ConFrameIterator;
+use crate::types::ConFrame;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Byte-addressable storage that can return an arbitrary `[start, end)`
+/// range.
+pub trait RangeSource {
+    /// The bytes `start..end`. Errors with
+    /// [`io::ErrorKind::UnexpectedEof`] when the range runs past the end.
+    fn read_range(&mut self, start: u64, end: u64) -> io::Result<Vec<u8>>;
+}
+
+impl RangeSource for File {
+    fn read_range(&mut self, start: u64, end: u64) -> io::Result<Vec<u8>> {
+        let len = usize::try_from(end.saturating_sub(start))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let mut buf = vec![0; len];
+        self.seek(SeekFrom::Start(start))?;
+        self.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+impl RangeSource for [u8] {
+    fn read_range(&mut self, start: u64, end: u64) -> io::Result<Vec<u8>> {
+        usize::try_from(start)
+            .ok()
+            .zip(usize::try_from(end).ok())
+            .and_then(|(s, e)| self.get(s..e))
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("range {start}..{end} outside {} bytes", self.len()),
+                )
+            })
+    }
+}
+
+/// A file served over HTTP, read with one `Range` request per call.
+///
+/// The server must answer `206 Partial Content`; a `200` means it ignored
+/// the range and is reported as [`io::ErrorKind::Unsupported`] rather than
+/// downloading the whole file. Only `http://` URLs work out of the box:
+/// ureq is built without TLS here, so enable its `rustls` feature in the
+/// application for `https://`, or pass a configured agent to
+/// [`HttpRange::with_agent`].
+#[cfg(feature = "http")]
+pub struct HttpRange {
+    agent: ureq::Agent,
+    url: String,
+}
+
+#[cfg(feature = "http")]
+impl HttpRange {
+    /// `url` read through a default ureq agent.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_agent(ureq::Agent::new_with_defaults(), url)
+    }
+
+    /// `url` read through `agent` (timeouts, proxies, TLS).
+    pub fn with_agent(agent: ureq::Agent, url: impl Into<String>) -> Self {
+        Self {
+            agent,
+            url: url.into(),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl RangeSource for HttpRange {
+    fn read_range(&mut self, start: u64, end: u64) -> io::Result<Vec<u8>> {
+        if end <= start {
+            return Ok(Vec::new());
+        }
+        let len = end - start;
+        let mut response = self
+            .agent
+            .get(&self.url)
+            .header("Range", format!("bytes={start}-{}", end - 1))
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::StatusCode(416) => io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("range {start}..{end} outside {}", self.url),
+                ),
+                e => e.into_io(),
+            })?;
+        if response.status() != 206 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "{} answered {} to a range request, expected 206",
+                    self.url,
+                    response.status()
+                ),
+            ));
+        }
+        // ureq rejects a body that reaches the limit, so allow one extra
+        // byte and let the length check below report an oversized answer.
+        let bytes = response
+            .body_mut()
+            .with_config()
+            .limit(len + 1)
+            .read_to_vec()
+            .map_err(ureq::Error::into_io)?;
+        if bytes.len() as u64 != len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "range {start}..{end} of {} returned {} bytes",
+                    self.url,
+                    bytes.len()
+                ),
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+/// Fetches `span` from `source` and parses the single frame it holds.
+///
+/// # Example
+/// ```
+/// use readcon_core::index_proj::frame_byte_spans;
+/// use readcon_core::range_read::read_frame_at;
+/// let text = std::fs::read_to_string(concat!(
+///     env!("CARGO_MANIFEST_DIR"),
+///     "/resources/test/tiny_multi_cuh2.con"
+/// ))
+/// .unwrap();
+/// let spans = frame_byte_spans(&text).unwrap();
+/// let mut file = std::fs::File::open(concat!(
+///     env!("CARGO_MANIFEST_DIR"),
+///     "/resources/test/tiny_multi_cuh2.con"
+/// ))
+/// .unwrap();
+/// let last = read_frame_at(&mut file, spans[spans.len() - 1]).unwrap();
+/// assert_eq!(last.atom_data.len(), 4);
+/// ```
+pub fn read_frame_at<S: RangeSource + ?Sized>(
+    source: &mut S,
+    span: FrameByteSpan,
+) -> Result<ConFrame, Box<dyn std::error::Error>> {
+    let bytes = source.read_range(span.start as u64, span.end as u64)?;
+    let text = std::str::from_utf8(&bytes)?;
+    match ConFrameIterator::new(text).next() {
+        Some(frame) => Ok(frame?),
+        None => Err(format!("no frame in bytes {}..{}", span.start, span.end).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index_proj::frame_byte_spans;
+    use crate::test_util::fixture;
+
+    /// Counts the bytes handed out, like a metered remote store.
+    struct Metered<'a> {
+        data: &'a [u8],
+        fetched: u64,
+    }
+
+    impl RangeSource for Metered<'_> {
+        fn read_range(&mut self, start: u64, end: u64) -> io::Result<Vec<u8>> {
+            self.fetched += end - start;
+            Ok(self.data[start as usize..end as usize].to_vec())
+        }
+    }
+
+    #[test]
+    fn fetches_only_the_requested_frame() {
+        let text = fixture("tiny_multi_cuh2.con");
+        let spans = frame_byte_spans(&text).unwrap();
+        let expected: Vec<ConFrame> = ConFrameIterator::new(&text).map(Result::unwrap).collect();
+        assert!(spans.len() > 1);
+
+        let mut source = Metered {
+            data: text.as_bytes(),
+            fetched: 0,
+        };
+        let frame = read_frame_at(&mut source, spans[1]).unwrap();
+        assert_eq!(frame.atom_data, expected[1].atom_data);
+        assert_eq!(source.fetched, spans[1].len() as u64);
+
+        let past = FrameByteSpan {
+            start: text.len(),
+            end: text.len() + 10,
+        };
+        assert!(read_frame_at(text.as_bytes().to_vec().as_mut_slice(), past).is_err());
+    }
+
+    /// Serves `body` on a local port, honouring `Range: bytes=a-b`, and
+    /// records each requested range.
+    #[cfg(feature = "http")]
+    fn serve_ranges(
+        body: Vec<u8>,
+        requests: usize,
+    ) -> (String, std::thread::JoinHandle<Vec<(u64, u64)>>) {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/traj.con", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut seen = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut range = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(v) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        let (a, b) = v.split_once('-').unwrap();
+                        range = Some((a.parse::<u64>().unwrap(), b.parse::<u64>().unwrap()));
+                    }
+                }
+                let (a, b) = range.expect("range header");
+                seen.push((a, b));
+                let (head, part): (String, &[u8]) = if a as usize >= body.len() {
+                    let head = format!(
+                        "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    );
+                    (head, &[])
+                } else {
+                    let b = b.min(body.len() as u64 - 1);
+                    let part = &body[a as usize..=b as usize];
+                    let head = format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {a}-{b}/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len(),
+                        part.len()
+                    );
+                    (head, part)
+                };
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(part).unwrap();
+            }
+            seen
+        });
+        (url, handle)
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn http_range_reads_one_frame() {
+        let text = fixture("tiny_multi_cuh2.con");
+        let spans = frame_byte_spans(&text).unwrap();
+        let expected: Vec<ConFrame> = ConFrameIterator::new(&text).map(Result::unwrap).collect();
+
+        let (url, server) = serve_ranges(text.clone().into_bytes(), 2);
+        let mut source = HttpRange::new(url);
+        let frame = read_frame_at(&mut source, spans[1]).unwrap();
+        assert_eq!(frame, expected[1]);
+
+        let past = FrameByteSpan {
+            start: text.len(),
+            end: text.len() + 10,
+        };
+        let err = source
+            .read_range(past.start as u64, past.end as u64)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let seen = server.join().unwrap();
+        assert_eq!(
+            seen,
+            vec![
+                (spans[1].start as u64, spans[1].end as u64 - 1),
+                (text.len() as u64, text.len() as u64 + 9),
+            ]
+        );
+    }
+}
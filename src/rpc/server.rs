@@ -60,7 +60,7 @@ impl read_con_service::Server for ReadConServiceImpl {
             for (k, atom) in frame.atom_data.iter().enumerate() {
                 let mut ab = atoms_builder.reborrow().get(k as u32);
                 let [vx, vy, vz] = atom.velocity.unwrap_or([0.0; 3]);
-                ab.set_symbol(&*atom.symbol);
+                ab.set_symbol(frame.symbol_of(atom));
                 ab.set_x(atom.x);
                 ab.set_y(atom.y);
                 ab.set_z(atom.z);
@@ -81,8 +81,7 @@ impl read_con_service::Server for ReadConServiceImpl {
         params: read_con_service::WriteFramesParams,
         mut results: read_con_service::WriteFramesResults,
     ) -> Promise<(), capnp::Error> {
-        use crate::types::{AtomDatum, ConFrame, FrameHeader};
-        use std::sync::Arc;
+        use crate::types::{AtomDatum, ConFrame, FrameHeader, SymbolId, intern_symbol};
 
         let req = pry!(params.get());
         let frame_data_list = pry!(pry!(req.get_req()).get_frames());
@@ -127,21 +126,29 @@ impl read_con_service::Server for ReadConServiceImpl {
             let mut atom_data = Vec::with_capacity(atoms_list.len() as usize);
             let mut natms_per_type: Vec<usize> = Vec::new();
             let mut masses_per_type: Vec<f64> = Vec::new();
-            let mut current_symbol = String::new();
+            // Each run of same-symbol atoms is one component.
+            let mut symbol_table: Vec<String> = Vec::new();
+            let mut current_symbol = "";
+            let mut current_id = SymbolId::default();
             let mut current_count: usize = 0;
 
             for j in 0..atoms_list.len() {
                 let a = atoms_list.get(j);
-                let sym = pry!(a.get_symbol())
-                    .to_str()
-                    .unwrap_or_default()
-                    .to_string();
+                let sym = pry!(a.get_symbol()).to_str().unwrap_or_default();
 
-                if sym != current_symbol {
+                if current_count == 0 || sym != current_symbol {
                     if current_count > 0 {
                         natms_per_type.push(current_count);
                     }
-                    current_symbol = sym.clone();
+                    current_symbol = sym;
+                    current_id = match intern_symbol(&mut symbol_table, sym) {
+                        Some(id) => id,
+                        None => {
+                            return Promise::err(capnp::Error::failed(
+                                "more than 65536 distinct symbols".into(),
+                            ));
+                        }
+                    };
                     current_count = 0;
                     masses_per_type.push(0.0); // mass not in schema atoms
                 }
@@ -149,7 +156,7 @@ impl read_con_service::Server for ReadConServiceImpl {
 
                 let has_vel = a.get_has_velocity();
                 atom_data.push(AtomDatum {
-                    symbol: Arc::from(sym),
+                    symbol: current_id,
                     x: a.get_x(),
                     y: a.get_y(),
                     z: a.get_z(),
@@ -169,6 +176,7 @@ impl read_con_service::Server for ReadConServiceImpl {
                     charge: None,
                     spin: None,
                     magmom: None,
+                    extra: Vec::new(),
                 });
             }
             if current_count > 0 {
@@ -183,6 +191,7 @@ impl read_con_service::Server for ReadConServiceImpl {
                 natm_types: natms_per_type.len(),
                 natms_per_type,
                 masses_per_type,
+                component_labels: Vec::new(),
                 spec_version: crate::CON_SPEC_VERSION,
                 metadata: std::collections::BTreeMap::new(),
                 sections: Vec::new(),
@@ -190,7 +199,11 @@ impl read_con_service::Server for ReadConServiceImpl {
                 sections_declared: false,
             };
 
-            frames.push(crate::types::con_frame_from_atom_data(header, atom_data));
+            frames.push(crate::types::con_frame_from_atom_data(
+                header,
+                symbol_table,
+                atom_data,
+            ));
         }
 
         let mut buffer: Vec<u8> = Vec::new();
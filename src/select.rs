@@ -0,0 +1,192 @@
+//! Atom selection producing consistent sub-frames.
+//!
+//! Slicing `atom_data` by hand leaves `natms_per_type`, `masses_per_type`,
+//! the SoA arrays and the `bonds` metadata describing atoms that are gone.
+//! [`ConFrame::select`] and [`ConFrame::select_indices`] build a new frame
+//! from the kept atoms instead, so it writes back as a valid CON file.
+//! Index lists from
+//! [`chemfiles_selection::select_atom_indices`](crate::chemfiles_selection::select_atom_indices)
+//! plug straight into [`ConFrame::select_indices`].
+
+use crate::error::ParseError;
+use crate::properties::gather_properties;
+use crate::types::{AtomDatum, Bond, ConFrame, FrameHeader, con_frame_from_atom_data, meta};
+
+impl ConFrame {
+    /// Sub-frame of the atoms for which `keep` returns `true`, in their
+    /// original order.
+    ///
+    /// - Types left without atoms are removed from the header, with their
+    ///   masses.
+    /// - Bonds between two kept atoms are renumbered; other bonds are
+    ///   dropped.
+    /// - The `energy` metadata key is dropped, since the total no longer
+    ///   describes the selection; other metadata, per-atom sections and
+    ///   [`properties`](ConFrame::properties) are kept.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("Cu", 0.0, 0.0, 0.0, [true; 3], 0, 63.546);
+    /// b.add_atom("Cu", 2.5, 0.0, 0.0, [false; 3], 1, 63.546);
+    /// b.add_atom("H", 1.0, 1.0, 1.0, [false; 3], 2, 1.008);
+    /// let frame = b.build();
+    /// let free_cu = frame.select(|a| frame.symbol_of(a) == "Cu" && !a.is_fixed());
+    /// assert_eq!(free_cu.header.natms_per_type, vec![1]);
+    /// assert_eq!(free_cu.header.masses_per_type, vec![63.546]);
+    /// assert_eq!(*free_cu.atom_ids(), [1]);
+    /// ```
+    pub fn select<F: FnMut(&AtomDatum) -> bool>(&self, keep: F) -> ConFrame {
+        let mask: Vec<bool> = self.atom_data.iter().map(keep).collect();
+        self.subframe(&mask)
+    }
+
+    /// Sub-frame of the atoms at `indices` (into `atom_data`); see
+    /// [`Self::select`]. Atoms keep their original order whatever the
+    /// order of `indices`, since CON files need type-grouped atoms;
+    /// repeated indices select an atom once.
+    ///
+    /// Errors are [`ParseError::ValidationError`] for an index past the
+    /// last atom.
+    pub fn select_indices(&self, indices: &[usize]) -> Result<ConFrame, ParseError> {
+        let n = self.atom_data.len();
+        let mut mask = vec![false; n];
+        for &i in indices {
+            *mask.get_mut(i).ok_or_else(|| {
+                ParseError::ValidationError(format!("atom index {i} out of range for {n} atoms"))
+            })? = true;
+        }
+        Ok(self.subframe(&mask))
+    }
+
+    fn subframe(&self, mask: &[bool]) -> ConFrame {
+        let mut header = self.header.clone();
+        header.natms_per_type.clear();
+        header.masses_per_type.clear();
+        header.component_labels.clear();
+        let mut atom_data = Vec::with_capacity(mask.iter().filter(|k| **k).count());
+        let mut start = 0;
+        for (t, &count) in self.header.natms_per_type.iter().enumerate() {
+            let end = (start + count).min(self.atom_data.len());
+            let before = atom_data.len();
+            atom_data.extend(
+                self.atom_data[start..end]
+                    .iter()
+                    .zip(&mask[start..end])
+                    .filter(|(_, k)| **k)
+                    .map(|(a, _)| a.clone()),
+            );
+            if atom_data.len() > before {
+                header.natms_per_type.push(atom_data.len() - before);
+                header
+                    .masses_per_type
+                    .push(self.header.masses_per_type.get(t).copied().unwrap_or(0.0));
+                // Custom labels follow their component; standard ones are
+                // renumbered.
+                if let Some(label) = self.header.component_labels.get(t) {
+                    let k = header.natms_per_type.len() - 1;
+                    header.component_labels.push(
+                        if *label == FrameHeader::standard_component_label(t) {
+                            FrameHeader::standard_component_label(k)
+                        } else {
+                            label.clone()
+                        },
+                    );
+                }
+            }
+            start = end;
+        }
+        header.natm_types = header.natms_per_type.len();
+        if header
+            .component_labels
+            .iter()
+            .enumerate()
+            .all(|(k, label)| *label == FrameHeader::standard_component_label(k))
+        {
+            header.component_labels.clear();
+        }
+
+        let mut next = 0u32;
+        let new_index: Vec<Option<u32>> = mask
+            .iter()
+            .map(|&k| {
+                k.then(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect();
+        remap_bonds(&mut header, &new_index);
+        header.metadata.remove(meta::ENERGY);
+        let kept: Vec<usize> = (0..start).filter(|&i| mask[i]).collect();
+        let mut out = con_frame_from_atom_data(header, self.symbol_table.clone(), atom_data);
+        out.properties = gather_properties(&self.properties, self.atom_data.len(), &kept);
+        out
+    }
+}
+
+/// Rewrites the `bonds` metadata for reordered or removed atoms:
+/// `new_index[old]` is the atom's new index, `None` drops its bonds.
+pub(crate) fn remap_bonds(header: &mut FrameHeader, new_index: &[Option<u32>]) {
+    let bonds = header.bonds();
+    if bonds.is_empty() {
+        return;
+    }
+    let at = |i: u32| new_index.get(i as usize).copied().flatten();
+    let bonds: Vec<Bond> = bonds
+        .into_iter()
+        .filter_map(|b| {
+            Some(Bond {
+                i: at(b.i)?,
+                j: at(b.j)?,
+                order: b.order,
+            })
+        })
+        .collect();
+    header.set_bonds(&bonds);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture_frame;
+
+    #[test]
+    fn select_recomputes_header_and_arrays() {
+        let frame = fixture_frame("tiny_cuh2_vel_forces.con");
+        let h = frame.select(|a| frame.symbol_of(a) == "H");
+        assert_eq!(h.header.natm_types, 1);
+        assert_eq!(h.header.natms_per_type, vec![2]);
+        assert_eq!(h.header.masses_per_type, vec![1.00793]);
+        assert_eq!(h.header.energy(), None);
+        assert_eq!(h.positions.nrows(), 2);
+        assert_eq!(h.velocities.nrows(), 2);
+        assert_eq!(h.masses.get_f64(1), 1.00793);
+        assert_eq!(*h.atom_ids(), [2, 3]);
+
+        // The sub-frame survives a write + parse cycle.
+        let mut w = crate::writer::ConFrameWriter::new(Vec::new());
+        w.write_frame(&h).unwrap();
+        let text = String::from_utf8(w.into_inner().unwrap()).unwrap();
+        let back = ConFrameIterator::new(&text).next().unwrap().unwrap();
+        assert_eq!(back, h);
+    }
+
+    #[test]
+    fn select_indices_remaps_bonds() {
+        let mut frame = fixture_frame("tiny_cuh2_vel_forces.con");
+        frame.header.set_bonds(&[
+            Bond::new(0, 1),
+            Bond::new(1, 3).with_order(2),
+            Bond::new(2, 3),
+        ]);
+        let sub = frame.select_indices(&[3, 1, 1]).unwrap();
+        assert_eq!(sub.header.natms_per_type, vec![1, 1]);
+        assert_eq!(*sub.atom_ids(), [1, 3]);
+        assert_eq!(sub.header.bonds(), vec![Bond::new(0, 1).with_order(2)]);
+        assert!(frame.select_indices(&[4]).is_err());
+        assert!(frame.select(|_| false).atom_data.is_empty());
+    }
+}
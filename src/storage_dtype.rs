@@ -276,6 +276,19 @@ impl Array2Storage {
         Self::F64(arr.into_shared())
     }
 
+    /// The backing allocation of an f64 array, in no particular order, if
+    /// this is the only reference to it. Used to recycle position buffers
+    /// between frames without copying.
+    pub(crate) fn into_f64_buffer(self) -> Option<Vec<f64>> {
+        match self {
+            Self::F64(arr) => arr
+                .try_into_owned_nocopy()
+                .ok()
+                .map(|owned| owned.into_raw_vec_and_offset().0),
+            _ => None,
+        }
+    }
+
     pub fn kind(&self) -> ElementKind {
         match self {
             Self::F64(_) => ElementKind::Float64,
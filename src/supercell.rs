@@ -0,0 +1,232 @@
+//! Supercell expansion.
+//!
+//! [`ConFrame::make_supercell`] tiles a frame `nx × ny × nz` times along its
+//! lattice vectors. The CON type grouping is kept: every type block holds
+//! all of its periodic images, so the result writes back as a regular CON
+//! file with `natms_per_type` scaled by the number of images.
+
+use crate::error::ParseError;
+use crate::properties::gather_properties;
+use crate::types::{AtomDatum, ConFrame, con_frame_from_atom_data, meta};
+
+impl ConFrame {
+    /// The frame replicated `reps = [nx, ny, nz]` times along the lattice
+    /// vectors.
+    ///
+    /// - Box lengths (and `lattice_vectors` metadata, when present) are
+    ///   scaled by `reps`; angles are unchanged.
+    /// - Image `m` (x fastest, then y, then z) of an atom with id `id` gets
+    ///   `atom_id = m * stride + id`, where `stride` is one more than the
+    ///   largest input id, so ids stay unique and image 0 keeps the
+    ///   original ids. When that would overflow `u64` (ids near
+    ///   `u64::MAX`, as some formats write), all atoms are numbered
+    ///   `0..N` in output order instead.
+    /// - Fixed flags, velocities, forces, the other per-atom sections and
+    ///   [`properties`](ConFrame::properties) are copied to every image.
+    /// - The `energy` and `bonds` metadata keys are dropped, since they no
+    ///   longer describe the larger system; other metadata is kept.
+    ///
+    /// Errors are [`ParseError::ValidationError`] for a zero repetition or a
+    /// degenerate cell.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([3.0; 3], [90.0; 3]);
+    /// b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+    /// b.add_atom("H", 1.5, 1.5, 1.5, [false; 3], 1, 1.008);
+    /// let big = b.build().make_supercell([2, 2, 1]).unwrap();
+    /// assert_eq!(big.header.boxl, [6.0, 6.0, 3.0]);
+    /// assert_eq!(big.header.natms_per_type, vec![4, 4]);
+    /// assert_eq!(big.atom_data[1].x, 3.0);
+    /// ```
+    pub fn make_supercell(&self, reps: [usize; 3]) -> Result<ConFrame, ParseError> {
+        if reps.contains(&0) {
+            return Err(ParseError::ValidationError(format!(
+                "supercell repetitions must be positive, got {reps:?}"
+            )));
+        }
+        let cell = self.header.cell().ok_or_else(|| {
+            ParseError::ValidationError("supercell needs a non-degenerate cell".into())
+        })?;
+        let [a, b, c] = cell.matrix();
+        let too_large = || {
+            ParseError::ValidationError(format!(
+                "supercell {reps:?} of {} atoms overflows the atom count",
+                self.atom_data.len()
+            ))
+        };
+        let nimages = reps[0]
+            .checked_mul(reps[1])
+            .and_then(|n| n.checked_mul(reps[2]))
+            .ok_or_else(too_large)?;
+        let natoms = self
+            .atom_data
+            .len()
+            .checked_mul(nimages)
+            .ok_or_else(too_large)?;
+        let natms_per_type = self
+            .header
+            .natms_per_type
+            .iter()
+            .map(|n| n.checked_mul(nimages))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(too_large)?;
+        let shifts: Vec<[f64; 3]> = (0..reps[2])
+            .flat_map(|k| (0..reps[1]).flat_map(move |j| (0..reps[0]).map(move |i| [i, j, k])))
+            .map(|[i, j, k]| {
+                let (i, j, k) = (i as f64, j as f64, k as f64);
+                [
+                    i * a[0] + j * b[0] + k * c[0],
+                    i * a[1] + j * b[1] + k * c[1],
+                    i * a[2] + j * b[2] + k * c[2],
+                ]
+            })
+            .collect();
+        // `None` when the largest image id would not fit in a u64.
+        let stride = match self.atom_data.iter().map(|x| x.atom_id).max() {
+            None => Some(0),
+            Some(max) => max.checked_add(1).filter(|&s| {
+                (nimages as u64 - 1)
+                    .checked_mul(s)
+                    .and_then(|v| v.checked_add(max))
+                    .is_some()
+            }),
+        };
+
+        let mut atom_data: Vec<AtomDatum> = Vec::with_capacity(natoms);
+        let mut source = Vec::with_capacity(atom_data.capacity());
+        let mut start = 0;
+        for &count in &self.header.natms_per_type {
+            let end = (start + count).min(self.atom_data.len());
+            for (m, shift) in shifts.iter().enumerate() {
+                source.extend(start..end);
+                for atom in &self.atom_data[start..end] {
+                    let mut copy = atom.clone();
+                    copy.x += shift[0];
+                    copy.y += shift[1];
+                    copy.z += shift[2];
+                    copy.atom_id = match stride {
+                        Some(stride) => m as u64 * stride + atom.atom_id,
+                        None => atom_data.len() as u64,
+                    };
+                    atom_data.push(copy);
+                }
+            }
+            start = end;
+        }
+
+        let mut header = self.header.clone();
+        for (l, n) in header.boxl.iter_mut().zip(reps) {
+            *l *= n as f64;
+        }
+        if let Some(mut lv) = header.lattice_vectors() {
+            for (row, n) in lv.iter_mut().zip(reps) {
+                row.iter_mut().for_each(|x| *x *= n as f64);
+            }
+            header.set_lattice_vectors(lv);
+        }
+        header.natms_per_type = natms_per_type;
+        header.metadata.remove(meta::ENERGY);
+        header.metadata.remove(meta::BONDS);
+        let mut out = con_frame_from_atom_data(header, self.symbol_table.clone(), atom_data);
+        out.properties = gather_properties(&self.properties, self.atom_data.len(), &source);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::fixture_frame;
+
+    #[test]
+    fn supercell_keeps_grouping_and_sections() {
+        let mut unit = fixture_frame("tiny_cuh2_vel_forces.con");
+        unit.header.set_energy(-3.0);
+        let n = unit.atom_data.len();
+        let big = unit.make_supercell([2, 1, 3]).unwrap();
+        assert_eq!(big.atom_data.len(), 6 * n);
+        assert_eq!(big.positions.nrows(), 6 * n);
+        assert_eq!(big.masses.len(), 6 * n);
+        assert_eq!(big.header.boxl[2], 3.0 * unit.header.boxl[2]);
+        assert_eq!(big.header.energy(), None);
+        let expected: Vec<usize> = unit.header.natms_per_type.iter().map(|c| 6 * c).collect();
+        assert_eq!(big.header.natms_per_type, expected);
+
+        // Type blocks stay contiguous.
+        let mut start = 0;
+        for (t, &count) in big.header.natms_per_type.iter().enumerate() {
+            let symbol = unit.symbol(unit.header.natms_per_type[..t].iter().sum::<usize>());
+            assert!(
+                big.atom_data[start..start + count]
+                    .iter()
+                    .all(|a| big.symbol_of(a) == symbol)
+            );
+            start += count;
+        }
+
+        let mut ids: Vec<u64> = big.atom_ids().to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 6 * n);
+        assert!(
+            big.atom_data
+                .iter()
+                .all(|a| a.velocity.is_some() && a.force.is_some())
+        );
+        assert_eq!(big.velocities.nrows(), 6 * n);
+
+        // With two Cu per image, index 2 is the first Cu shifted by a.
+        let a = unit.header.cell().unwrap().matrix()[0];
+        let (p0, p1) = (&unit.atom_data[0], &big.atom_data[2]);
+        assert_eq!(p1.atom_id, 4 + p0.atom_id);
+        assert!((p1.x - p0.x - a[0]).abs() < 1e-12 && (p1.y - p0.y - a[1]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn renumbers_when_image_ids_would_overflow() {
+        use crate::types::ConFrameBuilder;
+        let mut b = ConFrameBuilder::new([3.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], u64::MAX - 1, 63.546);
+        b.add_atom("H", 1.5, 1.5, 1.5, [false; 3], 7, 1.008);
+        let big = b.build().make_supercell([2, 1, 1]).unwrap();
+        assert_eq!(*big.atom_ids(), [0, 1, 2, 3]);
+
+        // Ids that leave room for every image keep the stride scheme.
+        let mut b = ConFrameBuilder::new([3.0; 3], [90.0; 3]);
+        b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], u64::MAX / 4, 63.546);
+        let big = b.build().make_supercell([2, 1, 1]).unwrap();
+        assert_eq!(*big.atom_ids(), [u64::MAX / 4, u64::MAX / 2]);
+    }
+
+    #[test]
+    fn rejects_overflowing_repetitions() {
+        use crate::error::ParseError;
+        let unit = fixture_frame("tiny_cuh2_vel_forces.con");
+        for reps in [[usize::MAX, 2, 1], [1, usize::MAX / 2, 1]] {
+            assert!(
+                matches!(
+                    unit.make_supercell(reps),
+                    Err(ParseError::ValidationError(_))
+                ),
+                "{reps:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_zero_repetition() {
+        assert!(
+            fixture_frame("tiny_cuh2_vel_forces.con")
+                .make_supercell([1, 0, 1])
+                .is_err()
+        );
+        assert_eq!(
+            fixture_frame("tiny_cuh2_vel_forces.con")
+                .make_supercell([1, 1, 1])
+                .unwrap()
+                .atom_data,
+            fixture_frame("tiny_cuh2_vel_forces.con").atom_data
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Fixture loading shared by the unit-test modules.
+
+use crate::iterators::ConFrameIterator;
+use crate::types::ConFrame;
+use std::path::PathBuf;
+
+/// Path of `resources/test/<name>`.
+pub(crate) fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("resources/test")
+        .join(name)
+}
+
+/// Text of `resources/test/<name>`.
+pub(crate) fn fixture(name: &str) -> String {
+    let p = fixture_path(name);
+    std::fs::read_to_string(&p).unwrap_or_else(|e| panic!("read {}: {e}", p.display()))
+}
+
+/// Every frame of `resources/test/<name>`.
+pub(crate) fn fixture_frames(name: &str) -> Vec<ConFrame> {
+    ConFrameIterator::new(&fixture(name))
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|e| panic!("parse {name}: {e}"))
+}
+
+/// First frame of `resources/test/<name>`.
+pub(crate) fn fixture_frame(name: &str) -> ConFrame {
+    fixture_frames(name).swap_remove(0)
+}
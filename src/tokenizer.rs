@@ -0,0 +1,524 @@
+//! Streaming CON tokenizer.
+//!
+//! [`ConTokenizer`] walks a CON buffer line by line and yields one
+//! [`Token`] per header line or whitespace-separated field, tagged with its
+//! role ([`TokenKind`]) and its byte span in the input. Numbers are not
+//! converted until asked for ([`Token::as_f64`], [`Token::parse`]), so a
+//! consumer that only needs a few columns never pays for building
+//! [`ConFrame`](crate::types::ConFrame)s.
+//!
+//! The tokenizer follows the same frame layout as
+//! [`ConFrameIterator::forward`](crate::iterators::ConFrameIterator::forward):
+//! the nine header lines, one coordinate block per atom type, then any
+//! number of blank-separated section blocks (velocities, forces, charges,
+//! ...) of the same shape. It checks only the structure it needs to find
+//! the next token (type and atom counts); values are not validated.
+//!
+//! # Example
+//! Collect the z coordinate of every hydrogen:
+//! ```
+//! use readcon_core::tokenizer::{ConTokenizer, TokenKind};
+//! let text = std::fs::read_to_string(concat!(
+//!     env!("CARGO_MANIFEST_DIR"),
+//!     "/resources/test/tiny_cuh2.con"
+//! ))
+//! .unwrap();
+//! let mut symbol = "";
+//! let mut z = Vec::new();
+//! for token in ConTokenizer::new(&text) {
+//!     let token = token.unwrap();
+//!     match token.kind {
+//!         TokenKind::Symbol { .. } => symbol = token.text,
+//!         TokenKind::Value { column: 2, .. } if token.block == 0 && symbol == "H" => {
+//!             z.push(token.as_f64().unwrap())
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! assert_eq!(z.len(), 2);
+//! ```
+
+use crate::error::ParseError;
+use crate::parser::ParserOptions;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+/// Role of a [`Token`] within its frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Header line 1, 5 or 6 (free text), trimmed.
+    Comment,
+    /// Header line 2: the JSON metadata object, or free text in legacy
+    /// files. Trimmed.
+    Metadata,
+    /// One of the three cell lengths (header line 3).
+    CellLength { axis: usize },
+    /// One of the three cell angles (header line 4).
+    CellAngle { axis: usize },
+    /// Number of atom types (header line 7).
+    TypeCount,
+    /// Atom count of one type (header line 8).
+    AtomCount { atom_type: usize },
+    /// Mass of one type (header line 9).
+    Mass { atom_type: usize },
+    /// Blank line opening a section block.
+    SectionBreak,
+    /// Element symbol heading a type's rows, trimmed.
+    Symbol { atom_type: usize },
+    /// Block label such as `Coordinates of Component 1`, trimmed.
+    BlockLabel { atom_type: usize },
+    /// Numeric payload of an atom row: x/y/z in the coordinate block,
+    /// the section's values elsewhere. `atom` counts rows across types.
+    Value { atom: usize, column: usize },
+    /// Fixed flag column of an atom row.
+    Fixed { atom: usize },
+    /// `atom_id` column of an atom row.
+    AtomId { atom: usize },
+}
+
+/// One token; `text` is `&input[span]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Byte range of `text` in the tokenized buffer.
+    pub span: Range<usize>,
+    /// One-based line number.
+    pub line: usize,
+    /// Zero-based frame index.
+    pub frame: usize,
+    /// 0 in the header and coordinate block; `k` in the `k`-th section
+    /// block after it.
+    pub block: usize,
+}
+
+impl Token<'_> {
+    /// The token as a float (`InvalidNumberFormat` if it is not one).
+    pub fn as_f64(&self) -> Result<f64, ParseError> {
+        crate::float::parse(self.text).ok_or_else(|| self.number_error())
+    }
+
+    /// The token parsed as `T` (`InvalidNumberFormat` on failure).
+    pub fn parse<T: std::str::FromStr>(&self) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| self.number_error())
+    }
+
+    fn number_error(&self) -> ParseError {
+        ParseError::InvalidNumberFormat(format!("line {}: {:?}", self.line, self.text))
+    }
+}
+
+/// Where the next line belongs.
+#[derive(Debug, Clone, Copy)]
+enum State {
+    /// Header line `0..9` of a new frame.
+    Header(usize),
+    Symbol {
+        atom_type: usize,
+    },
+    Label {
+        atom_type: usize,
+    },
+    Row {
+        atom_type: usize,
+        left: usize,
+    },
+    /// After a full block: section break, next frame or end.
+    BlockEnd,
+    Done,
+}
+
+/// Iterator over the [`Token`]s of a CON buffer; see the
+/// [module docs](self).
+///
+/// Yields `Err` and stops on a structural error (missing lines, a
+/// non-integer count, an atom row with fewer than three fields).
+pub struct ConTokenizer<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+    frame: usize,
+    block: usize,
+    atom: usize,
+    natoms_per_type: Vec<usize>,
+    state: State,
+    pending: VecDeque<Token<'a>>,
+    options: ParserOptions,
+}
+
+impl<'a> ConTokenizer<'a> {
+    /// Tokenizer over `text`, which may hold any number of frames.
+    pub fn new(text: &'a str) -> Self {
+        ConTokenizer {
+            text,
+            pos: 0,
+            line: 0,
+            frame: 0,
+            block: 0,
+            atom: 0,
+            natoms_per_type: Vec::new(),
+            state: State::Header(0),
+            pending: VecDeque::new(),
+            options: ParserOptions::default(),
+        }
+    }
+
+    /// Skip blank and comment lines between frames as `options` says.
+    pub fn options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Byte offset of the next unread line.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The line starting at `at` as (start, end without `\r\n`, next start).
+    fn line_at(&self, at: usize) -> Option<(usize, usize, usize)> {
+        if at >= self.text.len() {
+            return None;
+        }
+        let rest = &self.text.as_bytes()[at..];
+        let (mut end, next) = match memchr::memchr(b'\n', rest) {
+            Some(i) => (at + i, at + i + 1),
+            None => (self.text.len(), self.text.len()),
+        };
+        if end > at && self.text.as_bytes()[end - 1] == b'\r' {
+            end -= 1;
+        }
+        Some((at, end, next))
+    }
+
+    fn take_line(&mut self) -> Option<(usize, usize)> {
+        let (start, end, next) = self.line_at(self.pos)?;
+        self.pos = next;
+        self.line += 1;
+        Some((start, end))
+    }
+
+    fn push(&mut self, kind: TokenKind, span: Range<usize>) {
+        self.pending.push_back(Token {
+            kind,
+            text: &self.text[span.clone()],
+            span,
+            line: self.line,
+            frame: self.frame,
+            block: self.block,
+        });
+    }
+
+    /// The whole line, trimmed, as one token.
+    fn push_line(&mut self, kind: TokenKind, (start, end): (usize, usize)) {
+        let raw = &self.text[start..end];
+        let lead = raw.len() - raw.trim_start().len();
+        let trimmed = raw.trim();
+        self.push(kind, start + lead..start + lead + trimmed.len());
+    }
+
+    /// Whitespace-separated fields of the line as byte ranges.
+    fn fields(&self, (start, end): (usize, usize)) -> Vec<Range<usize>> {
+        let bytes = self.text.as_bytes();
+        let mut out = Vec::new();
+        let mut i = start;
+        while i < end {
+            while i < end && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            let s = i;
+            while i < end && !bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            if i > s {
+                out.push(s..i);
+            }
+        }
+        out
+    }
+
+    /// Fields of a header line that must hold exactly `n` of them.
+    fn fixed_fields(
+        &self,
+        line: (usize, usize),
+        n: usize,
+    ) -> Result<Vec<Range<usize>>, ParseError> {
+        let fields = self.fields(line);
+        if fields.len() != n {
+            return Err(ParseError::InvalidVectorLength {
+                expected: n,
+                found: fields.len(),
+            });
+        }
+        Ok(fields)
+    }
+
+    fn is_blank(&self, (start, end): (usize, usize)) -> bool {
+        self.text[start..end].trim().is_empty()
+    }
+
+    /// Whether a blank line at the cursor opens a section block (blank,
+    /// symbol, `... of Component N`), as in tolerant-mode frame skipping.
+    fn section_follows(&self) -> bool {
+        let Some((s, e, next)) = self.line_at(self.pos) else {
+            return false;
+        };
+        if !self.is_blank((s, e)) {
+            return false;
+        }
+        if self.options.is_strict() {
+            return true;
+        }
+        let label = self
+            .line_at(next)
+            .and_then(|(_, _, after)| self.line_at(after));
+        label.is_some_and(|(s, e, _)| self.text[s..e].contains("of Component"))
+    }
+
+    fn skip_ignorable(&mut self) {
+        if self.options.is_strict() {
+            return;
+        }
+        while let Some((s, e, next)) = self.line_at(self.pos) {
+            if !self.options.is_ignorable(&self.text[s..e]) {
+                break;
+            }
+            self.pos = next;
+            self.line += 1;
+        }
+    }
+
+    /// Consumes one line and queues its tokens.
+    fn step(&mut self) -> Result<(), ParseError> {
+        match self.state {
+            State::Done => {}
+            State::Header(0) => {
+                self.skip_ignorable();
+                let Some(line) = self.take_line() else {
+                    self.state = State::Done;
+                    return Ok(());
+                };
+                self.block = 0;
+                self.atom = 0;
+                self.push_line(TokenKind::Comment, line);
+                self.state = State::Header(1);
+            }
+            State::Header(n) => {
+                let line = self.take_line().ok_or(ParseError::IncompleteHeader)?;
+                match n {
+                    1 => self.push_line(TokenKind::Metadata, line),
+                    2 | 3 => {
+                        for (axis, f) in self.fixed_fields(line, 3)?.into_iter().enumerate() {
+                            let kind = if n == 2 {
+                                TokenKind::CellLength { axis }
+                            } else {
+                                TokenKind::CellAngle { axis }
+                            };
+                            self.push(kind, f);
+                        }
+                    }
+                    4 | 5 => self.push_line(TokenKind::Comment, line),
+                    6 => {
+                        let f = self.fixed_fields(line, 1)?.remove(0);
+                        self.push(TokenKind::TypeCount, f);
+                        let ntypes = self.pending.back().map(Token::parse::<usize>);
+                        let ntypes = ntypes.expect("token just pushed")?;
+                        self.natoms_per_type.clear();
+                        self.natoms_per_type.resize(ntypes, 0);
+                    }
+                    7 => {
+                        let ntypes = self.natoms_per_type.len();
+                        for (t, f) in self.fixed_fields(line, ntypes)?.into_iter().enumerate() {
+                            self.push(TokenKind::AtomCount { atom_type: t }, f);
+                            let count = self.pending.back().map(Token::parse::<usize>);
+                            self.natoms_per_type[t] = count.expect("token just pushed")?;
+                        }
+                    }
+                    _ => {
+                        let ntypes = self.natoms_per_type.len();
+                        for (t, f) in self.fixed_fields(line, ntypes)?.into_iter().enumerate() {
+                            self.push(TokenKind::Mass { atom_type: t }, f);
+                        }
+                    }
+                }
+                self.state = if n < 8 {
+                    State::Header(n + 1)
+                } else {
+                    self.first_block_state()
+                };
+            }
+            State::Symbol { atom_type } => {
+                let line = self.take_line().ok_or(ParseError::IncompleteFrame)?;
+                self.push_line(TokenKind::Symbol { atom_type }, line);
+                self.state = State::Label { atom_type };
+            }
+            State::Label { atom_type } => {
+                let line = self.take_line().ok_or(ParseError::IncompleteFrame)?;
+                self.push_line(TokenKind::BlockLabel { atom_type }, line);
+                self.state = self.rows_state(atom_type);
+            }
+            State::Row { atom_type, left } => {
+                let line = self.take_line().ok_or(ParseError::IncompleteFrame)?;
+                let fields = self.fields(line);
+                if fields.len() < 3 {
+                    return Err(ParseError::InvalidVectorLength {
+                        expected: 3,
+                        found: fields.len(),
+                    });
+                }
+                let atom = self.atom;
+                let nvalues = fields.len() - 2;
+                for (column, f) in fields.into_iter().enumerate() {
+                    let kind = match column {
+                        c if c < nvalues => TokenKind::Value { atom, column: c },
+                        c if c == nvalues => TokenKind::Fixed { atom },
+                        _ => TokenKind::AtomId { atom },
+                    };
+                    self.push(kind, f);
+                }
+                self.atom += 1;
+                self.state = if left > 1 {
+                    State::Row {
+                        atom_type,
+                        left: left - 1,
+                    }
+                } else {
+                    self.next_type_state(atom_type)
+                };
+            }
+            State::BlockEnd => {
+                if self.section_follows() {
+                    let (s, e) = self.take_line().expect("section_follows saw a line");
+                    self.push(TokenKind::SectionBreak, s..e);
+                    self.block += 1;
+                    self.atom = 0;
+                    self.state = self.first_block_state();
+                } else {
+                    self.frame += 1;
+                    self.state = State::Header(0);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn first_block_state(&self) -> State {
+        if self.natoms_per_type.is_empty() {
+            State::BlockEnd
+        } else {
+            State::Symbol { atom_type: 0 }
+        }
+    }
+
+    fn rows_state(&self, atom_type: usize) -> State {
+        match self.natoms_per_type[atom_type] {
+            0 => self.next_type_state(atom_type),
+            left => State::Row { atom_type, left },
+        }
+    }
+
+    fn next_type_state(&self, atom_type: usize) -> State {
+        if atom_type + 1 < self.natoms_per_type.len() {
+            State::Symbol {
+                atom_type: atom_type + 1,
+            }
+        } else {
+            State::BlockEnd
+        }
+    }
+}
+
+impl<'a> Iterator for ConTokenizer<'a> {
+    type Item = Result<Token<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.pending.pop_front() {
+                return Some(Ok(token));
+            }
+            if matches!(self.state, State::Done) {
+                return None;
+            }
+            if let Err(e) = self.step() {
+                self.pending.clear();
+                self.state = State::Done;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+
+    fn tokens(text: &str) -> Vec<Token<'_>> {
+        ConTokenizer::new(text).collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn coordinates_and_spans_match_parser() {
+        let text = fixture("tiny_multi_cuh2.con");
+        let frames: Vec<_> = ConFrameIterator::new(&text)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let toks = tokens(&text);
+        assert_eq!(toks.last().unwrap().frame + 1, frames.len());
+        for t in &toks {
+            assert_eq!(&text[t.span.clone()], t.text);
+        }
+        for (k, frame) in frames.iter().enumerate() {
+            let xs: Vec<f64> = toks
+                .iter()
+                .filter(|t| t.frame == k && matches!(t.kind, TokenKind::Value { column: 0, .. }))
+                .map(|t| t.as_f64().unwrap())
+                .collect();
+            let expect: Vec<f64> = frame.atom_data.iter().map(|a| a.x).collect();
+            assert_eq!(xs, expect);
+        }
+        let first = &toks[0];
+        assert_eq!((first.kind, first.line), (TokenKind::Comment, 1));
+    }
+
+    #[test]
+    fn section_blocks_are_numbered() {
+        let text = fixture("tiny_cuh2_charges_spins_magmoms.con");
+        let toks = tokens(&text);
+        let breaks = toks
+            .iter()
+            .filter(|t| t.kind == TokenKind::SectionBreak)
+            .count();
+        assert_eq!(breaks, 3);
+        let charges: Vec<f64> = toks
+            .iter()
+            .filter(|t| t.block == 1 && matches!(t.kind, TokenKind::Value { .. }))
+            .map(|t| t.as_f64().unwrap())
+            .collect();
+        assert_eq!(charges, vec![0.5, -0.25, 0.1, -0.1]);
+        let magmom_ids: Vec<u64> = toks
+            .iter()
+            .filter(|t| t.block == 3 && matches!(t.kind, TokenKind::AtomId { .. }))
+            .map(|t| t.parse().unwrap())
+            .collect();
+        assert_eq!(magmom_ids, vec![0, 1, 2, 3]);
+        assert!(toks.iter().all(|t| t.frame == 0));
+    }
+
+    #[test]
+    fn truncated_input_errors_once() {
+        let text = fixture("tiny_cuh2.con");
+        let lines: Vec<&str> = text.lines().collect();
+        let cut = lines[..lines.len() - 2].join("\n");
+        let results: Vec<_> = ConTokenizer::new(&cut).collect();
+        assert!(results.last().unwrap().is_err());
+        assert_eq!(results.iter().filter(|r| r.is_err()).count(), 1);
+
+        let noisy = format!("# leading comment\n\n{text}");
+        let toks: Vec<_> = ConTokenizer::new(&noisy)
+            .options(ParserOptions::tolerant())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(toks[0].line, 3);
+    }
+}
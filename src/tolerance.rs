@@ -0,0 +1,90 @@
+//! Numeric tolerances shared by comparison and validation APIs.
+//!
+//! [`Tolerances`] is passed to [`crate::diff::FrameDiff::compute`],
+//! [`ConFrame::approx_eq`](crate::types::ConFrame::approx_eq),
+//! [`crate::diff::dedup_indices`],
+//! [`ConFrame::fingerprint_with`](crate::types::ConFrame::fingerprint_with) and
+//! [`ConFrameBuilder::validate_with`](crate::types::ConFrameBuilder::validate_with),
+//! so one value states how close is close enough for a whole workflow.
+//! All tolerances are absolute and in the frame's own units (Å, degrees,
+//! amu and eV for eOn files).
+
+/// Absolute tolerances per quantity.
+///
+/// The [`Default`] values sit well above the round-off of CON text written
+/// at the default 6-digit precision and well below physically meaningful
+/// differences:
+///
+/// | field        | default | applies to                         |
+/// |--------------|---------|------------------------------------|
+/// | `coordinate` | 1e-6    | per-atom displacement norm         |
+/// | `cell`       | 1e-6    | cell lengths and angles            |
+/// | `mass`       | 1e-4    | per-type masses                    |
+/// | `energy`     | 1e-6    | `energy` metadata                  |
+///
+/// # Example
+/// ```
+/// use readcon_core::tolerance::Tolerances;
+/// let loose = Tolerances::default().with_coordinate(1e-3);
+/// assert_eq!(loose.coordinate, 1e-3);
+/// assert_eq!(loose.mass, 1e-4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerances {
+    pub coordinate: f64,
+    pub cell: f64,
+    pub mass: f64,
+    pub energy: f64,
+}
+
+impl Default for Tolerances {
+    fn default() -> Self {
+        Tolerances {
+            coordinate: 1e-6,
+            cell: 1e-6,
+            mass: 1e-4,
+            energy: 1e-6,
+        }
+    }
+}
+
+impl Tolerances {
+    /// All tolerances zero: only bit-identical values compare equal.
+    pub fn exact() -> Self {
+        Self::uniform(0.0)
+    }
+
+    /// The same tolerance for every quantity.
+    pub fn uniform(tol: f64) -> Self {
+        Tolerances {
+            coordinate: tol,
+            cell: tol,
+            mass: tol,
+            energy: tol,
+        }
+    }
+
+    /// Replaces the coordinate tolerance.
+    pub fn with_coordinate(mut self, tol: f64) -> Self {
+        self.coordinate = tol;
+        self
+    }
+
+    /// Replaces the cell tolerance.
+    pub fn with_cell(mut self, tol: f64) -> Self {
+        self.cell = tol;
+        self
+    }
+
+    /// Replaces the mass tolerance.
+    pub fn with_mass(mut self, tol: f64) -> Self {
+        self.mass = tol;
+        self
+    }
+
+    /// Replaces the energy tolerance.
+    pub fn with_energy(mut self, tol: f64) -> Self {
+        self.energy = tol;
+        self
+    }
+}
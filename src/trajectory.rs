@@ -0,0 +1,736 @@
+//! Format-independent trajectory reading and writing.
+//!
+//! [`FrameSource`] yields frames one at a time and [`FrameSink`] consumes
+//! them, so conversion code is written once as [`copy`] and works for every
+//! backend:
+//!
+//! | Format | Source | Sink |
+//! |--------|--------|------|
+//! | `.con` | [`ConFrameIterator`](crate::iterators::ConFrameIterator) | [`ConFrameWriter`] |
+//! | JSON lines | [`JsonlFrameIterator`](crate::formats::jsonl::JsonlFrameIterator) | [`FormatWriter::jsonl`] |
+//! | `.gro` | [`GroFrameIterator`](crate::formats::gro::GroFrameIterator) | [`FormatWriter::gro`] |
+//! | `.bcon` | [`BconReader::frames`](crate::formats::bcon::BconReader::frames) | [`BconWriter`] |
+//! | DCD | | [`DcdWriter`] |
+//! | XYZ / extended XYZ | [`XyzFrameIterator`](crate::formats::xyz::XyzFrameIterator) | [`FormatWriter::xyz`] |
+//! | mol2 / SDF | | [`FormatWriter::mol2`], [`FormatWriter::sdf`] |
+//! | memory | `Vec<ConFrame>::into_iter().map(Ok)` | `Vec<ConFrame>` |
+//!
+//! A new format slots in by yielding `Result<ConFrame, E>` from an iterator
+//! and implementing [`FrameSink`] for its writer. Sinks with a trailer
+//! ([`BconWriter::finish`], [`DcdWriter::finish`]) are finished by the
+//! caller after [`copy`]. [`copy_frames`] is [`copy`] with a choice of
+//! skipping unreadable frames instead of stopping at the first one. [`select`] restricts any source to a
+//! [`FrameRange`] (start, stop, step) before copying.
+//!
+//! [`Trajectory`] holds the frames of one system in memory for code that
+//! works on whole trajectories rather than frame by frame, and checks that
+//! every frame lists the same species in the same order.
+
+use crate::error::ParseError;
+use crate::formats::bcon::BconWriter;
+use crate::formats::dcd::DcdWriter;
+use crate::formats::{gro, jsonl, mol2, sdf, xyz};
+use crate::types::ConFrame;
+use crate::writer::ConFrameWriter;
+use std::io::{self, Seek, Write};
+use std::ops::{Bound, RangeBounds};
+
+/// A stream of frames.
+///
+/// Implemented for every iterator over `Result<ConFrame, E>`, which covers
+/// all readers in this crate.
+pub trait FrameSource {
+    /// Error produced when a frame cannot be read.
+    type Error: std::error::Error + 'static;
+
+    /// The next frame; `None` at the end of the stream.
+    fn next_frame(&mut self) -> Option<Result<ConFrame, Self::Error>>;
+}
+
+impl<I, E> FrameSource for I
+where
+    I: Iterator<Item = Result<ConFrame, E>>,
+    E: std::error::Error + 'static,
+{
+    type Error = E;
+
+    fn next_frame(&mut self) -> Option<Result<ConFrame, E>> {
+        self.next()
+    }
+}
+
+/// A consumer of frames.
+pub trait FrameSink {
+    /// Writes one frame.
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()>;
+
+    /// Pushes buffered output to the underlying writer.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> FrameSink for ConFrameWriter<W> {
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        ConFrameWriter::write_frame(self, frame)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        ConFrameWriter::flush(self)
+    }
+}
+
+impl<W: Write + Seek> FrameSink for BconWriter<W> {
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        BconWriter::write_frame(self, frame)
+    }
+}
+
+impl<W: Write + Seek> FrameSink for DcdWriter<W> {
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        DcdWriter::write_frame(self, frame)
+    }
+}
+
+/// Collects frames in memory.
+impl FrameSink for Vec<ConFrame> {
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        self.push(frame.clone());
+        Ok(())
+    }
+}
+
+impl<S: FrameSink + ?Sized> FrameSink for &mut S {
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        (**self).write_frame(frame)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+}
+
+/// [`FrameSink`] for the text formats whose writers are free functions
+/// (`write_frame(&mut W, &ConFrame)`).
+pub struct FormatWriter<W: Write> {
+    writer: W,
+    write: fn(&mut W, &ConFrame) -> io::Result<()>,
+}
+
+impl<W: Write> FormatWriter<W> {
+    /// JSON lines ([`crate::formats::jsonl`]).
+    pub fn jsonl(writer: W) -> Self {
+        Self {
+            writer,
+            write: jsonl::write_frame,
+        }
+    }
+
+    /// GROMACS `.gro` ([`crate::formats::gro`]).
+    pub fn gro(writer: W) -> Self {
+        Self {
+            writer,
+            write: gro::write_frame,
+        }
+    }
+
+    /// Tripos mol2 ([`crate::formats::mol2`]).
+    pub fn mol2(writer: W) -> Self {
+        Self {
+            writer,
+            write: mol2::write_frame,
+        }
+    }
+
+    /// MDL SDF ([`crate::formats::sdf`]).
+    pub fn sdf(writer: W) -> Self {
+        Self {
+            writer,
+            write: sdf::write_frame,
+        }
+    }
+
+    /// Extended XYZ ([`crate::formats::xyz`]).
+    pub fn xyz(writer: W) -> Self {
+        Self {
+            writer,
+            write: xyz::write_frame,
+        }
+    }
+
+    /// Flushes and returns the wrapped writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> FrameSink for FormatWriter<W> {
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        (self.write)(&mut self.writer, frame)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Frame indices `start..stop` taking every `step`-th frame, like a Python
+/// slice with non-negative bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRange {
+    /// First selected frame.
+    pub start: usize,
+    /// One past the last frame considered; `None` reads to the end.
+    pub stop: Option<usize>,
+    /// Distance between selected frames; must be at least 1.
+    pub step: usize,
+}
+
+impl Default for FrameRange {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            stop: None,
+            step: 1,
+        }
+    }
+}
+
+impl FrameRange {
+    /// Whether frame `index` is selected.
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.start
+            && self.stop.is_none_or(|stop| index < stop)
+            && (index - self.start).is_multiple_of(self.step.max(1))
+    }
+}
+
+/// Source adapter returned by [`select`].
+pub struct Select<S> {
+    source: S,
+    range: FrameRange,
+    index: usize,
+}
+
+/// Restricts `source` to the frames in `range`. Frames outside the range
+/// are still read (their errors are reported) but not yielded, and reading
+/// stops at `range.stop`.
+///
+/// # Example
+/// ```
+/// use readcon_core::iterators::ConFrameIterator;
+/// use readcon_core::trajectory::{FrameRange, copy, select};
+/// let con = std::fs::read_to_string("resources/test/tiny_multi_cuh2.con").unwrap();
+/// let range = FrameRange { start: 1, ..FrameRange::default() };
+/// let mut frames = Vec::new();
+/// assert_eq!(copy(&mut select(ConFrameIterator::new(&con), range), &mut frames).unwrap(), 1);
+/// ```
+pub fn select<S: FrameSource>(source: S, range: FrameRange) -> Select<S> {
+    Select {
+        source,
+        range,
+        index: 0,
+    }
+}
+
+impl<S: FrameSource> Iterator for Select<S> {
+    type Item = Result<ConFrame, S::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.range.stop.is_some_and(|stop| self.index >= stop) {
+                return None;
+            }
+            let frame = self.source.next_frame()?;
+            let index = self.index;
+            self.index += 1;
+            if frame.is_err() || self.range.contains(index) {
+                return Some(frame);
+            }
+        }
+    }
+}
+
+/// Error from [`copy`]: which frame failed and on which side.
+#[derive(Debug)]
+pub enum CopyError<E> {
+    /// Reading frame `index` from the source failed.
+    Read { index: usize, source: E },
+    /// Writing frame `index` to the sink failed.
+    Write { index: usize, source: io::Error },
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for CopyError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyError::Read { index, source } => write!(f, "reading frame {index}: {source}"),
+            CopyError::Write { index, source } => write!(f, "writing frame {index}: {source}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for CopyError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CopyError::Read { source, .. } => Some(source),
+            CopyError::Write { source, .. } => Some(source),
+        }
+    }
+}
+
+/// What [`copy_frames`] does with a frame the source cannot read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnReadError {
+    /// Stop with [`CopyError::Read`], as [`copy`] does.
+    #[default]
+    Abort,
+    /// Note the error in [`CopyReport::skipped`] and carry on with the next
+    /// frame. With a CON source, turn on
+    /// [`ConFrameIterator::recover`](crate::iterators::ConFrameIterator::recover)
+    /// so the reader resynchronizes on the next frame header; otherwise the
+    /// rest of a damaged frame may surface as further errors.
+    Skip,
+}
+
+/// Outcome of [`copy_frames`].
+#[derive(Debug)]
+pub struct CopyReport<E> {
+    /// Frames written to the sink.
+    pub written: usize,
+    /// Source index and error of every frame skipped under
+    /// [`OnReadError::Skip`].
+    pub skipped: Vec<(usize, E)>,
+}
+
+/// Streams every frame of `source` into `sink`, then flushes the sink.
+/// Frames are not collected, so memory use is one frame regardless of
+/// trajectory length. Unreadable frames abort the copy or are skipped
+/// according to `on_error`; write errors always abort. Indices in errors
+/// and in [`CopyReport::skipped`] count every frame of the source,
+/// unreadable ones included.
+///
+/// # Example
+/// ```
+/// use readcon_core::iterators::ConFrameIterator;
+/// use readcon_core::trajectory::{OnReadError, copy_frames};
+/// let one = std::fs::read_to_string("resources/test/tiny_cuh2.con").unwrap();
+/// let bad = one.replacen("Coordinates of Component 1\n", "Coordinates of Component 1\nx\n", 1);
+/// let text = format!("{one}{bad}{one}");
+/// let mut frames = Vec::new();
+/// let mut source = ConFrameIterator::new(&text).recover(true);
+/// let report = copy_frames(&mut source, &mut frames, OnReadError::Skip).unwrap();
+/// assert_eq!((report.written, report.skipped[0].0), (2, 1));
+/// assert!(copy_frames(&mut ConFrameIterator::new(&text), &mut Vec::new(), OnReadError::Abort).is_err());
+/// ```
+pub fn copy_frames<S, K>(
+    source: &mut S,
+    sink: &mut K,
+    on_error: OnReadError,
+) -> Result<CopyReport<S::Error>, CopyError<S::Error>>
+where
+    S: FrameSource + ?Sized,
+    K: FrameSink + ?Sized,
+{
+    let mut report = CopyReport {
+        written: 0,
+        skipped: Vec::new(),
+    };
+    let mut index = 0;
+    while let Some(frame) = source.next_frame() {
+        match frame {
+            Ok(frame) => {
+                sink.write_frame(&frame)
+                    .map_err(|source| CopyError::Write { index, source })?;
+                report.written += 1;
+            }
+            Err(source) => match on_error {
+                OnReadError::Abort => return Err(CopyError::Read { index, source }),
+                OnReadError::Skip => report.skipped.push((index, source)),
+            },
+        }
+        index += 1;
+    }
+    sink.flush()
+        .map_err(|source| CopyError::Write { index, source })?;
+    Ok(report)
+}
+
+/// Streams every frame of `source` into `sink`, then flushes the sink.
+/// Returns the number of frames copied. Frames are not collected, so
+/// memory use is one frame regardless of trajectory length. Stops at the
+/// first unreadable frame; see [`copy_frames`] to skip them instead.
+///
+/// # Example
+/// ```
+/// use readcon_core::formats::jsonl::JsonlFrameIterator;
+/// use readcon_core::iterators::ConFrameIterator;
+/// use readcon_core::trajectory::{FormatWriter, copy};
+/// let con = std::fs::read_to_string("resources/test/tiny_multi_cuh2.con").unwrap();
+/// let mut sink = FormatWriter::jsonl(Vec::new());
+/// let n = copy(&mut ConFrameIterator::new(&con), &mut sink).unwrap();
+/// let text = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+///
+/// let mut frames: Vec<_> = Vec::new();
+/// assert_eq!(copy(&mut JsonlFrameIterator::new(&text), &mut frames).unwrap(), n);
+/// assert_eq!(frames.len(), n);
+/// ```
+pub fn copy<S, K>(source: &mut S, sink: &mut K) -> Result<usize, CopyError<S::Error>>
+where
+    S: FrameSource + ?Sized,
+    K: FrameSink + ?Sized,
+{
+    Ok(copy_frames(source, sink, OnReadError::Abort)?.written)
+}
+
+/// Frames of one system in order, all with the species layout (atom count
+/// and symbol at each index) of the first.
+///
+/// # Example
+/// ```
+/// use readcon_core::iterators::ConFrameIterator;
+/// use readcon_core::trajectory::Trajectory;
+/// let con = std::fs::read_to_string("resources/test/tiny_multi_cuh2.con").unwrap();
+/// let mut traj = Trajectory::new();
+/// assert_eq!(traj.extend_from_reader(ConFrameIterator::new(&con)).unwrap(), 2);
+/// traj.push(traj.frames()[0].clone()).unwrap();
+/// assert_eq!(traj.stride(2).len(), 2);
+/// let mut out = Vec::new();
+/// traj.slice(1..).to_writer(&mut out).unwrap();
+/// assert_eq!(out.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trajectory {
+    frames: Vec<ConFrame>,
+}
+
+impl Trajectory {
+    /// An empty trajectory; the first frame pushed sets the layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps `frames` after checking their layouts agree.
+    ///
+    /// Errors are those of [`push`](Self::push).
+    pub fn from_frames(frames: Vec<ConFrame>) -> Result<Self, ParseError> {
+        let mut traj = Trajectory {
+            frames: Vec::with_capacity(frames.len()),
+        };
+        for frame in frames {
+            traj.push(frame)?;
+        }
+        Ok(traj)
+    }
+
+    /// Checks that `frame` has the trajectory's species layout.
+    ///
+    /// Errors are [`ParseError::ValidationError`] naming the first
+    /// difference.
+    pub fn check_layout(&self, frame: &ConFrame) -> Result<(), ParseError> {
+        let Some(first) = self.frames.first() else {
+            return Ok(());
+        };
+        let (n, m) = (first.atom_data.len(), frame.atom_data.len());
+        if n != m {
+            return Err(ParseError::ValidationError(format!(
+                "frame has {m} atoms, trajectory frames have {n}"
+            )));
+        }
+        match first
+            .atom_data
+            .iter()
+            .zip(&frame.atom_data)
+            .position(|(a, b)| first.symbol_of(a) != frame.symbol_of(b))
+        {
+            Some(i) => Err(ParseError::ValidationError(format!(
+                "atom {i} is {} in the frame and {} in the trajectory",
+                frame.symbol(i),
+                first.symbol(i)
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Appends `frame`, leaving the trajectory unchanged when its layout
+    /// differs ([`check_layout`](Self::check_layout)).
+    pub fn push(&mut self, frame: ConFrame) -> Result<(), ParseError> {
+        self.check_layout(&frame)?;
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    /// Appends every frame of `other`; nothing is appended when any of
+    /// them has a different layout.
+    pub fn append(&mut self, other: Trajectory) -> Result<(), ParseError> {
+        if let Some(frame) = other.frames.first() {
+            self.check_layout(frame)?;
+        }
+        self.frames.extend(other.frames);
+        Ok(())
+    }
+
+    /// Appends the frames of a reader such as
+    /// [`ConFrameIterator`](crate::iterators::ConFrameIterator) and returns
+    /// how many were added. Stops at the first read or layout error,
+    /// keeping the frames added before it.
+    pub fn extend_from_reader<I>(&mut self, frames: I) -> Result<usize, ParseError>
+    where
+        I: IntoIterator<Item = Result<ConFrame, ParseError>>,
+    {
+        let mut added = 0;
+        for frame in frames {
+            self.push(frame?)?;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Copy of the frames in `range`, clamped to the trajectory like a
+    /// Python slice.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Trajectory {
+        let len = self.frames.len();
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(len);
+        let stop = match range.end_bound() {
+            Bound::Included(&e) => e.saturating_add(1),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => len,
+        }
+        .clamp(start, len);
+        Trajectory {
+            frames: self.frames[start..stop].to_vec(),
+        }
+    }
+
+    /// Copy of every `step`-th frame, starting with the first; a `step` of
+    /// 0 counts as 1.
+    pub fn stride(&self, step: usize) -> Trajectory {
+        Trajectory {
+            frames: self.frames.iter().step_by(step.max(1)).cloned().collect(),
+        }
+    }
+
+    /// Writes every frame to `sink`, then flushes it.
+    pub fn to_writer<K: FrameSink + ?Sized>(&self, sink: &mut K) -> io::Result<()> {
+        for frame in &self.frames {
+            sink.write_frame(frame)?;
+        }
+        sink.flush()
+    }
+
+    /// The frames, in order.
+    pub fn frames(&self) -> &[ConFrame] {
+        &self.frames
+    }
+
+    /// Unwraps the frames.
+    pub fn into_frames(self) -> Vec<ConFrame> {
+        self.frames
+    }
+
+    /// Iterates over the frames.
+    pub fn iter(&self) -> std::slice::Iter<'_, ConFrame> {
+        self.frames.iter()
+    }
+
+    /// Number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// True when the trajectory has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a Trajectory {
+    type Item = &'a ConFrame;
+    type IntoIter = std::slice::Iter<'a, ConFrame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.iter()
+    }
+}
+
+impl IntoIterator for Trajectory {
+    type Item = ConFrame;
+    type IntoIter = std::vec::IntoIter<ConFrame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.into_iter()
+    }
+}
+
+/// Collects frames in memory, rejecting a frame whose layout differs.
+impl FrameSink for Trajectory {
+    fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        self.push(frame.clone())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::bcon::BconReader;
+    use crate::iterators::ConFrameIterator;
+    use crate::test_util::fixture;
+    use std::io::Cursor;
+
+    #[test]
+    fn copy_through_every_backend() {
+        let text = fixture("tiny_multi_cuh2.con");
+        let frames: Vec<ConFrame> = ConFrameIterator::new(&text)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let mut bcon = BconWriter::new(Cursor::new(Vec::new())).unwrap();
+        copy(&mut ConFrameIterator::new(&text), &mut bcon).unwrap();
+        let mut reader = BconReader::new(bcon.finish().unwrap()).unwrap();
+        let mut con = ConFrameWriter::new(Vec::new());
+        assert_eq!(copy(&mut reader.frames(), &mut con).unwrap(), frames.len());
+        let con = String::from_utf8(con.into_inner().unwrap()).unwrap();
+        let mut back = Vec::new();
+        copy(&mut ConFrameIterator::new(&con), &mut back).unwrap();
+        assert_eq!(back, frames);
+
+        for mut sink in [
+            FormatWriter::gro(Vec::new()),
+            FormatWriter::mol2(Vec::new()),
+            FormatWriter::sdf(Vec::new()),
+            FormatWriter::xyz(Vec::new()),
+        ] {
+            assert_eq!(
+                copy(
+                    &mut frames.iter().cloned().map(Ok::<_, io::Error>),
+                    &mut sink
+                )
+                .unwrap(),
+                2
+            );
+            assert!(!sink.into_inner().unwrap().is_empty());
+        }
+
+        let err = copy(&mut ConFrameIterator::new("garbage\n"), &mut Vec::new()).unwrap_err();
+        assert!(matches!(err, CopyError::Read { index: 0, .. }));
+    }
+
+    #[test]
+    fn copy_frames_skips_or_aborts_on_bad_frames() {
+        let source = || {
+            (0..5).map(|i| {
+                if i % 2 == 1 {
+                    Err(io::Error::other(format!("bad frame {i}")))
+                } else {
+                    Ok(crate::types::ConFrameBuilder::new([1.0; 3], [90.0; 3]).build())
+                }
+            })
+        };
+        let mut frames = Vec::new();
+        let report = copy_frames(&mut source(), &mut frames, OnReadError::Skip).unwrap();
+        assert_eq!((report.written, frames.len()), (3, 3));
+        let skipped: Vec<usize> = report.skipped.iter().map(|(i, _)| *i).collect();
+        assert_eq!(skipped, vec![1, 3]);
+        assert_eq!(report.skipped[1].1.to_string(), "bad frame 3");
+
+        let err = copy_frames(&mut source(), &mut Vec::new(), OnReadError::Abort).unwrap_err();
+        assert!(matches!(err, CopyError::Read { index: 1, .. }));
+        assert_eq!(err.to_string(), "reading frame 1: bad frame 1");
+
+        // Write errors abort either way, with the source index.
+        let mut wrong = Trajectory::from_frames(vec![
+            crate::types::ConFrameBuilder::new([1.0; 3], [90.0; 3]).build(),
+        ])
+        .unwrap();
+        let mut one = crate::types::ConFrameBuilder::new([1.0; 3], [90.0; 3]);
+        one.add_atom("H", 0.0, 0.0, 0.0, [false; 3], 0, 1.008);
+        let mut source = [Err(io::Error::other("x")), Ok(one.build())].into_iter();
+        let err = copy_frames(&mut source, &mut wrong, OnReadError::Skip).unwrap_err();
+        assert!(matches!(err, CopyError::Write { index: 1, .. }));
+    }
+
+    #[test]
+    fn select_applies_start_stop_step() {
+        let ids = |range: FrameRange| -> Vec<usize> {
+            let source = (0..10).map(|i| {
+                let mut b = crate::types::ConFrameBuilder::new([1.0; 3], [90.0; 3]);
+                b.prebox_header(i.to_string());
+                Ok::<_, io::Error>(b.build())
+            });
+            select(source, range)
+                .map(|f| f.unwrap().header.prebox_header.user.parse().unwrap())
+                .collect()
+        };
+        assert_eq!(ids(FrameRange::default()), (0..10).collect::<Vec<_>>());
+        let r = FrameRange {
+            start: 1,
+            stop: Some(8),
+            step: 3,
+        };
+        assert_eq!(ids(r), vec![1, 4, 7]);
+        assert!(r.contains(4) && !r.contains(5) && !r.contains(10));
+        let past_end = FrameRange {
+            start: 20,
+            ..FrameRange::default()
+        };
+        assert!(ids(past_end).is_empty());
+    }
+
+    #[test]
+    fn trajectory_checks_layout_and_slices() {
+        let frame = |label: usize, symbols: &[&str]| {
+            let mut b = crate::types::ConFrameBuilder::new([5.0; 3], [90.0; 3]);
+            b.prebox_header(label.to_string());
+            for (i, sym) in symbols.iter().enumerate() {
+                b.add_atom(sym, i as f64, 0.0, 0.0, [false; 3], i as u64, 1.0);
+            }
+            b.build()
+        };
+        let labels = |t: &Trajectory| -> Vec<String> {
+            t.iter()
+                .map(|f| f.header.prebox_header.user.clone())
+                .collect()
+        };
+        let mut traj =
+            Trajectory::from_frames((0..5).map(|i| frame(i, &["Cu", "H"])).collect()).unwrap();
+        assert_eq!(labels(&traj.slice(1..3)), ["1", "2"]);
+        assert_eq!(labels(&traj.slice(3..=10)), ["3", "4"]);
+        assert!(traj.slice(7..).is_empty());
+        assert_eq!(labels(&traj.stride(2)), ["0", "2", "4"]);
+        assert_eq!(traj.stride(0).len(), 5);
+
+        assert!(traj.push(frame(5, &["Cu"])).is_err());
+        let err = traj.push(frame(5, &["Cu", "O"])).unwrap_err();
+        assert!(err.to_string().contains("atom 1 is O"), "{err}");
+        assert!(
+            traj.append(Trajectory::from_frames(vec![frame(5, &["H", "H"])]).unwrap())
+                .is_err()
+        );
+        assert_eq!(traj.len(), 5);
+        traj.append(traj.slice(..2)).unwrap();
+        assert_eq!(traj.len(), 7);
+
+        // A reader stops at the first bad frame, keeping earlier ones.
+        let source = vec![
+            Ok(frame(7, &["Cu", "H"])),
+            Ok(frame(8, &["Cu"])),
+            Ok(frame(9, &["Cu", "H"])),
+        ];
+        assert!(traj.extend_from_reader(source).is_err());
+        assert_eq!(traj.len(), 8);
+
+        let mut copied = Trajectory::new();
+        traj.to_writer(&mut copied).unwrap();
+        assert_eq!(copied, traj);
+        let mut wrong = Trajectory::from_frames(vec![frame(0, &["O"])]).unwrap();
+        let err = traj.to_writer(&mut wrong).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(traj.into_frames().len(), 8);
+    }
+}
@@ -3,8 +3,8 @@
 //=============================================================================
 
 pub use rustc_hash::FxHashMap;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::sync::Arc;
 
 /// JSON metadata key names recognized by spec v2.
 ///
@@ -94,6 +94,11 @@ pub mod meta {
     /// `[i, j]` or `{"i": i, "j": j, "order"?: ...}` with 0-based indices into
     /// `atom_data` order (not `atom_id`). Absent means no topology (legacy).
     pub const BONDS: &str = "bonds";
+    /// Names of the numeric columns after the atom id on coordinate lines
+    /// (array of strings, file order). A `"charge"` column is read into
+    /// [`AtomDatum::charge`](super::AtomDatum::charge); the others stay in
+    /// [`AtomDatum::extra`](super::AtomDatum::extra).
+    pub const EXTRA_COLUMNS: &str = "extra_columns";
 }
 
 /// One optional bond endpoint pair on a frame (indices into `atom_data`).
@@ -174,6 +179,66 @@ impl PartialEq for PreboxHeader {
     }
 }
 
+/// Energy and time recovered from a frame's header, with the free-text
+/// lines they may have come from; see [`FrameHeader::frame_metadata`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameMetadata {
+    /// The JSON `energy` key, else an `energy` / `E` token in a free line.
+    pub energy: Option<f64>,
+    /// The JSON `time` key, else a `time` / `t` token in a free line.
+    pub time: Option<f64>,
+    /// The free-text lines as stored: the prebox user line, then the two
+    /// postbox lines.
+    pub raw: [String; 3],
+}
+
+impl FrameMetadata {
+    /// Scans a free-text line for `key=value`, `key: value` or `key value`
+    /// tokens, keys matched case-insensitively. Returns `(energy, time)`;
+    /// the first finite value for each wins.
+    ///
+    /// ```
+    /// use readcon_core::types::FrameMetadata;
+    /// let (e, t) = FrameMetadata::scan_line("Generated by eOn Energy: -3.25 time=12");
+    /// assert_eq!((e, t), (Some(-3.25), Some(12.0)));
+    /// ```
+    pub fn scan_line(line: &str) -> (Option<f64>, Option<f64>) {
+        let mut energy = None;
+        let mut time = None;
+        let mut tokens = line.split_whitespace().peekable();
+        while let Some(token) = tokens.next() {
+            let (key, inline) = match token.split_once(['=', ':']) {
+                Some((k, v)) => (k, (!v.is_empty()).then_some(v)),
+                None => (token, None),
+            };
+            let slot = match key.to_ascii_lowercase().as_str() {
+                "energy" | "e" => &mut energy,
+                "time" | "t" => &mut time,
+                _ => continue,
+            };
+            let value = match inline {
+                Some(v) => v,
+                None => match tokens.peek() {
+                    // `E = -3.2` splits the separator off on its own.
+                    Some(&"=") | Some(&":") => {
+                        tokens.next();
+                        match tokens.next() {
+                            Some(v) => v,
+                            None => break,
+                        }
+                    }
+                    Some(v) => v,
+                    None => break,
+                },
+            };
+            if slot.is_none() {
+                *slot = value.parse::<f64>().ok().filter(|v| v.is_finite());
+            }
+        }
+        (energy, time)
+    }
+}
+
 /// Holds all metadata from the 9-line header of a simulation frame.
 #[derive(Debug, Clone)]
 pub struct FrameHeader {
@@ -192,6 +257,11 @@ pub struct FrameHeader {
     pub natms_per_type: Vec<usize>,
     /// A vector containing the mass for each respective atom type.
     pub masses_per_type: Vec<f64>,
+    /// Label line of each component's coordinate block, trimmed, kept when
+    /// a file uses labels other than `Coordinates of Component N`. Empty
+    /// when every label is standard, as for frames built in memory; see
+    /// [`Self::component_label`].
+    pub component_labels: Vec<String>,
     /// CON spec version parsed from the JSON metadata line.
     pub spec_version: u32,
     /// Additional key-value metadata from the JSON metadata line.
@@ -228,12 +298,67 @@ impl PartialEq for FrameHeader {
             && self.natm_types == other.natm_types
             && self.natms_per_type == other.natms_per_type
             && self.masses_per_type == other.masses_per_type
+            && self.component_labels == other.component_labels
             && self.spec_version == other.spec_version
             && self.metadata == other.metadata
             && self.sections == other.sections
     }
 }
 
+impl FrameHeader {
+    /// `Coordinates of Component N` for the 0-based `type_idx`.
+    pub fn standard_component_label(type_idx: usize) -> String {
+        format!("Coordinates of Component {}", type_idx + 1)
+    }
+
+    /// The coordinate-block label of component `type_idx`: the one read
+    /// from the file when [`Self::component_labels`] holds it, the standard
+    /// label otherwise.
+    pub fn component_label(&self, type_idx: usize) -> Cow<'_, str> {
+        match self.component_labels.get(type_idx) {
+            Some(label) => Cow::Borrowed(label),
+            None => Cow::Owned(Self::standard_component_label(type_idx)),
+        }
+    }
+}
+
+/// Energy and time spread across the JSON metadata and the free-text lines.
+impl FrameHeader {
+    /// Energy and time of the frame: the JSON `energy` / `time` keys when
+    /// present, otherwise the first value found by
+    /// [`FrameMetadata::scan_line`] in the user line, then the postbox
+    /// lines, so legacy eOn headers need no hand-written regex.
+    pub fn frame_metadata(&self) -> FrameMetadata {
+        let raw = [
+            self.prebox_header.user.clone(),
+            self.postbox_header[0].clone(),
+            self.postbox_header[1].clone(),
+        ];
+        let mut energy = self.energy();
+        let mut time = self.time();
+        for line in &raw {
+            let (e, t) = FrameMetadata::scan_line(line);
+            energy = energy.or(e);
+            time = time.or(t);
+        }
+        FrameMetadata { energy, time, raw }
+    }
+
+    /// Stores `m`: energy and time go to the JSON metadata (left alone
+    /// when `None`), the raw lines replace the user and postbox lines.
+    pub fn set_frame_metadata(&mut self, m: &FrameMetadata) {
+        if let Some(e) = m.energy {
+            self.set_energy(e);
+        }
+        if let Some(t) = m.time {
+            self.set_time(t);
+        }
+        let [user, post0, post1] = m.raw.clone();
+        self.prebox_header.user = user;
+        self.postbox_header = [post0, post1];
+    }
+}
+
 /// Typed accessors for recommended JSON metadata keys.
 ///
 /// All getters read from `self.metadata`; all setters write to it.
@@ -349,6 +474,33 @@ impl FrameHeader {
         crate::units::unit_conversion_factor(from, to_unit)
     }
 
+    /// Unit for `dimension`, falling back to [`crate::units::assumed_unit`]
+    /// (Å, eV, amu, fs) when `metadata["units"]` does not declare one.
+    pub fn unit_or_assumed(&self, dimension: &str) -> Option<&str> {
+        self.unit_for(dimension)
+            .or_else(|| crate::units::assumed_unit(dimension))
+    }
+
+    /// Like [`conversion_factor_to`](Self::conversion_factor_to), but frames
+    /// without a declared unit are taken to be in the assumed one.
+    pub fn conversion_factor_or_assumed(
+        &self,
+        dimension: &str,
+        to_unit: &str,
+    ) -> Result<f64, crate::error::ParseError> {
+        let from = self.unit_or_assumed(dimension).ok_or_else(|| {
+            crate::error::ParseError::ValidationError(format!(
+                "metadata units.{dimension} is missing and has no assumed unit"
+            ))
+        })?;
+        crate::units::unit_conversion_factor(from, to_unit)
+    }
+
+    /// Cell angles `[alpha, beta, gamma]` in radians.
+    pub fn angles_radians(&self) -> [f64; 3] {
+        crate::units::angles_to_radians(self.angles)
+    }
+
     /// Sets the unit system.
     pub fn set_units(&mut self, units: serde_json::Value) {
         self.metadata.insert(meta::UNITS.into(), units);
@@ -387,6 +539,13 @@ impl FrameHeader {
         Some([row(0)?, row(1)?, row(2)?])
     }
 
+    /// Row-major lattice vectors `[a, b, c]` of [`Self::cell`]: the
+    /// [`Self::lattice_vectors`] override when present, else built from
+    /// `boxl` + `angles`. `None` for degenerate or non-finite cells.
+    pub fn lattice_matrix(&self) -> Option<[[f64; 3]; 3]> {
+        self.cell().map(|c| c.matrix())
+    }
+
     /// Sets the exact lattice vector matrix.
     pub fn set_lattice_vectors(&mut self, vecs: [[f64; 3]; 3]) {
         self.metadata.insert(
@@ -399,6 +558,34 @@ impl FrameHeader {
         );
     }
 
+    /// Names of the extra coordinate columns ([`meta::EXTRA_COLUMNS`]);
+    /// empty when undeclared.
+    pub fn extra_columns(&self) -> Vec<&str> {
+        self.metadata
+            .get(meta::EXTRA_COLUMNS)
+            .and_then(|v| v.as_array())
+            .map(|names| names.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Position among the extra coordinate columns of the one holding
+    /// [`AtomDatum::charge`].
+    pub fn charge_column(&self) -> Option<usize> {
+        self.extra_columns()
+            .iter()
+            .position(|&name| name == "charge")
+    }
+
+    /// Declares the extra coordinate columns; an empty list removes the key.
+    pub fn set_extra_columns(&mut self, names: &[&str]) {
+        if names.is_empty() {
+            self.metadata.remove(meta::EXTRA_COLUMNS);
+        } else {
+            self.metadata
+                .insert(meta::EXTRA_COLUMNS.into(), serde_json::json!(names));
+        }
+    }
+
     /// NEB bead (image) index.
     pub fn neb_bead(&self) -> Option<u64> {
         self.metadata.get(meta::NEB_BEAD).and_then(|v| v.as_u64())
@@ -508,19 +695,57 @@ fn bond_from_json_value(item: &serde_json::Value) -> Option<Bond> {
     None
 }
 
+/// Index of an atom's chemical symbol in its frame's
+/// [`ConFrame::symbol_table`].
+///
+/// Ids are only meaningful within one frame: resolve them with
+/// [`ConFrame::symbol_of`] and compare symbols across frames as strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SymbolId(pub u16);
+
+impl SymbolId {
+    /// Position in the symbol table.
+    pub fn index(self) -> usize {
+        usize::from(self.0)
+    }
+}
+
+/// Id of `symbol` in `table`, appending it when new; `None` when the table
+/// already holds `u16::MAX + 1` symbols. Tables hold a handful of species,
+/// so the lookup is a linear scan.
+pub fn intern_symbol(table: &mut Vec<String>, symbol: &str) -> Option<SymbolId> {
+    if let Some(i) = table.iter().position(|s| s == symbol) {
+        return Some(SymbolId(i as u16));
+    }
+    let id = SymbolId(u16::try_from(table.len()).ok()?);
+    table.push(symbol.to_string());
+    Some(id)
+}
+
+/// The error for a symbol that does not fit a full symbol table.
+pub(crate) fn symbol_table_full(symbol: &str) -> crate::error::ParseError {
+    crate::error::ParseError::ValidationError(format!(
+        "symbol {symbol:?} does not fit: a frame holds at most {} distinct symbols",
+        usize::from(u16::MAX) + 1
+    ))
+}
+
 /// Represents the data for a single atom in a frame.
 #[derive(Debug, Clone, PartialEq)]
 pub struct AtomDatum {
-    /// The chemical symbol of the atom (e.g., "C", "H", "O").
-    /// Using Arc<str> to avoid expensive clones for each atom of the same type.
-    pub symbol: Arc<str>,
+    /// The chemical symbol of the atom (e.g., "C", "H", "O"), as an index
+    /// into the owning frame's [`ConFrame::symbol_table`]. Read it with
+    /// [`ConFrame::symbol_of`] or [`ConFrame::symbol`] and change it with
+    /// [`ConFrame::set_symbol`].
+    pub symbol: SymbolId,
     /// The Cartesian x-coordinate.
     pub x: f64,
     /// The Cartesian y-coordinate.
     pub y: f64,
     /// The Cartesian z-coordinate.
     pub z: f64,
-    /// Per-direction constraint flags: [fixed_x, fixed_y, fixed_z].
+    /// Per-direction constraint flags: [fixed_x, fixed_y, fixed_z]; see
+    /// [`Self::constraint`] for the typed form.
     ///
     /// Encoded as a bitmask in column 4 of the file format:
     /// - 0 = free (all false)
@@ -548,12 +773,21 @@ pub struct AtomDatum {
     /// contributions; the per-frame total still lives in
     /// `FrameHeader.metadata` under the `energy` key.
     pub energy: Option<f64>,
-    /// Partial charge (present when `"charges"` section declared).
+    /// Partial charge (present when `"charges"` section declared, or when
+    /// [`meta::EXTRA_COLUMNS`] names a `"charge"` column).
     pub charge: Option<f64>,
     /// Spin / magnetic quantum number scalar (present when `"spins"` declared).
     pub spin: Option<f64>,
     /// Magnetic moment `[mx, my, mz]` (present when `"magmoms"` declared).
     pub magmom: Option<[f64; 3]>,
+    /// Numeric columns after the atom id on the coordinate line, in file
+    /// order (e.g. a per-atom energy or charge appended by an eOn
+    /// variant). Read only with
+    /// [`ParserOptions::extra_columns`](crate::parser::ParserOptions::extra_columns);
+    /// written back after the atom id. A column named `"charge"` by
+    /// [`meta::EXTRA_COLUMNS`] goes to [`Self::charge`] instead. Empty for
+    /// standard files.
+    pub extra: Vec<f64>,
 }
 
 impl AtomDatum {
@@ -567,6 +801,16 @@ impl AtomDatum {
         self.fixed[0] && self.fixed[1] && self.fixed[2]
     }
 
+    /// The atom's [`Constraint`].
+    pub fn constraint(&self) -> Constraint {
+        Constraint::from_mask(self.fixed)
+    }
+
+    /// Sets [`Self::fixed`] from `constraint`.
+    pub fn set_constraint(&mut self, constraint: Constraint) {
+        self.fixed = constraint.mask();
+    }
+
     /// Returns `true` if this atom has velocity data.
     pub fn has_velocity(&self) -> bool {
         self.velocity.is_some()
@@ -593,6 +837,38 @@ impl AtomDatum {
     pub fn has_magmom(&self) -> bool {
         self.magmom.is_some()
     }
+
+    /// Equality of everything but the symbol id, which only compares
+    /// within one frame.
+    fn same_except_symbol(&self, other: &Self) -> bool {
+        let AtomDatum {
+            symbol: _,
+            x,
+            y,
+            z,
+            fixed,
+            atom_id,
+            velocity,
+            force,
+            energy,
+            charge,
+            spin,
+            magmom,
+            extra,
+        } = self;
+        *x == other.x
+            && *y == other.y
+            && *z == other.z
+            && *fixed == other.fixed
+            && *atom_id == other.atom_id
+            && *velocity == other.velocity
+            && *force == other.force
+            && *energy == other.energy
+            && *charge == other.charge
+            && *spin == other.spin
+            && *magmom == other.magmom
+            && *extra == other.extra
+    }
 }
 
 /// Decode a column-4 bitmask value to per-direction fixed flags.
@@ -600,11 +876,13 @@ impl AtomDatum {
 /// - 0 = free
 /// - 1 = all-fixed (legacy, treated as [true, true, true])
 /// - 2-7 = bitmask (bit 0 = x, bit 1 = y, bit 2 = z)
+/// - 8 and above = all-fixed (not a bitmask; legacy "nonzero is fixed").
+///   Strict validation rejects these instead.
 pub fn decode_fixed_bitmask(val: u8) -> [bool; 3] {
     match val {
         0 => [false, false, false],
-        1 => [true, true, true], // legacy: treat as fully fixed
-        v => [v & 1 != 0, v & 2 != 0, v & 4 != 0],
+        v @ 2..=7 => [v & 1 != 0, v & 2 != 0, v & 4 != 0],
+        _ => [true, true, true], // legacy: treat as fully fixed
     }
 }
 
@@ -625,6 +903,122 @@ pub fn encode_fixed_bitmask(fixed: [bool; 3]) -> u8 {
     val
 }
 
+/// Movement constraint of one atom, the typed form of
+/// [`AtomDatum::fixed`].
+///
+/// Atoms store their constraint as the `fixed` mask, which every reader,
+/// writer and FFI path already fills; [`AtomDatum::constraint`] derives
+/// this enum from it on access and [`AtomDatum::set_constraint`] writes
+/// it back, so the two forms cannot disagree.
+///
+/// Each format writes it its own way: `.con` column 4 via
+/// [`Self::con_flag`], POSCAR-style selective dynamics via
+/// [`Self::selective_dynamics`], and the ASE exporters as `FixAtoms`, or
+/// per [`Self::ase_constraint`] as `FixedPlane` or `FixedLine`.
+///
+/// # Example
+/// ```
+/// use readcon_core::types::Constraint;
+/// let c = Constraint::from_mask([false, false, true]);
+/// assert_eq!(c, Constraint::FixedAxes([false, false, true]));
+/// assert_eq!(c.con_flag(), 4);
+/// assert_eq!(c.selective_dynamics(), [true, true, false]);
+/// assert_eq!(Constraint::from_con_flag(1), Constraint::Fixed);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    /// Moves along every axis.
+    #[default]
+    Free,
+    /// Fixed along every axis.
+    Fixed,
+    /// Fixed along the axes marked `true` (x, y, z), at least one and not
+    /// all three when built by [`Self::from_mask`].
+    FixedAxes([bool; 3]),
+}
+
+impl Constraint {
+    /// The constraint for per-axis fixed flags, normalized so an all-false
+    /// mask is [`Self::Free`] and an all-true one [`Self::Fixed`].
+    pub fn from_mask(mask: [bool; 3]) -> Self {
+        match mask {
+            [false, false, false] => Self::Free,
+            [true, true, true] => Self::Fixed,
+            mask => Self::FixedAxes(mask),
+        }
+    }
+
+    /// Per-axis fixed flags.
+    pub fn mask(self) -> [bool; 3] {
+        match self {
+            Self::Free => [false; 3],
+            Self::Fixed => [true; 3],
+            Self::FixedAxes(mask) => mask,
+        }
+    }
+
+    /// `.con` column 4 value (see [`encode_fixed_bitmask`]).
+    pub fn con_flag(self) -> u8 {
+        encode_fixed_bitmask(self.mask())
+    }
+
+    /// Reads a `.con` column 4 value (see [`decode_fixed_bitmask`]).
+    pub fn from_con_flag(flag: u8) -> Self {
+        Self::from_mask(decode_fixed_bitmask(flag))
+    }
+
+    /// POSCAR selective-dynamics flags: `true` (`T`) where the atom may
+    /// move, the inverse of [`Self::mask`].
+    pub fn selective_dynamics(self) -> [bool; 3] {
+        self.mask().map(|fixed| !fixed)
+    }
+
+    /// Reads POSCAR selective-dynamics flags (`true` = may move).
+    pub fn from_selective_dynamics(flags: [bool; 3]) -> Self {
+        Self::from_mask(flags.map(|movable| !movable))
+    }
+
+    /// ASE constraint class and `direction` for a partial fix: one fixed
+    /// axis leaves a `FixedPlane` normal to it, two leave a `FixedLine`
+    /// along the free one. `None` for [`Self::Free`] and [`Self::Fixed`];
+    /// exporters collect fully fixed atoms into one `FixAtoms`.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::Constraint;
+    /// let c = Constraint::FixedAxes([true, true, false]);
+    /// assert_eq!(c.ase_constraint(), Some(("FixedLine", [0.0, 0.0, 1.0])));
+    /// assert_eq!(Constraint::from_ase_constraint("FixedLine", [0.0, 0.0, -2.0]), Some(c));
+    /// ```
+    pub fn ase_constraint(self) -> Option<(&'static str, [f64; 3])> {
+        let Self::FixedAxes(mask) = self else {
+            return None;
+        };
+        let axis = |on: bool| mask.map(|fixed| if fixed == on { 1.0 } else { 0.0 });
+        Some(match mask.iter().filter(|&&fixed| fixed).count() {
+            1 => ("FixedPlane", axis(true)),
+            _ => ("FixedLine", axis(false)),
+        })
+    }
+
+    /// Reads an ASE `FixedPlane` or `FixedLine` back; `None` for other
+    /// classes and for directions that are not along a Cartesian axis.
+    pub fn from_ase_constraint(name: &str, direction: [f64; 3]) -> Option<Self> {
+        let mut along = (0..3).filter(|&i| direction[i].abs() > 1e-8);
+        let (Some(axis), None) = (along.next(), along.next()) else {
+            return None;
+        };
+        let line = match name {
+            "FixedPlane" => false,
+            "FixedLine" => true,
+            _ => return None,
+        };
+        let mut mask = [line; 3];
+        mask[axis] = !line;
+        Some(Self::from_mask(mask))
+    }
+}
+
 /// Represents a single, complete simulation frame, including header and all atomic data.
 ///
 /// **Numeric layout (metatensor-shaped):** coordinates and optional sections live in
@@ -632,10 +1026,16 @@ pub fn encode_fixed_bitmask(fixed: [bool; 3]) -> u8 {
 /// these as the source of truth for DLPack (`as_dlpack` exports **storage** dtype).
 /// Project in-memory representation with [`Self::project_storage_dtypes`]. On-disk CON
 /// text remains binary64. [`Self::atom_data`] is the AoS projection for the writer.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Equality compares atoms by symbol string, so frames whose
+/// [`Self::symbol_table`]s list the same species in another order, or keep
+/// an unused entry, are equal.
+#[derive(Debug, Clone)]
 pub struct ConFrame {
     /// The `FrameHeader` containing the frame's metadata.
     pub header: FrameHeader,
+    /// Each distinct chemical symbol once, indexed by [`AtomDatum::symbol`].
+    pub symbol_table: Vec<String>,
     /// AoS projection for CON serialization and symbol/fixed metadata.
     pub atom_data: Vec<AtomDatum>,
     /// Primary positions `(N, 3)`, type-grouped order (f32 or f64 storage).
@@ -656,9 +1056,107 @@ pub struct ConFrame {
     pub masses: crate::storage_dtype::FloatArray1,
     /// Per-atom ids `(N,)` u64 (always).
     pub atom_ids: ndarray::ArcArray1<u64>,
+    /// Named per-atom data without a CON encoding, kept in `atom_data`
+    /// order; see [`crate::properties`]. Not written to `.con` files.
+    pub properties: crate::properties::PropertyMap,
+    /// Unrecognized lines that followed the frame in its source, kept by
+    /// [`crate::parser::ParseMode::Permissive`] and written back only by a
+    /// [`crate::writer::ConFrameWriter::keep_trailing_lines`] writer.
+    pub trailing_lines: Vec<String>,
+}
+
+impl PartialEq for ConFrame {
+    fn eq(&self, other: &Self) -> bool {
+        let ConFrame {
+            header,
+            symbol_table: _,
+            atom_data,
+            positions,
+            velocities,
+            forces,
+            atom_energies,
+            charges,
+            spins,
+            magmoms,
+            masses,
+            atom_ids,
+            properties,
+            trailing_lines,
+        } = self;
+        *header == other.header
+            && atom_data.len() == other.atom_data.len()
+            && atom_data
+                .iter()
+                .zip(&other.atom_data)
+                .all(|(a, b)| self.symbol_of(a) == other.symbol_of(b) && a.same_except_symbol(b))
+            && *positions == other.positions
+            && *velocities == other.velocities
+            && *forces == other.forces
+            && *atom_energies == other.atom_energies
+            && *charges == other.charges
+            && *spins == other.spins
+            && *magmoms == other.magmoms
+            && *masses == other.masses
+            && *atom_ids == other.atom_ids
+            && *properties == other.properties
+            && *trailing_lines == other.trailing_lines
+    }
 }
 
 impl ConFrame {
+    /// Symbol of atom `i` of `atom_data`.
+    ///
+    /// # Panics
+    /// If `i` is out of bounds.
+    pub fn symbol(&self, i: usize) -> &str {
+        self.symbol_of(&self.atom_data[i])
+    }
+
+    /// Symbol of `atom`, an atom of this frame.
+    ///
+    /// An id past the end of [`Self::symbol_table`] means `atom` belongs to
+    /// another frame or was edited by hand; debug builds panic on it and
+    /// release builds resolve it to `""`.
+    pub fn symbol_of(&self, atom: &AtomDatum) -> &str {
+        let entry = self.symbol_table.get(atom.symbol.index());
+        debug_assert!(
+            entry.is_some(),
+            "symbol id {} outside a {}-entry symbol table",
+            atom.symbol.0,
+            self.symbol_table.len()
+        );
+        entry.map_or("", String::as_str)
+    }
+
+    /// Id of `symbol` in [`Self::symbol_table`], added when new.
+    ///
+    /// Errors with [`ParseError::ValidationError`](crate::error::ParseError::ValidationError)
+    /// when the table already holds `u16::MAX + 1` other symbols.
+    pub fn intern_symbol(&mut self, symbol: &str) -> Result<SymbolId, crate::error::ParseError> {
+        intern_symbol(&mut self.symbol_table, symbol).ok_or_else(|| symbol_table_full(symbol))
+    }
+
+    /// Sets the symbol of atom `i`.
+    ///
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+    /// let mut frame = b.build();
+    /// frame.set_symbol(0, "Ag").unwrap();
+    /// assert_eq!(frame.symbol(0), "Ag");
+    /// ```
+    ///
+    /// Errors as [`Self::intern_symbol`].
+    ///
+    /// # Panics
+    /// If `i` is out of bounds.
+    pub fn set_symbol(&mut self, i: usize, symbol: &str) -> Result<(), crate::error::ParseError> {
+        let id = self.intern_symbol(symbol)?;
+        self.atom_data[i].symbol = id;
+        Ok(())
+    }
+
     /// Apply [`crate::storage_dtype::StorageDtypes`] from metadata (or argument) to SoA fields.
     pub fn project_storage_dtypes(&mut self, dtypes: &crate::storage_dtype::StorageDtypes) {
         self.positions.project_to(dtypes.positions);
@@ -937,6 +1435,244 @@ impl ConFrame {
     pub fn has_bonds(&self) -> bool {
         self.header.has_bonds()
     }
+
+    /// Binary64 positions as contiguous `[x, y, z]` rows in `atom_data`
+    /// order. Unlike the [`Self::positions`] field this never reflects an
+    /// f32 storage projection.
+    pub fn positions(&self) -> Vec<[f64; 3]> {
+        self.atom_data.iter().map(|a| [a.x, a.y, a.z]).collect()
+    }
+
+    /// Positions split into separate x / y / z columns.
+    pub fn positions_soa(&self) -> Positions {
+        let n = self.atom_data.len();
+        let mut out = Positions {
+            x: Vec::with_capacity(n),
+            y: Vec::with_capacity(n),
+            z: Vec::with_capacity(n),
+        };
+        for a in &self.atom_data {
+            out.x.push(a.x);
+            out.y.push(a.y);
+            out.z.push(a.z);
+        }
+        out
+    }
+
+    /// Per-atom symbols in `atom_data` order.
+    pub fn symbols(&self) -> Vec<&str> {
+        self.atom_data.iter().map(|a| self.symbol_of(a)).collect()
+    }
+
+    /// Per-atom `[fixed_x, fixed_y, fixed_z]` flags in `atom_data` order.
+    pub fn fixed_mask(&self) -> Vec<[bool; 3]> {
+        self.atom_data.iter().map(|a| a.fixed).collect()
+    }
+
+    /// One `(symbol, mass, atoms)` item per component block, pairing
+    /// `natms_per_type` and `masses_per_type` with their slices of
+    /// `atom_data`.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+    /// b.add_atom("H", 1.0, 1.0, 1.0, [false; 3], 1, 1.008);
+    /// b.add_atom("Cu", 2.0, 0.0, 0.0, [false; 3], 2, 63.546);
+    /// let frame = b.build();
+    /// let blocks: Vec<_> = frame.by_type().map(|(s, m, a)| (s, m, a.len())).collect();
+    /// assert_eq!(blocks, vec![("Cu", 63.546, 2), ("H", 1.008, 1)]);
+    /// ```
+    pub fn by_type(&self) -> TypeBlocks<'_> {
+        TypeBlocks {
+            frame: self,
+            next_type: 0,
+            offset: 0,
+        }
+    }
+
+    /// Per-atom ids in `atom_data` order: borrowed when the
+    /// [`Self::atom_ids`] field is contiguous, as parsed and built frames
+    /// are, and copied when it was replaced by a strided view.
+    pub fn atom_ids(&self) -> Cow<'_, [u64]> {
+        match self.atom_ids.as_slice() {
+            Some(ids) => Cow::Borrowed(ids),
+            None => Cow::Owned(self.atom_ids.to_vec()),
+        }
+    }
+
+    /// Owned `(N, 3)` binary64 positions in `atom_data` order.
+    pub fn positions_array(&self) -> ndarray::Array2<f64> {
+        let mut out = ndarray::Array2::<f64>::zeros((self.atom_data.len(), 3));
+        for (mut row, a) in out.rows_mut().into_iter().zip(&self.atom_data) {
+            row[0] = a.x;
+            row[1] = a.y;
+            row[2] = a.z;
+        }
+        out
+    }
+
+    /// `(3, 3)` lattice matrix, one lattice vector per row; see
+    /// [`FrameHeader::lattice_matrix`].
+    pub fn cell_matrix(&self) -> Option<ndarray::Array2<f64>> {
+        let m = self.header.lattice_matrix()?;
+        Some(ndarray::Array2::from_shape_fn((3, 3), |(i, j)| m[i][j]))
+    }
+
+    /// Builds a frame from `(N,)` symbols, `(N, 3)` positions, `(N,)` masses
+    /// and a `(3, 3)` row-major lattice matrix. Cell lengths and angles are
+    /// derived from the matrix; a matrix that is not in the standard
+    /// orientation (`a` along x, `b` in the xy plane) is also kept verbatim
+    /// as `metadata["lattice_vectors"]`. Atom ids are the input row indices,
+    /// so the original order survives type grouping.
+    ///
+    /// Shape mismatches are [`crate::error::ParseError::InvalidVectorLength`];
+    /// the rest is checked by [`ConFrameBuilder::try_build`].
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use readcon_core::types::ConFrame;
+    /// let frame = ConFrame::from_arrays(
+    ///     &["O", "H"],
+    ///     array![[0.0, 0.0, 0.0], [0.96, 0.0, 0.0]].view(),
+    ///     array![15.999, 1.008].view(),
+    ///     array![[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]].view(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(frame.header.boxl, [10.0; 3]);
+    /// assert_eq!(frame.positions_array(), array![[0.0, 0.0, 0.0], [0.96, 0.0, 0.0]]);
+    /// ```
+    pub fn from_arrays<S: AsRef<str>>(
+        symbols: &[S],
+        positions: ndarray::ArrayView2<'_, f64>,
+        masses: ndarray::ArrayView1<'_, f64>,
+        cell: ndarray::ArrayView2<'_, f64>,
+    ) -> Result<ConFrame, crate::error::ParseError> {
+        use crate::error::ParseError;
+        let n = symbols.len();
+        if positions.dim() != (n, 3) {
+            return Err(ParseError::InvalidVectorLength {
+                expected: 3 * n,
+                found: positions.len(),
+            });
+        }
+        if masses.len() != n {
+            return Err(ParseError::InvalidVectorLength {
+                expected: n,
+                found: masses.len(),
+            });
+        }
+        if cell.dim() != (3, 3) {
+            return Err(ParseError::InvalidVectorLength {
+                expected: 9,
+                found: cell.len(),
+            });
+        }
+        let lattice: [[f64; 3]; 3] =
+            std::array::from_fn(|i| std::array::from_fn(|j| cell[[i, j]]));
+        let (boxl, angles) = crate::cell::Cell::from_matrix(lattice)
+            .ok_or_else(|| {
+                ParseError::ValidationError("cell matrix is singular or not finite".into())
+            })?
+            .to_lengths_angles();
+
+        let mut builder = ConFrameBuilder::new(boxl, angles);
+        for (i, symbol) in symbols.iter().enumerate() {
+            builder.add_atom(
+                symbol.as_ref(),
+                positions[[i, 0]],
+                positions[[i, 1]],
+                positions[[i, 2]],
+                [false; 3],
+                i as u64,
+                masses[i],
+            );
+        }
+        let mut frame = builder.try_build()?;
+        let standard = frame.header.lattice_matrix();
+        let matches = standard.is_some_and(|m| {
+            m.iter()
+                .flatten()
+                .zip(lattice.iter().flatten())
+                .all(|(x, y)| (x - y).abs() <= 1e-10 * (1.0 + y.abs()))
+        });
+        if !matches {
+            frame.header.set_lattice_vectors(lattice);
+        }
+        Ok(frame)
+    }
+}
+
+/// Iterator over component blocks; see [`ConFrame::by_type`].
+///
+/// Blocks are clamped to `atom_data`, so a header that promises more atoms
+/// than the frame holds yields short (or empty) slices rather than
+/// panicking. The symbol is that of the block's first atom, `""` for an
+/// empty block; a missing mass is `0.0`.
+#[derive(Debug, Clone)]
+pub struct TypeBlocks<'a> {
+    frame: &'a ConFrame,
+    next_type: usize,
+    offset: usize,
+}
+
+impl<'a> Iterator for TypeBlocks<'a> {
+    type Item = (&'a str, f64, &'a [AtomDatum]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = &self.frame.header;
+        let count = *header.natms_per_type.get(self.next_type)?;
+        let atoms = &self.frame.atom_data;
+        let start = self.offset.min(atoms.len());
+        let end = (start + count).min(atoms.len());
+        let mass = header
+            .masses_per_type
+            .get(self.next_type)
+            .copied()
+            .unwrap_or(0.0);
+        self.next_type += 1;
+        self.offset = end;
+        let block = &atoms[start..end];
+        Some((
+            block.first().map_or("", |a| self.frame.symbol_of(a)),
+            mass,
+            block,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.frame.header.natms_per_type.len() - self.next_type;
+        (left, Some(left))
+    }
+}
+
+impl ExactSizeIterator for TypeBlocks<'_> {}
+
+/// Positions as separate coordinate columns; see [`ConFrame::positions_soa`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Positions {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub z: Vec<f64>,
+}
+
+impl Positions {
+    /// Number of atoms.
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    /// True when there are no atoms.
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+
+    /// Row `i` as `[x, y, z]`.
+    pub fn get(&self, i: usize) -> Option<[f64; 3]> {
+        Some([*self.x.get(i)?, *self.y.get(i)?, *self.z.get(i)?])
+    }
 }
 
 /// A builder for constructing `ConFrame` objects from in-memory data.
@@ -1473,6 +2209,49 @@ impl ConFrameBuilder {
         Ok(self)
     }
 
+    /// Replaces every non-positive or non-finite mass (e.g. a `0.0`
+    /// placeholder passed to [`Self::add_atom`]) with the standard atomic
+    /// weight of the atom's symbol from [`crate::helpers::atomic_mass`].
+    /// Masses already set are kept.
+    ///
+    /// Errors are [`crate::error::ParseError::ValidationError`] naming the
+    /// first symbol that needs a mass but has no table entry; no mass is
+    /// changed in that case.
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("O", 0.0, 0.0, 0.0, [false; 3], 0, 0.0);
+    /// b.add_atom("H", 0.9, 0.0, 0.0, [false; 3], 1, 0.0);
+    /// b.infer_masses().unwrap();
+    /// let frame = b.build();
+    /// assert_eq!(frame.header.masses_per_type, vec![15.999, 1.008]);
+    /// ```
+    pub fn infer_masses(&mut self) -> Result<&mut Self, crate::error::ParseError> {
+        self.infer_masses_with(&crate::helpers::ElementRegistry::new())
+    }
+
+    /// [`Self::infer_masses`] with masses from `registry`, so custom
+    /// labels (`OW`, `Cu1`, ghost sites) resolve as well.
+    pub fn infer_masses_with(
+        &mut self,
+        registry: &crate::helpers::ElementRegistry,
+    ) -> Result<&mut Self, crate::error::ParseError> {
+        let mut inferred = Vec::new();
+        for (i, symbol) in self.symbols.iter().enumerate() {
+            let m = self.masses[i];
+            if m.is_finite() && m > 0.0 {
+                continue;
+            }
+            inferred.push((i, registry.mass(symbol)?));
+        }
+        for (i, mass) in inferred {
+            self.masses[i] = mass;
+        }
+        Ok(self)
+    }
+
     /// Updates the atom_id (the pre-grouping index from .con column 5)
     /// of an existing atom. The atom_id is the only per-atom field that
     /// `add_atom` set once and offered no post-add mutator for, which
@@ -2009,11 +2788,101 @@ impl ConFrameBuilder {
 
     // ----- end in-place mutation API ----------------------------------------
 
+    /// Checks the accumulated atoms for inputs [`Self::build`] would accept
+    /// but silently resolve: atoms of one symbol with different masses (the
+    /// first mass wins in `masses_per_type`), duplicate `atom_id`s, blank or
+    /// multi-word symbols (they break the symbol line on write), and
+    /// non-finite coordinates or cell values.
+    ///
+    /// Masses must match exactly; see [`Self::validate_with`] to allow
+    /// round-off. Errors are [`crate::error::ParseError::ValidationError`].
+    pub fn validate(&self) -> Result<(), crate::error::ParseError> {
+        self.validate_with(&crate::tolerance::Tolerances::exact())
+    }
+
+    /// [`Self::validate`] with same-symbol masses compared within
+    /// `tolerances.mass`.
+    pub fn validate_with(
+        &self,
+        tolerances: &crate::tolerance::Tolerances,
+    ) -> Result<(), crate::error::ParseError> {
+        if self
+            .cell
+            .iter()
+            .chain(&self.angles)
+            .any(|v| !v.is_finite())
+        {
+            return Err(crate::error::ParseError::ValidationError(
+                "cell lengths and angles must be finite".into(),
+            ));
+        }
+        let mut type_masses: Vec<(&str, f64)> = Vec::new();
+        let mut seen_ids: FxHashMap<u64, usize> =
+            FxHashMap::with_capacity_and_hasher(self.symbols.len(), Default::default());
+        for (i, symbol) in self.symbols.iter().enumerate() {
+            if symbol.trim().is_empty() || symbol.split_whitespace().count() != 1 {
+                return Err(crate::error::ParseError::ValidationError(format!(
+                    "atom {i}: symbol {symbol:?} must be a single non-empty token"
+                )));
+            }
+            let pos = self.positions.row(i);
+            if pos.iter().any(|v| !v.is_finite()) {
+                return Err(crate::error::ParseError::ValidationError(format!(
+                    "atom {i}: position must be finite"
+                )));
+            }
+            let mass = self.masses[i];
+            match type_masses.iter().find(|(s, _)| *s == symbol.as_str()) {
+                Some(&(_, first))
+                    if first != mass && (first - mass).abs() <= tolerances.mass => {}
+                Some(&(_, first)) if first != mass => {
+                    return Err(crate::error::ParseError::ValidationError(format!(
+                        "atom {i}: mass {mass} for {symbol} differs from {first} given earlier"
+                    )));
+                }
+                Some(_) => {}
+                None => type_masses.push((symbol.as_str(), mass)),
+            }
+            if let Some(prev) = seen_ids.insert(self.atom_ids[i], i) {
+                return Err(crate::error::ParseError::ValidationError(format!(
+                    "atoms {prev} and {i} share atom_id {}",
+                    self.atom_ids[i]
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// [`Self::validate`] followed by [`Self::build`].
+    ///
+    /// # Example
+    /// ```
+    /// use readcon_core::types::ConFrameBuilder;
+    /// let mut b = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    /// b.add_atom("H", 0.0, 0.0, 0.0, [false; 3], 0, 1.008);
+    /// b.add_atom("H", 1.0, 0.0, 0.0, [false; 3], 1, 2.014);
+    /// assert!(b.try_build().is_err());
+    /// ```
+    pub fn try_build(self) -> Result<ConFrame, crate::error::ParseError> {
+        self.validate()?;
+        self.assemble()
+    }
+
     /// Consumes the builder and produces a `ConFrame`.
     ///
     /// Atoms are grouped by symbol (in encounter order) to compute
     /// `natm_types`, `natms_per_type`, and `masses_per_type`.
+    ///
+    /// # Panics
+    /// If the atoms carry more than `u16::MAX + 1` distinct symbols, which
+    /// [`Self::try_build`] reports as an error instead.
     pub fn build(self) -> ConFrame {
+        self.assemble().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// [`Self::build`] without the checks of [`Self::validate`], erroring
+    /// instead of panicking on a symbol table overflow.
+    pub(crate) fn assemble(self) -> Result<ConFrame, crate::error::ParseError> {
         // Single-pass grouping: assign each atom a type index in encounter
         // order and bucket its position. The buckets preserve per-symbol
         // input order so the final flatten yields atoms grouped by type.
@@ -2042,9 +2911,13 @@ impl ConFrameBuilder {
             buckets[idx].push(i);
         }
 
-        // One Arc<str> per type so all atoms of the same symbol share storage.
-        let type_symbols: Vec<Arc<str>> =
-            type_order.iter().map(|s| Arc::from(s.as_str())).collect();
+        // One table entry per distinct symbol; types differing only in mass
+        // share it.
+        let mut symbol_table = Vec::new();
+        let type_symbols: Vec<SymbolId> = type_order
+            .iter()
+            .map(|s| intern_symbol(&mut symbol_table, s).ok_or_else(|| symbol_table_full(s)))
+            .collect::<Result<_, _>>()?;
 
         let has_vel = self.has_velocities;
         let has_frc = self.has_forces;
@@ -2052,7 +2925,7 @@ impl ConFrameBuilder {
 
         let mut atom_data: Vec<AtomDatum> = Vec::with_capacity(n);
         for (type_idx, indices) in buckets.iter().enumerate() {
-            let symbol = &type_symbols[type_idx];
+            let symbol = type_symbols[type_idx];
             for &i in indices {
                 let pos = self.positions.row(i);
                 let velocity = if has_vel {
@@ -2073,7 +2946,7 @@ impl ConFrameBuilder {
                     None
                 };
                 atom_data.push(AtomDatum {
-                    symbol: Arc::clone(symbol),
+                    symbol,
                     x: pos[0],
                     y: pos[1],
                     z: pos[2],
@@ -2085,6 +2958,7 @@ impl ConFrameBuilder {
                     charge: None,
                     spin: None,
                     magmom: None,
+                    extra: Vec::new(),
                 });
             }
         }
@@ -2158,6 +3032,7 @@ impl ConFrameBuilder {
             natm_types: type_order.len(),
             natms_per_type: type_counts,
             masses_per_type: type_masses,
+            component_labels: Vec::new(),
             spec_version: crate::CON_SPEC_VERSION,
             metadata,
             sections,
@@ -2165,8 +3040,9 @@ impl ConFrameBuilder {
             sections_declared,
         };
 
-        ConFrame {
+        Ok(ConFrame {
             header,
+            symbol_table,
             atom_data,
             positions: pos,
             velocities: vel,
@@ -2177,14 +3053,21 @@ impl ConFrameBuilder {
             magmoms: FloatArray2::zeros(dt.forces, 0, 3),
             masses: masses_arr,
             atom_ids: ids_arr,
-        }
+            properties: Default::default(),
+            trailing_lines: Vec::new(),
+        })
     }
 }
 
 /// Build a [`ConFrame`] from header + AoS atoms, filling SoA numeric arrays.
+/// The atoms' [`SymbolId`]s index `symbol_table`.
 /// Prefer [`con_frame_from_atom_data_with_positions`] on the CON parse hot path
 /// when positions were already written into SoA during coordinate parsing.
-pub fn con_frame_from_atom_data(header: FrameHeader, atom_data: Vec<AtomDatum>) -> ConFrame {
+pub fn con_frame_from_atom_data(
+    header: FrameHeader,
+    symbol_table: Vec<String>,
+    atom_data: Vec<AtomDatum>,
+) -> ConFrame {
     let n = atom_data.len();
     use crate::storage_dtype::{FloatArray2, StorageDtypes};
     let dt = StorageDtypes::from_metadata(&header.metadata).unwrap_or_default();
@@ -2192,7 +3075,7 @@ pub fn con_frame_from_atom_data(header: FrameHeader, atom_data: Vec<AtomDatum>)
     for (i, a) in atom_data.iter().enumerate() {
         pos.set_f64_row(i, [a.x, a.y, a.z]);
     }
-    con_frame_from_atom_data_with_positions(header, atom_data, pos)
+    con_frame_from_atom_data_with_positions(header, symbol_table, atom_data, pos)
 }
 
 /// Coords-only frame assembly (the common `.con` path after coordinate parse,
@@ -2205,6 +3088,7 @@ pub fn con_frame_from_atom_data(header: FrameHeader, atom_data: Vec<AtomDatum>)
 /// [`ConFrame::sync_arrays_from_atom_data`] afterward.
 pub fn con_frame_coords_only(
     header: FrameHeader,
+    symbol_table: Vec<String>,
     atom_data: Vec<AtomDatum>,
     positions: crate::storage_dtype::FloatArray2,
 ) -> ConFrame {
@@ -2243,6 +3127,7 @@ pub fn con_frame_coords_only(
     }
     ConFrame {
         header,
+        symbol_table,
         atom_data,
         positions,
         velocities: FloatArray2::zeros(dt.velocities, 0, 3),
@@ -2253,6 +3138,8 @@ pub fn con_frame_coords_only(
         magmoms: FloatArray2::zeros(dt.forces, 0, 3),
         masses: masses_arr,
         atom_ids: ids_arr,
+        properties: Default::default(),
+        trailing_lines: Vec::new(),
     }
 }
 
@@ -2261,6 +3148,7 @@ pub fn con_frame_coords_only(
 /// when present; the common coords-only case delegates to [`con_frame_coords_only`].
 pub fn con_frame_from_atom_data_with_positions(
     header: FrameHeader,
+    symbol_table: Vec<String>,
     atom_data: Vec<AtomDatum>,
     positions: crate::storage_dtype::FloatArray2,
 ) -> ConFrame {
@@ -2271,7 +3159,7 @@ pub fn con_frame_from_atom_data_with_positions(
     let has_spn = atom_data.first().is_some_and(|a| a.has_spin());
     let has_mm = atom_data.first().is_some_and(|a| a.has_magmom());
     if !has_vel && !has_frc && !has_eng && !has_chg && !has_spn && !has_mm {
-        return con_frame_coords_only(header, atom_data, positions);
+        return con_frame_coords_only(header, symbol_table, atom_data, positions);
     }
     let n = atom_data.len();
     debug_assert_eq!(positions.nrows(), n);
@@ -2329,6 +3217,7 @@ pub fn con_frame_from_atom_data_with_positions(
     }
     ConFrame {
         header,
+        symbol_table,
         atom_data,
         positions: pos,
         velocities: vel,
@@ -2339,6 +3228,8 @@ pub fn con_frame_from_atom_data_with_positions(
         magmoms: mm,
         masses: masses_arr,
         atom_ids: ids_arr,
+        properties: Default::default(),
+        trailing_lines: Vec::new(),
     }
 }
 
@@ -2368,12 +3259,58 @@ impl ConFrame {
     ) -> Result<f64, crate::error::ParseError> {
         self.header.conversion_factor_to(dimension, to_unit)
     }
+
+    /// Delegate: unit for `dimension`, or the assumed one when undeclared.
+    pub fn unit_or_assumed(&self, dimension: &str) -> Option<&str> {
+        self.header.unit_or_assumed(dimension)
+    }
+
+    /// Delegate: conversion factor from this frame's (or the assumed) unit.
+    pub fn conversion_factor_or_assumed(
+        &self,
+        dimension: &str,
+        to_unit: &str,
+    ) -> Result<f64, crate::error::ParseError> {
+        self.header.conversion_factor_or_assumed(dimension, to_unit)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fixed_bitmask_decode_is_stable_under_reencoding() {
+        for val in 0..=u8::MAX {
+            let fixed = decode_fixed_bitmask(val);
+            assert_eq!(
+                decode_fixed_bitmask(encode_fixed_bitmask(fixed)),
+                fixed,
+                "{val}"
+            );
+        }
+        assert_eq!(decode_fixed_bitmask(9), [true, true, true]);
+    }
+
+    #[test]
+    fn test_full_symbol_table_errors_instead_of_panicking() {
+        let mut builder = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+        builder.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+        let mut frame = builder.build();
+        frame
+            .symbol_table
+            .extend((1..=usize::from(u16::MAX)).map(|i| format!("X{i}")));
+        assert_eq!(frame.intern_symbol("X7").unwrap(), SymbolId(7));
+        assert!(matches!(
+            frame.intern_symbol("Ag"),
+            Err(crate::error::ParseError::ValidationError(_))
+        ));
+        assert!(frame.set_symbol(0, "Ag").is_err());
+        assert_eq!(frame.symbol(0), "Cu");
+        frame.set_symbol(0, "X9").unwrap();
+        assert_eq!(frame.symbol(0), "X9");
+    }
+
     #[test]
     fn test_atom_id_index_handles_non_sequential_ids() {
         let mut builder = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
@@ -2402,8 +3339,8 @@ mod tests {
         assert_eq!(frame.header.natms_per_type, vec![2, 1]);
         assert_eq!(frame.header.masses_per_type, vec![63.546, 1.008]);
         assert_eq!(frame.atom_data.len(), 3);
-        assert_eq!(&*frame.atom_data[0].symbol, "Cu");
-        assert_eq!(&*frame.atom_data[2].symbol, "H");
+        assert_eq!(frame.symbol(0), "Cu");
+        assert_eq!(frame.symbol(2), "H");
     }
 
     #[test]
@@ -2443,9 +3380,138 @@ mod tests {
         assert_eq!(frame.header.natm_types, 2);
         assert_eq!(frame.header.natms_per_type, vec![2, 1]);
         // Atoms should be grouped: H, H, Cu
-        assert_eq!(&*frame.atom_data[0].symbol, "H");
-        assert_eq!(&*frame.atom_data[1].symbol, "H");
-        assert_eq!(&*frame.atom_data[2].symbol, "Cu");
+        assert_eq!(frame.symbol(0), "H");
+        assert_eq!(frame.symbol(1), "H");
+        assert_eq!(frame.symbol(2), "Cu");
+    }
+
+    #[test]
+    fn test_soa_accessors() {
+        let mut builder = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
+        builder.add_atom("H", 0.0, 1.0, 2.0, [false; 3], 9, 1.008);
+        builder.add_atom("Cu", 3.0, 4.0, 5.0, [true, false, true], 4, 63.546);
+        builder.add_atom("H", 6.0, 7.0, 8.0, [false; 3], 2, 1.008);
+        let frame = builder.build();
+
+        assert_eq!(frame.symbols(), vec!["H", "H", "Cu"]);
+        assert_eq!(
+            frame.positions(),
+            vec![[0.0, 1.0, 2.0], [6.0, 7.0, 8.0], [3.0, 4.0, 5.0]]
+        );
+        let soa = frame.positions_soa();
+        assert_eq!(soa.len(), 3);
+        assert_eq!(soa.x, vec![0.0, 6.0, 3.0]);
+        assert_eq!(soa.get(2), Some([3.0, 4.0, 5.0]));
+        assert_eq!(soa.get(3), None);
+        assert_eq!(frame.fixed_mask()[2], [true, false, true]);
+        assert_eq!(*frame.atom_ids(), [9, 2, 4]);
+
+        // A strided view assigned to the public field is copied, not a panic.
+        let mut strided = frame.clone();
+        strided.atom_ids = frame.atom_ids.slice_move(ndarray::s![..;2]);
+        assert!(matches!(strided.atom_ids(), Cow::Owned(_)));
+        assert_eq!(*strided.atom_ids(), [9, 4]);
+    }
+
+    #[test]
+    fn test_by_type_clamps_to_atom_data() {
+        let mut builder = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
+        builder.add_atom("H", 0.0, 1.0, 2.0, [false; 3], 0, 1.008);
+        builder.add_atom("Cu", 3.0, 4.0, 5.0, [false; 3], 1, 63.546);
+        builder.add_atom("H", 6.0, 7.0, 8.0, [false; 3], 2, 1.008);
+        let mut frame = builder.build();
+        let blocks = frame.by_type();
+        assert_eq!(blocks.len(), 2);
+        let ids: Vec<Vec<u64>> = blocks
+            .map(|(_, _, atoms)| atoms.iter().map(|a| a.atom_id).collect())
+            .collect();
+        assert_eq!(ids, vec![vec![0, 2], vec![1]]);
+
+        frame.header.natms_per_type = vec![2, 4, 1];
+        let sizes: Vec<(&str, usize)> = frame.by_type().map(|(s, _, a)| (s, a.len())).collect();
+        assert_eq!(sizes, vec![("H", 2), ("Cu", 1), ("", 0)]);
+    }
+
+    #[test]
+    fn test_ndarray_roundtrip_and_rotated_cell() {
+        use ndarray::array;
+        let cell = array![[10.0, 0.0, 0.0], [2.0, 9.0, 0.0], [1.0, 1.5, 8.0]];
+        let frame = ConFrame::from_arrays(
+            &["Cu", "H", "Cu"],
+            array![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [2.0, 2.0, 2.0]].view(),
+            array![63.546, 1.008, 63.546].view(),
+            cell.view(),
+        )
+        .unwrap();
+        // Standard orientation: lengths/angles reproduce the matrix, no
+        // lattice_vectors metadata needed.
+        assert!(frame.header.lattice_vectors().is_none());
+        let back = frame.cell_matrix().unwrap();
+        for (x, y) in back.iter().zip(cell.iter()) {
+            assert!((x - y).abs() < 1e-9);
+        }
+        assert_eq!(*frame.atom_ids(), [0, 2, 1]);
+        assert_eq!(frame.positions_array().row(2).to_vec(), vec![1.0, 1.0, 1.0]);
+
+        // Rotated: a along y. Kept verbatim.
+        let rotated = array![[0.0, 10.0, 0.0], [-10.0, 0.0, 0.0], [0.0, 0.0, 10.0]];
+        let frame = ConFrame::from_arrays(
+            &["H"],
+            array![[0.0, 0.0, 0.0]].view(),
+            array![1.008].view(),
+            rotated.view(),
+        )
+        .unwrap();
+        assert_eq!(frame.header.boxl, [10.0; 3]);
+        assert_eq!(frame.cell_matrix().unwrap(), rotated);
+
+        let err = ConFrame::from_arrays(
+            &["H", "H"],
+            array![[0.0, 0.0, 0.0]].view(),
+            array![1.008, 1.008].view(),
+            rotated.view(),
+        );
+        assert!(matches!(
+            err,
+            Err(crate::error::ParseError::InvalidVectorLength {
+                expected: 6,
+                found: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_builder_try_build_validates() {
+        let mut ok = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
+        ok.add_atom("H", 0.0, 0.0, 0.0, [false; 3], 5, 1.008);
+        ok.add_atom("Cu", 1.0, 0.0, 0.0, [true; 3], 3, 63.546);
+        ok.add_atom("H", 2.0, 0.0, 0.0, [false; 3], 4, 1.008);
+        let frame = ok.clone().try_build().unwrap();
+        assert_eq!(frame, ok.build());
+
+        let mut dup = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
+        dup.add_atom("H", 0.0, 0.0, 0.0, [false; 3], 1, 1.008);
+        dup.add_atom("H", 1.0, 0.0, 0.0, [false; 3], 1, 1.008);
+        assert!(matches!(
+            dup.validate(),
+            Err(crate::error::ParseError::ValidationError(_))
+        ));
+
+        let mut bad_symbol = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
+        bad_symbol.add_atom("C u", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+        assert!(bad_symbol.validate().is_err());
+
+        let mut nan = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
+        nan.add_atom("H", f64::NAN, 0.0, 0.0, [false; 3], 0, 1.008);
+        assert!(nan.try_build().is_err());
+
+        let mut mass = ConFrameBuilder::new([10.0, 10.0, 10.0], [90.0, 90.0, 90.0]);
+        mass.add_atom("H", 0.0, 0.0, 0.0, [false; 3], 0, 1.008);
+        mass.add_atom("H", 1.0, 0.0, 0.0, [false; 3], 1, 1.00801);
+        assert!(mass.validate().is_err());
+        let tol = crate::tolerance::Tolerances::default();
+        assert!(mass.validate_with(&tol).is_ok());
+        assert!(mass.validate_with(&tol.with_mass(1e-6)).is_err());
     }
 
     #[test]
@@ -2458,6 +3524,7 @@ mod tests {
             natm_types: 0,
             natms_per_type: vec![],
             masses_per_type: vec![],
+            component_labels: Vec::new(),
             spec_version: 2,
             metadata: BTreeMap::new(),
             sections: Vec::new(),
@@ -2479,6 +3546,7 @@ mod tests {
             natm_types: 0,
             natms_per_type: vec![],
             masses_per_type: vec![],
+            component_labels: Vec::new(),
             spec_version: 2,
             metadata: BTreeMap::new(),
             sections: Vec::new(),
@@ -2504,6 +3572,7 @@ mod tests {
             natm_types: 0,
             natms_per_type: vec![],
             masses_per_type: vec![],
+            component_labels: Vec::new(),
             spec_version: 2,
             metadata: BTreeMap::new(),
             sections: Vec::new(),
@@ -2522,6 +3591,43 @@ mod tests {
         assert_eq!(header.neb_band(), Some(1));
     }
 
+    #[test]
+    fn test_frame_metadata_from_free_lines() {
+        let mut header = FrameHeader {
+            prebox_header: PreboxHeader::new("Generated by eOn E = -12.5"),
+            boxl: [10.0, 10.0, 10.0],
+            angles: [90.0, 90.0, 90.0],
+            postbox_header: ["time: 40".to_string(), "energy=1".to_string()],
+            natm_types: 0,
+            natms_per_type: vec![],
+            masses_per_type: vec![],
+            component_labels: Vec::new(),
+            spec_version: 2,
+            metadata: BTreeMap::new(),
+            sections: Vec::new(),
+            strict_validation: false,
+            sections_declared: false,
+        };
+        let m = header.frame_metadata();
+        assert_eq!((m.energy, m.time), (Some(-12.5), Some(40.0)));
+        assert_eq!(m.raw[0], "Generated by eOn E = -12.5");
+
+        // JSON metadata wins over the free text.
+        header.set_energy(-3.0);
+        assert_eq!(header.frame_metadata().energy, Some(-3.0));
+
+        let m = FrameMetadata {
+            energy: None,
+            time: Some(7.0),
+            raw: ["plain".into(), "0 0".into(), "0 0 0".into()],
+        };
+        header.set_frame_metadata(&m);
+        assert_eq!(header.prebox_header.user, "plain");
+        assert_eq!(header.postbox_header, ["0 0", "0 0 0"]);
+        assert_eq!((header.energy(), header.time()), (Some(-3.0), Some(7.0)));
+        assert_eq!(FrameMetadata::scan_line("time energy nan"), (None, None));
+    }
+
     #[test]
     fn project_positions_to_float32_storage_and_as_dlpack() {
         use crate::storage_dtype::FloatStorageKind;
@@ -2619,6 +3725,35 @@ mod tests {
         assert!((x_nm - 0.1).abs() < 1e-12);
     }
 
+    #[test]
+    fn undeclared_units_fall_back_to_assumed() {
+        let mut b = ConFrameBuilder::new([10.0; 3], [90.0, 90.0, 120.0]);
+        b.add_atom("H", 1.0, 2.0, 3.0, [false; 3], 0, 1.0);
+        let mut frame = b.build();
+        frame
+            .header
+            .set_units(serde_json::json!({"length": "bohr", "energy": "hartree"}));
+        assert_eq!(frame.unit_or_assumed("length"), Some("bohr"));
+        assert_eq!(frame.unit_or_assumed("time"), Some("fs"));
+        let to_ang = frame
+            .conversion_factor_or_assumed("length", "angstrom")
+            .unwrap();
+        assert!((to_ang - crate::units::ANGSTROM_PER_BOHR).abs() < 1e-12);
+
+        frame.header.metadata.remove(meta::UNITS);
+        assert!(frame.conversion_factor_to("length", "nm").is_err());
+        assert_eq!(frame.unit_or_assumed("length"), Some("angstrom"));
+        let to_nm = frame.conversion_factor_or_assumed("length", "nm").unwrap();
+        assert!((to_nm - 0.1).abs() < 1e-12);
+        assert!(
+            frame
+                .conversion_factor_or_assumed("force", "eV/angstrom")
+                .is_err()
+        );
+        let gamma = frame.header.angles_radians()[2];
+        assert!((gamma - 2.0 * std::f64::consts::FRAC_PI_3).abs() < 1e-12);
+    }
+
     #[test]
     fn test_metadata_helpers_units() {
         let mut header = FrameHeader {
@@ -2629,6 +3764,7 @@ mod tests {
             natm_types: 0,
             natms_per_type: vec![],
             masses_per_type: vec![],
+            component_labels: Vec::new(),
             spec_version: 2,
             metadata: BTreeMap::new(),
             sections: Vec::new(),
@@ -2652,6 +3788,7 @@ mod tests {
             natm_types: 0,
             natms_per_type: vec![],
             masses_per_type: vec![],
+            component_labels: Vec::new(),
             spec_version: 2,
             metadata: BTreeMap::new(),
             sections: Vec::new(),
@@ -2673,6 +3810,7 @@ mod tests {
             natm_types: 0,
             natms_per_type: vec![],
             masses_per_type: vec![],
+            component_labels: Vec::new(),
             spec_version: 2,
             metadata: BTreeMap::new(),
             sections: Vec::new(),
@@ -2858,13 +3996,13 @@ mod tests {
         let cu_ids: Vec<u64> = frame
             .atom_data
             .iter()
-            .filter(|a| a.symbol.as_ref() == "Cu")
+            .filter(|a| frame.symbol_of(a) == "Cu")
             .map(|a| a.atom_id)
             .collect();
         let h_ids: Vec<u64> = frame
             .atom_data
             .iter()
-            .filter(|a| a.symbol.as_ref() == "H")
+            .filter(|a| frame.symbol_of(a) == "H")
             .map(|a| a.atom_id)
             .collect();
         assert_eq!(cu_ids, vec![42, 7]);
@@ -424,13 +424,80 @@ pub fn validate_v3_units_metadata(units: &serde_json::Value) -> Result<(), Parse
     Ok(())
 }
 
+/// Length unit assumed for frames whose metadata declares none (Å).
+pub const ASSUMED_LENGTH_UNIT: &str = "angstrom";
+/// Energy unit assumed for frames whose metadata declares none.
+pub const ASSUMED_ENERGY_UNIT: &str = "eV";
+/// Mass unit of the per-type masses in the CON header.
+pub const ASSUMED_MASS_UNIT: &str = "amu";
+/// Time unit assumed for frames whose metadata declares none.
+pub const ASSUMED_TIME_UNIT: &str = "fs";
+/// Cell angles in the CON header are always in degrees; they are not part of
+/// `metadata["units"]`.
+pub const ANGLE_UNIT: &str = "degree";
+
+/// Å per nm.
+pub const ANGSTROM_PER_NM: f64 = 10.0;
+/// Å per Bohr radius (CODATA 2018).
+pub const ANGSTROM_PER_BOHR: f64 = 0.529_177_210_903;
+
+/// Unit assumed for `dimension` when a frame does not declare one, or `None`
+/// for dimensions without a convention (`velocity`, `force`, …).
+///
+/// # Example
+/// ```
+/// use readcon_core::units::assumed_unit;
+/// assert_eq!(assumed_unit("length"), Some("angstrom"));
+/// assert_eq!(assumed_unit("force"), None);
+/// ```
+pub fn assumed_unit(dimension: &str) -> Option<&'static str> {
+    match dimension {
+        "length" => Some(ASSUMED_LENGTH_UNIT),
+        "energy" => Some(ASSUMED_ENERGY_UNIT),
+        "mass" => Some(ASSUMED_MASS_UNIT),
+        "time" => Some(ASSUMED_TIME_UNIT),
+        "angle" => Some(ANGLE_UNIT),
+        _ => None,
+    }
+}
+
+/// Converts a length from Å to nm.
+pub fn angstrom_to_nm(x: f64) -> f64 {
+    x / ANGSTROM_PER_NM
+}
+
+/// Converts a length from nm to Å.
+pub fn nm_to_angstrom(x: f64) -> f64 {
+    x * ANGSTROM_PER_NM
+}
+
+/// Converts a length from Å to Bohr.
+pub fn angstrom_to_bohr(x: f64) -> f64 {
+    x / ANGSTROM_PER_BOHR
+}
+
+/// Converts a length from Bohr to Å.
+pub fn bohr_to_angstrom(x: f64) -> f64 {
+    x * ANGSTROM_PER_BOHR
+}
+
+/// Converts cell angles `[alpha, beta, gamma]` from degrees to radians.
+pub fn angles_to_radians(angles: [f64; 3]) -> [f64; 3] {
+    angles.map(f64::to_radians)
+}
+
+/// Converts cell angles `[alpha, beta, gamma]` from radians to degrees.
+pub fn angles_to_degrees(angles: [f64; 3]) -> [f64; 3] {
+    angles.map(f64::to_degrees)
+}
+
 /// Default LODE units object for new v3 frames.
 pub fn default_v3_units_json() -> serde_json::Value {
     serde_json::json!({
-        "length": "angstrom",
-        "energy": "eV",
-        "mass": "amu",
-        "time": "fs"
+        "length": ASSUMED_LENGTH_UNIT,
+        "energy": ASSUMED_ENERGY_UNIT,
+        "mass": ASSUMED_MASS_UNIT,
+        "time": ASSUMED_TIME_UNIT
     })
 }
 
@@ -467,4 +534,20 @@ mod tests {
         assert!(validate_v3_units_metadata(&serde_json::json!({"length": "A"})).is_err());
         validate_v3_units_metadata(&default_v3_units_json()).unwrap();
     }
+
+    #[test]
+    fn helpers_agree_with_unit_table() {
+        let nm = unit_conversion_factor("angstrom", "nm").unwrap();
+        assert!((super::angstrom_to_nm(2.5) - 2.5 * nm).abs() < 1e-12);
+        assert!((nm_to_angstrom(super::angstrom_to_nm(3.7)) - 3.7).abs() < 1e-12);
+        let bohr = unit_conversion_factor("bohr", "angstrom").unwrap();
+        assert!((bohr_to_angstrom(1.0) - bohr).abs() < 1e-12);
+        assert!((angstrom_to_bohr(bohr_to_angstrom(4.2)) - 4.2).abs() < 1e-12);
+        let rad = angles_to_radians([90.0, 60.0, 180.0]);
+        assert!((rad[0] - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((angles_to_degrees(rad)[1] - 60.0).abs() < 1e-12);
+        for dim in ["length", "energy", "mass", "time"] {
+            validate_unit_for_quantity(dim, assumed_unit(dim).unwrap()).unwrap();
+        }
+    }
 }
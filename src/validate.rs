@@ -0,0 +1,344 @@
+//! Whole-file validation with located diagnostics.
+//!
+//! [`validate_str`] parses every frame with [`ParserOptions::validate`] set,
+//! so the strict checks a file normally opts into with `"validate": true`
+//! (metadata schema, component labels, fixed-flag and atom-id columns,
+//! section identity) always run, and recovers after a bad frame so one
+//! report covers the whole file. Parsed frames then get semantic checks
+//! the grammar does not enforce:
+//!
+//! | Check | Severity |
+//! |-------|----------|
+//! | non-finite cell, position, velocity or force | error |
+//! | two atoms with the same `atom_id` | error |
+//! | degenerate cell (zero volume, angles outside 0–180°) | warning |
+//! | legacy frame without JSON metadata | warning |
+//! | atom type with no atoms, symbol repeated across types | warning |
+//! | symbol that is neither an element nor an isotope label | warning |
+//! | mass more than 0.5 amu from the standard (or isotopic) mass | warning |
+//!
+//! Every [`Issue`] carries the frame index and a 1-based line number: the
+//! offending atom line where there is one, else the frame's first line.
+//! `readcon validate` prints the report as text or JSON.
+
+use crate::cell::Cell;
+use crate::helpers::{parse_isotope, symbol_to_atomic_number};
+use crate::iterators::ConFrameIterator;
+use crate::masses::{MASS_WARNING_THRESHOLD, masses_in_amu, reference_mass};
+use crate::parser::ParserOptions;
+use crate::types::ConFrame;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// How serious an [`Issue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Suspicious but readable.
+    Warning,
+    /// The file is malformed or a value is unusable.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// One finding of [`validate_str`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    /// Zero-based frame index.
+    pub frame: usize,
+    /// One-based line number in the validated text.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: frame {}: {}",
+            self.line, self.severity, self.frame, self.message
+        )
+    }
+}
+
+/// Outcome of [`validate_str`] / [`validate_path`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Frames seen, including ones that failed to parse.
+    pub nframes: usize,
+    /// Findings in file order.
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// Number of issues with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+
+    /// The most serious severity found, `None` for a clean file.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues.iter().map(|i| i.severity).max()
+    }
+
+    /// Machine-readable form: `{"frames", "errors", "warnings", "issues":
+    /// [{"severity", "frame", "line", "message"}]}`.
+    pub fn to_json(&self) -> Value {
+        let issues: Vec<Value> = self
+            .issues
+            .iter()
+            .map(|i| {
+                json!({
+                    "severity": i.severity.to_string(),
+                    "frame": i.frame,
+                    "line": i.line,
+                    "message": i.message,
+                })
+            })
+            .collect();
+        json!({
+            "frames": self.nframes,
+            "errors": self.count(Severity::Error),
+            "warnings": self.count(Severity::Warning),
+            "issues": issues,
+        })
+    }
+}
+
+/// Collects the issues of one parsed frame starting at line `start`.
+struct FrameChecker<'a> {
+    frame: &'a ConFrame,
+    index: usize,
+    start: usize,
+    issues: &'a mut Vec<Issue>,
+}
+
+impl FrameChecker<'_> {
+    fn push(&mut self, severity: Severity, line: usize, message: String) {
+        self.issues.push(Issue {
+            severity,
+            frame: self.index,
+            line,
+            message,
+        });
+    }
+
+    /// Line of atom `i` in the coordinate block: 9 header lines, then a
+    /// symbol and a label line before each type's atoms.
+    fn atom_line(&self, i: usize) -> usize {
+        let mut before = 0;
+        let mut type_idx = 0;
+        for (t, &n) in self.frame.header.natms_per_type.iter().enumerate() {
+            type_idx = t;
+            if i < before + n {
+                break;
+            }
+            before += n;
+        }
+        self.start + 9 + 2 * (type_idx + 1) + i
+    }
+
+    fn check(mut self) {
+        let h = &self.frame.header;
+        let start = self.start;
+        if h.spec_version < 2 {
+            self.push(
+                Severity::Warning,
+                start + 1,
+                "legacy frame without JSON metadata (con_spec_version 1)".into(),
+            );
+        }
+        if h.boxl.iter().chain(&h.angles).any(|v| !v.is_finite()) {
+            self.push(
+                Severity::Error,
+                start + 2,
+                "cell lengths and angles must be finite".into(),
+            );
+        } else if h.angles.iter().any(|a| *a <= 0.0 || *a >= 180.0)
+            || Cell::from_header(h).is_none_or(|c| c.volume().abs() < 1e-12)
+        {
+            self.push(
+                Severity::Warning,
+                start + 2,
+                format!(
+                    "degenerate cell: lengths {:?}, angles {:?}",
+                    h.boxl, h.angles
+                ),
+            );
+        }
+
+        let mut offset = 0;
+        let mut seen_symbols: HashMap<&str, usize> = HashMap::new();
+        let amu = masses_in_amu(h);
+        for (t, &n) in h.natms_per_type.iter().enumerate() {
+            let type_line = self.atom_line(offset) - 2;
+            if n == 0 {
+                self.push(
+                    Severity::Warning,
+                    start + 7,
+                    format!("atom type {} has no atoms", t + 1),
+                );
+                continue;
+            }
+            let symbol = self.frame.symbol(offset);
+            if let Some(first) = seen_symbols.insert(symbol, t) {
+                self.push(
+                    Severity::Warning,
+                    type_line,
+                    format!("symbol {symbol} used by types {} and {}", first + 1, t + 1),
+                );
+            }
+            if symbol_to_atomic_number(symbol) == 0 && parse_isotope(symbol).is_none() {
+                self.push(
+                    Severity::Warning,
+                    type_line,
+                    format!("symbol {symbol:?} is not an element"),
+                );
+            } else if let (true, Some(mass), Some(standard)) =
+                (amu, h.masses_per_type.get(t), reference_mass(symbol))
+                && (mass - standard).abs() > MASS_WARNING_THRESHOLD
+            {
+                self.push(
+                    Severity::Warning,
+                    start + 9,
+                    format!("mass {mass} for {symbol} differs from the standard {standard}"),
+                );
+            }
+            offset += n;
+        }
+
+        let mut ids: HashMap<u64, usize> = HashMap::with_capacity(self.frame.atom_data.len());
+        for (i, atom) in self.frame.atom_data.iter().enumerate() {
+            let line = self.atom_line(i);
+            let vectors = [
+                ("position", Some([atom.x, atom.y, atom.z])),
+                ("velocity", atom.velocity),
+                ("force", atom.force),
+            ];
+            for (name, v) in vectors {
+                if v.is_some_and(|v| v.iter().any(|x| !x.is_finite())) {
+                    self.push(
+                        Severity::Error,
+                        line,
+                        format!("atom {i}: {name} is not finite"),
+                    );
+                }
+            }
+            if let Some(prev) = ids.insert(atom.atom_id, i) {
+                self.push(
+                    Severity::Error,
+                    line,
+                    format!(
+                        "atoms {prev} and {i} share atom_id {} (line {})",
+                        atom.atom_id,
+                        self.atom_line(prev)
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Validates every frame of `text`; see the [module docs](self).
+pub fn validate_str(text: &str) -> ValidationReport {
+    let options = ParserOptions {
+        validate: true,
+        ..Default::default()
+    };
+    let mut it = ConFrameIterator::new(text).options(options).recover(true);
+    let mut report = ValidationReport::default();
+    let (mut line, mut counted) = (1, 0);
+    loop {
+        let offset = it.offset();
+        let Some(item) = it.next() else { break };
+        line += memchr::memchr_iter(b'\n', &text.as_bytes()[counted..offset]).count();
+        counted = offset;
+        let index = report.nframes;
+        report.nframes += 1;
+        match item {
+            Ok(frame) => FrameChecker {
+                frame: &frame,
+                index,
+                start: line,
+                issues: &mut report.issues,
+            }
+            .check(),
+            Err(e) => report.issues.push(Issue {
+                severity: Severity::Error,
+                frame: index,
+                line,
+                message: e.to_string(),
+            }),
+        }
+    }
+    report
+}
+
+/// [`validate_str`] on a file (plain, `.gz` or `.zst`).
+pub fn validate_path(path: &Path) -> Result<ValidationReport, Box<dyn std::error::Error>> {
+    let contents = crate::compression::read_file_contents(path)?;
+    Ok(validate_str(contents.as_str()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::fixture;
+
+    #[test]
+    fn clean_fixture_has_no_errors() {
+        let report = validate_str(&fixture("tiny_multi_cuh2.con"));
+        assert_eq!(report.nframes, 2);
+        assert_eq!(report.count(Severity::Error), 0, "{:?}", report.issues);
+    }
+
+    #[test]
+    fn reports_located_issues_and_recovers() {
+        let one = fixture("tiny_cuh2.con");
+        let nlines = one.lines().count();
+        // Frame 1: second atom reuses atom_id 0 and has a NaN coordinate.
+        let lines: Vec<&str> = one.lines().collect();
+        let first_atom = lines
+            .iter()
+            .position(|l| l.starts_with("Coordinates of Component 1"))
+            .unwrap()
+            + 1;
+        let mut bad: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        let cols: Vec<&str> = lines[first_atom].split_whitespace().collect();
+        bad[first_atom + 1] = format!("NaN {} {} {} {}", cols[1], cols[2], cols[3], cols[4]);
+        let bad = bad.join("\n") + "\n";
+        let text = format!("{one}{bad}garbage\n{one}");
+
+        let report = validate_str(&text);
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+        let errors: Vec<&Issue> = report
+            .issues
+            .iter()
+            .filter(|i| i.severity == Severity::Error)
+            .collect();
+        assert!(
+            errors
+                .iter()
+                .any(|i| i.frame == 1 && i.line == nlines + first_atom + 2),
+            "{errors:?}"
+        );
+        assert!(errors.iter().any(|i| i.message.contains("not finite")));
+        // The garbage line fails as a frame, then validation carries on.
+        assert!(report.nframes >= 3);
+        let json = report.to_json();
+        assert_eq!(json["errors"], report.count(Severity::Error));
+    }
+}
@@ -0,0 +1,320 @@
+//! Parser/writer round-trip checking.
+//!
+//! For each frame `f = parse(x)`, [`verify_frame`] writes `f` with
+//! [`ConFrameWriter`], parses the text again and compares the result with
+//! `f` field by field. Text fields, counts, flags, ids and metadata must
+//! match exactly. Floats are written with a fixed number of decimals, so
+//! they only have to agree to within half a unit in the last written place
+//! (plus float round-off); at the default precision of 6 that is 5e-7.
+//! The first differing field is reported as a [`Divergence`].
+//!
+//! `readcon verify <file>...` runs the same check from the command
+//! line.
+
+use crate::error::ParseError;
+use crate::iterators::ConFrameIterator;
+use crate::types::ConFrame;
+use crate::writer::ConFrameWriter;
+use std::fmt;
+use std::path::Path;
+
+/// Writer settings used for the round trip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Decimal places passed to [`ConFrameWriter::with_precision`].
+    pub precision: usize,
+    /// Write in canonical mode ([`ConFrameWriter::canonical`]).
+    pub canonical: bool,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        VerifyOptions {
+            precision: crate::writer::DEFAULT_FLOAT_PRECISION,
+            canonical: false,
+        }
+    }
+}
+
+/// First field that did not survive the round trip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// Zero-based frame index in the input.
+    pub frame: usize,
+    /// Field path, e.g. `header.boxl[0]` or `atom[3].force[2]`.
+    pub field: String,
+    /// Value parsed from the input.
+    pub original: String,
+    /// Value after write and re-parse (`<unreadable>` details when the
+    /// written text failed to parse).
+    pub roundtrip: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame {}: {} differs after write+parse: {} -> {}",
+            self.frame, self.field, self.original, self.roundtrip
+        )
+    }
+}
+
+/// Outcome of [`verify_str`] / [`verify_path`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyReport {
+    /// Frames checked (up to and including a divergent one).
+    pub nframes: usize,
+    /// First divergence, `None` when every frame round-tripped.
+    pub divergence: Option<Divergence>,
+}
+
+impl VerifyReport {
+    /// True when every frame round-tripped.
+    pub fn is_ok(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+/// `frame` written with `options` and parsed back.
+pub fn roundtrip_frame(frame: &ConFrame, options: &VerifyOptions) -> Result<ConFrame, ParseError> {
+    let mut writer =
+        ConFrameWriter::with_precision(Vec::new(), options.precision).canonical(options.canonical);
+    let io = |e: std::io::Error| ParseError::ValidationError(format!("write failed: {e}"));
+    writer.write_frame(frame).map_err(io)?;
+    let bytes = writer.into_inner().map_err(io)?;
+    let text = String::from_utf8(bytes)
+        .map_err(|e| ParseError::ValidationError(format!("writer produced invalid UTF-8: {e}")))?;
+    ConFrameIterator::new(&text)
+        .next()
+        .unwrap_or(Err(ParseError::IncompleteHeader))
+}
+
+/// Checks one frame (numbered `index` in reports); see the
+/// [module docs](self).
+pub fn verify_frame(
+    frame: &ConFrame,
+    index: usize,
+    options: &VerifyOptions,
+) -> Result<(), Divergence> {
+    let back = roundtrip_frame(frame, options).map_err(|e| Divergence {
+        frame: index,
+        field: "frame".into(),
+        original: "parsed".into(),
+        roundtrip: format!("<unreadable: {e}>"),
+    })?;
+    let tol = 0.5 * 10f64.powi(-(options.precision.min(300) as i32));
+    Comparer { tol, found: None }.frames(frame, &back).map_or(
+        Ok(()),
+        |(field, original, roundtrip)| {
+            Err(Divergence {
+                frame: index,
+                field,
+                original,
+                roundtrip,
+            })
+        },
+    )
+}
+
+/// Parses every frame of `text` and verifies it, stopping at the first
+/// divergence. Errors are parse errors of the input itself.
+pub fn verify_str(text: &str, options: &VerifyOptions) -> Result<VerifyReport, ParseError> {
+    let mut nframes = 0;
+    for (index, frame) in ConFrameIterator::new(text).enumerate() {
+        let frame = frame?;
+        nframes += 1;
+        if let Err(d) = verify_frame(&frame, index, options) {
+            return Ok(VerifyReport {
+                nframes,
+                divergence: Some(d),
+            });
+        }
+    }
+    Ok(VerifyReport {
+        nframes,
+        divergence: None,
+    })
+}
+
+/// [`verify_str`] on a file (plain, gzip or zstd).
+pub fn verify_path(
+    path: &Path,
+    options: &VerifyOptions,
+) -> Result<VerifyReport, Box<dyn std::error::Error>> {
+    let contents = crate::compression::read_file_contents(path)?;
+    Ok(verify_str(contents.as_str()?, options)?)
+}
+
+/// Field-by-field comparison, keeping the first mismatch.
+struct Comparer {
+    tol: f64,
+    found: Option<(String, String, String)>,
+}
+
+impl Comparer {
+    fn exact<T: PartialEq + fmt::Debug>(&mut self, field: impl FnOnce() -> String, a: &T, b: &T) {
+        if self.found.is_none() && a != b {
+            self.found = Some((field(), format!("{a:?}"), format!("{b:?}")));
+        }
+    }
+
+    fn float(&mut self, field: impl FnOnce() -> String, a: f64, b: f64) {
+        if self.found.is_some() || a == b || (a.is_nan() && b.is_nan()) {
+            return;
+        }
+        let slack = self.tol + 4.0 * f64::EPSILON * a.abs().max(b.abs());
+        // Unequal non-finite values (inf vs finite, NaN vs number) never match.
+        if !(a.is_finite() && b.is_finite()) || (a - b).abs() > slack {
+            self.found = Some((field(), format!("{a:?}"), format!("{b:?}")));
+        }
+    }
+
+    fn floats(&mut self, field: &str, a: &[f64], b: &[f64]) {
+        self.exact(|| format!("{field}.len()"), &a.len(), &b.len());
+        for (k, (x, y)) in a.iter().zip(b).enumerate() {
+            self.float(|| format!("{field}[{k}]"), *x, *y);
+        }
+    }
+
+    fn optional<const N: usize>(&mut self, field: &str, a: Option<[f64; N]>, b: Option<[f64; N]>) {
+        match (a, b) {
+            (Some(a), Some(b)) => self.floats(field, &a, &b),
+            _ => self.exact(|| field.to_string(), &a.is_some(), &b.is_some()),
+        }
+    }
+
+    fn frames(mut self, a: &ConFrame, b: &ConFrame) -> Option<(String, String, String)> {
+        let (ha, hb) = (&a.header, &b.header);
+        self.exact(
+            || "header.comment".into(),
+            &ha.prebox_header.user,
+            &hb.prebox_header.user,
+        );
+        self.floats("header.boxl", &ha.boxl, &hb.boxl);
+        self.floats("header.angles", &ha.angles, &hb.angles);
+        self.exact(
+            || "header.postbox".into(),
+            &ha.postbox_header,
+            &hb.postbox_header,
+        );
+        self.exact(
+            || "header.natms_per_type".into(),
+            &ha.natms_per_type,
+            &hb.natms_per_type,
+        );
+        self.floats(
+            "header.masses_per_type",
+            &ha.masses_per_type,
+            &hb.masses_per_type,
+        );
+        self.exact(
+            || "header.spec_version".into(),
+            &ha.spec_version,
+            &hb.spec_version,
+        );
+        for key in ha.metadata.keys().chain(hb.metadata.keys()) {
+            self.exact(
+                || format!("header.metadata[{key:?}]"),
+                &ha.metadata.get(key),
+                &hb.metadata.get(key),
+            );
+        }
+        self.exact(
+            || "atom_data.len()".into(),
+            &a.atom_data.len(),
+            &b.atom_data.len(),
+        );
+        for (i, (x, y)) in a.atom_data.iter().zip(&b.atom_data).enumerate() {
+            self.exact(
+                || format!("atom[{i}].symbol"),
+                &a.symbol_of(x),
+                &b.symbol_of(y),
+            );
+            self.floats(
+                &format!("atom[{i}].position"),
+                &[x.x, x.y, x.z],
+                &[y.x, y.y, y.z],
+            );
+            self.exact(|| format!("atom[{i}].fixed"), &x.fixed, &y.fixed);
+            self.exact(|| format!("atom[{i}].atom_id"), &x.atom_id, &y.atom_id);
+            self.optional(&format!("atom[{i}].velocity"), x.velocity, y.velocity);
+            self.optional(&format!("atom[{i}].force"), x.force, y.force);
+            self.optional(
+                &format!("atom[{i}].energy"),
+                x.energy.map(|v| [v]),
+                y.energy.map(|v| [v]),
+            );
+            self.optional(
+                &format!("atom[{i}].charge"),
+                x.charge.map(|v| [v]),
+                y.charge.map(|v| [v]),
+            );
+            self.optional(
+                &format!("atom[{i}].spin"),
+                x.spin.map(|v| [v]),
+                y.spin.map(|v| [v]),
+            );
+            self.optional(&format!("atom[{i}].magmom"), x.magmom, y.magmom);
+            if self.found.is_some() {
+                break;
+            }
+        }
+        self.found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::fixture;
+
+    #[test]
+    fn fixtures_roundtrip() {
+        for name in [
+            "tiny_cuh2.con",
+            "tiny_multi_cuh2.con",
+            "tiny_cuh2.convel",
+            "tiny_multi_cuh2.convel",
+            "tiny_cuh2_dynamics.con",
+            "tiny_cuh2_forces.con",
+            "tiny_cuh2_vel_forces.con",
+            "tiny_cuh2_charges_spins_magmoms.con",
+            "cuh2.con",
+            "sulfolene.con",
+        ] {
+            for canonical in [false, true] {
+                let options = VerifyOptions {
+                    canonical,
+                    ..VerifyOptions::default()
+                };
+                let report = verify_str(&fixture(name), &options).unwrap();
+                assert!(report.is_ok(), "{name}: {}", report.divergence.unwrap());
+                assert!(report.nframes > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn reports_field_level_divergence() {
+        let text = fixture("tiny_cuh2_forces.con");
+        let mut frame = ConFrameIterator::new(&text).next().unwrap().unwrap();
+        // Anything within half a unit of the last written decimal passes.
+        frame.atom_data[0].force = Some([0.1234564, 0.0, 0.0]);
+        assert!(verify_frame(&frame, 0, &VerifyOptions::default()).is_ok());
+
+        // A two-line comment shifts every following header line.
+        let mut broken = frame.clone();
+        broken.header.prebox_header.user = "first\nsecond".into();
+        let d = verify_frame(&broken, 4, &VerifyOptions::default()).unwrap_err();
+        assert_eq!(d.frame, 4);
+        assert!(d.to_string().starts_with("frame 4: "));
+
+        // The writer emits one symbol per type block, so a stray symbol
+        // inside a block is lost.
+        frame.set_symbol(1, "Ag").unwrap();
+        let d = verify_frame(&frame, 0, &VerifyOptions::default()).unwrap_err();
+        assert_eq!(d.field, "atom[1].symbol");
+        assert_eq!((d.original.as_str(), d.roundtrip.as_str()), ("\"Ag\"", "\"Cu\""));
+    }
+}
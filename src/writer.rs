@@ -1,14 +1,98 @@
 use crate::types::{
-    ConFrame, SECTION_CHARGES, SECTION_ENERGIES, SECTION_FORCES, SECTION_MAGMOMS, SECTION_SPINS,
-    SECTION_VELOCITIES, encode_fixed_bitmask, meta,
+    ConFrame, FrameHeader, FrameMetadata, SECTION_CHARGES, SECTION_ENERGIES, SECTION_FORCES,
+    SECTION_MAGMOMS, SECTION_SPINS, SECTION_VELOCITIES, encode_fixed_bitmask, meta,
 };
 use serde_json::json;
-use std::fs::File;
-use std::io::{self, BufWriter, Write};
-use std::path::Path;
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Default floating-point precision used for writing coordinates, cell dimensions, and masses.
-const DEFAULT_FLOAT_PRECISION: usize = 6;
+pub(crate) const DEFAULT_FLOAT_PRECISION: usize = 6;
+
+/// What [`ConFrameWriter`] writes on the label line after each component's
+/// symbol.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ComponentLabels {
+    /// The labels read from the file ([`crate::types::FrameHeader::component_labels`]),
+    /// `Coordinates of Component N` where there are none.
+    #[default]
+    Preserve,
+    /// Always `Coordinates of Component N`, discarding stored labels.
+    Standard,
+    /// Every label generated from a template in which `{n}` is replaced by
+    /// the 1-based component index and `{symbol}` by its symbol.
+    Template(String),
+}
+
+impl ComponentLabels {
+    fn label<'a>(&self, frame: &'a ConFrame, type_idx: usize, symbol: &str) -> Cow<'a, str> {
+        match self {
+            Self::Preserve => frame.header.component_label(type_idx),
+            Self::Standard => Cow::Owned(FrameHeader::standard_component_label(type_idx)),
+            Self::Template(t) => Cow::Owned(
+                t.replace("{n}", &(type_idx + 1).to_string())
+                    .replace("{symbol}", symbol),
+            ),
+        }
+    }
+}
+
+/// The user line with ` energy=E` / ` time=T` appended from the JSON
+/// metadata, each only when the line does not already carry that value.
+fn annotated_user_line(header: &FrameHeader) -> Cow<'_, str> {
+    let mut line = Cow::Borrowed(header.prebox_header.user.as_str());
+    let (energy, time) = FrameMetadata::scan_line(&line);
+    for (key, value, present) in [
+        ("energy", header.energy(), energy.is_some()),
+        ("time", header.time(), time.is_some()),
+    ] {
+        if let (Some(v), false) = (value, present) {
+            let line = line.to_mut();
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&format!("{key}={v}"));
+        }
+    }
+    line
+}
+
+/// The per-component layout [`ConFrameWriter::write_frame`] relies on.
+fn check_layout(frame: &ConFrame) -> io::Result<()> {
+    let header = &frame.header;
+    let natoms = header
+        .natms_per_type
+        .iter()
+        .try_fold(0usize, |sum, &n| sum.checked_add(n));
+    if header.natm_types != header.natms_per_type.len()
+        || header.masses_per_type.len() != header.natms_per_type.len()
+        || natoms != Some(frame.atom_data.len())
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "frame header does not match its atoms: {} components, counts {:?}, {} masses, {} atoms",
+                header.natm_types,
+                header.natms_per_type,
+                header.masses_per_type.len(),
+                frame.atom_data.len()
+            ),
+        ));
+    }
+    if let Some(empty) = header.natms_per_type.iter().position(|&n| n == 0) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "component {} has no atoms, so it has no symbol to write",
+                empty + 1
+            ),
+        ));
+    }
+    Ok(())
+}
 
 /// A writer that can serialize and write `ConFrame` objects to any output stream.
 ///
@@ -39,6 +123,15 @@ pub struct ConFrameWriter<W: Write> {
     /// and re-serialisation. Hot for trajectory writes where every
     /// frame has the same `units` / `potential` / `validate` keys.
     metadata_cache: Option<MetadataCacheEntry>,
+    /// When true, [`ConFrame::trailing_lines`] are written after each frame.
+    trailing_lines: bool,
+    /// When true, each component's velocities follow its coordinates (eOn
+    /// dynamics layout) instead of forming a separate section.
+    interleaved_velocities: bool,
+    /// Label line written after each component's symbol.
+    component_labels: ComponentLabels,
+    /// When true, energy and time are also written into the user line.
+    annotate_user_line: bool,
 }
 
 #[derive(Debug)]
@@ -94,6 +187,10 @@ impl<W: Write> ConFrameWriter<W> {
             precision: DEFAULT_FLOAT_PRECISION,
             canonical: false,
             metadata_cache: None,
+            trailing_lines: false,
+            interleaved_velocities: false,
+            component_labels: ComponentLabels::Preserve,
+            annotate_user_line: false,
         }
     }
 
@@ -109,6 +206,10 @@ impl<W: Write> ConFrameWriter<W> {
             precision,
             canonical: false,
             metadata_cache: None,
+            trailing_lines: false,
+            interleaved_velocities: false,
+            component_labels: ComponentLabels::Preserve,
+            annotate_user_line: false,
         }
     }
 
@@ -134,12 +235,101 @@ impl<W: Write> ConFrameWriter<W> {
         self.canonical
     }
 
+    /// Opt-in: write each frame's [`ConFrame::trailing_lines`] after it, so
+    /// a file read in [`crate::parser::ParseMode::Permissive`] round-trips
+    /// without losing them. Off by default because the strict grammar
+    /// rejects such lines.
+    pub fn keep_trailing_lines(mut self, on: bool) -> Self {
+        self.set_keep_trailing_lines(on);
+        self
+    }
+
+    /// Set or clear trailing-line output on an existing writer.
+    pub fn set_keep_trailing_lines(&mut self, on: bool) {
+        self.trailing_lines = on;
+    }
+
+    /// Whether trailing lines are written.
+    pub fn keeps_trailing_lines(&self) -> bool {
+        self.trailing_lines
+    }
+
+    /// Opt-in eOn dynamics layout: each component's `Velocities of
+    /// Component N` block directly follows its coordinates, with no blank
+    /// separator and no `velocities` entry in the metadata `sections`.
+    pub fn interleaved_velocities(mut self, on: bool) -> Self {
+        self.set_interleaved_velocities(on);
+        self
+    }
+
+    /// Set or clear the interleaved velocity layout on an existing writer.
+    pub fn set_interleaved_velocities(&mut self, on: bool) {
+        self.interleaved_velocities = on;
+        self.metadata_cache = None;
+    }
+
+    /// Whether velocities are written in the interleaved layout.
+    pub fn is_interleaving_velocities(&self) -> bool {
+        self.interleaved_velocities
+    }
+
+    /// Choose the component label lines; [`ComponentLabels::Preserve`] by
+    /// default. Labels other than `Coordinates of Component N` are only
+    /// read back outside validate mode, which requires the standard text.
+    pub fn component_labels(mut self, labels: ComponentLabels) -> Self {
+        self.set_component_labels(labels);
+        self
+    }
+
+    /// Set the component label lines on an existing writer.
+    pub fn set_component_labels(&mut self, labels: ComponentLabels) {
+        self.component_labels = labels;
+    }
+
+    /// How component label lines are written.
+    pub fn component_label_mode(&self) -> &ComponentLabels {
+        &self.component_labels
+    }
+
+    /// Opt-in: append the metadata `energy` and `time` to the user line as
+    /// `energy=E time=T` for tools that only read the free-text header.
+    /// A value the line already holds (see
+    /// [`FrameMetadata::scan_line`]) is not repeated.
+    pub fn annotate_user_line(mut self, on: bool) -> Self {
+        self.set_annotate_user_line(on);
+        self
+    }
+
+    /// Set or clear user-line annotation on an existing writer.
+    pub fn set_annotate_user_line(&mut self, on: bool) {
+        self.annotate_user_line = on;
+    }
+
+    /// Whether energy and time are written into the user line.
+    pub fn annotates_user_line(&self) -> bool {
+        self.annotate_user_line
+    }
+
     /// Writes a single `ConFrame` to the output stream.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`], before writing anything,
+    /// when the header's component counts and masses do not describe
+    /// `atom_data`, or a component has no atoms (its symbol is only stored
+    /// on its atoms).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(atoms = frame.atom_data.len()))
+    )]
     pub fn write_frame(&mut self, frame: &ConFrame) -> io::Result<()> {
+        check_layout(frame)?;
         let prec = self.precision;
 
         // --- Write the 9-line Header ---
-        writeln!(self.writer, "{}", frame.header.prebox_header.user)?;
+        if self.annotate_user_line {
+            writeln!(self.writer, "{}", annotated_user_line(&frame.header))?;
+        } else {
+            writeln!(self.writer, "{}", frame.header.prebox_header.user)?;
+        }
 
         // Line 2: serialised JSON metadata. The serialisation is
         // deterministic in (spec_version, has_*, metadata), so we
@@ -149,10 +339,15 @@ impl<W: Write> ConFrameWriter<W> {
         // keys this avoids rebuilding and re-serialising the JSON
         // object on every frame.
         let spec_version = frame.header.spec_version;
-        let has_vel = frame.has_velocities();
+        let interleave = self.interleaved_velocities && frame.has_velocities();
+        // Interleaved velocities are part of the coordinate block, not a
+        // declared section.
+        let has_vel = frame.has_velocities() && !interleave;
         let has_frc = frame.has_forces();
         let has_eng = frame.has_energies();
-        let has_chg = frame.has_charges();
+        // Charges in a declared extra column are not repeated as a section.
+        let charge_column = frame.header.charge_column();
+        let has_chg = frame.has_charges() && charge_column.is_none();
         let has_spn = frame.has_spins();
         let has_mm = frame.has_magmoms();
 
@@ -277,13 +472,14 @@ impl<W: Write> ConFrameWriter<W> {
         // --- Write the Atom Data ---
         let mut atom_idx_offset = 0;
         for (type_idx, &num_atoms_in_type) in frame.header.natms_per_type.iter().enumerate() {
-            let symbol = &frame.atom_data[atom_idx_offset].symbol;
+            let symbol = frame.symbol(atom_idx_offset);
             writeln!(self.writer, "{}", symbol)?;
-            writeln!(self.writer, "Coordinates of Component {}", type_idx + 1)?;
+            let label = self.component_labels.label(frame, type_idx, symbol);
+            writeln!(self.writer, "{label}")?;
 
             for i in 0..num_atoms_in_type {
                 let atom = &frame.atom_data[atom_idx_offset + i];
-                writeln!(
+                write!(
                     self.writer,
                     "{x:.prec$} {y:.prec$} {z:.prec$} {fixed_flag} {atom_id}",
                     prec = prec,
@@ -293,18 +489,43 @@ impl<W: Write> ConFrameWriter<W> {
                     fixed_flag = encode_fixed_bitmask(atom.fixed),
                     atom_id = atom.atom_id
                 )?;
+                // A declared charge column takes its place among the extras.
+                let k = charge_column
+                    .filter(|&k| atom.charge.is_some() && k <= atom.extra.len())
+                    .unwrap_or(atom.extra.len());
+                let (before, after) = atom.extra.split_at(k);
+                let charge = atom.charge.filter(|_| charge_column == Some(k));
+                for v in before.iter().chain(&charge).chain(after) {
+                    write!(self.writer, " {v:.prec$}")?;
+                }
+                writeln!(self.writer)?;
+            }
+            if interleave {
+                writeln!(self.writer, "{}", symbol)?;
+                writeln!(self.writer, "Velocities of Component {}", type_idx + 1)?;
+                for i in 0..num_atoms_in_type {
+                    let atom = &frame.atom_data[atom_idx_offset + i];
+                    let [vx, vy, vz] = atom.velocity.unwrap_or([0.0; 3]);
+                    writeln!(
+                        self.writer,
+                        "{vx:.prec$} {vy:.prec$} {vz:.prec$} {fixed_flag} {atom_id}",
+                        prec = prec,
+                        fixed_flag = encode_fixed_bitmask(atom.fixed),
+                        atom_id = atom.atom_id
+                    )?;
+                }
             }
             atom_idx_offset += num_atoms_in_type;
         }
 
         // --- Write optional velocity section ---
-        if frame.has_velocities() {
+        if has_vel {
             // Blank separator line between coordinates and velocities
             writeln!(self.writer)?;
 
             let mut vel_idx_offset = 0;
             for (type_idx, &num_atoms_in_type) in frame.header.natms_per_type.iter().enumerate() {
-                let symbol = &frame.atom_data[vel_idx_offset].symbol;
+                let symbol = frame.symbol(vel_idx_offset);
                 writeln!(self.writer, "{}", symbol)?;
                 writeln!(self.writer, "Velocities of Component {}", type_idx + 1)?;
 
@@ -330,7 +551,7 @@ impl<W: Write> ConFrameWriter<W> {
 
             let mut force_idx_offset = 0;
             for (type_idx, &num_atoms_in_type) in frame.header.natms_per_type.iter().enumerate() {
-                let symbol = &frame.atom_data[force_idx_offset].symbol;
+                let symbol = frame.symbol(force_idx_offset);
                 writeln!(self.writer, "{}", symbol)?;
                 writeln!(self.writer, "Forces of Component {}", type_idx + 1)?;
 
@@ -355,7 +576,7 @@ impl<W: Write> ConFrameWriter<W> {
 
             let mut energy_idx_offset = 0;
             for (type_idx, &num_atoms_in_type) in frame.header.natms_per_type.iter().enumerate() {
-                let symbol = &frame.atom_data[energy_idx_offset].symbol;
+                let symbol = frame.symbol(energy_idx_offset);
                 writeln!(self.writer, "{}", symbol)?;
                 writeln!(self.writer, "Energies of Component {}", type_idx + 1)?;
 
@@ -374,11 +595,11 @@ impl<W: Write> ConFrameWriter<W> {
             }
         }
 
-        if frame.has_charges() {
+        if has_chg {
             writeln!(self.writer)?;
             let mut off = 0;
             for (type_idx, &num_atoms_in_type) in frame.header.natms_per_type.iter().enumerate() {
-                let symbol = &frame.atom_data[off].symbol;
+                let symbol = frame.symbol(off);
                 writeln!(self.writer, "{}", symbol)?;
                 writeln!(self.writer, "Charges of Component {}", type_idx + 1)?;
                 for i in 0..num_atoms_in_type {
@@ -400,7 +621,7 @@ impl<W: Write> ConFrameWriter<W> {
             writeln!(self.writer)?;
             let mut off = 0;
             for (type_idx, &num_atoms_in_type) in frame.header.natms_per_type.iter().enumerate() {
-                let symbol = &frame.atom_data[off].symbol;
+                let symbol = frame.symbol(off);
                 writeln!(self.writer, "{}", symbol)?;
                 writeln!(self.writer, "Spins of Component {}", type_idx + 1)?;
                 for i in 0..num_atoms_in_type {
@@ -422,7 +643,7 @@ impl<W: Write> ConFrameWriter<W> {
             writeln!(self.writer)?;
             let mut off = 0;
             for (type_idx, &num_atoms_in_type) in frame.header.natms_per_type.iter().enumerate() {
-                let symbol = &frame.atom_data[off].symbol;
+                let symbol = frame.symbol(off);
                 writeln!(self.writer, "{}", symbol)?;
                 writeln!(self.writer, "Magmoms of Component {}", type_idx + 1)?;
                 for i in 0..num_atoms_in_type {
@@ -440,9 +661,26 @@ impl<W: Write> ConFrameWriter<W> {
             }
         }
 
+        if self.trailing_lines {
+            for line in &frame.trailing_lines {
+                writeln!(self.writer, "{line}")?;
+            }
+        }
         Ok(())
     }
 
+    /// Writes any [`AtomicFrame`](crate::frame::AtomicFrame) as a CON
+    /// frame via [`ConFrame::from_atomic`]; conversion errors surface as
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn write_atomic<F: crate::frame::AtomicFrame + ?Sized>(
+        &mut self,
+        frame: &F,
+    ) -> io::Result<()> {
+        let frame = ConFrame::from_atomic(frame)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        self.write_frame(&frame)
+    }
+
     /// Writes all frames from an iterator to the output stream.
     ///
     /// This is the most convenient way to write a multi-frame file.
@@ -452,6 +690,17 @@ impl<W: Write> ConFrameWriter<W> {
         }
         Ok(())
     }
+
+    /// Flushes buffered output through to the wrapped writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flushes buffered output and returns the wrapped writer, e.g. so a
+    /// compression encoder can be finished explicitly and its error observed.
+    pub fn into_inner(self) -> io::Result<W> {
+        self.writer.into_inner().map_err(|e| e.into_error())
+    }
 }
 
 // Implementation block specifically for when the writer is a `File`.
@@ -459,6 +708,8 @@ impl ConFrameWriter<File> {
     /// Creates a new `ConFrameWriter` that writes to a file at the given path.
     ///
     /// This is a convenience function that creates the file and wraps it.
+    /// An existing file is truncated; see [`Self::append_to_path`] and
+    /// [`Self::create_new`] to keep it.
     pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         let file = File::create(path)?;
         Ok(Self::new(file))
@@ -469,6 +720,172 @@ impl ConFrameWriter<File> {
         let file = File::create(path)?;
         Ok(Self::with_precision(file, precision))
     }
+
+    /// Opens `path` with caller-chosen [`OpenOptions`], e.g. to combine
+    /// `append` with `create_new` or to set a Unix mode. Write access is
+    /// required; the options are used as given, so `truncate(true)`
+    /// still truncates.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::fs::OpenOptions;
+    /// use readcon_core::writer::ConFrameWriter;
+    /// let mut opts = OpenOptions::new();
+    /// opts.write(true).create(true).truncate(false);
+    /// let writer = ConFrameWriter::from_open_options("out.con", &opts).unwrap();
+    /// ```
+    pub fn from_open_options<P: AsRef<Path>>(path: P, options: &OpenOptions) -> io::Result<Self> {
+        Ok(Self::new(options.open(path)?))
+    }
+
+    /// Appends frames to `path`, creating it if missing. Existing frames
+    /// are never truncated; if the file does not end with a newline (an
+    /// interrupted earlier write), one is added first so the new frame
+    /// starts on its own line.
+    pub fn append_to_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        let len = file.seek(SeekFrom::End(0))?;
+        let mut writer = Self::new(file);
+        if len > 0 {
+            let mut last = [0u8; 1];
+            let file = writer.writer.get_mut();
+            file.seek(SeekFrom::Start(len - 1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                writer.writer.write_all(b"\n")?;
+            }
+        }
+        Ok(writer)
+    }
+
+    /// Creates `path` for writing, failing with
+    /// [`io::ErrorKind::AlreadyExists`] instead of truncating an existing
+    /// file (`O_CREAT | O_EXCL`).
+    pub fn create_new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(File::create_new(path)?))
+    }
+}
+
+/// A file written under a temporary name next to its target and renamed
+/// into place by [`AtomicFile::commit`], so readers only ever see the old
+/// file or the complete new one. Dropped without a commit (an error or a
+/// panic mid-write), the temporary is removed and the target is untouched.
+#[derive(Debug)]
+pub struct AtomicFile {
+    file: Option<File>,
+    tmp: PathBuf,
+    target: PathBuf,
+    committed: bool,
+}
+
+impl AtomicFile {
+    /// Creates the temporary `.<name>.<pid>.<n>.tmp` in the directory of
+    /// `path`; the rename is atomic because both share a filesystem.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let target = path.as_ref().to_path_buf();
+        let name = target.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} has no file name", target.display()),
+            )
+        })?;
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let tmp = target.with_file_name(format!(
+            ".{}.{}.{n}.tmp",
+            name.to_string_lossy(),
+            std::process::id()
+        ));
+        let file = File::create_new(&tmp)?;
+        Ok(Self {
+            file: Some(file),
+            tmp,
+            target,
+            committed: false,
+        })
+    }
+
+    /// Path the file is renamed to on commit.
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    /// Syncs the data to disk, renames the temporary over the target and
+    /// syncs the directory entry (on Unix), replacing any existing file.
+    pub fn commit(mut self) -> io::Result<()> {
+        if let Some(file) = self.file.take() {
+            file.sync_all()?;
+        }
+        std::fs::rename(&self.tmp, &self.target)?;
+        self.committed = true;
+        #[cfg(unix)]
+        {
+            let dir = match self.target.parent() {
+                Some(d) if !d.as_os_str().is_empty() => d,
+                _ => Path::new("."),
+            };
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.as_mut().map_or(Ok(0), |f| f.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), File::flush)
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.file.take();
+            let _ = std::fs::remove_file(&self.tmp);
+        }
+    }
+}
+
+// Crash-safe writer: frames go to a temporary file renamed on commit.
+impl ConFrameWriter<AtomicFile> {
+    /// Creates a writer whose output only replaces `path` when
+    /// [`Self::commit`] succeeds; dropping the writer instead discards
+    /// everything written. A crash mid-write never leaves a truncated
+    /// `.con` at `path`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use readcon_core::types::ConFrame;
+    /// # use readcon_core::writer::ConFrameWriter;
+    /// # let frames: Vec<ConFrame> = Vec::new();
+    /// let mut writer = ConFrameWriter::atomic_to_path("pos.con").unwrap();
+    /// writer.extend(frames.iter()).unwrap();
+    /// writer.commit().unwrap();
+    /// ```
+    pub fn atomic_to_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(AtomicFile::create(path)?))
+    }
+
+    /// [`Self::atomic_to_path`] with a custom precision.
+    pub fn atomic_to_path_with_precision<P: AsRef<Path>>(
+        path: P,
+        precision: usize,
+    ) -> io::Result<Self> {
+        Ok(Self::with_precision(AtomicFile::create(path)?, precision))
+    }
+
+    /// Flushes, syncs and renames the output into place; see
+    /// [`AtomicFile::commit`].
+    pub fn commit(self) -> io::Result<()> {
+        self.into_inner()?.commit()
+    }
 }
 
 // Gzip-compressed writer constructors.
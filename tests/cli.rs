@@ -0,0 +1,483 @@
+#![cfg(feature = "cli")]
+
+mod common;
+use readcon_core::iterators::ConFrameIterator;
+use std::fs;
+use std::path::Path;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn readcon(args: &[&std::ffi::OsStr]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_readcon"))
+        .args(args)
+        .output()
+        .expect("failed to run readcon")
+}
+
+/// Runs `readcon` with `input` piped to stdin.
+fn readcon_stdin(args: &[&std::ffi::OsStr], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_readcon"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run readcon");
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_cli_info_reports_frames() {
+    let input = test_case!("tiny_multi_cuh2.con");
+    let out = readcon(&["info".as_ref(), "--frames".as_ref(), input.as_os_str()]);
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("2 frame(s)"), "{stdout}");
+    assert!(stdout.contains("species: Cu 2, H 2"), "{stdout}");
+    assert!(stdout.contains("fixed atoms: 2"), "{stdout}");
+    assert!(stdout.contains("free atoms: 2"), "{stdout}");
+    assert!(stdout.contains("\n  1  4  2  "), "{stdout}");
+}
+
+#[test]
+fn test_cli_cat_concatenates_frames() {
+    let input = test_case!("tiny_cuh2.con");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("cat.con");
+    let out = readcon(&[
+        "cat".as_ref(),
+        input.as_os_str(),
+        input.as_os_str(),
+        "-o".as_ref(),
+        output.as_os_str(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let text = fs::read_to_string(&output).unwrap();
+    let frames: Vec<_> = ConFrameIterator::new(&text).map(|r| r.unwrap()).collect();
+    assert_eq!(frames.len(), 2);
+}
+
+#[test]
+fn test_cli_convert_and_verify() {
+    let input = test_case!("tiny_cuh2.convel");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("out.con");
+    let out = readcon(&["convert".as_ref(), input.as_os_str(), output.as_os_str()]);
+    assert!(out.status.success(), "{out:?}");
+    let out = readcon(&["verify".as_ref(), output.as_os_str()]);
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8(out.stdout).unwrap().contains("round-trip"));
+}
+
+#[test]
+fn test_cli_convert_formats_and_selection() {
+    let input = test_case!("tiny_multi_cuh2.con");
+    let dir = tempfile::tempdir().unwrap();
+    let xyz = dir.path().join("out.xyz");
+    let out = readcon(&["convert".as_ref(), input.as_os_str(), xyz.as_os_str()]);
+    assert!(out.status.success(), "{out:?}");
+    let text = fs::read_to_string(&xyz).unwrap();
+    assert_eq!(text.lines().next(), Some("4"));
+    assert!(text.contains("Lattice=\"15.3456 0 0 0 21.702 0 0 0 100\""));
+
+    let bcon = dir.path().join("last.bcon");
+    let out = readcon(&[
+        "convert".as_ref(),
+        input.as_os_str(),
+        bcon.as_os_str(),
+        "--start".as_ref(),
+        "1".as_ref(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let back = dir.path().join("back.con");
+    let out = readcon(&["convert".as_ref(), bcon.as_os_str(), back.as_os_str()]);
+    assert!(out.status.success(), "{out:?}");
+    let text = fs::read_to_string(&back).unwrap();
+    let original = fs::read_to_string(&input).unwrap();
+    let second = ConFrameIterator::new(&original).nth(1).unwrap().unwrap();
+    let frames: Vec<_> = ConFrameIterator::new(&text).map(|r| r.unwrap()).collect();
+    assert_eq!(frames, vec![second]);
+
+    let out = readcon(&[
+        "convert".as_ref(),
+        xyz.as_os_str(),
+        dir.path().join("x.dcd").as_os_str(),
+        "--stride".as_ref(),
+        "0".as_ref(),
+    ]);
+    assert!(!out.status.success());
+    let out = readcon(&[
+        "convert".as_ref(),
+        input.as_os_str(),
+        dir.path().join("x.unknown").as_os_str(),
+    ]);
+    assert!(!out.status.success());
+}
+
+#[test]
+fn test_cli_convert_skip_bad_frames() {
+    let one = fs::read_to_string(test_case!("tiny_cuh2.con")).unwrap();
+    let bad = one.replacen(
+        "Coordinates of Component 1\n",
+        "Coordinates of Component 1\nnot numbers\n",
+        1,
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("damaged.con");
+    fs::write(&input, format!("{one}{bad}{one}")).unwrap();
+    let output = dir.path().join("out.con");
+    let out = readcon(&["convert".as_ref(), input.as_os_str(), output.as_os_str()]);
+    assert!(!out.status.success(), "{out:?}");
+
+    let out = readcon(&[
+        "convert".as_ref(),
+        input.as_os_str(),
+        output.as_os_str(),
+        "--skip-bad-frames".as_ref(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert_eq!(
+        stderr.matches("skipped unreadable frame").count(),
+        1,
+        "{stderr}"
+    );
+    let text = fs::read_to_string(&output).unwrap();
+    assert_eq!(ConFrameIterator::new(&text).count(), 2);
+}
+
+#[test]
+fn test_cli_split_then_merge() {
+    let input = test_case!("tiny_multi_cuh2.con");
+    let dir = tempfile::tempdir().unwrap();
+    let template = dir.path().join("parts").join("f{:03}.con");
+    let out = readcon(&[
+        "split".as_ref(),
+        input.as_os_str(),
+        "-o".as_ref(),
+        template.as_os_str(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let parts = [
+        dir.path().join("parts/f000.con"),
+        dir.path().join("parts/f001.con"),
+    ];
+    assert!(parts.iter().all(|p| p.is_file()));
+
+    let merged = dir.path().join("merged.con");
+    let out = readcon(&[
+        "merge".as_ref(),
+        parts[0].as_os_str(),
+        parts[1].as_os_str(),
+        "-o".as_ref(),
+        merged.as_os_str(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let original = fs::read_to_string(&input).unwrap();
+    let text = fs::read_to_string(&merged).unwrap();
+    let a: Vec<_> = ConFrameIterator::new(&original).map(|r| r.unwrap()).collect();
+    let b: Vec<_> = ConFrameIterator::new(&text).map(|r| r.unwrap()).collect();
+    assert_eq!(a, b);
+
+    let mismatched = dir.path().join("bad.con");
+    let out = readcon(&[
+        "merge".as_ref(),
+        parts[0].as_os_str(),
+        test_case!("cuh2.con").as_os_str(),
+        "-o".as_ref(),
+        mismatched.as_os_str(),
+    ]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8(out.stderr).unwrap().contains("species layout"));
+    assert!(!mismatched.exists());
+}
+
+#[test]
+fn test_cli_diff_reports_moved_atoms() {
+    let input = test_case!("tiny_multi_cuh2.con");
+    let out = readcon(&["diff".as_ref(), input.as_os_str(), input.as_os_str()]);
+    assert_eq!(out.status.code(), Some(0), "{out:?}");
+
+    let original = fs::read_to_string(&input).unwrap();
+    let mut frames: Vec<_> = ConFrameIterator::new(&original)
+        .map(|r| r.unwrap())
+        .collect();
+    // Crossing the boundary plus 0.1 Å is a 0.1 Å move under minimum image.
+    let boxl = frames[1].header.boxl[0];
+    frames[1].atom_data[0].x += boxl + 0.1;
+    let dir = tempfile::tempdir().unwrap();
+    let moved = dir.path().join("moved.con");
+    let mut writer = readcon_core::writer::ConFrameWriter::from_path(&moved).unwrap();
+    writer.extend(frames.iter()).unwrap();
+    writer.flush().unwrap();
+
+    let out = readcon(&["diff".as_ref(), input.as_os_str(), moved.as_os_str()]);
+    assert_eq!(out.status.code(), Some(1), "{out:?}");
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert!(text.contains("frame 1:"), "{text}");
+    assert!(!text.contains("frame 0:"), "{text}");
+    assert!(text.contains("1 atom(s) moved; max 0.100000"), "{text}");
+
+    let out = readcon(&[
+        "diff".as_ref(),
+        input.as_os_str(),
+        moved.as_os_str(),
+        "--no-mic".as_ref(),
+    ]);
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert!(!text.contains("max 0.100000"), "{text}");
+
+    let out = readcon(&[
+        "diff".as_ref(),
+        input.as_os_str(),
+        test_case!("tiny_cuh2.con").as_os_str(),
+        "-q".as_ref(),
+    ]);
+    assert_eq!(out.status.code(), Some(1), "{out:?}");
+    assert!(out.stdout.is_empty());
+
+    let missing = dir.path().join("missing.con");
+    let out = readcon(&["diff".as_ref(), input.as_os_str(), missing.as_os_str()]);
+    assert_eq!(out.status.code(), Some(2), "{out:?}");
+}
+
+#[test]
+fn test_cli_head_tail_on_pipes() {
+    let input = test_case!("tiny_multi_cuh2.con");
+    let original = fs::read_to_string(&input).unwrap();
+    let frames: Vec<_> = ConFrameIterator::new(&original)
+        .map(|r| r.unwrap())
+        .collect();
+    let parse = |out: Output| -> Vec<_> {
+        assert!(out.status.success(), "{out:?}");
+        let text = String::from_utf8(out.stdout).unwrap();
+        ConFrameIterator::new(&text).map(|r| r.unwrap()).collect()
+    };
+
+    // Compressed data on stdin is detected from its magic bytes.
+    let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    gz.write_all(original.as_bytes()).unwrap();
+    let gz = gz.finish().unwrap();
+    let got = parse(readcon_stdin(&["tail".as_ref(), "-n".as_ref(), "1".as_ref()], &gz));
+    assert_eq!(got, vec![frames[1].clone()]);
+
+    let got = parse(readcon_stdin(&["head".as_ref()], original.as_bytes()));
+    assert_eq!(got, vec![frames[0].clone()]);
+
+    // `-` mixes stdin with files; --last spans input boundaries.
+    let got = parse(readcon_stdin(
+        &[
+            "cat".as_ref(),
+            "--last".as_ref(),
+            "3".as_ref(),
+            input.as_os_str(),
+            "-".as_ref(),
+        ],
+        original.as_bytes(),
+    ));
+    assert_eq!(got, vec![frames[1].clone(), frames[0].clone(), frames[1].clone()]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let out_path = dir.path().join("first.con");
+    let out = readcon(&[
+        "cat".as_ref(),
+        "--first".as_ref(),
+        "1".as_ref(),
+        input.as_os_str(),
+        "-o".as_ref(),
+        out_path.as_os_str(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let text = fs::read_to_string(&out_path).unwrap();
+    assert_eq!(ConFrameIterator::new(&text).count(), 1);
+
+    let out = readcon_stdin(&["validate".as_ref(), "-".as_ref()], original.as_bytes());
+    assert_eq!(out.status.code(), Some(0), "{out:?}");
+}
+
+#[test]
+fn test_cli_sort_is_deterministic() {
+    const HEADER: &str = "comment\n{\"con_spec_version\":2}\n10 10 10\n90 90 90\n0 0\n0 0 0\n";
+    // The same structure twice: species split differently, atoms in
+    // another order, different ids.
+    let split = format!(
+        "{HEADER}3\n1 1 1\n1.008 63.546 63.546\nH\nCoordinates of Component 1\n5 5 5 0 7\n\
+         Cu\nCoordinates of Component 2\n2 0 0 0 4\nCu\nCoordinates of Component 3\n0 0 0 1 9\n"
+    );
+    let merged = format!(
+        "{HEADER}2\n2 1\n63.546 1.008\nCu\nCoordinates of Component 1\n0 0 0 1 0\n2 0 0 0 1\n\
+         H\nCoordinates of Component 2\n5 5 5 0 2\n"
+    );
+    let sort = |text: &str| {
+        let out = readcon_stdin(
+            &[
+                "sort".as_ref(),
+                "-".as_ref(),
+                "--by".as_ref(),
+                "position".as_ref(),
+                "--species".as_ref(),
+                "z".as_ref(),
+            ],
+            text.as_bytes(),
+        );
+        assert!(out.status.success(), "{out:?}");
+        String::from_utf8(out.stdout).unwrap()
+    };
+    let sorted = sort(&split);
+    assert_eq!(sorted, sort(&merged));
+    let frame = ConFrameIterator::new(&sorted).next().unwrap().unwrap();
+    assert_eq!(frame.header.natms_per_type, vec![1, 2]);
+    assert_eq!(frame.symbols(), vec!["H", "Cu", "Cu"]);
+    assert_eq!(*frame.atom_ids(), [0, 1, 2]);
+    assert_eq!(frame.atom_data[1].x, 0.0);
+}
+
+#[test]
+fn test_cli_watch_follows_a_growing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("run.con");
+    fs::write(&path, "").unwrap();
+    let child = Command::new(env!("CARGO_BIN_EXE_readcon"))
+        .args(["watch".as_ref(), path.as_os_str()])
+        .args(["--to", "con", "--interval", "20", "--timeout", "1"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run readcon");
+
+    // Append the trajectory in pieces that split lines and frames.
+    let text = fs::read_to_string(test_case!("tiny_multi_cuh2.con")).unwrap();
+    let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    for piece in text.as_bytes().chunks(text.len() / 3 + 1) {
+        file.write_all(piece).unwrap();
+        file.flush().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let got = String::from_utf8(out.stdout).unwrap();
+    let parse = |t: &str| -> Vec<_> { ConFrameIterator::new(t).map(|r| r.unwrap()).collect() };
+    assert_eq!(parse(&got), parse(&text));
+}
+
+#[test]
+fn test_cli_extract_selects_frames() {
+    let input = test_case!("tiny_multi_cuh2.con");
+    let original = fs::read_to_string(&input).unwrap();
+    let frames: Vec<_> = ConFrameIterator::new(&original)
+        .map(|r| r.unwrap())
+        .collect();
+
+    let out = readcon(&[
+        "extract".as_ref(),
+        input.as_os_str(),
+        "--frames".as_ref(),
+        "last".as_ref(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let text = String::from_utf8(out.stdout).unwrap();
+    let got: Vec<_> = ConFrameIterator::new(&text).map(|r| r.unwrap()).collect();
+    assert_eq!(got, vec![frames[1].clone()]);
+
+    let out = readcon(&[
+        "extract".as_ref(),
+        input.as_os_str(),
+        "--frames".as_ref(),
+        "-1,0".as_ref(),
+    ]);
+    let text = String::from_utf8(out.stdout).unwrap();
+    let got: Vec<_> = ConFrameIterator::new(&text).map(|r| r.unwrap()).collect();
+    assert_eq!(got, frames);
+
+    let out = readcon(&[
+        "extract".as_ref(),
+        input.as_os_str(),
+        "--frames".as_ref(),
+        "2".as_ref(),
+    ]);
+    assert!(!out.status.success());
+}
+
+#[test]
+fn test_cli_validate_exit_status_and_json() {
+    let clean = test_case!("tiny_multi_cuh2.con");
+    let out = readcon(&["validate".as_ref(), clean.as_os_str()]);
+    assert_eq!(out.status.code(), Some(0), "{out:?}");
+
+    let mislabeled = test_case!("sulfolene.con");
+    let out = readcon(&[
+        "validate".as_ref(),
+        "--json".as_ref(),
+        clean.as_os_str(),
+        mislabeled.as_os_str(),
+    ]);
+    assert_eq!(out.status.code(), Some(2), "{out:?}");
+    let reports: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(reports[0]["errors"], 0);
+    assert_eq!(reports[1]["errors"], 1);
+    assert_eq!(reports[1]["issues"][0]["frame"], 0);
+    assert_eq!(reports[1]["issues"][0]["line"], 1);
+    assert!(
+        reports[1]["issues"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("coordinate label")
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let legacy = dir.path().join("legacy.con");
+    let text = fs::read_to_string(&clean).unwrap();
+    let v1: String = text
+        .lines()
+        .take_while(|l| !l.is_empty())
+        .map(|l| {
+            if l.starts_with('{') {
+                "legacy\n".to_string()
+            } else {
+                format!("{l}\n")
+            }
+        })
+        .collect();
+    fs::write(&legacy, v1).unwrap();
+    let out = readcon(&["validate".as_ref(), legacy.as_os_str()]);
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(out.status.code(), Some(1), "{stdout}");
+    assert!(stdout.contains(":2: warning: frame 0: legacy frame"), "{stdout}");
+}
+
+#[test]
+fn test_cli_errors_exit_nonzero() {
+    let out = readcon(&["info".as_ref(), "does/not/exist.con".as_ref()]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8(out.stderr).unwrap().starts_with("Error:"));
+
+    let out = readcon(&["no-such-command".as_ref()]);
+    assert!(!out.status.success());
+}
+
+#[test]
+fn test_cli_convert_provenance() {
+    let input = test_case!("tiny_multi_cuh2.con");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("out.jsonl");
+    let out = readcon(&[
+        "convert".as_ref(),
+        input.as_os_str(),
+        output.as_os_str(),
+        "--start".as_ref(),
+        "1".as_ref(),
+        "--provenance".as_ref(),
+    ]);
+    assert!(out.status.success(), "{out:?}");
+    let text = fs::read_to_string(&output).unwrap();
+    let frame = readcon_core::formats::jsonl::JsonlFrameIterator::new(&text)
+        .next()
+        .unwrap()
+        .unwrap();
+    let p = frame.comment().expect("provenance comment");
+    assert!(p.tool.starts_with("readcon "), "{p:?}");
+    assert_eq!(p.source.as_deref(), Some(input.to_str().unwrap()));
+    assert_eq!(p.frame_index, Some(1));
+    assert!(p.timestamp.is_some_and(|t| t.ends_with('Z')));
+}
@@ -66,3 +66,40 @@ fn test_convel_multi_writer_roundtrip() {
         assert_eq!(orig, round);
     }
 }
+
+#[test]
+fn test_interleaved_velocity_writer_roundtrip() {
+    let fdat = fs::read_to_string(test_case!("tiny_cuh2_dynamics.con"))
+        .expect("Can't find dynamics test file.");
+    let frames: Vec<_> = ConFrameIterator::new(&fdat).map(|r| r.unwrap()).collect();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    ConFrameWriter::new(&mut buffer)
+        .interleaved_velocities(true)
+        .extend(frames.iter())
+        .expect("Failed to write dynamics layout to buffer.");
+    let written = String::from_utf8(buffer).expect("Buffer is not valid UTF-8.");
+    assert!(!written.lines().any(|l| l.trim().is_empty()));
+    assert!(!written.contains("sections"));
+    let coords = written.find("Coordinates of Component 2").unwrap();
+    assert!(written[..coords].contains("Velocities of Component 1"));
+
+    let roundtrip: Vec<_> = ConFrameIterator::new(&written)
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(frames, roundtrip);
+
+    // Other sections stay declared and blank-separated.
+    let fdat = fs::read_to_string(test_case!("tiny_cuh2_vel_forces.con"))
+        .expect("Can't find velocity/force test file.");
+    let frame = ConFrameIterator::new(&fdat).next().unwrap().unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    ConFrameWriter::new(&mut buffer)
+        .interleaved_velocities(true)
+        .write_frame(&frame)
+        .unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.contains(r#""sections":["forces"]"#));
+    let roundtrip = ConFrameIterator::new(&written).next().unwrap().unwrap();
+    assert_eq!(roundtrip.atom_data, frame.atom_data);
+}
@@ -234,3 +234,175 @@ fn test_nonsequential_atom_index_roundtrip() {
     assert_eq!(rt.atom_data[4].atom_id, 5);
     assert_eq!(rt.atom_data[5].atom_id, 3);
 }
+
+#[test]
+fn test_append_and_create_new_keep_existing_frames() {
+    let text = fs::read_to_string(test_case!("tiny_cuh2.con")).unwrap();
+    let frame = ConFrameIterator::new(&text).next().unwrap().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("traj.con");
+
+    ConFrameWriter::create_new(&path)
+        .unwrap()
+        .write_frame(&frame)
+        .unwrap();
+    let err = ConFrameWriter::create_new(&path).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+
+    // Simulate an earlier write that lost its final newline.
+    let mut bytes = fs::read(&path).unwrap();
+    assert_eq!(bytes.pop(), Some(b'\n'));
+    fs::write(&path, &bytes).unwrap();
+
+    for _ in 0..2 {
+        let mut writer = ConFrameWriter::append_to_path(&path).unwrap();
+        writer.write_frame(&frame).unwrap();
+    }
+    let back = fs::read_to_string(&path).unwrap();
+    let frames: Vec<_> = ConFrameIterator::new(&back).map(|r| r.unwrap()).collect();
+    assert_eq!(frames.len(), 3);
+    assert!(frames.iter().all(|f| f.atom_data == frames[0].atom_data));
+    assert_eq!(frames[2].atom_data.len(), frame.atom_data.len());
+
+    let mut opts = fs::OpenOptions::new();
+    opts.write(true).create_new(true);
+    assert!(ConFrameWriter::from_open_options(&path, &opts).is_err());
+}
+
+#[test]
+fn test_atomic_writer_replaces_only_on_commit() {
+    let text = fs::read_to_string(test_case!("tiny_cuh2.con")).unwrap();
+    let frame = ConFrameIterator::new(&text).next().unwrap().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pos.con");
+    fs::write(&path, "previous").unwrap();
+
+    // Dropped mid-write: the old file survives and no temporary is left.
+    {
+        let mut writer = ConFrameWriter::atomic_to_path(&path).unwrap();
+        writer.write_frame(&frame).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+    assert_eq!(fs::read_to_string(&path).unwrap(), "previous");
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    let mut writer = ConFrameWriter::atomic_to_path(&path).unwrap();
+    writer.write_frame(&frame).unwrap();
+    writer.commit().unwrap();
+    let back = fs::read_to_string(&path).unwrap();
+    assert_eq!(ConFrameIterator::new(&back).count(), 1);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_writer_rejects_frames_it_cannot_represent() {
+    let text = "zero\n{\"con_spec_version\":2}\n10 10 10\n90 90 90\n\n\n2\n1 0\n63.546 1.008\n\
+                Cu\nCoordinates of Component 1\n0 0 0 0 0\nH\nCoordinates of Component 2\n";
+    let frame = ConFrameIterator::new(text).next().unwrap().unwrap();
+    assert_eq!(frame.header.natms_per_type, vec![1, 0]);
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let err = ConFrameWriter::new(&mut buffer)
+        .write_frame(&frame)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    let mut frame =
+        ConFrameIterator::new(&fs::read_to_string(test_case!("tiny_cuh2.con")).unwrap())
+            .next()
+            .unwrap()
+            .unwrap();
+    frame.atom_data.pop();
+    let err = ConFrameWriter::new(&mut buffer)
+        .write_frame(&frame)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(buffer.is_empty(), "nothing is written for a rejected frame");
+}
+
+#[test]
+fn test_component_labels_round_trip_or_regenerate() {
+    use readcon_core::writer::ComponentLabels;
+    let text = "custom\n{\"con_spec_version\":2}\n10 10 10\n90 90 90\n\n\n2\n1 1\n63.546 1.008\n\
+                Cu\nslab atoms\n0 0 0 1 0\nH\nCoordinates of Component 2\n1 1 1 0 1\n";
+    let frame = ConFrameIterator::new(text).next().unwrap().unwrap();
+    assert_eq!(
+        frame.header.component_labels,
+        vec!["slab atoms", "Coordinates of Component 2"]
+    );
+
+    let write = |labels: ComponentLabels| {
+        let mut buffer: Vec<u8> = Vec::new();
+        ConFrameWriter::new(&mut buffer)
+            .component_labels(labels)
+            .write_frame(&frame)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+    let preserved = write(ComponentLabels::Preserve);
+    assert!(preserved.contains("Cu\nslab atoms\n"));
+    let back = ConFrameIterator::new(&preserved).next().unwrap().unwrap();
+    assert_eq!(back.header.component_labels, frame.header.component_labels);
+
+    let standard = write(ComponentLabels::Standard);
+    assert!(standard.contains("Cu\nCoordinates of Component 1\n"));
+    let back = ConFrameIterator::new(&standard).next().unwrap().unwrap();
+    assert!(back.header.component_labels.is_empty());
+
+    let templated = write(ComponentLabels::Template("{symbol} block {n}".into()));
+    assert!(templated.contains("Cu\nCu block 1\n") && templated.contains("H\nH block 2\n"));
+
+    let h_only = frame.select(|a| frame.symbol_of(a) == "H");
+    assert!(h_only.header.component_labels.is_empty());
+    let cu_only = frame.select(|a| frame.symbol_of(a) == "Cu");
+    assert_eq!(cu_only.header.component_labels, vec!["slab atoms"]);
+}
+
+#[test]
+fn test_annotate_user_line_writes_energy_and_time() {
+    let mut builder = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    builder.prebox_header("Generated by eOn E=-1.5");
+    builder.add_atom("Cu", 0.0, 0.0, 0.0, [false; 3], 0, 63.546);
+    let mut frame = builder.build();
+    frame.header.set_energy(-2.0);
+    frame.header.set_time(0.25);
+
+    let mut buffer: Vec<u8> = Vec::new();
+    ConFrameWriter::new(&mut buffer)
+        .annotate_user_line(true)
+        .write_frame(&frame)
+        .unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(
+        text.starts_with("Generated by eOn E=-1.5 time=0.25\n"),
+        "{text}"
+    );
+    let back = ConFrameIterator::new(&text).next().unwrap().unwrap();
+    let m = back.header.frame_metadata();
+    assert_eq!((m.energy, m.time), (Some(-2.0), Some(0.25)));
+}
+
+#[test]
+fn test_isotope_labels_survive_write_and_read() {
+    let mut builder = ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    builder.add_atom("Cu65", 0.0, 0.0, 0.0, [false; 3], 0, 0.0);
+    builder.add_atom("Cu", 1.8, 0.0, 0.0, [false; 3], 1, 0.0);
+    builder.add_atom("H2", 0.0, 0.0, 1.5, [false; 3], 2, 0.0);
+    builder.infer_masses().unwrap();
+    let frame = builder.build();
+    assert_eq!(
+        frame.header.masses_per_type,
+        vec![64.92778970, 63.546, 2.01410178]
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    ConFrameWriter::new(&mut buffer)
+        .write_frame(&frame)
+        .unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    let back = ConFrameIterator::new(&text).next().unwrap().unwrap();
+    let symbols: Vec<&str> = back.symbols();
+    assert_eq!(symbols, ["Cu65", "Cu", "H2"]);
+    assert!(back.mass_mismatches(1e-4).is_empty());
+}
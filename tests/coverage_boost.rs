@@ -8,6 +8,7 @@ use std::path::Path;
 
 use readcon_core::compression::{
     detect_compression, detect_compression_from_extension, gzip_writer, read_file_contents,
+    read_reader_contents,
     FileContents, Compression,
 };
 use readcon_core::chemfiles_import::ChemfilesImportError;
@@ -112,6 +113,19 @@ fn compression_detect_extension_and_mmap_and_writers() {
         let mut w = gzip_writer(&gz_path).unwrap();
         w.write_all(b"hello").unwrap();
     }
+    // A second gzip member appended to the file is read back too.
+    {
+        let f = std::fs::OpenOptions::new().append(true).open(&gz_path).unwrap();
+        let mut w = flate2::write::GzEncoder::new(f, flate2::Compression::default());
+        w.write_all(b" world").unwrap();
+        w.finish().unwrap();
+    }
+    let back = read_file_contents(&gz_path).unwrap();
+    assert_eq!(back.as_str().unwrap(), "hello world");
+    let piped = read_reader_contents(std::fs::File::open(&gz_path).unwrap()).unwrap();
+    assert_eq!(piped.as_str().unwrap(), "hello world");
+    let plain = read_reader_contents(&b"plain text"[..]).unwrap();
+    assert_eq!(plain.as_str().unwrap(), "plain text");
     #[cfg(feature = "zstd")]
     {
         use readcon_core::compression::zstd_writer;
@@ -0,0 +1,86 @@
+//! Heap use of [`ConFrameF32`] against [`ConFrame`], measured with a
+//! counting global allocator. Kept in a binary of its own with a single
+//! test, so no other test allocates while the counters are read.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::types::ConFrameBuilder;
+use readcon_core::writer::ConFrameWriter;
+
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(live, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Runs `f`, returning its value, the bytes it left allocated and the
+/// most it had allocated at once.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, usize) {
+    let before = LIVE.load(Ordering::SeqCst);
+    PEAK.store(before, Ordering::SeqCst);
+    let value = f();
+    let retained = LIVE.load(Ordering::SeqCst) - before;
+    let peak = PEAK.load(Ordering::SeqCst) - before;
+    (value, retained, peak)
+}
+
+#[test]
+fn f32_frames_hold_at_most_half_the_memory_of_f64_frames() {
+    const NATOMS: usize = 20_000;
+    let mut b = ConFrameBuilder::new([100.0; 3], [90.0; 3]);
+    for i in 0..NATOMS {
+        let v = i as f64 * 0.001;
+        b.add_atom("Cu", v, v + 1.0, v + 2.0, [false; 3], i as u64, 63.546);
+    }
+    let mut w = ConFrameWriter::new(Vec::new());
+    w.write_frame(&b.build()).unwrap();
+    let text = String::from_utf8(w.into_inner().unwrap()).unwrap();
+
+    let (wide, wide_bytes, _) = measure(|| ConFrameIterator::new(&text).next().unwrap().unwrap());
+    let (narrow, narrow_bytes, narrow_peak) =
+        measure(|| ConFrameIterator::new(&text).next_f32().unwrap().unwrap());
+    assert_eq!(narrow.natoms(), NATOMS);
+    assert_eq!(narrow.to_con_frame().unwrap().atom_data.len(), wide.atom_data.len());
+
+    // 12 bytes of position, 8 of id and 1 of fixed flags per atom, plus
+    // the header; nothing per atom is kept in f64.
+    let per_atom = 12 + 8 + 1;
+    assert_eq!(narrow.atom_bytes(), NATOMS * per_atom);
+    assert!(narrow_bytes < NATOMS * per_atom + 4096, "{narrow_bytes}");
+    // Positions alone halve against the f64 frame's SoA positions, and
+    // the whole frame takes far less than half the f64 frame.
+    assert_eq!(
+        narrow.positions.capacity() * size_of::<[f32; 3]>() * 2,
+        wide.positions.nrows() * 3 * size_of::<f64>()
+    );
+    assert!(
+        2 * narrow_bytes <= wide_bytes,
+        "{narrow_bytes} vs {wide_bytes}"
+    );
+    // Parsing went straight into the f32 arrays: no f64 buffer was
+    // allocated on the way.
+    assert!(
+        narrow_peak < narrow_bytes + 4096,
+        "{narrow_peak} vs {narrow_bytes}"
+    );
+}
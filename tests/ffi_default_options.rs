@@ -0,0 +1,82 @@
+//! `rkr_set_default_options` changes process-wide state that every FFI
+//! reader consults, so it is exercised in this binary of its own: tests in
+//! the same process run in parallel and would read whatever defaults this
+//! one had set at that moment.
+
+use std::ffi::{CStr, CString};
+
+use readcon_core::ffi::{
+    RKROptions, RKRStatus, con_frame_iterator_next, free_con_frame_iterator, free_rkr_frame,
+    free_rkr_frame_array, read_con_string_iterator, rkr_get_default_options,
+    rkr_last_error_message, rkr_last_error_status, rkr_read_all_frames, rkr_read_first_frame,
+    rkr_set_default_options,
+};
+
+#[test]
+fn default_options_lenient_read_and_reset() {
+    let one = std::fs::read_to_string("resources/test/tiny_cuh2.con").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let p = dir.path().join("commented.con");
+    std::fs::write(&p, format!("# exported by md\n\n{one}\n{one}")).unwrap();
+    let path = CString::new(p.to_str().unwrap()).unwrap();
+
+    let opts = RKROptions {
+        precision: 0,
+        lenient: 1,
+        num_threads: 2,
+    };
+    assert_eq!(
+        unsafe { rkr_set_default_options(&opts) },
+        RKRStatus::RKR_STATUS_SUCCESS
+    );
+    let mut got = RKROptions::default();
+    assert_eq!(
+        unsafe { rkr_get_default_options(&mut got) },
+        RKRStatus::RKR_STATUS_SUCCESS
+    );
+    assert_eq!(got, opts);
+    let fr = unsafe { rkr_read_first_frame(path.as_ptr()) };
+    assert!(!fr.is_null());
+    unsafe { free_rkr_frame(fr) };
+    let mut n: usize = 0;
+    let arr = unsafe { rkr_read_all_frames(path.as_ptr(), &mut n) };
+    assert!(!arr.is_null());
+    assert_eq!(n, 2);
+    unsafe { free_rkr_frame_array(arr, n) };
+
+    // Lines skipped before a frame count towards the line an error names:
+    // the bad second frame starts on line 21, after 17 lines of the first
+    // frame, a blank, a comment and another blank.
+    let bad = one.replace("0.63940000000000108", "0.6394x");
+    let text = CString::new(format!("{one}\n# note\n\n{bad}")).unwrap();
+    let it = unsafe { read_con_string_iterator(text.as_ptr()) };
+    assert!(!it.is_null());
+    let fr = unsafe { con_frame_iterator_next(it) };
+    assert!(!fr.is_null());
+    unsafe { free_rkr_frame(fr) };
+    assert!(unsafe { con_frame_iterator_next(it) }.is_null());
+    assert_eq!(rkr_last_error_status(), RKRStatus::RKR_STATUS_PARSE_ERROR);
+    let message = unsafe { CStr::from_ptr(rkr_last_error_message()) }
+        .to_str()
+        .unwrap();
+    assert!(
+        message.starts_with("frame starting at line 21: "),
+        "{message}"
+    );
+    unsafe { free_con_frame_iterator(it) };
+
+    assert_eq!(
+        unsafe { rkr_set_default_options(std::ptr::null()) },
+        RKRStatus::RKR_STATUS_SUCCESS
+    );
+    assert_eq!(
+        unsafe { rkr_get_default_options(&mut got) },
+        RKRStatus::RKR_STATUS_SUCCESS
+    );
+    assert_eq!(got, RKROptions::default());
+    assert!(unsafe { rkr_read_first_frame(path.as_ptr()) }.is_null());
+    assert_eq!(
+        unsafe { rkr_get_default_options(std::ptr::null_mut()) },
+        RKRStatus::RKR_STATUS_NULL_POINTER
+    );
+}
@@ -0,0 +1,213 @@
+//! Soak tests for the unsafe C ABI: NULL handles, truncated inputs, buffer
+//! lengths at the edges, and repeated create/free cycles.
+//!
+//! Everything here stays inside memory the test owns, so the suite is meant
+//! to run under a sanitizer as well as plain `cargo test`:
+//!
+//! ```text
+//! RUSTFLAGS=-Zsanitizer=address cargo +nightly test --test ffi_soak --target x86_64-unknown-linux-gnu
+//! cargo +nightly miri test --test ffi_soak
+//! ```
+//!
+//! The `fuzz/` targets drive the same entry points with arbitrary bytes.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr;
+
+use readcon_core::ffi::*;
+
+const MULTI: &str = include_str!("../resources/test/tiny_multi_cuh2.con");
+
+/// Drains an iterator over `bytes`, exercising the per-frame accessors on
+/// every frame, and returns `(frames, last status)`.
+fn drain(bytes: &[u8]) -> (usize, RKRStatus) {
+    let it = unsafe { read_con_buffer_iterator(bytes.as_ptr(), bytes.len()) };
+    if it.is_null() {
+        return (0, rkr_last_error_status());
+    }
+    let mut frames = 0;
+    let status = loop {
+        let mut frame = ptr::null_mut();
+        let status = unsafe { con_frame_iterator_try_next(it, &mut frame) };
+        if status != RKRStatus::RKR_STATUS_SUCCESS || frame.is_null() {
+            break status;
+        }
+        frames += 1;
+        touch(frame);
+        unsafe { free_rkr_frame(frame) };
+    };
+    unsafe { free_con_frame_iterator(it) };
+    (frames, status)
+}
+
+/// Calls the read-only accessors that hand memory back to the caller.
+fn touch(frame: *mut RKRConFrame) {
+    let frame = frame as *const RKRConFrame;
+    unsafe {
+        let n = rkr_frame_atom_count(frame);
+        let mut positions = vec![0.0_f64; 3 * n];
+        assert_eq!(
+            rkr_frame_copy_positions(frame, positions.as_mut_ptr(), positions.len()),
+            RKRStatus::RKR_STATUS_SUCCESS
+        );
+        let c_frame = rkr_frame_to_c_frame(frame);
+        assert!(!c_frame.is_null());
+        assert_eq!((*c_frame).num_atoms, n);
+        free_c_frame(c_frame);
+        rkr_free_string(rkr_frame_metadata_json(frame));
+        rkr_free_string(rkr_frame_symbol(frame, 0));
+        rkr_free_string(rkr_frame_symbol(frame, n));
+        for line in 0..3 {
+            rkr_free_string(rkr_frame_get_header_line_cpp(frame, true, line));
+            rkr_free_string(rkr_frame_get_header_line_cpp(frame, false, line));
+        }
+    }
+}
+
+fn first_frame() -> *mut RKRConFrame {
+    let it = unsafe { read_con_buffer_iterator(MULTI.as_ptr(), MULTI.len()) };
+    let frame = unsafe { con_frame_iterator_next(it) };
+    unsafe { free_con_frame_iterator(it) };
+    assert!(!frame.is_null());
+    frame
+}
+
+#[test]
+fn null_handles_are_rejected() {
+    let null_frame: *const RKRConFrame = ptr::null();
+    let mut buf = [0 as c_char; 8];
+    unsafe {
+        assert_eq!(rkr_frame_atom_count(null_frame), 0);
+        assert!(rkr_frame_to_c_frame(null_frame).is_null());
+        assert!(rkr_frame_metadata_json(null_frame).is_null());
+        assert!(rkr_frame_symbol(null_frame, 0).is_null());
+        assert!(rkr_frame_positions_ptr(null_frame).is_null());
+        assert!(rkr_frame_get_header_line_cpp(null_frame, true, 0).is_null());
+        assert_eq!(
+            rkr_frame_get_header_line(null_frame, true, 0, buf.as_mut_ptr(), buf.len()),
+            RKRStatus::RKR_STATUS_NULL_POINTER
+        );
+        assert_eq!(
+            rkr_frame_copy_positions(null_frame, ptr::null_mut(), usize::MAX),
+            RKRStatus::RKR_STATUS_NULL_POINTER
+        );
+        assert_eq!(
+            rkr_frame_atom(null_frame, 0, ptr::null_mut()),
+            RKRStatus::RKR_STATUS_NULL_POINTER
+        );
+
+        assert!(read_con_file_iterator(ptr::null()).is_null());
+        assert_eq!(rkr_last_error_status(), RKRStatus::RKR_STATUS_NULL_POINTER);
+        assert!(read_con_string_iterator(ptr::null()).is_null());
+        assert!(read_con_buffer_iterator(ptr::null(), 1).is_null());
+        assert!(con_frame_iterator_next(ptr::null_mut()).is_null());
+        assert_eq!(con_frame_iterator_skip(ptr::null_mut(), usize::MAX), 0);
+        assert_eq!(con_frame_iterator_count_remaining(ptr::null()), 0);
+        assert!(create_writer_from_path_c(ptr::null()).is_null());
+        assert_eq!(
+            rkr_writer_extend(ptr::null_mut(), ptr::null(), 0),
+            RKRStatus::RKR_STATUS_NULL_POINTER
+        );
+    }
+}
+
+#[test]
+fn free_functions_accept_null() {
+    unsafe {
+        free_rkr_frame(ptr::null_mut());
+        free_con_frame_iterator(ptr::null_mut());
+        free_c_frame(ptr::null_mut());
+        rkr_free_string(ptr::null_mut());
+        free_rkr_writer(ptr::null_mut());
+        free_rkr_frame_builder(ptr::null_mut());
+        free_rkr_frame_array(ptr::null_mut(), 0);
+        free_rkr_frame_ptr_array(ptr::null_mut(), 0);
+    }
+}
+
+#[test]
+fn header_line_buffer_lengths_at_the_edges() {
+    let frame = first_frame();
+    let expected = unsafe { rkr_frame_get_header_line_cpp(frame, true, 0) };
+    let expected_line = unsafe { CStr::from_ptr(expected) }.to_owned();
+    unsafe { rkr_free_string(expected) };
+
+    let mut buf = vec![1 as c_char; expected_line.as_bytes().len() + 1];
+    unsafe {
+        assert_eq!(
+            rkr_frame_get_header_line(frame, true, 0, buf.as_mut_ptr(), 0),
+            RKRStatus::RKR_STATUS_BUFFER_TOO_SMALL
+        );
+        assert_eq!(buf[0], 1, "a zero-length buffer must not be written");
+
+        assert_eq!(
+            rkr_frame_get_header_line(frame, true, 0, buf.as_mut_ptr(), 1),
+            RKRStatus::RKR_STATUS_SUCCESS
+        );
+        assert_eq!(buf[0], 0);
+
+        // Only the line and its NUL are written, however large the claim.
+        assert_eq!(
+            rkr_frame_get_header_line(frame, true, 0, buf.as_mut_ptr(), usize::MAX),
+            RKRStatus::RKR_STATUS_SUCCESS
+        );
+        assert_eq!(CStr::from_ptr(buf.as_ptr()), expected_line.as_c_str());
+
+        assert_eq!(
+            rkr_frame_get_header_line(frame, false, usize::MAX, buf.as_mut_ptr(), buf.len()),
+            RKRStatus::RKR_STATUS_INDEX_OUT_OF_BOUNDS
+        );
+
+        let mut too_small = [0.0_f64; 2];
+        assert_eq!(
+            rkr_frame_copy_positions(frame, too_small.as_mut_ptr(), too_small.len()),
+            RKRStatus::RKR_STATUS_BUFFER_TOO_SMALL
+        );
+        free_rkr_frame(frame);
+    }
+}
+
+#[test]
+fn every_truncation_of_a_multi_frame_file_is_handled() {
+    let (full, status) = drain(MULTI.as_bytes());
+    assert_eq!(status, RKRStatus::RKR_STATUS_SUCCESS);
+    assert!(full >= 2);
+
+    for len in 0..MULTI.len() {
+        let (frames, status) = drain(&MULTI.as_bytes()[..len]);
+        assert!(frames <= full, "prefix {len} yielded {frames} frames");
+        if status != RKRStatus::RKR_STATUS_SUCCESS {
+            assert!(!rkr_last_error_message().is_null());
+        }
+    }
+}
+
+#[test]
+fn invalid_bytes_are_reported_not_parsed() {
+    let mut bytes = MULTI.as_bytes().to_vec();
+    bytes[10] = 0xff;
+    let (frames, status) = drain(&bytes);
+    assert_eq!(frames, 0);
+    assert_eq!(status, RKRStatus::RKR_STATUS_INVALID_UTF8);
+}
+
+#[test]
+fn repeated_create_free_cycles_stay_balanced() {
+    for _ in 0..200 {
+        let it = unsafe { read_con_buffer_iterator(MULTI.as_ptr(), MULTI.len()) };
+        assert!(!it.is_null());
+        let total = unsafe { con_frame_iterator_count_remaining(it) };
+        assert_eq!(unsafe { con_frame_iterator_skip(it, total) }, total);
+        assert_eq!(
+            unsafe { con_frame_iterator_reset(it) },
+            RKRStatus::RKR_STATUS_SUCCESS
+        );
+        let frame = unsafe { con_frame_iterator_next(it) };
+        touch(frame);
+        unsafe {
+            free_rkr_frame(frame);
+            free_con_frame_iterator(it);
+        }
+    }
+}
@@ -22,7 +22,7 @@ fn test_cuh2_parsing() {
 
         // Check the first atom
         let first_atom = &frame.atom_data[0];
-        assert_eq!(&*first_atom.symbol, "Cu");
+        assert_eq!(frame.symbol_of(first_atom), "Cu");
         assert_eq!(first_atom.x, 0.639_4);
         assert_eq!(first_atom.y, 0.904_5);
         assert_eq!(first_atom.z, -0.00009999999999977);
@@ -31,7 +31,7 @@ fn test_cuh2_parsing() {
 
         // Check the last atom
         let last_atom = &frame.atom_data.last().unwrap();
-        assert_eq!(&*last_atom.symbol, "H");
+        assert_eq!(frame.symbol_of(last_atom), "H");
         assert_eq!(last_atom.x, 7.942_1);
         assert_eq!(last_atom.y, 9.947);
         assert_eq!(last_atom.z, 4.757_600_000_000_001);
@@ -64,7 +64,7 @@ fn test_multi_parsing() {
     assert_eq!(first_frame.atom_data.len(), 4);
 
     let first_atom = &first_frame.atom_data[0];
-    assert_eq!(&*first_atom.symbol, "Cu");
+    assert_eq!(first_frame.symbol_of(first_atom), "Cu");
     assert_eq!(first_atom.x, 0.6394);
     assert_eq!(first_atom.y, 0.9045);
     assert_eq!(first_atom.z, 6.9753);
@@ -72,7 +72,7 @@ fn test_multi_parsing() {
     assert_eq!(first_atom.atom_id, 0);
 
     let last_atom = &first_frame.atom_data.last().unwrap();
-    assert_eq!(&*last_atom.symbol, "H");
+    assert_eq!(first_frame.symbol_of(last_atom), "H");
     assert_eq!(last_atom.x, 7.9421);
     assert_eq!(last_atom.y, 9.947);
     assert_eq!(last_atom.z, 11.733);
@@ -86,7 +86,7 @@ fn test_multi_parsing() {
     assert_eq!(second_frame.atom_data.len(), 4);
 
     let second_atom = &second_frame.atom_data[1];
-    assert_eq!(&*second_atom.symbol, "Cu");
+    assert_eq!(second_frame.symbol_of(second_atom), "Cu");
     assert_eq!(second_atom.x, 3.1969);
     assert_eq!(second_atom.y, 0.9045);
     assert_eq!(second_atom.z, 6.9752);
@@ -118,7 +118,7 @@ fn test_iterator_forward() {
         .expect("Parsing second frame should succeed");
     assert_eq!(second_frame.atom_data.len(), 4);
     let second_atom = &second_frame.atom_data[1];
-    assert_eq!(&*second_atom.symbol, "Cu");
+    assert_eq!(second_frame.symbol_of(second_atom), "Cu");
     assert_eq!(second_atom.x, 3.1969);
     assert_eq!(second_atom.y, 0.9045);
     assert_eq!(second_atom.z, 6.9752);
@@ -160,7 +160,7 @@ fn test_read_first_frame() {
     let frame = iterators::read_first_frame(&path).expect("read_first_frame should succeed");
     assert_eq!(frame.header.natm_types, 2);
     assert_eq!(frame.atom_data.len(), 218);
-    assert_eq!(&*frame.atom_data[0].symbol, "Cu");
+    assert_eq!(frame.symbol(0), "Cu");
     assert_eq!(frame.atom_data[0].atom_id, 0);
 }
 
@@ -170,7 +170,7 @@ fn test_read_first_frame_multi() {
     let path = test_case!("tiny_multi_cuh2.con");
     let frame = iterators::read_first_frame(&path).expect("read_first_frame should succeed");
     assert_eq!(frame.atom_data.len(), 4);
-    assert_eq!(&*frame.atom_data[0].symbol, "Cu");
+    assert_eq!(frame.symbol(0), "Cu");
     assert_eq!(frame.atom_data[0].x, 0.6394);
 }
 
@@ -197,3 +197,33 @@ fn test_count_frames_single() {
     let path = test_case!("tiny_cuh2.con");
     assert_eq!(iterators::count_frames(&path).expect("count"), 1);
 }
+
+#[test]
+fn test_symbols_are_interned_per_frame() {
+    let fdat = fs::read_to_string(test_case!("cuh2.con")).expect("Can't find test.");
+    let frame = ConFrameIterator::new(&fdat).next().unwrap().unwrap();
+    assert_eq!(frame.symbol_table, ["Cu", "H"]);
+    let (cu, h) = frame.atom_data.split_at(216);
+    assert!(cu.iter().all(|a| a.symbol == cu[0].symbol));
+    assert!(h.iter().all(|a| a.symbol == h[0].symbol));
+    assert_ne!(cu[0].symbol, h[0].symbol);
+    assert_eq!(frame.symbol(0), "Cu");
+    assert_eq!(frame.symbol(216), "H");
+
+    let mut builder = readcon_core::types::ConFrameBuilder::new([10.0; 3], [90.0; 3]);
+    for (i, sym) in ["Cu", "H", "Cu"].into_iter().enumerate() {
+        builder.add_atom(sym, i as f64, 0.0, 0.0, [false; 3], i as u64, 1.0);
+    }
+    let mut built = builder.build();
+    assert_eq!(built.symbol_table.len(), 2);
+    assert_eq!(built.atom_data[0].symbol, built.atom_data[1].symbol);
+
+    // Equality compares symbols, not ids: renaming into a fresh entry and
+    // back leaves an unused entry but an equal frame.
+    let before = built.clone();
+    built.set_symbol(2, "Ag").unwrap();
+    assert_ne!(built, before);
+    built.set_symbol(2, "H").unwrap();
+    assert_eq!(built.symbol_table, ["Cu", "H", "Ag"]);
+    assert_eq!(built, before);
+}
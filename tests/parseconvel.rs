@@ -21,7 +21,7 @@ fn test_convel_single_frame() {
 
     // Check coordinate data is still correct
     let first_atom = &frame.atom_data[0];
-    assert_eq!(&*first_atom.symbol, "Cu");
+    assert_eq!(frame.symbol_of(first_atom), "Cu");
     assert!((first_atom.x - 0.6394).abs() < 1e-4);
     assert!(first_atom.is_fixed());
 
@@ -29,7 +29,7 @@ fn test_convel_single_frame() {
     assert_eq!(first_atom.velocity, Some([0.001234, 0.002345, -0.003456]));
 
     let last_atom = &frame.atom_data[3];
-    assert_eq!(&*last_atom.symbol, "H");
+    assert_eq!(frame.symbol_of(last_atom), "H");
     assert_eq!(last_atom.velocity, Some([0.045678, -0.056789, -0.06789]));
     assert!(!last_atom.is_fixed());
 }
@@ -107,3 +107,90 @@ fn test_convel_forward_skip() {
     // No more frames
     assert!(parser.next().is_none());
 }
+
+#[test]
+fn test_empty_comment_line_after_legacy_frame_is_not_a_velocity_block() {
+    let single = fs::read_to_string(test_case!("tiny_cuh2.con")).expect("Can't find test file.");
+    let (_, rest) = single.split_once('\n').unwrap();
+    let fdat = format!("{single}\n{rest}");
+
+    let frames: Vec<_> = ConFrameIterator::new(&fdat)
+        .collect::<Result<_, _>>()
+        .expect("both frames should parse");
+    assert_eq!(frames.len(), 2);
+    assert!(!frames[0].has_velocities());
+    assert_eq!(frames[1].header.prebox_header.user, "");
+    assert_eq!(frames[1].atom_data, frames[0].atom_data);
+
+    let mut parser = ConFrameIterator::new(&fdat);
+    assert!(parser.forward().unwrap().is_ok());
+    assert!(parser.next().unwrap().is_ok());
+}
+
+#[test]
+fn test_dynamics_layout_interleaves_velocities() {
+    let fdat = fs::read_to_string(test_case!("tiny_cuh2_dynamics.con"))
+        .expect("Can't find dynamics test file.");
+    let convel =
+        fs::read_to_string(test_case!("tiny_cuh2.convel")).expect("Can't find convel test file.");
+    let frames: Vec<_> = ConFrameIterator::new(&fdat)
+        .collect::<Result<_, _>>()
+        .expect("dynamics frames should parse");
+    assert_eq!(frames.len(), 2);
+    assert!(frames.iter().all(|f| f.has_velocities()));
+    assert_eq!(frames[0].atom_data[0].velocity, Some([0.0; 3]));
+    assert_eq!(
+        frames[1].atom_data[3].velocity,
+        Some([0.045444, -0.056555, -0.067666])
+    );
+    let reference = ConFrameIterator::new(&convel).next().unwrap().unwrap();
+    assert_eq!(
+        frames[0].atom_data[2..],
+        reference.atom_data[2..],
+        "component 2 matches the blank-separated layout"
+    );
+
+    let mut parser = ConFrameIterator::new(&fdat);
+    assert!(parser.forward().unwrap().is_ok());
+    assert_eq!(parser.next().unwrap().unwrap(), frames[1]);
+    assert!(parser.next().is_none());
+
+    let mut parser = ConFrameIterator::new(&fdat);
+    let outline = parser.next_outline().unwrap().unwrap();
+    assert_eq!((outline.natoms(), outline.nfixed()), (4, 2));
+    assert!(parser.next_outline().unwrap().is_ok());
+    assert!(parser.next_outline().is_none());
+}
+
+#[test]
+fn test_dynamics_layout_single_component() {
+    let frame = "Random Number Seed\n{\"con_spec_version\":2}\n10 10 10\n90 90 90\n0 0\n0 0\n\
+                 1\n2\n63.546\nCu\nCoordinates of Component 1\n\
+                 0.0 0.0 0.0 0 7\n1.0 1.0 1.0 0 8\n\
+                 Cu\nVelocities of Component 1\n\
+                 0.1 0.2 0.3 0 7\n0.4 0.5 0.6 0 8\n";
+    let fdat = format!("{frame}{frame}");
+    let frames: Vec<_> = ConFrameIterator::new(&fdat)
+        .collect::<Result<_, _>>()
+        .expect("single-component dynamics frames should parse");
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1].atom_data[1].velocity, Some([0.4, 0.5, 0.6]));
+    assert_eq!(frames[1].velocities.nrows(), 2);
+
+    let mut parser = ConFrameIterator::new(&fdat);
+    assert!(parser.forward().unwrap().is_ok());
+    assert!(parser.forward().unwrap().is_ok());
+    assert!(parser.forward().is_none());
+}
+
+#[test]
+fn test_dynamics_layout_checks_atom_ids() {
+    let fdat = fs::read_to_string(test_case!("tiny_cuh2_dynamics.con"))
+        .expect("Can't find dynamics test file.")
+        .replacen("0.034567 0  2", "0.034567 0  9", 1);
+    let first = ConFrameIterator::new(&fdat).next().unwrap();
+    assert!(matches!(
+        first,
+        Err(readcon_core::error::ParseError::ValidationError(_))
+    ));
+}
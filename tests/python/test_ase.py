@@ -179,7 +179,7 @@ class TestAseMasses:
 class TestAseConstraints:
     """Fixed-coordinate masks roundtrip through ASE constraints."""
 
-    def test_to_ase_uses_fixcartesian_for_partial_masks(self):
+    def test_to_ase_uses_fixedline_for_partial_masks(self):
         frame = readcon.ConFrame(
             cell=[10.0, 10.0, 10.0],
             angles=[90.0, 90.0, 90.0],
@@ -192,18 +192,18 @@ class TestAseConstraints:
         )
 
         ase_atoms = frame.to_ase()
-        fix_cartesian = [
+        fixed_line = [
             constraint for constraint in ase_atoms.constraints
-            if constraint.__class__.__name__ == "FixCartesian"
+            if constraint.__class__.__name__ == "FixedLine"
         ]
         fix_atoms = [
             constraint for constraint in ase_atoms.constraints
             if constraint.__class__.__name__ == "FixAtoms"
         ]
 
-        assert len(fix_cartesian) == 1
-        assert fix_cartesian[0].index.tolist() == [0]
-        assert fix_cartesian[0].mask.tolist() == [True, False, True]
+        assert len(fixed_line) == 1
+        assert fixed_line[0].index.tolist() == [0]
+        assert fixed_line[0].dir.tolist() == [0.0, 1.0, 0.0]
         assert len(fix_atoms) == 1
         assert fix_atoms[0].index.tolist() == [1]
 
@@ -228,3 +228,20 @@ class TestAseConstraints:
             [False, True, False],
             [True, True, True],
         ]
+
+
+class TestAseDict:
+    """to_ase_dict() rows rebuild the same Atoms as to_ase()."""
+
+    def test_row_matches_to_ase(self):
+        from ase.db.row import AtomsRow
+
+        frame = readcon.read_con(_resource("tiny_cuh2.con"))[0]
+        row = frame.to_ase_dict()
+        atoms = AtomsRow(row).toatoms()
+        direct = frame.to_ase()
+        assert atoms.get_chemical_symbols() == direct.get_chemical_symbols()
+        np.testing.assert_allclose(atoms.positions, direct.positions)
+        np.testing.assert_allclose(atoms.cell[:], direct.cell[:], atol=1e-10)
+        fixed = [i for i, a in enumerate(frame.atoms) if all(a.fixed)]
+        assert row["constraints"][0]["kwargs"]["indices"] == fixed
@@ -105,6 +105,40 @@ class TestWriteCon:
         assert len(frames2[0]) == len(frames[0])
 
 
+class TestConWriter:
+    def test_context_manager_append_gzip(self, tmp_path):
+        frames = readcon.read_con(_resource("tiny_multi_cuh2.con"))
+        path = str(tmp_path / "out.con.gz")
+        with readcon.ConWriter(path, precision=9) as w:
+            w.write(frames[0])
+        assert w.closed
+        with readcon.ConWriter(path, append=True, compression="gz") as w:
+            w.extend(frames[1:])
+            assert w.frames_written == len(frames) - 1
+        frames2 = readcon.read_con(path)
+        assert len(frames2) == len(frames)
+
+    def test_truncates_without_append(self, tmp_path):
+        frames = readcon.read_con(_resource("tiny_multi_cuh2.con"))
+        path = str(tmp_path / "out.con")
+        for _ in range(2):
+            with readcon.ConWriter(path) as w:
+                w.extend(frames)
+        assert len(readcon.read_con(path)) == len(frames)
+
+    def test_write_after_close_raises(self, tmp_path):
+        frame = readcon.read_con(_resource("tiny_cuh2.con"))[0]
+        w = readcon.ConWriter(str(tmp_path / "out.con"))
+        w.close()
+        w.close()
+        with pytest.raises(ValueError):
+            w.write(frame)
+
+    def test_unknown_compression(self, tmp_path):
+        with pytest.raises(ValueError):
+            readcon.ConWriter(str(tmp_path / "out.con"), compression="bz2")
+
+
 class TestConvelWriteRoundtrip:
     def test_convel_roundtrip(self):
         frames = readcon.read_con(_resource("tiny_cuh2.convel"))
@@ -452,6 +486,102 @@ class TestNumpyArrays:
             assert frame.atoms[position].atom_id == atom_id
 
 
+class TestFromArrays:
+    def test_from_arrays_round_trip(self):
+        np = pytest.importorskip("numpy")
+        positions = np.array([[0.0, 0.0, 0.0], [1.0, 2.0, 3.0]])
+        frame = readcon.ConFrame.from_arrays(
+            ["Cu", "H"],
+            positions,
+            [10.0, 10.0, 10.0],
+            masses=np.array([63.546, 1.008]),
+            fixed=np.array([[True, True, True], [False, False, False]]),
+        )
+        assert len(frame) == 2
+        np.testing.assert_allclose(frame.coords_array(), positions)
+        reread = readcon.read_con_string(readcon.write_con_string([frame]))[0]
+        assert [a.symbol for a in reread.atoms] == ["Cu", "H"]
+        assert reread.atoms[0].fixed == [True, True, True]
+        assert reread.atoms[1].atom_id == 1
+
+    def test_from_arrays_keeps_uint64_ids_above_int64_max(self):
+        np = pytest.importorskip("numpy")
+        ids = np.array([2**63 + 5, 2**64 - 2], dtype=np.uint64)
+        frame = readcon.ConFrame.from_arrays(
+            ["Cu", "H"], np.zeros((2, 3)), [10.0] * 3, atom_ids=ids
+        )
+        assert [a.atom_id for a in frame.atoms] == [2**63 + 5, 2**64 - 2]
+        reread = readcon.read_con_string(readcon.write_con_string([frame]))[0]
+        assert [a.atom_id for a in reread.atoms] == [2**63 + 5, 2**64 - 2]
+        with pytest.raises(ValueError, match="non-negative"):
+            readcon.ConFrame.from_arrays(
+                ["H"], np.zeros((1, 3)), [10.0] * 3, atom_ids=np.array([-1])
+            )
+
+    def test_from_arrays_rejects_transposed_positions(self):
+        np = pytest.importorskip("numpy")
+        positions = np.zeros((3, 4))
+        with pytest.raises(ValueError, match="transposed"):
+            readcon.ConFrame.from_arrays(["H"] * 4, positions, [10.0] * 3)
+
+    def test_from_arrays_rejects_length_mismatch(self):
+        np = pytest.importorskip("numpy")
+        with pytest.raises(ValueError, match=r"shape \(3, 3\)"):
+            readcon.ConFrame.from_arrays(["H"] * 3, np.zeros((2, 3)), [10.0] * 3)
+        with pytest.raises(ValueError, match="masses"):
+            readcon.ConFrame.from_arrays(
+                ["H"] * 2, np.zeros((2, 3)), [10.0] * 3, masses=np.ones(3)
+            )
+
+    def test_from_arrays_rejects_bad_dtypes(self):
+        np = pytest.importorskip("numpy")
+        with pytest.raises(TypeError, match="positions"):
+            readcon.ConFrame.from_arrays(
+                ["H"], np.array([["a", "b", "c"]]), [10.0] * 3
+            )
+        with pytest.raises(TypeError, match="symbols"):
+            readcon.ConFrame.from_arrays(np.array([1]), np.zeros((1, 3)), [10.0] * 3)
+        with pytest.raises(TypeError, match="symbols"):
+            readcon.ConFrame.from_arrays("H", np.zeros((1, 3)), [10.0] * 3)
+
+
+class TestNotebookDisplay:
+    def test_repr_html(self):
+        frame = readcon.read_con(_resource("tiny_cuh2.con"))[0]
+        html = frame._repr_html_()
+        assert html.startswith("<div")
+        assert "formula" in html
+        assert "Cu" in html
+        assert html.count("<tr>") >= len(frame)
+
+    def test_to_nglview_requires_nglview(self):
+        pytest.importorskip("ase")
+        nglview = pytest.importorskip("nglview")
+        frame = readcon.read_con(_resource("tiny_cuh2.con"))[0]
+        assert isinstance(frame.to_nglview(), nglview.NGLWidget)
+
+
+class TestFrameDiff:
+    def test_self_diff_identical(self):
+        frame = readcon.read_con(_resource("tiny_cuh2.con"))[0]
+        d = frame.diff(frame)
+        assert d.is_identical()
+        assert not d
+        assert d.max_displacement == 0.0
+        assert "identical" in d._repr_html_()
+
+    def test_moved_atom(self):
+        a = readcon.read_con(_resource("tiny_cuh2.con"))[0]
+        b = readcon.read_con(_resource("tiny_cuh2.con"))[0]
+        b.atoms[1].x += 0.25
+        d = a.diff(b)
+        assert not d.is_identical()
+        assert [m[0] for m in d.moved] == [1]
+        assert d.max_displacement == pytest.approx(0.25)
+        assert d.to_dict()["identical"] is False
+        assert "<table>" in d._repr_html_()
+
+
 class TestErrorHandling:
     def test_bad_file_path(self):
         with pytest.raises(OSError):
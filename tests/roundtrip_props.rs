@@ -0,0 +1,121 @@
+//! Property-based round trips over `any::<ConFrame>()` (see
+//! `src/arbitrary.rs`):
+//!
+//! - write then parse recovers the frame to within the writer's precision;
+//! - parse then write reproduces the writer's own output byte for byte;
+//! - multi-frame trajectories keep frame count and order.
+//!
+//! ```text
+//! cargo test --features proptest --test roundtrip_props
+//! PROPTEST_CASES=5000 cargo test --release --features proptest --test roundtrip_props
+//! ```
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use readcon_core::iterators::ConFrameIterator;
+use readcon_core::types::ConFrame;
+use readcon_core::writer::ConFrameWriter;
+
+fn write(frames: &[ConFrame], precision: usize) -> String {
+    let mut buffer = Vec::new();
+    ConFrameWriter::with_precision(&mut buffer, precision)
+        .extend(frames.iter())
+        .unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+fn parse(text: &str) -> Vec<ConFrame> {
+    ConFrameIterator::new(text)
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|e| panic!("writer output did not parse: {e}\n{text}"))
+}
+
+/// Largest error a fixed `{:.precision$}` rendering can introduce into `v`.
+fn tolerance(v: f64, precision: usize) -> f64 {
+    0.5 * 10f64.powi(-(precision as i32)) + v.abs() * f64::EPSILON * 4.0
+}
+
+fn assert_close(label: &str, a: &[f64], b: &[f64], precision: usize) -> Result<(), TestCaseError> {
+    prop_assert_eq!(a.len(), b.len(), "{} length", label);
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        prop_assert!(
+            (x - y).abs() <= tolerance(*x, precision),
+            "{}[{}]: wrote {} read {} at precision {}",
+            label,
+            i,
+            x,
+            y,
+            precision
+        );
+    }
+    Ok(())
+}
+
+fn check_close(want: &ConFrame, got: &ConFrame, precision: usize) -> Result<(), TestCaseError> {
+    prop_assert_eq!(
+        &want.header.prebox_header.user,
+        &got.header.prebox_header.user
+    );
+    prop_assert_eq!(&want.header.postbox_header, &got.header.postbox_header);
+    prop_assert_eq!(&want.header.natms_per_type, &got.header.natms_per_type);
+    assert_close("boxl", &want.header.boxl, &got.header.boxl, precision)?;
+    assert_close("angles", &want.header.angles, &got.header.angles, precision)?;
+    assert_close(
+        "masses",
+        &want.header.masses_per_type,
+        &got.header.masses_per_type,
+        precision,
+    )?;
+    prop_assert_eq!(want.atom_data.len(), got.atom_data.len());
+    for (a, b) in want.atom_data.iter().zip(&got.atom_data) {
+        prop_assert_eq!(want.symbol_of(a), got.symbol_of(b));
+        prop_assert_eq!(a.fixed, b.fixed);
+        prop_assert_eq!(a.atom_id, b.atom_id);
+        assert_close("position", &[a.x, a.y, a.z], &[b.x, b.y, b.z], precision)?;
+        prop_assert_eq!(a.velocity.is_some(), b.velocity.is_some());
+        if let (Some(v), Some(w)) = (a.velocity, b.velocity) {
+            assert_close("velocity", &v, &w, precision)?;
+        }
+        prop_assert_eq!(a.force.is_some(), b.force.is_some());
+        if let (Some(f), Some(g)) = (a.force, b.force) {
+            assert_close("force", &f, &g, precision)?;
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn write_then_parse_is_identity_within_precision(
+        frame in any::<ConFrame>(),
+        precision in 3usize..=12,
+    ) {
+        let text = write(std::slice::from_ref(&frame), precision);
+        let parsed = parse(&text);
+        prop_assert_eq!(parsed.len(), 1);
+        check_close(&frame, &parsed[0], precision)?;
+    }
+
+    #[test]
+    fn parse_then_write_reproduces_writer_output(
+        frame in any::<ConFrame>(),
+        precision in 3usize..=12,
+    ) {
+        let text = write(std::slice::from_ref(&frame), precision);
+        let again = write(&parse(&text), precision);
+        prop_assert_eq!(text, again);
+    }
+
+    #[test]
+    fn trajectories_keep_frame_order(
+        frames in proptest::collection::vec(any::<ConFrame>(), 1..6),
+    ) {
+        let text = write(&frames, 6);
+        let parsed = parse(&text);
+        prop_assert_eq!(parsed.len(), frames.len());
+        for (want, got) in frames.iter().zip(&parsed) {
+            check_close(want, got, 6)?;
+        }
+        prop_assert_eq!(write(&parsed, 6), text);
+    }
+}